@@ -1,16 +1,16 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use super::discovery::run_ghostty;
-use crate::error::AppError;
+use crate::Error;
 
 /// Load all available actions from `ghostty +list-actions`.
-pub fn load_actions(ghostty_path: &PathBuf) -> Result<Vec<String>, AppError> {
-    let output = run_ghostty(ghostty_path, &["+list-actions"])?;
+pub async fn load_actions(ghostty_path: &Path) -> Result<Vec<String>, Error> {
+    let output = run_ghostty(ghostty_path, &["+list-actions"]).await?;
     Ok(parse_action_list(&output))
 }
 
 /// Parse actions output text into a list of action names.
-pub(crate) fn parse_action_list(output: &str) -> Vec<String> {
+pub fn parse_action_list(output: &str) -> Vec<String> {
     output
         .lines()
         .map(|l| l.trim().to_string())
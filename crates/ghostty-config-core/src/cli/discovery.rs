@@ -0,0 +1,502 @@
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use tokio::process::Command;
+
+use crate::config::os_shortcuts::Platform;
+use crate::{CliError, CliErrorKind, Error};
+
+/// Find the ghostty binary path: the first of [`find_ghostty_installations`].
+pub fn find_ghostty() -> Result<PathBuf, Error> {
+    find_ghostty_installations()
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            CliError::new(
+                CliErrorKind::NotFound,
+                "Could not find ghostty binary. Is Ghostty installed?",
+            )
+            .into()
+        })
+}
+
+/// Every Ghostty binary found in the usual install locations and on the `PATH`,
+/// e.g. a release app next to a nightly build. Links to the same binary are
+/// listed once.
+pub fn find_ghostty_installations() -> Vec<PathBuf> {
+    // Try common locations
+    #[cfg(windows)]
+    let candidates = windows_candidates(
+        std::env::var_os("ProgramFiles").map(PathBuf::from),
+        std::env::var_os("LOCALAPPDATA").map(PathBuf::from),
+    );
+    #[cfg(not(windows))]
+    let mut candidates: Vec<PathBuf> = [
+        "/Applications/Ghostty.app/Contents/MacOS/ghostty",
+        "/usr/local/bin/ghostty",
+        "/usr/bin/ghostty",
+        "/opt/homebrew/bin/ghostty",
+        "/snap/bin/ghostty",
+    ]
+    .into_iter()
+    .map(PathBuf::from)
+    .collect();
+    #[cfg(not(windows))]
+    if let Some(home) = std::env::var_os("HOME").map(PathBuf::from) {
+        candidates.push(home.join("Applications/Ghostty.app/Contents/MacOS/ghostty"));
+        candidates.push(home.join(".local/bin/ghostty"));
+    }
+
+    // Then everything on the `PATH`: `which -a ghostty` (`where` on Windows,
+    // which lists every match already)
+    #[cfg(windows)]
+    let which = std::process::Command::new("where").arg("ghostty").output();
+    #[cfg(not(windows))]
+    let which = std::process::Command::new("which")
+        .args(["-a", "ghostty"])
+        .output();
+    let on_path = match which {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    distinct_binaries(candidates.into_iter().chain(on_path))
+}
+
+/// The paths that exist, without the ones resolving to a binary already listed.
+fn distinct_binaries(paths: impl IntoIterator<Item = PathBuf>) -> Vec<PathBuf> {
+    let mut seen = Vec::new();
+    let mut found = Vec::new();
+    for path in paths {
+        let Ok(resolved) = path.canonicalize() else {
+            continue;
+        };
+        if !resolved.is_file() || seen.contains(&resolved) {
+            continue;
+        }
+        seen.push(resolved);
+        found.push(path);
+    }
+    found
+}
+
+/// Where Windows installs put `ghostty.exe`: the installer's Program Files
+/// folder, a per-user install, and the links winget adds to the `PATH`.
+#[cfg(any(windows, test))]
+fn windows_candidates(
+    program_files: Option<PathBuf>,
+    local_app_data: Option<PathBuf>,
+) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(dir) = program_files {
+        candidates.push(dir.join("Ghostty").join("ghostty.exe"));
+    }
+    if let Some(dir) = local_app_data {
+        candidates.push(dir.join("Programs").join("Ghostty").join("ghostty.exe"));
+        candidates.push(
+            dir.join("Microsoft")
+                .join("WinGet")
+                .join("Links")
+                .join("ghostty.exe"),
+        );
+    }
+    candidates
+}
+
+/// How long a ghostty command may run unless [`set_command_timeout`] says otherwise.
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+static COMMAND_TIMEOUT_MS: AtomicU64 = AtomicU64::new(DEFAULT_COMMAND_TIMEOUT.as_millis() as u64);
+
+/// Change how long [`run_ghostty`] waits before killing a command, for the whole process.
+pub fn set_command_timeout(timeout: Duration) {
+    COMMAND_TIMEOUT_MS.store(timeout.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// How long [`run_ghostty`] waits before killing a command.
+pub fn command_timeout() -> Duration {
+    Duration::from_millis(COMMAND_TIMEOUT_MS.load(Ordering::Relaxed))
+}
+
+/// Run a ghostty CLI command and return stdout, killing it after [`command_timeout`].
+pub async fn run_ghostty(ghostty_path: &Path, args: &[&str]) -> Result<String, Error> {
+    run_ghostty_with_timeout(ghostty_path, args, command_timeout()).await
+}
+
+/// Run a ghostty CLI command and return stdout, killing it after `timeout`.
+pub async fn run_ghostty_with_timeout(
+    ghostty_path: &Path,
+    args: &[&str],
+    timeout: Duration,
+) -> Result<String, Error> {
+    let command_line = std::iter::once(
+        ghostty_path
+            .file_stem()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_else(|| ghostty_path.to_string_lossy()),
+    )
+    .chain(args.iter().map(|&arg| arg.into()))
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    let mut command = Command::new(ghostty_path);
+    command.args(args).kill_on_drop(true);
+    let output = match tokio::time::timeout(timeout, command.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => {
+            let kind = if e.kind() == std::io::ErrorKind::NotFound {
+                CliErrorKind::NotFound
+            } else {
+                CliErrorKind::Spawn
+            };
+            return Err(CliError {
+                command: Some(command_line),
+                ..CliError::new(kind, format!("Failed to run ghostty: {}", e))
+            }
+            .into());
+        }
+        // Dropping the output future kills the command.
+        Err(_) => return Err(CliError::timeout(command_line, timeout).into()),
+    };
+
+    // Ghostty may output to stderr for some commands
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    if !output.status.success() && stdout.is_empty() {
+        // Some ghostty commands write to stderr even on success
+        if !stderr.is_empty() {
+            return Ok(stderr);
+        }
+        return Err(CliError {
+            command: Some(command_line.clone()),
+            exit_code: output.status.code(),
+            ..CliError::new(
+                CliErrorKind::Failed,
+                format!("`{}` failed: {}", command_line, output.status),
+            )
+        }
+        .into());
+    }
+
+    if stdout.is_empty() && !stderr.is_empty() {
+        return Ok(stderr);
+    }
+
+    Ok(stdout)
+}
+
+/// The installed Ghostty version, e.g. `1.1.3`, from `ghostty +version`.
+pub async fn ghostty_version(ghostty_path: &Path) -> Result<String, Error> {
+    let output = run_ghostty(ghostty_path, &["+version"]).await?;
+    parse_version_output(&output).ok_or_else(|| {
+        CliError::new(CliErrorKind::Output, "Could not read the Ghostty version").into()
+    })
+}
+
+/// What a new Ghostty window opened by [`launch_ghostty`] loads and runs. Anything
+/// left `None` comes from the config, as in any other window.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchOptions {
+    /// Load only this file, instead of the default config files.
+    pub config_file: Option<PathBuf>,
+    /// Start in this directory, instead of the config's `working-directory`.
+    pub working_directory: Option<PathBuf>,
+    /// Run this, instead of the config's `command`.
+    pub command: Option<String>,
+}
+
+/// How long [`launch_ghostty`] watches a new window's process for exiting with an
+/// error, e.g. when there is no display to open a window on.
+const LAUNCH_GRACE: Duration = Duration::from_millis(500);
+
+/// Open a new Ghostty window, separate from any Ghostty already running. Returns
+/// once the window is starting up; it stays open after this returns.
+pub async fn launch_ghostty(
+    ghostty_path: &Path,
+    options: &LaunchOptions,
+    platform: Platform,
+) -> Result<(), Error> {
+    let (program, args) = launch_command(ghostty_path, options, platform);
+    let command_line = std::iter::once(program.display().to_string())
+        .chain(args.iter().cloned())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut child = Command::new(&program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| {
+            let kind = if e.kind() == std::io::ErrorKind::NotFound {
+                CliErrorKind::NotFound
+            } else {
+                CliErrorKind::Spawn
+            };
+            CliError {
+                command: Some(command_line.clone()),
+                ..CliError::new(kind, format!("Couldn't start {}: {}", program.display(), e))
+            }
+        })?;
+    match tokio::time::timeout(LAUNCH_GRACE, child.wait()).await {
+        Ok(Ok(status)) if !status.success() => {
+            return Err(CliError {
+                command: Some(command_line.clone()),
+                exit_code: status.code(),
+                ..CliError::new(
+                    CliErrorKind::Failed,
+                    format!(
+                        "Ghostty exited right away ({}); try `{}` in a terminal to see why",
+                        status, command_line
+                    ),
+                )
+            }
+            .into());
+        }
+        Ok(_) => {}
+        // Still running: reap it whenever the window is closed.
+        Err(_) => {
+            tokio::spawn(async move {
+                let _ = child.wait().await;
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The command [`launch_ghostty`] runs, as `(program, args)`.
+///
+/// On macOS a Ghostty inside an app bundle is started through `open -n`, since
+/// the binary can't open windows by itself. On Linux, single-instance mode is
+/// turned off so the window isn't handed to a Ghostty that's already running,
+/// with its own config.
+pub fn launch_command(
+    ghostty_path: &Path,
+    options: &LaunchOptions,
+    platform: Platform,
+) -> (PathBuf, Vec<String>) {
+    let mut args = Vec::new();
+    if let Some(config_file) = &options.config_file {
+        args.push("--config-default-files=false".to_string());
+        args.push(format!("--config-file={}", config_file.display()));
+    }
+    if let Some(dir) = &options.working_directory {
+        args.push(format!("--working-directory={}", dir.display()));
+    }
+    if let Some(command) = &options.command {
+        args.push(format!("--command={}", command));
+    }
+    match platform {
+        Platform::MacOS => {
+            if let Some(bundle) = ghostty_path
+                .ancestors()
+                .find(|p| p.extension().is_some_and(|e| e == "app"))
+            {
+                let mut open_args = vec![
+                    "-na".to_string(),
+                    bundle.display().to_string(),
+                    "--args".to_string(),
+                ];
+                open_args.append(&mut args);
+                return (PathBuf::from("open"), open_args);
+            }
+        }
+        Platform::Linux => args.push("--gtk-single-instance=false".to_string()),
+    }
+    (ghostty_path.to_path_buf(), args)
+}
+
+/// Pull the version out of `+version` output, which reads
+/// `Ghostty 1.1.3` followed by a `- version: 1.1.3` detail line.
+pub fn parse_version_output(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("- version:"))
+        .or_else(|| {
+            output
+                .lines()
+                .next()
+                .and_then(|line| line.trim().strip_prefix("Ghostty "))
+        })
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_candidates() {
+        let candidates = windows_candidates(
+            Some(PathBuf::from("C:/Program Files")),
+            Some(PathBuf::from("C:/Users/me/AppData/Local")),
+        );
+        assert_eq!(
+            candidates,
+            vec![
+                PathBuf::from("C:/Program Files/Ghostty/ghostty.exe"),
+                PathBuf::from("C:/Users/me/AppData/Local/Programs/Ghostty/ghostty.exe"),
+                PathBuf::from("C:/Users/me/AppData/Local/Microsoft/WinGet/Links/ghostty.exe"),
+            ]
+        );
+        assert!(windows_candidates(None, None).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_distinct_binaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let release = dir.path().join("ghostty");
+        let nightly = dir.path().join("ghostty-tip");
+        std::fs::write(&release, "").unwrap();
+        std::fs::write(&nightly, "").unwrap();
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&release, &link).unwrap();
+
+        assert_eq!(
+            distinct_binaries([
+                release.clone(),
+                dir.path().join("missing"),
+                link,
+                nightly.clone(),
+                dir.path().to_path_buf(),
+                release.clone(),
+            ]),
+            vec![release, nightly]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_ghostty_times_out() {
+        let started = std::time::Instant::now();
+        let err = run_ghostty_with_timeout(Path::new("sleep"), &["5"], Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(2));
+        let Error::Cli(e) = err else {
+            panic!("expected a CLI error, got {err:?}");
+        };
+        assert_eq!(e.kind, CliErrorKind::Timeout);
+        assert_eq!(e.command.as_deref(), Some("sleep 5"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_ghostty_reports_failures() {
+        let missing = run_ghostty(Path::new("/nonexistent/ghostty"), &["+version"]).await;
+        assert!(matches!(missing, Err(Error::Cli(e)) if e.kind == CliErrorKind::NotFound));
+
+        let failed = run_ghostty(Path::new("false"), &[]).await;
+        assert!(
+            matches!(failed, Err(Error::Cli(e)) if e.kind == CliErrorKind::Failed && e.exit_code == Some(1))
+        );
+        assert_eq!(
+            run_ghostty(Path::new("echo"), &["hi"]).await.unwrap(),
+            "hi\n"
+        );
+    }
+
+    #[test]
+    fn test_launch_command() {
+        let scratch = LaunchOptions {
+            config_file: Some(PathBuf::from("/tmp/scratch/1/config")),
+            ..LaunchOptions::default()
+        };
+        let (program, args) =
+            launch_command(Path::new("/usr/bin/ghostty"), &scratch, Platform::Linux);
+        assert_eq!(program, Path::new("/usr/bin/ghostty"));
+        assert_eq!(
+            args,
+            [
+                "--config-default-files=false",
+                "--config-file=/tmp/scratch/1/config",
+                "--gtk-single-instance=false"
+            ]
+        );
+
+        let (program, args) = launch_command(
+            Path::new("/Applications/Ghostty.app/Contents/MacOS/ghostty"),
+            &scratch,
+            Platform::MacOS,
+        );
+        assert_eq!(program, Path::new("open"));
+        assert_eq!(
+            args,
+            [
+                "-na",
+                "/Applications/Ghostty.app",
+                "--args",
+                "--config-default-files=false",
+                "--config-file=/tmp/scratch/1/config"
+            ]
+        );
+
+        // A Ghostty built outside a bundle is started directly.
+        let (program, _) = launch_command(
+            Path::new("/opt/ghostty/bin/ghostty"),
+            &scratch,
+            Platform::MacOS,
+        );
+        assert_eq!(program, Path::new("/opt/ghostty/bin/ghostty"));
+
+        // Without a config file, the usual config is loaded.
+        let overrides = LaunchOptions {
+            working_directory: Some(PathBuf::from("/home/me/src")),
+            command: Some("htop -d 5".to_string()),
+            ..LaunchOptions::default()
+        };
+        let (_, args) = launch_command(Path::new("/usr/bin/ghostty"), &overrides, Platform::Linux);
+        assert_eq!(
+            args,
+            [
+                "--working-directory=/home/me/src",
+                "--command=htop -d 5",
+                "--gtk-single-instance=false"
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_launch_ghostty_reports_early_exit() {
+        let err = launch_ghostty(
+            Path::new("false"),
+            &LaunchOptions::default(),
+            Platform::Linux,
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(err, Error::Cli(e) if e.kind == CliErrorKind::Failed && e.exit_code == Some(1))
+        );
+
+        let missing = launch_ghostty(
+            Path::new("/nonexistent/ghostty"),
+            &LaunchOptions::default(),
+            Platform::Linux,
+        )
+        .await;
+        assert!(matches!(missing, Err(Error::Cli(e)) if e.kind == CliErrorKind::NotFound));
+    }
+
+    #[test]
+    fn test_parse_version_output() {
+        let output = "Ghostty 1.1.3\n\nVersion\n  - version: 1.1.3\n  - channel: stable\n";
+        assert_eq!(parse_version_output(output).as_deref(), Some("1.1.3"));
+        assert_eq!(
+            parse_version_output("Ghostty 1.2.0-dev+abc\n").as_deref(),
+            Some("1.2.0-dev+abc")
+        );
+        assert_eq!(parse_version_output("error: unknown command"), None);
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::discovery::run_ghostty;
+use crate::config::file_io::{tokenize_line, unquote_value, Line};
+use crate::config::model::{ConfigSchema, UserConfig};
+use crate::Error;
+
+/// Run `ghostty +show-config` for every option, not just the changed ones.
+///
+/// This is the config Ghostty actually loads: the saved file, its includes, and
+/// whatever Ghostty works out for the platform.
+pub async fn show_effective_config(ghostty_path: &Path) -> Result<String, Error> {
+    run_ghostty(ghostty_path, &["+show-config", "--changes-only=false"]).await
+}
+
+/// Where an effective value comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ValueSource {
+    /// The user's config file.
+    File,
+    /// A file pulled in with `config-file`.
+    Include,
+    /// Ghostty's documented default.
+    Default,
+    /// Neither the files nor the documented default, e.g. a platform default.
+    Other,
+}
+
+/// One option as Ghostty loads it, next to its default and the file's value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EffectiveValue {
+    pub key: String,
+    /// Every value Ghostty reports; several for repeatable keys.
+    pub values: Vec<String>,
+    /// The documented default, empty when the schema doesn't know the key.
+    pub default: String,
+    /// The values the user's file sets.
+    pub file: Vec<String>,
+    pub source: ValueSource,
+}
+
+impl EffectiveValue {
+    /// Whether the value differs from Ghostty's default, wherever it was set.
+    pub fn changed(&self) -> bool {
+        self.source != ValueSource::Default
+    }
+}
+
+/// Group `+show-config` output by key, keeping the order of repeated values.
+pub fn parse_effective(output: &str) -> BTreeMap<String, Vec<String>> {
+    let mut values: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for line in output.lines() {
+        if let Line::KeyValue { key, value, .. } = tokenize_line(line) {
+            values
+                .entry(key.to_string())
+                .or_default()
+                .push(value.to_string());
+        }
+    }
+    values
+}
+
+/// Work out where each effective value comes from.
+///
+/// A value is credited to the last included file that sets the key, then to the
+/// user's file, when it matches what that file says. Repeatable keys no file
+/// sets count as default, since Ghostty reports the built-in entries too
+/// (every default keybind, for one).
+pub fn compare_effective(
+    output: &str,
+    schema: &ConfigSchema,
+    user_config: &UserConfig,
+    included: &[UserConfig],
+) -> Vec<EffectiveValue> {
+    parse_effective(output)
+        .into_iter()
+        .map(|(key, values)| {
+            let option = schema.find_option(&key);
+            let repeatable = option.is_some_and(|o| o.is_repeatable);
+            let default = option.map(|o| o.default_value.clone()).unwrap_or_default();
+            let file: Vec<String> = user_config
+                .get_all(&key)
+                .into_iter()
+                .map(String::from)
+                .collect();
+            let include = included
+                .iter()
+                .rev()
+                .map(|config| config.get_all(&key))
+                .find(|values| !values.is_empty());
+
+            let source = if include.is_some_and(|set| matches(&set, &values, repeatable)) {
+                ValueSource::Include
+            } else if !file.is_empty() && matches(&file, &values, repeatable) {
+                ValueSource::File
+            } else if repeatable || matches(&[default.as_str()], &values, false) {
+                ValueSource::Default
+            } else {
+                ValueSource::Other
+            };
+
+            EffectiveValue {
+                key,
+                values,
+                default,
+                file,
+                source,
+            }
+        })
+        .collect()
+}
+
+/// Whether a file's values show up in the effective ones: every value for
+/// repeatable keys, the one that wins for the rest.
+fn matches<S: AsRef<str>>(set: &[S], effective: &[String], repeatable: bool) -> bool {
+    if repeatable {
+        return set
+            .iter()
+            .all(|value| effective.iter().any(|e| same_value(value.as_ref(), e)));
+    }
+    match (set.last(), effective.last()) {
+        (Some(value), Some(e)) => same_value(value.as_ref(), e),
+        _ => false,
+    }
+}
+
+/// Ghostty prints values without quotes and in its own letter case.
+fn same_value(a: &str, b: &str) -> bool {
+    unquote_value(a).eq_ignore_ascii_case(&unquote_value(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file_io::parse_config;
+    use crate::config::parser::parse_show_config;
+
+    fn schema() -> ConfigSchema {
+        parse_show_config(
+            "font-size = 13\n\ntheme = \n\nwindow-decoration = auto\n\nkeybind = ctrl+c=copy\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_effective_groups_repeated_keys() {
+        let values = parse_effective("keybind = a=b\nfont-size = 14\nkeybind = c=d\n");
+        assert_eq!(values["keybind"], vec!["a=b", "c=d"]);
+        assert_eq!(values["font-size"], vec!["14"]);
+    }
+
+    #[test]
+    fn test_compare_effective_attributes_sources() {
+        let output = "font-size = 14\ntheme = Nord\nwindow-decoration = none\n\
+                      keybind = ctrl+c=copy\nkeybind = ctrl+k=clear_screen\n";
+        let user = parse_config(
+            "font-size = 14\nkeybind = ctrl+k=clear_screen\n",
+            Path::new("/home/me/.config/ghostty/config"),
+        );
+        let include = parse_config("theme = \"nord\"\n", Path::new("/home/me/themes.conf"));
+
+        let values = compare_effective(output, &schema(), &user, &[include]);
+        let source = |key: &str| values.iter().find(|v| v.key == key).unwrap().source;
+        assert_eq!(source("font-size"), ValueSource::File);
+        assert_eq!(source("theme"), ValueSource::Include);
+        assert_eq!(source("window-decoration"), ValueSource::Other);
+        assert_eq!(source("keybind"), ValueSource::File);
+    }
+
+    #[test]
+    fn test_compare_effective_defaults_are_unchanged() {
+        let user = parse_config("", Path::new("/tmp/config"));
+        let values = compare_effective("font-size = 13\n", &schema(), &user, &[]);
+        assert_eq!(values[0].source, ValueSource::Default);
+        assert!(!values[0].changed());
+        assert_eq!(values[0].default, "13");
+    }
+}
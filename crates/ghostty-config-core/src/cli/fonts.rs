@@ -1,8 +1,10 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use super::discovery::run_ghostty;
+use crate::config::font_variation::{parse_fvar, FontAxis};
 use crate::config::model::FontFamily;
-use crate::error::AppError;
+use crate::Error;
 
 /// Parse the output of `ghostty +list-fonts`.
 ///
@@ -15,12 +17,13 @@ use crate::error::AppError;
 /// FamilyName2
 ///   ...
 /// ```
-pub fn load_fonts(ghostty_path: &PathBuf) -> Result<Vec<FontFamily>, AppError> {
-    let output = run_ghostty(ghostty_path, &["+list-fonts"])?;
+pub async fn load_fonts(ghostty_path: &Path) -> Result<Vec<FontFamily>, Error> {
+    let output = run_ghostty(ghostty_path, &["+list-fonts"]).await?;
     Ok(parse_font_list(&output))
 }
 
-fn parse_font_list(output: &str) -> Vec<FontFamily> {
+/// Parse `+list-fonts` output into font families.
+pub fn parse_font_list(output: &str) -> Vec<FontFamily> {
     let mut fonts = Vec::new();
     let mut current_family: Option<String> = None;
     let mut current_styles: Vec<String> = Vec::new();
@@ -60,10 +63,53 @@ fn parse_font_list(output: &str) -> Vec<FontFamily> {
         });
     }
 
-    fonts.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    fonts.sort_by_key(|f| f.name.to_lowercase());
     fonts
 }
 
+/// The variable-font axes of `family`, read from its font files.
+///
+/// Ghostty doesn't report where fonts live, so the files are looked up with
+/// fontconfig's `fc-list`. Returns `None` when no files were found (no fontconfig,
+/// or a font built into Ghostty); an empty list for a family without axes.
+pub fn font_axes(family: &str) -> Option<Vec<FontAxis>> {
+    let output = Command::new("fc-list")
+        .args([family, "--format", "%{file}\n"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let files: Vec<PathBuf> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(PathBuf::from)
+        .collect();
+    if files.is_empty() {
+        return None;
+    }
+    let per_file: Vec<Vec<FontAxis>> = files
+        .iter()
+        .filter_map(|file| std::fs::read(file).ok())
+        .filter_map(|data| parse_fvar(&data))
+        .collect();
+    Some(merge_axes(per_file))
+}
+
+/// Combine the axes of a family's files (e.g. separate roman and italic variable
+/// fonts), widening ranges where the same axis appears more than once.
+fn merge_axes(per_file: Vec<Vec<FontAxis>>) -> Vec<FontAxis> {
+    let mut merged: Vec<FontAxis> = Vec::new();
+    for axis in per_file.into_iter().flatten() {
+        match merged.iter_mut().find(|a| a.tag == axis.tag) {
+            Some(existing) => {
+                existing.min = existing.min.min(axis.min);
+                existing.max = existing.max.max(axis.max);
+            }
+            None => merged.push(axis),
+        }
+    }
+    merged
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,4 +169,22 @@ mod tests {
         assert_eq!(fonts[1].name, "SomeFont");
         assert!(fonts[1].styles.is_empty());
     }
+
+    #[test]
+    fn test_merge_axes() {
+        let axis = |tag: &str, min: f32, max: f32| FontAxis {
+            tag: tag.to_string(),
+            min,
+            default: 400.0,
+            max,
+        };
+        let merged = merge_axes(vec![
+            vec![axis("wght", 200.0, 700.0)],
+            vec![axis("wght", 100.0, 600.0), axis("slnt", -10.0, 0.0)],
+        ]);
+        assert_eq!(
+            merged,
+            vec![axis("wght", 100.0, 700.0), axis("slnt", -10.0, 0.0)]
+        );
+    }
 }
@@ -1,7 +1,7 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use super::discovery::run_ghostty;
-use crate::error::AppError;
+use crate::Error;
 use serde::{Deserialize, Serialize};
 
 /// A parsed keybinding.
@@ -14,13 +14,13 @@ pub struct Keybinding {
 /// Load default keybindings from `ghostty +list-keybinds`.
 ///
 /// Format: `keybind = trigger=action`
-pub fn load_keybinds(ghostty_path: &PathBuf) -> Result<Vec<Keybinding>, AppError> {
-    let output = run_ghostty(ghostty_path, &["+list-keybinds"])?;
+pub async fn load_keybinds(ghostty_path: &Path) -> Result<Vec<Keybinding>, Error> {
+    let output = run_ghostty(ghostty_path, &["+list-keybinds"]).await?;
     Ok(parse_keybind_list(&output))
 }
 
 /// Parse keybind output text into a list of keybindings.
-pub(crate) fn parse_keybind_list(output: &str) -> Vec<Keybinding> {
+pub fn parse_keybind_list(output: &str) -> Vec<Keybinding> {
     let mut keybinds = Vec::new();
 
     for line in output.lines() {
@@ -0,0 +1,12 @@
+//! Everything learned by running the `ghostty` binary: discovery, themes,
+//! fonts, keybinds, actions, and `+validate-config`, plus recorded output for
+//! when it can't be run.
+
+pub mod actions;
+pub mod discovery;
+pub mod effective;
+pub mod fonts;
+pub mod keybinds;
+pub mod snapshot;
+pub mod themes;
+pub mod validate;
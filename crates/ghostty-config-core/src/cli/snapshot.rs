@@ -0,0 +1,105 @@
+//! Discovery output recorded from released Ghostty versions and built into the
+//! crate, for when no `ghostty` binary can be run: before Ghostty is installed,
+//! or on a headless machine. Fonts aren't recorded, since they depend on the
+//! machine.
+
+use super::actions::parse_action_list;
+use super::keybinds::{parse_keybind_list, Keybinding};
+use crate::config::model::ConfigSchema;
+use crate::config::parser::parse_show_config;
+use crate::Error;
+
+/// What one Ghostty version printed for `+show-config --default --docs`,
+/// `+list-actions`, and `+list-keybinds` (on Linux).
+#[derive(Debug)]
+pub struct Snapshot {
+    pub version: &'static str,
+    show_config: &'static str,
+    actions: &'static str,
+    keybinds: &'static str,
+}
+
+/// Every recorded version, newest first.
+const SNAPSHOTS: &[Snapshot] = &[Snapshot {
+    version: "1.1.3",
+    show_config: include_str!("../../snapshots/1.1.3/show-config.txt"),
+    actions: include_str!("../../snapshots/1.1.3/list-actions.txt"),
+    keybinds: include_str!("../../snapshots/1.1.3/list-keybinds.txt"),
+}];
+
+/// The recorded versions, newest first.
+pub fn snapshot_versions() -> Vec<&'static str> {
+    SNAPSHOTS.iter().map(|s| s.version).collect()
+}
+
+/// The snapshot of `version`, or of the newest recorded version.
+pub fn snapshot(version: Option<&str>) -> Result<&'static Snapshot, Error> {
+    let Some(version) = version else {
+        return Ok(&SNAPSHOTS[0]);
+    };
+    SNAPSHOTS
+        .iter()
+        .find(|s| s.version == version.trim_start_matches('v'))
+        .ok_or_else(|| {
+            Error::Config(format!(
+                "No built-in schema for Ghostty {}; known versions: {}",
+                version,
+                snapshot_versions().join(", ")
+            ))
+        })
+}
+
+impl Snapshot {
+    pub fn schema(&self) -> Result<ConfigSchema, Error> {
+        parse_show_config(self.show_config)
+    }
+
+    pub fn actions(&self) -> Vec<String> {
+        parse_action_list(self.actions)
+    }
+
+    pub fn default_keybinds(&self) -> Vec<Keybinding> {
+        parse_keybind_list(self.keybinds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::{Category, ConfigValueType};
+
+    #[test]
+    fn test_every_snapshot_parses() {
+        for snapshot in SNAPSHOTS {
+            let schema = snapshot.schema().unwrap();
+            assert!(schema.options().len() > 100, "{}", snapshot.version);
+            assert!(!schema.options_for_category(&Category::Fonts).is_empty());
+            // Every default binding runs a recorded action.
+            let actions = snapshot.actions();
+            assert!(actions.iter().any(|a| a == "copy_to_clipboard"));
+            for binding in snapshot.default_keybinds() {
+                let name = binding.action.split(':').next().unwrap();
+                assert!(actions.iter().any(|a| a == name), "{}", binding.action);
+            }
+        }
+    }
+
+    #[test]
+    fn test_snapshot_types() {
+        let schema = snapshot(None).unwrap().schema().unwrap();
+        let cursor_style = schema.find_option("cursor-style").unwrap();
+        assert!(matches!(
+            &cursor_style.value_type,
+            ConfigValueType::Enum(values) if values.iter().any(|v| v.value == "bar")
+        ));
+        assert!(schema.find_option("keybind").unwrap().is_repeatable);
+        assert_eq!(schema.find_option("font-size").unwrap().default_value, "13");
+    }
+
+    #[test]
+    fn test_snapshot_lookup() {
+        assert_eq!(snapshot(None).unwrap().version, snapshot_versions()[0]);
+        assert_eq!(snapshot(Some("v1.1.3")).unwrap().version, "1.1.3");
+        assert!(snapshot(Some("0.1.0")).is_err());
+    }
+}
@@ -0,0 +1,444 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::model::{ThemeInfo, ThemeOrigin};
+use crate::Error;
+
+/// Get the theme directory path.
+pub fn theme_dir() -> Option<PathBuf> {
+    #[cfg(windows)]
+    let candidates: Vec<PathBuf> = [
+        std::env::var_os("ProgramFiles").map(|d| PathBuf::from(d).join("Ghostty")),
+        std::env::var_os("LOCALAPPDATA").map(|d| PathBuf::from(d).join("Programs").join("Ghostty")),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|install| install.join("share").join("ghostty").join("themes"))
+    .collect();
+    #[cfg(not(windows))]
+    let candidates: Vec<PathBuf> = [
+        "/Applications/Ghostty.app/Contents/Resources/ghostty/themes",
+        "/usr/share/ghostty/themes",
+        "/usr/local/share/ghostty/themes",
+    ]
+    .into_iter()
+    .map(PathBuf::from)
+    .collect();
+
+    for p in candidates {
+        if p.is_dir() {
+            return Some(p);
+        }
+    }
+
+    // Try XDG data dirs
+    if let Some(data_dirs) = std::env::var_os("XDG_DATA_DIRS") {
+        for dir in std::env::split_paths(&data_dirs) {
+            let p = dir.join("ghostty").join("themes");
+            if p.is_dir() {
+                return Some(p);
+            }
+        }
+    }
+
+    None
+}
+
+/// Directory Ghostty searches for custom themes: `themes/` next to the config file.
+pub fn user_theme_dir(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .map(|dir| dir.join("themes"))
+        .unwrap_or_else(|| PathBuf::from("themes"))
+}
+
+/// Every directory Ghostty reads user themes from, highest priority first: the
+/// editor's `user_theme_dir`, then `$XDG_CONFIG_HOME/ghostty/themes` (or
+/// `~/.config/ghostty/themes`), then the platform config directory, which differs
+/// on macOS and is `%APPDATA%` on Windows.
+pub fn user_theme_dirs(config_path: &Path) -> Vec<PathBuf> {
+    let xdg = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| directories::BaseDirs::new().map(|d| d.home_dir().join(".config")));
+    let platform = directories::BaseDirs::new().map(|d| d.config_dir().to_path_buf());
+
+    let mut dirs = vec![user_theme_dir(config_path)];
+    for dir in [xdg, platform].into_iter().flatten() {
+        let dir = dir.join("ghostty").join("themes");
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+fn check_theme_name(name: &str) -> Result<&str, Error> {
+    let name = name.trim();
+    if name.is_empty()
+        || name.starts_with('.')
+        || name.contains(['/', '\\'])
+        || name.chars().any(char::is_control)
+    {
+        return Err(Error::Config(format!("Invalid theme name '{}'", name)));
+    }
+    Ok(name)
+}
+
+fn read_user_theme(path: &Path, name: &str) -> Result<ThemeInfo, Error> {
+    let mut theme = parse_theme_file(path)
+        .ok_or_else(|| Error::Config(format!("Could not read back theme '{}'", name)))?;
+    theme.custom = true;
+    theme.origin = ThemeOrigin::User;
+    Ok(theme)
+}
+
+/// Write a new custom theme file into `dir` and return the parsed theme.
+///
+/// Refuses names that aren't a plain file name and won't replace an existing theme.
+pub fn write_user_theme(dir: &Path, name: &str, contents: &str) -> Result<ThemeInfo, Error> {
+    let name = check_theme_name(name)?;
+    let path = dir.join(name);
+    if path.exists() {
+        return Err(Error::Config(format!(
+            "A theme named '{}' already exists",
+            name
+        )));
+    }
+
+    fs::create_dir_all(dir)?;
+    fs::write(&path, contents)?;
+    read_user_theme(&path, name)
+}
+
+/// Overwrite an existing custom theme in `dir`.
+pub fn update_user_theme(dir: &Path, name: &str, contents: &str) -> Result<ThemeInfo, Error> {
+    let name = check_theme_name(name)?;
+    let path = dir.join(name);
+    if !path.is_file() {
+        return Err(Error::Config(format!("No custom theme named '{}'", name)));
+    }
+
+    fs::write(&path, contents)?;
+    read_user_theme(&path, name)
+}
+
+/// Delete a custom theme from `dir`.
+pub fn delete_user_theme(dir: &Path, name: &str) -> Result<(), Error> {
+    let name = check_theme_name(name)?;
+    let path = dir.join(name);
+    if !path.is_file() {
+        return Err(Error::Config(format!("No custom theme named '{}'", name)));
+    }
+
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+/// Load the bundled themes plus the user's, from every directory in
+/// `user_theme_dirs(config_path)`.
+pub fn load_themes(config_path: &Path) -> Result<Vec<ThemeInfo>, Error> {
+    let bundled = match theme_dir() {
+        Some(dir) => load_themes_from(&dir)?,
+        None => {
+            tracing::warn!("Could not find ghostty themes directory");
+            Vec::new()
+        }
+    };
+    merge_user_themes(bundled, &user_theme_dirs(config_path))
+}
+
+/// Add the themes in `user_dirs` to `bundled`. A user theme shadows a bundled
+/// theme with the same name, as it does in Ghostty, and earlier directories win
+/// over later ones. Only themes in the first directory are `custom`.
+pub fn merge_user_themes(
+    mut themes: Vec<ThemeInfo>,
+    user_dirs: &[PathBuf],
+) -> Result<Vec<ThemeInfo>, Error> {
+    let mut user: Vec<ThemeInfo> = Vec::new();
+    for (i, dir) in user_dirs.iter().enumerate() {
+        if !dir.is_dir() {
+            continue;
+        }
+        for theme in load_themes_from(dir)? {
+            if !user.iter().any(|u| u.name == theme.name) {
+                user.push(ThemeInfo {
+                    custom: i == 0,
+                    origin: ThemeOrigin::User,
+                    ..theme
+                });
+            }
+        }
+    }
+
+    if !user.is_empty() {
+        themes.retain(|t| !user.iter().any(|u| u.name == t.name));
+        themes.extend(user);
+        themes.sort_by_key(|t| t.name.to_lowercase());
+    }
+    Ok(themes)
+}
+
+/// Load every theme file in `dir`, sorted by name.
+pub fn load_themes_from(dir: &Path) -> Result<Vec<ThemeInfo>, Error> {
+    let mut themes = Vec::new();
+
+    let entries = fs::read_dir(dir)?;
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_file() {
+            if let Some(theme) = parse_theme_file(&path) {
+                themes.push(theme);
+            }
+        }
+    }
+
+    themes.sort_by_key(|t| t.name.to_lowercase());
+    Ok(themes)
+}
+
+/// Parse a single theme file and extract colors.
+pub fn parse_theme_file(path: &Path) -> Option<ThemeInfo> {
+    let name = path.file_name()?.to_str()?.to_string();
+    let content = fs::read_to_string(path).ok()?;
+
+    let mut background = String::from("#000000");
+    let mut foreground = String::from("#ffffff");
+    let mut palette = vec![String::new(); 16];
+    let mut cursor_color = None;
+    let mut selection_background = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+            let value = value.trim();
+
+            match key {
+                "background" => background = value.to_string(),
+                "foreground" => foreground = value.to_string(),
+                "cursor-color" => cursor_color = Some(value.to_string()),
+                "selection-background" => selection_background = Some(value.to_string()),
+                "palette" => {
+                    if let Some((idx_str, color)) = value.split_once('=') {
+                        if let Ok(idx) = idx_str.trim().parse::<usize>() {
+                            if idx < 16 {
+                                palette[idx] = color.trim().to_string();
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let is_dark = is_dark_color(&background);
+
+    Some(ThemeInfo {
+        name,
+        background,
+        foreground,
+        palette,
+        is_dark,
+        cursor_color,
+        selection_background,
+        custom: false,
+        origin: ThemeOrigin::Bundled,
+    })
+}
+
+/// Determine if a hex color is dark based on luminance.
+pub fn is_dark_color(hex: &str) -> bool {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() < 6 {
+        return true;
+    }
+
+    let r = u8::from_str_radix(&hex[0..2], 16).unwrap_or(0) as f64;
+    let g = u8::from_str_radix(&hex[2..4], 16).unwrap_or(0) as f64;
+    let b = u8::from_str_radix(&hex[4..6], 16).unwrap_or(0) as f64;
+
+    // Relative luminance
+    let luminance = 0.299 * r + 0.587 * g + 0.114 * b;
+    luminance < 128.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_is_dark_color_black() {
+        assert!(is_dark_color("#000000"));
+    }
+
+    #[test]
+    fn test_is_dark_color_white() {
+        assert!(!is_dark_color("#ffffff"));
+    }
+
+    #[test]
+    fn test_is_dark_color_dark_blue() {
+        assert!(is_dark_color("#1e1e2e"));
+    }
+
+    #[test]
+    fn test_is_dark_color_light_gray() {
+        assert!(!is_dark_color("#f0f0f0"));
+    }
+
+    #[test]
+    fn test_is_dark_color_without_hash() {
+        assert!(is_dark_color("000000"));
+        assert!(!is_dark_color("ffffff"));
+    }
+
+    #[test]
+    fn test_is_dark_color_short_hex() {
+        // Short hex should default to dark
+        assert!(is_dark_color("#abc"));
+    }
+
+    #[test]
+    fn test_parse_theme_file_basic() {
+        let content = "background = #1e1e2e\nforeground = #cdd6f4\npalette = 0=#45475a\npalette = 1=#f38ba8\n";
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(content.as_bytes()).unwrap();
+
+        let theme = parse_theme_file(tmp.path()).unwrap();
+        assert_eq!(theme.background, "#1e1e2e");
+        assert_eq!(theme.foreground, "#cdd6f4");
+        assert!(theme.is_dark);
+        assert_eq!(theme.palette[0], "#45475a");
+        assert_eq!(theme.palette[1], "#f38ba8");
+    }
+
+    #[test]
+    fn test_parse_theme_file_with_cursor_and_selection() {
+        let content = "background = #ffffff\nforeground = #000000\ncursor-color = #ff0000\nselection-background = #aaaaaa\n";
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(content.as_bytes()).unwrap();
+
+        let theme = parse_theme_file(tmp.path()).unwrap();
+        assert_eq!(theme.background, "#ffffff");
+        assert!(!theme.is_dark);
+        assert_eq!(theme.cursor_color, Some("#ff0000".to_string()));
+        assert_eq!(theme.selection_background, Some("#aaaaaa".to_string()));
+    }
+
+    #[test]
+    fn test_parse_theme_file_skips_comments() {
+        let content =
+            "# This is a comment\nbackground = #000000\n# Another comment\nforeground = #ffffff\n";
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(content.as_bytes()).unwrap();
+
+        let theme = parse_theme_file(tmp.path()).unwrap();
+        assert_eq!(theme.background, "#000000");
+        assert_eq!(theme.foreground, "#ffffff");
+    }
+
+    #[test]
+    fn test_parse_theme_file_defaults() {
+        // Empty file should use defaults
+        let content = "";
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(content.as_bytes()).unwrap();
+
+        let theme = parse_theme_file(tmp.path()).unwrap();
+        assert_eq!(theme.background, "#000000"); // default
+        assert_eq!(theme.foreground, "#ffffff"); // default
+        assert!(theme.is_dark);
+        assert_eq!(theme.cursor_color, None);
+        assert_eq!(theme.selection_background, None);
+    }
+
+    #[test]
+    fn test_parse_theme_file_palette_bounds() {
+        let content = "palette = 15=#abcdef\npalette = 16=#ignore\n";
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(content.as_bytes()).unwrap();
+
+        let theme = parse_theme_file(tmp.path()).unwrap();
+        assert_eq!(theme.palette[15], "#abcdef");
+        // palette[16] doesn't exist (only 16 entries)
+        assert_eq!(theme.palette.len(), 16);
+    }
+
+    #[test]
+    fn test_write_user_theme() {
+        let dir = tempfile::tempdir().unwrap();
+        let theme = write_user_theme(dir.path(), "My Nord", "background = #101010\n").unwrap();
+        assert_eq!(theme.name, "My Nord");
+        assert_eq!(theme.background, "#101010");
+        assert!(theme.custom);
+        assert!(write_user_theme(dir.path(), "My Nord", "").is_err());
+    }
+
+    #[test]
+    fn test_update_and_delete_user_theme() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(update_user_theme(dir.path(), "Mine", "").is_err());
+        write_user_theme(dir.path(), "Mine", "background = #101010\n").unwrap();
+
+        let theme = update_user_theme(dir.path(), "Mine", "background = #fafafa\n").unwrap();
+        assert_eq!(theme.background, "#fafafa");
+        assert!(!theme.is_dark);
+
+        delete_user_theme(dir.path(), "Mine").unwrap();
+        assert!(!dir.path().join("Mine").exists());
+        assert!(delete_user_theme(dir.path(), "Mine").is_err());
+        assert!(delete_user_theme(dir.path(), "../config").is_err());
+    }
+
+    #[test]
+    fn test_merge_user_themes() {
+        let bundled = tempfile::tempdir().unwrap();
+        let editor = tempfile::tempdir().unwrap();
+        let xdg = tempfile::tempdir().unwrap();
+        for name in ["Nord", "Dracula"] {
+            fs::write(bundled.path().join(name), "background = #000000\n").unwrap();
+        }
+        fs::write(editor.path().join("Nord"), "background = #111111\n").unwrap();
+        fs::write(xdg.path().join("Nord"), "background = #222222\n").unwrap();
+        fs::write(xdg.path().join("Mine"), "background = #333333\n").unwrap();
+
+        let dirs = [
+            editor.path().to_path_buf(),
+            xdg.path().to_path_buf(),
+            bundled.path().join("missing"),
+        ];
+        let themes = merge_user_themes(load_themes_from(bundled.path()).unwrap(), &dirs).unwrap();
+        let names: Vec<_> = themes.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Dracula", "Mine", "Nord"]);
+
+        assert_eq!(themes[0].origin, ThemeOrigin::Bundled);
+        assert_eq!(themes[1].origin, ThemeOrigin::User);
+        assert!(!themes[1].custom);
+        assert_eq!(themes[2].background, "#111111");
+        assert!(themes[2].custom);
+    }
+
+    #[test]
+    fn test_user_theme_dirs_start_with_editor_dir() {
+        let dirs = user_theme_dirs(Path::new("/tmp/conf/ghostty/config"));
+        assert_eq!(dirs[0], PathBuf::from("/tmp/conf/ghostty/themes"));
+        assert!(dirs.iter().skip(1).all(|d| d.ends_with("ghostty/themes")));
+    }
+
+    #[test]
+    fn test_write_user_theme_rejects_bad_names() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["", "../evil", "a/b", ".hidden"] {
+            assert!(write_user_theme(dir.path(), name, "").is_err(), "{}", name);
+        }
+    }
+}
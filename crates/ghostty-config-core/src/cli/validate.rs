@@ -0,0 +1,292 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::discovery::run_ghostty;
+use crate::config::file_io::parse_config;
+use crate::config::model::{ConfigEntry, UserConfig};
+use crate::config::scratch::scratch_files;
+use crate::Error;
+
+/// Run `ghostty +validate-config` and return the output.
+pub async fn validate_config(ghostty_path: &Path) -> Result<String, Error> {
+    match run_ghostty(ghostty_path, &["+validate-config"]).await {
+        Ok(output) => {
+            if output.trim().is_empty() {
+                Ok("Configuration is valid!".to_string())
+            } else {
+                Ok(output)
+            }
+        }
+        Err(e) => Ok(format!("Validation error: {}", e)),
+    }
+}
+
+/// Validate config text that hasn't been saved, as the contents of `file_path`.
+///
+/// Returns the validator's complaints; an empty string means the text is valid.
+pub async fn validate_text(
+    ghostty_path: &Path,
+    text: &str,
+    file_path: &Path,
+) -> Result<String, Error> {
+    validate_user_config(ghostty_path, &parse_config(text, file_path), &[]).await
+}
+
+/// Validate a config and its `included` files as they would be saved, including
+/// edits not yet written to disk.
+///
+/// Ghostty checks a scratch copy in a private temp directory, so `config-file`
+/// entries find the same files they would from the real config; see
+/// `config::scratch`. Messages name the real files, and line numbers in the main
+/// file refer to the text `render_config` produces.
+pub async fn validate_user_config(
+    ghostty_path: &Path,
+    config: &UserConfig,
+    included: &[UserConfig],
+) -> Result<String, Error> {
+    let mut builder = tempfile::Builder::new();
+    builder.prefix("ghostty-config-");
+    // Only this user may read the copy, or swap files in before Ghostty reads them.
+    #[cfg(unix)]
+    builder.permissions(std::os::unix::fs::PermissionsExt::from_mode(0o700));
+    let dir = builder.tempdir()?;
+    let files = scratch_files(config, included, dir.path());
+    for (path, text) in &files {
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)?
+            .write_all(text.as_bytes())?;
+    }
+    let output = run_ghostty(
+        ghostty_path,
+        &[
+            "+validate-config",
+            &format!("--config-file={}", files[0].0.display()),
+        ],
+    )
+    .await?;
+
+    // Longest first, so `include-1` isn't replaced inside `include-10`.
+    let mut renames: Vec<(String, String)> = files
+        .iter()
+        .zip(std::iter::once(config).chain(included))
+        .map(|((copy, _), original)| {
+            (
+                copy.display().to_string(),
+                original.file_path.display().to_string(),
+            )
+        })
+        .collect();
+    renames.sort_by_key(|(copy, _)| std::cmp::Reverse(copy.len()));
+    let mut output = output.trim().to_string();
+    for (copy, original) in renames {
+        output = output.replace(&copy, &original);
+    }
+    Ok(output)
+}
+
+/// A validator message about one line of a checked file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    /// The file as the validator named it: the main config or an included file.
+    pub file: String,
+    /// 1-based line number.
+    pub line: usize,
+    pub message: String,
+}
+
+static LOCATION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(.*?):(\d+):\s*(.*)$").unwrap());
+
+/// Pick out the `<path>:<line>: <message>` diagnostics from validator output.
+pub fn line_errors(output: &str) -> Vec<LineError> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let caps = LOCATION_RE.captures(line.trim())?;
+            Some(LineError {
+                file: caps[1].to_string(),
+                line: caps[2].parse().ok()?,
+                message: caps[3].trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// How serious a validator message is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One validator message, tied to a key and line where they can be worked out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    pub key: Option<String>,
+    /// 1-based line in the checked text.
+    pub line: Option<usize>,
+    pub message: String,
+    pub severity: Severity,
+}
+
+static SEVERITY_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^(error|warning|warn)\s*:\s*(.*)$").unwrap());
+static KEY_PREFIX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([a-z][a-z0-9-]*)\s*:").unwrap());
+
+/// Parse validator output for `config` into structured issues.
+///
+/// The key comes from the config entry on the reported line, or failing that from
+/// a `key: ...` prefix on the message. Messages about included files keep their
+/// location in the message instead. Lines are errors unless marked as warnings.
+pub fn validation_issues(output: &str, config: &UserConfig) -> Vec<ValidationIssue> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let main_file = config.file_path.display().to_string();
+            let (line_number, rest) = match LOCATION_RE.captures(line) {
+                Some(caps) if caps[1] == main_file => {
+                    (caps[2].parse().ok(), caps[3].trim().to_string())
+                }
+                _ => (None, line.to_string()),
+            };
+            let (severity, message) = match SEVERITY_RE.captures(&rest) {
+                Some(caps) if caps[1].eq_ignore_ascii_case("error") => {
+                    (Severity::Error, caps[2].trim().to_string())
+                }
+                Some(caps) => (Severity::Warning, caps[2].trim().to_string()),
+                None => (Severity::Error, rest),
+            };
+            let key = line_number
+                .and_then(|n: usize| config.entries.get(n.checked_sub(1)?))
+                .and_then(|entry| match entry {
+                    ConfigEntry::KeyValue { key, .. } => Some(key.clone()),
+                    _ => None,
+                })
+                .or_else(|| {
+                    KEY_PREFIX_RE
+                        .captures(&message)
+                        .map(|caps| caps[1].to_string())
+                });
+            ValidationIssue {
+                key,
+                line: line_number,
+                message,
+                severity,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_errors() {
+        let output = "/tmp/ghostty-config-1.validate:3:font-size: invalid value \"big\"\n\
+                      /tmp/ghostty-config-1.validate:7:foo: unknown field\n\
+                      warning: something without a location\n";
+        let errors = line_errors(output);
+        assert_eq!(
+            errors,
+            vec![
+                LineError {
+                    file: "/tmp/ghostty-config-1.validate".to_string(),
+                    line: 3,
+                    message: "font-size: invalid value \"big\"".to_string()
+                },
+                LineError {
+                    file: "/tmp/ghostty-config-1.validate".to_string(),
+                    line: 7,
+                    message: "foo: unknown field".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validation_issues() {
+        let config = crate::config::file_io::parse_config(
+            "# fonts\nfont-size = big\ntheme = nope\n",
+            std::path::Path::new("/tmp/config"),
+        );
+        let output = "/tmp/config:2:font-size: invalid value\n\
+                      warning: window-theme: deprecated value\n\
+                      something went wrong\n\
+                      /tmp/colors.conf:2:background: invalid value\n";
+        let issues = validation_issues(output, &config);
+        assert_eq!(issues.len(), 4);
+        assert_eq!(issues[0].key.as_deref(), Some("font-size"));
+        assert_eq!(issues[0].line, Some(2));
+        assert_eq!(issues[0].message, "font-size: invalid value");
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[1].key.as_deref(), Some("window-theme"));
+        assert_eq!(issues[1].line, None);
+        assert_eq!(issues[1].severity, Severity::Warning);
+        assert_eq!(issues[2].key, None);
+        assert_eq!(issues[2].message, "something went wrong");
+        // A line of an included file isn't the config's line 2.
+        assert_eq!(issues[3].key, None);
+        assert_eq!(issues[3].line, None);
+        assert_eq!(
+            issues[3].message,
+            "/tmp/colors.conf:2:background: invalid value"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_validate_user_config_with_includes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Reports the main file's first line, and that of each file it includes.
+        let dir = tempfile::tempdir().unwrap();
+        let ghostty = dir.path().join("ghostty");
+        std::fs::write(
+            &ghostty,
+            "#!/bin/sh\n\
+             file=\"${2#--config-file=}\"\n\
+             echo \"$file:1:$(head -n 1 \"$file\")\"\n\
+             sed -n 's/^config-file = //p' \"$file\" | while read -r inc; do\n\
+               echo \"$inc:1:$(head -n 1 \"$inc\")\"\n\
+             done\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&ghostty, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let config_path = dir.path().join("config");
+        let colors_path = dir.path().join("colors.conf");
+        std::fs::write(&colors_path, "background = #000000\n").unwrap();
+        let config = parse_config("config-file = colors.conf\n", &config_path);
+        let mut colors = parse_config("background = #000000\n", &colors_path);
+        colors.set("background", "#101010");
+
+        // The relative include is found, with its unsaved edit, under its real name.
+        let output = validate_user_config(&ghostty, &config, &[colors])
+            .await
+            .unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].starts_with(&format!("{}:1:config-file = ", config_path.display())));
+        assert_eq!(
+            lines[1],
+            format!("{}:1:background = #101010", colors_path.display())
+        );
+
+        // Text is checked the same way, against the included file on disk.
+        let output = validate_text(&ghostty, "config-file = colors.conf\n", &config_path)
+            .await
+            .unwrap();
+        assert!(output.ends_with(&format!("{}:1:background = #000000", colors_path.display())));
+    }
+}
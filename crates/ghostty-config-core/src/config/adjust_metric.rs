@@ -0,0 +1,125 @@
+//! The `adjust-*` font metric options, which take a pixel offset or a percentage.
+
+use std::fmt;
+
+/// Units offered for a metric adjustment.
+pub const ADJUSTMENT_UNITS: &[&str] = &["px", "%"];
+
+/// Groups the metrics editor shows the `adjust-*` keys in, in order.
+pub const METRIC_GROUPS: &[&str] = &["Cell", "Decorations", "Cursor", "Other"];
+
+/// A change to a font metric: whole pixels added to it, or a percentage of it.
+/// Either may be negative.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Adjustment {
+    Pixels(i32),
+    Percent(f64),
+}
+
+impl Adjustment {
+    /// Parse a value like `2`, `-1`, or `10%`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        match value.strip_suffix('%') {
+            Some(percent) => percent
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|p| p.is_finite() && *p > -100.0)
+                .map(Adjustment::Percent),
+            None => value.parse().ok().map(Adjustment::Pixels),
+        }
+    }
+
+    /// The metric `base` pixels tall or wide after this adjustment.
+    pub fn apply(&self, base: f64) -> f64 {
+        match self {
+            Adjustment::Pixels(px) => base + *px as f64,
+            Adjustment::Percent(percent) => base * (1.0 + percent / 100.0),
+        }
+    }
+
+    /// The amount without its unit, and the unit, for the editor's inputs.
+    pub fn split(&self) -> (String, &'static str) {
+        match self {
+            Adjustment::Pixels(px) => (px.to_string(), "px"),
+            Adjustment::Percent(percent) => (percent.to_string(), "%"),
+        }
+    }
+}
+
+impl fmt::Display for Adjustment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Adjustment::Pixels(px) => write!(f, "{}", px),
+            Adjustment::Percent(percent) => write!(f, "{}%", percent),
+        }
+    }
+}
+
+/// The config value for `amount` in `unit`, one of [`ADJUSTMENT_UNITS`].
+pub fn format_adjustment(amount: &str, unit: &str) -> Option<String> {
+    let value = match unit {
+        "px" => Adjustment::Pixels(amount.trim().parse().ok()?),
+        "%" => Adjustment::parse(&format!("{}%", amount.trim()))?,
+        _ => return None,
+    };
+    Some(value.to_string())
+}
+
+/// Which of [`METRIC_GROUPS`] an `adjust-*` key belongs in.
+pub fn metric_group(key: &str) -> &'static str {
+    let metric = key.strip_prefix("adjust-").unwrap_or(key);
+    if metric.starts_with("cell-") || metric == "font-baseline" {
+        "Cell"
+    } else if ["underline-", "strikethrough-", "overline-"]
+        .iter()
+        .any(|p| metric.starts_with(p))
+    {
+        "Decorations"
+    } else if metric.starts_with("cursor-") {
+        "Cursor"
+    } else {
+        "Other"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_adjustment() {
+        assert_eq!(Adjustment::parse("2"), Some(Adjustment::Pixels(2)));
+        assert_eq!(Adjustment::parse("-1"), Some(Adjustment::Pixels(-1)));
+        assert_eq!(Adjustment::parse("10%"), Some(Adjustment::Percent(10.0)));
+        assert_eq!(
+            Adjustment::parse("-12.5 %"),
+            Some(Adjustment::Percent(-12.5))
+        );
+        assert_eq!(Adjustment::parse("1.5"), None);
+        assert_eq!(Adjustment::parse("-100%"), None);
+        assert_eq!(Adjustment::parse("wide"), None);
+        assert_eq!(Adjustment::Percent(-12.5).to_string(), "-12.5%");
+    }
+
+    #[test]
+    fn test_apply_and_format() {
+        assert_eq!(Adjustment::Pixels(2).apply(16.0), 18.0);
+        assert_eq!(Adjustment::Percent(-25.0).apply(16.0), 12.0);
+        assert_eq!(format_adjustment("-2", "px"), Some("-2".to_string()));
+        assert_eq!(format_adjustment("15", "%"), Some("15%".to_string()));
+        assert_eq!(format_adjustment("1.5", "px"), None);
+        assert_eq!(format_adjustment("2", "em"), None);
+        assert_eq!(Adjustment::Percent(15.0).split(), ("15".to_string(), "%"));
+    }
+
+    #[test]
+    fn test_metric_group() {
+        assert_eq!(metric_group("adjust-cell-height"), "Cell");
+        assert_eq!(metric_group("adjust-font-baseline"), "Cell");
+        assert_eq!(metric_group("adjust-strikethrough-position"), "Decorations");
+        assert_eq!(metric_group("adjust-cursor-thickness"), "Cursor");
+        assert_eq!(metric_group("adjust-icon-height"), "Other");
+    }
+}
@@ -0,0 +1,265 @@
+//! Comments for keys the UI adds to a config file, so a file edited by hand and by
+//! this tool stays readable: each new key gets the first sentence of its docs, and
+//! keys appended at the end are grouped under category headers.
+
+use super::categorize::categorize_key;
+use super::model::{Category, ConfigEntry, ConfigSchema, UserConfig};
+
+/// The header comment introducing a category's keys, e.g. `# ===== Fonts =====`.
+pub fn category_header(category: &Category) -> String {
+    format!("# ===== {} =====", category.display_name())
+}
+
+/// The first sentence of an option's documentation, on one line.
+pub fn first_sentence(documentation: &str) -> Option<String> {
+    let paragraph = documentation.split("\n\n").next()?;
+    let text = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+    let sentence = match text.find(". ") {
+        Some(end) => &text[..=end],
+        None => &text,
+    };
+    (!sentence.is_empty()).then(|| sentence.to_string())
+}
+
+/// Document the keys in `config` that `base` (the file as last saved) doesn't set.
+///
+/// Keys appended at the end of the file move under their category's header, which
+/// is added when the file doesn't have one yet. New keys elsewhere keep their place
+/// and only get a comment, and only when there isn't one above them already.
+pub fn annotate_new_keys(config: &mut UserConfig, base: &UserConfig, schema: &ConfigSchema) {
+    let is_new = |key: &str| base.get(key).is_none();
+
+    // The appended run: new keys after the last entry that was already there.
+    let start = config
+        .entries
+        .iter()
+        .rposition(|e| !matches!(e, ConfigEntry::KeyValue { key, .. } if is_new(key)))
+        .map_or(0, |i| i + 1);
+    let appended = config.entries.split_off(start);
+
+    // Comment the new keys that stay where they are.
+    let mut seen: Vec<String> = Vec::new();
+    let mut index = 0;
+    while index < config.entries.len() {
+        if let ConfigEntry::KeyValue { key, .. } = &config.entries[index] {
+            if is_new(key) && !seen.contains(key) {
+                seen.push(key.clone());
+                let commented =
+                    index > 0 && matches!(config.entries[index - 1], ConfigEntry::Comment(_));
+                if let (false, Some(comment)) = (commented, doc_comment(key, schema)) {
+                    config.entries.insert(index, comment);
+                    index += 1;
+                }
+            }
+        }
+        index += 1;
+    }
+
+    // Group the appended keys by category, keeping each key's values together.
+    let mut groups: Vec<(Category, Vec<ConfigEntry>)> = Vec::new();
+    let mut keys: Vec<&str> = Vec::new();
+    for entry in &appended {
+        if let ConfigEntry::KeyValue { key, .. } = entry {
+            if !keys.contains(&key.as_str()) {
+                keys.push(key);
+            }
+        }
+    }
+    for key in keys {
+        let category = schema
+            .find_option(key)
+            .map(|o| o.category.clone())
+            .unwrap_or_else(|| categorize_key(key));
+        let mut lines: Vec<ConfigEntry> = doc_comment(key, schema).into_iter().collect();
+        lines.extend(
+            appended
+                .iter()
+                .filter(|e| matches!(e, ConfigEntry::KeyValue { key: k, .. } if k == key))
+                .cloned(),
+        );
+        match groups.iter_mut().find(|(c, _)| *c == category) {
+            Some((_, group)) => group.extend(lines),
+            None => groups.push((category, lines)),
+        }
+    }
+    groups.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (category, lines) in groups {
+        let header = category_header(&category);
+        match section_end(config, &header) {
+            Some(end) => {
+                config.entries.splice(end..end, lines);
+            }
+            None => {
+                if config
+                    .entries
+                    .last()
+                    .is_some_and(|e| !matches!(e, ConfigEntry::BlankLine))
+                {
+                    config.entries.push(ConfigEntry::BlankLine);
+                }
+                config.entries.push(ConfigEntry::Comment(header));
+                config.entries.extend(lines);
+            }
+        }
+    }
+}
+
+/// Put a `# default: <value>` comment above the first line of every option the
+/// schema knows, for exports that show how a config differs from the defaults.
+pub fn annotate_defaults(config: &mut UserConfig, schema: &ConfigSchema) {
+    let mut seen: Vec<String> = Vec::new();
+    let mut entries = Vec::with_capacity(config.entries.len());
+    for entry in config.entries.drain(..) {
+        if let ConfigEntry::KeyValue { key, .. } = &entry {
+            if let (false, Some(option)) = (seen.contains(key), schema.find_option(key)) {
+                seen.push(key.clone());
+                let default = match option.default_value.as_str() {
+                    "" => "(none)",
+                    value => value,
+                };
+                entries.push(ConfigEntry::Comment(format!("# default: {}", default)));
+            }
+        }
+        entries.push(entry);
+    }
+    config.entries = entries;
+}
+
+fn doc_comment(key: &str, schema: &ConfigSchema) -> Option<ConfigEntry> {
+    let sentence = first_sentence(&schema.find_option(key)?.documentation)?;
+    Some(ConfigEntry::Comment(format!("# {}", sentence)))
+}
+
+/// Where to add entries to the section under `header`: after its last entry, which
+/// is the one before the next blank line or header. `None` when the file has no
+/// such header.
+fn section_end(config: &UserConfig, header: &str) -> Option<usize> {
+    let start = config
+        .entries
+        .iter()
+        .position(|e| matches!(e, ConfigEntry::Comment(c) if c.trim() == header))?;
+    let mut end = start + 1;
+    for (index, entry) in config.entries.iter().enumerate().skip(start + 1) {
+        match entry {
+            ConfigEntry::Comment(c) if c.trim_start().starts_with("# =====") => break,
+            ConfigEntry::BlankLine if end > start + 1 => break,
+            ConfigEntry::BlankLine => {}
+            _ => end = index + 1,
+        }
+    }
+    Some(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file_io::{parse_config, render_config};
+    use crate::config::model::{ConfigOption, ConfigValueType};
+    use std::path::Path;
+
+    fn option(key: &str, category: Category, documentation: &str) -> ConfigOption {
+        ConfigOption {
+            key: key.to_string(),
+            default_value: String::new(),
+            documentation: documentation.to_string(),
+            value_type: ConfigValueType::Text,
+            category,
+            is_repeatable: key == "keybind",
+            conditions: Default::default(),
+        }
+    }
+
+    fn schema() -> ConfigSchema {
+        ConfigSchema::new(vec![
+            option(
+                "font-size",
+                Category::Fonts,
+                "Font size in points. This value can be a\nfraction.\n\nMore details.",
+            ),
+            option("font-family", Category::Fonts, "The font families to use."),
+            option(
+                "background",
+                Category::Colors,
+                "Background color for the window.",
+            ),
+            option("keybind", Category::Keybindings, ""),
+        ])
+    }
+
+    #[test]
+    fn test_first_sentence() {
+        assert_eq!(
+            first_sentence("Font size in points. This value can be a\nfraction.").as_deref(),
+            Some("Font size in points.")
+        );
+        assert_eq!(
+            first_sentence("No period\nat the end").as_deref(),
+            Some("No period at the end")
+        );
+        assert_eq!(first_sentence(""), None);
+    }
+
+    #[test]
+    fn test_annotate_appended_keys() {
+        let path = Path::new("config");
+        let base = parse_config(
+            "# ===== Fonts =====\nfont-family = Iosevka\n\n# mine\nkeybind = a=b\n",
+            path,
+        );
+        let mut config = base.clone();
+        config.push("keybind", "c=d");
+        config.set("background", "#000000");
+        config.set("font-size", "13");
+        config.set("unknown-thing", "1");
+
+        annotate_new_keys(&mut config, &base, &schema());
+        assert_eq!(
+            render_config(&config),
+            "# ===== Fonts =====\nfont-family = Iosevka\n# Font size in points.\nfont-size = 13\n\n\
+             # mine\nkeybind = a=b\nkeybind = c=d\n\n\
+             # ===== Colors =====\n# Background color for the window.\nbackground = #000000\n\n\
+             # ===== Advanced =====\nunknown-thing = 1\n"
+        );
+
+        // Nothing is new once saved.
+        let saved = config.clone();
+        annotate_new_keys(&mut config, &saved, &schema());
+        assert_eq!(render_config(&config), render_config(&saved));
+    }
+
+    #[test]
+    fn test_annotate_new_file() {
+        let base = UserConfig::new("config".into());
+        let mut config = base.clone();
+        config.set("font-size", "13");
+        annotate_new_keys(&mut config, &base, &schema());
+        assert_eq!(
+            render_config(&config),
+            "# ===== Fonts =====\n# Font size in points.\nfont-size = 13\n"
+        );
+    }
+
+    #[test]
+    fn test_annotate_defaults() {
+        let mut config = parse_config(
+            "font-size = 14
+keybind = a=b
+keybind = c=d
+unknown = 1
+",
+            Path::new("/tmp/config"),
+        );
+        annotate_defaults(&mut config, &schema());
+        assert_eq!(
+            render_config(&config),
+            "# default: (none)
+font-size = 14
+# default: (none)
+keybind = a=b
+keybind = c=d
+unknown = 1
+"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::file_io::unquote_value;
+use super::model::{ThemeInfo, UserConfig};
+use super::theme_selection::ThemeSelection;
+
+/// Background color used when neither the config nor a theme sets one.
+pub const DEFAULT_BACKGROUND: &str = "#1e1e2e";
+
+/// How `background-image` is scaled to the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ImageFit {
+    Contain,
+    Cover,
+    Stretch,
+    None,
+}
+
+impl ImageFit {
+    fn parse(value: &str) -> Self {
+        match value.trim() {
+            "cover" => ImageFit::Cover,
+            "stretch" => ImageFit::Stretch,
+            "none" => ImageFit::None,
+            _ => ImageFit::Contain,
+        }
+    }
+
+    /// Equivalent CSS `background-size` value.
+    pub fn css_size(&self) -> &'static str {
+        match self {
+            ImageFit::Contain => "contain",
+            ImageFit::Cover => "cover",
+            ImageFit::Stretch => "100% 100%",
+            ImageFit::None => "auto",
+        }
+    }
+}
+
+/// The configured background image, with its opacity already combined with `background-opacity`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BackgroundImage {
+    pub path: PathBuf,
+    /// Final opacity of the image layer, in `0.0..=1.0`.
+    pub opacity: f32,
+    pub fit: ImageFit,
+    /// Ghostty position such as `top-left` or `center`.
+    pub position: String,
+    pub repeat: bool,
+}
+
+impl BackgroundImage {
+    /// Equivalent CSS `background-position` value.
+    pub fn css_position(&self) -> String {
+        self.position.replace('-', " ")
+    }
+}
+
+/// The layers Ghostty draws behind the terminal text, bottom to top.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BackgroundLayers {
+    /// Background color from the config, falling back to the active theme.
+    pub color: String,
+    /// `background-opacity`, clamped to `0.0..=1.0`.
+    pub opacity: f32,
+    pub image: Option<BackgroundImage>,
+}
+
+/// The theme selected by the config, if it is one of the loaded themes. For a
+/// light/dark pair that's the dark one; see [`ThemeSelection::preview_name`].
+pub fn active_theme<'a>(config: &UserConfig, themes: &'a [ThemeInfo]) -> Option<&'a ThemeInfo> {
+    let selection = ThemeSelection::parse(config.get("theme")?);
+    let name = selection.preview_name()?;
+    themes.iter().find(|t| t.name == name)
+}
+
+/// Resolve a `background-image` path the way Ghostty does: `~/` is the home
+/// directory and relative paths are relative to the config file.
+pub fn resolve_image_path(raw: &str, config_path: &Path) -> PathBuf {
+    let raw = unquote_value(raw);
+    if let Some(rest) = raw.strip_prefix("~/") {
+        if let Some(dirs) = directories::BaseDirs::new() {
+            return dirs.home_dir().join(rest);
+        }
+    }
+    let path = PathBuf::from(raw.as_ref());
+    if path.is_absolute() {
+        return path;
+    }
+    match config_path.parent() {
+        Some(dir) => dir.join(path),
+        None => path,
+    }
+}
+
+fn parse_fraction(value: Option<&str>) -> f32 {
+    value
+        .and_then(|v| v.trim().parse::<f32>().ok())
+        .filter(|v| v.is_finite())
+        .unwrap_or(1.0)
+}
+
+/// Resolve the background Ghostty would draw for `config`.
+///
+/// `background-image-opacity` is relative to `background-opacity`, so an image
+/// opacity of 1.5 with a background opacity of 0.5 draws the image at 0.75.
+pub fn resolve_background(config: &UserConfig, themes: &[ThemeInfo]) -> BackgroundLayers {
+    let color = config
+        .get("background")
+        .map(String::from)
+        .or_else(|| active_theme(config, themes).map(|t| t.background.clone()))
+        .unwrap_or_else(|| DEFAULT_BACKGROUND.to_string());
+    let opacity = parse_fraction(config.get("background-opacity")).clamp(0.0, 1.0);
+
+    let image = config
+        .get("background-image")
+        .filter(|v| !v.trim().is_empty())
+        .map(|raw| BackgroundImage {
+            path: resolve_image_path(raw, &config.file_path),
+            opacity: (opacity * parse_fraction(config.get("background-image-opacity")))
+                .clamp(0.0, 1.0),
+            fit: ImageFit::parse(config.get("background-image-fit").unwrap_or("contain")),
+            position: config
+                .get("background-image-position")
+                .unwrap_or("center")
+                .trim()
+                .to_string(),
+            repeat: config.get("background-image-repeat").map(str::trim) == Some("true"),
+        });
+
+    BackgroundLayers {
+        color,
+        opacity,
+        image,
+    }
+}
+
+/// Apply an alpha to a `#rrggbb` color, producing a CSS `rgba()` value.
+///
+/// Colors that aren't plain hex are returned unchanged.
+pub fn with_alpha(color: &str, alpha: f32) -> String {
+    let hex = color.trim().trim_start_matches('#');
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    match (hex.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) if alpha < 1.0 => {
+            format!("rgba({}, {}, {}, {:.2})", r, g, b, alpha)
+        }
+        (6, Some(_), Some(_), Some(_)) => format!("#{}", hex),
+        _ => color.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::test_config;
+
+    fn theme(name: &str, background: &str) -> ThemeInfo {
+        ThemeInfo {
+            name: name.to_string(),
+            background: background.to_string(),
+            foreground: "#ffffff".to_string(),
+            palette: vec![String::new(); 16],
+            is_dark: true,
+            cursor_color: None,
+            selection_background: None,
+            custom: false,
+            origin: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_background_color_from_theme_or_config() {
+        let themes = vec![theme("Nord", "#2e3440")];
+        let themed = resolve_background(&test_config(&[("theme", "Nord")]), &themes);
+        assert_eq!(themed.color, "#2e3440");
+
+        let explicit = resolve_background(
+            &test_config(&[("theme", "Nord"), ("background", "#000000")]),
+            &themes,
+        );
+        assert_eq!(explicit.color, "#000000");
+
+        assert_eq!(
+            resolve_background(&test_config(&[]), &themes).color,
+            DEFAULT_BACKGROUND
+        );
+    }
+
+    #[test]
+    fn test_image_opacity_is_relative() {
+        let layers = resolve_background(
+            &test_config(&[
+                ("background-opacity", "0.5"),
+                ("background-image", "bg.png"),
+                ("background-image-opacity", "1.5"),
+                ("background-image-fit", "cover"),
+                ("background-image-position", "top-left"),
+            ]),
+            &[],
+        );
+        assert_eq!(layers.opacity, 0.5);
+        let image = layers.image.unwrap();
+        assert_eq!(image.opacity, 0.75);
+        assert_eq!(image.fit, ImageFit::Cover);
+        assert_eq!(image.css_position(), "top left");
+        assert_eq!(image.path, PathBuf::from("/home/me/.config/ghostty/bg.png"));
+        assert!(!image.repeat);
+    }
+
+    #[test]
+    fn test_no_image() {
+        let layers = resolve_background(&test_config(&[("background-opacity", "2")]), &[]);
+        assert_eq!(layers.opacity, 1.0);
+        assert!(layers.image.is_none());
+    }
+
+    #[test]
+    fn test_with_alpha() {
+        assert_eq!(with_alpha("#ff0080", 0.5), "rgba(255, 0, 128, 0.50)");
+        assert_eq!(with_alpha("ff0080", 1.0), "#ff0080");
+        assert_eq!(with_alpha("red", 0.5), "red");
+    }
+}
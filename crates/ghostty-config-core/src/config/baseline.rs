@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::path::PathBuf;
+use std::process::Command;
+
+use super::diff::{diff_keys, ChangeKind, KeyChange};
+use super::file_io::{parse_config, read_config_text};
+use super::model::UserConfig;
+use crate::{CliError, CliErrorKind, Error};
+
+/// Seconds to wait for a baseline URL before giving up.
+const FETCH_TIMEOUT_SECS: &str = "10";
+
+/// Where a team baseline config comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BaselineSource {
+    Url(String),
+    File(PathBuf),
+}
+
+impl BaselineSource {
+    /// Treat `http://` and `https://` as URLs and anything else as a file path.
+    pub fn parse(source: &str) -> Self {
+        let source = source.trim();
+        if source.starts_with("http://") || source.starts_with("https://") {
+            BaselineSource::Url(source.to_string())
+        } else {
+            BaselineSource::File(PathBuf::from(source))
+        }
+    }
+
+    /// Download or read the baseline config text.
+    ///
+    /// URLs are fetched with `curl`, which is present on every platform Ghostty runs on.
+    pub fn fetch(&self) -> Result<String, Error> {
+        match self {
+            BaselineSource::File(path) => {
+                if !path.is_file() {
+                    return Err(Error::Config(format!(
+                        "Baseline file not found: {}",
+                        path.display()
+                    )));
+                }
+                read_config_text(path)
+            }
+            BaselineSource::Url(url) => {
+                let output = Command::new("curl")
+                    .args(["-fsSL", "--max-time", FETCH_TIMEOUT_SECS, url])
+                    .output()
+                    .map_err(|e| {
+                        CliError::new(CliErrorKind::Spawn, format!("Failed to run curl: {}", e))
+                    })?;
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                    return Err(CliError {
+                        exit_code: output.status.code(),
+                        stderr: stderr.clone(),
+                        ..CliError::new(
+                            CliErrorKind::Failed,
+                            format!("Failed to fetch baseline from {}: {}", url, stderr),
+                        )
+                    }
+                    .into());
+                }
+                Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+        }
+    }
+}
+
+impl fmt::Display for BaselineSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BaselineSource::Url(url) => write!(f, "{}", url),
+            BaselineSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// A shared config the user's settings are compared against.
+#[derive(Debug, Clone)]
+pub struct Baseline {
+    pub source: BaselineSource,
+    pub config: UserConfig,
+    /// Keys the user chose to keep different from the baseline.
+    pub ignored: HashSet<String>,
+}
+
+impl Baseline {
+    /// Fetch and parse the baseline at `source`.
+    pub fn load(source: BaselineSource) -> Result<Self, Error> {
+        let text = source.fetch()?;
+        let path = match &source {
+            BaselineSource::File(path) => path.clone(),
+            BaselineSource::Url(_) => PathBuf::from("baseline"),
+        };
+        Ok(Baseline {
+            config: parse_config(&text, &path),
+            source,
+            ignored: HashSet::new(),
+        })
+    }
+
+    /// Keys where `current` differs from the baseline.
+    ///
+    /// Only keys the baseline sets count: settings the baseline doesn't mention are
+    /// personal, not deviations. `old` holds the baseline values, `new` the user's.
+    pub fn deviations(&self, current: &UserConfig) -> Vec<KeyChange> {
+        diff_keys(&self.config, current)
+            .into_iter()
+            .filter(|change| change.kind != ChangeKind::Added)
+            .collect()
+    }
+
+    /// Make `config` match the baseline for `key`. Returns false if the baseline doesn't set it.
+    pub fn adopt(&self, key: &str, config: &mut UserConfig) -> bool {
+        let values: Vec<String> = self
+            .config
+            .get_all(key)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        if values.is_empty() {
+            return false;
+        }
+        config.set_all(key, &values);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::test_config;
+
+    fn baseline(pairs: &[(&str, &str)]) -> Baseline {
+        Baseline {
+            source: BaselineSource::parse("https://example.com/ghostty"),
+            config: test_config(pairs),
+            ignored: HashSet::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_source() {
+        assert_eq!(
+            BaselineSource::parse(" https://example.com/config "),
+            BaselineSource::Url("https://example.com/config".to_string())
+        );
+        assert_eq!(
+            BaselineSource::parse("/etc/team/ghostty"),
+            BaselineSource::File(PathBuf::from("/etc/team/ghostty"))
+        );
+    }
+
+    #[test]
+    fn test_deviations_ignore_personal_keys() {
+        let baseline = baseline(&[("font-size", "14"), ("theme", "Nord"), ("keybind", "a=b")]);
+        let current = test_config(&[
+            ("font-size", "16"),
+            ("theme", "Nord"),
+            ("cursor-style", "bar"),
+        ]);
+        let deviations = baseline.deviations(&current);
+        let keys: Vec<&str> = deviations.iter().map(|d| d.key.as_str()).collect();
+        assert_eq!(keys, vec!["font-size", "keybind"]);
+        assert_eq!(deviations[0].kind, ChangeKind::Changed);
+        assert_eq!(deviations[1].kind, ChangeKind::Removed);
+    }
+
+    #[test]
+    fn test_adopt_copies_all_values() {
+        let baseline = baseline(&[("keybind", "a=b"), ("keybind", "c=d")]);
+        let mut current = test_config(&[("keybind", "x=y")]);
+        assert!(baseline.adopt("keybind", &mut current));
+        assert_eq!(current.get_all("keybind"), vec!["a=b", "c=d"]);
+        assert!(!baseline.adopt("font-size", &mut current));
+    }
+
+    #[test]
+    fn test_load_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("team");
+        std::fs::write(&path, "# team defaults\nfont-size = 13\n").unwrap();
+        let baseline = Baseline::load(BaselineSource::File(path)).unwrap();
+        assert_eq!(baseline.config.get("font-size"), Some("13"));
+
+        let missing = BaselineSource::File(dir.path().join("missing"));
+        assert!(Baseline::load(missing).is_err());
+    }
+}
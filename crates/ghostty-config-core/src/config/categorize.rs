@@ -1,138 +1,92 @@
-use super::model::Category;
-
-/// Assign a category to a config key based on its prefix/name.
-pub fn categorize_key(key: &str) -> Category {
-    // Exact matches first
-    match key {
-        "theme" => return Category::Appearance,
-        "keybind" => return Category::Keybindings,
-        "palette" => return Category::Colors,
-        "config-file" => return Category::Advanced,
-        "term" => return Category::Terminal,
-        "enquiry-response" => return Category::Terminal,
-        "title" => return Category::Window,
-        "class" | "x11-instance-name" => return Category::GTKLinux,
-        "scrollback-limit" => return Category::Scrollback,
-        "link" | "link-url" => return Category::Mouse,
-        _ => {}
-    }
-
-    // Prefix matches
-    if key.starts_with("font-") {
-        return Category::Fonts;
-    }
-
-    if key.starts_with("cursor-") {
-        return Category::Cursor;
-    }
-
-    if key.starts_with("mouse-") || key.starts_with("click-") {
-        return Category::Mouse;
-    }
-
-    if key.starts_with("clipboard-") || key.starts_with("copy-on-select") {
-        return Category::Clipboard;
-    }
-
-    if key.starts_with("shell-")
-        || key == "command"
-        || key == "wait-after-command"
-        || key == "initial-command"
-    {
-        return Category::Shell;
-    }
-
-    if key.starts_with("window-")
-        || key.starts_with("resize-")
-        || key.starts_with("fullscreen")
-        || key == "confirm-close-surface"
-    {
-        return Category::Window;
-    }
-
-    if key.starts_with("background") {
-        return Category::Background;
-    }
-
-    if key.starts_with("foreground")
-        || key.starts_with("selection-")
-        || key.contains("color")
-        || key == "bold-is-bright"
-        || key == "minimum-contrast"
-        || key == "palette"
-        || key == "invert-selection-fg-bg"
-        || key == "bold-color"
-        || key == "faint-opacity"
-    {
-        return Category::Colors;
-    }
-
-    if key.starts_with("macos-")
-        || key.starts_with("auto-update")
-        || key == "quick-terminal-position"
-        || key.starts_with("quick-terminal")
-    {
-        return Category::MacOS;
-    }
+use std::collections::HashMap;
+use std::sync::OnceLock;
 
-    if key.starts_with("gtk-") || key.starts_with("adw-") || key.starts_with("linux-") {
-        return Category::GTKLinux;
-    }
-
-    if key.starts_with("scrollback") || key.starts_with("scroll-") {
-        return Category::Scrollback;
-    }
+use serde::Deserialize;
 
-    if key.starts_with("input-")
-        || key == "vt-kam-allowed"
-        || key.starts_with("desktop-notifications")
-    {
-        return Category::Input;
-    }
+use super::model::Category;
+use crate::Error;
+
+/// The category table that ships with this crate.
+const EMBEDDED: &str = include_str!("categories.toml");
+
+/// Which category each option belongs to, read from a `categories.toml` table.
+///
+/// An exact key wins over a prefix, and a longer prefix over a shorter one, so
+/// entries don't depend on their order in the file.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryMap {
+    keys: HashMap<String, Category>,
+    prefixes: HashMap<String, Category>,
+}
 
-    if key == "adjust-cell-width"
-        || key == "adjust-cell-height"
-        || key == "adjust-font-baseline"
-        || key == "adjust-underline-position"
-        || key == "adjust-underline-thickness"
-        || key == "adjust-strikethrough-position"
-        || key == "adjust-strikethrough-thickness"
-        || key == "adjust-overline-position"
-        || key == "adjust-overline-thickness"
-        || key == "adjust-cursor-thickness"
-        || key == "adjust-cursor-height"
-        || key == "adjust-box-thickness"
-    {
-        return Category::Fonts;
-    }
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Section {
+    #[serde(default)]
+    keys: Vec<String>,
+    #[serde(default)]
+    prefixes: Vec<String>,
+}
 
-    if key == "unfocused-split-fill" || key == "split-color" || key.starts_with("unfocused-split") {
-        return Category::Appearance;
+impl CategoryMap {
+    /// Parse a table whose sections are category slugs.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let sections: HashMap<String, Section> = basic_toml::from_str(text)
+            .map_err(|e| Error::Config(format!("Invalid category table: {}", e)))?;
+        let mut map = CategoryMap::default();
+        for (slug, section) in sections {
+            let category = Category::all()
+                .into_iter()
+                .find(|c| c.slug() == slug)
+                .ok_or_else(|| Error::Config(format!("Unknown category `{}`", slug)))?;
+            for key in section.keys {
+                map.keys.insert(key, category.clone());
+            }
+            for prefix in section.prefixes {
+                map.prefixes.insert(prefix, category.clone());
+            }
+        }
+        Ok(map)
+    }
+
+    /// The table that ships with this crate.
+    pub fn embedded() -> Self {
+        Self::parse(EMBEDDED).expect("embedded categories.toml is valid")
+    }
+
+    /// Add `overrides`, replacing entries for the same key or prefix.
+    pub fn merge(&mut self, overrides: CategoryMap) {
+        self.keys.extend(overrides.keys);
+        self.prefixes.extend(overrides.prefixes);
+    }
+
+    pub fn category_of(&self, key: &str) -> Category {
+        if let Some(category) = self.keys.get(key) {
+            return category.clone();
+        }
+        self.prefixes
+            .iter()
+            .filter(|(prefix, _)| key.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(Category::Advanced, |(_, category)| category.clone())
     }
+}
 
-    if key == "osc-color-report-format"
-        || key == "abnormal-command-exit-runtime"
-        || key == "image-storage-limit"
-        || key == "custom-shader"
-        || key == "custom-shader-animation"
-        || key.starts_with("grapheme-")
-        || key.starts_with("freetype-")
-        || key == "async-backend"
-    {
-        return Category::Advanced;
-    }
+static CATEGORIES: OnceLock<CategoryMap> = OnceLock::new();
 
-    // Appearance-related
-    if key == "minimum-contrast"
-        || key == "bold-is-bright"
-        || key.starts_with("cell-")
-        || key.starts_with("focus-")
-        || key.starts_with("unfocused-")
-    {
-        return Category::Appearance;
-    }
+/// Use `overrides` on top of the embedded table from now on. Must be called before
+/// the first [`categorize_key`]; returns false, changing nothing, if it is too late.
+pub fn set_category_overrides(overrides: CategoryMap) -> bool {
+    let mut map = CategoryMap::embedded();
+    map.merge(overrides);
+    CATEGORIES.set(map).is_ok()
+}
 
-    Category::Advanced
+/// The category of a config key, from the embedded table plus any overrides.
+pub fn categorize_key(key: &str) -> Category {
+    CATEGORIES
+        .get_or_init(CategoryMap::embedded)
+        .category_of(key)
 }
 
 #[cfg(test)]
@@ -243,14 +197,15 @@ mod tests {
     #[test]
     fn test_appearance_keys() {
         assert_eq!(categorize_key("unfocused-split-fill"), Category::Appearance);
-        // split-color contains "color" so it matches Colors before Appearance
-        assert_eq!(categorize_key("split-color"), Category::Colors);
+        assert_eq!(categorize_key("split-divider-color"), Category::Appearance);
     }
 
     #[test]
     fn test_advanced_keys() {
-        // osc-color-report-format contains "color" so it matches Colors
-        assert_eq!(categorize_key("osc-color-report-format"), Category::Colors);
+        assert_eq!(
+            categorize_key("osc-color-report-format"),
+            Category::Advanced
+        );
         assert_eq!(categorize_key("custom-shader"), Category::Advanced);
         assert_eq!(categorize_key("image-storage-limit"), Category::Advanced);
     }
@@ -259,4 +214,36 @@ mod tests {
     fn test_unknown_key_defaults_to_advanced() {
         assert_eq!(categorize_key("totally-unknown-key"), Category::Advanced);
     }
+
+    #[test]
+    fn test_key_beats_prefix() {
+        // `focus-` is Appearance, but this one is about the mouse.
+        assert_eq!(categorize_key("focus-follows-mouse"), Category::Mouse);
+        assert_eq!(categorize_key("focus-other"), Category::Appearance);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut map = CategoryMap::embedded();
+        map.merge(
+            CategoryMap::parse(
+                "[terminal]\nkeys = [\"osc-color-report-format\"]\nprefixes = [\"font-synthetic\"]\n",
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            map.category_of("osc-color-report-format"),
+            Category::Terminal
+        );
+        // The longer prefix wins over `font-`.
+        assert_eq!(map.category_of("font-synthetic-style"), Category::Terminal);
+        assert_eq!(map.category_of("font-size"), Category::Fonts);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(CategoryMap::parse("[nope]\nkeys = [\"a\"]\n").is_err());
+        assert!(CategoryMap::parse("[fonts]\nkey = [\"a\"]\n").is_err());
+        assert!(CategoryMap::parse("not toml").is_err());
+    }
 }
@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+
+use super::custom_theme::normalize_hex;
+use crate::Error;
+
+/// Terminal configs whose color schemes can be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorFormat {
+    AlacrittyToml,
+    AlacrittyYaml,
+    Kitty,
+}
+
+impl ColorFormat {
+    /// Parse the format named in the import form; `auto` (or anything else) detects it.
+    pub fn parse(name: &str, text: &str) -> Result<Self, Error> {
+        match name.trim() {
+            "alacritty-toml" => Ok(ColorFormat::AlacrittyToml),
+            "alacritty-yaml" => Ok(ColorFormat::AlacrittyYaml),
+            "kitty" => Ok(ColorFormat::Kitty),
+            _ => Self::detect(text)
+                .ok_or_else(|| Error::Config("Could not tell which format this is".to_string())),
+        }
+    }
+
+    /// Guess the format from the text.
+    pub fn detect(text: &str) -> Option<Self> {
+        let lines: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .collect();
+        if lines.iter().any(|l| l.starts_with("[colors")) {
+            return Some(ColorFormat::AlacrittyToml);
+        }
+        if lines.iter().any(|l| l.starts_with("colors:")) {
+            return Some(ColorFormat::AlacrittyYaml);
+        }
+        let kitty_keys = ["foreground", "background", "cursor", "selection_background"];
+        if lines.iter().any(|l| {
+            let key = l.split_whitespace().next().unwrap_or("");
+            (key.starts_with("color") && key[5..].parse::<u8>().is_ok())
+                || kitty_keys.contains(&key)
+        }) {
+            return Some(ColorFormat::Kitty);
+        }
+        None
+    }
+}
+
+/// Colors read from another terminal's config, in Ghostty terms.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportedColors {
+    pub background: Option<String>,
+    pub foreground: Option<String>,
+    pub cursor_color: Option<String>,
+    pub cursor_text: Option<String>,
+    pub selection_background: Option<String>,
+    pub selection_foreground: Option<String>,
+    pub palette: [Option<String>; 16],
+}
+
+impl ImportedColors {
+    /// The Ghostty config entries for these colors, palette first as in theme files.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let mut entries: Vec<(String, String)> = self
+            .palette
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| Some(("palette".to_string(), format!("{}={}", i, c.as_ref()?))))
+            .collect();
+        let singles = [
+            ("background", &self.background),
+            ("foreground", &self.foreground),
+            ("cursor-color", &self.cursor_color),
+            ("cursor-text", &self.cursor_text),
+            ("selection-background", &self.selection_background),
+            ("selection-foreground", &self.selection_foreground),
+        ];
+        for (key, value) in singles {
+            if let Some(value) = value {
+                entries.push((key.to_string(), value.clone()));
+            }
+        }
+        entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    /// Render as a Ghostty theme file.
+    pub fn to_theme_file(&self) -> String {
+        self.entries()
+            .into_iter()
+            .map(|(key, value)| format!("{} = {}\n", key, value))
+            .collect()
+    }
+}
+
+pub(crate) const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Accept `#rrggbb`, `0xrrggbb`, and quoted forms of either.
+pub(crate) fn parse_color(value: &str) -> Option<String> {
+    let value = value.trim().trim_matches(['"', '\'']);
+    let value = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+    normalize_hex(value)
+}
+
+/// Drop a trailing `# comment` that isn't inside quotes.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('#', None) if i == 0 || line[..i].ends_with(char::is_whitespace) => {
+                return &line[..i];
+            }
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Map Alacritty's `section.key` color names onto Ghostty's.
+fn apply_alacritty(colors: &mut ImportedColors, section: &str, key: &str, value: &str) {
+    let Some(color) = parse_color(value) else {
+        return;
+    };
+    match (section, key) {
+        ("primary", "background") => colors.background = Some(color),
+        ("primary", "foreground") => colors.foreground = Some(color),
+        ("cursor", "cursor") => colors.cursor_color = Some(color),
+        ("cursor", "text") => colors.cursor_text = Some(color),
+        ("selection", "background") => colors.selection_background = Some(color),
+        ("selection", "text") => colors.selection_foreground = Some(color),
+        ("normal" | "bright", name) => {
+            if let Some(i) = ANSI_NAMES.iter().position(|n| *n == name) {
+                let offset = if section == "bright" { 8 } else { 0 };
+                colors.palette[i + offset] = Some(color);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Split `a = "x", b = 'y'` (the inside of a TOML inline table) into pairs.
+fn inline_table(body: &str) -> Vec<(&str, &str)> {
+    body.split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.trim(), v.trim()))
+        .collect()
+}
+
+fn parse_alacritty_toml(text: &str) -> ImportedColors {
+    let mut colors = ImportedColors::default();
+    let mut table = String::new();
+    for line in text.lines() {
+        let line = strip_comment(line).trim();
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            table = name.trim().to_string();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+
+        // `[colors.primary]` + `background = ...`, or `[colors]` + `primary = { ... }`
+        if let Some(section) = table.strip_prefix("colors.") {
+            apply_alacritty(&mut colors, section, key, value);
+        } else if table == "colors" {
+            if let Some(body) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+                for (k, v) in inline_table(body) {
+                    apply_alacritty(&mut colors, key, k, v);
+                }
+            } else if let Some((section, key)) = key.split_once('.') {
+                apply_alacritty(&mut colors, section, key, value);
+            }
+        }
+    }
+    colors
+}
+
+fn parse_alacritty_yaml(text: &str) -> ImportedColors {
+    let mut colors = ImportedColors::default();
+    // (indent, key) of each open mapping
+    let mut path: Vec<(usize, String)> = Vec::new();
+    for raw in text.lines() {
+        let line = strip_comment(raw);
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        while path.last().is_some_and(|(i, _)| *i >= indent) {
+            path.pop();
+        }
+        let value = value.trim();
+        if value.is_empty() {
+            path.push((indent, key.trim().to_string()));
+            continue;
+        }
+        if let [(_, colors_key), (_, section)] = path.as_slice() {
+            if colors_key == "colors" {
+                apply_alacritty(&mut colors, section, key.trim(), value);
+            }
+        }
+    }
+    colors
+}
+
+fn parse_kitty(text: &str) -> ImportedColors {
+    let mut colors = ImportedColors::default();
+    let values: HashMap<&str, &str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.starts_with('#'))
+        .filter_map(|l| l.split_once(char::is_whitespace))
+        .map(|(k, v)| (k, v.trim()))
+        .collect();
+    let color = |key: &str| values.get(key).and_then(|v| parse_color(v));
+
+    colors.background = color("background");
+    colors.foreground = color("foreground");
+    colors.cursor_color = color("cursor");
+    colors.cursor_text = color("cursor_text_color");
+    colors.selection_background = color("selection_background");
+    colors.selection_foreground = color("selection_foreground");
+    for (i, slot) in colors.palette.iter_mut().enumerate() {
+        *slot = color(&format!("color{}", i));
+    }
+    colors
+}
+
+/// Read the color scheme out of an Alacritty or Kitty config.
+pub fn import_colors(text: &str, format: ColorFormat) -> Result<ImportedColors, Error> {
+    let colors = match format {
+        ColorFormat::AlacrittyToml => parse_alacritty_toml(text),
+        ColorFormat::AlacrittyYaml => parse_alacritty_yaml(text),
+        ColorFormat::Kitty => parse_kitty(text),
+    };
+    if colors.is_empty() {
+        return Err(Error::Config("No colors found to import".to_string()));
+    }
+    Ok(colors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALACRITTY_TOML: &str = r##"
+[window]
+opacity = 0.9
+
+[colors.primary]
+background = "#1E1E2E" # base
+foreground = '0xcdd6f4'
+
+[colors.cursor]
+text = "#1e1e2e"
+cursor = "#f5e0dc"
+
+[colors.normal]
+black = "#45475a"
+red = "#f38ba8"
+
+[colors.bright]
+red = "#f38ba8"
+white = "#a6adc8"
+"##;
+
+    const ALACRITTY_YAML: &str = r##"
+colors:
+  primary:
+    background: '#1e1e2e'
+    foreground: '#cdd6f4'
+  selection:
+    text: '0x1e1e2e'
+    background: "#f5e0dc"   # rosewater
+  normal:
+    blue: '#89b4fa'
+font:
+  size: 12
+"##;
+
+    const KITTY: &str = "\
+# Catppuccin
+foreground              #CDD6F4
+background              #1E1E2E
+selection_background    #F5E0DC
+cursor                  #F5E0DC
+cursor_text_color       #1E1E2E
+color0                  #45475A
+color15                 #A6ADC8
+";
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            ColorFormat::detect(ALACRITTY_TOML),
+            Some(ColorFormat::AlacrittyToml)
+        );
+        assert_eq!(
+            ColorFormat::detect(ALACRITTY_YAML),
+            Some(ColorFormat::AlacrittyYaml)
+        );
+        assert_eq!(ColorFormat::detect(KITTY), Some(ColorFormat::Kitty));
+        assert_eq!(ColorFormat::detect("font-size = 12"), None);
+    }
+
+    #[test]
+    fn test_alacritty_toml() {
+        let colors = import_colors(ALACRITTY_TOML, ColorFormat::AlacrittyToml).unwrap();
+        assert_eq!(colors.background.as_deref(), Some("#1e1e2e"));
+        assert_eq!(colors.foreground.as_deref(), Some("#cdd6f4"));
+        assert_eq!(colors.cursor_color.as_deref(), Some("#f5e0dc"));
+        assert_eq!(colors.cursor_text.as_deref(), Some("#1e1e2e"));
+        assert_eq!(colors.palette[1].as_deref(), Some("#f38ba8"));
+        assert_eq!(colors.palette[9].as_deref(), Some("#f38ba8"));
+        assert_eq!(colors.palette[15].as_deref(), Some("#a6adc8"));
+        assert_eq!(colors.palette[2], None);
+    }
+
+    #[test]
+    fn test_alacritty_toml_inline_tables() {
+        let text = "[colors]\nprimary = { background = \"#000000\", foreground = \"#ffffff\" }\n";
+        let colors = import_colors(text, ColorFormat::AlacrittyToml).unwrap();
+        assert_eq!(colors.background.as_deref(), Some("#000000"));
+        assert_eq!(colors.foreground.as_deref(), Some("#ffffff"));
+    }
+
+    #[test]
+    fn test_alacritty_yaml() {
+        let colors = import_colors(ALACRITTY_YAML, ColorFormat::AlacrittyYaml).unwrap();
+        assert_eq!(colors.background.as_deref(), Some("#1e1e2e"));
+        assert_eq!(colors.selection_foreground.as_deref(), Some("#1e1e2e"));
+        assert_eq!(colors.selection_background.as_deref(), Some("#f5e0dc"));
+        assert_eq!(colors.palette[4].as_deref(), Some("#89b4fa"));
+    }
+
+    #[test]
+    fn test_kitty() {
+        let colors = import_colors(KITTY, ColorFormat::Kitty).unwrap();
+        assert_eq!(colors.foreground.as_deref(), Some("#cdd6f4"));
+        assert_eq!(colors.cursor_text.as_deref(), Some("#1e1e2e"));
+        assert_eq!(colors.palette[0].as_deref(), Some("#45475a"));
+        assert_eq!(colors.palette[15].as_deref(), Some("#a6adc8"));
+    }
+
+    #[test]
+    fn test_entries_and_theme_file() {
+        let colors = import_colors(KITTY, ColorFormat::Kitty).unwrap();
+        let file = colors.to_theme_file();
+        assert!(file.starts_with("palette = 0=#45475a\npalette = 15=#a6adc8\n"));
+        assert!(file.contains("background = #1e1e2e\n"));
+        assert!(file.contains("cursor-text = #1e1e2e\n"));
+    }
+
+    #[test]
+    fn test_nothing_to_import() {
+        assert!(import_colors("[window]\nopacity = 1\n", ColorFormat::AlacrittyToml).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+//! Color values as Ghostty reads them: hex, with or without alpha, or an X11 color name.
+
+use super::custom_theme::normalize_hex;
+
+/// X11 color names (from `rgb.txt`), lowercase without spaces. Where X11 and CSS
+/// disagree (`gray`, `green`, `maroon`, `purple`), X11 wins, as in Ghostty.
+const X11_COLORS: &[(&str, &str)] = &[
+    ("aliceblue", "#f0f8ff"),
+    ("antiquewhite", "#faebd7"),
+    ("aqua", "#00ffff"),
+    ("aquamarine", "#7fffd4"),
+    ("azure", "#f0ffff"),
+    ("beige", "#f5f5dc"),
+    ("bisque", "#ffe4c4"),
+    ("black", "#000000"),
+    ("blanchedalmond", "#ffebcd"),
+    ("blue", "#0000ff"),
+    ("blueviolet", "#8a2be2"),
+    ("brown", "#a52a2a"),
+    ("burlywood", "#deb887"),
+    ("cadetblue", "#5f9ea0"),
+    ("chartreuse", "#7fff00"),
+    ("chocolate", "#d2691e"),
+    ("coral", "#ff7f50"),
+    ("cornflowerblue", "#6495ed"),
+    ("cornsilk", "#fff8dc"),
+    ("crimson", "#dc143c"),
+    ("cyan", "#00ffff"),
+    ("darkblue", "#00008b"),
+    ("darkcyan", "#008b8b"),
+    ("darkgoldenrod", "#b8860b"),
+    ("darkgray", "#a9a9a9"),
+    ("darkgreen", "#006400"),
+    ("darkgrey", "#a9a9a9"),
+    ("darkkhaki", "#bdb76b"),
+    ("darkmagenta", "#8b008b"),
+    ("darkolivegreen", "#556b2f"),
+    ("darkorange", "#ff8c00"),
+    ("darkorchid", "#9932cc"),
+    ("darkred", "#8b0000"),
+    ("darksalmon", "#e9967a"),
+    ("darkseagreen", "#8fbc8f"),
+    ("darkslateblue", "#483d8b"),
+    ("darkslategray", "#2f4f4f"),
+    ("darkslategrey", "#2f4f4f"),
+    ("darkturquoise", "#00ced1"),
+    ("darkviolet", "#9400d3"),
+    ("deeppink", "#ff1493"),
+    ("deepskyblue", "#00bfff"),
+    ("dimgray", "#696969"),
+    ("dimgrey", "#696969"),
+    ("dodgerblue", "#1e90ff"),
+    ("firebrick", "#b22222"),
+    ("floralwhite", "#fffaf0"),
+    ("forestgreen", "#228b22"),
+    ("fuchsia", "#ff00ff"),
+    ("gainsboro", "#dcdcdc"),
+    ("ghostwhite", "#f8f8ff"),
+    ("gold", "#ffd700"),
+    ("goldenrod", "#daa520"),
+    ("gray", "#bebebe"),
+    ("green", "#00ff00"),
+    ("greenyellow", "#adff2f"),
+    ("grey", "#bebebe"),
+    ("honeydew", "#f0fff0"),
+    ("hotpink", "#ff69b4"),
+    ("indianred", "#cd5c5c"),
+    ("indigo", "#4b0082"),
+    ("ivory", "#fffff0"),
+    ("khaki", "#f0e68c"),
+    ("lavender", "#e6e6fa"),
+    ("lavenderblush", "#fff0f5"),
+    ("lawngreen", "#7cfc00"),
+    ("lemonchiffon", "#fffacd"),
+    ("lightblue", "#add8e6"),
+    ("lightcoral", "#f08080"),
+    ("lightcyan", "#e0ffff"),
+    ("lightgoldenrod", "#eedd82"),
+    ("lightgoldenrodyellow", "#fafad2"),
+    ("lightgray", "#d3d3d3"),
+    ("lightgreen", "#90ee90"),
+    ("lightgrey", "#d3d3d3"),
+    ("lightpink", "#ffb6c1"),
+    ("lightsalmon", "#ffa07a"),
+    ("lightseagreen", "#20b2aa"),
+    ("lightskyblue", "#87cefa"),
+    ("lightslateblue", "#8470ff"),
+    ("lightslategray", "#778899"),
+    ("lightslategrey", "#778899"),
+    ("lightsteelblue", "#b0c4de"),
+    ("lightyellow", "#ffffe0"),
+    ("lime", "#00ff00"),
+    ("limegreen", "#32cd32"),
+    ("linen", "#faf0e6"),
+    ("magenta", "#ff00ff"),
+    ("maroon", "#b03060"),
+    ("mediumaquamarine", "#66cdaa"),
+    ("mediumblue", "#0000cd"),
+    ("mediumorchid", "#ba55d3"),
+    ("mediumpurple", "#9370db"),
+    ("mediumseagreen", "#3cb371"),
+    ("mediumslateblue", "#7b68ee"),
+    ("mediumspringgreen", "#00fa9a"),
+    ("mediumturquoise", "#48d1cc"),
+    ("mediumvioletred", "#c71585"),
+    ("midnightblue", "#191970"),
+    ("mintcream", "#f5fffa"),
+    ("mistyrose", "#ffe4e1"),
+    ("moccasin", "#ffe4b5"),
+    ("navajowhite", "#ffdead"),
+    ("navy", "#000080"),
+    ("navyblue", "#000080"),
+    ("oldlace", "#fdf5e6"),
+    ("olive", "#808000"),
+    ("olivedrab", "#6b8e23"),
+    ("orange", "#ffa500"),
+    ("orangered", "#ff4500"),
+    ("orchid", "#da70d6"),
+    ("palegoldenrod", "#eee8aa"),
+    ("palegreen", "#98fb98"),
+    ("paleturquoise", "#afeeee"),
+    ("palevioletred", "#db7093"),
+    ("papayawhip", "#ffefd5"),
+    ("peachpuff", "#ffdab9"),
+    ("peru", "#cd853f"),
+    ("pink", "#ffc0cb"),
+    ("plum", "#dda0dd"),
+    ("powderblue", "#b0e0e6"),
+    ("purple", "#a020f0"),
+    ("rebeccapurple", "#663399"),
+    ("red", "#ff0000"),
+    ("rosybrown", "#bc8f8f"),
+    ("royalblue", "#4169e1"),
+    ("saddlebrown", "#8b4513"),
+    ("salmon", "#fa8072"),
+    ("sandybrown", "#f4a460"),
+    ("seagreen", "#2e8b57"),
+    ("seashell", "#fff5ee"),
+    ("sienna", "#a0522d"),
+    ("silver", "#c0c0c0"),
+    ("skyblue", "#87ceeb"),
+    ("slateblue", "#6a5acd"),
+    ("slategray", "#708090"),
+    ("slategrey", "#708090"),
+    ("snow", "#fffafa"),
+    ("springgreen", "#00ff7f"),
+    ("steelblue", "#4682b4"),
+    ("tan", "#d2b48c"),
+    ("teal", "#008080"),
+    ("thistle", "#d8bfd8"),
+    ("tomato", "#ff6347"),
+    ("turquoise", "#40e0d0"),
+    ("violet", "#ee82ee"),
+    ("violetred", "#d02090"),
+    ("webgray", "#808080"),
+    ("webgreen", "#008000"),
+    ("webgrey", "#808080"),
+    ("webmaroon", "#800000"),
+    ("webpurple", "#800080"),
+    ("wheat", "#f5deb3"),
+    ("white", "#ffffff"),
+    ("whitesmoke", "#f5f5f5"),
+    ("x11gray", "#bebebe"),
+    ("x11green", "#00ff00"),
+    ("x11grey", "#bebebe"),
+    ("x11maroon", "#b03060"),
+    ("x11purple", "#a020f0"),
+    ("yellow", "#ffff00"),
+    ("yellowgreen", "#9acd32"),
+];
+
+/// Values some color options take instead of a color, such as `cursor-color`
+/// following the text under the cursor.
+pub const COLOR_KEYWORDS: &[&str] = &["cell-foreground", "cell-background"];
+
+/// The `#rrggbb` of an X11 color name. Case and spaces don't matter, so
+/// `Light Steel Blue` and `lightsteelblue` are the same; `gray0`–`gray100`
+/// are the X11 shades of gray.
+pub fn x11_color(name: &str) -> Option<String> {
+    let name: String = name
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if let Some((_, hex)) = X11_COLORS.iter().find(|(n, _)| *n == name) {
+        return Some(hex.to_string());
+    }
+    let percent: u32 = name
+        .strip_prefix("gray")
+        .or_else(|| name.strip_prefix("grey"))?
+        .parse()
+        .ok()
+        .filter(|p| *p <= 100)?;
+    let level = (percent * 255 + 50) / 100;
+    Some(format!("#{0:02x}{0:02x}{0:02x}", level))
+}
+
+/// A color value as lowercase `#rrggbb`, or `#rrggbbaa` when it has alpha.
+///
+/// Takes hex in the short, long, or alpha form, with or without the `#`, or an
+/// X11 color name. Keywords like `cell-foreground` aren't colors.
+pub fn resolve_color(value: &str) -> Option<String> {
+    let value = value.trim();
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    let is_hex = !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit());
+    match hex.len() {
+        3 if is_hex => {
+            let long: String = hex.chars().flat_map(|c| [c, c]).collect();
+            return normalize_hex(&long);
+        }
+        6 if is_hex => return normalize_hex(hex),
+        8 if is_hex => return Some(format!("#{}", hex.to_ascii_lowercase())),
+        _ => {}
+    }
+    if is_hex || value.starts_with('#') {
+        return None;
+    }
+    x11_color(value)
+}
+
+/// The `#rrggbb` to show in a color picker for a color value, without its alpha.
+pub fn picker_hex(value: &str) -> Option<String> {
+    resolve_color(value).map(|hex| hex[..7].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_color() {
+        assert_eq!(resolve_color("#1D1F21"), Some("#1d1f21".to_string()));
+        assert_eq!(resolve_color("1d1f21"), Some("#1d1f21".to_string()));
+        assert_eq!(resolve_color("#fff"), Some("#ffffff".to_string()));
+        assert_eq!(resolve_color("#1d1f21cc"), Some("#1d1f21cc".to_string()));
+        assert_eq!(
+            resolve_color("Light Steel Blue"),
+            Some("#b0c4de".to_string())
+        );
+        assert_eq!(resolve_color("purple"), Some("#a020f0".to_string()));
+        assert_eq!(resolve_color("gray50"), Some("#808080".to_string()));
+        assert_eq!(resolve_color("grey100"), Some("#ffffff".to_string()));
+        for bad in [
+            "#12",
+            "#zzzzzz",
+            "123456789",
+            "gray101",
+            "notacolor",
+            "cell-foreground",
+        ] {
+            assert_eq!(resolve_color(bad), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_picker_hex() {
+        assert_eq!(picker_hex("#1d1f21cc"), Some("#1d1f21".to_string()));
+        assert_eq!(picker_hex("navy"), Some("#000080".to_string()));
+        assert_eq!(picker_hex("cell-background"), None);
+    }
+}
@@ -0,0 +1,225 @@
+//! Simulating color vision deficiencies, to check that a palette's colors stay
+//! distinguishable for people who see fewer hues.
+
+use super::color_names::resolve_color;
+use super::custom_theme::{hex_to_rgb, PALETTE_NAMES};
+
+type Rgb = (u8, u8, u8);
+
+/// Accent colors closer than this after simulation (CIE76 ΔE) are hard to tell apart.
+const MIN_DISTINCT: f64 = 15.0;
+
+/// The slots compared with each other: the normal accents, then the bright ones.
+const ACCENT_GROUPS: [std::ops::RangeInclusive<usize>; 2] = [1..=6, 9..=14];
+
+/// A color vision deficiency the preview can simulate, each the complete loss of
+/// one kind of cone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorVision {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl ColorVision {
+    pub const ALL: [ColorVision; 3] = [
+        ColorVision::Protanopia,
+        ColorVision::Deuteranopia,
+        ColorVision::Tritanopia,
+    ];
+
+    pub fn slug(self) -> &'static str {
+        match self {
+            ColorVision::Protanopia => "protanopia",
+            ColorVision::Deuteranopia => "deuteranopia",
+            ColorVision::Tritanopia => "tritanopia",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorVision::Protanopia => "Protanopia (red-blind)",
+            ColorVision::Deuteranopia => "Deuteranopia (green-blind)",
+            ColorVision::Tritanopia => "Tritanopia (blue-blind)",
+        }
+    }
+
+    pub fn parse(slug: &str) -> Option<ColorVision> {
+        ColorVision::ALL.into_iter().find(|v| v.slug() == slug)
+    }
+
+    /// Machado, Oliveira & Fernandes (2009) at full severity, on linear RGB.
+    fn matrix(self) -> [[f64; 3]; 3] {
+        match self {
+            ColorVision::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            ColorVision::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            ColorVision::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+}
+
+/// How `color` looks with `vision`.
+pub fn simulate(color: Rgb, vision: ColorVision) -> Rgb {
+    let linear = to_linear(color);
+    let m = vision.matrix();
+    let row = |r: [f64; 3]| r[0] * linear[0] + r[1] * linear[1] + r[2] * linear[2];
+    from_linear([row(m[0]), row(m[1]), row(m[2])])
+}
+
+/// A color value as it looks with `vision`, as `#rrggbb` (keeping any alpha), or
+/// `None` for values that aren't colors.
+pub fn simulate_value(value: &str, vision: ColorVision) -> Option<String> {
+    let hex = resolve_color(value)?;
+    let (r, g, b) = simulate(hex_to_rgb(&hex[..7])?, vision);
+    Some(format!("#{:02x}{:02x}{:02x}{}", r, g, b, &hex[7..]))
+}
+
+/// Pairs of ANSI accents (among the normal ones, and among the bright ones) that
+/// are distinct in `palette` but look alike with `vision`, e.g. red and green.
+pub fn confusable_pairs(palette: &[String], vision: ColorVision) -> Vec<(usize, usize)> {
+    let lab = |index: usize| {
+        palette
+            .get(index)
+            .and_then(|c| resolve_color(c))
+            .and_then(|hex| hex_to_rgb(&hex[..7]))
+            .map(|rgb| (to_lab(rgb), to_lab(simulate(rgb, vision))))
+    };
+    let mut pairs = Vec::new();
+    for group in ACCENT_GROUPS {
+        for a in group.clone() {
+            for b in (a + 1)..=*group.end() {
+                let (Some((a_lab, a_seen)), Some((b_lab, b_seen))) = (lab(a), lab(b)) else {
+                    continue;
+                };
+                if delta_e(a_lab, b_lab) >= MIN_DISTINCT && delta_e(a_seen, b_seen) < MIN_DISTINCT {
+                    pairs.push((a, b));
+                }
+            }
+        }
+    }
+    pairs
+}
+
+/// "Red and green, Bright red and bright green", for messages.
+pub fn describe_pairs(pairs: &[(usize, usize)]) -> String {
+    pairs
+        .iter()
+        .map(|&(a, b)| {
+            format!(
+                "{} and {}",
+                PALETTE_NAMES[a],
+                PALETTE_NAMES[b].to_lowercase()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn to_linear((r, g, b): Rgb) -> [f64; 3] {
+    [r, g, b].map(|c| {
+        let c = c as f64 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    })
+}
+
+fn from_linear(channels: [f64; 3]) -> Rgb {
+    let [r, g, b] = channels.map(|c| {
+        let c = c.clamp(0.0, 1.0);
+        let c = if c <= 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        };
+        (c * 255.0).round() as u8
+    });
+    (r, g, b)
+}
+
+/// CIE L*a*b* under D65.
+fn to_lab(color: Rgb) -> [f64; 3] {
+    let [r, g, b] = to_linear(color);
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+    let f = |t: f64| {
+        if t > 0.008856 {
+            t.cbrt()
+        } else {
+            7.787 * t + 16.0 / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+fn delta_e(a: [f64; 3], b: [f64; 3]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulate() {
+        // Greys look the same to everyone.
+        for vision in ColorVision::ALL {
+            assert_eq!(simulate((128, 128, 128), vision), (128, 128, 128));
+            assert_eq!(simulate((255, 255, 255), vision), (255, 255, 255));
+        }
+        // Without red cones, pure red is a dark olive.
+        let (r, g, b) = simulate((255, 0, 0), ColorVision::Protanopia);
+        assert!(r < 120 && g < 120 && b < 20, "{:?}", (r, g, b));
+
+        assert_eq!(
+            simulate_value("#ffffff80", ColorVision::Deuteranopia).as_deref(),
+            Some("#ffffff80")
+        );
+        assert_eq!(
+            simulate_value("cell-foreground", ColorVision::Tritanopia),
+            None
+        );
+        assert_eq!(
+            ColorVision::parse("tritanopia"),
+            Some(ColorVision::Tritanopia)
+        );
+        assert_eq!(ColorVision::parse("red"), None);
+    }
+
+    #[test]
+    fn test_confusable_pairs() {
+        // Gruvbox Dark, whose red and green are close in lightness.
+        let palette: Vec<String> = [
+            "#282828", "#cc241d", "#98971a", "#d79921", "#458588", "#b16286", "#689d6a", "#a89984",
+            "#928374", "#fb4934", "#b8bb26", "#fabd2f", "#83a598", "#d3869b", "#8ec07c", "#ebdbb2",
+        ]
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+        let pairs = confusable_pairs(&palette, ColorVision::Deuteranopia);
+        assert!(pairs.contains(&(1, 2)), "{:?}", pairs);
+        assert!(pairs.iter().all(|&(a, b)| a < b && (a < 8) == (b < 8)));
+        assert!(describe_pairs(&pairs).starts_with("Red and green"));
+
+        // Colors that already look alike aren't reported.
+        let mut same = palette.clone();
+        same[2] = same[1].clone();
+        assert!(!confusable_pairs(&same, ColorVision::Deuteranopia).contains(&(1, 2)));
+    }
+}
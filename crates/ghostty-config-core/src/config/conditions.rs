@@ -0,0 +1,349 @@
+//! When an option has an effect: the platforms it works on and the other options
+//! it depends on, read from the embedded `conditions.toml`.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use super::model::ConfigOption;
+use super::os_shortcuts::Platform;
+use crate::Error;
+
+/// The condition table that ships with this crate.
+const EMBEDDED: &str = include_str!("conditions.toml");
+
+/// What an option needs to have an effect. Empty for most options.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OptionConditions {
+    /// The platforms it works on; empty for all of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platforms: Vec<Platform>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requires: Option<Requirement>,
+}
+
+impl OptionConditions {
+    /// Whether the option always has an effect.
+    pub fn is_empty(&self) -> bool {
+        self.platforms.is_empty() && self.requires.is_none()
+    }
+}
+
+/// Another option that must be set, or set to certain values, e.g.
+/// `background-blur-radius` needs a `background-opacity` other than 1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Requirement {
+    pub key: String,
+    /// The value must be one of these.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+    /// The value must be none of these.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not: Vec<String>,
+}
+
+impl Requirement {
+    /// Whether `value`, the required key's value or default, meets this.
+    pub fn is_met(&self, value: &str) -> bool {
+        let value = value.trim();
+        if !self.values.is_empty() {
+            self.values.iter().any(|v| same_value(v, value))
+        } else if !self.not.is_empty() {
+            !self.not.iter().any(|v| same_value(v, value))
+        } else {
+            !value.is_empty()
+        }
+    }
+
+    /// "when `font-thicken` is true", for explanations.
+    fn describe(&self) -> String {
+        if !self.values.is_empty() {
+            format!("when {} is {}", self.key, self.values.join(" or "))
+        } else if !self.not.is_empty() {
+            format!("when {} isn't {}", self.key, self.not.join(" or "))
+        } else {
+            format!("when {} is set", self.key)
+        }
+    }
+}
+
+fn same_value(a: &str, b: &str) -> bool {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlatformSection {
+    #[serde(default)]
+    keys: Vec<String>,
+    #[serde(default)]
+    prefixes: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConditionFile {
+    #[serde(default)]
+    platforms: HashMap<String, PlatformSection>,
+    #[serde(default)]
+    requires: HashMap<String, Requirement>,
+}
+
+/// Every option's conditions, read from a `conditions.toml` table.
+#[derive(Debug, Default)]
+pub struct ConditionMap {
+    platform_keys: Vec<(Platform, String)>,
+    platform_prefixes: Vec<(Platform, String)>,
+    requires: HashMap<String, Requirement>,
+}
+
+impl ConditionMap {
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let file: ConditionFile = basic_toml::from_str(text)
+            .map_err(|e| Error::Config(format!("Invalid condition table: {}", e)))?;
+        let mut map = ConditionMap {
+            requires: file.requires,
+            ..Default::default()
+        };
+        for (slug, section) in file.platforms {
+            let platform = Platform::ALL
+                .into_iter()
+                .find(|p| p.slug() == slug)
+                .ok_or_else(|| Error::Config(format!("Unknown platform `{}`", slug)))?;
+            map.platform_keys
+                .extend(section.keys.into_iter().map(|k| (platform, k)));
+            map.platform_prefixes
+                .extend(section.prefixes.into_iter().map(|p| (platform, p)));
+        }
+        Ok(map)
+    }
+
+    /// The table that ships with this crate.
+    pub fn embedded() -> Self {
+        Self::parse(EMBEDDED).expect("embedded conditions.toml is valid")
+    }
+
+    pub fn conditions_of(&self, key: &str) -> OptionConditions {
+        let mut platforms: Vec<Platform> = self
+            .platform_keys
+            .iter()
+            .filter(|(_, k)| k == key)
+            .chain(
+                self.platform_prefixes
+                    .iter()
+                    .filter(|(_, prefix)| key.starts_with(prefix.as_str())),
+            )
+            .map(|&(platform, _)| platform)
+            .collect();
+        platforms.sort_by_key(|p| p.display_name());
+        platforms.dedup();
+        OptionConditions {
+            platforms,
+            requires: self.requires.get(key).cloned(),
+        }
+    }
+}
+
+static CONDITIONS: OnceLock<ConditionMap> = OnceLock::new();
+
+/// The conditions of a config key, from the embedded table. An option the table
+/// doesn't tie to a platform gets the one its `documentation` names, if any.
+pub fn conditions_of(key: &str, documentation: &str) -> OptionConditions {
+    let mut conditions = CONDITIONS
+        .get_or_init(ConditionMap::embedded)
+        .conditions_of(key);
+    if conditions.platforms.is_empty() {
+        conditions.platforms = platforms_from_docs(documentation);
+    }
+    conditions
+}
+
+/// The platform an option's docs restrict it to, from a note such as "macOS only."
+/// or "Only supported on Linux" in their first paragraph. Later paragraphs are
+/// left alone: they describe individual values, e.g. a `window-decoration` choice
+/// that is Linux only.
+pub fn platforms_from_docs(documentation: &str) -> Vec<Platform> {
+    let summary = documentation
+        .split("\n\n")
+        .next()
+        .unwrap_or_default()
+        .replace('\n', " ")
+        .to_lowercase();
+    let names = |platform: Platform| match platform {
+        Platform::MacOS => &["macos"][..],
+        Platform::Linux => &["linux", "gtk"][..],
+    };
+    let platforms: Vec<Platform> = Platform::ALL
+        .into_iter()
+        .filter(|&platform| {
+            names(platform).iter().any(|name| {
+                summary.contains(&format!("{} only", name))
+                    || [
+                        "only on",
+                        "only supported on",
+                        "only applies to",
+                        "only available on",
+                    ]
+                    .iter()
+                    .any(|phrase| summary.contains(&format!("{} {}", phrase, name)))
+            })
+        })
+        .collect();
+    // A note naming both doesn't restrict anything.
+    if platforms.len() == 1 {
+        platforms
+    } else {
+        Vec::new()
+    }
+}
+
+/// Why `option` has no effect on `platform` with the config as it stands, or `None`
+/// when it does. `value_of` gives another option's value, or its default when unset.
+pub fn unmet_condition(
+    option: &ConfigOption,
+    platform: Platform,
+    value_of: impl Fn(&str) -> String,
+) -> Option<String> {
+    let conditions = &option.conditions;
+    if !conditions.platforms.is_empty() && !conditions.platforms.contains(&platform) {
+        let names: Vec<&str> = conditions
+            .platforms
+            .iter()
+            .map(|p| p.display_name())
+            .collect();
+        return Some(format!("{} only", names.join(" and ")));
+    }
+    let requirement = conditions.requires.as_ref()?;
+    (!requirement.is_met(&value_of(&requirement.key)))
+        .then(|| format!("Only has an effect {}", requirement.describe()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::snapshot::snapshot;
+
+    #[test]
+    fn test_conditions_of() {
+        let blur = conditions_of("background-blur-radius", "");
+        assert!(blur.platforms.is_empty());
+        assert_eq!(blur.requires.unwrap().key, "background-opacity");
+        assert_eq!(
+            conditions_of("macos-titlebar-style", "").platforms,
+            [Platform::MacOS]
+        );
+        assert_eq!(
+            conditions_of("gtk-adwaita", "").platforms,
+            [Platform::Linux]
+        );
+        assert_eq!(conditions_of("font-size", ""), OptionConditions::default());
+
+        // Every key in the table is a real option, and so is every key it requires.
+        let schema = snapshot(None).unwrap().schema().unwrap();
+        let map = ConditionMap::embedded();
+        let keys = map
+            .platform_keys
+            .iter()
+            .map(|(_, k)| k)
+            .chain(map.requires.keys())
+            .chain(map.requires.values().map(|r| &r.key));
+        for key in keys {
+            assert!(schema.find_option(key).is_some(), "{}", key);
+        }
+        assert_eq!(
+            schema
+                .find_option("macos-icon")
+                .unwrap()
+                .conditions
+                .platforms,
+            [Platform::MacOS]
+        );
+    }
+
+    #[test]
+    fn test_platforms_from_docs() {
+        assert_eq!(
+            platforms_from_docs("Draw fonts with a thicker stroke. macOS only."),
+            [Platform::MacOS]
+        );
+        assert_eq!(
+            platforms_from_docs("Use libadwaita.\nThis is only supported on\nLinux."),
+            [Platform::Linux]
+        );
+        assert_eq!(
+            platforms_from_docs("Show the GTK titlebar. GTK only."),
+            [Platform::Linux]
+        );
+        // Not a platform of its own, and not just one either.
+        assert!(platforms_from_docs("Blur behind the window. macOS and KDE only.").is_empty());
+        assert!(platforms_from_docs("Works on macOS only or Linux only.").is_empty());
+        // A note on one of the values isn't about the option.
+        assert!(platforms_from_docs(
+            "Window decorations.\n\n  * `client` - Draw them in Ghostty. Linux only."
+        )
+        .is_empty());
+        assert_eq!(
+            conditions_of("some-new-key", "Resize in steps. Only available on macOS.").platforms,
+            [Platform::MacOS]
+        );
+    }
+
+    #[test]
+    fn test_unmet_condition() {
+        let schema = snapshot(None).unwrap().schema().unwrap();
+        let option = |key: &str| schema.find_option(key).unwrap();
+        let defaults = |key: &str| schema.find_option(key).unwrap().default_value.clone();
+
+        assert_eq!(
+            unmet_condition(option("background-blur-radius"), Platform::Linux, defaults).as_deref(),
+            Some("Only has an effect when background-opacity isn't 1")
+        );
+        let translucent = |key: &str| match key {
+            "background-opacity" => "0.9".to_string(),
+            _ => defaults(key),
+        };
+        assert_eq!(
+            unmet_condition(
+                option("background-blur-radius"),
+                Platform::Linux,
+                translucent
+            ),
+            None
+        );
+        let opaque = |key: &str| match key {
+            "background-opacity" => "1.0".to_string(),
+            _ => defaults(key),
+        };
+        assert!(
+            unmet_condition(option("background-blur-radius"), Platform::Linux, opaque).is_some()
+        );
+
+        assert_eq!(
+            unmet_condition(option("custom-shader-animation"), Platform::Linux, defaults)
+                .as_deref(),
+            Some("Only has an effect when custom-shader is set")
+        );
+        assert_eq!(
+            unmet_condition(option("gtk-titlebar"), Platform::MacOS, defaults).as_deref(),
+            Some("Linux desktop only")
+        );
+        assert_eq!(
+            unmet_condition(option("gtk-titlebar"), Platform::Linux, defaults),
+            None
+        );
+        // The platform is explained before the option it depends on.
+        assert_eq!(
+            unmet_condition(option("font-thicken-strength"), Platform::Linux, defaults).as_deref(),
+            Some("macOS only")
+        );
+        assert_eq!(
+            unmet_condition(option("font-thicken-strength"), Platform::MacOS, defaults).as_deref(),
+            Some("Only has an effect when font-thicken is true")
+        );
+    }
+}
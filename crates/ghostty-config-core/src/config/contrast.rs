@@ -0,0 +1,186 @@
+//! WCAG contrast checks for themes: the foreground and each palette color against
+//! the background.
+
+use serde::Serialize;
+
+use super::custom_theme::{hex_to_rgb, PALETTE_NAMES};
+use super::model::ThemeInfo;
+
+/// WCAG AA minimum for body text.
+pub const AA_NORMAL: f64 = 4.5;
+/// WCAG AA minimum for large or bold text.
+pub const AA_LARGE: f64 = 3.0;
+/// WCAG AAA minimum for body text.
+pub const AAA_NORMAL: f64 = 7.0;
+
+/// The best WCAG level a contrast ratio meets for text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum ContrastLevel {
+    Aaa,
+    Aa,
+    /// Only readable as large or bold text.
+    AaLarge,
+    Fail,
+}
+
+impl ContrastLevel {
+    pub fn of(ratio: f64) -> ContrastLevel {
+        if ratio >= AAA_NORMAL {
+            ContrastLevel::Aaa
+        } else if ratio >= AA_NORMAL {
+            ContrastLevel::Aa
+        } else if ratio >= AA_LARGE {
+            ContrastLevel::AaLarge
+        } else {
+            ContrastLevel::Fail
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ContrastLevel::Aaa => "AAA",
+            ContrastLevel::Aa => "AA",
+            ContrastLevel::AaLarge => "AA large text only",
+            ContrastLevel::Fail => "Fails",
+        }
+    }
+
+    /// Whether text in this color is readable at normal size.
+    pub fn passes_aa(self) -> bool {
+        matches!(self, ContrastLevel::Aaa | ContrastLevel::Aa)
+    }
+}
+
+/// One color checked against the theme background.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ContrastCheck {
+    /// "Foreground" or the palette slot's name, e.g. "Bright black".
+    pub label: String,
+    pub color: String,
+    /// Rounded to two decimals.
+    pub ratio: f64,
+    pub level: ContrastLevel,
+    /// The palette slot that conventionally matches the background: black on a
+    /// dark theme, white and bright white on a light one. Reported, but not a
+    /// problem when it fails.
+    pub background_shade: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ContrastReport {
+    pub theme: String,
+    pub background: String,
+    pub checks: Vec<ContrastCheck>,
+}
+
+impl ContrastReport {
+    /// Checks below AA that readers would actually see as text.
+    pub fn warnings(&self) -> impl Iterator<Item = &ContrastCheck> {
+        self.checks
+            .iter()
+            .filter(|c| !c.level.passes_aa() && !c.background_shade)
+    }
+}
+
+/// Check the foreground and every set palette color against the background.
+/// Colors that aren't valid hex are skipped.
+pub fn theme_contrast(theme: &ThemeInfo) -> ContrastReport {
+    let mut checks = Vec::new();
+    if let Some(bg) = hex_to_rgb(&theme.background) {
+        let colors = std::iter::once(("Foreground", &theme.foreground, false)).chain(
+            theme.palette.iter().take(16).enumerate().map(|(i, color)| {
+                let shade = if theme.is_dark {
+                    i == 0
+                } else {
+                    i == 7 || i == 15
+                };
+                (PALETTE_NAMES[i], color, shade)
+            }),
+        );
+        for (label, color, background_shade) in colors {
+            let Some(rgb) = hex_to_rgb(color) else {
+                continue;
+            };
+            let ratio = contrast_ratio(rgb, bg);
+            checks.push(ContrastCheck {
+                label: label.to_string(),
+                color: color.clone(),
+                ratio: (ratio * 100.0).round() / 100.0,
+                level: ContrastLevel::of(ratio),
+                background_shade,
+            });
+        }
+    }
+    ContrastReport {
+        theme: theme.name.clone(),
+        background: theme.background.clone(),
+        checks,
+    }
+}
+
+/// WCAG relative luminance.
+fn luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let linear = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// WCAG contrast ratio, from 1 (identical) to 21 (black on white).
+pub fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((contrast_ratio((0, 0, 0), (255, 255, 255)) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio((119, 119, 119), (255, 255, 255)) - 4.48).abs() < 0.01);
+        assert_eq!(ContrastLevel::of(21.0), ContrastLevel::Aaa);
+        assert_eq!(ContrastLevel::of(4.5), ContrastLevel::Aa);
+        assert_eq!(ContrastLevel::of(3.2), ContrastLevel::AaLarge);
+        assert_eq!(ContrastLevel::of(1.2), ContrastLevel::Fail);
+    }
+
+    #[test]
+    fn test_theme_contrast() {
+        let mut palette = vec![String::new(); 16];
+        palette[0] = "#111111".to_string();
+        palette[1] = "#550000".to_string();
+        palette[2] = "#50fa7b".to_string();
+        palette[3] = "not a color".to_string();
+        let theme = ThemeInfo {
+            name: "Dim".to_string(),
+            background: "#000000".to_string(),
+            foreground: "#777777".to_string(),
+            palette,
+            is_dark: true,
+            cursor_color: None,
+            selection_background: None,
+            custom: false,
+            origin: Default::default(),
+        };
+        let report = theme_contrast(&theme);
+        let labels: Vec<_> = report.checks.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, vec!["Foreground", "Black", "Red", "Green"]);
+        assert_eq!(report.checks[0].level, ContrastLevel::Aa);
+        assert!(report.checks[1].background_shade);
+
+        // Black on black is expected; dark red on black is not.
+        let warnings: Vec<_> = report.warnings().map(|c| c.label.as_str()).collect();
+        assert_eq!(warnings, vec!["Red"]);
+    }
+}
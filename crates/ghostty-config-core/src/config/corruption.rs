@@ -0,0 +1,107 @@
+use std::path::{Path, PathBuf};
+
+/// Something about a config file that means it isn't a config file any more.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorruptionProblem {
+    /// 1-based line, when the problem has one.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Lines git and other merge tools leave behind in a conflicted file.
+const CONFLICT_MARKERS: &[&str] = &["<<<<<<<", "|||||||", "=======", ">>>>>>>"];
+
+/// Look for damage the line parser would silently accept: binary content, text
+/// that isn't UTF-8, and unresolved merge-conflict markers.
+///
+/// Ordinary mistakes like unknown keys are left to `ghostty +validate-config`.
+pub fn detect_corruption(bytes: &[u8]) -> Vec<CorruptionProblem> {
+    let mut problems = Vec::new();
+
+    if let Some(pos) = bytes.iter().position(|&b| b == 0) {
+        problems.push(CorruptionProblem {
+            line: Some(line_at(bytes, pos)),
+            message: "The file contains binary data".to_string(),
+        });
+    } else if let Err(e) = std::str::from_utf8(bytes) {
+        problems.push(CorruptionProblem {
+            line: Some(line_at(bytes, e.valid_up_to())),
+            message: "The file is not valid UTF-8 text".to_string(),
+        });
+    }
+
+    let text = String::from_utf8_lossy(bytes);
+    for (index, line) in text.lines().enumerate() {
+        if let Some(marker) = CONFLICT_MARKERS
+            .iter()
+            .find(|m| line.starts_with(*m) && !line[m.len()..].starts_with(['<', '|', '=', '>']))
+        {
+            problems.push(CorruptionProblem {
+                line: Some(index + 1),
+                message: format!("Unresolved merge conflict marker `{}`", marker),
+            });
+        }
+    }
+
+    problems
+}
+
+fn line_at(bytes: &[u8], pos: usize) -> usize {
+    bytes[..pos].iter().filter(|&&b| b == b'\n').count() + 1
+}
+
+/// Copies of `config_path` that editors and tools commonly leave next to it,
+/// such as `config.bak` or `config~`, that exist on disk.
+pub fn backup_candidates(config_path: &Path) -> Vec<PathBuf> {
+    let Some(name) = config_path.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+    [".bak", ".backup", ".orig", ".old", "~"]
+        .iter()
+        .map(|suffix| config_path.with_file_name(format!("{}{}", name, suffix)))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_file() {
+        let text = "# ===== Fonts =====\nfont-size = 14\nkeybind = ctrl+shift+equal=increase_font_size:1\n";
+        assert!(detect_corruption(text.as_bytes()).is_empty());
+    }
+
+    #[test]
+    fn test_merge_markers() {
+        let text = "font-size = 14\n<<<<<<< HEAD\ntheme = a\n=======\ntheme = b\n>>>>>>> main\n";
+        let lines: Vec<Option<usize>> = detect_corruption(text.as_bytes())
+            .into_iter()
+            .map(|p| p.line)
+            .collect();
+        assert_eq!(lines, vec![Some(2), Some(4), Some(6)]);
+    }
+
+    #[test]
+    fn test_binary_content() {
+        let problems = detect_corruption(b"font-size = 14\n\x00\x01\x02");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(2));
+
+        let problems = detect_corruption(b"theme = \xff\xfe\n");
+        assert_eq!(problems[0].message, "The file is not valid UTF-8 text");
+    }
+
+    #[test]
+    fn test_backup_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config");
+        std::fs::write(dir.path().join("config.bak"), "font-size = 12\n").unwrap();
+        std::fs::write(dir.path().join("other.bak"), "").unwrap();
+        assert_eq!(
+            backup_candidates(&config),
+            vec![dir.path().join("config.bak")]
+        );
+    }
+}
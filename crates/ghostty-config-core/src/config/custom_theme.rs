@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use super::model::{ThemeInfo, ThemeOrigin};
+use crate::cli::themes::is_dark_color;
+use crate::Error;
+
+/// Conventional names of the 16 ANSI palette slots, for editor labels.
+pub const PALETTE_NAMES: [&str; 16] = [
+    "Black",
+    "Red",
+    "Green",
+    "Yellow",
+    "Blue",
+    "Magenta",
+    "Cyan",
+    "White",
+    "Bright black",
+    "Bright red",
+    "Bright green",
+    "Bright yellow",
+    "Bright blue",
+    "Bright magenta",
+    "Bright cyan",
+    "Bright white",
+];
+
+/// Normalize a hex color to lowercase `#rrggbb`, accepting it with or without the `#`.
+pub fn normalize_hex(value: &str) -> Option<String> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(format!("#{}", hex.to_ascii_lowercase()))
+    } else {
+        None
+    }
+}
+
+/// Red, green, and blue channels of a hex color, with or without the `#`.
+pub fn hex_to_rgb(value: &str) -> Option<(u8, u8, u8)> {
+    let hex = normalize_hex(value)?;
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(1)?, channel(3)?, channel(5)?))
+}
+
+fn required_color(fields: &HashMap<String, String>, key: &str) -> Result<String, Error> {
+    optional_color(fields, key)?.ok_or_else(|| Error::Config(format!("{} is required", key)))
+}
+
+fn optional_color(fields: &HashMap<String, String>, key: &str) -> Result<Option<String>, Error> {
+    match fields.get(key).map(|v| v.trim()).filter(|v| !v.is_empty()) {
+        None => Ok(None),
+        Some(value) => normalize_hex(value)
+            .map(Some)
+            .ok_or_else(|| Error::Config(format!("{} is not a hex color: '{}'", key, value))),
+    }
+}
+
+/// Build a theme from the editor form.
+///
+/// Fields use the theme file keys (`background`, `cursor-color`, ...) and
+/// `palette-0` through `palette-15`. Background and foreground are required.
+pub fn theme_from_fields(name: &str, fields: &HashMap<String, String>) -> Result<ThemeInfo, Error> {
+    let background = required_color(fields, "background")?;
+    let foreground = required_color(fields, "foreground")?;
+    let palette = (0..16)
+        .map(|i| optional_color(fields, &format!("palette-{}", i)).map(Option::unwrap_or_default))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ThemeInfo {
+        name: name.trim().to_string(),
+        is_dark: is_dark_color(&background),
+        background,
+        foreground,
+        palette,
+        cursor_color: optional_color(fields, "cursor-color")?,
+        selection_background: optional_color(fields, "selection-background")?,
+        custom: true,
+        origin: ThemeOrigin::User,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize_hex() {
+        assert_eq!(normalize_hex("#AABBCC").as_deref(), Some("#aabbcc"));
+        assert_eq!(normalize_hex(" 112233 ").as_deref(), Some("#112233"));
+        assert_eq!(normalize_hex("#abc"), None);
+        assert_eq!(normalize_hex("red"), None);
+        assert_eq!(hex_to_rgb("#ff8000"), Some((255, 128, 0)));
+        assert_eq!(hex_to_rgb("nope"), None);
+    }
+
+    #[test]
+    fn test_theme_from_fields() {
+        let theme = theme_from_fields(
+            " Midnight ",
+            &fields(&[
+                ("background", "#101020"),
+                ("foreground", "#E0E0E0"),
+                ("palette-1", "#ff5555"),
+                ("cursor-color", ""),
+                ("selection-background", "#333344"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(theme.name, "Midnight");
+        assert_eq!(theme.foreground, "#e0e0e0");
+        assert!(theme.is_dark);
+        assert!(theme.custom);
+        assert_eq!(theme.palette.len(), 16);
+        assert_eq!(theme.palette[1], "#ff5555");
+        assert_eq!(theme.palette[0], "");
+        assert_eq!(theme.cursor_color, None);
+        assert_eq!(theme.selection_background.as_deref(), Some("#333344"));
+    }
+
+    #[test]
+    fn test_theme_from_fields_rejects_bad_colors() {
+        assert!(theme_from_fields("x", &fields(&[("foreground", "#ffffff")])).is_err());
+        assert!(theme_from_fields(
+            "x",
+            &fields(&[
+                ("background", "#000000"),
+                ("foreground", "#ffffff"),
+                ("palette-3", "yellowish"),
+            ])
+        )
+        .is_err());
+    }
+}
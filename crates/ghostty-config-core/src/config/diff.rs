@@ -0,0 +1,322 @@
+use serde::Serialize;
+
+use super::model::UserConfig;
+
+/// The role of a single line in a line-level diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum LineTag {
+    Context,
+    Added,
+    Removed,
+}
+
+/// One line of a diff, tagged with whether it was kept, added, or removed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffLine {
+    pub tag: LineTag,
+    pub text: String,
+}
+
+/// A group of nearby changes with surrounding context, as in `diff -u`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Hunk {
+    /// 1-based line number of the first line in the old text.
+    pub old_start: usize,
+    pub old_len: usize,
+    /// 1-based line number of the first line in the new text.
+    pub new_start: usize,
+    pub new_len: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+impl Hunk {
+    /// The `@@ -a,b +c,d @@` header line.
+    pub fn header(&self) -> String {
+        format!(
+            "@@ -{},{} +{},{} @@",
+            self.old_start, self.old_len, self.new_start, self.new_len
+        )
+    }
+}
+
+/// How a key differs between two configs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A key-level change. Repeatable keys carry all of their values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeyChange {
+    pub key: String,
+    pub kind: ChangeKind,
+    pub old: Vec<String>,
+    pub new: Vec<String>,
+}
+
+/// Compute a full line diff between two texts using a longest-common-subsequence table.
+///
+/// Config files are small (hundreds of lines at most), so the quadratic table is fine.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = length of the LCS of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            lines.push(DiffLine {
+                tag: LineTag::Context,
+                text: a[i].to_string(),
+            });
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine {
+                tag: LineTag::Removed,
+                text: a[i].to_string(),
+            });
+            i += 1;
+        } else {
+            lines.push(DiffLine {
+                tag: LineTag::Added,
+                text: b[j].to_string(),
+            });
+            j += 1;
+        }
+    }
+    for line in &a[i..] {
+        lines.push(DiffLine {
+            tag: LineTag::Removed,
+            text: line.to_string(),
+        });
+    }
+    for line in &b[j..] {
+        lines.push(DiffLine {
+            tag: LineTag::Added,
+            text: line.to_string(),
+        });
+    }
+
+    lines
+}
+
+/// Compute unified-diff hunks with `context` unchanged lines around each change.
+pub fn unified_hunks(old: &str, new: &str, context: usize) -> Vec<Hunk> {
+    let lines = diff_lines(old, new);
+
+    // Group change indices that are close enough to share context.
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.tag == LineTag::Context {
+            continue;
+        }
+        match groups.last_mut() {
+            Some((_, end)) if i - *end - 1 <= 2 * context => *end = i,
+            _ => groups.push((i, i)),
+        }
+    }
+
+    // Line numbers (0-based) in the old/new text before each diff line.
+    let mut old_no = Vec::with_capacity(lines.len());
+    let mut new_no = Vec::with_capacity(lines.len());
+    let (mut o, mut n) = (0, 0);
+    for line in &lines {
+        old_no.push(o);
+        new_no.push(n);
+        match line.tag {
+            LineTag::Context => {
+                o += 1;
+                n += 1;
+            }
+            LineTag::Removed => o += 1,
+            LineTag::Added => n += 1,
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(first, last)| {
+            let start = first.saturating_sub(context);
+            let end = (last + context).min(lines.len() - 1);
+            let slice = &lines[start..=end];
+            Hunk {
+                old_start: old_no[start] + 1,
+                old_len: slice.iter().filter(|l| l.tag != LineTag::Added).count(),
+                new_start: new_no[start] + 1,
+                new_len: slice.iter().filter(|l| l.tag != LineTag::Removed).count(),
+                lines: slice.to_vec(),
+            }
+        })
+        .collect()
+}
+
+/// Compare the key/value pairs of two configs, ignoring comments and layout.
+///
+/// Keys are reported in the order they first appear (old config first).
+pub fn diff_keys(old: &UserConfig, new: &UserConfig) -> Vec<KeyChange> {
+    diff_configs(&[old], &[new])
+}
+
+/// [`diff_keys`] for a config together with the files it includes, each key's
+/// values taken from all of the files in order. A key moved from one file to
+/// another with the same values isn't a change.
+pub fn diff_configs(old: &[&UserConfig], new: &[&UserConfig]) -> Vec<KeyChange> {
+    let mut keys: Vec<&str> = Vec::new();
+    for config in old.iter().chain(new) {
+        for (key, _) in config.all_set_values() {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+    let values = |configs: &[&UserConfig], key: &str| -> Vec<String> {
+        configs
+            .iter()
+            .flat_map(|c| c.get_all(key))
+            .map(String::from)
+            .collect()
+    };
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let old_values = values(old, key);
+            let new_values = values(new, key);
+            let kind = match (old_values.is_empty(), new_values.is_empty()) {
+                (true, false) => ChangeKind::Added,
+                (false, true) => ChangeKind::Removed,
+                _ if old_values != new_values => ChangeKind::Changed,
+                _ => return None,
+            };
+            Some(KeyChange {
+                key: key.to_string(),
+                kind,
+                old: old_values,
+                new: new_values,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::test_config;
+
+    #[test]
+    fn test_diff_lines_identical() {
+        let lines = diff_lines("a\nb\n", "a\nb\n");
+        assert!(lines.iter().all(|l| l.tag == LineTag::Context));
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_diff_lines_changed_line() {
+        let lines = diff_lines("a\nb\nc\n", "a\nB\nc\n");
+        let tags: Vec<LineTag> = lines.iter().map(|l| l.tag).collect();
+        assert_eq!(
+            tags,
+            vec![
+                LineTag::Context,
+                LineTag::Removed,
+                LineTag::Added,
+                LineTag::Context
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_from_empty() {
+        let lines = diff_lines("", "font-size = 14\n");
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].tag, LineTag::Added);
+    }
+
+    #[test]
+    fn test_unified_hunks_no_changes() {
+        assert!(unified_hunks("a\nb\n", "a\nb\n", 3).is_empty());
+    }
+
+    #[test]
+    fn test_unified_hunks_header_and_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "1\n2\n3\n4\n5\nsix\n7\n8\n9\n10\n";
+        let hunks = unified_hunks(old, new, 2);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].header(), "@@ -4,5 +4,5 @@");
+        assert_eq!(hunks[0].lines.first().unwrap().text, "4");
+        assert_eq!(hunks[0].lines.last().unwrap().text, "8");
+    }
+
+    #[test]
+    fn test_unified_hunks_split_distant_changes() {
+        let old = "a\n1\n2\n3\n4\n5\n6\n7\nb\n";
+        let new = "A\n1\n2\n3\n4\n5\n6\n7\nB\n";
+        assert_eq!(unified_hunks(old, new, 1).len(), 2);
+        assert_eq!(unified_hunks(old, new, 4).len(), 1);
+    }
+
+    #[test]
+    fn test_diff_keys_added_removed_changed() {
+        let old = test_config(&[("font-size", "13"), ("theme", "Dracula")]);
+        let new = test_config(&[("font-size", "14"), ("background", "#000000")]);
+        let changes = diff_keys(&old, &new);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].key, "font-size");
+        assert_eq!(changes[0].kind, ChangeKind::Changed);
+        assert_eq!(changes[1].key, "theme");
+        assert_eq!(changes[1].kind, ChangeKind::Removed);
+        assert_eq!(changes[2].key, "background");
+        assert_eq!(changes[2].kind, ChangeKind::Added);
+    }
+
+    #[test]
+    fn test_diff_keys_repeatable_values() {
+        let old = test_config(&[("keybind", "ctrl+a=select_all")]);
+        let new = test_config(&[("keybind", "ctrl+a=select_all"), ("keybind", "ctrl+c=copy")]);
+        let changes = diff_keys(&old, &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, ChangeKind::Changed);
+        assert_eq!(changes[0].new.len(), 2);
+    }
+
+    #[test]
+    fn test_diff_keys_unchanged() {
+        let old = test_config(&[("font-size", "13")]);
+        let new = test_config(&[("font-size", "13")]);
+        assert!(diff_keys(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_configs_across_includes() {
+        let main = test_config(&[("font-size", "13"), ("keybind", "ctrl+a=select_all")]);
+        let colors = test_config(&[("background", "#000000")]);
+
+        // Moving a key into an include doesn't change what Ghostty loads.
+        let moved_main = test_config(&[("keybind", "ctrl+a=select_all")]);
+        let moved_colors = test_config(&[("background", "#000000"), ("font-size", "13")]);
+        assert!(diff_configs(&[&main, &colors], &[&moved_main, &moved_colors]).is_empty());
+
+        let edited_colors = test_config(&[("background", "#101010"), ("keybind", "ctrl+c=copy")]);
+        let changes = diff_configs(&[&main, &colors], &[&main, &edited_colors]);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].key, "keybind");
+        assert_eq!(changes[0].new, ["ctrl+a=select_all", "ctrl+c=copy"]);
+        assert_eq!(changes[1].key, "background");
+        assert_eq!(changes[1].old, ["#000000"]);
+    }
+}
@@ -0,0 +1,456 @@
+//! Health checks for a config: settings that load without error but don't do what
+//! the user meant, each with a fix where one is obvious.
+//!
+//! A check is a function from a [`DoctorContext`] to findings; add one to
+//! [`CHECKS`] to have it run.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::includes::resolve_include;
+use super::keybind_conflicts::normalize_trigger;
+use super::lint::{lint_config, LintKind};
+use super::model::{ConfigEntry, ConfigSchema, ConfigValueType, FontFamily, ThemeInfo, UserConfig};
+use super::palette::ansi_palette;
+use super::paths::path_warning;
+use super::theme_selection::ThemeSelection;
+
+/// What the checks look at. Empty font or theme lists (discovery failed) turn the
+/// checks that need them off rather than flagging everything.
+pub struct DoctorContext<'a> {
+    pub config: &'a UserConfig,
+    pub schema: &'a ConfigSchema,
+    pub fonts: &'a [FontFamily],
+    pub themes: &'a [ThemeInfo],
+}
+
+/// How much a finding matters; findings are listed most important first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Priority {
+    /// Ghostty ignores the setting or reports an error.
+    High,
+    /// The setting is shadowed or probably mistyped.
+    Medium,
+    /// Worth tidying up.
+    Low,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    /// Identifies the finding for [`find_fix`] while the config is unchanged.
+    pub id: String,
+    pub check: &'static str,
+    pub priority: Priority,
+    pub key: String,
+    /// 1-based line in the config file, when the finding is about one line.
+    pub line: Option<usize>,
+    pub message: String,
+    pub fix: Option<Fix>,
+}
+
+/// A remedy the user can apply with one click.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Fix {
+    /// Button text, e.g. "Remove line".
+    pub label: String,
+    pub edits: Vec<FixEdit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "edit", rename_all = "kebab-case")]
+pub enum FixEdit {
+    /// Drop the entry on this line.
+    RemoveLine { line: usize },
+    /// Keep the entry on this line but change its key.
+    RenameKey { line: usize, key: String },
+}
+
+pub type Check = fn(&DoctorContext) -> Vec<Finding>;
+
+/// Every check, by name.
+pub const CHECKS: &[(&str, Check)] = &[
+    ("lint", check_lint),
+    ("missing-font", check_fonts),
+    ("missing-path", check_paths),
+    ("missing-theme", check_themes),
+    ("palette-gap", check_palette),
+    ("keybind-conflict", check_keybinds),
+];
+
+/// Run every check, most important findings first.
+pub fn diagnose(ctx: &DoctorContext) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = CHECKS.iter().flat_map(|(_, check)| check(ctx)).collect();
+    findings.sort_by_key(|f| (f.priority, f.line));
+    findings
+}
+
+/// The fix for finding `id`, if it is still there.
+pub fn find_fix(ctx: &DoctorContext, id: &str) -> Option<Fix> {
+    diagnose(ctx).into_iter().find(|f| f.id == id)?.fix
+}
+
+/// Apply `fix` to `config`. Returns the keys it changed.
+pub fn apply_fix(config: &mut UserConfig, fix: &Fix) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut removals = Vec::new();
+    for edit in &fix.edits {
+        match edit {
+            FixEdit::RemoveLine { line } => removals.push(*line),
+            FixEdit::RenameKey { line, key } => {
+                if let Some(ConfigEntry::KeyValue { key: k, .. }) =
+                    config.entries.get_mut(line.wrapping_sub(1))
+                {
+                    keys.push(std::mem::replace(k, key.clone()));
+                    keys.push(key.clone());
+                }
+            }
+        }
+    }
+    // Last line first, so earlier line numbers stay valid.
+    removals.sort_unstable_by(|a, b| b.cmp(a));
+    for line in removals {
+        if let Some(ConfigEntry::KeyValue { key, .. }) = config.entries.get(line.wrapping_sub(1)) {
+            keys.push(key.clone());
+            config.entries.remove(line - 1);
+        }
+    }
+    keys.dedup();
+    keys
+}
+
+/// Each `key = value` entry with its 1-based line.
+fn key_values(config: &UserConfig) -> impl Iterator<Item = (usize, &str, &str)> {
+    config
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| match entry {
+            ConfigEntry::KeyValue { key, value, .. } => {
+                Some((index + 1, key.as_str(), value.as_str()))
+            }
+            _ => None,
+        })
+}
+
+fn finding(
+    check: &'static str,
+    priority: Priority,
+    key: &str,
+    line: usize,
+    message: String,
+    fix: Option<Fix>,
+) -> Finding {
+    Finding {
+        id: format!("{}:{}", check, line),
+        check,
+        priority,
+        key: key.to_string(),
+        line: Some(line),
+        message,
+        fix,
+    }
+}
+
+fn remove_line(line: usize) -> Option<Fix> {
+    Some(Fix {
+        label: "Remove line".to_string(),
+        edits: vec![FixEdit::RemoveLine { line }],
+    })
+}
+
+/// Unknown keys, deprecations, and repeated single-value keys from [`lint_config`].
+fn check_lint(ctx: &DoctorContext) -> Vec<Finding> {
+    lint_config(ctx.config, ctx.schema)
+        .into_iter()
+        .map(|w| {
+            let (check, priority, fix) = match w.kind {
+                LintKind::UnknownKey => (
+                    "unknown-key",
+                    Priority::Medium,
+                    match w.suggestion {
+                        Some(key) => Some(Fix {
+                            label: format!("Rename to {}", key),
+                            edits: vec![FixEdit::RenameKey { line: w.line, key }],
+                        }),
+                        None => remove_line(w.line),
+                    },
+                ),
+                LintKind::Duplicate => ("duplicate", Priority::Low, remove_line(w.line)),
+                LintKind::Deprecated => ("deprecated", Priority::Low, None),
+            };
+            finding(check, priority, &w.key, w.line, w.message, fix)
+        })
+        .collect()
+}
+
+/// `font-family*` values naming a family that isn't installed.
+fn check_fonts(ctx: &DoctorContext) -> Vec<Finding> {
+    if ctx.fonts.is_empty() {
+        return Vec::new();
+    }
+    key_values(ctx.config)
+        .filter(|(_, key, value)| key.starts_with("font-family") && !value.is_empty())
+        .filter(|(_, _, value)| {
+            let name = value.trim_matches('"');
+            !ctx.fonts.iter().any(|f| f.name.eq_ignore_ascii_case(name))
+        })
+        .map(|(line, key, value)| {
+            finding(
+                "missing-font",
+                Priority::High,
+                key,
+                line,
+                format!(
+                    "Font family \"{}\" is not installed",
+                    value.trim_matches('"')
+                ),
+                remove_line(line),
+            )
+        })
+        .collect()
+}
+
+/// Path options pointing at files that don't exist or can't be read.
+fn check_paths(ctx: &DoctorContext) -> Vec<Finding> {
+    key_values(ctx.config)
+        .filter_map(|(line, key, value)| {
+            let option = ctx.schema.find_option(key)?;
+            if !matches!(option.value_type, ConfigValueType::Path) || value.is_empty() {
+                return None;
+            }
+            let message = path_warning(key, value, &ctx.config.file_path)?;
+            Some(finding(
+                "missing-path",
+                Priority::High,
+                key,
+                line,
+                message,
+                remove_line(line),
+            ))
+        })
+        .collect()
+}
+
+/// `theme` values naming a theme that isn't installed, or a theme file that
+/// doesn't exist.
+fn check_themes(ctx: &DoctorContext) -> Vec<Finding> {
+    key_values(ctx.config)
+        .filter(|(_, key, _)| *key == "theme")
+        .filter_map(|(line, key, value)| {
+            let missing: Vec<String> = theme_names(value)
+                .into_iter()
+                .filter(|name| !theme_exists(ctx, name))
+                .collect();
+            if missing.is_empty() {
+                return None;
+            }
+            Some(finding(
+                "missing-theme",
+                Priority::High,
+                key,
+                line,
+                format!("Theme \"{}\" doesn't exist", missing.join("\", \"")),
+                remove_line(line),
+            ))
+        })
+        .collect()
+}
+
+fn theme_names(value: &str) -> Vec<String> {
+    match ThemeSelection::parse(value) {
+        ThemeSelection::None => Vec::new(),
+        ThemeSelection::Single(name) => vec![name],
+        ThemeSelection::Paired { light, dark } if light == dark => vec![light],
+        ThemeSelection::Paired { light, dark } => vec![light, dark],
+    }
+}
+
+/// Themes may be given by name or as a path to a theme file. Names can't be
+/// checked without a theme list.
+fn theme_exists(ctx: &DoctorContext, name: &str) -> bool {
+    if name.contains('/') {
+        return resolve_include(&ctx.config.file_path, name).is_file();
+    }
+    ctx.themes.is_empty() || ctx.themes.iter().any(|t| t.name == name)
+}
+
+/// Some but not all of the 16 ANSI colors set, with no theme to supply the rest:
+/// the missing ones fall back to Ghostty's defaults, which rarely match.
+fn check_palette(ctx: &DoctorContext) -> Vec<Finding> {
+    let has_theme = ctx
+        .config
+        .get("theme")
+        .map(theme_names)
+        .is_some_and(|names| !names.is_empty() && names.iter().all(|n| theme_exists(ctx, n)));
+    if has_theme {
+        return Vec::new();
+    }
+    let palette = ansi_palette(&ctx.config.get_all("palette"));
+    let missing: Vec<String> = (0..16)
+        .filter(|&i| palette[i].is_none())
+        .map(|i| i.to_string())
+        .collect();
+    if missing.is_empty() || missing.len() == 16 {
+        return Vec::new();
+    }
+    vec![Finding {
+        id: "palette-gap".to_string(),
+        check: "palette-gap",
+        priority: Priority::Low,
+        key: "palette".to_string(),
+        line: None,
+        message: format!(
+            "The palette sets {} of the 16 colors without a theme; colors {} use Ghostty's defaults",
+            16 - missing.len(),
+            missing.join(", ")
+        ),
+        fix: None,
+    }]
+}
+
+/// `keybind` entries whose trigger a later entry binds again, so they never fire.
+fn check_keybinds(ctx: &DoctorContext) -> Vec<Finding> {
+    let binds: Vec<(usize, &str, String)> = key_values(ctx.config)
+        .filter(|(_, key, _)| *key == "keybind")
+        .filter_map(|(line, _, value)| {
+            let (trigger, _) = value.split_once('=')?;
+            Some((line, value, normalize_trigger(trigger)))
+        })
+        .collect();
+    let last: HashMap<&str, usize> = binds
+        .iter()
+        .map(|(line, _, trigger)| (trigger.as_str(), *line))
+        .collect();
+    binds
+        .iter()
+        .filter(|(line, _, trigger)| last[trigger.as_str()] != *line)
+        .map(|(line, value, trigger)| {
+            finding(
+                "keybind-conflict",
+                Priority::Medium,
+                "keybind",
+                *line,
+                format!(
+                    "\"{}\" never fires: line {} binds {} again",
+                    value,
+                    last[trigger.as_str()],
+                    trigger
+                ),
+                remove_line(*line),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file_io::{parse_config, render_config};
+    use crate::config::model::{Category, ConfigOption};
+    use std::path::Path;
+
+    fn option(key: &str, value_type: ConfigValueType, is_repeatable: bool) -> ConfigOption {
+        ConfigOption {
+            key: key.to_string(),
+            default_value: String::new(),
+            documentation: String::new(),
+            value_type,
+            category: Category::Advanced,
+            is_repeatable,
+            conditions: Default::default(),
+        }
+    }
+
+    fn schema() -> ConfigSchema {
+        ConfigSchema::new(vec![
+            option("font-family", ConfigValueType::Font, true),
+            option("font-size", ConfigValueType::Float, false),
+            option("theme", ConfigValueType::Text, false),
+            option("palette", ConfigValueType::Palette, true),
+            option("keybind", ConfigValueType::Keybind, true),
+            option("config-file", ConfigValueType::Path, true),
+        ])
+    }
+
+    fn theme(name: &str) -> ThemeInfo {
+        ThemeInfo {
+            name: name.to_string(),
+            background: "#000000".to_string(),
+            foreground: "#ffffff".to_string(),
+            palette: Vec::new(),
+            is_dark: true,
+            cursor_color: None,
+            selection_background: None,
+            custom: false,
+            origin: Default::default(),
+        }
+    }
+
+    fn diagnose_config(config: &UserConfig) -> Vec<Finding> {
+        let fonts = [FontFamily {
+            name: "JetBrains Mono".to_string(),
+            styles: Vec::new(),
+        }];
+        diagnose(&DoctorContext {
+            config,
+            schema: &schema(),
+            fonts: &fonts,
+            themes: &[theme("Dracula")],
+        })
+    }
+
+    fn diagnose_text(text: &str) -> Vec<Finding> {
+        diagnose_config(&parse_config(
+            text,
+            Path::new("/nonexistent/ghostty/config"),
+        ))
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let findings = diagnose_text(
+            "font-family = jetbrains mono\nfont-family = Comic Mono\ntheme = light:Dracula,dark:Nope\n\
+             config-file = missing.conf\nconfig-file = ?optional.conf\nkeybind = ctrl+shift+a=copy_to_clipboard\n\
+             keybind = shift+ctrl+a=paste_from_clipboard\nfont-szie = 12\n",
+        );
+        let summary: Vec<_> = findings
+            .iter()
+            .map(|f| (f.id.as_str(), f.priority))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("missing-font:2", Priority::High),
+                ("missing-theme:3", Priority::High),
+                ("missing-path:4", Priority::High),
+                ("keybind-conflict:6", Priority::Medium),
+                ("unknown-key:8", Priority::Medium),
+            ]
+        );
+        assert_eq!(findings[1].message, "Theme \"Nope\" doesn't exist");
+    }
+
+    #[test]
+    fn test_palette_gap() {
+        let findings = diagnose_text("palette = 0=#000000\npalette = 1=#ff0000\n");
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("colors 2, 3, 4"));
+
+        assert!(diagnose_text("theme = Dracula\npalette = 0=#000000\n").is_empty());
+    }
+
+    #[test]
+    fn test_apply_fix() {
+        let mut config = parse_config(
+            "font-szie = 12\nkeybind = a=b\nkeybind = a=c\nfont-family = Nope\n",
+            Path::new("config"),
+        );
+        // Line numbers change with each fix, so diagnose again in between.
+        while let Some(fix) = diagnose_config(&config).into_iter().find_map(|f| f.fix) {
+            apply_fix(&mut config, &fix);
+        }
+        assert_eq!(render_config(&config), "font-size = 12\nkeybind = a=c\n");
+    }
+}
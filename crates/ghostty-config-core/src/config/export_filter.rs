@@ -0,0 +1,145 @@
+//! Exporting part of a config: the options of some categories, or only the ones
+//! that differ from Ghostty's defaults.
+
+use super::categorize::categorize_key;
+use super::file_io::unquote_value;
+use super::model::{Category, ConfigEntry, ConfigSchema, UserConfig};
+use crate::Error;
+
+/// Which lines of a config to export. The default keeps everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportFilter {
+    /// Only options in these categories; every category when empty.
+    pub categories: Vec<Category>,
+    /// Only values that differ from the option's default.
+    pub changed_only: bool,
+}
+
+impl ExportFilter {
+    /// Parse a comma-separated list of category slugs, e.g. `fonts,colors`.
+    pub fn parse_categories(list: &str) -> Result<Vec<Category>, Error> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|slug| !slug.is_empty())
+            .map(|slug| {
+                Category::all()
+                    .into_iter()
+                    .find(|c| c.slug() == slug)
+                    .ok_or_else(|| Error::Config(format!("Unknown category: {}", slug)))
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty() && !self.changed_only
+    }
+
+    /// Whether the line `key = value` is exported.
+    pub fn includes(&self, key: &str, value: &str, schema: &ConfigSchema) -> bool {
+        let option = schema.find_option(key);
+        if !self.categories.is_empty() {
+            let category = option
+                .map(|o| o.category.clone())
+                .unwrap_or_else(|| categorize_key(key));
+            if !self.categories.contains(&category) {
+                return false;
+            }
+        }
+        !self.changed_only || option.is_none_or(|o| unquote_value(value) != o.default_value)
+    }
+
+    /// The lines of `config` the filter keeps. A filter that keeps only some
+    /// options drops comments and blank lines, which may be about other options;
+    /// comments after a value stay with it.
+    pub fn apply(&self, config: &UserConfig, schema: &ConfigSchema) -> UserConfig {
+        if self.is_empty() {
+            return config.clone();
+        }
+        let entries = config
+            .entries
+            .iter()
+            .filter(|entry| match entry {
+                ConfigEntry::KeyValue { key, value, .. } => self.includes(key, value, schema),
+                _ => false,
+            })
+            .cloned()
+            .collect();
+        UserConfig {
+            entries,
+            file_path: config.file_path.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file_io::{parse_config, render_config};
+    use crate::config::model::{ConfigOption, ConfigValueType};
+    use std::path::Path;
+
+    fn schema() -> ConfigSchema {
+        let option = |key: &str, default: &str, category| ConfigOption {
+            key: key.to_string(),
+            default_value: default.to_string(),
+            documentation: String::new(),
+            value_type: ConfigValueType::Text,
+            category,
+            is_repeatable: false,
+            conditions: Default::default(),
+        };
+        ConfigSchema::new(vec![
+            option("font-size", "13", Category::Fonts),
+            option("background", "#282c34", Category::Colors),
+            option("cursor-style", "block", Category::Cursor),
+        ])
+    }
+
+    fn config() -> UserConfig {
+        parse_config(
+            "# Fonts\nfont-size = 13\n\nbackground = #000000  # darker\ncursor-style = bar\n",
+            Path::new("/tmp/config"),
+        )
+    }
+
+    #[test]
+    fn test_parse_categories() {
+        assert_eq!(
+            ExportFilter::parse_categories("fonts, colors").unwrap(),
+            vec![Category::Fonts, Category::Colors]
+        );
+        assert!(ExportFilter::parse_categories("fonts,nope").is_err());
+        assert!(ExportFilter::parse_categories("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_empty_filter_keeps_everything() {
+        let config = config();
+        let kept = ExportFilter::default().apply(&config, &schema());
+        assert_eq!(render_config(&kept), render_config(&config));
+    }
+
+    #[test]
+    fn test_filter_by_category() {
+        let filter = ExportFilter {
+            categories: vec![Category::Fonts, Category::Colors],
+            changed_only: false,
+        };
+        assert_eq!(
+            render_config(&filter.apply(&config(), &schema())),
+            "font-size = 13\nbackground = #000000  # darker\n"
+        );
+    }
+
+    #[test]
+    fn test_filter_changed_only() {
+        let filter = ExportFilter {
+            categories: Vec::new(),
+            changed_only: true,
+        };
+        assert_eq!(
+            render_config(&filter.apply(&config(), &schema())),
+            "background = #000000  # darker\ncursor-style = bar\n"
+        );
+    }
+}
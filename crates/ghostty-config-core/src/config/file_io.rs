@@ -0,0 +1,596 @@
+use std::borrow::Cow;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use super::model::{ConfigEntry, UserConfig};
+use crate::Error;
+
+/// Read a config file, preserving comments and blank lines.
+pub fn read_config(path: &Path) -> Result<UserConfig, Error> {
+    if !path.exists() {
+        return Ok(UserConfig::new(path.to_path_buf()));
+    }
+
+    let content = fs::read_to_string(path)?;
+    Ok(parse_config(&content, path))
+}
+
+/// Parse config text, preserving comments and blank lines. `path` is where it would be saved.
+pub fn parse_config(content: &str, path: &Path) -> UserConfig {
+    let mut config = UserConfig::new(path.to_path_buf());
+
+    for line in content.lines() {
+        let entry = match tokenize_line(line) {
+            Line::Blank => ConfigEntry::BlankLine,
+            Line::Comment(text) => ConfigEntry::Comment(text.to_string()),
+            Line::KeyValue {
+                key,
+                value,
+                comment,
+            } => ConfigEntry::KeyValue {
+                key: key.to_string(),
+                value: value.to_string(),
+                comment: comment.map(String::from),
+            },
+            // Treat unparseable lines as comments to preserve them
+            Line::Other(text) => ConfigEntry::Comment(text.to_string()),
+        };
+        config.entries.push(entry);
+    }
+
+    config
+}
+
+/// One line of a config file, split into its parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line<'a> {
+    Blank,
+    /// A whole-line comment, including any indentation before the `#`.
+    Comment(&'a str),
+    /// `key = value`, where `value` is exactly as written (quotes and escapes
+    /// included) and `comment` is a trailing comment with the whitespace before it.
+    KeyValue {
+        key: &'a str,
+        value: &'a str,
+        comment: Option<&'a str>,
+    },
+    /// Anything else, kept verbatim.
+    Other(&'a str),
+}
+
+/// Split a config line into its parts.
+///
+/// The key runs up to the first `=`; the value is everything after it, with
+/// the surrounding whitespace trimmed. Inside a double-quoted part of the value
+/// `=`, `#`, and a backslash-escaped `"` are plain text. Outside quotes a `#`
+/// with whitespace on both sides starts a trailing comment, so colors like
+/// `#282c34` are left alone.
+pub fn tokenize_line(line: &str) -> Line<'_> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return Line::Blank;
+    }
+    if trimmed.starts_with('#') {
+        return Line::Comment(line);
+    }
+    let Some((key, rest)) = line.split_once('=') else {
+        return Line::Other(line);
+    };
+    let key = key.trim();
+    if key.is_empty() || key.contains(char::is_whitespace) || key.contains('"') {
+        return Line::Other(line);
+    }
+
+    let rest = rest.trim();
+    let bytes = rest.as_bytes();
+    let mut quoted = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if quoted => i += 1,
+            b'"' => quoted = !quoted,
+            b'#' if !quoted
+                && i > 0
+                && bytes[i - 1].is_ascii_whitespace()
+                && bytes.get(i + 1).is_none_or(|c| c.is_ascii_whitespace()) =>
+            {
+                let end = rest[..i].trim_end().len();
+                return Line::KeyValue {
+                    key,
+                    value: &rest[..end],
+                    comment: Some(&rest[end..]),
+                };
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    Line::KeyValue {
+        key,
+        value: rest,
+        comment: None,
+    }
+}
+
+/// The text a value stands for: a fully double-quoted value loses its quotes
+/// and has its escapes (`\"`, `\\`, `\n`, `\t`, `\r`, `\xNN`, `\u{N}`)
+/// decoded. Anything else is returned unchanged.
+pub fn unquote_value(value: &str) -> Cow<'_, str> {
+    let value = value.trim();
+    let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .filter(|_| value.len() >= 2)
+    else {
+        return Cow::Borrowed(value);
+    };
+    if !inner.contains('\\') {
+        return Cow::Borrowed(inner);
+    }
+
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                match u8::from_str_radix(&hex, 16) {
+                    Ok(byte) if byte.is_ascii() => out.push(byte as char),
+                    _ => {
+                        out.push_str("\\x");
+                        out.push_str(&hex);
+                    }
+                }
+            }
+            Some('u') => {
+                let rest = chars.as_str();
+                let decoded = rest
+                    .strip_prefix('{')
+                    .and_then(|r| r.split_once('}'))
+                    .and_then(|(hex, tail)| {
+                        let c = char::from_u32(u32::from_str_radix(hex, 16).ok()?)?;
+                        Some((c, tail))
+                    });
+                match decoded {
+                    Some((c, tail)) => {
+                        out.push(c);
+                        chars = tail.chars();
+                    }
+                    None => out.push_str("\\u"),
+                }
+            }
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Wrap text in double quotes, escaping what `unquote_value` decodes.
+pub fn quote_value(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// The text to write for a value: the value itself when reading it back gives
+/// the same value, otherwise the value quoted. This keeps values set through
+/// the API with surrounding spaces, line breaks, or a ` # ` intact.
+fn written_value(value: &str) -> Cow<'_, str> {
+    let line = format!("k = {}", value);
+    let round_trips = !value.contains(['\n', '\r'])
+        && matches!(
+            tokenize_line(&line),
+            Line::KeyValue { value: v, comment: None, .. } if v == value
+        );
+    if round_trips {
+        Cow::Borrowed(value)
+    } else {
+        Cow::Owned(quote_value(value))
+    }
+}
+
+/// Read the raw text of a config file (empty if it does not exist yet).
+pub fn read_config_text(path: &Path) -> Result<String, Error> {
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    Ok(fs::read_to_string(path)?)
+}
+
+/// Write the config file, preserving structure.
+pub fn write_config(config: &UserConfig) -> Result<(), Error> {
+    // Ensure parent directory exists
+    if let Some(parent) = config.file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    write_atomic(&config.file_path, render_config(config))
+}
+
+/// Replace the file at `path` with `contents` so that a crash leaves either the old
+/// file or the new one, never a truncated mix.
+///
+/// The text goes to a temporary file in the same directory, is synced to disk, and
+/// is renamed over the target. The new file keeps the old one's permissions, or is
+/// readable only by its owner if there was none, and a symlinked config has its
+/// target replaced rather than the link.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), Error> {
+    replace_file(path, contents.as_ref(), None)
+}
+
+/// Copy `from` over `to` the way [`write_atomic`] writes, giving the copy `from`'s
+/// permissions like `fs::copy` does.
+pub fn copy_atomic(from: &Path, to: &Path) -> Result<(), Error> {
+    let permissions = fs::metadata(from)?.permissions();
+    replace_file(to, &fs::read(from)?, Some(permissions))
+}
+
+/// [`write_atomic`], with `permissions` for the new file instead of the old one's.
+fn replace_file(
+    path: &Path,
+    contents: &[u8],
+    permissions: Option<fs::Permissions>,
+) -> Result<(), Error> {
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let dir = match target.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let name = target
+        .file_name()
+        .ok_or_else(|| Error::Config(format!("Not a file path: {}", path.display())))?;
+    // A new, randomly named file only this user can read until it's complete, so
+    // concurrent writers don't share it and a private config never leaks.
+    let prefix = format!(".{}.", name.to_string_lossy());
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(&prefix).suffix(".tmp");
+    #[cfg(unix)]
+    builder.permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600));
+    let mut temp = builder.tempfile_in(dir)?;
+    temp.write_all(contents)?;
+    let permissions = permissions.or_else(|| fs::metadata(&target).ok().map(|m| m.permissions()));
+    if let Some(permissions) = permissions {
+        temp.as_file().set_permissions(permissions)?;
+    }
+    temp.as_file().sync_all()?;
+    temp.persist(&target).map_err(|e| e.error)?;
+
+    // Make the rename itself durable. Directories can't be opened for this on
+    // every platform, so failing here isn't an error.
+    if let Ok(dir) = fs::File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// Render a config to the exact text `write_config` would put on disk.
+pub fn render_config(config: &UserConfig) -> String {
+    let mut output = String::new();
+    for entry in &config.entries {
+        match entry {
+            ConfigEntry::Comment(text) => {
+                output.push_str(text);
+                output.push('\n');
+            }
+            ConfigEntry::BlankLine => {
+                output.push('\n');
+            }
+            ConfigEntry::KeyValue {
+                key,
+                value,
+                comment,
+            } => {
+                output.push_str(key);
+                output.push_str(" = ");
+                output.push_str(&written_value(value));
+                if let Some(comment) = comment {
+                    output.push_str(comment);
+                }
+                output.push('\n');
+            }
+        }
+    }
+
+    output
+}
+
+/// Get the default config file path: `ghostty/config` in the platform config
+/// directory (`%APPDATA%` on Windows).
+pub fn default_config_path() -> std::path::PathBuf {
+    if let Some(config_dir) = dirs_config_dir() {
+        config_dir.join("ghostty").join("config")
+    } else {
+        // Fallback
+        let home = std::env::var("HOME")
+            .or_else(|_| std::env::var("USERPROFILE"))
+            .unwrap_or_else(|_| ".".to_string());
+        Path::new(&home)
+            .join(".config")
+            .join("ghostty")
+            .join("config")
+    }
+}
+
+fn dirs_config_dir() -> Option<std::path::PathBuf> {
+    directories::BaseDirs::new().map(|d| d.config_dir().to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_roundtrip() {
+        let content = "# My config\nfont-size = 14\n\n# Theme\ntheme = Dracula\n";
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(content.as_bytes()).unwrap();
+
+        let config = read_config(tmp.path()).unwrap();
+        assert_eq!(config.entries.len(), 5);
+        assert_eq!(config.get("font-size"), Some("14"));
+        assert_eq!(config.get("theme"), Some("Dracula"));
+    }
+
+    #[test]
+    fn test_read_nonexistent_file() {
+        let config = read_config(Path::new("/tmp/nonexistent_ghostty_test_config")).unwrap();
+        assert!(config.entries.is_empty());
+    }
+
+    #[test]
+    fn test_read_preserves_comments() {
+        let content = "# Comment line\nfont-size = 14\n";
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(content.as_bytes()).unwrap();
+
+        let config = read_config(tmp.path()).unwrap();
+        assert_eq!(config.entries.len(), 2);
+        assert!(matches!(&config.entries[0], ConfigEntry::Comment(c) if c == "# Comment line"));
+    }
+
+    #[test]
+    fn test_read_preserves_blank_lines() {
+        let content = "font-size = 14\n\n\ntheme = Dracula\n";
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(content.as_bytes()).unwrap();
+
+        let config = read_config(tmp.path()).unwrap();
+        // 1 kv + 2 blanks + 1 kv = 4
+        assert_eq!(config.entries.len(), 4);
+        assert!(matches!(&config.entries[1], ConfigEntry::BlankLine));
+        assert!(matches!(&config.entries[2], ConfigEntry::BlankLine));
+    }
+
+    #[test]
+    fn test_read_unparseable_line_becomes_comment() {
+        let content = "some random text\nfont-size = 14\n";
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(content.as_bytes()).unwrap();
+
+        let config = read_config(tmp.path()).unwrap();
+        assert!(matches!(&config.entries[0], ConfigEntry::Comment(c) if c == "some random text"));
+    }
+
+    #[test]
+    fn test_write_and_read_back() {
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_path_buf();
+
+        let mut config = UserConfig::new(path.clone());
+        config
+            .entries
+            .push(ConfigEntry::Comment("# Generated config".to_string()));
+        config.entries.push(ConfigEntry::BlankLine);
+        config.entries.push(ConfigEntry::key_value(
+            "font-size".to_string(),
+            "16".to_string(),
+        ));
+        config.entries.push(ConfigEntry::key_value(
+            "theme".to_string(),
+            "Dracula".to_string(),
+        ));
+
+        write_config(&config).unwrap();
+
+        let read_back = read_config(&path).unwrap();
+        assert_eq!(read_back.get("font-size"), Some("16"));
+        assert_eq!(read_back.get("theme"), Some("Dracula"));
+        assert_eq!(read_back.entries.len(), 4);
+    }
+
+    #[test]
+    fn test_write_preserves_formatting() {
+        let content = "# My settings\nfont-size = 14\n\n# Colors\nbackground = #1e1e2e\n";
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(content.as_bytes()).unwrap();
+        let path = tmp.path().to_path_buf();
+
+        let config = read_config(&path).unwrap();
+        write_config(&config).unwrap();
+
+        let written = fs::read_to_string(&path).unwrap();
+        assert_eq!(written, content);
+    }
+
+    #[test]
+    fn test_trailing_comment_survives_value_update() {
+        let path = Path::new("/tmp/test");
+        let mut config = parse_config("font-size = 14   # big monitor\n", path);
+        assert_eq!(config.get("font-size"), Some("14"));
+        config.set("font-size", "16");
+        assert_eq!(render_config(&config), "font-size = 16   # big monitor\n");
+    }
+
+    #[test]
+    fn test_hash_inside_value_is_not_a_comment() {
+        let path = Path::new("/tmp/test");
+        let content = "background = #282c34\ntitle = \"a # b\"\nkeybind = ctrl+#=csi:x\n";
+        let config = parse_config(content, path);
+        assert_eq!(config.get("background"), Some("#282c34"));
+        assert_eq!(config.get("title"), Some("\"a # b\""));
+        assert_eq!(config.get("keybind"), Some("ctrl+#=csi:x"));
+        assert_eq!(render_config(&config), content);
+    }
+
+    #[test]
+    fn test_quoted_values_round_trip_exactly() {
+        let path = Path::new("/tmp/test");
+        let content = "title = \"say \\\"hi\\\" # not a comment\"  # a comment\n\
+                       font-family = \"  Fira = Code  \"\n\
+                       keybind = ctrl+shift+\"=text:\\x1b\n\
+                       \t# indented = comment\n";
+        let config = parse_config(content, path);
+        assert_eq!(
+            config.get("title"),
+            Some("\"say \\\"hi\\\" # not a comment\"")
+        );
+        assert_eq!(config.get("font-family"), Some("\"  Fira = Code  \""));
+        assert!(matches!(&config.entries[3], ConfigEntry::Comment(_)));
+        assert_eq!(render_config(&config), content);
+    }
+
+    #[test]
+    fn test_values_that_would_not_read_back_are_quoted() {
+        let path = Path::new("/tmp/test");
+        let mut config = UserConfig::new(path.to_path_buf());
+        config.set("title", " a # b\\c ");
+        let written = render_config(&config);
+        assert_eq!(written, "title = \" a # b\\\\c \"\n");
+        let read_back = parse_config(&written, path);
+        assert_eq!(unquote_value(read_back.get("title").unwrap()), " a # b\\c ");
+    }
+
+    #[test]
+    fn test_unquote_value() {
+        assert_eq!(unquote_value("plain"), "plain");
+        assert_eq!(unquote_value("\"~/my dir\""), "~/my dir");
+        assert_eq!(unquote_value(r#""a\"b\\c\n\x41\u{e9}""#), "a\"b\\c\nAé");
+        assert_eq!(unquote_value("\""), "\"");
+        assert_eq!(quote_value("a\"b\\c\n"), r#""a\"b\\c\n""#);
+    }
+
+    #[test]
+    fn test_set_then_write_roundtrip() {
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_path_buf();
+
+        let mut config = UserConfig::new(path.clone());
+        config.set("font-size", "14");
+        config.set("theme", "Dracula");
+        write_config(&config).unwrap();
+
+        let read_back = read_config(&path).unwrap();
+        assert_eq!(read_back.get("font-size"), Some("14"));
+        assert_eq!(read_back.get("theme"), Some("Dracula"));
+    }
+
+    #[test]
+    fn test_default_config_path_exists() {
+        let path = default_config_path();
+        // Just verify it returns a path that includes "ghostty" and "config"
+        let path_str = path.to_string_lossy();
+        assert!(path_str.contains("ghostty"));
+        assert!(path_str.ends_with("config"));
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config");
+        write_atomic(&path, "font-size = 12\n").unwrap();
+        write_atomic(&path, "font-size = 14\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "font-size = 14\n");
+        // No temporary file is left behind.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_atomic_concurrent_writers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("profile");
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || write_atomic(&path, format!("font-size = {}\n", i)))
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap().unwrap();
+        }
+        let text = fs::read_to_string(&path).unwrap();
+        assert!((0..8).any(|i| text == format!("font-size = {}\n", i)));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_new_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config");
+        write_atomic(&path, "font-size = 12\n").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_keeps_mode_and_symlink() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let dir = tempfile::tempdir().unwrap();
+        let real = dir.path().join("real-config");
+        fs::write(&real, "font-size = 12\n").unwrap();
+        fs::set_permissions(&real, fs::Permissions::from_mode(0o600)).unwrap();
+        let link = dir.path().join("config");
+        symlink(&real, &link).unwrap();
+
+        write_atomic(&link, "font-size = 14\n").unwrap();
+        assert!(fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_to_string(&real).unwrap(), "font-size = 14\n");
+        let mode = fs::metadata(&real).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_atomic_keeps_source_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config");
+        fs::write(&config, b"font-size = 12\n\xff").unwrap();
+        fs::set_permissions(&config, fs::Permissions::from_mode(0o600)).unwrap();
+        let backup = dir.path().join("config.bak");
+
+        copy_atomic(&config, &backup).unwrap();
+        assert_eq!(fs::read(&backup).unwrap(), b"font-size = 12\n\xff");
+        let mode = fs::metadata(&backup).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
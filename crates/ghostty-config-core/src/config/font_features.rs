@@ -0,0 +1,133 @@
+use super::model::UserConfig;
+
+/// OpenType features worth offering as toggles, besides the stylistic sets.
+const COMMON_FEATURES: &[(&str, &str)] = &[
+    ("calt", "Contextual alternates"),
+    ("liga", "Standard ligatures"),
+    ("dlig", "Discretionary ligatures"),
+    ("zero", "Slashed zero"),
+    ("onum", "Oldstyle figures"),
+    ("tnum", "Tabular figures"),
+    ("case", "Case-sensitive forms"),
+    ("frac", "Fractions"),
+];
+
+/// Features offered as toggles: the common ones, then stylistic sets `ss01`–`ss20`.
+pub fn common_features() -> Vec<(String, String)> {
+    COMMON_FEATURES
+        .iter()
+        .map(|(tag, name)| (tag.to_string(), name.to_string()))
+        .chain((1..=20).map(|n| (format!("ss{:02}", n), format!("Stylistic set {}", n))))
+        .collect()
+}
+
+/// One feature setting from a `font-feature` value: `+tag` or `tag` turns it on,
+/// `-tag` off, and `tag=N`/`tag on`/`tag off` say so explicitly.
+fn parse_setting(item: &str) -> Option<(String, bool)> {
+    let item = item.trim();
+    let (tag, enabled) = if let Some(tag) = item.strip_prefix('+') {
+        (tag, true)
+    } else if let Some(tag) = item.strip_prefix('-') {
+        (tag, false)
+    } else if let Some((tag, value)) = item.split_once(['=', ' ']) {
+        let enabled = match value.trim() {
+            "0" | "off" | "false" => false,
+            "on" | "true" => true,
+            n => n.parse::<u32>().ok()? > 0,
+        };
+        (tag, enabled)
+    } else {
+        (item, true)
+    };
+    let tag = tag.trim();
+    (!tag.is_empty() && tag.len() <= 4 && tag.chars().all(|c| c.is_ascii_alphanumeric()))
+        .then(|| (tag.to_string(), enabled))
+}
+
+/// Every feature the `font-feature` values turn on or off, in order, with later
+/// settings of a feature replacing earlier ones. Entries may list several features
+/// separated by commas.
+pub fn parse_features(values: &[&str]) -> Vec<(String, bool)> {
+    let mut features: Vec<(String, bool)> = Vec::new();
+    for (tag, enabled) in values
+        .iter()
+        .flat_map(|v| v.split(','))
+        .filter_map(parse_setting)
+    {
+        match features.iter_mut().find(|(t, _)| *t == tag) {
+            Some(existing) => existing.1 = enabled,
+            None => features.push((tag, enabled)),
+        }
+    }
+    features
+}
+
+/// Turn feature `tag` on, off, or (`None`) back to the font's default.
+///
+/// The tag is taken out of every `font-feature` entry, dropping entries left empty,
+/// so the other features keep their settings; a new setting is added at the end.
+pub fn set_feature(config: &mut UserConfig, tag: &str, enabled: Option<bool>) {
+    let mut values: Vec<String> = config
+        .get_all("font-feature")
+        .into_iter()
+        .filter_map(|value| {
+            let items: Vec<&str> = value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| parse_setting(item).is_none_or(|(t, _)| t != tag))
+                .collect();
+            (!items.is_empty()).then(|| items.join(", "))
+        })
+        .collect();
+    if let Some(enabled) = enabled {
+        values.push(format!("{}{}", if enabled { '+' } else { '-' }, tag));
+    }
+    config.set_all("font-feature", &values);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_features() {
+        let features = parse_features(&["-calt, -liga", "+ss01", "zero", "calt=1", "dlig off"]);
+        assert_eq!(
+            features,
+            vec![
+                ("calt".to_string(), true),
+                ("liga".to_string(), false),
+                ("ss01".to_string(), true),
+                ("zero".to_string(), true),
+                ("dlig".to_string(), false),
+            ]
+        );
+        assert!(parse_features(&["", "+not-a-tag"]).is_empty());
+    }
+
+    #[test]
+    fn test_set_feature_keeps_others() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/config"));
+        config.push("font-feature", "-calt, -liga");
+        config.push("font-feature", "+ss02");
+
+        set_feature(&mut config, "calt", Some(true));
+        assert_eq!(
+            config.get_all("font-feature"),
+            vec!["-liga", "+ss02", "+calt"]
+        );
+
+        set_feature(&mut config, "ss02", None);
+        set_feature(&mut config, "liga", None);
+        assert_eq!(config.get_all("font-feature"), vec!["+calt"]);
+    }
+
+    #[test]
+    fn test_common_features() {
+        let features = common_features();
+        assert_eq!(features[0].0, "calt");
+        assert_eq!(features.last().unwrap().0, "ss20");
+        assert_eq!(features.len(), COMMON_FEATURES.len() + 20);
+    }
+}
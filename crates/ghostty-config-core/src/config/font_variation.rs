@@ -0,0 +1,259 @@
+use serde::Serialize;
+
+use super::model::UserConfig;
+
+/// The `font-variation*` keys and the `font-family*` key whose font each one adjusts.
+pub const VARIATION_KEYS: &[(&str, &str)] = &[
+    ("font-variation", "font-family"),
+    ("font-variation-bold", "font-family-bold"),
+    ("font-variation-italic", "font-family-italic"),
+    ("font-variation-bold-italic", "font-family-bold-italic"),
+];
+
+/// A design axis of a variable font, such as weight (`wght`) or width (`wdth`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FontAxis {
+    pub tag: String,
+    pub min: f32,
+    pub default: f32,
+    pub max: f32,
+}
+
+impl FontAxis {
+    fn new(tag: &str, min: f32, default: f32, max: f32) -> Self {
+        Self {
+            tag: tag.to_string(),
+            min,
+            default,
+            max,
+        }
+    }
+
+    /// Human-readable name for the registered axes; other axes show their tag.
+    pub fn label(&self) -> &str {
+        match self.tag.as_str() {
+            "wght" => "Weight",
+            "wdth" => "Width",
+            "slnt" => "Slant",
+            "ital" => "Italic",
+            "opsz" => "Optical size",
+            tag => tag,
+        }
+    }
+}
+
+/// The registered OpenType axes with their usual ranges, for when a font's own
+/// axes can't be read.
+pub fn standard_axes() -> Vec<FontAxis> {
+    vec![
+        FontAxis::new("wght", 100.0, 400.0, 900.0),
+        FontAxis::new("wdth", 50.0, 100.0, 200.0),
+        FontAxis::new("slnt", -15.0, 0.0, 0.0),
+        FontAxis::new("ital", 0.0, 0.0, 1.0),
+    ]
+}
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// A 16.16 fixed-point number.
+fn read_fixed(data: &[u8], at: usize) -> Option<f32> {
+    Some(read_u32(data, at)? as i32 as f32 / 65536.0)
+}
+
+/// Read the axes from the `fvar` table of a TrueType/OpenType font file, or the
+/// first font of a collection.
+///
+/// Returns `None` for files that aren't fonts; an empty list for static fonts.
+pub fn parse_fvar(data: &[u8]) -> Option<Vec<FontAxis>> {
+    let mut font = 0;
+    if data.get(0..4)? == b"ttcf" {
+        font = read_u32(data, 12)? as usize;
+    }
+    const SFNT_VERSIONS: [&[u8]; 3] = [&[0, 1, 0, 0], b"OTTO", b"true"];
+    if !SFNT_VERSIONS.contains(&data.get(font..font + 4)?) {
+        return None;
+    }
+    let tables = read_u16(data, font + 4)? as usize;
+    let fvar = (0..tables)
+        .map(|i| font + 12 + i * 16)
+        .find(|&record| data.get(record..record + 4) == Some(b"fvar"));
+    let Some(record) = fvar else {
+        return Some(Vec::new());
+    };
+    let table = read_u32(data, record + 8)? as usize;
+
+    let axes_offset = read_u16(data, table + 4)? as usize;
+    let axis_count = read_u16(data, table + 8)? as usize;
+    let axis_size = read_u16(data, table + 10)? as usize;
+    let mut axes = Vec::with_capacity(axis_count);
+    for i in 0..axis_count {
+        let axis = table + axes_offset + i * axis_size;
+        const HIDDEN_AXIS: u16 = 0x0001;
+        if read_u16(data, axis + 16)? & HIDDEN_AXIS != 0 {
+            continue;
+        }
+        axes.push(FontAxis {
+            tag: String::from_utf8_lossy(data.get(axis..axis + 4)?)
+                .trim_end()
+                .to_string(),
+            min: read_fixed(data, axis + 4)?,
+            default: read_fixed(data, axis + 8)?,
+            max: read_fixed(data, axis + 12)?,
+        });
+    }
+    Some(axes)
+}
+
+/// Split a `font-variation` value like `wght=700` into its axis tag and value.
+pub fn parse_variation(value: &str) -> Option<(String, f32)> {
+    let (tag, number) = value.split_once('=')?;
+    let tag = tag.trim();
+    if tag.is_empty() || tag.len() > 4 {
+        return None;
+    }
+    Some((tag.to_string(), number.trim().parse().ok()?))
+}
+
+/// `value` without a needless fractional part: `700`, but `-7.5`.
+pub fn format_axis_value(value: f32) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{}", value)
+    }
+}
+
+/// Set axis `tag` in the repeatable `key`, leaving the other axes' entries alone.
+pub fn set_variation(config: &mut UserConfig, key: &str, tag: &str, value: f32) {
+    let entry = format!("{}={}", tag, format_axis_value(value));
+    let mut replaced = false;
+    let mut values: Vec<String> = Vec::new();
+    for existing in config.get_all(key) {
+        if parse_variation(existing).is_some_and(|(t, _)| t == tag) {
+            if !replaced {
+                values.push(entry.clone());
+                replaced = true;
+            }
+        } else {
+            values.push(existing.to_string());
+        }
+    }
+    if !replaced {
+        values.push(entry);
+    }
+    config.set_all(key, &values);
+}
+
+/// Drop axis `tag` from `key` so the font's default applies. Returns whether it was set.
+pub fn remove_variation(config: &mut UserConfig, key: &str, tag: &str) -> bool {
+    let all = config.get_all(key);
+    let values: Vec<String> = all
+        .iter()
+        .filter(|v| parse_variation(v).is_none_or(|(t, _)| t != tag))
+        .map(|v| v.to_string())
+        .collect();
+    if values.len() == all.len() {
+        return false;
+    }
+    config.set_all(key, &values);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// A minimal font: the table directory with only an `fvar` table.
+    fn font_with_axes(axes: &[(&[u8; 4], f32, f32, f32, u16)]) -> Vec<u8> {
+        let fixed = |v: f32| ((v * 65536.0) as i32).to_be_bytes();
+        let mut data = Vec::new();
+        data.extend(0x0001_0000u32.to_be_bytes());
+        data.extend(1u16.to_be_bytes());
+        data.extend([0; 6]);
+        let table_offset = 12 + 16;
+        data.extend(b"fvar");
+        data.extend(0u32.to_be_bytes());
+        data.extend((table_offset as u32).to_be_bytes());
+        data.extend(0u32.to_be_bytes());
+
+        data.extend(1u16.to_be_bytes());
+        data.extend(0u16.to_be_bytes());
+        data.extend(16u16.to_be_bytes());
+        data.extend(2u16.to_be_bytes());
+        data.extend((axes.len() as u16).to_be_bytes());
+        data.extend(20u16.to_be_bytes());
+        data.extend([0; 4]);
+        for (tag, min, default, max, flags) in axes {
+            data.extend(*tag);
+            data.extend(fixed(*min));
+            data.extend(fixed(*default));
+            data.extend(fixed(*max));
+            data.extend(flags.to_be_bytes());
+            data.extend(256u16.to_be_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_fvar() {
+        let data = font_with_axes(&[
+            (b"wght", 100.0, 400.0, 800.0, 0),
+            (b"slnt", -10.0, 0.0, 0.0, 0),
+            (b"XTRA", 0.0, 0.0, 1.0, 1),
+        ]);
+        let axes = parse_fvar(&data).unwrap();
+        assert_eq!(
+            axes,
+            vec![
+                FontAxis::new("wght", 100.0, 400.0, 800.0),
+                FontAxis::new("slnt", -10.0, 0.0, 0.0),
+            ]
+        );
+
+        assert_eq!(parse_fvar(&font_with_axes(&[])[..12]), Some(Vec::new()));
+        assert_eq!(parse_fvar(b"not a font"), None);
+    }
+
+    #[test]
+    fn test_parse_variation() {
+        assert_eq!(
+            parse_variation("wght = 700"),
+            Some(("wght".to_string(), 700.0))
+        );
+        assert_eq!(
+            parse_variation("slnt=-7.5"),
+            Some(("slnt".to_string(), -7.5))
+        );
+        assert_eq!(parse_variation("weight=700"), None);
+        assert_eq!(parse_variation("wght"), None);
+        assert_eq!(format_axis_value(700.0), "700");
+        assert_eq!(format_axis_value(-7.5), "-7.5");
+    }
+
+    #[test]
+    fn test_set_and_remove_variation() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/config"));
+        config.push("font-variation", "wght=500");
+        config.push("font-variation", "wdth=90");
+        set_variation(&mut config, "font-variation", "wght", 650.0);
+        set_variation(&mut config, "font-variation", "slnt", -5.0);
+        assert_eq!(
+            config.get_all("font-variation"),
+            vec!["wght=650", "wdth=90", "slnt=-5"]
+        );
+
+        assert!(remove_variation(&mut config, "font-variation", "wdth"));
+        assert!(!remove_variation(&mut config, "font-variation", "opsz"));
+        assert_eq!(
+            config.get_all("font-variation"),
+            vec!["wght=650", "slnt=-5"]
+        );
+    }
+}
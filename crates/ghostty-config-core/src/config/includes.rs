@@ -0,0 +1,99 @@
+use std::path::{Path, PathBuf};
+
+use super::file_io::{read_config, unquote_value};
+use super::model::UserConfig;
+
+/// Resolve a `config-file` value the way Ghostty does: a leading `?` marks the
+/// file optional, `~/` is the home directory, and relative paths are relative
+/// to the directory of the file that includes them.
+pub fn resolve_include(config_path: &Path, value: &str) -> PathBuf {
+    let value = value.trim().trim_start_matches('?');
+    let value = unquote_value(value);
+    if let Some(rest) = value.strip_prefix("~/") {
+        if let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+            return PathBuf::from(home).join(rest);
+        }
+    }
+    let path = PathBuf::from(value.as_ref());
+    if path.is_absolute() {
+        return path;
+    }
+    config_path
+        .parent()
+        .map(|dir| dir.join(&path))
+        .unwrap_or(path)
+}
+
+/// Files pulled in by the config's `config-file` entries, in load order.
+pub fn include_paths(config: &UserConfig) -> Vec<PathBuf> {
+    config
+        .get_all("config-file")
+        .into_iter()
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| resolve_include(&config.file_path, value))
+        .collect()
+}
+
+/// Read every included file that exists. Unreadable files are logged and skipped.
+pub fn load_included(config: &UserConfig) -> Vec<UserConfig> {
+    include_paths(config)
+        .into_iter()
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            read_config(&path)
+                .inspect_err(|e| tracing::warn!("Skipping {}: {}", path.display(), e))
+                .ok()
+        })
+        .collect()
+}
+
+/// Add a `config-file` entry for `target` unless the config already includes it.
+///
+/// Returns true if an entry was added.
+pub fn ensure_included(config: &mut UserConfig, target: &str) -> bool {
+    let path = resolve_include(&config.file_path, target);
+    if include_paths(config).contains(&path) {
+        return false;
+    }
+    let mut values: Vec<String> = config
+        .get_all("config-file")
+        .into_iter()
+        .map(String::from)
+        .collect();
+    values.push(target.trim().to_string());
+    config.set_all("config-file", &values);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::test_config;
+
+    #[test]
+    fn test_include_paths() {
+        let config = test_config(&[
+            ("config-file", "colors.conf"),
+            ("font-size", "14"),
+            ("config-file", "?/etc/ghostty/extra"),
+        ]);
+        assert_eq!(
+            include_paths(&config),
+            vec![
+                PathBuf::from("/home/me/.config/ghostty/colors.conf"),
+                PathBuf::from("/etc/ghostty/extra"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ensure_included() {
+        let mut config = test_config(&[("config-file", "?colors.conf"), ("font-size", "14")]);
+        assert!(!ensure_included(&mut config, "colors.conf"));
+        assert!(ensure_included(&mut config, "keybinds.conf"));
+        assert_eq!(
+            config.get_all("config-file"),
+            vec!["?colors.conf", "keybinds.conf"]
+        );
+    }
+}
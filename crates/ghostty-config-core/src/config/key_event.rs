@@ -0,0 +1,225 @@
+//! Turning a key press recorded in the browser into Ghostty trigger syntax.
+
+use serde::Deserialize;
+
+use super::keybind::{is_valid_key, parse_trigger};
+use super::os_shortcuts::Platform;
+use crate::Error;
+
+/// A browser `KeyboardEvent`, as far as a trigger needs it.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct KeyEvent {
+    /// `KeyboardEvent.key`: the character or named key produced.
+    pub key: String,
+    /// `KeyboardEvent.code`: the physical key, regardless of layout and modifiers.
+    #[serde(default)]
+    pub code: String,
+    #[serde(default)]
+    pub ctrl: bool,
+    #[serde(default)]
+    pub alt: bool,
+    #[serde(default)]
+    pub shift: bool,
+    /// The Command key on macOS, the Super/Windows key elsewhere.
+    #[serde(default)]
+    pub meta: bool,
+}
+
+/// `KeyboardEvent.key` values that are modifiers pressed on their own.
+const MODIFIER_KEYS: &[&str] = &["Control", "Alt", "AltGraph", "Shift", "Meta", "OS", "Super"];
+
+/// Named keys, by `KeyboardEvent.key`.
+const NAMED_KEYS: &[(&str, &str)] = &[
+    ("ArrowUp", "arrow_up"),
+    ("ArrowDown", "arrow_down"),
+    ("ArrowLeft", "arrow_left"),
+    ("ArrowRight", "arrow_right"),
+    ("Enter", "enter"),
+    ("Backspace", "backspace"),
+    ("Delete", "delete"),
+    ("Insert", "insert"),
+    ("Tab", "tab"),
+    ("Escape", "escape"),
+    (" ", "space"),
+    ("PageUp", "page_up"),
+    ("PageDown", "page_down"),
+    ("Home", "home"),
+    ("End", "end"),
+    ("CapsLock", "caps_lock"),
+    ("PrintScreen", "print_screen"),
+    ("Pause", "pause"),
+    ("ContextMenu", "context_menu"),
+];
+
+/// Punctuation keys, by `KeyboardEvent.code`, so shift doesn't turn `/` into `?`.
+const PUNCTUATION_CODES: &[(&str, &str)] = &[
+    ("Backquote", "backquote"),
+    ("Backslash", "backslash"),
+    ("BracketLeft", "bracket_left"),
+    ("BracketRight", "bracket_right"),
+    ("Comma", "comma"),
+    ("Equal", "equal"),
+    ("Minus", "minus"),
+    ("Period", "period"),
+    ("Quote", "quote"),
+    ("Semicolon", "semicolon"),
+    ("Slash", "slash"),
+    ("IntlBackslash", "intl_backslash"),
+];
+
+/// The trigger `event` presses, with the platform's name for the Command/Super key.
+///
+/// Returns `Ok(None)` for a modifier pressed on its own, since the recorder keeps
+/// listening until a real key arrives.
+pub fn trigger_for_event(event: &KeyEvent, platform: Platform) -> Result<Option<String>, Error> {
+    if MODIFIER_KEYS.contains(&event.key.as_str()) {
+        return Ok(None);
+    }
+    let key = event_key(event)
+        .ok_or_else(|| Error::Config(format!("Ghostty has no name for the key '{}'", event.key)))?;
+
+    let mut parts = Vec::new();
+    if event.ctrl {
+        parts.push("ctrl");
+    }
+    if event.alt {
+        parts.push("alt");
+    }
+    if event.shift {
+        parts.push("shift");
+    }
+    if event.meta {
+        parts.push(match platform {
+            Platform::MacOS => "cmd",
+            Platform::Linux => "super",
+        });
+    }
+    parts.push(&key);
+    let trigger = parts.join("+");
+    parse_trigger(&trigger)?;
+    Ok(Some(trigger))
+}
+
+/// The key name for `event`, ignoring its modifiers.
+///
+/// Letters and digits come from the physical key, since shift and option change
+/// the character they type (`shift+1` types `!`, `alt+a` types `å` on macOS).
+fn event_key(event: &KeyEvent) -> Option<String> {
+    let code = event.code.as_str();
+    if let Some(letter) = code.strip_prefix("Key").filter(|l| l.len() == 1) {
+        return Some(letter.to_ascii_lowercase());
+    }
+    if let Some(digit) = code.strip_prefix("Digit").filter(|d| d.len() == 1) {
+        return Some(digit.to_string());
+    }
+    if let Some((_, name)) = PUNCTUATION_CODES.iter().find(|(c, _)| *c == code) {
+        return Some(name.to_string());
+    }
+    if let Some(numpad) = code.strip_prefix("Numpad") {
+        let name = format!("numpad_{}", camel_to_snake(numpad));
+        if is_valid_key(&name) {
+            return Some(name);
+        }
+    }
+    if let Some((_, name)) = NAMED_KEYS.iter().find(|(k, _)| *k == event.key) {
+        return Some(name.to_string());
+    }
+    let key = event.key.to_lowercase();
+    // Function keys, and characters from keys without a known code.
+    is_valid_key(&key).then_some(key)
+}
+
+/// `PageUp` as `page_up`.
+fn camel_to_snake(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(key: &str, code: &str) -> KeyEvent {
+        KeyEvent {
+            key: key.to_string(),
+            code: code.to_string(),
+            ..KeyEvent::default()
+        }
+    }
+
+    fn trigger(event: &KeyEvent, platform: Platform) -> Option<String> {
+        trigger_for_event(event, platform).unwrap()
+    }
+
+    #[test]
+    fn test_modifiers_by_platform() {
+        let event = KeyEvent {
+            ctrl: true,
+            shift: true,
+            meta: true,
+            ..press("Enter", "Enter")
+        };
+        assert_eq!(
+            trigger(&event, Platform::Linux),
+            Some("ctrl+shift+super+enter".to_string())
+        );
+        assert_eq!(
+            trigger(&event, Platform::MacOS),
+            Some("ctrl+shift+cmd+enter".to_string())
+        );
+    }
+
+    #[test]
+    fn test_keys_use_physical_codes() {
+        let shifted = KeyEvent {
+            shift: true,
+            ..press("!", "Digit1")
+        };
+        assert_eq!(
+            trigger(&shifted, Platform::Linux),
+            Some("shift+1".to_string())
+        );
+        let option = KeyEvent {
+            alt: true,
+            ..press("å", "KeyA")
+        };
+        assert_eq!(trigger(&option, Platform::MacOS), Some("alt+a".to_string()));
+        let slash = KeyEvent {
+            shift: true,
+            ..press("?", "Slash")
+        };
+        assert_eq!(
+            trigger(&slash, Platform::Linux),
+            Some("shift+slash".to_string())
+        );
+        assert_eq!(
+            trigger(&press("Enter", "NumpadEnter"), Platform::Linux),
+            Some("numpad_enter".to_string())
+        );
+        assert_eq!(
+            trigger(&press("ArrowUp", "ArrowUp"), Platform::Linux),
+            Some("arrow_up".to_string())
+        );
+        assert_eq!(
+            trigger(&press("F12", "F12"), Platform::Linux),
+            Some("f12".to_string())
+        );
+    }
+
+    #[test]
+    fn test_modifier_only_and_unknown_keys() {
+        let shift = KeyEvent {
+            shift: true,
+            ..press("Shift", "ShiftLeft")
+        };
+        assert_eq!(trigger(&shift, Platform::Linux), None);
+        assert!(trigger_for_event(&press("Unidentified", ""), Platform::Linux).is_err());
+    }
+}
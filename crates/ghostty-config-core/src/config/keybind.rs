@@ -0,0 +1,403 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::Error;
+
+/// Named keys Ghostty accepts in a trigger, besides single characters.
+///
+/// Includes both the W3C-style names (`arrow_up`, `bracket_left`) and the older
+/// names (`up`, `left_bracket`) that Ghostty still parses.
+const KEY_NAMES: &[&str] = &[
+    // Digits and letters spelled out (W3C)
+    "digit_0",
+    "digit_1",
+    "digit_2",
+    "digit_3",
+    "digit_4",
+    "digit_5",
+    "digit_6",
+    "digit_7",
+    "digit_8",
+    "digit_9",
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    // Punctuation
+    "backquote",
+    "grave_accent",
+    "backslash",
+    "bracket_left",
+    "bracket_right",
+    "left_bracket",
+    "right_bracket",
+    "comma",
+    "equal",
+    "minus",
+    "period",
+    "quote",
+    "apostrophe",
+    "semicolon",
+    "slash",
+    "plus",
+    "intl_backslash",
+    "intl_ro",
+    "intl_yen",
+    // Whitespace and editing
+    "backspace",
+    "enter",
+    "space",
+    "tab",
+    "delete",
+    "insert",
+    "escape",
+    // Navigation
+    "arrow_up",
+    "arrow_down",
+    "arrow_left",
+    "arrow_right",
+    "up",
+    "down",
+    "left",
+    "right",
+    "home",
+    "end",
+    "page_up",
+    "page_down",
+    // Locks and system
+    "caps_lock",
+    "num_lock",
+    "scroll_lock",
+    "print_screen",
+    "pause",
+    "context_menu",
+    "fn",
+    "fn_lock",
+    "help",
+    // Modifier keys bound on their own
+    "alt_left",
+    "alt_right",
+    "control_left",
+    "control_right",
+    "meta_left",
+    "meta_right",
+    "shift_left",
+    "shift_right",
+    "left_alt",
+    "right_alt",
+    "left_control",
+    "right_control",
+    "left_super",
+    "right_super",
+    "left_shift",
+    "right_shift",
+    // Numpad
+    "numpad_0",
+    "numpad_1",
+    "numpad_2",
+    "numpad_3",
+    "numpad_4",
+    "numpad_5",
+    "numpad_6",
+    "numpad_7",
+    "numpad_8",
+    "numpad_9",
+    "numpad_add",
+    "numpad_subtract",
+    "numpad_multiply",
+    "numpad_divide",
+    "numpad_decimal",
+    "numpad_separator",
+    "numpad_enter",
+    "numpad_equal",
+    "numpad_up",
+    "numpad_down",
+    "numpad_left",
+    "numpad_right",
+    "numpad_begin",
+    "numpad_home",
+    "numpad_end",
+    "numpad_insert",
+    "numpad_delete",
+    "numpad_page_up",
+    "numpad_page_down",
+    "kp_0",
+    "kp_1",
+    "kp_2",
+    "kp_3",
+    "kp_4",
+    "kp_5",
+    "kp_6",
+    "kp_7",
+    "kp_8",
+    "kp_9",
+    "kp_decimal",
+    "kp_divide",
+    "kp_multiply",
+    "kp_subtract",
+    "kp_add",
+    "kp_enter",
+    "kp_equal",
+    // Media
+    "audio_volume_down",
+    "audio_volume_mute",
+    "audio_volume_up",
+    "media_play_pause",
+    "media_stop",
+    "media_track_next",
+    "media_track_previous",
+    // Mouse buttons offered by the keybinding editor
+    "mouse_button_left",
+    "mouse_button_middle",
+    "mouse_button_right",
+    "mouse_button_4",
+    "mouse_button_5",
+];
+
+/// Prefixes that change how a binding is dispatched, not which keys fire it.
+const FLAGS: &[&str] = &["global", "all", "unconsumed", "performable"];
+
+/// A keyboard modifier, in the order they are written in a canonical trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Modifier {
+    Ctrl,
+    Alt,
+    Shift,
+    Super,
+}
+
+impl Modifier {
+    /// Parse a modifier name, accepting Ghostty's aliases (`cmd`, `opt`, `control`, ...).
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "ctrl" | "control" => Some(Modifier::Ctrl),
+            "alt" | "opt" | "option" => Some(Modifier::Alt),
+            "shift" => Some(Modifier::Shift),
+            "super" | "cmd" | "command" => Some(Modifier::Super),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Modifier::Ctrl => "ctrl",
+            Modifier::Alt => "alt",
+            Modifier::Shift => "shift",
+            Modifier::Super => "super",
+        }
+    }
+}
+
+/// One key press with its modifiers, e.g. `ctrl+shift+t`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Chord {
+    /// Sorted and deduplicated.
+    pub modifiers: Vec<Modifier>,
+    pub key: String,
+    /// `physical:` — match the key by position rather than the character it produces.
+    pub physical: bool,
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for m in &self.modifiers {
+            write!(f, "{}+", m.as_str())?;
+        }
+        if self.physical {
+            write!(f, "physical:")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// A parsed keybind trigger: optional flags and one or more chords (`ctrl+a>n`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Trigger {
+    pub flags: Vec<String>,
+    pub sequence: Vec<Chord>,
+}
+
+impl Trigger {
+    /// The chords in canonical form without flags; equal for triggers that fire on the same keys.
+    pub fn keys(&self) -> String {
+        self.sequence
+            .iter()
+            .map(Chord::to_string)
+            .collect::<Vec<_>>()
+            .join(">")
+    }
+}
+
+impl fmt::Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for flag in &self.flags {
+            write!(f, "{}:", flag)?;
+        }
+        write!(f, "{}", self.keys())
+    }
+}
+
+/// Whether `key` is a key Ghostty accepts: a known name or a single character.
+pub fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => !c.is_whitespace() && !c.is_control(),
+        _ => KEY_NAMES.contains(&key) || is_function_key(key),
+    }
+}
+
+fn is_function_key(key: &str) -> bool {
+    key.strip_prefix('f')
+        .and_then(|n| n.parse::<u8>().ok())
+        .is_some_and(|n| (1..=25).contains(&n))
+}
+
+fn parse_chord(chord: &str) -> Result<Chord, Error> {
+    if chord.is_empty() {
+        return Err(Error::Config("Empty key in trigger sequence".to_string()));
+    }
+
+    let mut modifiers = Vec::new();
+    let mut key: Option<(&str, bool)> = None;
+    for part in chord.split('+') {
+        if part.is_empty() {
+            return Err(Error::Config(format!("Empty key in '{}'", chord)));
+        }
+        if let Some(m) = Modifier::parse(part) {
+            modifiers.push(m);
+            continue;
+        }
+        let (name, physical) = match part.strip_prefix("physical:") {
+            Some(name) => (name, true),
+            None => (part, false),
+        };
+        if !is_valid_key(name) {
+            return Err(Error::Config(format!("Unknown key '{}'", name)));
+        }
+        if key.replace((name, physical)).is_some() {
+            return Err(Error::Config(format!(
+                "'{}' has more than one non-modifier key",
+                chord
+            )));
+        }
+    }
+
+    let Some((key, physical)) = key else {
+        return Err(Error::Config(format!("'{}' has no key", chord)));
+    };
+    modifiers.sort();
+    modifiers.dedup();
+    Ok(Chord {
+        modifiers,
+        key: key.to_string(),
+        physical,
+    })
+}
+
+/// Parse and validate a keybind trigger.
+///
+/// Names are case-insensitive. Sequences are written `a>b`; they can't be combined
+/// with the `global:` or `all:` flags.
+pub fn parse_trigger(trigger: &str) -> Result<Trigger, Error> {
+    let lowered = trigger.trim().to_lowercase();
+    if lowered.is_empty() {
+        return Err(Error::Config("Trigger is empty".to_string()));
+    }
+
+    let mut rest = lowered.as_str();
+    let mut flags = Vec::new();
+    while let Some((prefix, tail)) = rest.split_once(':') {
+        if !FLAGS.contains(&prefix) {
+            break;
+        }
+        flags.push(prefix.to_string());
+        rest = tail;
+    }
+
+    let sequence = rest
+        .split('>')
+        .map(|chord| parse_chord(chord.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if sequence.len() > 1 && flags.iter().any(|f| f == "global" || f == "all") {
+        return Err(Error::Config(
+            "Key sequences can't be global or all".to_string(),
+        ));
+    }
+
+    Ok(Trigger { flags, sequence })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_trigger() {
+        let trigger = parse_trigger("shift+ctrl+t").unwrap();
+        assert!(trigger.flags.is_empty());
+        assert_eq!(trigger.sequence.len(), 1);
+        assert_eq!(
+            trigger.sequence[0].modifiers,
+            vec![Modifier::Ctrl, Modifier::Shift]
+        );
+        assert_eq!(trigger.sequence[0].key, "t");
+        assert_eq!(trigger.to_string(), "ctrl+shift+t");
+    }
+
+    #[test]
+    fn test_parse_aliases_and_case() {
+        assert_eq!(
+            parse_trigger("Cmd+Opt+K").unwrap().to_string(),
+            "alt+super+k"
+        );
+    }
+
+    #[test]
+    fn test_parse_physical_and_flags() {
+        let trigger = parse_trigger("global:unconsumed:ctrl+physical:grave_accent").unwrap();
+        assert_eq!(trigger.flags, vec!["global", "unconsumed"]);
+        assert!(trigger.sequence[0].physical);
+        assert_eq!(trigger.keys(), "ctrl+physical:grave_accent");
+        assert_eq!(
+            trigger.to_string(),
+            "global:unconsumed:ctrl+physical:grave_accent"
+        );
+    }
+
+    #[test]
+    fn test_parse_sequence() {
+        let trigger = parse_trigger("ctrl+a>n").unwrap();
+        assert_eq!(trigger.sequence.len(), 2);
+        assert_eq!(trigger.sequence[1].key, "n");
+        assert!(trigger.sequence[1].modifiers.is_empty());
+        assert_eq!(trigger.keys(), "ctrl+a>n");
+    }
+
+    #[test]
+    fn test_parse_named_and_function_keys() {
+        assert!(parse_trigger("super+arrow_up").is_ok());
+        assert!(parse_trigger("ctrl+page_down").is_ok());
+        assert!(parse_trigger("f12").is_ok());
+        assert!(parse_trigger("mouse_button_left").is_ok());
+        assert!(parse_trigger("f26").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        assert!(parse_trigger("").is_err());
+        assert!(parse_trigger("ctrl+").is_err());
+        assert!(parse_trigger("ctrl+shift").is_err());
+        assert!(parse_trigger("ctrl+a+b").is_err());
+        assert!(parse_trigger("ctrl+notakey").is_err());
+        assert!(parse_trigger("ctrl+a>").is_err());
+        assert!(parse_trigger("global:ctrl+a>n").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use serde::Serialize;
+
+use super::keybind::parse_trigger;
+use crate::cli::keybinds::Keybinding;
+
+/// Where a conflicting binding comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ConflictSource {
+    /// One of Ghostty's built-in bindings.
+    Default,
+    /// A `keybind` entry in the user's config.
+    Custom,
+    /// A shortcut the operating system takes before Ghostty sees it.
+    System,
+}
+
+/// An existing binding that fires on the same keys as a new trigger.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeybindConflict {
+    pub trigger: String,
+    pub action: String,
+    pub source: ConflictSource,
+}
+
+/// Normalize a trigger so equivalent spellings compare equal.
+///
+/// Uses the canonical key form from [`parse_trigger`], which drops flags like
+/// `global:`, resolves modifier aliases and sorts modifiers. Triggers that don't
+/// parse fall back to their trimmed, lowercased text.
+pub fn normalize_trigger(trigger: &str) -> String {
+    parse_trigger(trigger)
+        .map(|t| t.keys())
+        .unwrap_or_else(|_| trigger.trim().to_lowercase())
+}
+
+/// Whether two triggers fire on the same keys.
+pub fn same_trigger(a: &str, b: &str) -> bool {
+    normalize_trigger(a) == normalize_trigger(b)
+}
+
+/// Find existing default and custom bindings that share `trigger`.
+///
+/// `custom` holds raw `keybind` values (`trigger=action`). Defaults that the user
+/// already overrides are reported too, since adding another binding still shadows them.
+pub fn find_conflicts(
+    trigger: &str,
+    defaults: &[Keybinding],
+    custom: &[&str],
+) -> Vec<KeybindConflict> {
+    let wanted = normalize_trigger(trigger);
+    let mut conflicts = Vec::new();
+
+    for kb in defaults {
+        if normalize_trigger(&kb.trigger) == wanted {
+            conflicts.push(KeybindConflict {
+                trigger: kb.trigger.clone(),
+                action: kb.action.clone(),
+                source: ConflictSource::Default,
+            });
+        }
+    }
+
+    for value in custom {
+        if let Some((t, a)) = value.split_once('=') {
+            if normalize_trigger(t) == wanted {
+                conflicts.push(KeybindConflict {
+                    trigger: t.trim().to_string(),
+                    action: a.trim().to_string(),
+                    source: ConflictSource::Custom,
+                });
+            }
+        }
+    }
+
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kb(trigger: &str, action: &str) -> Keybinding {
+        Keybinding {
+            trigger: trigger.to_string(),
+            action: action.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_modifier_order() {
+        assert_eq!(normalize_trigger("shift+ctrl+t"), "ctrl+shift+t");
+        assert_eq!(
+            normalize_trigger("super+alt+ctrl+shift+a"),
+            "ctrl+alt+shift+super+a"
+        );
+    }
+
+    #[test]
+    fn test_normalize_aliases() {
+        assert_eq!(normalize_trigger("cmd+c"), "super+c");
+        assert_eq!(normalize_trigger("Command+Option+c"), "alt+super+c");
+        assert_eq!(normalize_trigger("control+c"), "ctrl+c");
+    }
+
+    #[test]
+    fn test_normalize_flags_and_sequences() {
+        assert_eq!(
+            normalize_trigger("global:cmd+grave_accent"),
+            "super+grave_accent"
+        );
+        assert_eq!(normalize_trigger("shift+ctrl+a>n"), "ctrl+shift+a>n");
+        assert_eq!(normalize_trigger("ctrl+physical:a"), "ctrl+physical:a");
+    }
+
+    #[test]
+    fn test_same_trigger() {
+        assert!(same_trigger("cmd+shift+t", "shift+super+t"));
+        assert!(!same_trigger("ctrl+t", "ctrl+shift+t"));
+    }
+
+    #[test]
+    fn test_find_conflicts_default_and_custom() {
+        let defaults = vec![kb("super+t", "new_tab"), kb("super+w", "close_surface")];
+        let custom = vec!["cmd+t=new_window", "ctrl+a=select_all"];
+        let conflicts = find_conflicts("t+cmd", &defaults, &custom);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].source, ConflictSource::Default);
+        assert_eq!(conflicts[0].action, "new_tab");
+        assert_eq!(conflicts[1].source, ConflictSource::Custom);
+        assert_eq!(conflicts[1].action, "new_window");
+    }
+
+    #[test]
+    fn test_find_conflicts_none() {
+        let defaults = vec![kb("super+t", "new_tab")];
+        assert!(find_conflicts("super+shift+t", &defaults, &[]).is_empty());
+    }
+}
@@ -0,0 +1,878 @@
+//! Keybindings from iTerm2, kitty, and WezTerm configs, translated to Ghostty's.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::keybind::{is_valid_key, parse_trigger};
+use crate::Error;
+
+/// Terminal configs whose keybindings can be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeymapFormat {
+    /// An exported `.itermkeymap` (JSON) or the key map in iTerm2's preferences plist.
+    ITerm,
+    Kitty,
+    Wezterm,
+}
+
+impl KeymapFormat {
+    /// Parse the format named in the import form; `auto` (or anything else) detects it.
+    pub fn parse(name: &str, text: &str) -> Result<Self, Error> {
+        match name.trim() {
+            "iterm" => Ok(KeymapFormat::ITerm),
+            "kitty" => Ok(KeymapFormat::Kitty),
+            "wezterm" => Ok(KeymapFormat::Wezterm),
+            _ => Self::detect(text)
+                .ok_or_else(|| Error::Config("Could not tell which format this is".to_string())),
+        }
+    }
+
+    /// Guess the format from the text.
+    pub fn detect(text: &str) -> Option<Self> {
+        if ITERM_KEY_RE.is_match(text) {
+            return Some(KeymapFormat::ITerm);
+        }
+        if text
+            .lines()
+            .any(|l| l.trim_start().starts_with("map ") || l.trim_start().starts_with("kitty_mod "))
+        {
+            return Some(KeymapFormat::Kitty);
+        }
+        if text.contains("wezterm") || WEZTERM_KEY_RE.is_match(text) {
+            return Some(KeymapFormat::Wezterm);
+        }
+        None
+    }
+}
+
+/// A binding that couldn't be translated, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedBinding {
+    /// The binding as written in the imported config.
+    pub source: String,
+    pub reason: String,
+}
+
+/// Keybindings read from another terminal's config, in Ghostty terms.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportedKeybinds {
+    /// `trigger=action` values for `keybind` entries.
+    pub bindings: Vec<String>,
+    pub skipped: Vec<SkippedBinding>,
+}
+
+/// A binding as a parser found it: the trigger and Ghostty action it maps to, or
+/// why it doesn't map.
+struct Found {
+    source: String,
+    binding: Result<(String, String), String>,
+}
+
+/// Read the keybindings out of an iTerm2, kitty, or WezTerm config.
+///
+/// Actions are translated to the closest Ghostty action. One that has no
+/// equivalent, or whose equivalent isn't in `actions` (the running Ghostty's
+/// `+list-actions`; empty to skip the check), is listed as skipped.
+pub fn import_keybinds(
+    text: &str,
+    format: KeymapFormat,
+    actions: &[String],
+) -> Result<ImportedKeybinds, Error> {
+    let mut imported = ImportedKeybinds::default();
+    for (source, checked) in translate_keybinds(text, format, actions) {
+        match checked {
+            Ok(binding) => imported.bindings.push(binding),
+            Err(reason) => imported.skipped.push(SkippedBinding { source, reason }),
+        }
+    }
+    if imported.bindings.is_empty() && imported.skipped.is_empty() {
+        return Err(Error::Config("No keybindings found to import".to_string()));
+    }
+    Ok(imported)
+}
+
+/// Each binding as written, with its `trigger=action` value or why it has none.
+pub(crate) fn translate_keybinds(
+    text: &str,
+    format: KeymapFormat,
+    actions: &[String],
+) -> Vec<(String, Result<String, String>)> {
+    let found = match format {
+        KeymapFormat::ITerm => parse_iterm(text),
+        KeymapFormat::Kitty => parse_kitty(text, actions),
+        KeymapFormat::Wezterm => parse_wezterm(text, actions),
+    };
+    found
+        .into_iter()
+        .map(|Found { source, binding }| {
+            let checked =
+                binding.and_then(|(trigger, action)| check_binding(&trigger, &action, actions));
+            (source, checked)
+        })
+        .collect()
+}
+
+/// `trigger=action` if the trigger parses and, unless `actions` is empty, the
+/// running Ghostty has the action; why not otherwise.
+pub(crate) fn check_binding(
+    trigger: &str,
+    action: &str,
+    actions: &[String],
+) -> Result<String, String> {
+    parse_trigger(trigger).map_err(|e| match e {
+        Error::Config(msg) => msg,
+        other => other.to_string(),
+    })?;
+    let name = action.split(':').next().unwrap_or(action);
+    if !actions.is_empty() && !actions.iter().any(|a| a == name) {
+        return Err(format!("this Ghostty has no {} action", name));
+    }
+    Ok(format!("{}={}", trigger, action))
+}
+
+/// `action` when Ghostty has an action by that name: the closest match for an
+/// action the translation tables don't know.
+fn same_name(action: &str, actions: &[String]) -> Option<String> {
+    actions
+        .iter()
+        .any(|a| a == action)
+        .then(|| action.to_string())
+}
+
+/// Text for a `text:` action, escaped the way Ghostty reads it.
+pub(crate) fn text_action(text: &str) -> String {
+    let mut escaped = String::from("text:");
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\x{:02x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A key name from another terminal as Ghostty spells it, for the names that
+/// differ. Single characters and Ghostty's own names pass through.
+pub(crate) fn ghostty_key(name: &str) -> Option<String> {
+    let lower = name.to_lowercase();
+    let mapped = match lower.as_str() {
+        "up" | "uparrow" => "arrow_up",
+        "down" | "downarrow" => "arrow_down",
+        "left" | "leftarrow" => "arrow_left",
+        "right" | "rightarrow" => "arrow_right",
+        "return" | "enter" => "enter",
+        "esc" | "escape" => "escape",
+        "pageup" | "page_up" | "pgup" => "page_up",
+        "pagedown" | "page_down" | "pgdn" => "page_down",
+        "del" | "delete" => "delete",
+        "grave" => "backquote",
+        "apostrophe" => "quote",
+        "left_bracket" => "bracket_left",
+        "right_bracket" => "bracket_right",
+        other => other,
+    };
+    is_valid_key(mapped).then(|| mapped.to_string())
+}
+
+/// A modifier name from another terminal as Ghostty spells it.
+pub(crate) fn ghostty_modifier(name: &str) -> Option<&'static str> {
+    match name.to_lowercase().as_str() {
+        "ctrl" | "control" | "⌃" => Some("ctrl"),
+        "shift" | "⇧" => Some("shift"),
+        "alt" | "opt" | "option" | "meta" | "⌥" => Some("alt"),
+        "cmd" | "command" | "super" | "win" | "⌘" => Some("super"),
+        _ => None,
+    }
+}
+
+/// Join modifiers and a key into one chord, e.g. `ctrl+shift+t`.
+pub(crate) fn chord(modifiers: &[&str], key: &str) -> String {
+    let mut parts = modifiers.to_vec();
+    parts.push(key);
+    parts.join("+")
+}
+
+// iTerm2 ---------------------------------------------------------------------
+
+/// A key map entry's key: the character, modifier flags, and optionally the key code.
+static ITERM_KEY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:<key>|")(0x[0-9a-fA-F]+)-(0x[0-9a-fA-F]+)(?:-0x[0-9a-fA-F]+)?(?:</key>|")"#)
+        .unwrap()
+});
+static ITERM_ACTION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"Action"?(?:</key>\s*<integer>|\s*:\s*)(\d+)"#).unwrap());
+static ITERM_TEXT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"Text(?:</key>\s*<string>((?s:.*?))</string>|"\s*:\s*"((?:[^"\\]|\\.)*)")"#)
+        .unwrap()
+});
+
+/// Characters iTerm2 (via AppKit) uses for keys that don't type one.
+const ITERM_SPECIAL_KEYS: &[(u32, &str)] = &[
+    (0x09, "tab"),
+    (0x0d, "enter"),
+    (0x1b, "escape"),
+    (0x20, "space"),
+    (0x7f, "backspace"),
+    (0xf700, "arrow_up"),
+    (0xf701, "arrow_down"),
+    (0xf702, "arrow_left"),
+    (0xf703, "arrow_right"),
+    (0xf727, "insert"),
+    (0xf728, "delete"),
+    (0xf729, "home"),
+    (0xf72b, "end"),
+    (0xf72c, "page_up"),
+    (0xf72d, "page_down"),
+];
+
+/// AppKit modifier flags, in canonical order.
+const ITERM_MODIFIERS: &[(u64, &str)] = &[
+    (0x40000, "ctrl"),
+    (0x80000, "alt"),
+    (0x20000, "shift"),
+    (0x100000, "super"),
+];
+
+fn parse_iterm(text: &str) -> Vec<Found> {
+    let keys: Vec<_> = ITERM_KEY_RE.captures_iter(text).collect();
+    let mut found = Vec::new();
+    for (i, caps) in keys.iter().enumerate() {
+        let whole = caps.get(0).unwrap();
+        let end = keys
+            .get(i + 1)
+            .map_or(text.len(), |next| next.get(0).unwrap().start());
+        let body = &text[whole.end()..end];
+        let Some(action) = ITERM_ACTION_RE
+            .captures(body)
+            .and_then(|c| c[1].parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let text_arg = ITERM_TEXT_RE
+            .captures(body)
+            .and_then(|c| match (c.get(1), c.get(2)) {
+                (Some(xml), _) => Some(unescape_xml(xml.as_str())),
+                (_, Some(json)) => Some(unescape_json(json.as_str())),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let character = u32::from_str_radix(&caps[1][2..], 16).unwrap_or(0);
+        let flags = u64::from_str_radix(&caps[2][2..], 16).unwrap_or(0);
+        let source = format!("{}-{} (action {})", &caps[1], &caps[2], action);
+        let binding = iterm_trigger(character, flags).and_then(|trigger| {
+            let action = iterm_action(action, &text_arg)
+                .ok_or_else(|| format!("iTerm2 action {} has no Ghostty equivalent", action))?;
+            Ok((trigger, action))
+        });
+        found.push(Found { source, binding });
+    }
+    found
+}
+
+fn iterm_trigger(character: u32, flags: u64) -> Result<String, String> {
+    let key = if let Some((_, name)) = ITERM_SPECIAL_KEYS.iter().find(|(c, _)| *c == character) {
+        name.to_string()
+    } else if (0xf704..=0xf71b).contains(&character) {
+        format!("f{}", character - 0xf704 + 1)
+    } else {
+        char::from_u32(character)
+            .filter(|c| !c.is_control())
+            .map(|c| c.to_lowercase().to_string())
+            .ok_or_else(|| format!("unknown key code 0x{:x}", character))?
+    };
+    let modifiers: Vec<&str> = ITERM_MODIFIERS
+        .iter()
+        .filter(|(flag, _)| flags & flag != 0)
+        .map(|(_, name)| *name)
+        .collect();
+    Ok(chord(&modifiers, &key))
+}
+
+/// The Ghostty action for one of iTerm2's numbered key actions.
+fn iterm_action(action: u32, text: &str) -> Option<String> {
+    let action = match action {
+        0 => "next_tab",
+        2 => "previous_tab",
+        4 => "scroll_to_bottom",
+        5 => "scroll_to_top",
+        6 => "scroll_page_lines:1",
+        7 => "scroll_page_lines:-1",
+        8 => "scroll_page_down",
+        9 => "scroll_page_up",
+        10 => return Some(format!("esc:{}", text)),
+        11 => {
+            let bytes: Option<String> = text
+                .split_whitespace()
+                .map(|b| {
+                    let b = b.strip_prefix("0x").unwrap_or(b);
+                    u8::from_str_radix(b, 16)
+                        .ok()
+                        .map(|b| format!("\\x{:02x}", b))
+                })
+                .collect();
+            return bytes
+                .filter(|b| !b.is_empty())
+                .map(|b| format!("text:{}", b));
+        }
+        12 => return Some(text_action(text)),
+        13 => "ignore",
+        16 => "text:\\x08",
+        17 => "text:\\x7f",
+        18 => "goto_split:left",
+        19 => "goto_split:right",
+        20 => "goto_split:up",
+        21 => "goto_split:down",
+        23 => "toggle_fullscreen",
+        26 => "new_window",
+        27 => "new_tab",
+        // iTerm2's horizontal split stacks the panes.
+        28 => "new_split:down",
+        29 => "new_split:right",
+        30 => "goto_split:next",
+        31 => "goto_split:previous",
+        33 => "move_tab:-1",
+        34 => "move_tab:1",
+        _ => return None,
+    };
+    Some(action.to_string())
+}
+
+/// Undo the escapes in a JSON string, for the ones iTerm2 writes.
+fn unescape_json(text: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('t') => unescaped.push('\t'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// kitty ----------------------------------------------------------------------
+
+fn parse_kitty(text: &str, actions: &[String]) -> Vec<Found> {
+    let mut kitty_mod = vec!["ctrl", "shift"];
+    let mut found = Vec::new();
+    for line in text.lines().map(str::trim) {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("kitty_mod") => {
+                if let Some(mods) = words.next() {
+                    kitty_mod = mods.split('+').filter_map(ghostty_modifier).collect();
+                }
+            }
+            Some("map") => {
+                let rest: Vec<&str> = words.collect();
+                let binding = if rest.first().is_some_and(|w| w.starts_with("--")) {
+                    Err("kitty's map options have no Ghostty equivalent".to_string())
+                } else if rest.len() < 2 {
+                    Err("no action".to_string())
+                } else {
+                    kitty_trigger(rest[0], &kitty_mod).and_then(|trigger| {
+                        let action = kitty_action(rest[1], &rest[2..])
+                            .or_else(|| same_name(rest[1], actions))
+                            .ok_or_else(|| {
+                                format!("kitty's {} has no Ghostty equivalent", rest[1])
+                            })?;
+                        Ok((trigger, action))
+                    })
+                };
+                found.push(Found {
+                    source: line.to_string(),
+                    binding,
+                });
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+fn kitty_trigger(keys: &str, kitty_mod: &[&str]) -> Result<String, String> {
+    let chords: Result<Vec<String>, String> = keys
+        .split('>')
+        .map(|chord_text| {
+            let mut modifiers = Vec::new();
+            let mut key = None;
+            // `+` on its own is the plus key, not a separator.
+            let parts: Vec<&str> = match chord_text.strip_suffix("++") {
+                Some(modifiers) => modifiers.split('+').chain(["plus"]).collect(),
+                None => chord_text.split('+').collect(),
+            };
+            for part in parts {
+                if part == "kitty_mod" {
+                    modifiers.extend_from_slice(kitty_mod);
+                } else if let Some(m) = ghostty_modifier(part) {
+                    modifiers.push(m);
+                } else {
+                    key = Some(ghostty_key(part).ok_or_else(|| format!("unknown key '{}'", part))?);
+                }
+            }
+            let key = key.ok_or_else(|| format!("'{}' has no key", chord_text))?;
+            Ok(chord(&modifiers, &key))
+        })
+        .collect();
+    Ok(chords?.join(">"))
+}
+
+/// The Ghostty action for a kitty action and its arguments.
+fn kitty_action(action: &str, args: &[&str]) -> Option<String> {
+    let arg = |i: usize| args.get(i).copied().unwrap_or("");
+    let mapped = match action {
+        "new_tab" | "new_tab_with_cwd" => "new_tab".to_string(),
+        "close_tab" => "close_tab".to_string(),
+        "next_tab"
+        | "previous_tab"
+        | "toggle_fullscreen"
+        | "copy_to_clipboard"
+        | "paste_from_clipboard"
+        | "paste_from_selection"
+        | "scroll_page_up"
+        | "scroll_page_down"
+        | "quit" => action.to_string(),
+        "goto_tab" => match arg(0).parse::<u32>() {
+            Ok(n) if n > 0 => format!("goto_tab:{}", n),
+            _ => return None,
+        },
+        // kitty's windows are Ghostty's splits, and its OS windows Ghostty's windows.
+        "new_window" | "new_window_with_cwd" => "new_split:auto".to_string(),
+        "close_window" => "close_surface".to_string(),
+        "new_os_window" | "new_os_window_with_cwd" => "new_window".to_string(),
+        "close_os_window" => "close_window".to_string(),
+        "next_window" => "goto_split:next".to_string(),
+        "previous_window" => "goto_split:previous".to_string(),
+        "neighboring_window" => match arg(0) {
+            "left" | "right" => format!("goto_split:{}", arg(0)),
+            "top" | "up" => "goto_split:up".to_string(),
+            "bottom" | "down" => "goto_split:down".to_string(),
+            _ => return None,
+        },
+        "toggle_layout" if arg(0) == "stack" => "toggle_split_zoom".to_string(),
+        "toggle_maximized" => "toggle_maximize".to_string(),
+        "change_font_size" => {
+            let amount = arg(1);
+            match amount.parse::<f64>() {
+                Ok(n) if amount.starts_with('+') => format!("increase_font_size:{}", n),
+                Ok(n) if amount.starts_with('-') => format!("decrease_font_size:{}", -n),
+                Ok(0.0) => "reset_font_size".to_string(),
+                _ => return None,
+            }
+        }
+        "scroll_line_up" => "scroll_page_lines:-1".to_string(),
+        "scroll_line_down" => "scroll_page_lines:1".to_string(),
+        "scroll_home" => "scroll_to_top".to_string(),
+        "scroll_end" => "scroll_to_bottom".to_string(),
+        "clear_terminal" => "clear_screen".to_string(),
+        "load_config_file" => "reload_config".to_string(),
+        "edit_config_file" => "open_config".to_string(),
+        "move_tab_forward" => "move_tab:1".to_string(),
+        "move_tab_backward" => "move_tab:-1".to_string(),
+        // kitty's escapes (`\x1b`, `\n`) read the same in Ghostty.
+        "send_text" if args.len() > 1 => format!("text:{}", args[1..].join(" ")),
+        "no_op" | "discard_event" => "unbind".to_string(),
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+// WezTerm --------------------------------------------------------------------
+
+static WEZTERM_KEY_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bkey\s*=\s*["']"#).unwrap());
+static WEZTERM_FIELD_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\b(key|mods)\s*=\s*["']([^"']*)["']"#).unwrap());
+static WEZTERM_LEADER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bleader\s*=\s*\{").unwrap());
+static WEZTERM_ACTION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\baction\s*=\s*").unwrap());
+
+/// The text from the `{` at `start` through its matching `}`.
+fn braced(text: &str, start: usize) -> Option<&str> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, c) in text[start..].char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('{', None) => depth += 1,
+            ('}', None) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[start..=start + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Where the expression starting at `text` ends: at a top-level `,` or the closing `}`.
+fn expression_end(text: &str) -> usize {
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('{' | '(', None) => depth += 1,
+            ('}' | ')', None) if depth == 0 => return i,
+            ('}' | ')', None) => depth -= 1,
+            (',', None) if depth == 0 => return i,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// The `key` and `mods` fields of one `{ key = ..., mods = ... }` table.
+fn wezterm_fields(entry: &str) -> (Option<String>, String) {
+    let mut key = None;
+    let mut mods = String::new();
+    for caps in WEZTERM_FIELD_RE.captures_iter(entry) {
+        match &caps[1] {
+            "key" => key = Some(caps[2].to_string()),
+            _ => mods = caps[2].to_string(),
+        }
+    }
+    (key, mods)
+}
+
+fn wezterm_chord(key: &str, mods: &str) -> Result<String, String> {
+    let (key, physical) = match key.strip_prefix("phys:") {
+        Some(key) => (key, true),
+        None => (key.strip_prefix("mapped:").unwrap_or(key), false),
+    };
+    let mut name = ghostty_key(key).ok_or_else(|| format!("unknown key '{}'", key))?;
+    if physical {
+        name = format!("physical:{}", name);
+    }
+    let modifiers: Vec<&str> = mods
+        .split(['|', '+', ' '])
+        .filter(|m| !m.is_empty() && *m != "NONE")
+        .map(|m| ghostty_modifier(m).ok_or_else(|| format!("unknown modifier '{}'", m)))
+        .collect::<Result<_, _>>()?;
+    Ok(chord(&modifiers, &name))
+}
+
+fn parse_wezterm(text: &str, actions: &[String]) -> Vec<Found> {
+    let leader = WEZTERM_LEADER_RE.find(text).and_then(|m| {
+        let (key, mods) = wezterm_fields(braced(text, m.end() - 1)?);
+        wezterm_chord(&key?, &mods).ok()
+    });
+
+    let mut found = Vec::new();
+    let mut seen_until = 0;
+    for m in WEZTERM_KEY_RE.find_iter(text) {
+        // Each binding is the innermost table holding a `key`.
+        let Some(start) = text[..m.start()].rfind('{') else {
+            continue;
+        };
+        if start < seen_until {
+            continue;
+        }
+        let Some(entry) = braced(text, start) else {
+            continue;
+        };
+        seen_until = start + entry.len();
+        let Some(field) = WEZTERM_ACTION_RE.find(entry) else {
+            // The leader key itself, or a table that isn't a binding.
+            continue;
+        };
+        let end = field.end() + expression_end(&entry[field.end()..]);
+        let action = entry[field.end()..end].trim();
+        // Read `key` and `mods` around the action, which may hold tables of its own.
+        let others = format!("{}{}", &entry[..field.start()], &entry[end..]);
+        let (Some(key), mods) = wezterm_fields(&others) else {
+            continue;
+        };
+
+        let mods_without_leader: Vec<&str> = mods.split('|').filter(|m| *m != "LEADER").collect();
+        let uses_leader = mods_without_leader.len() != mods.split('|').count();
+        let trigger =
+            wezterm_chord(&key, &mods_without_leader.join("|")).and_then(|chord| {
+                match (uses_leader, &leader) {
+                    (false, _) => Ok(chord),
+                    (true, Some(leader)) => Ok(format!("{}>{}", leader, chord)),
+                    (true, None) => Err("uses a leader key that isn't set".to_string()),
+                }
+            });
+        let binding = trigger.and_then(|trigger| {
+            let translated = wezterm_action(action)
+                .or_else(|| same_name(&snake_case(wezterm_action_parts(action).0), actions))
+                .ok_or_else(|| {
+                    let name = action
+                        .split(|c: char| c == '(' || c == '{' || c.is_whitespace())
+                        .next()
+                        .unwrap_or(action);
+                    format!("WezTerm's {} has no Ghostty equivalent", name)
+                })?;
+            Ok((trigger, translated))
+        });
+        found.push(Found {
+            source: entry.split_whitespace().collect::<Vec<_>>().join(" "),
+            binding,
+        });
+    }
+    found
+}
+
+/// Split a WezTerm action like `act.ActivateTabRelative(1)` or
+/// `wezterm.action{SpawnTab="CurrentPaneDomain"}` into its name and argument.
+fn wezterm_action_parts(action: &str) -> (&str, &str) {
+    let action = action.trim();
+    let action = ["wezterm.action.", "act.", "action."]
+        .iter()
+        .find_map(|p| action.strip_prefix(p))
+        .unwrap_or(action);
+    // The older `wezterm.action{Name=arg}` and `"Name"` forms.
+    let action = action
+        .strip_prefix("wezterm.action")
+        .filter(|a| a.trim_start().starts_with('{'))
+        .map(|a| a.trim().trim_start_matches('{').trim_end_matches('}'))
+        .unwrap_or(action)
+        .trim_matches(['"', '\'']);
+    let name_len = action
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(action.len());
+    let arg = action[name_len..]
+        .trim()
+        .trim_start_matches('=')
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .trim()
+        .trim_matches(['"', '\'']);
+    (&action[..name_len], arg)
+}
+
+/// `ActivateTab` as `activate_tab`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+/// The Ghostty action for a WezTerm key assignment.
+fn wezterm_action(action: &str) -> Option<String> {
+    let (name, arg) = wezterm_action_parts(action);
+    let number = || arg.parse::<i64>().ok();
+    let mapped = match name {
+        "SpawnTab" => "new_tab".to_string(),
+        "SpawnWindow" => "new_window".to_string(),
+        "CloseCurrentTab" => "close_tab".to_string(),
+        "CloseCurrentPane" => "close_surface".to_string(),
+        "ActivateTabRelative" => match number()? {
+            1 => "next_tab".to_string(),
+            -1 => "previous_tab".to_string(),
+            _ => return None,
+        },
+        // WezTerm counts tabs from 0, and -1 is the last tab.
+        "ActivateTab" => match number()? {
+            -1 => "last_tab".to_string(),
+            n if n >= 0 => format!("goto_tab:{}", n + 1),
+            _ => return None,
+        },
+        "MoveTabRelative" => format!("move_tab:{}", number()?),
+        "CopyTo" => "copy_to_clipboard".to_string(),
+        "PasteFrom" if arg == "PrimarySelection" => "paste_from_selection".to_string(),
+        "PasteFrom" => "paste_from_clipboard".to_string(),
+        "IncreaseFontSize" => "increase_font_size:1".to_string(),
+        "DecreaseFontSize" => "decrease_font_size:1".to_string(),
+        "ResetFontSize" => "reset_font_size".to_string(),
+        // WezTerm's horizontal split puts the panes side by side.
+        "SplitHorizontal" => "new_split:right".to_string(),
+        "SplitVertical" => "new_split:down".to_string(),
+        "ActivatePaneDirection" => match arg {
+            "Left" | "Right" | "Up" | "Down" => format!("goto_split:{}", arg.to_lowercase()),
+            "Next" => "goto_split:next".to_string(),
+            "Prev" => "goto_split:previous".to_string(),
+            _ => return None,
+        },
+        "TogglePaneZoomState" => "toggle_split_zoom".to_string(),
+        "ToggleFullScreen" => "toggle_fullscreen".to_string(),
+        "ScrollByPage" => match number()? {
+            n if n < 0 => "scroll_page_up".to_string(),
+            _ => "scroll_page_down".to_string(),
+        },
+        "ScrollByLine" => format!("scroll_page_lines:{}", number()?),
+        "ScrollToTop" => "scroll_to_top".to_string(),
+        "ScrollToBottom" => "scroll_to_bottom".to_string(),
+        "ClearScrollback" => "clear_screen".to_string(),
+        "ReloadConfiguration" => "reload_config".to_string(),
+        "QuitApplication" => "quit".to_string(),
+        "ActivateCommandPalette" => "toggle_command_palette".to_string(),
+        "Search" => "start_search".to_string(),
+        // Lua's escapes (`\x1b`, `\n`) read the same in Ghostty.
+        "SendString" => format!("text:{}", arg),
+        "DisableDefaultAssignment" => "unbind".to_string(),
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITERM_JSON: &str = r#"{
+  "Key Mappings" : {
+    "0x74-0x100000-0x11" : { "Action" : 27, "Text" : "" },
+    "0xf702-0x280000-0x7b" : { "Action" : 10, "Text" : "b" },
+    "0x64-0x120000-0x2" : { "Action" : 28, "Text" : "" },
+    "0x6b-0x100000-0x28" : { "Action" : 25, "Text" : "Clear Buffer" }
+  }
+}"#;
+
+    const ITERM_PLIST: &str = r#"<key>GlobalKeyMap</key>
+<dict>
+    <key>0x61-0x40000</key>
+    <dict>
+        <key>Action</key>
+        <integer>12</integer>
+        <key>Text</key>
+        <string>echo &quot;hi&quot;
+</string>
+    </dict>
+</dict>"#;
+
+    const KITTY: &str = "\
+kitty_mod ctrl+alt
+map kitty_mod+t new_tab
+map ctrl+shift+enter new_window
+map cmd+plus change_font_size all +2.0
+map ctrl+a>c launch --type=tab
+map --when-focus-on var:in_editor ctrl+s no_op
+map ctrl+shift+left neighboring_window left
+";
+
+    const WEZTERM: &str = r#"
+local wezterm = require 'wezterm'
+local act = wezterm.action
+return {
+  leader = { key = 'a', mods = 'CTRL', timeout_milliseconds = 1000 },
+  keys = {
+    { key = 't', mods = 'CMD', action = act.SpawnTab 'CurrentPaneDomain' },
+    { mods = 'LEADER', key = '%', action = act.SplitHorizontal { domain = 'CurrentPaneDomain' } },
+    { key = 'LeftArrow', mods = 'CTRL|SHIFT', action = act.ActivatePaneDirection 'Left' },
+    { key = '1', mods = 'ALT', action = wezterm.action.ActivateTab(0) },
+    { key = 'k', mods = 'CMD', action = act.ClearScrollback 'ScrollbackAndViewport' },
+    { key = 'p', mods = 'CMD', action = wezterm.action_callback(function(win, pane) end) },
+  },
+}
+"#;
+
+    fn import(text: &str, format: KeymapFormat) -> ImportedKeybinds {
+        import_keybinds(text, format, &[]).unwrap()
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(KeymapFormat::detect(ITERM_JSON), Some(KeymapFormat::ITerm));
+        assert_eq!(KeymapFormat::detect(ITERM_PLIST), Some(KeymapFormat::ITerm));
+        assert_eq!(KeymapFormat::detect(KITTY), Some(KeymapFormat::Kitty));
+        assert_eq!(KeymapFormat::detect(WEZTERM), Some(KeymapFormat::Wezterm));
+        assert_eq!(KeymapFormat::detect("font-size = 12"), None);
+    }
+
+    #[test]
+    fn test_iterm() {
+        let imported = import(ITERM_JSON, KeymapFormat::ITerm);
+        assert_eq!(
+            imported.bindings,
+            vec![
+                "super+t=new_tab",
+                "alt+arrow_left=esc:b",
+                "shift+super+d=new_split:down"
+            ]
+        );
+        assert_eq!(imported.skipped.len(), 1);
+        assert_eq!(imported.skipped[0].source, "0x6b-0x100000 (action 25)");
+
+        let imported = import(ITERM_PLIST, KeymapFormat::ITerm);
+        assert_eq!(imported.bindings, vec![r#"ctrl+a=text:echo "hi"\n"#]);
+    }
+
+    #[test]
+    fn test_kitty() {
+        let imported = import(KITTY, KeymapFormat::Kitty);
+        assert_eq!(
+            imported.bindings,
+            vec![
+                "ctrl+alt+t=new_tab",
+                "ctrl+shift+enter=new_split:auto",
+                "super+plus=increase_font_size:2",
+                "ctrl+shift+arrow_left=goto_split:left",
+            ]
+        );
+        let skipped: Vec<&str> = imported.skipped.iter().map(|s| s.reason.as_str()).collect();
+        assert_eq!(
+            skipped,
+            vec![
+                "kitty's launch has no Ghostty equivalent",
+                "kitty's map options have no Ghostty equivalent"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wezterm() {
+        let imported = import(WEZTERM, KeymapFormat::Wezterm);
+        assert_eq!(
+            imported.bindings,
+            vec![
+                "super+t=new_tab",
+                "ctrl+a>%=new_split:right",
+                "ctrl+shift+arrow_left=goto_split:left",
+                "alt+1=goto_tab:1",
+                "super+k=clear_screen",
+            ]
+        );
+        assert_eq!(imported.skipped.len(), 1);
+        assert!(imported.skipped[0].source.contains("action_callback"));
+    }
+
+    #[test]
+    fn test_actions_are_checked() {
+        let actions = vec!["new_tab".to_string(), "select_all".to_string()];
+        let kitty = format!("{}map ctrl+shift+a select_all\n", KITTY);
+        let imported = import_keybinds(&kitty, KeymapFormat::Kitty, &actions).unwrap();
+        assert_eq!(
+            imported.bindings,
+            vec!["ctrl+alt+t=new_tab", "ctrl+shift+a=select_all"]
+        );
+        assert!(imported
+            .skipped
+            .iter()
+            .any(|s| s.reason == "this Ghostty has no new_split action"));
+        assert!(import_keybinds("", KeymapFormat::Kitty, &actions).is_err());
+    }
+}
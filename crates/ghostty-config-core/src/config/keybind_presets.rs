@@ -0,0 +1,223 @@
+//! Curated sets of keybindings, installed as a marked block in the config so they
+//! can be removed again without touching the user's own bindings.
+
+use super::model::{ConfigEntry, UserConfig};
+
+/// A named bundle of `keybind` values.
+#[derive(Debug, Clone, Copy)]
+pub struct KeybindPreset {
+    pub name: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    /// `trigger=action` values, as they are written after `keybind =`.
+    pub bindings: &'static [&'static str],
+}
+
+pub const PRESETS: &[KeybindPreset] = &[
+    KeybindPreset {
+        name: "tmux",
+        title: "tmux-style",
+        description: "Tabs and splits behind a ctrl+b prefix, like tmux's defaults.",
+        bindings: &[
+            "ctrl+b>c=new_tab",
+            "ctrl+b>n=next_tab",
+            "ctrl+b>p=previous_tab",
+            "ctrl+b>1=goto_tab:1",
+            "ctrl+b>2=goto_tab:2",
+            "ctrl+b>3=goto_tab:3",
+            "ctrl+b>4=goto_tab:4",
+            "ctrl+b>5=goto_tab:5",
+            "ctrl+b>v=new_split:right",
+            "ctrl+b>s=new_split:down",
+            "ctrl+b>h=goto_split:left",
+            "ctrl+b>j=goto_split:down",
+            "ctrl+b>k=goto_split:up",
+            "ctrl+b>l=goto_split:right",
+            "ctrl+b>z=toggle_split_zoom",
+            "ctrl+b>x=close_surface",
+            "ctrl+b>bracket_left=scroll_page_up",
+        ],
+    },
+    KeybindPreset {
+        name: "macos",
+        title: "macOS-native",
+        description: "Command-key shortcuts that match Terminal.app and other macOS apps.",
+        bindings: &[
+            "super+n=new_window",
+            "super+t=new_tab",
+            "super+w=close_surface",
+            "super+shift+bracket_left=previous_tab",
+            "super+shift+bracket_right=next_tab",
+            "super+d=new_split:right",
+            "super+shift+d=new_split:down",
+            "super+bracket_left=goto_split:previous",
+            "super+bracket_right=goto_split:next",
+            "super+c=copy_to_clipboard",
+            "super+v=paste_from_clipboard",
+            "super+k=clear_screen",
+            "super+equal=increase_font_size:1",
+            "super+minus=decrease_font_size:1",
+            "super+0=reset_font_size",
+            "super+comma=open_config",
+        ],
+    },
+    KeybindPreset {
+        name: "vim",
+        title: "vim-style",
+        description: "Split navigation behind a ctrl+w prefix, and vim's scrolling keys.",
+        bindings: &[
+            "ctrl+w>h=goto_split:left",
+            "ctrl+w>j=goto_split:down",
+            "ctrl+w>k=goto_split:up",
+            "ctrl+w>l=goto_split:right",
+            "ctrl+w>v=new_split:right",
+            "ctrl+w>s=new_split:down",
+            "ctrl+w>q=close_surface",
+            "ctrl+w>o=toggle_split_zoom",
+            "ctrl+w>equal=equalize_splits",
+            "ctrl+shift+u=scroll_page_up",
+            "ctrl+shift+d=scroll_page_down",
+        ],
+    },
+];
+
+pub fn find_preset(name: &str) -> Option<&'static KeybindPreset> {
+    PRESETS.iter().find(|p| p.name == name)
+}
+
+fn begin_marker(name: &str) -> String {
+    format!("# ghostty-config preset {} begin", name)
+}
+
+fn end_marker(name: &str) -> String {
+    format!("# ghostty-config preset {} end", name)
+}
+
+/// Append `preset` to `config` as a marked block, replacing any earlier copy of it.
+/// Being last, its bindings win over the same triggers set earlier in the file.
+pub fn install_preset(config: &mut UserConfig, preset: &KeybindPreset) {
+    remove_preset(config, preset.name);
+    if config
+        .entries
+        .last()
+        .is_some_and(|e| !matches!(e, ConfigEntry::BlankLine))
+    {
+        config.entries.push(ConfigEntry::BlankLine);
+    }
+    config
+        .entries
+        .push(ConfigEntry::Comment(begin_marker(preset.name)));
+    for binding in preset.bindings {
+        config.entries.push(ConfigEntry::key_value(
+            "keybind".to_string(),
+            binding.to_string(),
+        ));
+    }
+    config
+        .entries
+        .push(ConfigEntry::Comment(end_marker(preset.name)));
+}
+
+/// Remove the block `install_preset` added for the preset `name`, markers included.
+/// Entries the user added inside the block go with it. Returns whether there was one.
+///
+/// A begin marker without its end marker is left alone rather than guessing where
+/// the block stopped.
+pub fn remove_preset(config: &mut UserConfig, name: &str) -> bool {
+    let (begin, end) = (begin_marker(name), end_marker(name));
+    let is_comment =
+        |e: &ConfigEntry, text: &str| matches!(e, ConfigEntry::Comment(c) if c.trim() == text);
+    let Some(start) = config.entries.iter().position(|e| is_comment(e, &begin)) else {
+        return false;
+    };
+    let Some(len) = config.entries[start..]
+        .iter()
+        .position(|e| is_comment(e, &end))
+    else {
+        return false;
+    };
+    config.entries.drain(start..=start + len);
+    // Take the blank line that install_preset put before the block.
+    if start > 0 && matches!(config.entries[start - 1], ConfigEntry::BlankLine) {
+        config.entries.remove(start - 1);
+    }
+    true
+}
+
+/// Names of the presets installed in `config`, in file order.
+pub fn installed_presets(config: &UserConfig) -> Vec<&'static str> {
+    config
+        .entries
+        .iter()
+        .filter_map(|e| match e {
+            ConfigEntry::Comment(c) => PRESETS
+                .iter()
+                .find(|p| c.trim() == begin_marker(p.name))
+                .map(|p| p.name),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file_io::render_config;
+    use crate::config::keybind::parse_trigger;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_presets_parse() {
+        for preset in PRESETS {
+            for binding in preset.bindings {
+                let (trigger, _) = binding.split_once('=').unwrap();
+                assert!(
+                    parse_trigger(trigger).is_ok(),
+                    "{}: {}",
+                    preset.name,
+                    binding
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_install_and_remove() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/config"));
+        config.set("font-size", "14");
+        let tmux = find_preset("tmux").unwrap();
+        install_preset(&mut config, tmux);
+        config.push("keybind", "ctrl+a=select_all");
+
+        let text = render_config(&config);
+        assert!(text.starts_with(
+            "font-size = 14\n\n# ghostty-config preset tmux begin\nkeybind = ctrl+b>c=new_tab\n"
+        ));
+        assert!(text.contains("# ghostty-config preset tmux end\n"));
+        assert_eq!(installed_presets(&config), vec!["tmux"]);
+
+        // Installing again replaces the block rather than duplicating it.
+        install_preset(&mut config, tmux);
+        assert_eq!(config.get_all("keybind").len(), tmux.bindings.len() + 1);
+
+        assert!(remove_preset(&mut config, "tmux"));
+        assert_eq!(config.get_all("keybind"), vec!["ctrl+a=select_all"]);
+        assert_eq!(
+            render_config(&config),
+            "font-size = 14\nkeybind = ctrl+a=select_all\n"
+        );
+        assert!(!remove_preset(&mut config, "tmux"));
+        assert!(installed_presets(&config).is_empty());
+    }
+
+    #[test]
+    fn test_unterminated_block_is_kept() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/config"));
+        config
+            .entries
+            .push(ConfigEntry::Comment(begin_marker("vim")));
+        config.push("keybind", "ctrl+w>h=goto_split:left");
+        assert!(!remove_preset(&mut config, "vim"));
+        assert_eq!(config.entries.len(), 2);
+    }
+}
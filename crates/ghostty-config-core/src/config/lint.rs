@@ -0,0 +1,234 @@
+//! Problems the current Ghostty would have with a config that the line parser
+//! accepts: keys it doesn't know, values its docs call deprecated, and single-value
+//! keys set more than once.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::model::{ConfigEntry, ConfigSchema, UserConfig};
+use super::type_inference::is_repeatable;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum LintKind {
+    /// Not an option of this Ghostty version, e.g. one that was renamed or removed.
+    UnknownKey,
+    /// The option, or the value it is set to, is documented as deprecated.
+    Deprecated,
+    /// A key that takes one value appears more than once; only the last counts.
+    Duplicate,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LintWarning {
+    pub kind: LintKind,
+    pub key: String,
+    pub value: String,
+    /// 1-based line in the config file.
+    pub line: usize,
+    pub message: String,
+    /// A known key the unknown one was probably meant to be.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+/// Check every key in `config` against `schema`, in file order.
+///
+/// Returns nothing when the schema is empty, since without Ghostty's option list
+/// every key would look unknown.
+pub fn lint_config(config: &UserConfig, schema: &ConfigSchema) -> Vec<LintWarning> {
+    if schema.options().is_empty() {
+        return Vec::new();
+    }
+
+    let mut warnings = Vec::new();
+    let mut last_line: HashMap<&str, usize> = HashMap::new();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for (index, entry) in config.entries.iter().enumerate() {
+        let ConfigEntry::KeyValue { key, value, .. } = entry else {
+            continue;
+        };
+        let line = index + 1;
+        let warning = |kind, message, suggestion| LintWarning {
+            kind,
+            key: key.clone(),
+            value: value.clone(),
+            line,
+            message,
+            suggestion,
+        };
+
+        let Some(option) = schema.find_option(key) else {
+            let suggestion = closest_key(key, schema);
+            let message = match &suggestion {
+                Some(s) => format!("`{}` is not a Ghostty option; did you mean `{}`?", key, s),
+                None => format!("`{}` is not an option in this version of Ghostty", key),
+            };
+            warnings.push(warning(LintKind::UnknownKey, message, suggestion));
+            continue;
+        };
+
+        if let Some(message) = deprecation(key, value, &option.documentation) {
+            warnings.push(warning(LintKind::Deprecated, message, None));
+        }
+        if !option.is_repeatable && !is_repeatable(key) {
+            last_line.insert(key, line);
+            *counts.entry(key).or_default() += 1;
+        }
+    }
+
+    for (index, entry) in config.entries.iter().enumerate() {
+        let ConfigEntry::KeyValue { key, value, .. } = entry else {
+            continue;
+        };
+        let (Some(&count), Some(&last)) = (counts.get(key.as_str()), last_line.get(key.as_str()))
+        else {
+            continue;
+        };
+        if count > 1 && index + 1 != last {
+            warnings.push(LintWarning {
+                kind: LintKind::Duplicate,
+                key: key.clone(),
+                value: value.clone(),
+                line: index + 1,
+                message: format!(
+                    "`{}` is set {} times; only line {} takes effect",
+                    key, count, last
+                ),
+                suggestion: None,
+            });
+        }
+    }
+
+    warnings.sort_by_key(|w| w.line);
+    warnings
+}
+
+/// Why `key = value` is deprecated according to the option's docs: the first
+/// paragraph saying the option itself is, or a line naming the value in backticks.
+fn deprecation(key: &str, value: &str, documentation: &str) -> Option<String> {
+    let summary = documentation.split("\n\n").next().unwrap_or_default();
+    if summary.to_lowercase().contains("deprecated") {
+        return Some(format!("`{}` is deprecated", key));
+    }
+    if value.is_empty() {
+        return None;
+    }
+    let quoted = format!("`{}`", value);
+    documentation
+        .lines()
+        .any(|line| line.contains(&quoted) && line.to_lowercase().contains("deprecated"))
+        .then(|| format!("`{}` is a deprecated value for `{}`", value, key))
+}
+
+/// The known key nearest to `key` in edit distance, if it is close enough to be
+/// a typo or a rename.
+fn closest_key(key: &str, schema: &ConfigSchema) -> Option<String> {
+    let limit = (key.len() / 3).clamp(1, 3);
+    schema
+        .options()
+        .iter()
+        .map(|o| (edit_distance(key, &o.key), &o.key))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, k)| k.clone())
+}
+
+/// Levenshtein distance over bytes; config keys are ASCII.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.bytes().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file_io::parse_config;
+    use crate::config::model::{Category, ConfigOption, ConfigValueType};
+    use std::path::Path;
+
+    fn option(key: &str, documentation: &str, is_repeatable: bool) -> ConfigOption {
+        ConfigOption {
+            key: key.to_string(),
+            default_value: String::new(),
+            documentation: documentation.to_string(),
+            value_type: ConfigValueType::Text,
+            category: Category::Advanced,
+            is_repeatable,
+            conditions: Default::default(),
+        }
+    }
+
+    fn schema() -> ConfigSchema {
+        ConfigSchema::new(vec![
+            option("font-size", "Font size in points.", false),
+            option("keybind", "Key bindings.", true),
+            option(
+                "gtk-tabs-location",
+                "Where tabs go.\n\n* `top`\n* `hidden` - Deprecated, use `window-show-tab-bar`.",
+                false,
+            ),
+            option("background-blur", "Blur behind the window.", false),
+            option("old-thing", "Deprecated: does nothing now.", false),
+        ])
+    }
+
+    #[test]
+    fn test_lint_config() {
+        let text = "font-size = 12\n# comment\nbackground-blur-radius = 20\nfont-size = 14\n\
+                    keybind = a=b\nkeybind = c=d\ngtk-tabs-location = hidden\nold-thing = 1\n\
+                    gtk-tabs-location = top\nno-such-option-anywhere = 1\n";
+        let config = parse_config(text, Path::new("config"));
+        let warnings = lint_config(&config, &schema());
+        let summary: Vec<_> = warnings
+            .iter()
+            .map(|w| (w.kind, w.key.as_str(), w.line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (LintKind::Duplicate, "font-size", 1),
+                (LintKind::UnknownKey, "background-blur-radius", 3),
+                (LintKind::Deprecated, "gtk-tabs-location", 7),
+                (LintKind::Duplicate, "gtk-tabs-location", 7),
+                (LintKind::Deprecated, "old-thing", 8),
+                (LintKind::UnknownKey, "no-such-option-anywhere", 10),
+            ]
+        );
+        assert_eq!(
+            warnings[0].message,
+            "`font-size` is set 2 times; only line 4 takes effect"
+        );
+        assert_eq!(warnings[1].suggestion.as_deref(), None);
+        assert_eq!(
+            warnings[2].message,
+            "`hidden` is a deprecated value for `gtk-tabs-location`"
+        );
+    }
+
+    #[test]
+    fn test_suggestion_and_empty_schema() {
+        let config = parse_config("font-szie = 12\n", Path::new("config"));
+        let warnings = lint_config(&config, &schema());
+        assert_eq!(warnings[0].suggestion.as_deref(), Some("font-size"));
+        assert!(lint_config(&config, &ConfigSchema::new(Vec::new())).is_empty());
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::model::UserConfig;
+
+/// Which side wins for a key that was changed both on disk and in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Resolution {
+    /// Keep the in-memory (unsaved) value.
+    Ours,
+    /// Take the value from the file as it is now on disk.
+    Theirs,
+}
+
+/// A key edited differently on disk and in memory since the common base.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MergeConflict {
+    pub key: String,
+    pub base: Vec<String>,
+    pub theirs: Vec<String>,
+    pub ours: Vec<String>,
+}
+
+/// Result of a key-level three-way merge.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MergeOutcome {
+    /// Keys changed only on disk; the external values are taken.
+    pub from_theirs: Vec<String>,
+    /// Keys changed only in memory; the unsaved values are kept.
+    pub from_ours: Vec<String>,
+    /// Keys changed on both sides to different values.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// Three-way merge of key values.
+///
+/// `base` is the config as last read or written by this process, `theirs` is the
+/// file as it is now on disk, and `ours` is the in-memory config with unsaved edits.
+pub fn three_way_merge(base: &UserConfig, theirs: &UserConfig, ours: &UserConfig) -> MergeOutcome {
+    let mut keys: Vec<&str> = Vec::new();
+    for config in [base, theirs, ours] {
+        for (key, _) in config.all_set_values() {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+
+    let mut outcome = MergeOutcome::default();
+    for key in keys {
+        let base_values = base.get_all(key);
+        let their_values = theirs.get_all(key);
+        let our_values = ours.get_all(key);
+
+        if our_values == their_values {
+            continue;
+        } else if our_values == base_values {
+            outcome.from_theirs.push(key.to_string());
+        } else if their_values == base_values {
+            outcome.from_ours.push(key.to_string());
+        } else {
+            outcome.conflicts.push(MergeConflict {
+                key: key.to_string(),
+                base: base_values.into_iter().map(String::from).collect(),
+                theirs: their_values.into_iter().map(String::from).collect(),
+                ours: our_values.into_iter().map(String::from).collect(),
+            });
+        }
+    }
+
+    outcome
+}
+
+/// Build the merged config on top of the external file, so its comments and layout survive.
+///
+/// Conflicts without an entry in `resolutions` keep the in-memory value.
+pub fn apply_merge(
+    theirs: &UserConfig,
+    ours: &UserConfig,
+    outcome: &MergeOutcome,
+    resolutions: &HashMap<String, Resolution>,
+) -> UserConfig {
+    let mut merged = theirs.clone();
+    merged.file_path = ours.file_path.clone();
+
+    let kept_ours = outcome.from_ours.iter().chain(
+        outcome
+            .conflicts
+            .iter()
+            .filter(|c| resolutions.get(&c.key) != Some(&Resolution::Theirs))
+            .map(|c| &c.key),
+    );
+    for key in kept_ours {
+        let values: Vec<String> = ours.get_all(key).into_iter().map(String::from).collect();
+        merged.set_all(key, &values);
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::{test_config, ConfigEntry};
+
+    #[test]
+    fn test_merge_takes_external_only_changes() {
+        let base = test_config(&[("font-size", "13")]);
+        let theirs = test_config(&[("font-size", "15")]);
+        let ours = test_config(&[("font-size", "13")]);
+        let outcome = three_way_merge(&base, &theirs, &ours);
+        assert_eq!(outcome.from_theirs, vec!["font-size"]);
+        assert!(outcome.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_merge_keeps_local_only_changes() {
+        let base = test_config(&[("font-size", "13")]);
+        let theirs = test_config(&[("font-size", "13"), ("theme", "Nord")]);
+        let ours = test_config(&[("font-size", "16")]);
+        let outcome = three_way_merge(&base, &theirs, &ours);
+        assert_eq!(outcome.from_ours, vec!["font-size"]);
+        assert_eq!(outcome.from_theirs, vec!["theme"]);
+
+        let merged = apply_merge(&theirs, &ours, &outcome, &HashMap::new());
+        assert_eq!(merged.get("font-size"), Some("16"));
+        assert_eq!(merged.get("theme"), Some("Nord"));
+    }
+
+    #[test]
+    fn test_merge_same_change_both_sides_is_not_a_conflict() {
+        let base = test_config(&[("font-size", "13")]);
+        let theirs = test_config(&[("font-size", "14")]);
+        let ours = test_config(&[("font-size", "14")]);
+        let outcome = three_way_merge(&base, &theirs, &ours);
+        assert!(outcome.conflicts.is_empty());
+        assert!(outcome.from_ours.is_empty());
+        assert!(outcome.from_theirs.is_empty());
+    }
+
+    #[test]
+    fn test_merge_conflict_resolution() {
+        let base = test_config(&[("font-size", "13"), ("theme", "Dracula")]);
+        let theirs = test_config(&[("font-size", "15"), ("theme", "Nord")]);
+        let ours = test_config(&[("font-size", "16"), ("theme", "Tomorrow")]);
+        let outcome = three_way_merge(&base, &theirs, &ours);
+        assert_eq!(outcome.conflicts.len(), 2);
+
+        let mut resolutions = HashMap::new();
+        resolutions.insert("font-size".to_string(), Resolution::Theirs);
+        resolutions.insert("theme".to_string(), Resolution::Ours);
+        let merged = apply_merge(&theirs, &ours, &outcome, &resolutions);
+        assert_eq!(merged.get("font-size"), Some("15"));
+        assert_eq!(merged.get("theme"), Some("Tomorrow"));
+    }
+
+    #[test]
+    fn test_merge_local_removal() {
+        let base = test_config(&[("font-size", "13"), ("theme", "Dracula")]);
+        let theirs = test_config(&[("font-size", "13"), ("theme", "Dracula")]);
+        let ours = test_config(&[("font-size", "13")]);
+        let outcome = three_way_merge(&base, &theirs, &ours);
+        let merged = apply_merge(&theirs, &ours, &outcome, &HashMap::new());
+        assert_eq!(merged.get("theme"), None);
+    }
+
+    #[test]
+    fn test_merge_preserves_external_comments() {
+        let base = test_config(&[("font-size", "13")]);
+        let mut theirs = test_config(&[("font-size", "13")]);
+        theirs
+            .entries
+            .insert(0, ConfigEntry::Comment("# edited in vim".to_string()));
+        let ours = test_config(&[("font-size", "18")]);
+        let outcome = three_way_merge(&base, &theirs, &ours);
+        let merged = apply_merge(&theirs, &ours, &outcome, &HashMap::new());
+        assert!(matches!(&merged.entries[0], ConfigEntry::Comment(c) if c == "# edited in vim"));
+        assert_eq!(merged.get("font-size"), Some("18"));
+    }
+}
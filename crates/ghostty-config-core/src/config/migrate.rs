@@ -0,0 +1,1493 @@
+//! Migrating from another terminal: its fonts, colors, padding, opacity,
+//! scrollback, and keybindings as Ghostty settings, and what couldn't be carried
+//! over.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::color_import::{parse_color, ANSI_NAMES};
+use super::keybind_import::{
+    check_binding, chord, ghostty_key, ghostty_modifier, text_action, translate_keybinds,
+    KeymapFormat,
+};
+use crate::Error;
+
+/// Ghostty limits scrollback in bytes rather than lines. A line of a wide
+/// terminal takes about this many.
+const BYTES_PER_SCROLLBACK_LINE: u64 = 1000;
+
+/// Why a setting is listed as unmapped when there's nothing more specific to say.
+const NOT_MAPPED: &str = "no Ghostty setting is mapped from this";
+
+/// Terminals whose configs can be migrated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrateSource {
+    /// A profile exported as JSON.
+    ITerm,
+    /// `alacritty.toml`.
+    Alacritty,
+    Kitty,
+    Wezterm,
+    /// `settings.json`.
+    WindowsTerminal,
+}
+
+impl MigrateSource {
+    pub fn all() -> [Self; 5] {
+        [
+            MigrateSource::ITerm,
+            MigrateSource::Alacritty,
+            MigrateSource::Kitty,
+            MigrateSource::Wezterm,
+            MigrateSource::WindowsTerminal,
+        ]
+    }
+
+    pub fn parse(slug: &str) -> Result<Self, Error> {
+        Self::all()
+            .into_iter()
+            .find(|s| s.slug() == slug.trim())
+            .ok_or_else(|| Error::Config(format!("Unknown terminal: {}", slug)))
+    }
+
+    pub fn slug(self) -> &'static str {
+        match self {
+            MigrateSource::ITerm => "iterm",
+            MigrateSource::Alacritty => "alacritty",
+            MigrateSource::Kitty => "kitty",
+            MigrateSource::Wezterm => "wezterm",
+            MigrateSource::WindowsTerminal => "windows-terminal",
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            MigrateSource::ITerm => "iTerm2",
+            MigrateSource::Alacritty => "Alacritty",
+            MigrateSource::Kitty => "kitty",
+            MigrateSource::Wezterm => "WezTerm",
+            MigrateSource::WindowsTerminal => "Windows Terminal",
+        }
+    }
+
+    /// What to paste, for the migration form.
+    pub fn hint(self) -> &'static str {
+        match self {
+            MigrateSource::ITerm => {
+                "A profile saved with Settings > Profiles > Other Actions > Save Profile as JSON"
+            }
+            MigrateSource::Alacritty => "alacritty.toml (Alacritty 0.13 or later)",
+            MigrateSource::Kitty => "kitty.conf",
+            MigrateSource::Wezterm => ".wezterm.lua or wezterm/wezterm.lua",
+            MigrateSource::WindowsTerminal => "settings.json (Settings > Open JSON file)",
+        }
+    }
+
+    /// Where this terminal's config usually is. iTerm2 keeps its settings in a
+    /// binary plist, so a profile has to be exported instead.
+    pub fn default_paths(self) -> Vec<PathBuf> {
+        let Some(dirs) = directories::BaseDirs::new() else {
+            return Vec::new();
+        };
+        let home = dirs.home_dir();
+        // kitty, Alacritty, and WezTerm use ~/.config on macOS too.
+        let xdg = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".config"));
+        match self {
+            MigrateSource::ITerm => Vec::new(),
+            MigrateSource::Alacritty => vec![
+                xdg.join("alacritty").join("alacritty.toml"),
+                xdg.join("alacritty.toml"),
+                home.join(".alacritty.toml"),
+                dirs.config_dir().join("alacritty").join("alacritty.toml"),
+            ],
+            MigrateSource::Kitty => vec![xdg.join("kitty").join("kitty.conf")],
+            MigrateSource::Wezterm => vec![
+                home.join(".wezterm.lua"),
+                xdg.join("wezterm").join("wezterm.lua"),
+            ],
+            MigrateSource::WindowsTerminal => [
+                "Microsoft.WindowsTerminal",
+                "Microsoft.WindowsTerminalPreview",
+            ]
+            .iter()
+            .map(|package| {
+                dirs.data_local_dir()
+                    .join("Packages")
+                    .join(format!("{}_8wekyb3d8bbwe", package))
+                    .join("LocalState")
+                    .join("settings.json")
+            })
+            .collect(),
+        }
+    }
+
+    /// The first of [`Self::default_paths`] that exists.
+    pub fn find_config(self) -> Option<PathBuf> {
+        self.default_paths().into_iter().find(|p| p.is_file())
+    }
+}
+
+/// A setting from the other terminal and the Ghostty setting it becomes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Mapping {
+    /// The setting as the other terminal names it.
+    pub source: String,
+    pub key: String,
+    pub value: String,
+    /// What changed on the way, e.g. an estimated conversion.
+    pub note: Option<String>,
+}
+
+/// A setting that isn't carried over, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Unmapped {
+    pub source: String,
+    pub reason: String,
+}
+
+/// The result of reading another terminal's config.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Migration {
+    pub mappings: Vec<Mapping>,
+    pub unmapped: Vec<Unmapped>,
+}
+
+impl Migration {
+    fn map(&mut self, source: impl Into<String>, key: &str, value: impl Into<String>) {
+        self.mappings.push(Mapping {
+            source: source.into(),
+            key: key.to_string(),
+            value: value.into(),
+            note: None,
+        });
+    }
+
+    fn map_with_note(
+        &mut self,
+        source: impl Into<String>,
+        key: &str,
+        value: impl Into<String>,
+        note: impl Into<String>,
+    ) {
+        self.mappings.push(Mapping {
+            source: source.into(),
+            key: key.to_string(),
+            value: value.into(),
+            note: Some(note.into()),
+        });
+    }
+
+    fn skip(&mut self, source: impl Into<String>, reason: impl Into<String>) {
+        self.unmapped.push(Unmapped {
+            source: source.into(),
+            reason: reason.into(),
+        });
+    }
+
+    /// The Ghostty config entries, in the order they were found.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        self.mappings
+            .iter()
+            .map(|m| (m.key.clone(), m.value.clone()))
+            .collect()
+    }
+
+    fn map_color(&mut self, source: String, key: &str, value: &str) {
+        match parse_color(value) {
+            Some(color) => self.map(source, key, color),
+            None => self.skip(
+                source,
+                format!("{} is not a color Ghostty can use", value.trim()),
+            ),
+        }
+    }
+
+    fn map_palette(&mut self, source: String, index: usize, value: &str) {
+        match parse_color(value) {
+            Some(color) => self.map(source, "palette", format!("{}={}", index, color)),
+            None => self.skip(
+                source,
+                format!("{} is not a color Ghostty can use", value.trim()),
+            ),
+        }
+    }
+
+    fn map_font_size(&mut self, source: String, value: Option<f64>) {
+        match value.filter(|size| *size > 0.0) {
+            Some(size) => self.map(source, "font-size", format_number(size)),
+            None => self.skip(source, "not a font size"),
+        }
+    }
+
+    fn map_opacity(&mut self, source: String, opacity: Option<f64>) {
+        match opacity {
+            Some(opacity) => self.map(
+                source,
+                "background-opacity",
+                format_number(opacity.clamp(0.0, 1.0)),
+            ),
+            None => self.skip(source, "not an opacity"),
+        }
+    }
+
+    fn map_scrollback(&mut self, source: String, lines: Option<f64>) {
+        match lines {
+            Some(lines) if lines >= 0.0 => self.map_with_note(
+                source,
+                "scrollback-limit",
+                (lines as u64 * BYTES_PER_SCROLLBACK_LINE).to_string(),
+                format!(
+                    "Ghostty limits scrollback in bytes; about {} lines",
+                    lines as u64
+                ),
+            ),
+            Some(_) => self.skip(source, "Ghostty's scrollback is always limited"),
+            None => self.skip(source, "not a number of lines"),
+        }
+    }
+
+    /// Padding given as `[top, right, bottom, left]`.
+    fn map_padding(&mut self, source: String, sides: [f64; 4]) {
+        let [top, right, bottom, left] = sides;
+        let pair = |a: f64, b: f64| {
+            if a == b {
+                format_number(a)
+            } else {
+                format!("{},{}", format_number(a), format_number(b))
+            }
+        };
+        self.map(source.clone(), "window-padding-x", pair(left, right));
+        self.map(source, "window-padding-y", pair(top, bottom));
+    }
+
+    fn map_keybinds(&mut self, text: &str, format: KeymapFormat, actions: &[String]) {
+        for (source, checked) in translate_keybinds(text, format, actions) {
+            match checked {
+                Ok(binding) => self.map(source, "keybind", binding),
+                Err(reason) => self.skip(source, reason),
+            }
+        }
+    }
+}
+
+/// Read another terminal's config as Ghostty settings.
+///
+/// Keybinding actions are checked against `actions` (the running Ghostty's
+/// `+list-actions`; empty to skip the check), as for a keybinding import.
+pub fn migrate(text: &str, source: MigrateSource, actions: &[String]) -> Result<Migration, Error> {
+    let mut migration = Migration::default();
+    match source {
+        MigrateSource::ITerm => migrate_iterm(text, actions, &mut migration)?,
+        MigrateSource::Alacritty => migrate_alacritty(text, actions, &mut migration)?,
+        MigrateSource::Kitty => migrate_kitty(text, actions, &mut migration),
+        MigrateSource::Wezterm => migrate_wezterm(text, actions, &mut migration),
+        MigrateSource::WindowsTerminal => migrate_windows_terminal(text, actions, &mut migration)?,
+    }
+    if migration.mappings.is_empty() && migration.unmapped.is_empty() {
+        return Err(Error::Config(format!(
+            "No {} settings found to migrate",
+            source.display_name()
+        )));
+    }
+    Ok(migration)
+}
+
+/// A number as Ghostty writes it: at most two decimals, none when whole.
+fn format_number(n: f64) -> String {
+    format!("{}", (n * 100.0).round() / 100.0)
+}
+
+/// A JSON or TOML number, or a string holding one.
+fn number(value: &Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+}
+
+/// A setting's value for the mapping table, shortened when long.
+fn shown(value: &str) -> String {
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    match value.char_indices().nth(60) {
+        Some((cut, _)) => format!("{}…", &value[..cut]),
+        None => value,
+    }
+}
+
+// iTerm2 ---------------------------------------------------------------------
+
+/// iTerm2 profile colors and the Ghostty keys they map to.
+const ITERM_COLORS: &[(&str, &str)] = &[
+    ("Background Color", "background"),
+    ("Foreground Color", "foreground"),
+    ("Cursor Color", "cursor-color"),
+    ("Cursor Text Color", "cursor-text"),
+    ("Selection Color", "selection-background"),
+    ("Selected Text Color", "selection-foreground"),
+];
+
+/// Profile fields that only identify the profile.
+const ITERM_IDENTITY: &[&str] = &["Name", "Guid", "Tags", "Keyboard Map"];
+
+/// An iTerm2 color, stored as 0-1 RGB components.
+fn iterm_color(value: &Value) -> Option<String> {
+    let component = |name: &str| {
+        let c = value.get(format!("{} Component", name))?.as_f64()?;
+        Some((c.clamp(0.0, 1.0) * 255.0).round() as u8)
+    };
+    Some(format!(
+        "#{:02x}{:02x}{:02x}",
+        component("Red")?,
+        component("Green")?,
+        component("Blue")?
+    ))
+}
+
+fn migrate_iterm(text: &str, actions: &[String], m: &mut Migration) -> Result<(), Error> {
+    let invalid = || {
+        Error::Config(
+            "Paste an iTerm2 profile saved as JSON (Profiles > Other Actions > Save Profile as JSON)"
+                .to_string(),
+        )
+    };
+    let parsed: Value = serde_json::from_str(text).map_err(|_| invalid())?;
+    // A dynamic profiles file holds a list of profiles; take the first.
+    let profile = match parsed.get("Profiles").and_then(|p| p.get(0)) {
+        Some(first) => first,
+        None => &parsed,
+    };
+    let profile = profile.as_object().ok_or_else(invalid)?;
+    let unlimited = profile
+        .get("Unlimited Scrollback")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    for (name, value) in profile {
+        let name = name.as_str();
+        if ITERM_IDENTITY.contains(&name) {
+            continue;
+        }
+        // With separate light and dark colors, the dark ones stand in for the
+        // plain ones when those are missing.
+        let (color_name, dark) = match name.strip_suffix(" (Dark)") {
+            Some(base) if !profile.contains_key(base) => (base, true),
+            _ => (name, false),
+        };
+        let color_key = ITERM_COLORS
+            .iter()
+            .find(|(n, _)| *n == color_name)
+            .map(|(_, key)| *key);
+        let palette_index = color_name
+            .strip_prefix("Ansi ")
+            .and_then(|n| n.strip_suffix(" Color"))
+            .and_then(|n| n.parse::<usize>().ok())
+            .filter(|i| *i < 16);
+        if color_key.is_some() || palette_index.is_some() {
+            let Some(color) = iterm_color(value) else {
+                m.skip(name, "not an iTerm2 color");
+                continue;
+            };
+            let (key, color) = match palette_index {
+                Some(i) => ("palette", format!("{}={}", i, color)),
+                None => (color_key.unwrap_or_default(), color),
+            };
+            if dark {
+                m.map_with_note(name, key, color, "the dark mode color");
+            } else {
+                m.map(name, key, color);
+            }
+            continue;
+        }
+
+        match name {
+            "Normal Font" => {
+                // "JetBrainsMono-Regular 13": a PostScript name and a size.
+                let font = value.as_str().unwrap_or_default();
+                let (postscript, size) = font.rsplit_once(' ').unwrap_or((font, ""));
+                let family = postscript.split('-').next().unwrap_or(postscript);
+                m.map_with_note(
+                    name,
+                    "font-family",
+                    family,
+                    format!(
+                        "from the PostScript name {}; check the family on the Fonts page",
+                        postscript
+                    ),
+                );
+                m.map_font_size(name.to_string(), size.parse().ok());
+            }
+            "Transparency" => m.map_opacity(name.to_string(), number(value).map(|t| 1.0 - t)),
+            "Scrollback Lines" if unlimited => m.skip(
+                name,
+                "unlimited in iTerm2; Ghostty's scrollback is always limited",
+            ),
+            "Scrollback Lines" => m.map_scrollback(name.to_string(), number(value)),
+            "Unlimited Scrollback" => {}
+            "Side Margins" | "Top/Bottom Margins" => match number(value) {
+                Some(margin) => {
+                    let key = if name == "Side Margins" {
+                        "window-padding-x"
+                    } else {
+                        "window-padding-y"
+                    };
+                    m.map(name, key, format_number(margin));
+                }
+                None => m.skip(name, "not a margin"),
+            },
+            _ if name.ends_with(" (Light)") && (color_key.is_some() || name.contains("Color")) => m
+                .skip(
+                    name,
+                    "light mode colors need a light theme (theme = light:…,dark:…)",
+                ),
+            _ => m.skip(name, NOT_MAPPED),
+        }
+    }
+
+    if profile.contains_key("Keyboard Map") {
+        m.map_keybinds(text, KeymapFormat::ITerm, actions);
+    }
+    Ok(())
+}
+
+// Alacritty ------------------------------------------------------------------
+
+/// Alacritty actions and the Ghostty actions they map to.
+const ALACRITTY_ACTIONS: &[(&str, &str)] = &[
+    ("copy", "copy_to_clipboard"),
+    ("paste", "paste_from_clipboard"),
+    ("increasefontsize", "increase_font_size:1"),
+    ("decreasefontsize", "decrease_font_size:1"),
+    ("resetfontsize", "reset_font_size"),
+    ("scrollpageup", "scroll_page_up"),
+    ("scrollpagedown", "scroll_page_down"),
+    ("scrolltotop", "scroll_to_top"),
+    ("scrolltobottom", "scroll_to_bottom"),
+    ("clearhistory", "clear_screen"),
+    ("spawnnewinstance", "new_window"),
+    ("createnewwindow", "new_window"),
+    ("createnewtab", "new_tab"),
+    ("selectnexttab", "next_tab"),
+    ("selectprevioustab", "previous_tab"),
+    ("togglefullscreen", "toggle_fullscreen"),
+    ("quit", "quit"),
+    ("none", "unbind"),
+    ("receivechar", "unbind"),
+];
+
+/// Leaves of a TOML document as `(dotted.path, value)`. Arrays are leaves.
+fn flatten<'a>(prefix: &str, value: &'a Value, leaves: &mut Vec<(String, &'a Value)>) {
+    match value {
+        Value::Object(table) => {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&path, value, leaves);
+            }
+        }
+        _ => leaves.push((prefix.to_string(), value)),
+    }
+}
+
+fn migrate_alacritty(text: &str, actions: &[String], m: &mut Migration) -> Result<(), Error> {
+    let config: Value = basic_toml::from_str(text)
+        .map_err(|e| Error::Config(format!("Not a valid alacritty.toml: {}", e)))?;
+    let mut leaves = Vec::new();
+    flatten("", &config, &mut leaves);
+
+    for (path, value) in leaves {
+        let source = format!("{} = {}", path, shown(&value.to_string()));
+        let string = value.as_str().unwrap_or_default();
+        match path.as_str() {
+            "font.normal.family" => m.map(source, "font-family", string),
+            "font.bold.family" => m.map(source, "font-family-bold", string),
+            "font.italic.family" => m.map(source, "font-family-italic", string),
+            "font.bold_italic.family" => m.map(source, "font-family-bold-italic", string),
+            "font.size" => m.map_font_size(source, number(value)),
+            "window.opacity" => m.map_opacity(source, number(value)),
+            "scrolling.history" => m.map_scrollback(source, number(value)),
+            "window.padding.x" | "window.padding.y" => match number(value) {
+                Some(padding) => {
+                    let key = if path.ends_with('x') {
+                        "window-padding-x"
+                    } else {
+                        "window-padding-y"
+                    };
+                    m.map(source, key, format_number(padding));
+                }
+                None => m.skip(source, "not a padding"),
+            },
+            "keyboard.bindings" => {
+                for binding in value.as_array().into_iter().flatten() {
+                    alacritty_keybind(binding, actions, m);
+                }
+            }
+            _ => match path.strip_prefix("colors.").and_then(|p| p.split_once('.')) {
+                Some((section, name)) => alacritty_color(section, name, source, string, m),
+                None => m.skip(source, NOT_MAPPED),
+            },
+        }
+    }
+    Ok(())
+}
+
+fn alacritty_color(section: &str, name: &str, source: String, value: &str, m: &mut Migration) {
+    let key = match (section, name) {
+        ("primary", "background") => "background",
+        ("primary", "foreground") => "foreground",
+        ("cursor", "cursor") => "cursor-color",
+        ("cursor", "text") => "cursor-text",
+        ("selection", "background") => "selection-background",
+        ("selection", "text") => "selection-foreground",
+        ("normal" | "bright", name) => {
+            match ANSI_NAMES.iter().position(|n| *n == name) {
+                Some(i) => {
+                    m.map_palette(source, i + if section == "bright" { 8 } else { 0 }, value)
+                }
+                None => m.skip(source, NOT_MAPPED),
+            }
+            return;
+        }
+        _ => return m.skip(source, NOT_MAPPED),
+    };
+    m.map_color(source, key, value);
+}
+
+/// An Alacritty key name as Ghostty spells it.
+fn alacritty_key(name: &str) -> Option<String> {
+    let lower = name.to_lowercase();
+    let mapped = match lower.as_str() {
+        "back" => "backspace",
+        "equals" => "equal",
+        other => other
+            .strip_prefix("key")
+            .filter(|d| d.len() == 1 && d.chars().all(|c| c.is_ascii_digit()))
+            .unwrap_or(other),
+    };
+    ghostty_key(mapped)
+}
+
+fn alacritty_keybind(binding: &Value, actions: &[String], m: &mut Migration) {
+    let field = |name: &str| binding.get(name).and_then(Value::as_str).unwrap_or("");
+    let (key, mods) = (field("key"), field("mods"));
+    let target = match (field("action"), field("chars")) {
+        ("", "") => field("command"),
+        ("", chars) => chars,
+        (action, _) => action,
+    };
+    let source = if mods.is_empty() {
+        format!("{}: {}", key, target)
+    } else {
+        format!("{}+{}: {}", mods, key, target)
+    };
+    if !field("mode").is_empty() {
+        return m.skip(source, "Ghostty bindings don't depend on terminal modes");
+    }
+
+    let trigger = mods
+        .split('|')
+        .map(str::trim)
+        .filter(|m| !m.is_empty() && *m != "None")
+        .map(|name| ghostty_modifier(name).ok_or_else(|| format!("unknown modifier '{}'", name)))
+        .collect::<Result<Vec<_>, _>>()
+        .and_then(|modifiers| {
+            let key = alacritty_key(key).ok_or_else(|| format!("unknown key '{}'", key))?;
+            Ok(chord(&modifiers, &key))
+        });
+    let action = if !field("chars").is_empty() {
+        Ok(text_action(field("chars")))
+    } else {
+        let name = field("action").to_lowercase();
+        ALACRITTY_ACTIONS
+            .iter()
+            .find(|(a, _)| *a == name)
+            .map(|(_, action)| action.to_string())
+            .ok_or_else(|| format!("Alacritty's {} has no Ghostty equivalent", target))
+    };
+    match trigger.and_then(|trigger| check_binding(&trigger, &action?, actions)) {
+        Ok(binding) => m.map(source, "keybind", binding),
+        Err(reason) => m.skip(source, reason),
+    }
+}
+
+// kitty ----------------------------------------------------------------------
+
+/// kitty color settings and the Ghostty keys they map to.
+const KITTY_COLORS: &[(&str, &str)] = &[
+    ("background", "background"),
+    ("foreground", "foreground"),
+    ("cursor", "cursor-color"),
+    ("cursor_text_color", "cursor-text"),
+    ("selection_background", "selection-background"),
+    ("selection_foreground", "selection-foreground"),
+];
+
+/// A kitty font setting's family: `JetBrains Mono`, or the newer
+/// `family="JetBrains Mono" style=Bold`.
+fn kitty_font(value: &str) -> &str {
+    match value.strip_prefix("family=") {
+        Some(rest) => match rest.strip_prefix('"') {
+            Some(quoted) => quoted.split('"').next().unwrap_or(quoted),
+            None => rest.split_whitespace().next().unwrap_or(rest),
+        },
+        None => value.trim_matches('"'),
+    }
+}
+
+fn migrate_kitty(text: &str, actions: &[String], m: &mut Migration) {
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, value) = line
+            .split_once(char::is_whitespace)
+            .map_or((line, ""), |(name, value)| (name, value.trim()));
+        let source = line.to_string();
+        match name {
+            "font_family" | "bold_font" | "italic_font" | "bold_italic_font" => {
+                let family = kitty_font(value);
+                if family == "auto" {
+                    m.skip(source, "kitty picks this face itself; so does Ghostty");
+                    continue;
+                }
+                let key = match name {
+                    "font_family" => "font-family",
+                    "bold_font" => "font-family-bold",
+                    "italic_font" => "font-family-italic",
+                    _ => "font-family-bold-italic",
+                };
+                m.map(source, key, family);
+            }
+            "font_size" => m.map_font_size(source, value.parse().ok()),
+            "background_opacity" => m.map_opacity(source, value.parse().ok()),
+            "scrollback_lines" => m.map_scrollback(source, value.parse().ok()),
+            "window_padding_width" => {
+                let values: Option<Vec<f64>> =
+                    value.split_whitespace().map(|v| v.parse().ok()).collect();
+                // CSS order: all; vertical horizontal; top horizontal bottom; or all four.
+                let sides = match values.as_deref() {
+                    Some(&[all]) => Some([all, all, all, all]),
+                    Some(&[v, h]) => Some([v, h, v, h]),
+                    Some(&[t, h, b]) => Some([t, h, b, h]),
+                    Some(&[t, r, b, l]) => Some([t, r, b, l]),
+                    _ => None,
+                };
+                match sides {
+                    Some(sides) => m.map_padding(source, sides),
+                    None => m.skip(source, "not a padding"),
+                }
+            }
+            "map" | "kitty_mod" => {}
+            _ => {
+                let color_key = KITTY_COLORS.iter().find(|(n, _)| *n == name);
+                let palette_index = name
+                    .strip_prefix("color")
+                    .and_then(|i| i.parse::<usize>().ok())
+                    .filter(|i| *i < 256);
+                match (color_key, palette_index) {
+                    (Some((_, key)), _) => m.map_color(source, key, value),
+                    (_, Some(i)) => m.map_palette(source, i, value),
+                    _ => m.skip(source, NOT_MAPPED),
+                }
+            }
+        }
+    }
+    m.map_keybinds(text, KeymapFormat::Kitty, actions);
+}
+
+// WezTerm --------------------------------------------------------------------
+
+/// A top-level setting: `config.name = ` or `name = ` in the returned table.
+static WEZTERM_SETTING_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*(?:config\.)?([a-z_]+)[ \t]*=[ \t]*").unwrap());
+/// Font families in `wezterm.font("A")`, `font_with_fallback { "A", { family = "B" } }`.
+static WEZTERM_FONT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?:[{(,]\s*|family\s*=\s*)["']([^"']+)["']"#).unwrap());
+
+/// WezTerm color fields and the Ghostty keys they map to.
+const WEZTERM_COLORS: &[(&str, &str)] = &[
+    ("background", "background"),
+    ("foreground", "foreground"),
+    ("cursor_bg", "cursor-color"),
+    ("cursor_fg", "cursor-text"),
+    ("selection_bg", "selection-background"),
+    ("selection_fg", "selection-foreground"),
+];
+
+/// Settings read along with the keybindings.
+const WEZTERM_KEY_SETTINGS: &[&str] = &["keys", "leader", "disable_default_key_bindings"];
+
+/// `text` without `--` comments.
+fn strip_lua_comments(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let mut quote = None;
+            let bytes = line.as_bytes();
+            for (i, c) in line.char_indices() {
+                match (c, quote) {
+                    ('"' | '\'', None) => quote = Some(c),
+                    (c, Some(q)) if c == q => quote = None,
+                    ('-', None) if bytes.get(i + 1) == Some(&b'-') => return &line[..i],
+                    _ => {}
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Where the Lua expression at the start of `text` ends: at a top-level `,` or
+/// `;`, a closing bracket it didn't open, or, with `at_newline`, the line's end.
+fn lua_expression_end(text: &str, at_newline: bool) -> usize {
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (c, quote) {
+            (c, Some(q)) if c == q => quote = None,
+            (_, Some(_)) => {}
+            ('"' | '\'', None) => quote = Some(c),
+            ('{' | '(' | '[', None) => depth += 1,
+            ('}' | ')' | ']', None) if depth == 0 => return i,
+            ('}' | ')' | ']', None) => depth -= 1,
+            (',' | ';', None) if depth == 0 => return i,
+            ('\n', None) if depth == 0 && at_newline => return i,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// The fields of a Lua table constructor: `(Some(name), value)` for
+/// `name = value`, `(None, value)` for list items.
+fn lua_fields(table: &str) -> Vec<(Option<&str>, &str)> {
+    let mut rest = table
+        .trim()
+        .strip_prefix('{')
+        .and_then(|t| t.strip_suffix('}'))
+        .unwrap_or("");
+    let mut fields = Vec::new();
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',' || c == ';');
+        if rest.is_empty() {
+            break;
+        }
+        let end = lua_expression_end(rest, false).max(1);
+        let item = rest[..end].trim();
+        rest = &rest[end..];
+        let named = item.split_once('=').filter(|(name, value)| {
+            let name = name.trim();
+            !value.starts_with('=')
+                && !name.is_empty()
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+        match named {
+            Some((name, value)) => fields.push((Some(name.trim()), value.trim())),
+            None => fields.push((None, item)),
+        }
+    }
+    fields
+}
+
+/// A Lua string literal's contents.
+fn lua_string(value: &str) -> &str {
+    value.trim().trim_matches(['"', '\''])
+}
+
+/// A WezTerm padding: points or pixels; cells and percentages can't be converted.
+fn wezterm_length(value: &str) -> Option<f64> {
+    let value = lua_string(value);
+    value
+        .strip_suffix("px")
+        .or_else(|| value.strip_suffix("pt"))
+        .unwrap_or(value)
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn migrate_wezterm(text: &str, actions: &[String], m: &mut Migration) {
+    let code = strip_lua_comments(text);
+    let mut consumed = 0;
+    for caps in WEZTERM_SETTING_RE.captures_iter(&code) {
+        let whole = caps.get(0).unwrap();
+        if whole.start() < consumed {
+            continue;
+        }
+        let rest = &code[whole.end()..];
+        let value_start = rest.len() - rest.trim_start().len();
+        let end = value_start + lua_expression_end(&rest[value_start..], true);
+        let value = rest[value_start..end].trim();
+        consumed = whole.end() + end;
+
+        let name = &caps[1];
+        let source = format!("{} = {}", name, shown(value));
+        match name {
+            "font" => {
+                let families: Vec<&str> = WEZTERM_FONT_RE
+                    .captures_iter(value)
+                    .map(|c| c.get(1).unwrap().as_str())
+                    .collect();
+                if families.is_empty() {
+                    m.skip(source.clone(), "no font family found");
+                }
+                for family in families {
+                    m.map(source.clone(), "font-family", family);
+                }
+            }
+            "font_size" => m.map_font_size(source, lua_string(value).parse().ok()),
+            "window_background_opacity" => m.map_opacity(source, lua_string(value).parse().ok()),
+            "scrollback_lines" => m.map_scrollback(source, lua_string(value).parse().ok()),
+            "window_padding" => {
+                let sides: HashMap<&str, Option<f64>> = lua_fields(value)
+                    .into_iter()
+                    .filter_map(|(name, value)| Some((name?, wezterm_length(value))))
+                    .collect();
+                let side = |name| sides.get(name).copied().flatten();
+                match (side("top"), side("right"), side("bottom"), side("left")) {
+                    (Some(t), Some(r), Some(b), Some(l)) => m.map_padding(source, [t, r, b, l]),
+                    _ => m.skip(
+                        source,
+                        "Ghostty pads in points; all four sides are needed, and cells or percentages can't be converted",
+                    ),
+                }
+            }
+            "color_scheme" => m.map_with_note(
+                source,
+                "theme",
+                lua_string(value),
+                "if Ghostty has a theme by this name",
+            ),
+            "colors" => wezterm_colors(value, m),
+            _ if WEZTERM_KEY_SETTINGS.contains(&name) => {}
+            _ => m.skip(source, NOT_MAPPED),
+        }
+    }
+    m.map_keybinds(text, KeymapFormat::Wezterm, actions);
+}
+
+fn wezterm_colors(table: &str, m: &mut Migration) {
+    for (name, value) in lua_fields(table) {
+        let Some(name) = name else {
+            continue;
+        };
+        let source = format!("colors.{} = {}", name, shown(value));
+        if let Some((_, key)) = WEZTERM_COLORS.iter().find(|(n, _)| *n == name) {
+            m.map_color(source, key, lua_string(value));
+            continue;
+        }
+        let offset = match name {
+            "ansi" => 0,
+            "brights" => 8,
+            _ => {
+                m.skip(source, NOT_MAPPED);
+                continue;
+            }
+        };
+        for (i, (_, color)) in lua_fields(value).into_iter().take(8).enumerate() {
+            m.map_palette(
+                format!("colors.{}[{}]", name, i + 1),
+                offset + i,
+                lua_string(color),
+            );
+        }
+    }
+}
+
+// Windows Terminal -----------------------------------------------------------
+
+/// Scheme fields for the 16 palette colors, in order.
+const WINDOWS_TERMINAL_PALETTE: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "purple",
+    "cyan",
+    "white",
+    "brightBlack",
+    "brightRed",
+    "brightGreen",
+    "brightYellow",
+    "brightBlue",
+    "brightPurple",
+    "brightCyan",
+    "brightWhite",
+];
+
+/// Scheme fields for the other colors and the Ghostty keys they map to.
+const WINDOWS_TERMINAL_COLORS: &[(&str, &str)] = &[
+    ("background", "background"),
+    ("foreground", "foreground"),
+    ("cursorColor", "cursor-color"),
+    ("selectionBackground", "selection-background"),
+];
+
+/// Profile fields that only identify the profile.
+const WINDOWS_TERMINAL_IDENTITY: &[&str] = &["guid", "name", "hidden", "source"];
+
+/// JSON with comments and trailing commas, as Windows Terminal writes it, made
+/// plain JSON.
+fn strip_jsonc(text: &str) -> String {
+    let mut json = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            json.push(c);
+            match c {
+                '\\' => json.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                json.push(c);
+            }
+            ('/', Some('/')) => while chars.next_if(|c| *c != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+            }
+            (']' | '}', _) => {
+                let trimmed = json.trim_end().len();
+                if json[..trimmed].ends_with(',') {
+                    json.truncate(trimmed - 1);
+                }
+                json.push(c);
+            }
+            _ => json.push(c),
+        }
+    }
+    json
+}
+
+/// The default profile's settings, over the defaults for every profile.
+fn windows_terminal_profile(settings: &Value) -> Map<String, Value> {
+    let profiles = settings.get("profiles");
+    let mut merged = profiles
+        .and_then(|p| p.get("defaults"))
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    let list = profiles
+        .and_then(|p| p.get("list").or(Some(p)))
+        .and_then(Value::as_array);
+    let default_guid = settings.get("defaultProfile").and_then(Value::as_str);
+    let default = list.and_then(|list| {
+        list.iter()
+            .find(|p| {
+                default_guid.is_some() && p.get("guid").and_then(Value::as_str) == default_guid
+            })
+            .or_else(|| list.first())
+    });
+    if let Some(profile) = default.and_then(Value::as_object) {
+        merged.extend(profile.clone());
+    }
+    merged
+}
+
+fn migrate_windows_terminal(
+    text: &str,
+    actions: &[String],
+    m: &mut Migration,
+) -> Result<(), Error> {
+    let settings: Value = serde_json::from_str(&strip_jsonc(text))
+        .map_err(|e| Error::Config(format!("Not a valid Windows Terminal settings.json: {}", e)))?;
+
+    for (name, value) in windows_terminal_profile(&settings) {
+        if WINDOWS_TERMINAL_IDENTITY.contains(&name.as_str()) {
+            continue;
+        }
+        let source = format!("{}: {}", name, shown(&value.to_string()));
+        match name.as_str() {
+            "font" => {
+                for (field, value) in value.as_object().into_iter().flatten() {
+                    let source = format!("font.{}: {}", field, shown(&value.to_string()));
+                    match field.as_str() {
+                        "face" => windows_terminal_font(source, value, m),
+                        "size" => m.map_font_size(source, number(value)),
+                        _ => m.skip(source, NOT_MAPPED),
+                    }
+                }
+            }
+            "fontFace" => windows_terminal_font(source, &value, m),
+            "fontSize" => m.map_font_size(source, number(&value)),
+            "opacity" => m.map_opacity(source, number(&value).map(|o| o / 100.0)),
+            "historySize" => m.map_scrollback(source, number(&value)),
+            "padding" => {
+                let values: Option<Vec<f64>> = value
+                    .as_str()
+                    .map(|p| p.split(',').map(|v| v.trim().parse().ok()).collect())
+                    .unwrap_or_else(|| number(&value).map(|n| vec![n]));
+                // Windows Terminal's order: all; horizontal, vertical; or left, top, right, bottom.
+                let sides = match values.as_deref() {
+                    Some(&[all]) => Some([all, all, all, all]),
+                    Some(&[h, v]) => Some([v, h, v, h]),
+                    Some(&[l, t, r, b]) => Some([t, r, b, l]),
+                    _ => None,
+                };
+                match sides {
+                    Some(sides) => m.map_padding(source, sides),
+                    None => m.skip(source, "not a padding"),
+                }
+            }
+            "colorScheme" => {
+                // A name, or `{ "dark": ..., "light": ... }`.
+                let scheme = value
+                    .as_str()
+                    .or_else(|| value.get("dark").and_then(Value::as_str))
+                    .unwrap_or_default();
+                windows_terminal_scheme(&settings, scheme, source, m);
+            }
+            _ => m.skip(source, NOT_MAPPED),
+        }
+    }
+    windows_terminal_keybinds(&settings, actions, m);
+    Ok(())
+}
+
+fn windows_terminal_font(source: String, face: &Value, m: &mut Migration) {
+    // Fallback faces are comma-separated.
+    for family in face.as_str().unwrap_or_default().split(',').map(str::trim) {
+        if !family.is_empty() {
+            m.map(source.clone(), "font-family", family);
+        }
+    }
+}
+
+fn windows_terminal_scheme(settings: &Value, name: &str, source: String, m: &mut Migration) {
+    let scheme = settings
+        .get("schemes")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .find(|s| s.get("name").and_then(Value::as_str) == Some(name));
+    let Some(scheme) = scheme else {
+        return m.map_with_note(
+            source,
+            "theme",
+            name,
+            "built into Windows Terminal; Ghostty may have a theme by this name",
+        );
+    };
+    let color = |field: &str| scheme.get(field).and_then(Value::as_str);
+    for (field, key) in WINDOWS_TERMINAL_COLORS {
+        if let Some(value) = color(field) {
+            m.map_color(format!("{}.{}", name, field), key, value);
+        }
+    }
+    for (i, field) in WINDOWS_TERMINAL_PALETTE.iter().enumerate() {
+        if let Some(value) = color(field) {
+            m.map_palette(format!("{}.{}", name, field), i, value);
+        }
+    }
+}
+
+/// The Ghostty action for a Windows Terminal command: a name, or an object with
+/// an `action` and its arguments.
+fn windows_terminal_action(command: &Value) -> Option<String> {
+    let name = command
+        .as_str()
+        .or_else(|| command.get("action").and_then(Value::as_str))?;
+    let arg = |field: &str| command.get(field);
+    let mapped = match name {
+        "copy" => "copy_to_clipboard".to_string(),
+        "paste" => "paste_from_clipboard".to_string(),
+        "newTab" => "new_tab".to_string(),
+        "newWindow" => "new_window".to_string(),
+        "closeTab" | "closePane" => "close_surface".to_string(),
+        "closeWindow" => "close_window".to_string(),
+        "nextTab" => "next_tab".to_string(),
+        "prevTab" => "previous_tab".to_string(),
+        "switchToTab" => format!("goto_tab:{}", arg("index")?.as_u64()? + 1),
+        "splitPane" => {
+            let split = arg("split").and_then(Value::as_str).unwrap_or("auto");
+            let direction = match split {
+                "right" | "vertical" => "right",
+                "down" | "horizontal" => "down",
+                "left" => "left",
+                "up" => "up",
+                _ => "auto",
+            };
+            format!("new_split:{}", direction)
+        }
+        "moveFocus" => format!(
+            "goto_split:{}",
+            match arg("direction")?.as_str()? {
+                "previous" => "previous",
+                "nextInOrder" => "next",
+                direction @ ("left" | "right" | "up" | "down") => direction,
+                _ => return None,
+            }
+        ),
+        "adjustFontSize" => match arg("delta").and_then(Value::as_f64).unwrap_or(1.0) {
+            delta if delta < 0.0 => format!("decrease_font_size:{}", format_number(-delta)),
+            delta => format!("increase_font_size:{}", format_number(delta)),
+        },
+        "resetFontSize" => "reset_font_size".to_string(),
+        "toggleFullscreen" => "toggle_fullscreen".to_string(),
+        "scrollUpPage" => "scroll_page_up".to_string(),
+        "scrollDownPage" => "scroll_page_down".to_string(),
+        "scrollToTop" => "scroll_to_top".to_string(),
+        "scrollToBottom" => "scroll_to_bottom".to_string(),
+        "clearBuffer" => "clear_screen".to_string(),
+        "openSettings" => "open_config".to_string(),
+        "commandPalette" => "toggle_command_palette".to_string(),
+        "quit" => "quit".to_string(),
+        "unbound" => "unbind".to_string(),
+        "sendInput" => text_action(arg("input")?.as_str()?),
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+/// A Windows Terminal key chord like `ctrl+shift+pgup` in Ghostty's terms.
+fn windows_terminal_trigger(keys: &str) -> Result<String, String> {
+    let mut modifiers = Vec::new();
+    let mut key = None;
+    for part in keys.split('+').map(str::trim) {
+        match ghostty_modifier(part) {
+            Some(modifier) => modifiers.push(modifier),
+            None => key = Some(ghostty_key(part).ok_or_else(|| format!("unknown key '{}'", part))?),
+        }
+    }
+    let key = key.ok_or_else(|| format!("'{}' has no key", keys))?;
+    Ok(chord(&modifiers, &key))
+}
+
+fn windows_terminal_keybinds(settings: &Value, actions: &[String], m: &mut Migration) {
+    let list = |name: &str| {
+        settings
+            .get(name)
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let defined = list("actions");
+    // Newer settings name each action and bind keys to the name separately.
+    let by_id: HashMap<&str, &Value> = defined
+        .iter()
+        .filter_map(|a| Some((a.get("id")?.as_str()?, a.get("command")?)))
+        .collect();
+    let bindings = list("keybindings");
+    let bound = defined.iter().chain(&bindings).filter_map(|entry| {
+        let command = entry
+            .get("command")
+            .or_else(|| by_id.get(entry.get("id")?.as_str()?).copied())?;
+        Some((entry.get("keys")?, command))
+    });
+
+    for (keys, command) in bound {
+        let chords: Vec<&str> = match keys {
+            Value::Array(keys) => keys.iter().filter_map(Value::as_str).collect(),
+            keys => keys.as_str().into_iter().collect(),
+        };
+        for keys in chords {
+            let name = command
+                .as_str()
+                .or_else(|| command.get("action").and_then(Value::as_str))
+                .unwrap_or("unbound");
+            let source = format!("{}: {}", keys, name);
+            let binding = windows_terminal_trigger(keys).and_then(|trigger| {
+                let action = windows_terminal_action(command).ok_or_else(|| {
+                    format!("Windows Terminal's {} has no Ghostty equivalent", name)
+                })?;
+                check_binding(&trigger, &action, actions)
+            });
+            match binding {
+                Ok(binding) => m.map(source, "keybind", binding),
+                Err(reason) => m.skip(source, reason),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapped(migration: &Migration, key: &str) -> Vec<String> {
+        migration
+            .mappings
+            .iter()
+            .filter(|m| m.key == key)
+            .map(|m| m.value.clone())
+            .collect()
+    }
+
+    fn unmapped(migration: &Migration) -> Vec<&str> {
+        migration
+            .unmapped
+            .iter()
+            .map(|u| u.source.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_source() {
+        for source in MigrateSource::all() {
+            assert_eq!(MigrateSource::parse(source.slug()).unwrap(), source);
+        }
+        assert!(MigrateSource::parse("hyper").is_err());
+    }
+
+    #[test]
+    fn test_migrate_kitty() {
+        let text = "\
+# Fonts
+font_family family=\"JetBrains Mono\" style=Regular
+bold_font auto
+font_size 13.5
+background_opacity 0.9
+window_padding_width 4 8
+scrollback_lines 5000
+background #1e1e2e
+color1 #f38ba8
+cursor_text_color background
+tab_bar_style powerline
+map ctrl+shift+t new_tab
+map ctrl+shift+l next_layout
+";
+        let migration = migrate(text, MigrateSource::Kitty, &[]).unwrap();
+        assert_eq!(mapped(&migration, "font-family"), vec!["JetBrains Mono"]);
+        assert_eq!(mapped(&migration, "font-size"), vec!["13.5"]);
+        assert_eq!(mapped(&migration, "background-opacity"), vec!["0.9"]);
+        assert_eq!(mapped(&migration, "window-padding-x"), vec!["8"]);
+        assert_eq!(mapped(&migration, "window-padding-y"), vec!["4"]);
+        assert_eq!(mapped(&migration, "scrollback-limit"), vec!["5000000"]);
+        assert_eq!(mapped(&migration, "background"), vec!["#1e1e2e"]);
+        assert_eq!(mapped(&migration, "palette"), vec!["1=#f38ba8"]);
+        assert_eq!(mapped(&migration, "keybind"), vec!["ctrl+shift+t=new_tab"]);
+        assert_eq!(
+            unmapped(&migration),
+            vec![
+                "bold_font auto",
+                "cursor_text_color background",
+                "tab_bar_style powerline",
+                "map ctrl+shift+l next_layout",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_migrate_alacritty() {
+        let text = r##"
+[font]
+size = 12
+normal = { family = "Fira Code", style = "Regular" }
+
+[window]
+opacity = 0.85
+padding = { x = 6, y = 4 }
+
+[scrolling]
+history = 10000
+
+[colors.primary]
+background = "0x282a36"
+
+[colors.normal]
+red = "#ff5555"
+
+[[keyboard.bindings]]
+key = "N"
+mods = "Control|Shift"
+action = "SpawnNewInstance"
+
+[[keyboard.bindings]]
+key = "Key1"
+mods = "Alt"
+chars = "\u001b1"
+
+[[keyboard.bindings]]
+key = "K"
+mods = "Command"
+mode = "~Vi"
+action = "ClearHistory"
+"##;
+        let migration = migrate(text, MigrateSource::Alacritty, &[]).unwrap();
+        assert_eq!(mapped(&migration, "font-family"), vec!["Fira Code"]);
+        assert_eq!(mapped(&migration, "font-size"), vec!["12"]);
+        assert_eq!(mapped(&migration, "background-opacity"), vec!["0.85"]);
+        assert_eq!(mapped(&migration, "window-padding-x"), vec!["6"]);
+        assert_eq!(mapped(&migration, "window-padding-y"), vec!["4"]);
+        assert_eq!(mapped(&migration, "scrollback-limit"), vec!["10000000"]);
+        assert_eq!(mapped(&migration, "background"), vec!["#282a36"]);
+        assert_eq!(mapped(&migration, "palette"), vec!["1=#ff5555"]);
+        assert_eq!(
+            mapped(&migration, "keybind"),
+            vec!["ctrl+shift+n=new_window", "alt+1=text:\\x1b1"]
+        );
+        assert_eq!(
+            unmapped(&migration),
+            vec!["font.normal.style = \"Regular\"", "Command+K: ClearHistory"]
+        );
+        assert!(migrate("[font", MigrateSource::Alacritty, &[]).is_err());
+    }
+
+    #[test]
+    fn test_migrate_wezterm() {
+        let text = r#"
+local wezterm = require 'wezterm'
+local config = wezterm.config_builder()
+
+config.font = wezterm.font_with_fallback {
+  'JetBrains Mono', -- main
+  { family = 'Symbols Nerd Font', scale = 0.9 },
+}
+config.font_size = 13.0
+config.color_scheme = 'Catppuccin Mocha'
+config.window_background_opacity = 0.95
+config.window_padding = { left = 2, right = '4px', top = 0, bottom = 0 }
+config.colors = {
+  cursor_bg = '#f5e0dc',
+  ansi = { '#45475a', '#f38ba8' },
+  tab_bar = { background = '#11111b' },
+}
+config.hide_tab_bar_if_only_one_tab = true
+config.keys = {
+  { key = 't', mods = 'CMD', action = wezterm.action.SpawnTab 'CurrentPaneDomain' },
+}
+return config
+"#;
+        let migration = migrate(text, MigrateSource::Wezterm, &[]).unwrap();
+        assert_eq!(
+            mapped(&migration, "font-family"),
+            vec!["JetBrains Mono", "Symbols Nerd Font"]
+        );
+        assert_eq!(mapped(&migration, "font-size"), vec!["13"]);
+        assert_eq!(mapped(&migration, "theme"), vec!["Catppuccin Mocha"]);
+        assert_eq!(mapped(&migration, "background-opacity"), vec!["0.95"]);
+        assert_eq!(mapped(&migration, "window-padding-x"), vec!["2,4"]);
+        assert_eq!(mapped(&migration, "window-padding-y"), vec!["0"]);
+        assert_eq!(mapped(&migration, "cursor-color"), vec!["#f5e0dc"]);
+        assert_eq!(
+            mapped(&migration, "palette"),
+            vec!["0=#45475a", "1=#f38ba8"]
+        );
+        assert_eq!(mapped(&migration, "keybind"), vec!["super+t=new_tab"]);
+        assert_eq!(
+            unmapped(&migration),
+            vec![
+                "colors.tab_bar = { background = '#11111b' }",
+                "hide_tab_bar_if_only_one_tab = true",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_migrate_iterm() {
+        let text = r#"{
+  "Name": "Default",
+  "Guid": "A1B2",
+  "Normal Font": "JetBrainsMono-Regular 14",
+  "Transparency": 0.1,
+  "Scrollback Lines": 2000,
+  "Side Margins": 8,
+  "Ansi 1 Color (Dark)": {"Red Component": 1, "Green Component": 0.3333, "Blue Component": 0.3333},
+  "Ansi 1 Color (Light)": {"Red Component": 0.8, "Green Component": 0, "Blue Component": 0},
+  "Background Color": {"Red Component": 0, "Green Component": 0, "Blue Component": 0},
+  "Blinking Cursor": true,
+  "Keyboard Map": {
+    "0x74-0x100000": {"Action": 27, "Text": ""}
+  }
+}"#;
+        let migration = migrate(text, MigrateSource::ITerm, &[]).unwrap();
+        assert_eq!(mapped(&migration, "font-family"), vec!["JetBrainsMono"]);
+        assert_eq!(mapped(&migration, "font-size"), vec!["14"]);
+        assert_eq!(mapped(&migration, "background-opacity"), vec!["0.9"]);
+        assert_eq!(mapped(&migration, "scrollback-limit"), vec!["2000000"]);
+        assert_eq!(mapped(&migration, "window-padding-x"), vec!["8"]);
+        assert_eq!(mapped(&migration, "palette"), vec!["1=#ff5555"]);
+        assert_eq!(mapped(&migration, "background"), vec!["#000000"]);
+        assert_eq!(mapped(&migration, "keybind").len(), 1);
+        assert_eq!(
+            unmapped(&migration),
+            vec!["Ansi 1 Color (Light)", "Blinking Cursor"]
+        );
+        assert!(migrate("font = x", MigrateSource::ITerm, &[]).is_err());
+    }
+
+    #[test]
+    fn test_migrate_windows_terminal() {
+        let text = r##"{
+    // Comments and trailing commas are allowed.
+    "defaultProfile": "{b}",
+    "profiles": {
+        "defaults": { "font": { "face": "Cascadia Code", "size": 11 }, "opacity": 85 },
+        "list": [
+            { "guid": "{a}", "name": "PowerShell" },
+            { "guid": "{b}", "name": "Ubuntu", "colorScheme": "Dracula", "padding": "8, 4", "historySize": 9001, "cursorShape": "bar", },
+        ],
+    },
+    "schemes": [
+        { "name": "Dracula", "background": "#282A36", "red": "#FF5555" }
+    ],
+    "actions": [
+        { "command": { "action": "copy", "singleLine": false }, "id": "User.copy" },
+        { "command": "find", "keys": "ctrl+shift+f" },
+    ],
+    "keybindings": [
+        { "id": "User.copy", "keys": "ctrl+c" },
+        { "id": null, "keys": "ctrl+v" }
+    ]
+}"##;
+        let migration = migrate(text, MigrateSource::WindowsTerminal, &[]).unwrap();
+        assert_eq!(mapped(&migration, "font-family"), vec!["Cascadia Code"]);
+        assert_eq!(mapped(&migration, "font-size"), vec!["11"]);
+        assert_eq!(mapped(&migration, "background-opacity"), vec!["0.85"]);
+        assert_eq!(mapped(&migration, "window-padding-x"), vec!["8"]);
+        assert_eq!(mapped(&migration, "window-padding-y"), vec!["4"]);
+        assert_eq!(mapped(&migration, "scrollback-limit"), vec!["9001000"]);
+        assert_eq!(mapped(&migration, "background"), vec!["#282a36"]);
+        assert_eq!(mapped(&migration, "palette"), vec!["1=#ff5555"]);
+        assert_eq!(
+            mapped(&migration, "keybind"),
+            vec!["ctrl+c=copy_to_clipboard"]
+        );
+        assert_eq!(
+            unmapped(&migration),
+            vec!["cursorShape: \"bar\"", "ctrl+shift+f: find"]
+        );
+    }
+
+    #[test]
+    fn test_strip_jsonc_keeps_strings() {
+        assert_eq!(
+            strip_jsonc(r#"{"a": "http://x/*y*/", /* c */ "b": [1, 2,], } // end"#),
+            r#"{"a": "http://x/*y*/",  "b": [1, 2]} "#
+        );
+    }
+
+    #[test]
+    fn test_migrate_checks_actions() {
+        let actions = vec!["new_tab".to_string()];
+        let migration = migrate(
+            "map ctrl+c copy_to_clipboard\n",
+            MigrateSource::Kitty,
+            &actions,
+        )
+        .unwrap();
+        assert!(migration.mappings.is_empty());
+        assert_eq!(migration.unmapped.len(), 1);
+    }
+}
@@ -0,0 +1,44 @@
+//! Reading, editing, and comparing Ghostty config files, independent of the CLI.
+
+pub mod adjust_metric;
+pub mod annotate;
+pub mod background;
+pub mod baseline;
+pub mod categorize;
+pub mod color_import;
+pub mod color_names;
+pub mod color_vision;
+pub mod conditions;
+pub mod contrast;
+pub mod corruption;
+pub mod custom_theme;
+pub mod diff;
+pub mod doctor;
+pub mod export_filter;
+pub mod file_io;
+pub mod font_features;
+pub mod font_variation;
+pub mod includes;
+pub mod key_event;
+pub mod keybind;
+pub mod keybind_conflicts;
+pub mod keybind_import;
+pub mod keybind_presets;
+pub mod lint;
+pub mod merge;
+pub mod migrate;
+pub mod model;
+pub mod os_shortcuts;
+pub mod palette;
+pub mod palette_generator;
+pub mod parser;
+pub mod paths;
+pub mod scratch;
+pub mod starter;
+pub mod structured;
+pub mod theme_metadata;
+pub mod theme_overrides;
+pub mod theme_selection;
+pub mod type_inference;
+pub mod units;
+pub mod validate_value;
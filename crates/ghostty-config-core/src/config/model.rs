@@ -0,0 +1,838 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use super::categorize::categorize_key;
+use super::conditions::{conditions_of, OptionConditions};
+use super::os_shortcuts::Platform;
+
+/// One choice of an enum option.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnumVariant {
+    pub value: String,
+    /// What the docs say after the value's bullet; empty when they say nothing.
+    pub doc: String,
+}
+
+/// The type of value a config option accepts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ConfigValueType {
+    Boolean,
+    Integer,
+    Float,
+    /// A fraction from 0 to 1, such as an opacity, edited with a slider.
+    Percent,
+    Color,
+    Enum(Vec<EnumVariant>),
+    Text,
+    Font,
+    Path,
+    /// A length of time, edited as an amount and a unit.
+    Duration,
+    /// A number of bytes, edited as an amount and a unit.
+    ByteSize,
+    /// An `adjust-*` font metric change: pixels or a percentage.
+    AdjustMetric,
+    Keybind,
+    Palette,
+    CommaSeparated(Box<ConfigValueType>),
+}
+
+impl fmt::Display for ConfigValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigValueType::Boolean => write!(f, "boolean"),
+            ConfigValueType::Integer => write!(f, "integer"),
+            ConfigValueType::Float => write!(f, "float"),
+            ConfigValueType::Percent => write!(f, "percent"),
+            ConfigValueType::Color => write!(f, "color"),
+            ConfigValueType::Enum(_) => write!(f, "enum"),
+            ConfigValueType::Text => write!(f, "text"),
+            ConfigValueType::Font => write!(f, "font"),
+            ConfigValueType::Path => write!(f, "path"),
+            ConfigValueType::Duration => write!(f, "duration"),
+            ConfigValueType::ByteSize => write!(f, "byte-size"),
+            ConfigValueType::AdjustMetric => write!(f, "adjust-metric"),
+            ConfigValueType::Keybind => write!(f, "keybind"),
+            ConfigValueType::Palette => write!(f, "palette"),
+            ConfigValueType::CommaSeparated(_) => write!(f, "comma-separated"),
+        }
+    }
+}
+
+/// UI category for grouping config options.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum Category {
+    Fonts,
+    Colors,
+    Window,
+    Cursor,
+    Mouse,
+    Clipboard,
+    Keybindings,
+    Shell,
+    Appearance,
+    Background,
+    MacOS,
+    GTKLinux,
+    Scrollback,
+    Input,
+    Terminal,
+    Advanced,
+}
+
+impl Category {
+    /// The platform a category's options are specific to, if any.
+    pub fn platform(&self) -> Option<Platform> {
+        match self {
+            Category::MacOS => Some(Platform::MacOS),
+            Category::GTKLinux => Some(Platform::Linux),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<Category> {
+        vec![
+            Category::Fonts,
+            Category::Colors,
+            Category::Window,
+            Category::Cursor,
+            Category::Mouse,
+            Category::Clipboard,
+            Category::Keybindings,
+            Category::Shell,
+            Category::Appearance,
+            Category::Background,
+            Category::MacOS,
+            Category::GTKLinux,
+            Category::Scrollback,
+            Category::Input,
+            Category::Terminal,
+            Category::Advanced,
+        ]
+    }
+
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Category::Fonts => "fonts",
+            Category::Colors => "colors",
+            Category::Window => "window",
+            Category::Cursor => "cursor",
+            Category::Mouse => "mouse",
+            Category::Clipboard => "clipboard",
+            Category::Keybindings => "keybindings",
+            Category::Shell => "shell",
+            Category::Appearance => "appearance",
+            Category::Background => "background",
+            Category::MacOS => "macos",
+            Category::GTKLinux => "gtk-linux",
+            Category::Scrollback => "scrollback",
+            Category::Input => "input",
+            Category::Terminal => "terminal",
+            Category::Advanced => "advanced",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Category::Fonts => "Fonts",
+            Category::Colors => "Colors",
+            Category::Window => "Window",
+            Category::Cursor => "Cursor",
+            Category::Mouse => "Mouse",
+            Category::Clipboard => "Clipboard",
+            Category::Keybindings => "Keybindings",
+            Category::Shell => "Shell",
+            Category::Appearance => "Appearance",
+            Category::Background => "Background",
+            Category::MacOS => "macOS",
+            Category::GTKLinux => "GTK / Linux",
+            Category::Scrollback => "Scrollback",
+            Category::Input => "Input",
+            Category::Terminal => "Terminal",
+            Category::Advanced => "Advanced",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Category::Fonts => "Aa",
+            Category::Colors => "\u{1f3a8}",
+            Category::Window => "\u{25a1}",
+            Category::Cursor => "\u{258d}",
+            Category::Mouse => "\u{1f5b1}",
+            Category::Clipboard => "\u{1f4cb}",
+            Category::Keybindings => "\u{2328}",
+            Category::Shell => ">_",
+            Category::Appearance => "\u{2728}",
+            Category::Background => "\u{1f5bc}",
+            Category::MacOS => "\u{1f34e}",
+            Category::GTKLinux => "\u{1f427}",
+            Category::Scrollback => "\u{2195}",
+            Category::Input => "\u{270f}",
+            Category::Terminal => "\u{1f4df}",
+            Category::Advanced => "\u{2699}",
+        }
+    }
+}
+
+/// A single config option parsed from ghostty +show-config --default --docs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigOption {
+    pub key: String,
+    pub default_value: String,
+    pub documentation: String,
+    pub value_type: ConfigValueType,
+    pub category: Category,
+    pub is_repeatable: bool,
+    /// When it has an effect; see `conditions::unmet_condition`.
+    #[serde(default)]
+    pub conditions: OptionConditions,
+}
+
+/// Ghostty's config reference, with one anchor per key.
+pub const GHOSTTY_DOCS_URL: &str = "https://ghostty.org/docs/config/reference";
+
+impl ConfigOption {
+    /// HTML id of this option's field on its category page.
+    pub fn anchor(&self) -> String {
+        format!("field-{}", self.key)
+    }
+
+    /// Link to this option's field in the UI.
+    pub fn permalink(&self) -> String {
+        format!("/category/{}#{}", self.category.slug(), self.anchor())
+    }
+
+    /// The same option in the official Ghostty docs.
+    pub fn docs_url(&self) -> String {
+        format!("{}#{}", GHOSTTY_DOCS_URL, self.key)
+    }
+}
+
+/// The full schema of all discovered config options.
+///
+/// Indexed by key and by category when built, since handlers look options up
+/// on every request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "SchemaOptions")]
+pub struct ConfigSchema {
+    options: Vec<ConfigOption>,
+    #[serde(skip)]
+    by_key: HashMap<String, usize>,
+    #[serde(skip)]
+    by_category: HashMap<Category, Vec<usize>>,
+}
+
+/// What a [`ConfigSchema`] is serialized as; the indexes are rebuilt on load.
+#[derive(Deserialize)]
+struct SchemaOptions {
+    options: Vec<ConfigOption>,
+}
+
+impl From<SchemaOptions> for ConfigSchema {
+    fn from(schema: SchemaOptions) -> Self {
+        Self::new(schema.options)
+    }
+}
+
+impl ConfigSchema {
+    pub fn new(options: Vec<ConfigOption>) -> Self {
+        let mut by_key = HashMap::with_capacity(options.len());
+        let mut by_category: HashMap<Category, Vec<usize>> = HashMap::new();
+        for (i, option) in options.iter().enumerate() {
+            by_key.insert(option.key.clone(), i);
+            by_category
+                .entry(option.category.clone())
+                .or_default()
+                .push(i);
+        }
+        Self {
+            options,
+            by_key,
+            by_category,
+        }
+    }
+
+    /// The same options, categorized by the current category table and with the
+    /// current conditions; for a schema cached before either table changed.
+    pub fn recategorize(self) -> Self {
+        let options = self
+            .options
+            .into_iter()
+            .map(|option| ConfigOption {
+                category: categorize_key(&option.key),
+                conditions: conditions_of(&option.key, &option.documentation),
+                ..option
+            })
+            .collect();
+        Self::new(options)
+    }
+
+    /// All options, in the order Ghostty lists them.
+    pub fn options(&self) -> &[ConfigOption] {
+        &self.options
+    }
+
+    pub fn options_for_category(&self, cat: &Category) -> Vec<&ConfigOption> {
+        self.by_category
+            .get(cat)
+            .map(|indexes| indexes.iter().map(|&i| &self.options[i]).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn find_option(&self, key: &str) -> Option<&ConfigOption> {
+        self.by_key.get(key).map(|&i| &self.options[i])
+    }
+}
+
+/// An entry in the user's config file — preserves structure.
+#[derive(Debug, Clone)]
+pub enum ConfigEntry {
+    Comment(String),
+    BlankLine,
+    KeyValue {
+        key: String,
+        value: String,
+        /// A comment after the value on the same line, with the whitespace before
+        /// it, e.g. `  # big monitor`. It stays put when the value changes.
+        comment: Option<String>,
+    },
+}
+
+impl ConfigEntry {
+    /// A `key = value` line without a trailing comment.
+    pub fn key_value(key: impl Into<String>, value: impl Into<String>) -> Self {
+        ConfigEntry::KeyValue {
+            key: key.into(),
+            value: value.into(),
+            comment: None,
+        }
+    }
+}
+
+/// The user's config file, preserving comments and ordering.
+#[derive(Debug, Clone)]
+pub struct UserConfig {
+    pub entries: Vec<ConfigEntry>,
+    pub file_path: PathBuf,
+}
+
+impl UserConfig {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            entries: Vec::new(),
+            file_path,
+        }
+    }
+
+    /// Get the value for a key (returns the last occurrence for repeatable keys).
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter().rev().find_map(|e| match e {
+            ConfigEntry::KeyValue { key: k, value, .. } if k == key => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Get all values for a repeatable key.
+    pub fn get_all(&self, key: &str) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter_map(|e| match e {
+                ConfigEntry::KeyValue { key: k, value, .. } if k == key => Some(value.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Set a value. Updates existing key in-place or appends.
+    pub fn set(&mut self, key: &str, value: &str) {
+        // Find existing key and update in-place
+        for entry in &mut self.entries {
+            if let ConfigEntry::KeyValue {
+                key: k, value: v, ..
+            } = entry
+            {
+                if k == key {
+                    *v = value.to_string();
+                    return;
+                }
+            }
+        }
+        // Key not found — append
+        self.entries
+            .push(ConfigEntry::key_value(key.to_string(), value.to_string()));
+    }
+
+    /// Replace every value of a key, keeping the position of its first occurrence.
+    ///
+    /// Appends when the key is not present; removes the key when `values` is empty.
+    pub fn set_all(&mut self, key: &str, values: &[String]) {
+        let position = self
+            .entries
+            .iter()
+            .position(|e| matches!(e, ConfigEntry::KeyValue { key: k, .. } if k == key));
+        let comments: Vec<Option<String>> = self
+            .entries
+            .iter()
+            .filter_map(|e| match e {
+                ConfigEntry::KeyValue {
+                    key: k, comment, ..
+                } if k == key => Some(comment.clone()),
+                _ => None,
+            })
+            .collect();
+        self.remove(key);
+
+        // Each value keeps the trailing comment of the line it replaces.
+        let new_entries = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| ConfigEntry::KeyValue {
+                key: key.to_string(),
+                value: value.clone(),
+                comment: comments.get(i).cloned().flatten(),
+            });
+        match position {
+            Some(index) => {
+                self.entries.splice(index..index, new_entries);
+            }
+            None => self.entries.extend(new_entries),
+        }
+    }
+
+    /// Remove a key (reset to default). Removes the line entirely.
+    pub fn remove(&mut self, key: &str) {
+        self.entries.retain(|e| match e {
+            ConfigEntry::KeyValue { key: k, .. } => k != key,
+            _ => true,
+        });
+    }
+
+    /// Position in `entries` of the `index`th value of `key`.
+    fn position_of(&self, key: &str, index: usize) -> Option<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| matches!(e, ConfigEntry::KeyValue { key: k, .. } if k == key))
+            .nth(index)
+            .map(|(position, _)| position)
+    }
+
+    /// Replace the `index`th value of a repeatable key, leaving its other values alone.
+    ///
+    /// Returns false when the key has no such entry.
+    pub fn set_at(&mut self, key: &str, index: usize, value: &str) -> bool {
+        let Some(position) = self.position_of(key, index) else {
+            return false;
+        };
+        if let ConfigEntry::KeyValue { value: v, .. } = &mut self.entries[position] {
+            *v = value.to_string();
+        }
+        true
+    }
+
+    /// Remove the `index`th value of a repeatable key. Returns false when there is none.
+    pub fn remove_at(&mut self, key: &str, index: usize) -> bool {
+        let Some(position) = self.position_of(key, index) else {
+            return false;
+        };
+        self.entries.remove(position);
+        true
+    }
+
+    /// Add a value of a repeatable key directly after its `index`th value, so that it
+    /// becomes value `index + 1`. Returns false when the key has no such entry.
+    pub fn insert_after(&mut self, key: &str, index: usize, value: &str) -> bool {
+        let Some(position) = self.position_of(key, index) else {
+            return false;
+        };
+        self.entries.insert(
+            position + 1,
+            ConfigEntry::key_value(key.to_string(), value.to_string()),
+        );
+        true
+    }
+
+    /// Append a value of a repeatable key after any it already has.
+    pub fn push(&mut self, key: &str, value: &str) {
+        self.entries
+            .push(ConfigEntry::key_value(key.to_string(), value.to_string()));
+    }
+
+    /// Get all set key-value pairs.
+    pub fn all_set_values(&self) -> Vec<(&str, &str)> {
+        self.entries
+            .iter()
+            .filter_map(|e| match e {
+                ConfigEntry::KeyValue { key, value, .. } => Some((key.as_str(), value.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Info about an installed theme.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeInfo {
+    pub name: String,
+    pub background: String,
+    pub foreground: String,
+    pub palette: Vec<String>,
+    pub is_dark: bool,
+    pub cursor_color: Option<String>,
+    pub selection_background: Option<String>,
+    /// Lives in the themes directory next to the config, where the theme editor
+    /// writes, so it can be edited and deleted here.
+    #[serde(default)]
+    pub custom: bool,
+    #[serde(default)]
+    pub origin: ThemeOrigin,
+}
+
+/// Where a theme was found.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeOrigin {
+    /// Shipped with Ghostty.
+    #[default]
+    Bundled,
+    /// One of the user's theme directories; see `user_theme_dirs`.
+    User,
+}
+
+impl ThemeOrigin {
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeOrigin::Bundled => "Bundled",
+            ThemeOrigin::User => "User",
+        }
+    }
+}
+
+/// Info about a font family.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontFamily {
+    pub name: String,
+    pub styles: Vec<String>,
+}
+
+/// A config at `/home/me/.config/ghostty/config` setting each key in order, for tests.
+#[cfg(test)]
+pub(crate) fn test_config(pairs: &[(&str, &str)]) -> UserConfig {
+    let mut config = UserConfig::new(PathBuf::from("/home/me/.config/ghostty/config"));
+    for (key, value) in pairs {
+        config
+            .entries
+            .push(ConfigEntry::key_value(key.to_string(), value.to_string()));
+    }
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --- ConfigValueType ---
+
+    #[test]
+    fn test_config_value_type_display() {
+        assert_eq!(ConfigValueType::Boolean.to_string(), "boolean");
+        assert_eq!(ConfigValueType::Integer.to_string(), "integer");
+        assert_eq!(ConfigValueType::Float.to_string(), "float");
+        assert_eq!(ConfigValueType::Percent.to_string(), "percent");
+        assert_eq!(ConfigValueType::Color.to_string(), "color");
+        assert_eq!(ConfigValueType::Enum(vec![]).to_string(), "enum");
+        assert_eq!(ConfigValueType::Text.to_string(), "text");
+        assert_eq!(ConfigValueType::Font.to_string(), "font");
+        assert_eq!(ConfigValueType::Path.to_string(), "path");
+        assert_eq!(ConfigValueType::Duration.to_string(), "duration");
+        assert_eq!(ConfigValueType::ByteSize.to_string(), "byte-size");
+        assert_eq!(ConfigValueType::AdjustMetric.to_string(), "adjust-metric");
+        assert_eq!(ConfigValueType::Keybind.to_string(), "keybind");
+        assert_eq!(ConfigValueType::Palette.to_string(), "palette");
+        assert_eq!(
+            ConfigValueType::CommaSeparated(Box::new(ConfigValueType::Text)).to_string(),
+            "comma-separated"
+        );
+    }
+
+    // --- Category ---
+
+    #[test]
+    fn test_category_all_returns_all_variants() {
+        let all = Category::all();
+        assert_eq!(all.len(), 16);
+    }
+
+    #[test]
+    fn test_category_slug_roundtrip() {
+        for cat in Category::all() {
+            let slug = cat.slug();
+            assert!(!slug.is_empty());
+            // Slug should be lowercase and contain only alphanumeric chars or hyphens
+            assert!(slug.chars().all(|c| c.is_alphanumeric() || c == '-'));
+        }
+    }
+
+    #[test]
+    fn test_category_display_name_nonempty() {
+        for cat in Category::all() {
+            assert!(!cat.display_name().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_category_icon_nonempty() {
+        for cat in Category::all() {
+            assert!(!cat.icon().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_category_equality() {
+        assert_eq!(Category::Fonts, Category::Fonts);
+        assert_ne!(Category::Fonts, Category::Colors);
+    }
+
+    #[test]
+    fn test_option_links() {
+        let option = ConfigOption {
+            key: "font-size".to_string(),
+            default_value: "13".to_string(),
+            documentation: String::new(),
+            value_type: ConfigValueType::Float,
+            category: Category::Fonts,
+            is_repeatable: false,
+            conditions: Default::default(),
+        };
+        assert_eq!(option.anchor(), "field-font-size");
+        assert_eq!(option.permalink(), "/category/fonts#field-font-size");
+        assert_eq!(
+            option.docs_url(),
+            "https://ghostty.org/docs/config/reference#font-size"
+        );
+    }
+
+    // --- ConfigSchema ---
+
+    #[test]
+    fn test_schema_find_option() {
+        let schema = ConfigSchema::new(vec![
+            ConfigOption {
+                key: "font-size".to_string(),
+                default_value: "13".to_string(),
+                documentation: "Font size".to_string(),
+                value_type: ConfigValueType::Float,
+                category: Category::Fonts,
+                is_repeatable: false,
+                conditions: Default::default(),
+            },
+            ConfigOption {
+                key: "theme".to_string(),
+                default_value: "".to_string(),
+                documentation: "Theme".to_string(),
+                value_type: ConfigValueType::Text,
+                category: Category::Appearance,
+                is_repeatable: false,
+                conditions: Default::default(),
+            },
+        ]);
+        assert!(schema.find_option("font-size").is_some());
+        assert!(schema.find_option("theme").is_some());
+        assert!(schema.find_option("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_schema_options_for_category() {
+        let schema = ConfigSchema::new(vec![
+            ConfigOption {
+                key: "font-size".to_string(),
+                default_value: "13".to_string(),
+                documentation: "".to_string(),
+                value_type: ConfigValueType::Float,
+                category: Category::Fonts,
+                is_repeatable: false,
+                conditions: Default::default(),
+            },
+            ConfigOption {
+                key: "font-thicken".to_string(),
+                default_value: "false".to_string(),
+                documentation: "".to_string(),
+                value_type: ConfigValueType::Boolean,
+                category: Category::Fonts,
+                is_repeatable: false,
+                conditions: Default::default(),
+            },
+            ConfigOption {
+                key: "theme".to_string(),
+                default_value: "".to_string(),
+                documentation: "".to_string(),
+                value_type: ConfigValueType::Text,
+                category: Category::Appearance,
+                is_repeatable: false,
+                conditions: Default::default(),
+            },
+        ]);
+        let font_opts = schema.options_for_category(&Category::Fonts);
+        assert_eq!(font_opts.len(), 2);
+        let appearance_opts = schema.options_for_category(&Category::Appearance);
+        assert_eq!(appearance_opts.len(), 1);
+        let cursor_opts = schema.options_for_category(&Category::Cursor);
+        assert_eq!(cursor_opts.len(), 0);
+    }
+
+    // --- UserConfig ---
+
+    #[test]
+    fn test_user_config_new_is_empty() {
+        let config = UserConfig::new(PathBuf::from("/tmp/test"));
+        assert!(config.entries.is_empty());
+        assert_eq!(config.get("anything"), None);
+    }
+
+    #[test]
+    fn test_user_config_set_and_get() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/test"));
+        config.set("font-size", "14");
+        assert_eq!(config.get("font-size"), Some("14"));
+    }
+
+    #[test]
+    fn test_user_config_set_overwrites() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/test"));
+        config.set("font-size", "14");
+        config.set("font-size", "16");
+        assert_eq!(config.get("font-size"), Some("16"));
+        // Should still have only 1 entry
+        assert_eq!(config.all_set_values().len(), 1);
+    }
+
+    #[test]
+    fn test_user_config_remove() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/test"));
+        config.set("font-size", "14");
+        config.set("theme", "Dracula");
+        config.remove("font-size");
+        assert_eq!(config.get("font-size"), None);
+        assert_eq!(config.get("theme"), Some("Dracula"));
+    }
+
+    #[test]
+    fn test_user_config_get_all() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/test"));
+        config.entries.push(ConfigEntry::key_value(
+            "keybind".to_string(),
+            "ctrl+a=select_all".to_string(),
+        ));
+        config.entries.push(ConfigEntry::key_value(
+            "keybind".to_string(),
+            "ctrl+c=copy".to_string(),
+        ));
+        let all = config.get_all("keybind");
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0], "ctrl+a=select_all");
+        assert_eq!(all[1], "ctrl+c=copy");
+    }
+
+    #[test]
+    fn test_user_config_get_returns_last_occurrence() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/test"));
+        config.entries.push(ConfigEntry::key_value(
+            "keybind".to_string(),
+            "first".to_string(),
+        ));
+        config.entries.push(ConfigEntry::key_value(
+            "keybind".to_string(),
+            "second".to_string(),
+        ));
+        assert_eq!(config.get("keybind"), Some("second"));
+    }
+
+    #[test]
+    fn test_user_config_all_set_values() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/test"));
+        config
+            .entries
+            .push(ConfigEntry::Comment("# comment".to_string()));
+        config.set("font-size", "14");
+        config.entries.push(ConfigEntry::BlankLine);
+        config.set("theme", "Dracula");
+
+        let values = config.all_set_values();
+        assert_eq!(values.len(), 2);
+        assert!(values.contains(&("font-size", "14")));
+        assert!(values.contains(&("theme", "Dracula")));
+    }
+
+    #[test]
+    fn test_user_config_set_all_replaces_in_place() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/test"));
+        config.set("theme", "Dracula");
+        config.entries.push(ConfigEntry::key_value(
+            "keybind".to_string(),
+            "ctrl+a=select_all".to_string(),
+        ));
+        config.set("font-size", "14");
+        config.entries.push(ConfigEntry::key_value(
+            "keybind".to_string(),
+            "ctrl+c=copy".to_string(),
+        ));
+
+        config.set_all("keybind", &["ctrl+v=paste".to_string()]);
+        assert_eq!(config.get_all("keybind"), vec!["ctrl+v=paste"]);
+        assert!(
+            matches!(&config.entries[1], ConfigEntry::KeyValue { key, .. } if key == "keybind")
+        );
+
+        config.set_all("palette", &["0=#000000".to_string()]);
+        assert_eq!(config.get("palette"), Some("0=#000000"));
+
+        config.set_all("keybind", &[]);
+        assert!(config.get_all("keybind").is_empty());
+    }
+
+    #[test]
+    fn test_user_config_edit_entries_by_index() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/test"));
+        config.push("font-feature", "-calt");
+        config.set("font-size", "14");
+        config.push("font-feature", "-liga");
+
+        assert!(config.set_at("font-feature", 1, "+ss01"));
+        assert!(!config.set_at("font-feature", 2, "-dlig"));
+        assert_eq!(config.get_all("font-feature"), vec!["-calt", "+ss01"]);
+
+        assert!(config.insert_after("font-feature", 0, "-dlig"));
+        assert_eq!(
+            config.get_all("font-feature"),
+            vec!["-calt", "-dlig", "+ss01"]
+        );
+        assert!(
+            matches!(&config.entries[1], ConfigEntry::KeyValue { key, .. } if key == "font-feature")
+        );
+
+        assert!(config.remove_at("font-feature", 0));
+        assert!(!config.remove_at("font-feature", 5));
+        assert_eq!(config.get_all("font-feature"), vec!["-dlig", "+ss01"]);
+        assert_eq!(config.get("font-size"), Some("14"));
+    }
+
+    #[test]
+    fn test_user_config_remove_preserves_comments() {
+        let mut config = UserConfig::new(PathBuf::from("/tmp/test"));
+        config
+            .entries
+            .push(ConfigEntry::Comment("# header".to_string()));
+        config.set("font-size", "14");
+        config.entries.push(ConfigEntry::BlankLine);
+        config.set("theme", "Dracula");
+
+        config.remove("font-size");
+        // Comment and blank line should still be there
+        assert!(matches!(&config.entries[0], ConfigEntry::Comment(_)));
+        assert_eq!(config.get("font-size"), None);
+        assert_eq!(config.get("theme"), Some("Dracula"));
+    }
+}
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+
+use super::keybind::{parse_trigger, Chord};
+use super::keybind_conflicts::{ConflictSource, KeybindConflict};
+
+/// An operating system Ghostty runs on. Its own shortcuts can swallow a
+/// keybinding, and some options only work on one of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    MacOS,
+    Linux,
+}
+
+impl Platform {
+    pub const ALL: [Platform; 2] = [Platform::MacOS, Platform::Linux];
+
+    /// The platform this binary was built for.
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Platform::MacOS
+        } else {
+            Platform::Linux
+        }
+    }
+
+    /// As in `conditions.toml` and JSON.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Platform::MacOS => "macos",
+            Platform::Linux => "linux",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Platform::MacOS => "macOS",
+            Platform::Linux => "Linux desktop",
+        }
+    }
+
+    /// Chords the OS or desktop handles before Ghostty sees them.
+    ///
+    /// Only defaults that are on out of the box are listed; users can remap most of them.
+    fn shortcuts(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Platform::MacOS => MACOS_SHORTCUTS,
+            Platform::Linux => LINUX_SHORTCUTS,
+        }
+    }
+}
+
+const MACOS_SHORTCUTS: &[(&str, &str)] = &[
+    ("super+space", "Spotlight"),
+    ("super+alt+space", "Finder search"),
+    ("ctrl+space", "Select previous input source"),
+    ("ctrl+super+space", "Character viewer"),
+    ("super+tab", "App switcher"),
+    ("super+shift+tab", "App switcher (reverse)"),
+    ("super+alt+escape", "Force Quit"),
+    ("ctrl+super+q", "Lock screen"),
+    ("super+alt+d", "Show or hide the Dock"),
+    ("super+shift+3", "Screenshot"),
+    ("super+shift+4", "Screenshot of selection"),
+    ("super+shift+5", "Screenshot toolbar"),
+    ("ctrl+arrow_up", "Mission Control"),
+    ("ctrl+arrow_down", "Application windows"),
+    ("ctrl+arrow_left", "Move one space left"),
+    ("ctrl+arrow_right", "Move one space right"),
+];
+
+const LINUX_SHORTCUTS: &[(&str, &str)] = &[
+    ("alt+tab", "Window switcher"),
+    ("alt+shift+tab", "Window switcher (reverse)"),
+    ("super+tab", "App switcher"),
+    ("alt+f4", "Close window"),
+    ("super+l", "Lock screen"),
+    ("ctrl+alt+delete", "Log out"),
+    ("ctrl+alt+t", "Open terminal"),
+    ("ctrl+alt+arrow_left", "Previous workspace"),
+    ("ctrl+alt+arrow_right", "Next workspace"),
+    ("super+d", "Show desktop"),
+    ("print_screen", "Screenshot"),
+    ("ctrl+alt+f1", "Switch virtual terminal"),
+    ("ctrl+alt+f2", "Switch virtual terminal"),
+    ("ctrl+alt+f3", "Switch virtual terminal"),
+];
+
+/// Older key names Ghostty still accepts, mapped to the names used in the tables.
+fn canonical_key(key: &str) -> &str {
+    match key {
+        "up" => "arrow_up",
+        "down" => "arrow_down",
+        "left" => "arrow_left",
+        "right" => "arrow_right",
+        other => other,
+    }
+}
+
+fn same_chord(a: &Chord, b: &Chord) -> bool {
+    a.modifiers == b.modifiers && canonical_key(&a.key) == canonical_key(&b.key)
+}
+
+/// OS shortcuts on `platform` that `trigger` collides with.
+///
+/// Only the first chord matters: once the OS takes it, a sequence can never
+/// continue. Triggers that don't parse have no conflicts here.
+pub fn os_conflicts(trigger: &str, platform: Platform) -> Vec<KeybindConflict> {
+    let Some(first) = parse_trigger(trigger)
+        .ok()
+        .and_then(|t| t.sequence.into_iter().next())
+    else {
+        return Vec::new();
+    };
+
+    platform
+        .shortcuts()
+        .iter()
+        .filter(|(shortcut, _)| {
+            parse_trigger(shortcut)
+                .ok()
+                .and_then(|t| t.sequence.into_iter().next())
+                .is_some_and(|chord| same_chord(&chord, &first))
+        })
+        .map(|(shortcut, description)| KeybindConflict {
+            trigger: shortcut.to_string(),
+            action: description.to_string(),
+            source: ConflictSource::System,
+        })
+        .collect()
+}
+
+/// Custom `keybind` values (`trigger=action`) that collide with an OS shortcut,
+/// paired with what the OS uses the chord for.
+pub fn os_blocked_keybinds<'a>(keybinds: &[&'a str], platform: Platform) -> Vec<(&'a str, String)> {
+    keybinds
+        .iter()
+        .filter_map(|value| {
+            let (trigger, _) = value.split_once('=')?;
+            let conflict = os_conflicts(trigger, platform).into_iter().next()?;
+            Some((*value, conflict.action))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_parse() {
+        for platform in [Platform::MacOS, Platform::Linux] {
+            for (shortcut, _) in platform.shortcuts() {
+                assert!(parse_trigger(shortcut).is_ok(), "{}", shortcut);
+            }
+        }
+    }
+
+    #[test]
+    fn test_os_conflicts() {
+        let conflicts = os_conflicts("cmd+space", Platform::MacOS);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].action, "Spotlight");
+        assert_eq!(conflicts[0].source, ConflictSource::System);
+
+        assert_eq!(os_conflicts("global:ctrl+up", Platform::MacOS).len(), 1);
+        assert_eq!(os_conflicts("alt+tab>n", Platform::Linux).len(), 1);
+        assert!(os_conflicts("cmd+space", Platform::Linux).is_empty());
+        assert!(os_conflicts("ctrl+shift+t", Platform::MacOS).is_empty());
+        assert!(os_conflicts("not a trigger", Platform::MacOS).is_empty());
+    }
+
+    #[test]
+    fn test_os_blocked_keybinds() {
+        let keybinds = ["super+tab=next_tab", "ctrl+t=new_tab", "garbage"];
+        assert_eq!(
+            os_blocked_keybinds(&keybinds, Platform::Linux),
+            vec![("super+tab=next_tab", "App switcher".to_string())]
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use super::model::UserConfig;
+
+/// Ghostty's palette has 256 entries; the first 16 are the ANSI colors themes set.
+pub const PALETTE_SIZE: usize = 256;
+
+/// Split a `palette` value like `4=#81a2be` into its index and color.
+pub fn parse_palette_entry(value: &str) -> Option<(usize, String)> {
+    let (index, color) = value.split_once('=')?;
+    let index: usize = index.trim().parse().ok()?;
+    let color = color.trim();
+    (index < PALETTE_SIZE && !color.is_empty()).then(|| (index, color.to_string()))
+}
+
+/// The explicit color of each of the 16 ANSI slots. Later entries win, as in Ghostty.
+pub fn ansi_palette(values: &[&str]) -> [Option<String>; 16] {
+    let mut slots: [Option<String>; 16] = Default::default();
+    for (index, color) in values.iter().filter_map(|v| parse_palette_entry(v)) {
+        if let Some(slot) = slots.get_mut(index) {
+            *slot = Some(color);
+        }
+    }
+    slots
+}
+
+/// The explicit color of palette slot `index`, if the config sets one.
+pub fn palette_entry(config: &UserConfig, index: usize) -> Option<String> {
+    config
+        .get_all("palette")
+        .into_iter()
+        .rev()
+        .filter_map(parse_palette_entry)
+        .find(|(i, _)| *i == index)
+        .map(|(_, color)| color)
+}
+
+/// Set slot `index` to `color`, leaving every other palette entry where it is.
+///
+/// Replaces the slot's existing entry in place, or appends one.
+pub fn set_palette_entry(config: &mut UserConfig, index: usize, color: &str) {
+    let entry = format!("{}={}", index, color);
+    let mut replaced = false;
+    let mut values: Vec<String> = Vec::new();
+    for value in config.get_all("palette") {
+        if parse_palette_entry(value).is_some_and(|(i, _)| i == index) {
+            if !replaced {
+                values.push(entry.clone());
+                replaced = true;
+            }
+        } else {
+            values.push(value.to_string());
+        }
+    }
+    if !replaced {
+        values.push(entry);
+    }
+    config.set_all("palette", &values);
+}
+
+/// Drop slot `index` so the theme's (or Ghostty's default) color applies again.
+///
+/// Returns whether the config set that slot.
+pub fn remove_palette_entry(config: &mut UserConfig, index: usize) -> bool {
+    let all = config.get_all("palette");
+    let values: Vec<String> = all
+        .iter()
+        .filter(|v| parse_palette_entry(v).is_none_or(|(i, _)| i != index))
+        .map(|v| v.to_string())
+        .collect();
+    if values.len() == all.len() {
+        return false;
+    }
+    config.set_all("palette", &values);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file_io::parse_config;
+    use std::path::Path;
+
+    fn config(text: &str) -> UserConfig {
+        parse_config(text, Path::new("/tmp/config"))
+    }
+
+    #[test]
+    fn test_parse_palette_entry() {
+        assert_eq!(
+            parse_palette_entry(" 4 = #81a2be"),
+            Some((4, "#81a2be".to_string()))
+        );
+        assert_eq!(parse_palette_entry("255=ffffff").map(|e| e.0), Some(255));
+        assert_eq!(parse_palette_entry("256=#ffffff"), None);
+        assert_eq!(parse_palette_entry("#ffffff"), None);
+        assert_eq!(parse_palette_entry("3="), None);
+    }
+
+    #[test]
+    fn test_ansi_palette() {
+        let slots = ansi_palette(&["1=#ff0000", "200=#123456", "1=#aa0000"]);
+        assert_eq!(slots[1].as_deref(), Some("#aa0000"));
+        assert_eq!(slots.iter().flatten().count(), 1);
+    }
+
+    #[test]
+    fn test_set_palette_entry_keeps_other_slots() {
+        let mut c = config("palette = 0=#000000\nfont-size = 13\npalette = 1=#ff0000\n");
+        set_palette_entry(&mut c, 1, "#00ff00");
+        set_palette_entry(&mut c, 9, "#ff5555");
+        assert_eq!(
+            c.get_all("palette"),
+            vec!["0=#000000", "1=#00ff00", "9=#ff5555"]
+        );
+        assert_eq!(palette_entry(&c, 1).as_deref(), Some("#00ff00"));
+        assert_eq!(palette_entry(&c, 2), None);
+    }
+
+    #[test]
+    fn test_remove_palette_entry() {
+        let mut c = config("palette = 0=#000000\npalette = 1=#ff0000\n");
+        assert!(remove_palette_entry(&mut c, 0));
+        assert!(!remove_palette_entry(&mut c, 5));
+        assert_eq!(c.get_all("palette"), vec!["1=#ff0000"]);
+    }
+}
@@ -0,0 +1,462 @@
+//! Generating a theme's colors from one seed color, or from the dominant colors
+//! of an image such as a wallpaper: a background, a foreground, and the 16 ANSI
+//! colors, each readable on that background.
+
+use serde::Serialize;
+
+use super::contrast::{contrast_ratio, AAA_NORMAL, AA_LARGE};
+
+type Rgb = (u8, u8, u8);
+
+/// Hues of red, green, yellow, blue, magenta, and cyan: ANSI slots 1 to 6.
+const ANSI_HUES: [f64; 6] = [0.0, 120.0, 50.0, 215.0, 300.0, 185.0];
+/// How close, in degrees, a color's hue must be to an ANSI hue to be used for it.
+const HUE_MATCH: f64 = 30.0;
+/// How far the accents without a color of their own are pulled towards the
+/// seed's hue, so they look like they belong together: a share of the way,
+/// up to a number of degrees, so red stays red.
+const TINT: f64 = 0.1;
+const MAX_TINT: f64 = 10.0;
+/// Colors duller than this don't count as any hue.
+const MIN_CHROMA: f64 = 0.15;
+/// Rounds of k-means that refine the colors the median cut finds.
+const REFINE_ROUNDS: usize = 4;
+
+/// Whether the palette goes on a dark or a light background.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Appearance {
+    #[default]
+    Dark,
+    Light,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GeneratedPalette {
+    pub background: String,
+    pub foreground: String,
+    /// The 16 ANSI colors, as `#rrggbb`.
+    pub palette: Vec<String>,
+}
+
+impl GeneratedPalette {
+    /// The colors as `palette` values, e.g. `1=#cc6666`.
+    pub fn palette_entries(&self) -> Vec<String> {
+        self.palette
+            .iter()
+            .enumerate()
+            .map(|(index, color)| format!("{}={}", index, color))
+            .collect()
+    }
+}
+
+/// What a palette is built from: the background's hue and saturation, and the
+/// hue and saturation of each accent.
+struct Scheme {
+    background: (f64, f64),
+    accents: [(f64, f64); 6],
+    appearance: Appearance,
+}
+
+/// A palette around `seed`: the ANSI color nearest its hue becomes the seed's
+/// hue, the background is a dark (or light) shade of it, and the other accents
+/// lean slightly towards it.
+pub fn palette_from_seed(seed: Rgb, appearance: Appearance) -> GeneratedPalette {
+    let (hue, saturation, _) = to_hsl(seed);
+    let chromatic = saturation >= MIN_CHROMA;
+    let accent_saturation = if chromatic {
+        saturation.clamp(0.4, 0.8)
+    } else {
+        0.55
+    };
+    let nearest = closest_ansi_hue(hue);
+    let accents = std::array::from_fn(|slot| {
+        let target = ANSI_HUES[slot];
+        let hue = if !chromatic {
+            target
+        } else if slot == nearest && hue_distance(hue, target) <= HUE_MATCH {
+            hue
+        } else {
+            tinted(target, hue)
+        };
+        (hue, accent_saturation)
+    });
+    build(Scheme {
+        background: (hue, if chromatic { saturation * 0.35 } else { 0.0 }),
+        accents,
+        appearance,
+    })
+}
+
+/// A palette from an image's `colors`, most prominent first (see
+/// `dominant_colors`). The most prominent color sets the background and, unless
+/// `appearance` is given, whether it is dark; each ANSI color takes the most
+/// prominent image color of its hue, or leans towards the image's main accent.
+pub fn palette_from_colors(
+    colors: &[Rgb],
+    appearance: Option<Appearance>,
+) -> Option<GeneratedPalette> {
+    let hsl: Vec<(f64, f64, f64)> = colors.iter().map(|&c| to_hsl(c)).collect();
+    let &(background_hue, background_saturation, background_lightness) = hsl.first()?;
+    let appearance = appearance.unwrap_or(if background_lightness < 0.5 {
+        Appearance::Dark
+    } else {
+        Appearance::Light
+    });
+    let seed = hsl
+        .iter()
+        .find(|(_, s, _)| *s >= 0.3)
+        .or_else(|| hsl.iter().max_by(|a, b| a.1.total_cmp(&b.1)))
+        .filter(|(_, s, _)| *s >= MIN_CHROMA)
+        .map(|&(h, s, _)| (h, s.clamp(0.4, 0.8)));
+
+    let accents = std::array::from_fn(|slot| {
+        let target = ANSI_HUES[slot];
+        let matched = hsl.iter().find(|(h, s, _)| {
+            *s >= MIN_CHROMA
+                && closest_ansi_hue(*h) == slot
+                && hue_distance(*h, target) <= HUE_MATCH
+        });
+        match (matched, seed) {
+            (Some(&(h, s, _)), _) => (h, s.clamp(0.4, 0.85)),
+            (None, Some((h, s))) => (tinted(target, h), s),
+            (None, None) => (target, 0.55),
+        }
+    });
+    Some(build(Scheme {
+        background: (background_hue, background_saturation * 0.5),
+        accents,
+        appearance,
+    }))
+}
+
+fn build(scheme: Scheme) -> GeneratedPalette {
+    let dark = scheme.appearance == Appearance::Dark;
+    let (hue, saturation) = scheme.background;
+    let saturation = saturation.min(0.25);
+    let background = from_hsl(hue, saturation, if dark { 0.11 } else { 0.96 });
+    let grey = |lightness: f64| from_hsl(hue, (saturation * 0.6).min(0.12), lightness);
+    let foreground = readable(grey(if dark { 0.86 } else { 0.18 }), background, AAA_NORMAL);
+
+    // Black and white are greys of the background's hue. The one that matches
+    // the background (black on dark, white on light) needn't stand out from it.
+    let (black, white, bright_black, bright_white) = if dark {
+        (grey(0.2), grey(0.78), grey(0.5), grey(0.94))
+    } else {
+        (grey(0.2), grey(0.82), grey(0.45), grey(0.97))
+    };
+    let (black, white, bright_white) = if dark {
+        (
+            black,
+            readable(white, background, AA_LARGE),
+            readable(bright_white, background, AA_LARGE),
+        )
+    } else {
+        (readable(black, background, AA_LARGE), white, bright_white)
+    };
+    let bright_black = readable(bright_black, background, AA_LARGE);
+
+    let (normal, bright) = if dark { (0.62, 0.74) } else { (0.38, 0.46) };
+    let accent = |(hue, saturation): (f64, f64), lightness: f64| {
+        readable(from_hsl(hue, saturation, lightness), background, AA_LARGE)
+    };
+    let mut palette = vec![black];
+    palette.extend(scheme.accents.iter().map(|&a| accent(a, normal)));
+    palette.extend([white, bright_black]);
+    palette.extend(
+        scheme
+            .accents
+            .iter()
+            .map(|&(h, s)| accent((h, (s + 0.1).min(0.9)), bright)),
+    );
+    palette.push(bright_white);
+
+    GeneratedPalette {
+        background: to_hex(background),
+        foreground: to_hex(foreground),
+        palette: palette.into_iter().map(to_hex).collect(),
+    }
+}
+
+/// `color`, lightened on a dark background or darkened on a light one until its
+/// contrast with `background` reaches `min_ratio`, or as far as it goes.
+fn readable(color: Rgb, background: Rgb, min_ratio: f64) -> Rgb {
+    let (hue, saturation, mut lightness) = to_hsl(color);
+    let step = if to_hsl(background).2 < 0.5 {
+        0.02
+    } else {
+        -0.02
+    };
+    let mut color = color;
+    while contrast_ratio(color, background) < min_ratio && (0.0..=1.0).contains(&(lightness + step))
+    {
+        lightness += step;
+        color = from_hsl(hue, saturation, lightness);
+    }
+    color
+}
+
+/// The most prominent colors of `pixels`, most prominent first, by median cut:
+/// the group of pixels with the widest spread in one channel is split at its
+/// median until there are `count` groups, whose averages are then refined.
+pub fn dominant_colors(pixels: &[Rgb], count: usize) -> Vec<Rgb> {
+    if pixels.is_empty() || count == 0 {
+        return Vec::new();
+    }
+    let mut groups: Vec<Vec<Rgb>> = vec![pixels.to_vec()];
+    while groups.len() < count {
+        let Some((index, channel, spread)) = groups
+            .iter()
+            .enumerate()
+            .map(|(i, group)| {
+                let (channel, spread) = widest_channel(group);
+                (i, channel, spread)
+            })
+            .max_by_key(|&(_, _, spread)| spread)
+        else {
+            break;
+        };
+        if spread == 0 {
+            break;
+        }
+        let mut group = groups.swap_remove(index);
+        group.sort_unstable_by_key(|&pixel| channel_value(pixel, channel));
+        let upper = group.split_off(group.len() / 2);
+        groups.extend([group, upper]);
+    }
+
+    // The split is rough where a group straddles two colors; a few rounds of
+    // k-means settle each pixel with its nearest color.
+    let mut colors: Vec<(Rgb, usize)> = groups.iter().map(|g| (average(g), g.len())).collect();
+    for _ in 0..REFINE_ROUNDS {
+        let mut sums = vec![([0u64; 3], 0usize); colors.len()];
+        for &pixel in pixels {
+            let nearest = (0..colors.len())
+                .min_by_key(|&i| distance(pixel, colors[i].0))
+                .unwrap_or(0);
+            let (sum, n) = &mut sums[nearest];
+            for (channel, total) in sum.iter_mut().enumerate() {
+                *total += channel_value(pixel, channel) as u64;
+            }
+            *n += 1;
+        }
+        colors = sums
+            .into_iter()
+            .filter(|&(_, n)| n > 0)
+            .map(|(sum, n)| {
+                let mean = |c: usize| (sum[c] / n as u64) as u8;
+                ((mean(0), mean(1), mean(2)), n)
+            })
+            .collect();
+    }
+    colors.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+    colors.into_iter().map(|(color, _)| color).collect()
+}
+
+/// Squared distance between two colors.
+fn distance(a: Rgb, b: Rgb) -> u32 {
+    (0..3)
+        .map(|c| (channel_value(a, c) as i32 - channel_value(b, c) as i32).pow(2) as u32)
+        .sum()
+}
+
+/// The channel (0 red, 1 green, 2 blue) whose values spread the widest, and by how much.
+fn widest_channel(pixels: &[Rgb]) -> (usize, u8) {
+    (0..3)
+        .map(|channel| {
+            let values = pixels.iter().map(|&p| channel_value(p, channel));
+            let spread = values.clone().max().unwrap_or(0) - values.min().unwrap_or(0);
+            (channel, spread)
+        })
+        .max_by_key(|&(_, spread)| spread)
+        .unwrap_or((0, 0))
+}
+
+fn channel_value((r, g, b): Rgb, channel: usize) -> u8 {
+    match channel {
+        0 => r,
+        1 => g,
+        _ => b,
+    }
+}
+
+fn average(pixels: &[Rgb]) -> Rgb {
+    let n = pixels.len().max(1) as u64;
+    let sum = |channel| {
+        pixels
+            .iter()
+            .map(|&p| channel_value(p, channel) as u64)
+            .sum::<u64>()
+    };
+    ((sum(0) / n) as u8, (sum(1) / n) as u8, (sum(2) / n) as u8)
+}
+
+/// ANSI hue `target` pulled towards `seed`'s hue.
+fn tinted(target: f64, seed: f64) -> f64 {
+    target + (hue_offset(target, seed) * TINT).clamp(-MAX_TINT, MAX_TINT)
+}
+
+/// The ANSI slot (0 red to 5 cyan) whose hue is closest to `hue`.
+fn closest_ansi_hue(hue: f64) -> usize {
+    (0..ANSI_HUES.len())
+        .min_by(|&a, &b| {
+            hue_distance(hue, ANSI_HUES[a]).total_cmp(&hue_distance(hue, ANSI_HUES[b]))
+        })
+        .unwrap_or(0)
+}
+
+/// Degrees between two hues around the color wheel, from 0 to 180.
+fn hue_distance(a: f64, b: f64) -> f64 {
+    hue_offset(a, b).abs()
+}
+
+/// The signed turn, in degrees, from hue `from` to hue `to` the short way round.
+fn hue_offset(from: f64, to: f64) -> f64 {
+    (to - from + 540.0).rem_euclid(360.0) - 180.0
+}
+
+/// Hue in degrees, saturation, and lightness, each from 0 to 1 but the hue.
+fn to_hsl((r, g, b): Rgb) -> (f64, f64, f64) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness);
+    }
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    (hue * 60.0, saturation, lightness)
+}
+
+fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Rgb {
+    let (saturation, lightness) = (saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0));
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |c: f64| ((c + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+fn to_hex((r, g, b): Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::custom_theme::hex_to_rgb;
+
+    fn rgb(hex: &str) -> Rgb {
+        hex_to_rgb(hex).unwrap()
+    }
+
+    /// Every color but the background's own shade is readable on the background.
+    fn assert_readable(palette: &GeneratedPalette, background_shades: &[usize]) {
+        let background = rgb(&palette.background);
+        assert!(contrast_ratio(rgb(&palette.foreground), background) >= AAA_NORMAL);
+        for (index, color) in palette.palette.iter().enumerate() {
+            if !background_shades.contains(&index) {
+                assert!(
+                    contrast_ratio(rgb(color), background) >= AA_LARGE,
+                    "{} {} on {}",
+                    index,
+                    color,
+                    palette.background
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_hsl_round_trip() {
+        for hex in ["#ff0000", "#81a2be", "#1d1f21", "#ffffff", "#7f7f7f"] {
+            let (h, s, l) = to_hsl(rgb(hex));
+            assert_eq!(to_hex(from_hsl(h, s, l)), hex);
+        }
+        assert_eq!(to_hsl(rgb("#00ff00")).0, 120.0);
+        assert_eq!(hue_offset(350.0, 10.0), 20.0);
+        assert_eq!(hue_distance(10.0, 350.0), 20.0);
+    }
+
+    #[test]
+    fn test_palette_from_seed() {
+        let palette = palette_from_seed(rgb("#2e7de9"), Appearance::Dark);
+        assert_eq!(palette.palette.len(), 16);
+        assert_readable(&palette, &[0]);
+        // Blue takes the seed's hue; the background is a dark shade of it.
+        let (blue, _, _) = to_hsl(rgb(&palette.palette[4]));
+        assert!(hue_distance(blue, to_hsl(rgb("#2e7de9")).0) < 2.0);
+        let (hue, _, lightness) = to_hsl(rgb(&palette.background));
+        assert!(lightness < 0.15 && hue_distance(hue, blue) < 5.0);
+        // Red stays red.
+        assert!(hue_distance(to_hsl(rgb(&palette.palette[1])).0, 0.0) < HUE_MATCH);
+
+        let light = palette_from_seed(rgb("#2e7de9"), Appearance::Light);
+        assert!(to_hsl(rgb(&light.background)).2 > 0.9);
+        assert_readable(&light, &[7, 15]);
+
+        // A grey seed gives a neutral background and standard hues.
+        let grey = palette_from_seed(rgb("#808080"), Appearance::Dark);
+        assert_eq!(to_hsl(rgb(&grey.background)).1, 0.0);
+        assert_readable(&grey, &[0]);
+    }
+
+    #[test]
+    fn test_palette_entries() {
+        let palette = palette_from_seed(rgb("#cc6666"), Appearance::Dark);
+        let entries = palette.palette_entries();
+        assert_eq!(entries.len(), 16);
+        assert_eq!(entries[3], format!("3={}", palette.palette[3]));
+    }
+
+    #[test]
+    fn test_dominant_colors() {
+        let mut pixels = vec![(10, 20, 40); 60];
+        pixels.extend(vec![(200, 40, 40); 30]);
+        pixels.extend(vec![(40, 200, 60); 10]);
+        assert_eq!(
+            dominant_colors(&pixels, 3),
+            vec![(10, 20, 40), (200, 40, 40), (40, 200, 60)]
+        );
+        // No more colors than there are.
+        assert_eq!(dominant_colors(&pixels[..60], 4), vec![(10, 20, 40)]);
+        assert!(dominant_colors(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_palette_from_colors() {
+        // A dark blue wallpaper with an orange-red and a green detail.
+        let colors = [rgb("#0b1a33"), rgb("#e0522a"), rgb("#3fae5a")];
+        let palette = palette_from_colors(&colors, None).unwrap();
+        assert!(to_hsl(rgb(&palette.background)).2 < 0.15);
+        assert_readable(&palette, &[0]);
+        let (red, _, _) = to_hsl(rgb(&palette.palette[1]));
+        assert!(hue_distance(red, to_hsl(colors[1]).0) < 2.0);
+        let (green, _, _) = to_hsl(rgb(&palette.palette[2]));
+        assert!(hue_distance(green, to_hsl(colors[2]).0) < 2.0);
+
+        let light = palette_from_colors(&[rgb("#f4efe6"), rgb("#e0522a")], None).unwrap();
+        assert!(to_hsl(rgb(&light.background)).2 > 0.9);
+        let dark = palette_from_colors(&[rgb("#f4efe6")], Some(Appearance::Dark)).unwrap();
+        assert!(to_hsl(rgb(&dark.background)).2 < 0.15);
+
+        assert_eq!(palette_from_colors(&[], None), None);
+    }
+}
@@ -1,7 +1,8 @@
 use super::categorize::categorize_key;
+use super::conditions::conditions_of;
 use super::model::{ConfigOption, ConfigSchema};
 use super::type_inference::{infer_type, is_repeatable};
-use crate::error::AppError;
+use crate::Error;
 
 /// Parse the output of `ghostty +show-config --default --docs` into a ConfigSchema.
 ///
@@ -14,7 +15,7 @@ use crate::error::AppError;
 ///
 /// Blank lines separate blocks. Documentation lines start with `# `.
 /// The key = value line follows its documentation block.
-pub fn parse_show_config(output: &str) -> Result<ConfigSchema, AppError> {
+pub fn parse_show_config(output: &str) -> Result<ConfigSchema, Error> {
     let mut options = Vec::new();
     let mut doc_lines: Vec<String> = Vec::new();
     let mut seen_keys = std::collections::HashSet::new();
@@ -53,6 +54,7 @@ pub fn parse_show_config(output: &str) -> Result<ConfigSchema, AppError> {
                 let value_type = infer_type(&key, &value, &documentation);
                 let category = categorize_key(&key);
                 let repeatable = is_repeatable(&key);
+                let conditions = conditions_of(&key, &documentation);
 
                 options.push(ConfigOption {
                     key: key.clone(),
@@ -61,6 +63,7 @@ pub fn parse_show_config(output: &str) -> Result<ConfigSchema, AppError> {
                     value_type,
                     category,
                     is_repeatable: repeatable,
+                    conditions,
                 });
                 seen_keys.insert(key);
             }
@@ -69,7 +72,7 @@ pub fn parse_show_config(output: &str) -> Result<ConfigSchema, AppError> {
         }
     }
 
-    Ok(ConfigSchema { options })
+    Ok(ConfigSchema::new(options))
 }
 
 #[cfg(test)]
@@ -86,19 +89,19 @@ font-size = 13
 font-thicken = false
 "#;
         let schema = parse_show_config(input).unwrap();
-        assert_eq!(schema.options.len(), 2);
-        assert_eq!(schema.options[0].key, "font-size");
-        assert_eq!(schema.options[0].default_value, "13");
-        assert_eq!(schema.options[1].key, "font-thicken");
-        assert_eq!(schema.options[1].default_value, "false");
+        assert_eq!(schema.options().len(), 2);
+        assert_eq!(schema.options()[0].key, "font-size");
+        assert_eq!(schema.options()[0].default_value, "13");
+        assert_eq!(schema.options()[1].key, "font-thicken");
+        assert_eq!(schema.options()[1].default_value, "false");
     }
 
     #[test]
     fn test_parse_empty_default() {
         let input = "# The font family.\nfont-family = \n";
         let schema = parse_show_config(input).unwrap();
-        assert_eq!(schema.options[0].key, "font-family");
-        assert_eq!(schema.options[0].default_value, "");
+        assert_eq!(schema.options()[0].key, "font-family");
+        assert_eq!(schema.options()[0].default_value, "");
     }
 
     #[test]
@@ -111,17 +114,17 @@ font-thicken = false
 some-key = value
 "#;
         let schema = parse_show_config(input).unwrap();
-        assert_eq!(schema.options.len(), 1);
-        assert!(schema.options[0].documentation.contains("Line one."));
-        assert!(schema.options[0].documentation.contains("Line three."));
+        assert_eq!(schema.options().len(), 1);
+        assert!(schema.options()[0].documentation.contains("Line one."));
+        assert!(schema.options()[0].documentation.contains("Line three."));
     }
 
     #[test]
     fn test_parse_no_docs() {
         let input = "bare-key = 42\n";
         let schema = parse_show_config(input).unwrap();
-        assert_eq!(schema.options[0].key, "bare-key");
-        assert_eq!(schema.options[0].documentation, "");
+        assert_eq!(schema.options()[0].key, "bare-key");
+        assert_eq!(schema.options()[0].documentation, "");
     }
 
     #[test]
@@ -129,7 +132,7 @@ some-key = value
         let input = "# Doc.\nfont-thicken = false\n";
         let schema = parse_show_config(input).unwrap();
         assert!(matches!(
-            schema.options[0].value_type,
+            schema.options()[0].value_type,
             ConfigValueType::Boolean
         ));
     }
@@ -139,10 +142,10 @@ some-key = value
         let input = "# Doc.\nkeybind = \n";
         let schema = parse_show_config(input).unwrap();
         assert!(matches!(
-            schema.options[0].value_type,
+            schema.options()[0].value_type,
             ConfigValueType::Keybind
         ));
-        assert!(schema.options[0].is_repeatable);
+        assert!(schema.options()[0].is_repeatable);
     }
 
     #[test]
@@ -150,10 +153,10 @@ some-key = value
         let input = "# Doc.\npalette = \n";
         let schema = parse_show_config(input).unwrap();
         assert!(matches!(
-            schema.options[0].value_type,
+            schema.options()[0].value_type,
             ConfigValueType::Palette
         ));
-        assert!(schema.options[0].is_repeatable);
+        assert!(schema.options()[0].is_repeatable);
     }
 
     #[test]
@@ -200,6 +203,6 @@ font-thicken = false
 keybind =
 "#;
         let schema = parse_show_config(input).unwrap();
-        assert_eq!(schema.options.len(), 7);
+        assert_eq!(schema.options().len(), 7);
     }
 }
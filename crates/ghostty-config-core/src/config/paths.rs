@@ -0,0 +1,170 @@
+//! Values of path options: expanding `~` and environment variables before they
+//! are stored, and checking what they point at.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::file_io::unquote_value;
+use super::includes::resolve_include;
+
+/// Path options that also take a keyword instead of a path.
+const PATH_KEYWORDS: &[(&str, &[&str])] = &[("working-directory", &["home", "inherit"])];
+
+/// Options whose path names a directory rather than a file.
+const DIRECTORY_KEYS: &[&str] = &["working-directory"];
+
+/// Whether `value` is one of the keywords `key` takes instead of a path.
+pub fn is_path_keyword(key: &str, value: &str) -> bool {
+    PATH_KEYWORDS
+        .iter()
+        .find(|(k, _)| *k == key)
+        .is_some_and(|(_, keywords)| keywords.contains(&value))
+}
+
+/// Expand a leading `~` and `$VAR` or `${VAR}` references in a path value.
+///
+/// Ghostty only understands `~/` itself, so variables have to be expanded before
+/// the value is saved. Unset variables are left as written, as is the `?` that
+/// marks a path optional.
+pub fn expand_path(value: &str) -> String {
+    expand_with(value, |name| std::env::var(name).ok())
+}
+
+fn expand_with(value: &str, var: impl Fn(&str) -> Option<String>) -> String {
+    let value = value.trim();
+    let (optional, rest) = match value.strip_prefix('?') {
+        Some(rest) => ("?", rest),
+        None => ("", value),
+    };
+    let rest = unquote_value(rest);
+
+    let mut expanded = String::from(optional);
+    let mut rest: &str = &rest;
+    if rest == "~" || rest.starts_with("~/") || rest.starts_with("~\\") {
+        if let Some(home) = var("HOME").or_else(|| var("USERPROFILE")) {
+            expanded.push_str(&home);
+            rest = &rest[1..];
+        }
+    }
+
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, len) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            },
+            None => {
+                let end = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..end], end)
+            }
+        };
+        match var(name).filter(|_| !name.is_empty()) {
+            Some(value) => expanded.push_str(&value),
+            None => expanded.push_str(&rest[start..start + 1 + len]),
+        }
+        rest = &after[len..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Why the path in `value` won't work for `key`: it doesn't exist, can't be
+/// read, or is a file where a directory is wanted or the other way around.
+///
+/// Keywords and optional paths (`?` prefix) that don't exist pass. Relative
+/// paths are resolved against `config_path` the way Ghostty does.
+pub fn path_warning(key: &str, value: &str, config_path: &Path) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() || is_path_keyword(key, value) {
+        return None;
+    }
+    let path = resolve_include(config_path, value);
+    let shown = path.display();
+    let metadata = match fs::metadata(&path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return (!value.starts_with('?')).then(|| format!("{}: {} doesn't exist", key, shown));
+        }
+        Err(e) => return Some(format!("{}: {} can't be read: {}", key, shown, e)),
+    };
+
+    let readable = if DIRECTORY_KEYS.contains(&key) {
+        if !metadata.is_dir() {
+            return Some(format!("{}: {} is not a directory", key, shown));
+        }
+        fs::read_dir(&path).map(drop)
+    } else {
+        if metadata.is_dir() {
+            return Some(format!("{}: {} is a directory, not a file", key, shown));
+        }
+        fs::File::open(&path).map(drop)
+    };
+    readable
+        .err()
+        .map(|e| format!("{}: {} can't be read: {}", key, shown, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/me".to_string()),
+            "XDG_CONFIG_HOME" => Some("/home/me/.config".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_tilde() {
+        assert_eq!(expand_with("~/bg.png", env), "/home/me/bg.png");
+        assert_eq!(expand_with("~", env), "/home/me");
+        assert_eq!(expand_with("?~/x.glsl", env), "?/home/me/x.glsl");
+        assert_eq!(expand_with("~other/x", env), "~other/x");
+        assert_eq!(expand_with("\"~/a b.png\"", env), "/home/me/a b.png");
+    }
+
+    #[test]
+    fn test_expand_variables() {
+        assert_eq!(
+            expand_with("$XDG_CONFIG_HOME/ghostty/bg.png", env),
+            "/home/me/.config/ghostty/bg.png"
+        );
+        assert_eq!(expand_with("${HOME}/a", env), "/home/me/a");
+        assert_eq!(expand_with("$UNSET/a", env), "$UNSET/a");
+        assert_eq!(expand_with("${UNSET}/a", env), "${UNSET}/a");
+        assert_eq!(expand_with("price$", env), "price$");
+        assert_eq!(expand_with("${HOME", env), "${HOME");
+        assert_eq!(expand_with("/plain/path", env), "/plain/path");
+    }
+
+    #[test]
+    fn test_path_warning() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config");
+        fs::write(dir.path().join("shader.glsl"), "").unwrap();
+
+        assert_eq!(path_warning("custom-shader", "shader.glsl", &config), None);
+        let missing = path_warning("custom-shader", "missing.glsl", &config).unwrap();
+        assert!(missing.contains("doesn't exist"));
+        assert_eq!(
+            path_warning("custom-shader", "?missing.glsl", &config),
+            None
+        );
+        assert!(path_warning("custom-shader", ".", &config)
+            .unwrap()
+            .contains("is a directory"));
+
+        assert_eq!(path_warning("working-directory", "home", &config), None);
+        assert_eq!(path_warning("working-directory", ".", &config), None);
+        assert!(path_warning("working-directory", "shader.glsl", &config)
+            .unwrap()
+            .contains("not a directory"));
+    }
+}
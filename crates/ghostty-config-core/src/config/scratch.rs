@@ -0,0 +1,98 @@
+//! A throwaway copy of the config, unsaved edits included, for trying it out in a
+//! Ghostty window without touching the real files.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::file_io::render_config;
+use super::includes::resolve_include;
+use super::model::UserConfig;
+
+/// The files of a scratch copy of `config` and its `included` files in `dir`, main
+/// config first, as `(path, text)`.
+///
+/// The main config is written as `config` and each included file as `include-N`.
+/// Every `config-file` entry is pointed at the copy of the file it names, or at the
+/// file's absolute path when it isn't loaded, since relative paths would otherwise
+/// be looked up in `dir`.
+pub fn scratch_files(
+    config: &UserConfig,
+    included: &[UserConfig],
+    dir: &Path,
+) -> Vec<(PathBuf, String)> {
+    let copies: HashMap<&Path, PathBuf> = included
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            (
+                c.file_path.as_path(),
+                dir.join(format!("include-{}", i + 1)),
+            )
+        })
+        .collect();
+    std::iter::once((config, dir.join("config")))
+        .chain(
+            included
+                .iter()
+                .map(|c| (c, copies[c.file_path.as_path()].clone())),
+        )
+        .map(|(original, path)| {
+            let mut copy = original.clone();
+            let values: Vec<String> = original
+                .get_all("config-file")
+                .into_iter()
+                .map(|value| {
+                    if value.trim().is_empty() {
+                        return value.to_string();
+                    }
+                    let target = resolve_include(&original.file_path, value);
+                    let target = copies.get(target.as_path()).unwrap_or(&target);
+                    let optional = if value.trim().starts_with('?') {
+                        "?"
+                    } else {
+                        ""
+                    };
+                    format!("{}{}", optional, target.display())
+                })
+                .collect();
+            if !values.is_empty() {
+                copy.set_all("config-file", &values);
+            }
+            copy.file_path = path.clone();
+            (path, render_config(&copy))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file_io::parse_config;
+
+    #[test]
+    fn test_scratch_files() {
+        let config = parse_config(
+            "font-size = 15\nconfig-file = colors.conf\nconfig-file = ?/etc/ghostty/extra\n",
+            Path::new("/home/me/.config/ghostty/config"),
+        );
+        let mut colors = parse_config(
+            "background = #000000\nconfig-file = ?more.conf\n",
+            Path::new("/home/me/.config/ghostty/colors.conf"),
+        );
+        colors.set("background", "#101010");
+
+        let files = scratch_files(&config, &[colors], Path::new("/tmp/scratch/1"));
+        assert_eq!(files.len(), 2);
+        let (main_path, main) = &files[0];
+        assert_eq!(main_path, Path::new("/tmp/scratch/1/config"));
+        assert!(main.contains("font-size = 15"));
+        assert!(main.contains("config-file = /tmp/scratch/1/include-1\n"));
+        assert!(main.contains("config-file = ?/etc/ghostty/extra\n"));
+
+        // The include keeps its unsaved edits and its own includes still resolve.
+        let (include_path, include) = &files[1];
+        assert_eq!(include_path, Path::new("/tmp/scratch/1/include-1"));
+        assert!(include.contains("background = #101010"));
+        assert!(include.contains("config-file = ?/home/me/.config/ghostty/more.conf"));
+    }
+}
@@ -0,0 +1,179 @@
+//! A commented starter config for someone without a config file yet, built from
+//! the answers to the first-run wizard (`/welcome`).
+
+use std::path::PathBuf;
+
+use super::annotate::annotate_new_keys;
+use super::keybind_presets::{find_preset, install_preset};
+use super::model::{ConfigEntry, ConfigSchema, UserConfig};
+
+/// How much room to leave between the window edge and the text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Padding {
+    /// Ghostty's own default; nothing is written.
+    #[default]
+    Default,
+    Compact,
+    Comfortable,
+    Spacious,
+}
+
+impl Padding {
+    pub const ALL: [Padding; 4] = [
+        Padding::Default,
+        Padding::Compact,
+        Padding::Comfortable,
+        Padding::Spacious,
+    ];
+
+    pub fn slug(self) -> &'static str {
+        match self {
+            Padding::Default => "default",
+            Padding::Compact => "compact",
+            Padding::Comfortable => "comfortable",
+            Padding::Spacious => "spacious",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Padding::Default => "Ghostty's default",
+            Padding::Compact => "Compact (4px)",
+            Padding::Comfortable => "Comfortable (12px)",
+            Padding::Spacious => "Spacious (24px)",
+        }
+    }
+
+    pub fn parse(slug: &str) -> Option<Padding> {
+        Padding::ALL.into_iter().find(|p| p.slug() == slug)
+    }
+
+    /// Pixels on each side, or `None` to leave Ghostty's default alone.
+    fn pixels(self) -> Option<u32> {
+        match self {
+            Padding::Default => None,
+            Padding::Compact => Some(4),
+            Padding::Comfortable => Some(12),
+            Padding::Spacious => Some(24),
+        }
+    }
+}
+
+/// The wizard's answers. Empty strings leave an option at its default.
+#[derive(Debug, Clone, Default)]
+pub struct StarterChoices {
+    /// The `theme` value: one theme, or a `light:NAME,dark:NAME` pair.
+    pub theme: String,
+    pub font_family: String,
+    pub font_size: String,
+    pub cursor_style: String,
+    pub padding: Padding,
+    /// A keybinding preset to install, by name; see `keybind_presets`.
+    pub keybind_preset: Option<String>,
+}
+
+impl StarterChoices {
+    /// The options the answers set, in the order they're asked.
+    fn values(&self) -> Vec<(&'static str, String)> {
+        let mut values = vec![
+            ("theme", self.theme.trim().to_string()),
+            ("font-family", self.font_family.trim().to_string()),
+            ("font-size", self.font_size.trim().to_string()),
+            ("cursor-style", self.cursor_style.trim().to_string()),
+        ];
+        if let Some(pixels) = self.padding.pixels() {
+            values.extend([
+                ("window-padding-x", pixels.to_string()),
+                ("window-padding-y", pixels.to_string()),
+                ("window-padding-balance", "true".to_string()),
+            ]);
+        }
+        values.retain(|(_, value)| !value.is_empty());
+        values
+    }
+}
+
+/// A config for `path` with the options `choices` sets, each under its category's
+/// header with the first sentence of its docs, then the keybinding preset if one
+/// was picked. Options `schema` doesn't know (an older Ghostty) are left out.
+pub fn starter_config(
+    choices: &StarterChoices,
+    schema: &ConfigSchema,
+    path: PathBuf,
+) -> UserConfig {
+    let blank = UserConfig::new(path.clone());
+    let mut config = UserConfig::new(path);
+    config.entries.extend([
+        ConfigEntry::Comment("# Ghostty config, started with ghostty-config.".to_string()),
+        ConfigEntry::Comment(
+            "# Every option: https://ghostty.org/docs/config/reference".to_string(),
+        ),
+    ]);
+    for (key, value) in choices.values() {
+        if schema.find_option(key).is_some() {
+            config.entries.push(ConfigEntry::key_value(key, value));
+        }
+    }
+    annotate_new_keys(&mut config, &blank, schema);
+    if let Some(preset) = choices.keybind_preset.as_deref().and_then(find_preset) {
+        install_preset(&mut config, preset);
+    }
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::snapshot::snapshot;
+    use crate::config::file_io::render_config;
+
+    #[test]
+    fn test_starter_config() {
+        let schema = snapshot(None).unwrap().schema().unwrap();
+        let choices = StarterChoices {
+            theme: "light:Builtin Light,dark:Builtin Dark".to_string(),
+            font_family: " JetBrains Mono ".to_string(),
+            font_size: String::new(),
+            cursor_style: "bar".to_string(),
+            padding: Padding::Comfortable,
+            keybind_preset: Some("vim".to_string()),
+        };
+        let config = starter_config(&choices, &schema, PathBuf::from("/tmp/config"));
+        assert_eq!(
+            config.get("theme"),
+            Some("light:Builtin Light,dark:Builtin Dark")
+        );
+        assert_eq!(config.get("font-family"), Some("JetBrains Mono"));
+        assert_eq!(config.get("font-size"), None);
+        assert_eq!(config.get("window-padding-y"), Some("12"));
+        assert!(config
+            .get_all("keybind")
+            .contains(&"ctrl+w>h=goto_split:left"));
+
+        let text = render_config(&config);
+        assert!(text.starts_with("# Ghostty config, started with ghostty-config."));
+        assert!(text.contains("# ===== Fonts =====\n# "));
+        assert!(text.contains("# ghostty-config preset vim begin"));
+        // Each header comes once, before its keys.
+        assert_eq!(text.matches("# ===== Window =====").count(), 1);
+        assert!(
+            text.find("# ===== Window =====").unwrap() < text.find("window-padding-x").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_defaults_write_nothing() {
+        let schema = snapshot(None).unwrap().schema().unwrap();
+        let config = starter_config(
+            &StarterChoices::default(),
+            &schema,
+            PathBuf::from("/tmp/config"),
+        );
+        assert!(!config
+            .entries
+            .iter()
+            .any(|e| matches!(e, ConfigEntry::KeyValue { .. })));
+        assert_eq!(Padding::parse("spacious"), Some(Padding::Spacious));
+        assert_eq!(Padding::parse("huge"), None);
+    }
+}
@@ -0,0 +1,504 @@
+//! The config as JSON, TOML, or YAML, for tools that generate dotfiles from
+//! data: Nix and home-manager, Ansible, chezmoi templates.
+//!
+//! The data is one object from option name to value. Values are strings, as in
+//! the config file; repeatable options and options set more than once are
+//! arrays. Imports also take numbers and booleans.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::file_io::unquote_value;
+use super::model::{ConfigSchema, UserConfig};
+use crate::Error;
+
+/// A format the config can be exported to or imported from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    /// Ghostty's own `key = value` format.
+    Native,
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl DataFormat {
+    pub fn parse(name: &str) -> Result<Self, Error> {
+        match name {
+            "native" | "ghostty" | "text" => Ok(DataFormat::Native),
+            "json" => Ok(DataFormat::Json),
+            "toml" => Ok(DataFormat::Toml),
+            "yaml" | "yml" => Ok(DataFormat::Yaml),
+            _ => Err(Error::Config(format!(
+                "Unknown format: {} (expected native, json, toml, or yaml)",
+                name
+            ))),
+        }
+    }
+
+    /// Guess the format of pasted text. TOML and Ghostty's format look alike, so
+    /// text is only taken for TOML when it has a table or an array value.
+    pub fn detect(text: &str) -> Self {
+        let trimmed = text.trim_start();
+        if trimmed.starts_with('{') {
+            return DataFormat::Json;
+        }
+        let lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+        for line in lines {
+            if line == "---" || line.starts_with("- ") {
+                return DataFormat::Yaml;
+            }
+            if line.starts_with('[') {
+                return DataFormat::Toml;
+            }
+            let key_end = line
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+                .unwrap_or(line.len());
+            let rest = line[key_end..].trim_start();
+            if rest.starts_with(':') {
+                return DataFormat::Yaml;
+            }
+            if let Some(value) = rest.strip_prefix('=') {
+                if value.trim_start().starts_with('[') {
+                    return DataFormat::Toml;
+                }
+            }
+        }
+        DataFormat::Native
+    }
+
+    /// The media type of an export.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            DataFormat::Native => "text/plain; charset=utf-8",
+            DataFormat::Json => "application/json",
+            DataFormat::Toml => "application/toml",
+            DataFormat::Yaml => "application/yaml",
+        }
+    }
+
+    /// The file extension of an export, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            DataFormat::Native => "conf",
+            DataFormat::Json => "json",
+            DataFormat::Toml => "toml",
+            DataFormat::Yaml => "yaml",
+        }
+    }
+}
+
+/// One option's value in exported data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum DataValue {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// The options set in `config`, by name. Quotes are taken off values.
+pub fn config_data(config: &UserConfig, schema: &ConfigSchema) -> BTreeMap<String, DataValue> {
+    let mut values: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (key, value) in config.all_set_values() {
+        values
+            .entry(key.to_string())
+            .or_default()
+            .push(unquote_value(value).into_owned());
+    }
+    values
+        .into_iter()
+        .map(|(key, mut values)| {
+            let repeatable = schema.find_option(&key).is_some_and(|o| o.is_repeatable);
+            let value = if repeatable || values.len() > 1 {
+                DataValue::Many(values)
+            } else {
+                DataValue::One(values.remove(0))
+            };
+            (key, value)
+        })
+        .collect()
+}
+
+/// Serialize the options set in `config`. [`DataFormat::Native`] is not data and
+/// is refused; render the config for that.
+pub fn export_data(
+    config: &UserConfig,
+    schema: &ConfigSchema,
+    format: DataFormat,
+) -> Result<String, Error> {
+    let data = config_data(config, schema);
+    match format {
+        DataFormat::Json => serde_json::to_string_pretty(&data)
+            .map(|json| json + "\n")
+            .map_err(|e| Error::Internal(e.into())),
+        DataFormat::Toml => basic_toml::to_string(&data).map_err(|e| Error::Internal(e.into())),
+        DataFormat::Yaml => Ok(to_yaml(&data)),
+        DataFormat::Native => Err(Error::Config(
+            "The native format is the config file itself".to_string(),
+        )),
+    }
+}
+
+/// Block-style YAML with every string double-quoted, so values like `true`,
+/// `#fff`, or `ctrl+a=copy` stay strings. JSON string escapes are valid YAML.
+fn to_yaml(data: &BTreeMap<String, DataValue>) -> String {
+    let quote = |s: &str| serde_json::Value::from(s).to_string();
+    let mut yaml = String::new();
+    for (key, value) in data {
+        match value {
+            DataValue::One(value) => yaml.push_str(&format!("{}: {}\n", key, quote(value))),
+            DataValue::Many(values) if values.is_empty() => {
+                yaml.push_str(&format!("{}: []\n", key))
+            }
+            DataValue::Many(values) => {
+                yaml.push_str(&format!("{}:\n", key));
+                for value in values {
+                    yaml.push_str(&format!("  - {}\n", quote(value)));
+                }
+            }
+        }
+    }
+    yaml
+}
+
+/// A value as it may appear in imported data.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum Scalar {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl Scalar {
+    fn into_string(self) -> String {
+        match self {
+            Scalar::Bool(b) => b.to_string(),
+            Scalar::Int(n) => n.to_string(),
+            Scalar::Float(n) => n.to_string(),
+            Scalar::Text(s) => s,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum ImportValue {
+    One(Scalar),
+    Many(Vec<Scalar>),
+}
+
+impl ImportValue {
+    fn into_strings(self) -> Vec<String> {
+        match self {
+            ImportValue::One(value) => vec![value.into_string()],
+            ImportValue::Many(values) => values.into_iter().map(Scalar::into_string).collect(),
+        }
+    }
+}
+
+/// Read JSON, TOML, or YAML data into a config for `file_path`, with one line
+/// per value and keys in alphabetical order.
+///
+/// Only the flat shape [`export_data`] writes is understood: nested objects or
+/// tables are rejected.
+pub fn import_data(text: &str, format: DataFormat, file_path: &Path) -> Result<UserConfig, Error> {
+    let parse_error = |e: &dyn std::fmt::Display| {
+        Error::Config(format!(
+            "Not a flat {} object of option names and values: {}",
+            format.extension().to_uppercase(),
+            e
+        ))
+    };
+    let data: Vec<(String, Vec<String>)> = match format {
+        DataFormat::Json => serde_json::from_str::<BTreeMap<String, ImportValue>>(text)
+            .map_err(|e| parse_error(&e))?
+            .into_iter()
+            .map(|(key, value)| (key, value.into_strings()))
+            .collect(),
+        DataFormat::Toml => basic_toml::from_str::<BTreeMap<String, ImportValue>>(text)
+            .map_err(|e| parse_error(&e))?
+            .into_iter()
+            .map(|(key, value)| (key, value.into_strings()))
+            .collect(),
+        DataFormat::Yaml => parse_yaml(text).map_err(|e| parse_error(&e))?,
+        DataFormat::Native => {
+            return Err(Error::Config(
+                "Parse the native format as a config file".to_string(),
+            ))
+        }
+    };
+
+    let mut config = UserConfig::new(file_path.to_path_buf());
+    for (key, values) in data {
+        for value in values {
+            config.push(&key, &value);
+        }
+    }
+    Ok(config)
+}
+
+/// The YAML [`to_yaml`] writes, plus what people write by hand for the same
+/// data: plain and single-quoted scalars, flow lists (`[a, b]`), comments.
+fn parse_yaml(text: &str) -> Result<Vec<(String, Vec<String>)>, String> {
+    let mut data: Vec<(String, Vec<String>)> = Vec::new();
+    for (number, raw) in text.lines().enumerate() {
+        let line = strip_yaml_comment(raw);
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed == "---" || trimmed == "..." {
+            continue;
+        }
+        let at = |message: &str| format!("line {}: {}", number + 1, message);
+
+        if let Some(item) = trimmed.strip_prefix('-') {
+            let Some((_, values)) = data.last_mut() else {
+                return Err(at("list item without a key"));
+            };
+            values.push(yaml_scalar(item.trim()).map_err(|e| at(&e))?);
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            return Err(at("nested values are not supported"));
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            return Err(at("expected `key: value`"));
+        };
+        let key = key.trim().to_string();
+        let value = value.trim();
+        let values = if value.is_empty() {
+            Vec::new()
+        } else if let Some(items) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            split_flow_list(items)
+                .into_iter()
+                .map(yaml_scalar)
+                .collect::<Result<_, _>>()
+                .map_err(|e| at(&e))?
+        } else if value.starts_with('{') {
+            return Err(at("nested values are not supported"));
+        } else {
+            vec![yaml_scalar(value).map_err(|e| at(&e))?]
+        };
+        data.push((key, values));
+    }
+    data.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(data)
+}
+
+/// Drop a `#` comment that isn't inside quotes.
+fn strip_yaml_comment(line: &str) -> &str {
+    let mut after_space = true;
+    for (i, c, quoted) in unquoted_chars(line) {
+        if !quoted && c == '#' && after_space {
+            return &line[..i];
+        }
+        after_space = c.is_whitespace();
+    }
+    line
+}
+
+/// The items of a flow list's body, split on commas outside quotes.
+fn split_flow_list(items: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for (i, c, quoted) in unquoted_chars(items) {
+        if !quoted && c == ',' {
+            parts.push(items[start..i].trim());
+            start = i + 1;
+        }
+    }
+    parts.push(items[start..].trim());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Each character with its position and whether it is inside quotes. Quote
+/// marks themselves count as quoted.
+fn unquoted_chars(text: &str) -> impl Iterator<Item = (usize, char, bool)> + '_ {
+    let mut quote = None;
+    let mut escaped = false;
+    text.char_indices().map(move |(i, c)| {
+        let quoted = match quote {
+            Some('"') if escaped => {
+                escaped = false;
+                true
+            }
+            Some('"') if c == '\\' => {
+                escaped = true;
+                true
+            }
+            Some(q) if c == q => {
+                quote = None;
+                true
+            }
+            Some(_) => true,
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                true
+            }
+            None => false,
+        };
+        (i, c, quoted)
+    })
+}
+
+fn yaml_scalar(value: &str) -> Result<String, String> {
+    if value.starts_with('"') {
+        return serde_json::from_str::<String>(value).map_err(|e| e.to_string());
+    }
+    if let Some(inner) = value.strip_prefix('\'') {
+        return inner
+            .strip_suffix('\'')
+            .map(|s| s.replace("''", "'"))
+            .ok_or_else(|| "unterminated quote".to_string());
+    }
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file_io::{parse_config, render_config};
+    use crate::config::model::{Category, ConfigOption, ConfigValueType};
+
+    fn schema() -> ConfigSchema {
+        let option = |key: &str, is_repeatable| ConfigOption {
+            key: key.to_string(),
+            default_value: String::new(),
+            documentation: String::new(),
+            value_type: ConfigValueType::Text,
+            category: Category::Fonts,
+            is_repeatable,
+            conditions: Default::default(),
+        };
+        ConfigSchema::new(vec![
+            option("font-size", false),
+            option("font-family", true),
+            option("keybind", true),
+            option("theme", false),
+        ])
+    }
+
+    fn config() -> UserConfig {
+        parse_config(
+            "# mine\nfont-size = 14\nfont-family = \"JetBrains Mono\"\n\
+             keybind = ctrl+a=select_all\nkeybind = ctrl+c=copy\ntheme = dark:Nord,light:Nord Light\n",
+            Path::new("/tmp/config"),
+        )
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(DataFormat::detect("{\"a\": 1}"), DataFormat::Json);
+        assert_eq!(DataFormat::detect("font-size: 13\n"), DataFormat::Yaml);
+        assert_eq!(
+            DataFormat::detect("keybind = [\"a=b\"]\n"),
+            DataFormat::Toml
+        );
+        assert_eq!(DataFormat::detect("[colors]\n"), DataFormat::Toml);
+        assert_eq!(
+            DataFormat::detect("# c\nfont-size = 13\nkeybind = ctrl+a=copy\n"),
+            DataFormat::Native
+        );
+    }
+
+    #[test]
+    fn test_config_data_uses_arrays_for_repeatables() {
+        let data = config_data(&config(), &schema());
+        assert_eq!(data["font-size"], DataValue::One("14".to_string()));
+        assert_eq!(
+            data["font-family"],
+            DataValue::Many(vec!["JetBrains Mono".to_string()])
+        );
+        assert_eq!(
+            data["keybind"],
+            DataValue::Many(vec![
+                "ctrl+a=select_all".to_string(),
+                "ctrl+c=copy".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_export_json() {
+        let json = export_data(&config(), &schema(), DataFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["font-size"], "14");
+        assert_eq!(value["keybind"][1], "ctrl+c=copy");
+    }
+
+    #[test]
+    fn test_export_yaml() {
+        let yaml = export_data(&config(), &schema(), DataFormat::Yaml).unwrap();
+        assert!(yaml.contains("font-size: \"14\"\n"));
+        assert!(yaml.contains("keybind:\n  - \"ctrl+a=select_all\"\n  - \"ctrl+c=copy\"\n"));
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let original = config();
+        for format in [DataFormat::Json, DataFormat::Toml, DataFormat::Yaml] {
+            let text = export_data(&original, &schema(), format).unwrap();
+            let imported = import_data(&text, format, Path::new("/tmp/config")).unwrap();
+            assert_eq!(
+                config_data(&imported, &schema()),
+                config_data(&original, &schema()),
+                "{:?}",
+                format
+            );
+        }
+    }
+
+    #[test]
+    fn test_import_typed_values() {
+        let config = import_data(
+            "{\"font-size\": 13.5, \"window-decoration\": false, \"keybind\": [\"a=b\"]}",
+            DataFormat::Json,
+            Path::new("/tmp/config"),
+        )
+        .unwrap();
+        assert_eq!(config.get("font-size"), Some("13.5"));
+        assert_eq!(config.get("window-decoration"), Some("false"));
+
+        let config = import_data(
+            "font-size = 13\nkeybind = [\"ctrl+a=copy\", \"ctrl+b=paste\"]\n",
+            DataFormat::Toml,
+            Path::new("/tmp/config"),
+        )
+        .unwrap();
+        assert_eq!(config.get("font-size"), Some("13"));
+        assert_eq!(config.get_all("keybind").len(), 2);
+    }
+
+    #[test]
+    fn test_import_hand_written_yaml() {
+        let yaml = "---\n# fonts\nfont-size: 13  # big\ntheme: 'It''s mine'\n\
+                    keybind: [ctrl+a=copy, \"ctrl+b=paste\"]\nfont-family:\n  - Iosevka\n";
+        let config = import_data(yaml, DataFormat::Yaml, Path::new("/tmp/config")).unwrap();
+        assert_eq!(config.get("font-size"), Some("13"));
+        assert_eq!(config.get("theme"), Some("It's mine"));
+        assert_eq!(
+            config.get_all("keybind"),
+            vec!["ctrl+a=copy", "ctrl+b=paste"]
+        );
+        assert_eq!(config.get("font-family"), Some("Iosevka"));
+        assert_eq!(
+            render_config(&config),
+            "font-family = Iosevka\nfont-size = 13\nkeybind = ctrl+a=copy\n\
+             keybind = ctrl+b=paste\ntheme = It's mine\n"
+        );
+    }
+
+    #[test]
+    fn test_import_rejects_nesting() {
+        let path = Path::new("/tmp/config");
+        assert!(import_data("{\"a\": {\"b\": 1}}", DataFormat::Json, path).is_err());
+        assert!(import_data("[colors]\nbg = \"#000\"\n", DataFormat::Toml, path).is_err());
+        assert!(import_data("colors:\n  bg: black\n", DataFormat::Yaml, path).is_err());
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::BTreeMap;
+
+use super::contrast::contrast_ratio;
+use super::custom_theme::hex_to_rgb;
+use super::model::ThemeInfo;
+
+/// Trailing name words that mark a variant rather than a different theme,
+/// e.g. "Gruvbox Dark Hard" is a variant of "Gruvbox".
+const VARIANT_WORDS: &[&str] = &[
+    "dark",
+    "light",
+    "hard",
+    "medium",
+    "soft",
+    "dim",
+    "dimmed",
+    "storm",
+    "moon",
+    "dawn",
+    "day",
+    "mocha",
+    "latte",
+    "frappe",
+    "macchiato",
+    "mirage",
+    "bright",
+    "high",
+    "higher",
+    "low",
+    "lower",
+    "contrast",
+    "hc",
+    "alt",
+];
+
+/// Solarized's eight accent colors.
+const SOLARIZED_ACCENTS: [(u8, u8, u8); 8] = [
+    (0xb5, 0x89, 0x00),
+    (0xcb, 0x4b, 0x16),
+    (0xdc, 0x32, 0x2f),
+    (0xd3, 0x36, 0x82),
+    (0x6c, 0x71, 0xc4),
+    (0x26, 0x8b, 0xd2),
+    (0x2a, 0xa1, 0x98),
+    (0x85, 0x99, 0x00),
+];
+
+/// Solarized's dark (base03) and light (base3) backgrounds.
+const SOLARIZED_BACKGROUNDS: [(u8, u8, u8); 2] = [(0x00, 0x2b, 0x36), (0xfd, 0xf6, 0xe3)];
+
+/// A trait of a theme's colors, worked out from its palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeTag {
+    /// Built on Solarized's backgrounds or accent colors.
+    SolarizedLike,
+    /// Soft, light accent colors.
+    Pastel,
+    /// Foreground and background far apart in luminance.
+    HighContrast,
+    /// Accent colors that are gray or share a single hue.
+    Monochrome,
+}
+
+impl ThemeTag {
+    pub fn all() -> [ThemeTag; 4] {
+        [
+            ThemeTag::SolarizedLike,
+            ThemeTag::Pastel,
+            ThemeTag::HighContrast,
+            ThemeTag::Monochrome,
+        ]
+    }
+
+    pub fn slug(&self) -> &'static str {
+        match self {
+            ThemeTag::SolarizedLike => "solarized-like",
+            ThemeTag::Pastel => "pastel",
+            ThemeTag::HighContrast => "high-contrast",
+            ThemeTag::Monochrome => "monochrome",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemeTag::SolarizedLike => "Solarized-like",
+            ThemeTag::Pastel => "Pastel",
+            ThemeTag::HighContrast => "High contrast",
+            ThemeTag::Monochrome => "Monochrome",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<ThemeTag> {
+        ThemeTag::all().into_iter().find(|t| t.slug() == slug)
+    }
+}
+
+/// The family a theme belongs to: its name without trailing variant words.
+///
+/// "Gruvbox Dark Hard" and "gruvbox-light" are both in the "Gruvbox"/"gruvbox"
+/// family; compare families case-insensitively.
+pub fn theme_family(name: &str) -> String {
+    let is_separator = |c: char| c == ' ' || c == '-' || c == '_';
+    let mut family = name.trim();
+    while let Some(split) = family.rfind(is_separator) {
+        let last = &family[split + 1..];
+        if !VARIANT_WORDS.contains(&last.to_lowercase().as_str()) {
+            break;
+        }
+        family = family[..split].trim_end_matches(is_separator);
+    }
+    family.to_string()
+}
+
+/// Families with more than one theme, with their sizes, sorted by name.
+pub fn theme_families(themes: &[ThemeInfo]) -> Vec<(String, usize)> {
+    let mut families: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    for theme in themes {
+        let family = theme_family(&theme.name);
+        families
+            .entry(family.to_lowercase())
+            .or_insert_with(|| (family, 0))
+            .1 += 1;
+    }
+    families
+        .into_values()
+        .filter(|(_, count)| *count > 1)
+        .collect()
+}
+
+/// Everything [`ThemeTag`] says about `theme`.
+pub fn theme_tags(theme: &ThemeInfo) -> Vec<ThemeTag> {
+    let accents: Vec<(u8, u8, u8)> = accent_colors(theme);
+    let mut tags = Vec::new();
+    if is_solarized_like(theme, &accents) {
+        tags.push(ThemeTag::SolarizedLike);
+    }
+    if is_pastel(&accents) {
+        tags.push(ThemeTag::Pastel);
+    }
+    if let (Some(fg), Some(bg)) = (hex_to_rgb(&theme.foreground), hex_to_rgb(&theme.background)) {
+        if contrast_ratio(fg, bg) >= 15.0 {
+            tags.push(ThemeTag::HighContrast);
+        }
+    }
+    if is_monochrome(&accents) {
+        tags.push(ThemeTag::Monochrome);
+    }
+    tags
+}
+
+/// The normal red through cyan palette slots (1–6) that are set.
+fn accent_colors(theme: &ThemeInfo) -> Vec<(u8, u8, u8)> {
+    theme
+        .palette
+        .iter()
+        .take(7)
+        .skip(1)
+        .filter_map(|c| hex_to_rgb(c))
+        .collect()
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let d = |x: u8, y: u8| (x as f64 - y as f64).powi(2);
+    (d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)).sqrt()
+}
+
+fn is_solarized_like(theme: &ThemeInfo, accents: &[(u8, u8, u8)]) -> bool {
+    let background_matches = hex_to_rgb(&theme.background).is_some_and(|bg| {
+        SOLARIZED_BACKGROUNDS
+            .iter()
+            .any(|&s| distance(bg, s) < 20.0)
+    });
+    let matching_accents = accents
+        .iter()
+        .filter(|&&c| SOLARIZED_ACCENTS.iter().any(|&s| distance(c, s) < 40.0))
+        .count();
+    background_matches || matching_accents >= 4
+}
+
+/// HSV saturation and value, both 0–1.
+fn saturation_value((r, g, b): (u8, u8, u8)) -> (f64, f64) {
+    let max = r.max(g).max(b) as f64 / 255.0;
+    let min = r.min(g).min(b) as f64 / 255.0;
+    let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+    (saturation, max)
+}
+
+fn hue((r, g, b): (u8, u8, u8)) -> f64 {
+    let (r, g, b) = (r as f64, g as f64, b as f64);
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+    if delta == 0.0 {
+        return 0.0;
+    }
+    let sector = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    sector * 60.0
+}
+
+fn is_pastel(accents: &[(u8, u8, u8)]) -> bool {
+    if accents.len() < 3 {
+        return false;
+    }
+    let n = accents.len() as f64;
+    let (saturation, value) = accents
+        .iter()
+        .map(|&c| saturation_value(c))
+        .fold((0.0, 0.0), |(s, v), (cs, cv)| (s + cs / n, v + cv / n));
+    (0.15..=0.55).contains(&saturation) && value >= 0.75
+}
+
+fn is_monochrome(accents: &[(u8, u8, u8)]) -> bool {
+    if accents.len() < 3 {
+        return false;
+    }
+    let mut hues: Vec<f64> = accents
+        .iter()
+        .filter(|&&c| saturation_value(c).0 >= 0.2)
+        .map(|&c| hue(c))
+        .collect();
+    if hues.len() < 2 {
+        return true;
+    }
+    // The hues share one region of the color wheel if the largest gap between
+    // neighbors (going around the circle) leaves less than 40° for the rest.
+    hues.sort_by(|a, b| a.total_cmp(b));
+    let wrap_gap = 360.0 - hues[hues.len() - 1] + hues[0];
+    let largest_gap = hues
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .fold(wrap_gap, f64::max);
+    360.0 - largest_gap < 40.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme(name: &str, background: &str, foreground: &str, accents: [&str; 6]) -> ThemeInfo {
+        let mut palette = vec![String::new(); 16];
+        for (i, color) in accents.iter().enumerate() {
+            palette[i + 1] = color.to_string();
+        }
+        ThemeInfo {
+            name: name.to_string(),
+            background: background.to_string(),
+            foreground: foreground.to_string(),
+            palette,
+            is_dark: true,
+            cursor_color: None,
+            selection_background: None,
+            custom: false,
+            origin: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_theme_family() {
+        assert_eq!(theme_family("Gruvbox Dark"), "Gruvbox");
+        assert_eq!(theme_family("Gruvbox Dark Hard"), "Gruvbox");
+        assert_eq!(theme_family("Gruvbox Material"), "Gruvbox Material");
+        assert_eq!(theme_family("rose-pine-dawn"), "rose-pine");
+        assert_eq!(theme_family("Tokyo Night Storm"), "Tokyo Night");
+        assert_eq!(theme_family("Dark"), "Dark");
+        assert_eq!(theme_family("Dracula"), "Dracula");
+    }
+
+    #[test]
+    fn test_theme_families_only_groups() {
+        let names = [
+            "Gruvbox Dark",
+            "gruvbox-light",
+            "Dracula",
+            "Nord",
+            "Nord Light",
+        ];
+        let themes: Vec<ThemeInfo> = names
+            .iter()
+            .map(|n| theme(n, "#000000", "#ffffff", [""; 6]))
+            .collect();
+        assert_eq!(
+            theme_families(&themes),
+            vec![("Gruvbox".to_string(), 2), ("Nord".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn test_tags_solarized() {
+        let solarized = theme(
+            "Builtin Solarized Dark",
+            "#002b36",
+            "#839496",
+            [
+                "#dc322f", "#859900", "#b58900", "#268bd2", "#d33682", "#2aa198",
+            ],
+        );
+        assert!(theme_tags(&solarized).contains(&ThemeTag::SolarizedLike));
+    }
+
+    #[test]
+    fn test_tags_pastel_and_contrast() {
+        let pastel = theme(
+            "Catppuccin Mocha",
+            "#1e1e2e",
+            "#cdd6f4",
+            [
+                "#f38ba8", "#a6e3a1", "#f9e2af", "#89b4fa", "#f5c2e7", "#94e2d5",
+            ],
+        );
+        assert_eq!(theme_tags(&pastel), vec![ThemeTag::Pastel]);
+
+        let stark = theme(
+            "Stark",
+            "#000000",
+            "#ffffff",
+            [
+                "#ff0000", "#00ff00", "#ffff00", "#0000ff", "#ff00ff", "#00ffff",
+            ],
+        );
+        assert_eq!(theme_tags(&stark), vec![ThemeTag::HighContrast]);
+    }
+
+    #[test]
+    fn test_tags_monochrome() {
+        let green = theme(
+            "Phosphor",
+            "#001100",
+            "#33ff33",
+            [
+                "#1a801a", "#33ff33", "#2bcc2b", "#22aa22", "#44dd55", "#55ee66",
+            ],
+        );
+        assert!(theme_tags(&green).contains(&ThemeTag::Monochrome));
+
+        let gray = theme(
+            "Grayscale",
+            "#101010",
+            "#d0d0d0",
+            [
+                "#606060", "#808080", "#a0a0a0", "#707070", "#909090", "#b0b0b0",
+            ],
+        );
+        assert!(theme_tags(&gray).contains(&ThemeTag::Monochrome));
+    }
+
+    #[test]
+    fn test_tag_slugs_round_trip() {
+        for tag in ThemeTag::all() {
+            assert_eq!(ThemeTag::from_slug(tag.slug()), Some(tag));
+        }
+        assert_eq!(ThemeTag::from_slug("neon"), None);
+    }
+}
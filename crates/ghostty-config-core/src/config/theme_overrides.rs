@@ -0,0 +1,190 @@
+use serde::Serialize;
+
+use super::background::active_theme;
+use super::model::{ThemeInfo, UserConfig};
+
+/// Keys a theme sets that an explicit config value silently overrides.
+pub const THEME_COLOR_KEYS: &[&str] = &[
+    "background",
+    "foreground",
+    "cursor-color",
+    "cursor-text",
+    "selection-background",
+    "selection-foreground",
+    "palette",
+];
+
+/// An explicit color key that takes precedence over the active theme.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ThemeOverride {
+    pub key: String,
+    /// The explicit value; `N=#rrggbb` for palette entries.
+    pub value: String,
+    /// What the theme sets for the same key, when known.
+    pub theme_value: Option<String>,
+}
+
+fn palette_index(value: &str) -> Option<usize> {
+    value.split_once('=')?.0.trim().parse().ok()
+}
+
+fn theme_value(theme: &ThemeInfo, key: &str, value: &str) -> Option<String> {
+    match key {
+        "background" => Some(theme.background.clone()),
+        "foreground" => Some(theme.foreground.clone()),
+        "cursor-color" => theme.cursor_color.clone(),
+        "selection-background" => theme.selection_background.clone(),
+        "palette" => palette_index(value)
+            .and_then(|i| theme.palette.get(i))
+            .filter(|c| !c.is_empty())
+            .cloned(),
+        _ => None,
+    }
+}
+
+/// List explicit color keys that override the configured theme.
+///
+/// Empty when no theme is set. If the theme isn't one of `themes` (e.g. a path),
+/// the overrides are still reported, just without the theme's values.
+pub fn find_theme_overrides(config: &UserConfig, themes: &[ThemeInfo]) -> Vec<ThemeOverride> {
+    if config.get("theme").is_none_or(|t| t.trim().is_empty()) {
+        return Vec::new();
+    }
+    let theme = active_theme(config, themes);
+
+    config
+        .all_set_values()
+        .into_iter()
+        .filter(|(key, _)| THEME_COLOR_KEYS.contains(key))
+        .map(|(key, value)| ThemeOverride {
+            key: key.to_string(),
+            value: value.to_string(),
+            theme_value: theme.and_then(|t| theme_value(t, key, value)),
+        })
+        .collect()
+}
+
+/// Remove every explicit theme color key so the theme applies unmodified.
+pub fn clear_theme_overrides(config: &mut UserConfig) {
+    for key in THEME_COLOR_KEYS {
+        config.remove(key);
+    }
+}
+
+/// Render a Ghostty theme file: the base theme's colors with `overrides` applied.
+pub fn render_theme_file(base: Option<&ThemeInfo>, overrides: &[ThemeOverride]) -> String {
+    let mut lines: Vec<(String, String)> = Vec::new();
+    if let Some(theme) = base {
+        for (i, color) in theme.palette.iter().enumerate() {
+            if !color.is_empty() {
+                lines.push(("palette".to_string(), format!("{}={}", i, color)));
+            }
+        }
+        lines.push(("background".to_string(), theme.background.clone()));
+        lines.push(("foreground".to_string(), theme.foreground.clone()));
+        if let Some(c) = &theme.cursor_color {
+            lines.push(("cursor-color".to_string(), c.clone()));
+        }
+        if let Some(c) = &theme.selection_background {
+            lines.push(("selection-background".to_string(), c.clone()));
+        }
+    }
+
+    for o in overrides {
+        let existing = lines.iter_mut().find(|(key, value)| {
+            *key == o.key && (o.key != "palette" || palette_index(value) == palette_index(&o.value))
+        });
+        match existing {
+            Some((_, value)) => *value = o.value.clone(),
+            None => lines.push((o.key.clone(), o.value.clone())),
+        }
+    }
+
+    let mut out = String::new();
+    for (key, value) in lines {
+        out.push_str(&format!("{} = {}\n", key, value));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::test_config;
+
+    fn nord() -> ThemeInfo {
+        let mut palette = vec![String::new(); 16];
+        palette[1] = "#bf616a".to_string();
+        ThemeInfo {
+            name: "Nord".to_string(),
+            background: "#2e3440".to_string(),
+            foreground: "#d8dee9".to_string(),
+            palette,
+            is_dark: true,
+            cursor_color: Some("#eceff4".to_string()),
+            selection_background: None,
+            custom: false,
+            origin: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_no_theme_no_overrides() {
+        let config = test_config(&[("background", "#000000")]);
+        assert!(find_theme_overrides(&config, &[nord()]).is_empty());
+    }
+
+    #[test]
+    fn test_find_overrides_with_theme_values() {
+        let config = test_config(&[
+            ("theme", "Nord"),
+            ("background", "#000000"),
+            ("palette", "1=#ff0000"),
+            ("font-size", "14"),
+        ]);
+        let overrides = find_theme_overrides(&config, &[nord()]);
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides[0].key, "background");
+        assert_eq!(overrides[0].theme_value.as_deref(), Some("#2e3440"));
+        assert_eq!(overrides[1].value, "1=#ff0000");
+        assert_eq!(overrides[1].theme_value.as_deref(), Some("#bf616a"));
+    }
+
+    #[test]
+    fn test_unknown_theme_still_reports() {
+        let config = test_config(&[("theme", "/path/to/theme"), ("foreground", "#eeeeee")]);
+        let overrides = find_theme_overrides(&config, &[]);
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].theme_value, None);
+    }
+
+    #[test]
+    fn test_clear_overrides() {
+        let mut config = test_config(&[
+            ("theme", "Nord"),
+            ("background", "#000000"),
+            ("palette", "1=#ff0000"),
+            ("palette", "2=#00ff00"),
+        ]);
+        clear_theme_overrides(&mut config);
+        assert_eq!(config.all_set_values(), vec![("theme", "Nord")]);
+    }
+
+    #[test]
+    fn test_render_theme_file_applies_overrides() {
+        let config = test_config(&[
+            ("theme", "Nord"),
+            ("background", "#000000"),
+            ("palette", "1=#ff0000"),
+            ("cursor-text", "#111111"),
+        ]);
+        let theme = nord();
+        let overrides = find_theme_overrides(&config, std::slice::from_ref(&theme));
+        let file = render_theme_file(Some(&theme), &overrides);
+        assert!(file.contains("palette = 1=#ff0000\n"));
+        assert!(!file.contains("#bf616a"));
+        assert!(file.contains("background = #000000\n"));
+        assert!(file.contains("foreground = #d8dee9\n"));
+        assert!(file.contains("cursor-text = #111111\n"));
+    }
+}
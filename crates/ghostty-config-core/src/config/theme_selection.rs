@@ -0,0 +1,199 @@
+//! The `theme` option: one theme, or a `light:NAME,dark:NAME` pair that Ghostty
+//! switches between with the system appearance.
+
+/// Which half of a light/dark pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeVariant {
+    Light,
+    Dark,
+}
+
+impl ThemeVariant {
+    pub fn parse(value: &str) -> Option<ThemeVariant> {
+        match value {
+            "light" => Some(ThemeVariant::Light),
+            "dark" => Some(ThemeVariant::Dark),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeVariant::Light => "Light",
+            ThemeVariant::Dark => "Dark",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeSelection {
+    /// No theme set.
+    None,
+    Single(String),
+    Paired {
+        light: String,
+        dark: String,
+    },
+}
+
+impl ThemeSelection {
+    /// Parse a `theme` value. A pair missing one half uses the other for both, as
+    /// Ghostty needs both to switch.
+    pub fn parse(value: &str) -> ThemeSelection {
+        let value = value.trim();
+        if value.is_empty() {
+            return ThemeSelection::None;
+        }
+        let (mut light, mut dark) = (None, None);
+        for part in value.split(',') {
+            match part.trim().split_once(':') {
+                Some(("light", name)) => light = Some(name.trim().to_string()),
+                Some(("dark", name)) => dark = Some(name.trim().to_string()),
+                _ => {}
+            }
+        }
+        match (light, dark) {
+            (None, None) => ThemeSelection::Single(value.to_string()),
+            (Some(light), Some(dark)) => ThemeSelection::Paired { light, dark },
+            (Some(name), None) | (None, Some(name)) => ThemeSelection::Paired {
+                light: name.clone(),
+                dark: name,
+            },
+        }
+    }
+
+    /// The `theme` value for this selection; empty for none.
+    pub fn to_value(&self) -> String {
+        match self {
+            ThemeSelection::None => String::new(),
+            ThemeSelection::Single(name) => name.clone(),
+            ThemeSelection::Paired { light, dark } => format!("light:{},dark:{}", light, dark),
+        }
+    }
+
+    /// This selection with `variant` set to `name`. A single theme becomes the
+    /// other half of the new pair.
+    pub fn with_variant(self, variant: ThemeVariant, name: &str) -> ThemeSelection {
+        let (light, dark) = match self {
+            ThemeSelection::None => (name.to_string(), name.to_string()),
+            ThemeSelection::Single(other) => (other.clone(), other),
+            ThemeSelection::Paired { light, dark } => (light, dark),
+        };
+        let (light, dark) = match variant {
+            ThemeVariant::Light => (name.to_string(), dark),
+            ThemeVariant::Dark => (light, name.to_string()),
+        };
+        ThemeSelection::Paired { light, dark }
+    }
+
+    /// The variants `name` is used for: empty when it isn't selected, and both for
+    /// a single theme.
+    pub fn variants_of(&self, name: &str) -> Vec<ThemeVariant> {
+        match self {
+            ThemeSelection::None => Vec::new(),
+            ThemeSelection::Single(single) if single == name => {
+                vec![ThemeVariant::Light, ThemeVariant::Dark]
+            }
+            ThemeSelection::Single(_) => Vec::new(),
+            ThemeSelection::Paired { light, dark } => {
+                let mut variants = Vec::new();
+                if light == name {
+                    variants.push(ThemeVariant::Light);
+                }
+                if dark == name {
+                    variants.push(ThemeVariant::Dark);
+                }
+                variants
+            }
+        }
+    }
+
+    /// The theme the preview shows: the dark one of a pair, matching the
+    /// preview's dark chrome.
+    pub fn preview_name(&self) -> Option<&str> {
+        match self {
+            ThemeSelection::None => None,
+            ThemeSelection::Single(name) => Some(name),
+            ThemeSelection::Paired { dark, .. } => Some(dark),
+        }
+    }
+
+    /// A short description for headings, e.g. `Nord` or `Light: X · Dark: Y`.
+    pub fn describe(&self) -> String {
+        match self {
+            ThemeSelection::None => String::new(),
+            ThemeSelection::Single(name) => name.clone(),
+            ThemeSelection::Paired { light, dark } if light == dark => light.clone(),
+            ThemeSelection::Paired { light, dark } => format!("Light: {} · Dark: {}", light, dark),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_serialize() {
+        assert_eq!(ThemeSelection::parse(""), ThemeSelection::None);
+        assert_eq!(
+            ThemeSelection::parse("Nord"),
+            ThemeSelection::Single("Nord".to_string())
+        );
+        let pair = ThemeSelection::parse("dark: Nord , light:Solarized Light");
+        assert_eq!(
+            pair,
+            ThemeSelection::Paired {
+                light: "Solarized Light".to_string(),
+                dark: "Nord".to_string()
+            }
+        );
+        assert_eq!(pair.to_value(), "light:Solarized Light,dark:Nord");
+        assert_eq!(pair.describe(), "Light: Solarized Light · Dark: Nord");
+        assert_eq!(
+            ThemeSelection::parse("light:Nord").to_value(),
+            "light:Nord,dark:Nord"
+        );
+    }
+
+    #[test]
+    fn test_preview_name() {
+        assert_eq!(
+            ThemeSelection::parse("Dracula").preview_name(),
+            Some("Dracula")
+        );
+        assert_eq!(
+            ThemeSelection::parse("light:Solarized Light,dark:Nord").preview_name(),
+            Some("Nord")
+        );
+        assert_eq!(
+            ThemeSelection::parse("light:Solarized Light").preview_name(),
+            Some("Solarized Light")
+        );
+        assert_eq!(ThemeSelection::parse("").preview_name(), None);
+    }
+
+    #[test]
+    fn test_with_variant() {
+        let pair =
+            ThemeSelection::parse("Nord").with_variant(ThemeVariant::Light, "Solarized Light");
+        assert_eq!(pair.to_value(), "light:Solarized Light,dark:Nord");
+        let pair = pair.with_variant(ThemeVariant::Dark, "Dracula");
+        assert_eq!(pair.to_value(), "light:Solarized Light,dark:Dracula");
+        assert_eq!(
+            ThemeSelection::None
+                .with_variant(ThemeVariant::Dark, "Nord")
+                .to_value(),
+            "light:Nord,dark:Nord"
+        );
+    }
+
+    #[test]
+    fn test_variants_of() {
+        let pair = ThemeSelection::parse("light:A,dark:B");
+        assert_eq!(pair.variants_of("A"), vec![ThemeVariant::Light]);
+        assert_eq!(pair.variants_of("B"), vec![ThemeVariant::Dark]);
+        assert!(pair.variants_of("C").is_empty());
+        assert_eq!(ThemeSelection::parse("A").variants_of("A").len(), 2);
+    }
+}
@@ -1,10 +1,12 @@
 use regex::Regex;
 use std::sync::LazyLock;
 
-use super::model::ConfigValueType;
+use super::color_names::{resolve_color, COLOR_KEYWORDS};
+use super::model::{ConfigValueType, EnumVariant};
+use super::units::parse_duration;
 
 static ENUM_BULLET_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^\s+\*\s+`([^`]+)`").unwrap());
+    LazyLock::new(|| Regex::new(r"^(\s+)\*\s+`([^`]+)`(.*)$").unwrap());
 
 /// Infer the value type of a config option from its key, default value, and documentation.
 pub fn infer_type(key: &str, default: &str, docs: &str) -> ConfigValueType {
@@ -13,6 +15,11 @@ pub fn infer_type(key: &str, default: &str, docs: &str) -> ConfigValueType {
         return t;
     }
 
+    // Font metric adjustments: pixels or a percentage
+    if key.starts_with("adjust-") {
+        return ConfigValueType::AdjustMetric;
+    }
+
     // Keybind
     if key == "keybind" {
         return ConfigValueType::Keybind;
@@ -45,13 +52,19 @@ pub fn infer_type(key: &str, default: &str, docs: &str) -> ConfigValueType {
         || key == "cursor-text"
         || key == "bold-color"
         || key.starts_with("split-"))
-        && (default.is_empty() || default.starts_with('#'))
+        && (default.is_empty()
+            || resolve_color(default).is_some()
+            || COLOR_KEYWORDS.contains(&default))
     {
         return ConfigValueType::Color;
     }
 
     // Path keys
-    if key == "config-file" || key == "working-directory" || key.starts_with("custom-shader") {
+    if key == "config-file"
+        || key == "working-directory"
+        || key == "background-image"
+        || key.starts_with("custom-shader")
+    {
         return ConfigValueType::Path;
     }
 
@@ -61,6 +74,26 @@ pub fn infer_type(key: &str, default: &str, docs: &str) -> ConfigValueType {
         return ConfigValueType::Enum(enum_values);
     }
 
+    // Duration: default has units, like `750ms`, or docs give a count in milliseconds
+    let integer_default = !default.is_empty() && default.parse::<u64>().is_ok();
+    if parse_duration(default).is_some() || (integer_default && docs.contains("milliseconds")) {
+        return ConfigValueType::Duration;
+    }
+
+    // Byte size: docs give a count in bytes
+    if integer_default && docs.contains("in bytes") {
+        return ConfigValueType::ByteSize;
+    }
+
+    // Percent: an opacity between 0 and 1
+    if key.ends_with("-opacity")
+        && default
+            .parse::<f64>()
+            .is_ok_and(|n| (0.0..=1.0).contains(&n))
+    {
+        return ConfigValueType::Percent;
+    }
+
     // Float: default contains a decimal point
     if default.contains('.') && default.parse::<f64>().is_ok() {
         return ConfigValueType::Float;
@@ -81,25 +114,37 @@ pub fn infer_type(key: &str, default: &str, docs: &str) -> ConfigValueType {
 
 /// Extract enum values from documentation bullet lists like:
 ///   * `value` - Description
-fn extract_enum_values(docs: &str) -> Vec<String> {
-    let mut values = Vec::new();
-    let mut in_list = false;
+fn extract_enum_values(docs: &str) -> Vec<EnumVariant> {
+    let mut values: Vec<EnumVariant> = Vec::new();
+    // The indent of the bullet whose description may continue on the next lines.
+    let mut bullet_indent: Option<usize> = None;
 
     for line in docs.lines() {
         if let Some(caps) = ENUM_BULLET_RE.captures(line) {
-            let val = caps[1].to_string();
+            let val = &caps[2];
+            bullet_indent = None;
             // Skip values that look like examples or non-enum items
             if !val.contains(' ') && !val.contains('=') && !val.starts_with("e.g") {
-                values.push(val);
-                in_list = true;
+                let doc = caps[3]
+                    .trim()
+                    .trim_start_matches(['-', ':', '–', '—'])
+                    .trim();
+                values.push(EnumVariant {
+                    value: val.to_string(),
+                    doc: doc.to_string(),
+                });
+                bullet_indent = Some(caps[1].len());
+            }
+        } else if let Some(indent) = bullet_indent {
+            let text = line.trim_start();
+            if text.is_empty() || line.len() - text.len() <= indent {
+                bullet_indent = None;
+            } else if let Some(last) = values.last_mut() {
+                if !last.doc.is_empty() {
+                    last.doc.push(' ');
+                }
+                last.doc.push_str(text);
             }
-        } else if in_list
-            && !line.trim().is_empty()
-            && !line.starts_with("  ")
-            && !line.starts_with('#')
-        {
-            // We've left the bullet list
-            // Actually, keep collecting — docs may have multiple paragraphs between bullets
         }
     }
 
@@ -127,6 +172,20 @@ pub fn is_repeatable(key: &str) -> bool {
     )
 }
 
+fn manual_override(key: &str) -> Option<ConfigValueType> {
+    match key {
+        "font-size" => Some(ConfigValueType::Float),
+        "window-padding-x" | "window-padding-y" => Some(ConfigValueType::Text),
+        "window-padding-balance" => Some(ConfigValueType::Boolean),
+        "scrollback-limit" => Some(ConfigValueType::ByteSize),
+        "image-storage-limit" => Some(ConfigValueType::ByteSize),
+        "click-repeat-interval" => Some(ConfigValueType::Duration),
+        "font-thicken-strength" => Some(ConfigValueType::Integer),
+        "faint-opacity" => Some(ConfigValueType::Percent),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,6 +224,18 @@ mod tests {
             infer_type("bold-color", "", ""),
             ConfigValueType::Color
         ));
+        assert!(matches!(
+            infer_type("split-divider-color", "gray", ""),
+            ConfigValueType::Color
+        ));
+        assert!(matches!(
+            infer_type("cursor-text", "cell-background", ""),
+            ConfigValueType::Color
+        ));
+        assert!(matches!(
+            infer_type("selection-background", "#1d1f21cc", ""),
+            ConfigValueType::Color
+        ));
     }
 
     #[test]
@@ -206,7 +277,7 @@ mod tests {
     #[test]
     fn test_integer_inference() {
         assert!(matches!(
-            infer_type("scrollback-limit", "10000", ""),
+            infer_type("window-height", "0", ""),
             ConfigValueType::Integer
         ));
         assert!(matches!(
@@ -215,6 +286,42 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_duration_and_size_inference() {
+        assert!(matches!(
+            infer_type("click-repeat-interval", "0", ""),
+            ConfigValueType::Duration
+        )); // manual override
+        assert!(matches!(
+            infer_type("resize-overlay-duration", "750ms", ""),
+            ConfigValueType::Duration
+        ));
+        assert!(matches!(
+            infer_type("some-delay", "200", "The delay in milliseconds."),
+            ConfigValueType::Duration
+        ));
+        assert!(matches!(
+            infer_type("scrollback-limit", "10000000", ""),
+            ConfigValueType::ByteSize
+        ));
+        assert!(matches!(
+            infer_type("some-limit", "4096", "The limit in bytes."),
+            ConfigValueType::ByteSize
+        ));
+    }
+
+    #[test]
+    fn test_adjust_metric_inference() {
+        assert!(matches!(
+            infer_type("adjust-cell-height", "", ""),
+            ConfigValueType::AdjustMetric
+        ));
+        assert!(matches!(
+            infer_type("adjust-icon-height", "", ""),
+            ConfigValueType::AdjustMetric
+        ));
+    }
+
     #[test]
     fn test_float_inference() {
         assert!(matches!(
@@ -222,11 +329,27 @@ mod tests {
             ConfigValueType::Float
         )); // manual override
         assert!(matches!(
-            infer_type("faint-opacity", "0.5", ""),
+            infer_type("unknown-float", "1.5", ""),
             ConfigValueType::Float
         ));
+    }
+
+    #[test]
+    fn test_percent_inference() {
         assert!(matches!(
-            infer_type("unknown-float", "1.5", ""),
+            infer_type("faint-opacity", "0.5", ""),
+            ConfigValueType::Percent
+        ));
+        assert!(matches!(
+            infer_type("background-opacity", "1", ""),
+            ConfigValueType::Percent
+        ));
+        assert!(matches!(
+            infer_type("unfocused-split-opacity", "0.7", ""),
+            ConfigValueType::Percent
+        ));
+        assert!(matches!(
+            infer_type("some-opacity", "2.5", ""),
             ConfigValueType::Float
         ));
     }
@@ -245,6 +368,10 @@ mod tests {
             infer_type("custom-shader", "", ""),
             ConfigValueType::Path
         ));
+        assert!(matches!(
+            infer_type("background-image", "", ""),
+            ConfigValueType::Path
+        ));
     }
 
     #[test]
@@ -260,6 +387,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_enum_descriptions() {
+        let docs = "Valid values:\n\n  * `never` - Never hide the\n    mouse.\n  * `always`\n\n    Not part of the bullet.\n  * `auto`: Decide based\n    on the platform\n";
+        let ConfigValueType::Enum(values) = infer_type("mouse-hide", "", docs) else {
+            panic!("expected an enum");
+        };
+        let pairs: Vec<_> = values
+            .iter()
+            .map(|v| (v.value.as_str(), v.doc.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("never", "Never hide the mouse."),
+                ("always", ""),
+                ("auto", "Decide based on the platform"),
+            ]
+        );
+    }
+
     #[test]
     fn test_enum_skips_examples() {
         let docs = "  * `e.g. something` - skip\n  * `value one` - skip spaces\n";
@@ -304,31 +451,7 @@ mod tests {
         ));
         assert!(matches!(
             infer_type("adjust-cell-width", "", ""),
-            ConfigValueType::Text
+            ConfigValueType::AdjustMetric
         ));
     }
 }
-
-fn manual_override(key: &str) -> Option<ConfigValueType> {
-    match key {
-        "font-size" => Some(ConfigValueType::Float),
-        "adjust-cell-width" | "adjust-cell-height" => Some(ConfigValueType::Text),
-        "adjust-font-baseline"
-        | "adjust-underline-position"
-        | "adjust-underline-thickness"
-        | "adjust-strikethrough-position"
-        | "adjust-strikethrough-thickness"
-        | "adjust-overline-position"
-        | "adjust-overline-thickness"
-        | "adjust-cursor-thickness"
-        | "adjust-cursor-height"
-        | "adjust-box-thickness" => Some(ConfigValueType::Text),
-        "window-padding-x" | "window-padding-y" => Some(ConfigValueType::Text),
-        "window-padding-balance" => Some(ConfigValueType::Boolean),
-        "scrollback-limit" => Some(ConfigValueType::Integer),
-        "image-storage-limit" => Some(ConfigValueType::Integer),
-        "font-thicken-strength" => Some(ConfigValueType::Integer),
-        "faint-opacity" => Some(ConfigValueType::Float),
-        _ => None,
-    }
-}
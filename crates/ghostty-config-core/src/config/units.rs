@@ -0,0 +1,150 @@
+//! Durations and byte sizes, which the UI edits as an amount plus a unit.
+
+/// Units Ghostty accepts in a duration, in nanoseconds. The longest matching
+/// suffix wins, so `ms` isn't read as minutes.
+const DURATION_SUFFIXES: &[(&str, u64)] = &[
+    ("ns", 1),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ms", 1_000_000),
+    ("s", 1_000_000_000),
+    ("m", 60_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("d", 86_400_000_000_000),
+    ("w", 604_800_000_000_000),
+    ("y", 31_536_000_000_000_000),
+];
+
+/// Units offered for durations, largest first, in milliseconds.
+pub const DURATION_UNITS: &[(&str, u64)] =
+    &[("h", 3_600_000), ("m", 60_000), ("s", 1_000), ("ms", 1)];
+
+/// Units offered for byte sizes, largest first. Ghostty's documented defaults
+/// are decimal, such as 10 MB of scrollback.
+pub const BYTE_UNITS: &[(&str, u64)] = &[
+    ("GB", 1_000_000_000),
+    ("MB", 1_000_000),
+    ("KB", 1_000),
+    ("B", 1),
+];
+
+/// A Ghostty duration like `750ms`, `1h30m`, or `1h 30m`, in nanoseconds.
+pub fn parse_duration(value: &str) -> Option<u64> {
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    let mut total: u64 = 0;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let amount: u64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let (suffix, nanos) = DURATION_SUFFIXES
+            .iter()
+            .filter(|(suffix, _)| rest.starts_with(suffix))
+            .max_by_key(|(suffix, _)| suffix.len())?;
+        total = total.checked_add(amount.checked_mul(*nanos)?)?;
+        rest = rest[suffix.len()..].trim_start();
+    }
+    Some(total)
+}
+
+/// Milliseconds in a duration value. Options whose default is a bare number take
+/// a bare number of milliseconds, so that is accepted too.
+pub fn duration_millis(value: &str) -> Option<u64> {
+    match value.trim().parse::<u64>() {
+        Ok(millis) => Some(millis),
+        Err(_) => parse_duration(value).map(|nanos| nanos / 1_000_000),
+    }
+}
+
+/// Whether an option's durations are bare milliseconds rather than Ghostty
+/// durations with units, judging by its default.
+pub fn is_plain_millis(default: &str) -> bool {
+    default.trim().parse::<u64>().is_ok()
+}
+
+/// The largest unit that expresses `amount` exactly, with the amount in that unit.
+fn split(amount: u64, units: &[(&'static str, u64)]) -> (u64, &'static str) {
+    let smallest = &units[units.len() - 1];
+    let (unit, size) = if amount == 0 {
+        smallest
+    } else {
+        units
+            .iter()
+            .find(|(_, size)| amount.is_multiple_of(*size))
+            .unwrap_or(smallest)
+    };
+    (amount / size, unit)
+}
+
+/// A duration value as an amount and one of [`DURATION_UNITS`], for its input.
+/// Precision below a millisecond is dropped.
+pub fn split_duration(value: &str) -> Option<(u64, &'static str)> {
+    Some(split(duration_millis(value)?, DURATION_UNITS))
+}
+
+/// A byte size value as an amount and one of [`BYTE_UNITS`], for its input.
+pub fn split_byte_size(value: &str) -> Option<(u64, &'static str)> {
+    Some(split(value.trim().parse().ok()?, BYTE_UNITS))
+}
+
+/// The config value for `amount` of duration `unit`: `amount` and the unit, or
+/// the total in milliseconds when `plain_millis`.
+pub fn format_duration(amount: u64, unit: &str, plain_millis: bool) -> Option<String> {
+    let (_, size) = DURATION_UNITS.iter().find(|(u, _)| *u == unit)?;
+    if plain_millis {
+        Some(amount.checked_mul(*size)?.to_string())
+    } else {
+        Some(format!("{}{}", amount, unit))
+    }
+}
+
+/// The config value for `amount` of byte size `unit`, which Ghostty takes in bytes.
+pub fn format_byte_size(amount: u64, unit: &str) -> Option<String> {
+    let (_, size) = BYTE_UNITS.iter().find(|(u, _)| *u == unit)?;
+    Some(amount.checked_mul(*size)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("750ms"), Some(750_000_000));
+        assert_eq!(parse_duration("5s"), Some(5_000_000_000));
+        assert_eq!(parse_duration("1h30m"), parse_duration("1h 30m"));
+        assert_eq!(parse_duration("1h30m"), Some(5_400_000_000_000));
+        assert_eq!(parse_duration("10µs"), Some(10_000));
+        assert_eq!(parse_duration("500"), None);
+        assert_eq!(parse_duration("5 parsecs"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn test_split_and_format_duration() {
+        assert_eq!(split_duration("750ms"), Some((750, "ms")));
+        assert_eq!(split_duration("90s"), Some((90, "s")));
+        assert_eq!(split_duration("2h"), Some((2, "h")));
+        assert_eq!(split_duration("300000"), Some((5, "m")));
+        assert_eq!(split_duration("0"), Some((0, "ms")));
+        assert_eq!(format_duration(5, "s", false), Some("5s".to_string()));
+        assert_eq!(format_duration(5, "s", true), Some("5000".to_string()));
+        assert_eq!(format_duration(5, "fortnights", false), None);
+        assert!(is_plain_millis("0"));
+        assert!(!is_plain_millis("5s"));
+    }
+
+    #[test]
+    fn test_byte_size() {
+        assert_eq!(split_byte_size("10000000"), Some((10, "MB")));
+        assert_eq!(split_byte_size("320000000"), Some((320, "MB")));
+        assert_eq!(split_byte_size("1500"), Some((1500, "B")));
+        assert_eq!(split_byte_size("lots"), None);
+        assert_eq!(format_byte_size(25, "MB"), Some("25000000".to_string()));
+        assert_eq!(format_byte_size(1, "TB"), None);
+    }
+}
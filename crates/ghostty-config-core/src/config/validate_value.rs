@@ -0,0 +1,228 @@
+use super::adjust_metric::Adjustment;
+use super::color_names::{resolve_color, COLOR_KEYWORDS};
+use super::model::{ConfigOption, ConfigValueType};
+use super::palette::parse_palette_entry;
+use super::units::duration_millis;
+
+/// Numeric options with a documented range, inclusive.
+const RANGES: &[(&str, f64, f64)] = &[
+    ("background-opacity", 0.0, 1.0),
+    ("unfocused-split-opacity", 0.15, 1.0),
+    ("minimum-contrast", 1.0, 21.0),
+];
+
+/// Check `value` against what `option` accepts, before it goes into the config.
+///
+/// Types this can't check, such as free text and keybinds, always pass; Ghostty's
+/// own validation still covers those.
+pub fn validate_value(option: &ConfigOption, value: &str) -> Result<(), String> {
+    check(&option.key, &option.value_type, value.trim())
+}
+
+fn check(key: &str, value_type: &ConfigValueType, value: &str) -> Result<(), String> {
+    match value_type {
+        ConfigValueType::Boolean => match value {
+            "true" | "false" => Ok(()),
+            _ => Err(format!("{} must be true or false", key)),
+        },
+        ConfigValueType::Integer => {
+            let n: i64 = value
+                .parse()
+                .map_err(|_| format!("{} must be a whole number", key))?;
+            // Ghostty's integer options are all unsigned.
+            if n < 0 {
+                return Err(format!("{} can't be negative", key));
+            }
+            check_range(key, n as f64)
+        }
+        ConfigValueType::Float => {
+            let n: f64 = value
+                .parse()
+                .ok()
+                .filter(|n: &f64| n.is_finite())
+                .ok_or_else(|| format!("{} must be a number", key))?;
+            check_range(key, n)
+        }
+        ConfigValueType::Percent => {
+            let n: f64 = value
+                .parse()
+                .ok()
+                .filter(|n: &f64| (0.0..=1.0).contains(n))
+                .ok_or_else(|| format!("{} must be a number from 0 to 1", key))?;
+            check_range(key, n)
+        }
+        ConfigValueType::AdjustMetric => match Adjustment::parse(value) {
+            Some(_) => Ok(()),
+            None => Err(format!(
+                "{} must be whole pixels like -1 or a percentage like 10%",
+                key
+            )),
+        },
+        ConfigValueType::Duration => match duration_millis(value) {
+            Some(_) => Ok(()),
+            None => Err(format!("{} must be a duration like 500ms or 1h30m", key)),
+        },
+        ConfigValueType::ByteSize => match value.parse::<u64>() {
+            Ok(_) => Ok(()),
+            Err(_) => Err(format!("{} must be a number of bytes", key)),
+        },
+        ConfigValueType::Color => {
+            if resolve_color(value).is_some() || COLOR_KEYWORDS.contains(&value) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{} must be a hex color like #1d1f21 or #1d1f21cc, or an X11 color name",
+                    key
+                ))
+            }
+        }
+        ConfigValueType::Enum(values) => {
+            if values.iter().any(|v| v.value == value) {
+                Ok(())
+            } else {
+                let names: Vec<&str> = values.iter().map(|v| v.value.as_str()).collect();
+                Err(format!("{} must be one of: {}", key, names.join(", ")))
+            }
+        }
+        // Any text is a path. Ones that don't exist are only warned about, with
+        // `paths::path_warning`, since the file may be created later.
+        ConfigValueType::Path => Ok(()),
+        ConfigValueType::Palette => match parse_palette_entry(value) {
+            Some((_, color)) if resolve_color(&color).is_some() => Ok(()),
+            _ => Err(format!(
+                "{} must look like 4=#81a2be, with an index below 256",
+                key
+            )),
+        },
+        ConfigValueType::CommaSeparated(item_type) => value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .try_for_each(|item| check(key, item_type, item)),
+        ConfigValueType::Text | ConfigValueType::Font | ConfigValueType::Keybind => Ok(()),
+    }
+}
+
+/// The documented minimum and maximum of a numeric option, if it has them.
+pub fn value_range(key: &str) -> Option<(f64, f64)> {
+    RANGES
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, min, max)| (*min, *max))
+}
+
+fn check_range(key: &str, n: f64) -> Result<(), String> {
+    match value_range(key) {
+        Some((min, max)) if n < min || n > max => {
+            Err(format!("{} must be between {} and {}", key, min, max))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::{Category, EnumVariant};
+
+    fn option(key: &str, value_type: ConfigValueType) -> ConfigOption {
+        ConfigOption {
+            key: key.to_string(),
+            default_value: String::new(),
+            documentation: String::new(),
+            value_type,
+            category: Category::Fonts,
+            is_repeatable: false,
+            conditions: Default::default(),
+        }
+    }
+
+    fn validate(key: &str, value_type: ConfigValueType, value: &str) -> Result<(), String> {
+        validate_value(&option(key, value_type), value)
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert!(validate("scrollback-limit", ConfigValueType::Integer, "10000").is_ok());
+        assert!(validate("scrollback-limit", ConfigValueType::Integer, "abc").is_err());
+        assert!(validate("scrollback-limit", ConfigValueType::Integer, "-1").is_err());
+        assert!(validate("font-size", ConfigValueType::Float, "13.5").is_ok());
+        assert!(validate("font-size", ConfigValueType::Float, "abc").is_err());
+        assert!(validate("font-size", ConfigValueType::Float, "NaN").is_err());
+        assert!(validate("background-opacity", ConfigValueType::Percent, "0.9").is_ok());
+        assert!(validate("faint-opacity", ConfigValueType::Percent, "1.2").is_err());
+        assert!(validate("unfocused-split-opacity", ConfigValueType::Percent, "0.1").is_err());
+        assert_eq!(
+            validate("background-opacity", ConfigValueType::Float, "1.5"),
+            Err("background-opacity must be between 0 and 1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_durations_and_sizes() {
+        assert!(validate("undo-timeout", ConfigValueType::Duration, "5s").is_ok());
+        assert!(validate("click-repeat-interval", ConfigValueType::Duration, "500").is_ok());
+        assert!(validate("undo-timeout", ConfigValueType::Duration, "soon").is_err());
+        assert!(validate("scrollback-limit", ConfigValueType::ByteSize, "10000000").is_ok());
+        assert!(validate("scrollback-limit", ConfigValueType::ByteSize, "10MB").is_err());
+    }
+
+    #[test]
+    fn test_adjust_metrics() {
+        assert!(validate("adjust-cell-height", ConfigValueType::AdjustMetric, "-2").is_ok());
+        assert!(validate("adjust-cell-height", ConfigValueType::AdjustMetric, "10%").is_ok());
+        assert!(validate("adjust-cell-height", ConfigValueType::AdjustMetric, "2em").is_err());
+    }
+
+    #[test]
+    fn test_booleans_and_enums() {
+        assert!(validate("bold-is-bright", ConfigValueType::Boolean, "true").is_ok());
+        assert!(validate("bold-is-bright", ConfigValueType::Boolean, "yes").is_err());
+        let variant = |value: &str| EnumVariant {
+            value: value.to_string(),
+            doc: String::new(),
+        };
+        let cursor = ConfigValueType::Enum(vec![variant("block"), variant("bar")]);
+        assert!(validate("cursor-style", cursor.clone(), "bar").is_ok());
+        assert_eq!(
+            validate("cursor-style", cursor, "underscore"),
+            Err("cursor-style must be one of: block, bar".to_string())
+        );
+    }
+
+    #[test]
+    fn test_colors() {
+        for ok in [
+            "#1d1f21",
+            "1d1f21",
+            "#fff",
+            "light steel blue",
+            "cell-foreground",
+        ] {
+            assert!(
+                validate("background", ConfigValueType::Color, ok).is_ok(),
+                "{}",
+                ok
+            );
+        }
+        for bad in ["#12", "#zzzzzz", "123456789", "bad!", "notacolor"] {
+            assert!(
+                validate("background", ConfigValueType::Color, bad).is_err(),
+                "{}",
+                bad
+            );
+        }
+        assert!(validate("palette", ConfigValueType::Palette, "4=#81a2be").is_ok());
+        assert!(validate("palette", ConfigValueType::Palette, "256=#81a2be").is_err());
+        assert!(validate("palette", ConfigValueType::Palette, "4=#81").is_err());
+        assert!(validate("palette", ConfigValueType::Palette, "4=navy").is_ok());
+        assert!(validate("palette", ConfigValueType::Palette, "4=cell-foreground").is_err());
+    }
+
+    #[test]
+    fn test_comma_separated() {
+        let styles = ConfigValueType::CommaSeparated(Box::new(ConfigValueType::Boolean));
+        assert!(validate("x", styles.clone(), "true, false").is_ok());
+        assert!(validate("x", styles, "true, maybe").is_err());
+    }
+}
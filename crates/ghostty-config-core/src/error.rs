@@ -0,0 +1,207 @@
+use std::fmt;
+use std::time::Duration;
+
+/// Errors from running the Ghostty CLI or reading and editing config files.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The `ghostty` binary is missing, failed, or printed something unexpected.
+    #[error("CLI error: {0}")]
+    Cli(CliError),
+
+    /// A config file, theme, or value was rejected.
+    #[error("Config error: {0}")]
+    Config(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Internal(#[from] anyhow::Error),
+}
+
+/// What went wrong with an external command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CliErrorKind {
+    /// The program isn't installed where it was looked for.
+    NotFound,
+    /// The program couldn't be started.
+    Spawn,
+    /// The program was killed after running longer than allowed.
+    Timeout,
+    /// The program exited unsuccessfully.
+    Failed,
+    /// The program ran, but its output couldn't be understood.
+    Output,
+}
+
+/// A failed external command, with what is known about the run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliError {
+    pub kind: CliErrorKind,
+    pub message: String,
+    /// The command line, e.g. `ghostty +validate-config`, when one was run.
+    pub command: Option<String>,
+    /// The exit code of a command that failed.
+    pub exit_code: Option<i32>,
+    /// What the command wrote to stderr, trimmed.
+    pub stderr: String,
+}
+
+impl CliError {
+    pub fn new(kind: CliErrorKind, message: impl Into<String>) -> Self {
+        CliError {
+            kind,
+            message: message.into(),
+            command: None,
+            exit_code: None,
+            stderr: String::new(),
+        }
+    }
+
+    /// A command that ran longer than `timeout`.
+    pub fn timeout(command: String, timeout: Duration) -> Self {
+        CliError {
+            command: Some(command.clone()),
+            ..CliError::new(
+                CliErrorKind::Timeout,
+                format!(
+                    "`{}` did not finish within {}s",
+                    command,
+                    timeout.as_secs_f32()
+                ),
+            )
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<CliError> for Error {
+    fn from(e: CliError) -> Self {
+        Error::Cli(e)
+    }
+}
+
+/// What a web frontend needs to describe an [`Error`] to the user.
+///
+/// The `axum` response for an error carries one in its extensions, so middleware
+/// can render the error as HTML or JSON instead of the plain-text body.
+#[cfg(feature = "axum")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorReport {
+    pub status: axum::http::StatusCode,
+    /// A stable, kebab-case name for the kind of error, e.g. `cli-timeout`.
+    pub kind: &'static str,
+    /// A short summary that doesn't change between occurrences.
+    pub title: &'static str,
+    pub detail: String,
+    pub command: Option<String>,
+    pub exit_code: Option<i32>,
+    pub stderr: String,
+}
+
+#[cfg(feature = "axum")]
+impl ErrorReport {
+    pub fn new(error: &Error) -> Self {
+        use axum::http::StatusCode;
+
+        let (status, kind, title) = match error {
+            Error::Cli(e) => match e.kind {
+                CliErrorKind::NotFound => (
+                    StatusCode::BAD_GATEWAY,
+                    "cli-not-found",
+                    "Ghostty not found",
+                ),
+                CliErrorKind::Spawn => (
+                    StatusCode::BAD_GATEWAY,
+                    "cli-spawn",
+                    "Ghostty could not be started",
+                ),
+                CliErrorKind::Timeout => (
+                    StatusCode::GATEWAY_TIMEOUT,
+                    "cli-timeout",
+                    "Ghostty took too long",
+                ),
+                CliErrorKind::Failed => (
+                    StatusCode::BAD_GATEWAY,
+                    "cli-failed",
+                    "Ghostty command failed",
+                ),
+                CliErrorKind::Output => (
+                    StatusCode::BAD_GATEWAY,
+                    "cli-output",
+                    "Unexpected output from Ghostty",
+                ),
+            },
+            Error::Config(_) => (StatusCode::BAD_REQUEST, "config", "Invalid config"),
+            Error::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "io", "File error"),
+            Error::Internal(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal",
+                "Internal error",
+            ),
+        };
+        let (detail, command, exit_code, stderr) = match error {
+            Error::Cli(e) => (
+                e.message.clone(),
+                e.command.clone(),
+                e.exit_code,
+                e.stderr.clone(),
+            ),
+            Error::Config(msg) => (msg.clone(), None, None, String::new()),
+            Error::Io(e) => (e.to_string(), None, None, String::new()),
+            Error::Internal(e) => (e.to_string(), None, None, String::new()),
+        };
+        ErrorReport {
+            status,
+            kind,
+            title,
+            detail,
+            command,
+            exit_code,
+            stderr,
+        }
+    }
+}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for Error {
+    fn into_response(self) -> axum::response::Response {
+        let report = ErrorReport::new(&self);
+        tracing::error!(status = %report.status, message = %report.detail, "request error");
+        let mut response = (report.status, report.detail.clone()).into_response();
+        response.extensions_mut().insert(report);
+        response
+    }
+}
+
+#[cfg(all(test, feature = "axum"))]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    #[test]
+    fn test_response_carries_report() {
+        let error = Error::from(CliError::timeout(
+            "ghostty +list-fonts".to_string(),
+            Duration::from_secs(5),
+        ));
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        let report = response.extensions().get::<ErrorReport>().unwrap();
+        assert_eq!(report.kind, "cli-timeout");
+        assert_eq!(report.command.as_deref(), Some("ghostty +list-fonts"));
+    }
+
+    #[test]
+    fn test_config_error_is_bad_request() {
+        let report = ErrorReport::new(&Error::Config("bad value".to_string()));
+        assert_eq!(report.status, StatusCode::BAD_REQUEST);
+        assert_eq!(report.detail, "bad value");
+    }
+}
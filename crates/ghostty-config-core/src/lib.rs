@@ -0,0 +1,59 @@
+//! Config parsing and Ghostty integration shared by the web UI and other tools.
+//!
+//! The crate has no web dependencies unless the `axum` feature is enabled, which
+//! only adds an `IntoResponse` impl for [`Error`] and the `ErrorReport` its
+//! responses carry for middleware that renders errors.
+//!
+//! - [`cli`] runs the `ghostty` binary to discover the config schema, themes,
+//!   fonts, keybinds, and actions, and to validate config files. Commands run on
+//!   tokio and are killed if they take longer than
+//!   [`command_timeout`](cli::discovery::command_timeout).
+//! - [`config`] reads and writes config files, infers option types, groups
+//!   options into categories, and diffs or merges configs.
+//!
+//! ```no_run
+//! use ghostty_config_core::cli::discovery::{find_ghostty, run_ghostty};
+//! use ghostty_config_core::config::file_io::{default_config_path, read_config};
+//! use ghostty_config_core::config::parser::parse_show_config;
+//!
+//! # async fn example() -> Result<(), ghostty_config_core::Error> {
+//! let ghostty = find_ghostty()?;
+//! let output = run_ghostty(&ghostty, &["+show-config", "--default", "--docs"]).await?;
+//! let schema = parse_show_config(&output)?;
+//! let config = read_config(&default_config_path())?;
+//! for option in schema.options() {
+//!     if let Some(value) = config.get(&option.key) {
+//!         println!("{} = {}", option.key, value);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Edits keep the file's comments, blank lines, and key order, so a tool can change
+//! one setting without rewriting the rest:
+//!
+//! ```
+//! use std::path::Path;
+//!
+//! use ghostty_config_core::config::file_io::{parse_config, render_config};
+//!
+//! let text = "# Fonts\nfont-size = 12\n\ntheme = Nord\n";
+//! let mut config = parse_config(text, Path::new("config"));
+//! config.set("font-size", "14");
+//! config.remove("theme");
+//! config.set("cursor-style", "bar");
+//! assert_eq!(
+//!     render_config(&config),
+//!     "# Fonts\nfont-size = 14\n\ncursor-style = bar\n"
+//! );
+//! // `write_config` saves the same text to `config.file_path`.
+//! ```
+
+pub mod cli;
+pub mod config;
+pub mod error;
+
+#[cfg(feature = "axum")]
+pub use error::ErrorReport;
+pub use error::{CliError, CliErrorKind, Error};
@@ -1,35 +1,583 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, Notify, RwLock};
 
-use crate::cli::keybinds::Keybinding;
-use crate::config::model::{ConfigSchema, FontFamily, ThemeInfo, UserConfig};
+use crate::autosave::AutosaveMode;
+use crate::browser::BrowserLauncher;
+use crate::config::baseline::Baseline;
+use crate::config::categorize::categorize_key;
+use crate::config::corruption::{detect_corruption, CorruptionProblem};
+use crate::config::diff::{self, diff_configs};
+use crate::config::file_io::{
+    parse_config, read_config, read_config_text, render_config, write_atomic,
+};
+use crate::config::font_variation::FontAxis;
+use crate::config::includes::load_included;
+use crate::config::includes::{ensure_included, resolve_include};
+use crate::config::model::{ThemeInfo, UserConfig};
+use crate::config::os_shortcuts::Platform;
+use crate::error::AppError;
+use crate::events::AppEvent;
+use crate::ghostty::{Ghostty, Installation};
+use crate::history::{ChangeKind, History};
+use crate::live_validation::ValidationStatus;
+use crate::remote::RemoteTarget;
+use crate::revision::{self, Revisions};
+use crate::session::{self, session_path, PendingSession};
+use crate::settings::Settings;
+use crate::theme_state::ThemeState;
+use crate::updates::Update;
 
 pub struct AppState {
-    pub schema: ConfigSchema,
+    /// The active Ghostty installation and its schema, fonts, actions, and default
+    /// keybinds; see [`AppState::ghostty`].
+    pub ghostty: RwLock<Arc<Ghostty>>,
+    /// Installations found on this machine, looked up on first use; see `/api/ghostty`.
+    pub installations: RwLock<Option<Vec<Installation>>>,
+    /// Whether `POST /api/refresh` is rerunning discovery in the background.
+    pub refreshing: AtomicBool,
     pub user_config: RwLock<UserConfig>,
-    pub themes: Vec<ThemeInfo>,
-    pub fonts: Vec<FontFamily>,
-    pub actions: Vec<String>,
-    pub default_keybinds: Vec<Keybinding>,
-    pub ghostty_path: PathBuf,
-    /// Set of keys with unsaved changes.
-    pub unsaved: RwLock<HashSet<String>>,
+    /// Files pulled in with `config-file`, as loaded or edited this session.
+    pub included: RwLock<Vec<UserConfig>>,
+    /// The config as last read from or written to disk; base for three-way merges.
+    pub base_config: RwLock<UserConfig>,
+    /// Installed themes, plus custom themes created while running.
+    pub themes: RwLock<Vec<ThemeInfo>>,
+    /// Variable-font axes by family, looked up on first use; see `font_variation_api`.
+    pub font_axes: RwLock<HashMap<String, Option<Vec<FontAxis>>>>,
+    pub browser: BrowserLauncher,
+    /// Keys whose values in memory differ from the files on disk, with both; see
+    /// [`AppState::mark_unsaved`].
+    pub unsaved: RwLock<Vec<diff::KeyChange>>,
+    /// Goes up with every change to the in-memory config, by an edit or a reload
+    /// from disk; see `crate::revision`.
+    pub revisions: std::sync::Mutex<Revisions>,
+    /// Held by each write that needs a revision, from the revision check until
+    /// it's done, and by reloads from disk, so that a write can't go ahead on a
+    /// revision another one is making out of date.
+    pub config_writes: tokio::sync::Mutex<()>,
+    /// Pushes state changes to open pages; see `/events` and `/ws`.
+    pub events: broadcast::Sender<AppEvent>,
+    /// Drop toast animations and the blinking preview cursor; see `/api/accessibility/reduced-motion`.
+    pub reduced_motion: AtomicBool,
+    /// Team baseline the config is compared against, if one was given; see `/baseline`.
+    pub baseline: RwLock<Option<Baseline>>,
+    /// This tool's own preferences, saved to `settings_path`.
+    pub settings: RwLock<Settings>,
+    pub settings_path: PathBuf,
+    /// Result of this session's update check, once one has run; see `/api/updates`.
+    pub updates: RwLock<Option<Vec<Update>>>,
+    /// Why the config file couldn't be loaded. While non-empty the UI only offers
+    /// the raw editor and backup restore, and refuses every other change.
+    pub safe_mode: RwLock<Vec<CorruptionProblem>>,
+    /// Latest background validation of the in-memory config; see `live_validation`.
+    pub validation: RwLock<Option<ValidationStatus>>,
+    /// Woken whenever the in-memory config changes, to schedule a revalidation.
+    pub revalidate: Notify,
+    /// Starred and recently applied themes, saved next to `settings_path`.
+    pub theme_state: RwLock<ThemeState>,
+    /// Journal of changes to the config; see `/history`.
+    pub history: RwLock<History>,
+    /// Edits left unsaved when the server last stopped, until restored or
+    /// discarded; see `/api/session`.
+    pub pending_session: RwLock<Option<PendingSession>>,
+    /// Whether `--live-preview` enabled the PTY-backed preview; see `/ws/preview`.
+    pub live_preview: bool,
+    /// Woken by `POST /api/shutdown` to stop the server gracefully.
+    pub shutdown: Notify,
+    /// Required on changing requests; `None` with `--no-auth`. See `auth`.
+    pub auth_token: Option<String>,
+    /// Whether `--bind` lets other machines reach the server. Every request then
+    /// needs the token, reads included.
+    pub exposed: bool,
+    /// The `--remote` config that `user_config`'s file is a working copy of.
+    pub remote: Option<RemoteTarget>,
+    /// The platform of the machine whose config is edited: this one, or the
+    /// `--remote` one. Options for other platforms are hidden or greyed out.
+    pub platform: Platform,
+    /// Whether edits are written as they settle; see `/api/autosave`.
+    pub autosave: RwLock<AutosaveMode>,
+    /// Woken on every edit, to schedule an autosave.
+    pub autosave_due: Notify,
 }
 
 impl AppState {
-    pub async fn mark_unsaved(&self, key: &str) {
-        self.unsaved.write().await.insert(key.to_string());
+    /// The active Ghostty. Hold on to it for the whole request, so switching
+    /// installations midway doesn't mix two schemas.
+    pub async fn ghostty(&self) -> Arc<Ghostty> {
+        self.ghostty.read().await.clone()
+    }
+
+    /// Whether options for `platforms` (empty for every platform) are listed:
+    /// those for `self.platform` always are, others only with "Show all platforms".
+    pub async fn shows_platforms(&self, platforms: &[Platform]) -> bool {
+        platforms.is_empty()
+            || platforms.contains(&self.platform)
+            || self.settings.read().await.show_all_platforms
+    }
+
+    /// Send an event to every subscribed page. Having no subscribers is fine.
+    ///
+    /// Events that mean the in-memory config or the validator changed also
+    /// schedule a revalidation, and edits an autosave.
+    pub fn publish(&self, event: AppEvent) {
+        if matches!(event, AppEvent::ConfigChanged { .. }) {
+            self.autosave_due.notify_one();
+        }
+        if matches!(
+            event,
+            AppEvent::ConfigChanged { .. }
+                | AppEvent::ConfigReloaded
+                | AppEvent::GhosttyChanged { .. }
+        ) {
+            self.revalidate.notify_one();
+        }
+        let _ = self.events.send(event);
+    }
+
+    /// Record in-memory edits and tell open pages about each key whose pending
+    /// value changed. The unsaved changes are worked out again against the files on
+    /// disk, so an edit that puts a key back the way it was saved leaves nothing
+    /// unsaved.
+    pub async fn mark_unsaved(&self) {
+        self.bump_revision();
+        self.record_changes().await;
+        self.persist_session().await;
+        let changes = self.changes_since_saved().await;
+        let mut unsaved = self.unsaved.write().await;
+        let edited = edited_keys(&unsaved, &changes);
+        *unsaved = changes;
+        for key in edited {
+            self.publish(AppEvent::ConfigChanged {
+                key,
+                unsaved: unsaved.len(),
+            });
+        }
+    }
+
+    /// The in-memory config's current revision.
+    pub fn revision(&self) -> u64 {
+        self.revisions.lock().unwrap().current()
+    }
+
+    /// Note a change to the in-memory config, made by the page behind the request
+    /// being handled if any, so that writes from other pages showing the config as
+    /// it was before are refused.
+    pub fn bump_revision(&self) {
+        self.revisions
+            .lock()
+            .unwrap()
+            .bump(revision::current_client());
+    }
+
+    /// Whether anyone but `client` changed the in-memory config since `revision`.
+    pub fn changed_since(&self, revision: u64, client: Option<&str>) -> bool {
+        self.revisions
+            .lock()
+            .unwrap()
+            .changed_since(revision, client)
+    }
+
+    /// How the in-memory config and its includes differ from the files as last
+    /// read from or written to disk.
+    async fn changes_since_saved(&self) -> Vec<diff::KeyChange> {
+        let base = self.base_config.read().await;
+        let base_included = load_included(&base);
+        let saved: Vec<&UserConfig> = std::iter::once(&*base)
+            .chain(base_included.iter())
+            .collect();
+        let user_config = self.user_config.read().await;
+        let included = self.included.read().await;
+        let current: Vec<&UserConfig> = std::iter::once(&*user_config)
+            .chain(included.iter())
+            .collect();
+        diff_configs(&saved, &current)
+    }
+
+    /// Journal every key that changed since the last recorded change.
+    pub async fn record_changes(&self) {
+        let user_config = self.user_config.read().await;
+        let included = self.included.read().await;
+        let configs: Vec<&UserConfig> = std::iter::once(&*user_config)
+            .chain(included.iter())
+            .collect();
+        if let Err(e) = self.history.write().await.record(&configs) {
+            tracing::warn!("Failed to record config history: {}", e);
+        }
+    }
+
+    /// Journal a save or apply, after any edits it wrote that weren't recorded yet.
+    pub async fn record_action(&self, kind: ChangeKind) {
+        self.record_changes().await;
+        if let Err(e) = self.history.write().await.record_action(kind) {
+            tracing::warn!("Failed to record config history: {}", e);
+        }
+    }
+
+    /// Take the config as it is now as the journal's starting point, e.g. after it
+    /// was reloaded because someone else changed the file.
+    pub async fn resync_history(&self) {
+        let user_config = self.user_config.read().await;
+        let included = self.included.read().await;
+        let configs: Vec<&UserConfig> = std::iter::once(&*user_config)
+            .chain(included.iter())
+            .collect();
+        self.history.write().await.resync(&configs);
+    }
+
+    /// Keep the unsaved edits on disk, so they can be restored if the server stops
+    /// before they are saved.
+    pub async fn persist_session(&self) {
+        let base = self.base_config.read().await;
+        let user_config = self.user_config.read().await;
+        let included = self.included.read().await;
+        let base_included = load_included(&base);
+        let saved: Vec<&UserConfig> = std::iter::once(&*base)
+            .chain(base_included.iter())
+            .collect();
+        let current: Vec<&UserConfig> = std::iter::once(&*user_config)
+            .chain(included.iter())
+            .collect();
+        let mut pending = PendingSession::between(user_config.file_path.clone(), &saved, &current);
+        // Keep a previous run's edits that haven't been restored or discarded yet.
+        if let Some(previous) = self.pending_session.read().await.as_ref() {
+            for (key, values) in &previous.edits {
+                pending
+                    .edits
+                    .entry(key.clone())
+                    .or_insert_with(|| values.clone());
+            }
+        }
+        if let Err(e) = pending.save(&session_path(&self.settings_path)) {
+            tracing::warn!("Failed to keep unsaved edits: {}", e);
+        }
+    }
+
+    /// Give `key` exactly `values`, removing it everywhere first and adding them to
+    /// its category's save target. No values removes it.
+    pub async fn replace_values(&self, key: &str, values: &[String]) -> Result<(), AppError> {
+        self.remove_key(key).await;
+        if !values.is_empty() {
+            self.add_entry(key, |config| {
+                for value in values {
+                    config.push(key, value);
+                }
+            })
+            .await?;
+        }
+        Ok(())
     }
 
     pub async fn clear_unsaved(&self) {
+        if self.pending_session.read().await.is_none() {
+            if let Err(e) = session::remove(&session_path(&self.settings_path)) {
+                tracing::warn!("Failed to forget unsaved edits: {}", e);
+            }
+        }
         self.unsaved.write().await.clear();
+        self.publish(AppEvent::UnsavedCount { unsaved: 0 });
     }
 
     pub async fn unsaved_count(&self) -> usize {
         self.unsaved.read().await.len()
     }
+
+    /// The keys with unsaved changes, for marking their fields.
+    pub async fn unsaved_keys(&self) -> HashSet<String> {
+        self.unsaved
+            .read()
+            .await
+            .iter()
+            .map(|change| change.key.clone())
+            .collect()
+    }
+
+    /// The value of `key`, falling back to included files when the main config doesn't set it.
+    pub async fn value_of(&self, key: &str) -> Option<String> {
+        if let Some(value) = self.user_config.read().await.get(key) {
+            return Some(value.to_string());
+        }
+        self.included
+            .read()
+            .await
+            .iter()
+            .rev()
+            .find_map(|c| c.get(key).map(String::from))
+    }
+
+    /// Every value of `key`, from the main config and then each included file.
+    pub async fn all_values(&self, key: &str) -> Vec<String> {
+        let user_config = self.user_config.read().await;
+        let included = self.included.read().await;
+        std::iter::once(&*user_config)
+            .chain(included.iter())
+            .flat_map(|c| c.get_all(key))
+            .map(String::from)
+            .collect()
+    }
+
+    /// Change `key` in the file that already sets it. A key set nowhere yet goes to
+    /// its category's save target, if one is configured, or else the main config.
+    pub async fn edit_key(
+        &self,
+        key: &str,
+        edit: impl FnOnce(&mut UserConfig),
+    ) -> Result<(), AppError> {
+        self.edit_in_target(key, true, edit).await
+    }
+
+    /// Add a new entry for a repeatable `key` (like `keybind`) to its category's save
+    /// target, or the main config when there is none.
+    pub async fn add_entry(
+        &self,
+        key: &str,
+        edit: impl FnOnce(&mut UserConfig),
+    ) -> Result<(), AppError> {
+        self.edit_in_target(key, false, edit).await
+    }
+
+    /// Apply `edit` to the file holding value `index` of a repeatable `key`, counting
+    /// values in the same order as `all_values`. `edit` gets the value's index within
+    /// that file. Returns false when there is no such value.
+    pub async fn edit_entry(
+        &self,
+        key: &str,
+        index: usize,
+        edit: impl FnOnce(&mut UserConfig, usize) -> bool,
+    ) -> bool {
+        let mut user_config = self.user_config.write().await;
+        let mut included = self.included.write().await;
+        let mut index = index;
+        for config in std::iter::once(&mut *user_config).chain(included.iter_mut()) {
+            let count = config.get_all(key).len();
+            if index < count {
+                return edit(config, index);
+            }
+            index -= count;
+        }
+        false
+    }
+
+    /// Remove `key` from the main config and every included file.
+    pub async fn remove_key(&self, key: &str) {
+        self.user_config.write().await.remove(key);
+        for config in self.included.write().await.iter_mut() {
+            config.remove(key);
+        }
+    }
+
+    /// Apply every change in `changes` under one write lock, or none of them if one
+    /// fails. `None` removes the key; values replace the key's values where
+    /// `edit_key` would put them. Returns whether a `config-file` line was added
+    /// to reach a save target.
+    pub async fn edit_keys(&self, changes: &[KeyChange]) -> Result<bool, AppError> {
+        let save_targets = self.settings.read().await.save_targets.clone();
+        let mut user_config = self.user_config.write().await;
+        let mut included = self.included.write().await;
+
+        let mut new_user_config = user_config.clone();
+        let mut new_included = included.clone();
+        let mut added = false;
+        for (key, value) in changes {
+            match value {
+                None => {
+                    new_user_config.remove(key);
+                    for config in new_included.iter_mut() {
+                        config.remove(key);
+                    }
+                }
+                Some(values) => {
+                    let target = save_targets.get(categorize_key(key).slug()).cloned();
+                    added |= edit_in(
+                        &mut new_user_config,
+                        &mut new_included,
+                        target,
+                        key,
+                        true,
+                        |config| config.set_all(key, values),
+                    )?;
+                }
+            }
+        }
+        *user_config = new_user_config;
+        *included = new_included;
+        Ok(added)
+    }
+
+    async fn edit_in_target(
+        &self,
+        key: &str,
+        reuse_existing: bool,
+        edit: impl FnOnce(&mut UserConfig),
+    ) -> Result<(), AppError> {
+        let target = self
+            .settings
+            .read()
+            .await
+            .save_targets
+            .get(categorize_key(key).slug())
+            .cloned();
+        let mut user_config = self.user_config.write().await;
+        let mut included = self.included.write().await;
+        edit_in(
+            &mut user_config,
+            &mut included,
+            target,
+            key,
+            reuse_existing,
+            edit,
+        )?;
+        Ok(())
+    }
+
+    pub async fn in_safe_mode(&self) -> bool {
+        !self.safe_mode.read().await.is_empty()
+    }
+
+    /// Leave safe mode if the config file on disk is intact again, loading it.
+    ///
+    /// Returns true if the file was loaded.
+    pub async fn reload_repaired(&self) -> Result<bool, AppError> {
+        let _writing = self.config_writes.lock().await;
+        let path = self.user_config.read().await.file_path.clone();
+        let bytes = std::fs::read(&path)?;
+        let problems = detect_corruption(&bytes);
+        if !problems.is_empty() {
+            *self.safe_mode.write().await = problems;
+            return Ok(false);
+        }
+
+        let config = parse_config(&String::from_utf8_lossy(&bytes), &path);
+        *self.included.write().await = load_included(&config);
+        *self.base_config.write().await = config.clone();
+        *self.user_config.write().await = config;
+        self.bump_revision();
+        self.resync_history().await;
+        self.clear_unsaved().await;
+        self.safe_mode.write().await.clear();
+        self.publish(AppEvent::ConfigReloaded);
+        Ok(true)
+    }
+
+    /// Re-read the config file and return it if it was changed by someone else
+    /// since this process last read or wrote it.
+    ///
+    /// With `--remote` the working copy is refreshed first, so edits made on the
+    /// remote machine are merged like local ones.
+    pub async fn external_changes(&self) -> Result<Option<UserConfig>, AppError> {
+        let base = self.base_config.read().await;
+        if let Some(remote) = &self.remote {
+            let text = remote.fetch().await?;
+            if read_config_text(&base.file_path)? != text {
+                write_atomic(&base.file_path, &text)?;
+            }
+        }
+        let on_disk = read_config(&base.file_path)?;
+        if render_config(&on_disk) == render_config(&base) {
+            Ok(None)
+        } else {
+            Ok(Some(on_disk))
+        }
+    }
 }
 
 pub type SharedState = Arc<AppState>;
+
+/// A key and the values `edit_keys` gives it; `None` removes it.
+pub type KeyChange = (String, Option<Vec<String>>);
+
+/// The keys whose pending values differ between two sets of unsaved changes: a
+/// key edited again, newly edited, or put back the way it was saved.
+fn edited_keys(before: &[diff::KeyChange], after: &[diff::KeyChange]) -> Vec<String> {
+    let pending = |changes: &[diff::KeyChange], key: &str| {
+        changes
+            .iter()
+            .find(|change| change.key == key)
+            .map(|change| change.new.clone())
+    };
+    let mut keys: Vec<String> = Vec::new();
+    for change in before.iter().chain(after) {
+        if !keys.contains(&change.key)
+            && pending(before, &change.key) != pending(after, &change.key)
+        {
+            keys.push(change.key.clone());
+        }
+    }
+    keys
+}
+
+/// Apply `edit` to the file `edit_in_target` picks for `key`, with `target` being
+/// the save target of its category. Returns whether the main config gained a
+/// `config-file` line for the target.
+fn edit_in(
+    user_config: &mut UserConfig,
+    included: &mut Vec<UserConfig>,
+    target: Option<String>,
+    key: &str,
+    reuse_existing: bool,
+    edit: impl FnOnce(&mut UserConfig),
+) -> Result<bool, AppError> {
+    if reuse_existing {
+        if user_config.get(key).is_some() {
+            edit(user_config);
+            return Ok(false);
+        }
+        if let Some(config) = included.iter_mut().rev().find(|c| c.get(key).is_some()) {
+            edit(config);
+            return Ok(false);
+        }
+    }
+
+    let path = target
+        .as_deref()
+        .map(|t| resolve_include(&user_config.file_path, t));
+    let (Some(target), Some(path)) = (target, path) else {
+        edit(user_config);
+        return Ok(false);
+    };
+    if path == user_config.file_path {
+        edit(user_config);
+        return Ok(false);
+    }
+
+    let index = match included.iter().position(|c| c.file_path == path) {
+        Some(index) => index,
+        None => {
+            included.push(read_config(&path)?);
+            included.len() - 1
+        }
+    };
+    edit(&mut included[index]);
+    Ok(ensure_included(user_config, &target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::diff::ChangeKind;
+
+    fn change(key: &str, new: &[&str]) -> diff::KeyChange {
+        diff::KeyChange {
+            key: key.to_string(),
+            kind: ChangeKind::Changed,
+            old: vec!["old".to_string()],
+            new: new.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_edited_keys() {
+        let before = [change("font-size", &["14"]), change("theme", &["Nord"])];
+        let after = [
+            change("font-size", &["15"]),
+            change("theme", &["Nord"]),
+            change("keybind", &["ctrl+a=select_all"]),
+        ];
+        assert_eq!(edited_keys(&before, &after), ["font-size", "keybind"]);
+        // Putting a key back the way it was saved drops it from the changes.
+        assert_eq!(edited_keys(&after, &after[1..]), ["font-size"]);
+        assert!(edited_keys(&after, &after).is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+use crate::cli::discovery::{find_ghostty, DEFAULT_COMMAND_TIMEOUT};
+use crate::config::file_io::default_config_path;
+use crate::error::AppError;
+use crate::remote::RemoteTarget;
+
+/// Port the UI is served on unless `--port` says otherwise.
+pub const DEFAULT_PORT: u16 = 3456;
+
+/// Web-based configuration GUI for Ghostty terminal.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Port to serve the UI on [default: 3456, or a free port if that one is taken].
+    #[arg(long)]
+    pub port: Option<u16>,
+
+    /// Address to listen on. Use `0.0.0.0` to reach the UI from another machine or
+    /// from outside a container; every request then needs the session token.
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1")]
+    pub bind: IpAddr,
+
+    /// Config file to edit instead of Ghostty's default location. With `--remote`,
+    /// a path on the remote machine.
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Edit the config on another machine over SSH: `user@host`, or
+    /// `ssh://user@host:port/path`. The schema and validation still use the
+    /// local Ghostty. Needs key or agent authentication, since saves can't prompt.
+    #[arg(long, value_name = "HOST")]
+    pub remote: Option<String>,
+
+    /// Ghostty binary to use instead of searching the usual install locations.
+    #[arg(long, value_name = "PATH")]
+    pub ghostty: Option<PathBuf>,
+
+    /// Ghostty version whose built-in schema to use when Ghostty can't be run,
+    /// e.g. before installing it [default: the newest built in].
+    #[arg(long, value_name = "VERSION")]
+    pub schema_version: Option<String>,
+
+    /// Don't open a browser on startup.
+    #[arg(long, alias = "no-browser")]
+    pub no_open: bool,
+
+    /// Browser command to open the UI with instead of the system default
+    /// (e.g. `firefox` or `google-chrome --profile-directory=Work`).
+    #[arg(long, value_name = "COMMAND")]
+    pub browser: Option<String>,
+
+    /// Page to open on startup, e.g. `/themes` or `/category/fonts`.
+    #[arg(long, value_name = "PATH", default_value = "/")]
+    pub open_page: String,
+
+    /// Team baseline config to compare against: a file path or an http(s) URL.
+    #[arg(long, value_name = "PATH_OR_URL")]
+    pub baseline: Option<String>,
+
+    /// Ask Ghostty for its options, fonts, and keybinds again instead of using the
+    /// cache from the last run with this Ghostty version, e.g. after installing fonts.
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Offer a live terminal preview that runs your `command` (or shell) in the
+    /// browser. Off by default, since it gives the UI a shell.
+    #[arg(long)]
+    pub live_preview: bool,
+
+    /// Accept changes from any local client, not just the browser opened with this
+    /// session's token.
+    #[arg(long)]
+    pub no_auth: bool,
+
+    /// Seconds to wait for a `ghostty` command (validation, discovery) before
+    /// giving up on it.
+    #[arg(long, value_name = "SECS", default_value_t = DEFAULT_COMMAND_TIMEOUT.as_secs())]
+    pub ghostty_timeout: u64,
+}
+
+impl Args {
+    /// The `--ghostty` binary, or the installed one.
+    pub fn ghostty_path(&self) -> anyhow::Result<PathBuf> {
+        match &self.ghostty {
+            Some(path) if path.is_file() => Ok(path.clone()),
+            Some(path) => anyhow::bail!("Ghostty binary not found at {}", path.display()),
+            None => Ok(find_ghostty()?),
+        }
+    }
+
+    /// The `--config` file, or Ghostty's default one.
+    pub fn config_path(&self) -> PathBuf {
+        self.config.clone().unwrap_or_else(default_config_path)
+    }
+
+    /// The `--remote` config, if one was given.
+    pub fn remote_target(&self) -> Result<Option<RemoteTarget>, AppError> {
+        let Some(spec) = &self.remote else {
+            return Ok(None);
+        };
+        let path = self.config.as_ref().map(|p| p.to_string_lossy());
+        RemoteTarget::parse(spec, path.as_deref()).map(Some)
+    }
+
+    /// Whether the UI can be reached from other machines.
+    pub fn exposed(&self) -> bool {
+        !self.bind.is_loopback()
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Time the schema, theme, and font parsers against recorded fixtures.
+    Bench(BenchArgs),
+    /// Print the value of a config key, or its default if it isn't set.
+    Get { key: String },
+    /// Set a config key and save the file. Repeatable keys like `keybind` take
+    /// several values, which replace the existing ones.
+    Set {
+        key: String,
+        #[arg(required = true, num_args = 1..)]
+        values: Vec<String>,
+    },
+    /// Remove a config key from the file, restoring its default.
+    Unset { key: String },
+    /// Check the config file with `ghostty +validate-config`.
+    Validate,
+    /// List config keys with their current values.
+    List {
+        /// Only keys in this category, e.g. `fonts` or `colors`.
+        #[arg(long, value_name = "SLUG")]
+        category: Option<String>,
+        /// Only keys set in the config file.
+        #[arg(long)]
+        modified: bool,
+    },
+}
+
+#[derive(Debug, clap::Args)]
+pub struct BenchArgs {
+    /// Directory holding `show-config.txt`, `list-fonts.txt`, and a `themes/` directory.
+    #[arg(long, value_name = "DIR", default_value = "fixtures")]
+    pub fixtures: PathBuf,
+
+    /// Number of timed runs per benchmark.
+    #[arg(long, default_value_t = 50)]
+    pub iterations: u32,
+
+    /// Capture fresh fixtures from the installed Ghostty before timing.
+    #[arg(long)]
+    pub record: bool,
+}
@@ -0,0 +1,172 @@
+//! The session token that keeps other local users and processes, and with a
+//! non-loopback `--bind` other machines, from changing the config through the
+//! server. The URL the browser is opened with carries it once;
+//! after that it lives in a cookie.
+
+use std::io;
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, Method, StatusCode, Uri};
+use axum::middleware::Next;
+use axum::response::{Html, IntoResponse, Redirect, Response};
+
+use crate::app_state::SharedState;
+use crate::routes::config_api::toast_html;
+
+/// Cookie holding the token once a page has been opened with it.
+pub const COOKIE: &str = "ghostty_config_token";
+/// Header for clients that aren't browsers, e.g. a second launch opening this one.
+pub const HEADER: &str = "x-auth-token";
+/// Query parameter carrying the token in the opened URL.
+pub const QUERY: &str = "token";
+
+/// A new random token: 32 hex digits from the system's random source.
+pub fn generate_token() -> io::Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(io::Error::other)?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Require the token on every request that changes something, and on the live
+/// preview's shell. Without `--no-auth` only the browser that was opened with the
+/// token can edit. When other machines can reach the server, every request needs it.
+pub async fn guard(State(state): State<SharedState>, request: Request, next: Next) -> Response {
+    let Some(token) = state.auth_token.as_deref() else {
+        return next.run(request).await;
+    };
+
+    // The opened link: keep the token in a cookie and drop it from the address bar.
+    if request.method() == Method::GET {
+        if let Some(given) = query_token(request.uri()) {
+            if tokens_match(&given, token) {
+                let cookie = format!("{}={}; Path=/; HttpOnly; SameSite=Strict", COOKIE, token);
+                let location = without_token(request.uri());
+                return ([(header::SET_COOKIE, cookie)], Redirect::to(&location)).into_response();
+            }
+        }
+    }
+
+    if !(state.exposed || needs_token(request.method(), request.uri().path()))
+        || presented_token(request.headers()).is_some_and(|given| tokens_match(given, token))
+    {
+        return next.run(request).await;
+    }
+    (
+        StatusCode::UNAUTHORIZED,
+        Html(toast_html(
+            "Not signed in: open the link ghostty-config printed or opened at startup",
+            true,
+        )),
+    )
+        .into_response()
+}
+
+/// Anything but a plain read, plus the sockets that run a shell and stream every
+/// change, and the file browser, which reads outside the config.
+fn needs_token(method: &Method, path: &str) -> bool {
+    !(method == Method::GET || method == Method::HEAD)
+        || path == "/ws"
+        || path == "/ws/preview"
+        || path.starts_with("/api/files")
+}
+
+fn presented_token(headers: &HeaderMap) -> Option<&str> {
+    if let Some(value) = headers.get(HEADER).and_then(|v| v.to_str().ok()) {
+        return Some(value);
+    }
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .find_map(|pair| {
+            let (name, value) = pair.trim().split_once('=')?;
+            (name == COOKIE).then_some(value)
+        })
+}
+
+fn query_token(uri: &Uri) -> Option<String> {
+    uri.query()?.split('&').find_map(|pair| {
+        let (name, value) = pair.split_once('=')?;
+        (name == QUERY).then(|| value.to_string())
+    })
+}
+
+/// `uri`'s path and query with the token parameter removed.
+fn without_token(uri: &Uri) -> String {
+    let rest: Vec<&str> = uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some(QUERY))
+        .collect();
+    if rest.is_empty() {
+        uri.path().to_string()
+    } else {
+        format!("{}?{}", uri.path(), rest.join("&"))
+    }
+}
+
+/// Compare without stopping at the first difference, so timing doesn't give the
+/// token away.
+fn tokens_match(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_token() {
+        let token = generate_token().unwrap();
+        assert_eq!(token.len(), 32);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, generate_token().unwrap());
+    }
+
+    #[test]
+    fn test_needs_token() {
+        assert!(needs_token(&Method::POST, "/api/save"));
+        assert!(needs_token(&Method::DELETE, "/api/themes/custom/x"));
+        assert!(needs_token(&Method::GET, "/api/files/thumbnail"));
+        assert!(needs_token(&Method::GET, "/ws"));
+        assert!(needs_token(&Method::GET, "/ws/preview"));
+        assert!(!needs_token(&Method::GET, "/api/themes"));
+    }
+
+    #[test]
+    fn test_presented_token() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(presented_token(&headers), None);
+        headers.insert(
+            header::COOKIE,
+            "theme=dark; ghostty_config_token=abc".parse().unwrap(),
+        );
+        assert_eq!(presented_token(&headers), Some("abc"));
+        headers.insert(HEADER, "def".parse().unwrap());
+        assert_eq!(presented_token(&headers), Some("def"));
+    }
+
+    #[test]
+    fn test_query_token() {
+        let uri: Uri = "/themes?view=recent&token=abc".parse().unwrap();
+        assert_eq!(query_token(&uri).as_deref(), Some("abc"));
+        assert_eq!(without_token(&uri), "/themes?view=recent");
+        let uri: Uri = "/?token=abc".parse().unwrap();
+        assert_eq!(without_token(&uri), "/");
+        assert_eq!(query_token(&"/".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc", "abc"));
+        assert!(!tokens_match("abd", "abc"));
+        assert!(!tokens_match("ab", "abc"));
+    }
+}
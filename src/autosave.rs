@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+use crate::app_state::SharedState;
+use crate::history::ChangeKind;
+use crate::routes::config_api::{
+    blocked_message, escape_html, publish_toast, trigger_ghostty_reload, validation_problems,
+    write_and_reload, RELOAD_SHORTCUT,
+};
+
+/// Wait this long after the last edit before writing, so a burst of edits
+/// (dragging a slider, typing in a field) is one save.
+const DEBOUNCE: Duration = Duration::from_millis(750);
+
+/// Whether edits are written without pressing Save. Off by default, and not
+/// remembered: each run starts with the unsaved buffer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AutosaveMode {
+    #[default]
+    Off,
+    /// Write the config once edits settle.
+    Save,
+    /// Write it and tell Ghostty to reload, like Save & Apply.
+    Apply,
+}
+
+impl AutosaveMode {
+    pub const ALL: [AutosaveMode; 3] = [AutosaveMode::Off, AutosaveMode::Save, AutosaveMode::Apply];
+
+    pub fn slug(self) -> &'static str {
+        match self {
+            AutosaveMode::Off => "off",
+            AutosaveMode::Save => "save",
+            AutosaveMode::Apply => "apply",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AutosaveMode::Off => "Autosave off",
+            AutosaveMode::Save => "Autosave",
+            AutosaveMode::Apply => "Autosave & apply",
+        }
+    }
+
+    pub fn parse(slug: &str) -> Option<AutosaveMode> {
+        AutosaveMode::ALL.into_iter().find(|m| m.slug() == slug)
+    }
+}
+
+/// Write the unsaved edits, if autosave is on and there are any. Edits are left
+/// unsaved, with a toast saying why, when the file changed on disk meanwhile or
+/// Ghostty rejects the config; the next edit tries again.
+///
+/// Only the config that was validated is written: if it's edited while Ghostty
+/// checks it, this autosave gives way to the one the edit schedules.
+pub async fn autosave(state: &SharedState) {
+    let mode = *state.autosave.read().await;
+    if mode == AutosaveMode::Off || state.unsaved_count().await == 0 {
+        return;
+    }
+    let revision = state.revision();
+    if let Some(problems) = validation_problems(state).await {
+        let main_file = state.user_config.read().await.file_path.clone();
+        let message = blocked_message(&problems, &main_file);
+        publish_toast(state, &escape_html(&message), true);
+        return;
+    }
+
+    // No edit can land from here until the saved file is read back.
+    let _writing = state.config_writes.lock().await;
+    if state.revision() != revision {
+        return;
+    }
+    match state.external_changes().await {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            publish_toast(
+                state,
+                r#"Not autosaved — the config changed on disk; <a href="/merge" class="underline">merge</a> first"#,
+                true,
+            );
+            return;
+        }
+        Err(e) => {
+            tracing::warn!("Autosave couldn't check the file on disk: {}", e);
+            return;
+        }
+    }
+
+    let annotate = state.settings.read().await.annotate_new_keys;
+    if let Err(e) = write_and_reload(state, annotate).await {
+        tracing::warn!("Autosave failed: {}", e);
+        publish_toast(state, "Autosave failed; save by hand to see why", true);
+        return;
+    }
+    if mode == AutosaveMode::Save {
+        publish_toast(state, "Autosaved", false);
+    } else if state.remote.is_some() {
+        // The Ghostty this could signal isn't the one reading a remote config.
+        publish_toast(state, "Autosaved to the remote config", false);
+    } else if trigger_ghostty_reload().is_ok() {
+        state.record_action(ChangeKind::Apply).await;
+        publish_toast(state, "Autosaved and Ghostty reloaded", false);
+    } else {
+        let message = format!("Autosaved (reload Ghostty with {})", RELOAD_SHORTCUT);
+        publish_toast(state, &message, true);
+    }
+}
+
+/// Autosave in the background once edits settle, while autosave is on.
+pub fn spawn(state: SharedState) {
+    tokio::spawn(async move {
+        loop {
+            state.autosave_due.notified().await;
+            // Restart the wait on every further edit until things settle.
+            while tokio::time::timeout(DEBOUNCE, state.autosave_due.notified())
+                .await
+                .is_ok()
+            {}
+            autosave(&state).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_slugs() {
+        for mode in AutosaveMode::ALL {
+            assert_eq!(AutosaveMode::parse(mode.slug()), Some(mode));
+        }
+        assert_eq!(AutosaveMode::parse("always"), None);
+        assert_eq!(AutosaveMode::default(), AutosaveMode::Off);
+    }
+}
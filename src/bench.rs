@@ -0,0 +1,187 @@
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::args::BenchArgs;
+use crate::cli::discovery::{find_ghostty, run_ghostty};
+use crate::cli::fonts::parse_font_list;
+use crate::cli::themes::{load_themes_from, theme_dir};
+use crate::config::parser::parse_show_config;
+
+const SHOW_CONFIG_FIXTURE: &str = "show-config.txt";
+const FONTS_FIXTURE: &str = "list-fonts.txt";
+const THEMES_FIXTURE: &str = "themes";
+
+/// Timing for one benchmark.
+#[derive(Debug)]
+pub struct BenchResult {
+    pub name: &'static str,
+    pub iterations: u32,
+    pub total: Duration,
+    /// Input size processed per iteration.
+    pub bytes: usize,
+    /// Items (options, themes, font families) produced per iteration.
+    pub items: usize,
+}
+
+impl BenchResult {
+    pub fn mean(&self) -> Duration {
+        self.total / self.iterations.max(1)
+    }
+
+    pub fn mib_per_sec(&self) -> f64 {
+        let secs = self.mean().as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        self.bytes as f64 / (1024.0 * 1024.0) / secs
+    }
+
+    pub fn items_per_sec(&self) -> f64 {
+        let secs = self.mean().as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        self.items as f64 / secs
+    }
+}
+
+/// Run `f` once to warm up, then `iterations` timed times.
+///
+/// `f` returns the number of items it produced, which also keeps the work from
+/// being optimized away.
+fn time<F>(
+    name: &'static str,
+    iterations: u32,
+    bytes: usize,
+    mut f: F,
+) -> anyhow::Result<BenchResult>
+where
+    F: FnMut() -> anyhow::Result<usize>,
+{
+    let mut items = f()?;
+    let mut total = Duration::ZERO;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        items = f()?;
+        total += start.elapsed();
+    }
+    Ok(BenchResult {
+        name,
+        iterations,
+        total,
+        bytes,
+        items,
+    })
+}
+
+fn dir_size(dir: &Path) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.metadata().ok())
+                .filter(|m| m.is_file())
+                .map(|m| m.len() as usize)
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Replace the fixtures in `dir` with output from the installed Ghostty.
+async fn record_fixtures(dir: &Path) -> anyhow::Result<()> {
+    let ghostty_path = find_ghostty()?;
+    fs::create_dir_all(dir.join(THEMES_FIXTURE))?;
+
+    let show_config = run_ghostty(&ghostty_path, &["+show-config", "--default", "--docs"]).await?;
+    fs::write(dir.join(SHOW_CONFIG_FIXTURE), show_config)?;
+
+    let fonts = run_ghostty(&ghostty_path, &["+list-fonts"]).await?;
+    fs::write(dir.join(FONTS_FIXTURE), fonts)?;
+
+    if let Some(themes) = theme_dir() {
+        for entry in fs::read_dir(themes)?.flatten() {
+            if entry.path().is_file() {
+                fs::copy(
+                    entry.path(),
+                    dir.join(THEMES_FIXTURE).join(entry.file_name()),
+                )?;
+            }
+        }
+    }
+
+    println!("Recorded fixtures in {}", dir.display());
+    Ok(())
+}
+
+/// Run all benchmarks against the fixtures in `dir`.
+pub fn run_benchmarks(dir: &Path, iterations: u32) -> anyhow::Result<Vec<BenchResult>> {
+    let show_config = fs::read_to_string(dir.join(SHOW_CONFIG_FIXTURE))?;
+    let fonts = fs::read_to_string(dir.join(FONTS_FIXTURE))?;
+    let themes_dir = dir.join(THEMES_FIXTURE);
+
+    Ok(vec![
+        time("parse_show_config", iterations, show_config.len(), || {
+            Ok(parse_show_config(&show_config)?.options().len())
+        })?,
+        time("load_themes", iterations, dir_size(&themes_dir), || {
+            Ok(load_themes_from(&themes_dir)?.len())
+        })?,
+        time("parse_font_list", iterations, fonts.len(), || {
+            Ok(parse_font_list(&fonts).len())
+        })?,
+    ])
+}
+
+/// Entry point for `ghostty-config bench`.
+pub async fn run(args: &BenchArgs) -> anyhow::Result<()> {
+    if args.record {
+        record_fixtures(&args.fixtures).await?;
+    }
+
+    let results = run_benchmarks(&args.fixtures, args.iterations)?;
+
+    println!(
+        "{:<20} {:>8} {:>12} {:>12} {:>14}",
+        "benchmark", "items", "mean", "MiB/s", "items/s"
+    );
+    for r in &results {
+        println!(
+            "{:<20} {:>8} {:>12} {:>12.2} {:>14.0}",
+            r.name,
+            r.items,
+            format!("{:.2?}", r.mean()),
+            r.mib_per_sec(),
+            r.items_per_sec()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_benchmarks_on_fixtures() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let results = run_benchmarks(&dir, 1).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|r| r.items > 0 && r.bytes > 0));
+    }
+
+    #[test]
+    fn test_bench_result_rates() {
+        let result = BenchResult {
+            name: "x",
+            iterations: 2,
+            total: Duration::from_secs(2),
+            bytes: 1024 * 1024,
+            items: 10,
+        };
+        assert_eq!(result.mean(), Duration::from_secs(1));
+        assert_eq!(result.mib_per_sec(), 1.0);
+        assert_eq!(result.items_per_sec(), 10.0);
+    }
+}
@@ -0,0 +1,197 @@
+use std::process::{Command, Stdio};
+
+use crate::auth;
+use crate::error::AppError;
+
+/// Opens pages of the running UI in a browser.
+#[derive(Debug, Clone)]
+pub struct BrowserLauncher {
+    /// Browser command, split into words like a shell would; `None` uses the
+    /// system default.
+    pub command: Option<String>,
+    /// Base URL of the server, e.g. `http://127.0.0.1:3456`.
+    pub base_url: String,
+    /// Session token added to opened URLs; see `auth`.
+    pub token: Option<String>,
+}
+
+impl BrowserLauncher {
+    /// Full URL for a page of this server, signed in with the token if there is one.
+    pub fn page_url(&self, page: &str) -> Result<String, AppError> {
+        let mut url = format!("{}{}", self.base_url, normalize_page(page)?);
+        if let Some(token) = &self.token {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(auth::QUERY);
+            url.push('=');
+            url.push_str(token);
+        }
+        Ok(url)
+    }
+
+    /// Open a page of this server in the configured browser. Returns once the
+    /// browser is started: browsers that don't fork would otherwise hold the
+    /// caller until they're closed.
+    pub fn open(&self, page: &str) -> Result<String, AppError> {
+        let url = self.page_url(page)?;
+        let Some(command) = &self.command else {
+            open::that_detached(&url)?;
+            return Ok(url);
+        };
+        let words = split_command(command)?;
+        let Some((program, args)) = words.split_first() else {
+            return Err(AppError::Config("The browser command is empty".to_string()));
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .arg(&url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| AppError::Config(format!("Couldn't run {}: {}", program, e)))?;
+        // Reap it whenever it exits.
+        std::thread::spawn(move || child.wait());
+        Ok(url)
+    }
+}
+
+/// Split a command line into words with POSIX shell quoting: whitespace separates
+/// words, single quotes keep everything literal, and a backslash escapes the next
+/// character, inside double quotes only if it is `"`, `\`, `$`, or a backtick.
+fn split_command(command: &str) -> Result<Vec<String>, AppError> {
+    let unterminated = || AppError::Config(format!("Unterminated quote in {}", command));
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next().ok_or_else(unterminated)? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next().ok_or_else(unterminated)? {
+                        '"' => break,
+                        '\\' => match chars.next().ok_or_else(unterminated)? {
+                            c @ ('"' | '\\' | '$' | '`') => word.push(c),
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                let word = word.get_or_insert_with(String::new);
+                word.extend(chars.next());
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Turn a page argument into an absolute path on this server.
+///
+/// Only local paths are accepted so `/api/open` can't be used to launch arbitrary URLs.
+fn normalize_page(page: &str) -> Result<String, AppError> {
+    let page = page.trim();
+    if page.contains("://") || page.starts_with("//") {
+        return Err(AppError::Config(format!(
+            "Page must be a path on this server: {}",
+            page
+        )));
+    }
+    if page.starts_with('/') {
+        Ok(page.to_string())
+    } else {
+        Ok(format!("/{}", page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launcher() -> BrowserLauncher {
+        BrowserLauncher {
+            command: None,
+            base_url: "http://127.0.0.1:3456".to_string(),
+            token: None,
+        }
+    }
+
+    #[test]
+    fn test_page_url_root() {
+        assert_eq!(launcher().page_url("/").unwrap(), "http://127.0.0.1:3456/");
+        assert_eq!(launcher().page_url("").unwrap(), "http://127.0.0.1:3456/");
+    }
+
+    #[test]
+    fn test_page_url_adds_leading_slash() {
+        assert_eq!(
+            launcher().page_url("themes").unwrap(),
+            "http://127.0.0.1:3456/themes"
+        );
+        assert_eq!(
+            launcher().page_url("/category/fonts").unwrap(),
+            "http://127.0.0.1:3456/category/fonts"
+        );
+    }
+
+    #[test]
+    fn test_page_url_adds_token() {
+        let launcher = BrowserLauncher {
+            token: Some("abc".to_string()),
+            ..launcher()
+        };
+        assert_eq!(
+            launcher.page_url("/").unwrap(),
+            "http://127.0.0.1:3456/?token=abc"
+        );
+        assert_eq!(
+            launcher.page_url("/themes?view=recent").unwrap(),
+            "http://127.0.0.1:3456/themes?view=recent&token=abc"
+        );
+    }
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(
+            split_command("google-chrome --profile-directory=Work").unwrap(),
+            ["google-chrome", "--profile-directory=Work"]
+        );
+        assert_eq!(
+            split_command(
+                r#"  '/Applications/Google Chrome' --profile-directory="Profile 1" a\ b "" "#
+            )
+            .unwrap(),
+            [
+                "/Applications/Google Chrome",
+                "--profile-directory=Profile 1",
+                "a b",
+                ""
+            ]
+        );
+        assert_eq!(split_command(r#""a\"b\c""#).unwrap(), [r#"a"b\c"#]);
+        assert!(split_command("firefox 'x").is_err());
+        assert!(split_command("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_page_url_rejects_external_urls() {
+        assert!(launcher().page_url("https://example.com").is_err());
+        assert!(launcher().page_url("//example.com/x").is_err());
+    }
+}
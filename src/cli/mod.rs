@@ -1,6 +0,0 @@
-pub mod actions;
-pub mod discovery;
-pub mod fonts;
-pub mod keybinds;
-pub mod themes;
-pub mod validate;
@@ -0,0 +1,227 @@
+use crate::args::{Args, Command};
+use crate::cli::validate::{validate_text, validate_user_config};
+use crate::config::file_io::{read_config, read_config_text, write_config};
+use crate::config::includes::load_included;
+use crate::config::model::{Category, ConfigOption, ConfigSchema, UserConfig};
+use crate::discovery_cache::{self, cache_path};
+use crate::settings::{categories_path, load_category_overrides, settings_path};
+
+/// Run a headless subcommand against the config file, without starting the server.
+pub async fn run(command: &Command, args: &Args) -> anyhow::Result<()> {
+    if args.remote.is_some() {
+        anyhow::bail!("--remote only works when serving the UI");
+    }
+    let config_path = args.config_path();
+
+    match command {
+        Command::Bench(bench_args) => crate::bench::run(bench_args).await,
+        Command::Validate => {
+            let problems = validate_text(
+                &args.ghostty_path()?,
+                &read_config_text(&config_path)?,
+                &config_path,
+            )
+            .await?;
+            if !problems.is_empty() {
+                anyhow::bail!("{}", problems);
+            }
+            println!("{} is valid", config_path.display());
+            Ok(())
+        }
+        Command::Get { key } => {
+            let schema = load_schema(args).await?;
+            let option = find_option(&schema, key)?;
+            let config = read_config(&config_path)?;
+            for value in current_values(&config, option) {
+                println!("{}", value);
+            }
+            Ok(())
+        }
+        Command::Set { key, values } => {
+            let schema = load_schema(args).await?;
+            let option = find_option(&schema, key)?;
+            let mut config = read_config(&config_path)?;
+            set_values(&mut config, option, values)?;
+            match args.ghostty_path() {
+                Ok(ghostty_path) => {
+                    let problems = validate_user_config(&ghostty_path, &config, &[]).await?;
+                    if !problems.is_empty() {
+                        anyhow::bail!("Not saved, Ghostty rejected the value:\n{}", problems);
+                    }
+                }
+                Err(e) => eprintln!("Saving without validation: {}", e),
+            }
+            write_config(&config)?;
+            Ok(())
+        }
+        Command::Unset { key } => {
+            let schema = load_schema(args).await?;
+            let option = find_option(&schema, key)?;
+            let mut config = read_config(&config_path)?;
+            if config.get(&option.key).is_some() {
+                config.remove(&option.key);
+                write_config(&config)?;
+            }
+            Ok(())
+        }
+        Command::List { category, modified } => {
+            let schema = load_schema(args).await?;
+            let category = category.as_deref().map(find_category).transpose()?;
+            let config = read_config(&config_path)?;
+            for line in list_lines(&schema, &config, category.as_ref(), *modified) {
+                println!("{}", line);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// The schema from Ghostty, or the built-in one when Ghostty can't be run.
+async fn load_schema(args: &Args) -> anyhow::Result<ConfigSchema> {
+    let settings_path = settings_path();
+    load_category_overrides(&categories_path(&settings_path));
+    let cache = cache_path(&settings_path);
+    let ghostty_path = args.ghostty_path().ok();
+    let (discovery, _) = discovery_cache::load_or_snapshot(
+        ghostty_path.as_deref(),
+        &cache,
+        args.refresh,
+        args.schema_version.as_deref(),
+    )
+    .await?;
+    Ok(discovery.schema)
+}
+
+fn find_option<'a>(schema: &'a ConfigSchema, key: &str) -> anyhow::Result<&'a ConfigOption> {
+    schema
+        .find_option(key)
+        .ok_or_else(|| anyhow::anyhow!("Unknown config key: {}", key))
+}
+
+fn find_category(slug: &str) -> anyhow::Result<Category> {
+    Category::all()
+        .into_iter()
+        .find(|cat| cat.slug() == slug)
+        .ok_or_else(|| {
+            let slugs: Vec<&str> = Category::all().iter().map(|c| c.slug()).collect();
+            anyhow::anyhow!(
+                "Unknown category: {} (expected one of {})",
+                slug,
+                slugs.join(", ")
+            )
+        })
+}
+
+/// The values in effect for `option`: from the config file, then from the files it
+/// includes, then the default.
+fn current_values(config: &UserConfig, option: &ConfigOption) -> Vec<String> {
+    std::iter::once(config.clone())
+        .chain(load_included(config).into_iter().rev())
+        .map(|c| {
+            c.get_all(&option.key)
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .find(|values| !values.is_empty())
+        .unwrap_or_else(|| vec![option.default_value.clone()])
+}
+
+/// Set `option` in `config`. Only repeatable options accept several values.
+fn set_values(
+    config: &mut UserConfig,
+    option: &ConfigOption,
+    values: &[String],
+) -> anyhow::Result<()> {
+    match values {
+        [value] if !option.is_repeatable => config.set(&option.key, value),
+        _ if option.is_repeatable => config.set_all(&option.key, values),
+        _ => anyhow::bail!("{} takes a single value", option.key),
+    }
+    Ok(())
+}
+
+/// `key = value` lines for `list`, using the default for keys the config doesn't set.
+fn list_lines(
+    schema: &ConfigSchema,
+    config: &UserConfig,
+    category: Option<&Category>,
+    modified_only: bool,
+) -> Vec<String> {
+    schema
+        .options()
+        .iter()
+        .filter(|o| category.is_none_or(|cat| &o.category == cat))
+        .flat_map(|option| {
+            let set = config.get_all(&option.key);
+            if set.is_empty() && !modified_only {
+                vec![format!("{} = {}", option.key, option.default_value)]
+            } else {
+                set.into_iter()
+                    .map(|value| format!("{} = {}", option.key, value))
+                    .collect()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::config::model::ConfigValueType;
+
+    fn option(key: &str, default: &str, category: Category, repeatable: bool) -> ConfigOption {
+        ConfigOption {
+            key: key.to_string(),
+            default_value: default.to_string(),
+            documentation: String::new(),
+            value_type: ConfigValueType::Text,
+            category,
+            is_repeatable: repeatable,
+            conditions: Default::default(),
+        }
+    }
+
+    fn schema() -> ConfigSchema {
+        ConfigSchema::new(vec![
+            option("font-size", "13", Category::Fonts, false),
+            option("font-family", "", Category::Fonts, true),
+            option("theme", "", Category::Appearance, false),
+        ])
+    }
+
+    #[test]
+    fn test_set_values() {
+        let schema = schema();
+        let mut config = UserConfig::new(PathBuf::from("/nonexistent/config"));
+        set_values(&mut config, &schema.options()[0], &["14".to_string()]).unwrap();
+        assert_eq!(config.get("font-size"), Some("14"));
+
+        let fonts = ["Iosevka".to_string(), "Symbols".to_string()];
+        set_values(&mut config, &schema.options()[1], &fonts).unwrap();
+        assert_eq!(config.get_all("font-family"), vec!["Iosevka", "Symbols"]);
+
+        assert!(set_values(&mut config, &schema.options()[0], &fonts).is_err());
+        assert_eq!(current_values(&config, &schema.options()[2]), vec![""]);
+    }
+
+    #[test]
+    fn test_list_lines() {
+        let schema = schema();
+        let mut config = UserConfig::new(PathBuf::from("/nonexistent/config"));
+        config.set("font-size", "15");
+
+        assert_eq!(
+            list_lines(&schema, &config, Some(&Category::Fonts), false),
+            vec!["font-size = 15", "font-family = "]
+        );
+        assert_eq!(
+            list_lines(&schema, &config, None, true),
+            vec!["font-size = 15"]
+        );
+        assert!(find_category("fonts").is_ok());
+        assert!(find_category("nope").is_err());
+    }
+}
@@ -1,5 +0,0 @@
-pub mod categorize;
-pub mod file_io;
-pub mod model;
-pub mod parser;
-pub mod type_inference;
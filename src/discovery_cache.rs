@@ -0,0 +1,227 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::actions::load_actions;
+use crate::cli::discovery::{ghostty_version, run_ghostty};
+use crate::cli::fonts::load_fonts;
+use crate::cli::keybinds::{load_keybinds, Keybinding};
+use crate::cli::snapshot::snapshot;
+use crate::config::model::{ConfigSchema, FontFamily};
+use crate::config::parser::parse_show_config;
+use crate::error::AppError;
+
+/// Everything startup learns by running `ghostty`. Only changes when Ghostty
+/// does, apart from fonts, so it's cached per Ghostty version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Discovery {
+    /// `ghostty +version` of the binary that produced this.
+    pub version: String,
+    pub schema: ConfigSchema,
+    pub fonts: Vec<FontFamily>,
+    pub actions: Vec<String>,
+    pub default_keybinds: Vec<Keybinding>,
+}
+
+/// `discovery-cache.json`, next to `settings.json`.
+pub fn cache_path(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("discovery-cache.json")
+}
+
+/// Discovery results for `ghostty_path`: from the cache when it was written for
+/// the same Ghostty version, otherwise by running Ghostty and caching the result.
+///
+/// `refresh` skips the cache, e.g. to pick up newly installed fonts.
+pub async fn load(ghostty_path: &Path, cache: &Path, refresh: bool) -> Result<Discovery, AppError> {
+    let version = ghostty_version(ghostty_path)
+        .await
+        .inspect_err(|e| tracing::warn!("Not caching discovery: {}", e))
+        .ok();
+
+    if let (Some(version), false) = (&version, refresh) {
+        if let Some(cached) = read_cache(cache, version) {
+            tracing::info!("Using cached discovery for Ghostty {}", version);
+            return Ok(Discovery {
+                schema: cached.schema.recategorize(),
+                ..cached
+            });
+        }
+    }
+
+    let (discovery, complete) = discover(ghostty_path, version.clone().unwrap_or_default()).await?;
+    // A partial result would hide fonts or keybinds until the next version bump.
+    if version.is_some() && complete {
+        if let Err(e) = write_cache(cache, &discovery) {
+            tracing::warn!("Failed to cache discovery in {}: {}", cache.display(), e);
+        }
+    }
+    Ok(discovery)
+}
+
+/// [`load`], or the built-in snapshot of `snapshot_version` (the newest when
+/// `None`) when there's no Ghostty to run or discovery fails. The flag says
+/// whether the snapshot was used.
+pub async fn load_or_snapshot(
+    ghostty_path: Option<&Path>,
+    cache: &Path,
+    refresh: bool,
+    snapshot_version: Option<&str>,
+) -> Result<(Discovery, bool), AppError> {
+    if let Some(ghostty_path) = ghostty_path {
+        match load(ghostty_path, cache, refresh).await {
+            Ok(discovery) => return Ok((discovery, false)),
+            Err(e) => tracing::warn!("Discovery failed: {}", e),
+        }
+    }
+    let discovery = from_snapshot(snapshot_version)?;
+    tracing::warn!(
+        "Using the built-in Ghostty {} schema; validation is off",
+        discovery.version
+    );
+    Ok((discovery, true))
+}
+
+/// Discovery from a built-in snapshot. It has no fonts, since those depend on
+/// the machine.
+pub fn from_snapshot(version: Option<&str>) -> Result<Discovery, AppError> {
+    let snapshot = snapshot(version)?;
+    Ok(Discovery {
+        version: snapshot.version.to_string(),
+        schema: snapshot.schema()?,
+        fonts: Vec::new(),
+        actions: snapshot.actions(),
+        default_keybinds: snapshot.default_keybinds(),
+    })
+}
+
+fn read_cache(path: &Path, version: &str) -> Option<Discovery> {
+    let text = fs::read_to_string(path).ok()?;
+    let cached: Discovery = serde_json::from_str(&text)
+        .inspect_err(|e| tracing::warn!("Ignoring invalid cache {}: {}", path.display(), e))
+        .ok()?;
+    (cached.version == version).then_some(cached)
+}
+
+fn write_cache(path: &Path, discovery: &Discovery) -> Result<(), AppError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string(discovery)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to serialize cache: {}", e)))?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+/// Run Ghostty for the schema, fonts, actions, and default keybinds, all at once
+/// since each is a separate process. The schema is required; the rest fall back to
+/// empty, which makes the result incomplete.
+async fn discover(ghostty_path: &Path, version: String) -> Result<(Discovery, bool), AppError> {
+    tracing::info!("Discovering config options, fonts, actions, and keybinds...");
+    let (schema, fonts, actions, default_keybinds) = tokio::join!(
+        async {
+            let output =
+                run_ghostty(ghostty_path, &["+show-config", "--default", "--docs"]).await?;
+            parse_show_config(&output)
+        },
+        load_fonts(ghostty_path),
+        load_actions(ghostty_path),
+        load_keybinds(ghostty_path),
+    );
+    let schema = schema?;
+
+    let mut complete = true;
+    let fonts = or_empty("fonts", fonts, &mut complete);
+    let actions = or_empty("actions", actions, &mut complete);
+    let default_keybinds = or_empty("default keybinds", default_keybinds, &mut complete);
+
+    Ok((
+        Discovery {
+            version,
+            schema,
+            fonts,
+            actions,
+            default_keybinds,
+        },
+        complete,
+    ))
+}
+
+fn or_empty<T>(what: &str, result: Result<Vec<T>, AppError>, complete: &mut bool) -> Vec<T> {
+    result.unwrap_or_else(|e| {
+        tracing::warn!("Failed to load {}: {}", what, e);
+        *complete = false;
+        Vec::new()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::{Category, ConfigOption, ConfigValueType};
+
+    fn discovery(version: &str) -> Discovery {
+        Discovery {
+            version: version.to_string(),
+            schema: ConfigSchema::new(vec![ConfigOption {
+                key: "font-size".to_string(),
+                default_value: "13".to_string(),
+                documentation: String::new(),
+                value_type: ConfigValueType::Float,
+                category: Category::Fonts,
+                is_repeatable: false,
+                conditions: Default::default(),
+            }]),
+            fonts: vec![FontFamily {
+                name: "Menlo".to_string(),
+                styles: Vec::new(),
+            }],
+            actions: vec!["copy_to_clipboard".to_string()],
+            default_keybinds: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = cache_path(&dir.path().join("ghostty-config").join("settings.json"));
+        assert!(read_cache(&path, "1.1.3").is_none());
+
+        write_cache(&path, &discovery("1.1.3")).unwrap();
+        let cached = read_cache(&path, "1.1.3").unwrap();
+        assert_eq!(cached.fonts[0].name, "Menlo");
+        assert_eq!(cached.actions, vec!["copy_to_clipboard"]);
+        // The schema's lookups work on what was read back.
+        assert!(cached.schema.find_option("font-size").is_some());
+        assert_eq!(
+            cached.schema.options_for_category(&Category::Fonts).len(),
+            1
+        );
+
+        // A different Ghostty version invalidates it.
+        assert!(read_cache(&path, "1.2.0").is_none());
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(read_cache(&path, "1.1.3").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("discovery-cache.json");
+        let (discovery, used) = load_or_snapshot(None, &cache, false, Some("1.1.3"))
+            .await
+            .unwrap();
+        assert!(used);
+        assert_eq!(discovery.version, "1.1.3");
+        assert!(discovery.schema.find_option("font-family").is_some());
+        assert!(discovery.fonts.is_empty());
+        assert!(!discovery.default_keybinds.is_empty());
+        // Nothing was cached for a version no binary reported.
+        assert!(!cache.exists());
+
+        assert!(load_or_snapshot(None, &cache, false, Some("0.9"))
+            .await
+            .is_err());
+    }
+}
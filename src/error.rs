@@ -1,30 +1,190 @@
-use axum::http::StatusCode;
-use axum::response::{IntoResponse, Response};
+//! The server reports the core library's errors directly; the `axum` feature of
+//! `ghostty-config-core` turns them into HTTP responses. [`render_errors`] then
+//! dresses those responses for whoever asked: a toast for HTMX, problem details
+//! for API clients, and an error page for the browser.
 
-#[derive(Debug, thiserror::Error)]
-pub enum AppError {
-    #[error("CLI error: {0}")]
-    Cli(String),
+use axum::extract::Request;
+use axum::http::{header, HeaderValue};
+use axum::middleware::Next;
+use axum::response::{Html, IntoResponse, Response};
+pub use ghostty_config_core::Error as AppError;
+pub use ghostty_config_core::{CliError, CliErrorKind, ErrorReport};
 
-    #[error("Config error: {0}")]
-    Config(String),
+use crate::cli::validate::{Severity, ValidationIssue};
+use crate::routes::config_api::{escape_html, toast_html};
+use crate::routes::validation_api::issue_badge_oob;
 
-    #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
+/// Replace the plain-text body of an [`AppError`] response, keeping its status.
+///
+/// HTMX requests get an error toast, retargeted at the toast container whatever
+/// the element asked for, plus an "Invalid" badge on the field when the request
+/// edited one. Other `/api/` requests get `application/problem+json`, and pages
+/// get a styled error page. Responses that aren't from an `AppError` pass through.
+pub async fn render_errors(request: Request, next: Next) -> Response {
+    let htmx = request.headers().contains_key("hx-request");
+    let path = request.uri().path().to_string();
+    let response = next.run(request).await;
+    let Some(report) = response.extensions().get::<ErrorReport>().cloned() else {
+        return response;
+    };
 
-    #[error("{0}")]
-    Internal(#[from] anyhow::Error),
+    if htmx {
+        let mut response = (report.status, Html(htmx_fragment(&report, &path))).into_response();
+        let headers = response.headers_mut();
+        headers.insert("hx-retarget", HeaderValue::from_static("#toast-container"));
+        headers.insert("hx-reswap", HeaderValue::from_static("innerHTML"));
+        response
+    } else if path.starts_with("/api/") {
+        (
+            report.status,
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            problem_json(&report).to_string(),
+        )
+            .into_response()
+    } else {
+        (report.status, Html(error_page(&report))).into_response()
+    }
+}
+
+/// The config key a request edits, from `/api/config/<key>` and
+/// `/api/config/<key>/<index>`.
+fn field_key(path: &str) -> Option<&str> {
+    let key = path.strip_prefix("/api/config/")?.split('/').next()?;
+    let valid = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    valid.then_some(key)
+}
+
+fn htmx_fragment(report: &ErrorReport, path: &str) -> String {
+    let mut html = toast_html(&escape_html(&report.detail), true);
+    if let Some(key) = field_key(path) {
+        html.push_str(&issue_badge_oob(&ValidationIssue {
+            key: Some(key.to_string()),
+            line: None,
+            message: report.detail.clone(),
+            severity: Severity::Error,
+        }));
+    }
+    html
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let (status, message) = match &self {
-            AppError::Cli(msg) => (StatusCode::BAD_GATEWAY, msg.clone()),
-            AppError::Config(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
-            AppError::Io(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
-            AppError::Internal(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
-        };
-        tracing::error!(%status, %message, "request error");
-        (status, message).into_response()
+/// An RFC 9457 problem details object, with the failed command's details as
+/// extension members.
+fn problem_json(report: &ErrorReport) -> serde_json::Value {
+    let mut problem = serde_json::json!({
+        "type": format!("urn:ghostty-config:error:{}", report.kind),
+        "title": report.title,
+        "status": report.status.as_u16(),
+        "detail": report.detail,
+    });
+    if let Some(command) = &report.command {
+        problem["command"] = command.as_str().into();
+    }
+    if let Some(code) = report.exit_code {
+        problem["exit_code"] = code.into();
+    }
+    if !report.stderr.is_empty() {
+        problem["stderr"] = report.stderr.as_str().into();
+    }
+    problem
+}
+
+fn error_page(report: &ErrorReport) -> String {
+    let command = report
+        .command
+        .as_deref()
+        .map(|command| {
+            format!(
+                r#"<p class="mt-4 text-xs text-gray-500">Command: <code class="font-mono">{}</code></p>"#,
+                escape_html(command)
+            )
+        })
+        .unwrap_or_default();
+    let stderr = if report.stderr.is_empty() {
+        String::new()
+    } else {
+        format!(
+            r#"<pre class="mt-2 p-3 bg-gray-50 border border-gray-200 rounded-lg text-xs font-mono text-gray-700 whitespace-pre-wrap">{}</pre>"#,
+            escape_html(&report.stderr)
+        )
+    };
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en" class="h-full">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>{title} - Ghostty Config</title>
+    <script src="https://cdn.tailwindcss.com"></script>
+</head>
+<body class="h-full bg-gray-50 text-gray-900 flex items-center justify-center p-4">
+    <main role="alert" class="max-w-lg w-full bg-white rounded-xl border border-gray-200 shadow-sm p-6">
+        <p class="text-xs font-semibold text-amber-600 uppercase tracking-wider">Error {status}</p>
+        <h1 class="mt-1 text-xl font-bold text-gray-900">{title}</h1>
+        <p class="mt-2 text-sm text-gray-700 whitespace-pre-line">{detail}</p>
+        {command}{stderr}
+        <a href="/" class="inline-block mt-6 px-4 py-2 text-sm font-medium text-white bg-indigo-600 rounded-lg hover:bg-indigo-700">Back to Ghostty Config</a>
+    </main>
+</body>
+</html>
+"#,
+        title = report.title,
+        status = report.status.as_u16(),
+        detail = escape_html(&report.detail),
+        command = command,
+        stderr = stderr,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn report(error: AppError) -> ErrorReport {
+        ErrorReport::new(&error)
+    }
+
+    #[test]
+    fn test_field_key() {
+        assert_eq!(field_key("/api/config/font-size"), Some("font-size"));
+        assert_eq!(field_key("/api/config/keybind/2"), Some("keybind"));
+        assert_eq!(field_key("/api/config"), None);
+        assert_eq!(field_key("/api/config/%3Cx%3E"), None);
+        assert_eq!(field_key("/api/raw"), None);
+    }
+
+    #[test]
+    fn test_htmx_fragment_escapes_and_marks_field() {
+        let report = report(AppError::Config("<b>bad</b> value".to_string()));
+        let html = htmx_fragment(&report, "/api/config/font-size");
+        assert!(html.contains(r#"role="alert""#));
+        assert!(html.contains("&lt;b&gt;bad&lt;/b&gt; value"));
+        assert!(html.contains(r#"id="issue-font-size""#));
+
+        let html = htmx_fragment(&report, "/api/save");
+        assert!(!html.contains("hx-swap-oob"));
+    }
+
+    #[test]
+    fn test_problem_json_includes_command() {
+        let report = report(
+            CliError::timeout("ghostty +list-fonts".to_string(), Duration::from_secs(5)).into(),
+        );
+        let problem = problem_json(&report);
+        assert_eq!(problem["status"], 504);
+        assert_eq!(problem["type"], "urn:ghostty-config:error:cli-timeout");
+        assert_eq!(problem["command"], "ghostty +list-fonts");
+        assert!(problem.get("exit_code").is_none());
+    }
+
+    #[test]
+    fn test_error_page_escapes_detail() {
+        let html = error_page(&report(AppError::Config("<script>".to_string())));
+        assert!(html.contains("Error 400"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
     }
 }
@@ -0,0 +1,107 @@
+use serde::Serialize;
+
+use crate::cli::validate::ValidationIssue;
+
+/// How many events a slow subscriber can fall behind before it starts missing them.
+pub const EVENT_CAPACITY: usize = 64;
+
+/// A state change pushed to every open page over `/events` and `/ws`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum AppEvent {
+    /// The config file was edited outside the UI and has been reloaded.
+    ConfigReloaded,
+    /// The config file was edited outside the UI while there are unsaved edits,
+    /// so it was not reloaded; the user needs to merge.
+    ExternalChange,
+    /// A key was changed in memory; `unsaved` is the new unsaved count.
+    ConfigChanged { key: String, unsaved: usize },
+    /// The in-memory config was written to disk.
+    Saved,
+    /// `ghostty +validate-config` ran against the config, unsaved edits included.
+    Validated {
+        valid: bool,
+        output: String,
+        issues: Vec<ValidationIssue>,
+    },
+    /// Background validation of the pending config gave a different result.
+    ValidationStatus { valid: bool, errors: usize },
+    /// Reduced-motion mode was switched on or off.
+    ReducedMotion { enabled: bool },
+    /// Autosave was switched; `mode` is `off`, `save`, or `apply`.
+    Autosave { mode: String },
+    /// The unsaved count changed without a key being edited, e.g. all edits were
+    /// discarded by a reload or merge.
+    UnsavedCount { unsaved: usize },
+    /// Another Ghostty installation was picked, so the options, fonts, and
+    /// keybinds on every page may have changed.
+    GhosttyChanged { version: String },
+    /// A page showed a toast; `message` is HTML.
+    Toast { message: String, error: bool },
+    /// The server is stopping. `/events` and `/ws` streams end after this one.
+    ShuttingDown,
+}
+
+impl AppEvent {
+    /// The SSE event name, matching the serialized `type`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::ConfigReloaded => "config-reloaded",
+            AppEvent::ExternalChange => "external-change",
+            AppEvent::ConfigChanged { .. } => "config-changed",
+            AppEvent::Saved => "saved",
+            AppEvent::Validated { .. } => "validated",
+            AppEvent::ValidationStatus { .. } => "validation-status",
+            AppEvent::ReducedMotion { .. } => "reduced-motion",
+            AppEvent::Autosave { .. } => "autosave",
+            AppEvent::UnsavedCount { .. } => "unsaved-count",
+            AppEvent::GhosttyChanged { .. } => "ghostty-changed",
+            AppEvent::Toast { .. } => "toast",
+            AppEvent::ShuttingDown => "shutting-down",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_name_matches_type() {
+        let events = [
+            AppEvent::ConfigReloaded,
+            AppEvent::ExternalChange,
+            AppEvent::ConfigChanged {
+                key: "font-size".to_string(),
+                unsaved: 1,
+            },
+            AppEvent::Saved,
+            AppEvent::Validated {
+                valid: true,
+                output: String::new(),
+                issues: Vec::new(),
+            },
+            AppEvent::ValidationStatus {
+                valid: false,
+                errors: 2,
+            },
+            AppEvent::ReducedMotion { enabled: true },
+            AppEvent::Autosave {
+                mode: "save".to_string(),
+            },
+            AppEvent::UnsavedCount { unsaved: 0 },
+            AppEvent::GhosttyChanged {
+                version: "1.1.3".to_string(),
+            },
+            AppEvent::Toast {
+                message: "Updated (unsaved)".to_string(),
+                error: false,
+            },
+            AppEvent::ShuttingDown,
+        ];
+        for event in events {
+            let json = serde_json::to_value(&event).unwrap();
+            assert_eq!(json["type"], event.name());
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! Browsing files for path-valued options such as `background-image`, kept to
+//! the home directory and the assets folder that uploaded images are saved in.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// Extensions served as images, with their content types.
+const IMAGE_TYPES: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+];
+
+/// `assets` in this tool's data directory, where uploaded images are kept.
+pub fn assets_dir() -> PathBuf {
+    directories::BaseDirs::new()
+        .map(|d| d.data_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("ghostty-config")
+        .join("assets")
+}
+
+/// The directories browsing may reach: the home directory and the assets folder.
+pub fn browse_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = directories::BaseDirs::new()
+        .map(|d| d.home_dir().to_path_buf())
+        .into_iter()
+        .collect();
+    roots.push(assets_dir());
+    roots
+}
+
+/// The content type of an image file, judged by its extension.
+pub fn image_content_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    IMAGE_TYPES
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, content_type)| *content_type)
+}
+
+/// Resolve a requested path, refusing anything outside `roots`.
+///
+/// An empty path is the first root; `~` and relative paths are taken from it.
+/// Symlinks are followed before the check, so a link can't lead out of a root.
+pub fn resolve_in(roots: &[PathBuf], requested: &str) -> Result<PathBuf, AppError> {
+    let home = roots
+        .first()
+        .ok_or_else(|| AppError::Config("No home directory to browse".to_string()))?;
+    let requested = requested.trim();
+    let path = match requested.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            home.join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => home.join(requested),
+    };
+    let path = path.canonicalize().map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => AppError::Config(format!("{} doesn't exist", path.display())),
+        _ => AppError::Io(e),
+    })?;
+    let inside = roots
+        .iter()
+        .any(|root| root.canonicalize().is_ok_and(|root| path.starts_with(root)));
+    if !inside {
+        return Err(AppError::Config(format!(
+            "{} is outside your home directory",
+            path.display()
+        )));
+    }
+    Ok(path)
+}
+
+/// A file or directory in a [`Listing`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DirEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub is_image: bool,
+}
+
+/// The contents of one directory, directories first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Listing {
+    pub path: PathBuf,
+    /// The directory above, unless that would leave the roots.
+    pub parent: Option<PathBuf>,
+    pub entries: Vec<DirEntry>,
+}
+
+/// List `dir`, which must already be resolved with [`resolve_in`]. Names starting
+/// with `.` are left out unless `hidden` is set.
+pub fn list_dir(roots: &[PathBuf], dir: &Path, hidden: bool) -> Result<Listing, AppError> {
+    if !dir.is_dir() {
+        return Err(AppError::Config(format!(
+            "{} is not a directory",
+            dir.display()
+        )));
+    }
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') && !hidden {
+            continue;
+        }
+        let path = entry.path();
+        // Follows symlinks; a broken link is skipped.
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        entries.push(DirEntry {
+            is_image: metadata.is_file() && image_content_type(&path).is_some(),
+            is_dir: metadata.is_dir(),
+            name,
+            path,
+        });
+    }
+    entries.sort_by(|a, b| {
+        b.is_dir
+            .cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    let parent = dir.parent().and_then(|parent| {
+        let inside = roots.iter().any(|root| {
+            root.canonicalize()
+                .is_ok_and(|root| parent.starts_with(root))
+        });
+        inside.then(|| parent.to_path_buf())
+    });
+    Ok(Listing {
+        path: dir.to_path_buf(),
+        parent,
+        entries,
+    })
+}
+
+/// Save an uploaded image in `dir` under a cleaned-up version of `name`, adding
+/// a number when the name is taken. Returns the saved file's path.
+///
+/// Only PNG, JPEG, GIF, and WebP files are accepted, checked by their contents.
+pub fn save_asset(dir: &Path, name: &str, bytes: &[u8]) -> Result<PathBuf, AppError> {
+    let name = asset_name(name)
+        .ok_or_else(|| AppError::Config(format!("{} is not a supported image name", name)))?;
+    if !looks_like_image(bytes) {
+        return Err(AppError::Config(format!(
+            "{} is not a PNG, JPEG, GIF, or WebP image",
+            name
+        )));
+    }
+
+    fs::create_dir_all(dir)?;
+    let (stem, extension) = name.rsplit_once('.').unwrap_or((&name, ""));
+    let mut path = dir.join(&name);
+    let mut n = 1;
+    // Claim the name by creating the file, so two uploads can't both take it.
+    let mut file = loop {
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => break file,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                path = dir.join(format!("{}-{}.{}", stem, n, extension));
+                n += 1;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    };
+    if let Err(e) = file.write_all(bytes) {
+        let _ = fs::remove_file(&path);
+        return Err(e.into());
+    }
+    Ok(path)
+}
+
+/// The last component of an uploaded file's name with anything but letters,
+/// digits, `.`, `_`, and `-` replaced, if it names an image.
+fn asset_name(name: &str) -> Option<String> {
+    let name = name.rsplit(['/', '\\']).next()?.trim();
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let (stem, _) = cleaned.rsplit_once('.')?;
+    let valid = !stem.is_empty()
+        && !cleaned.starts_with('.')
+        && image_content_type(Path::new(&cleaned)).is_some();
+    valid.then_some(cleaned)
+}
+
+fn looks_like_image(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"\x89PNG\r\n\x1a\n")
+        || bytes.starts_with(b"\xff\xd8\xff")
+        || bytes.starts_with(b"GIF87a")
+        || bytes.starts_with(b"GIF89a")
+        || (bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n rest";
+
+    #[test]
+    fn test_resolve_in_stays_inside_roots() {
+        let home = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        fs::create_dir(home.path().join("Pictures")).unwrap();
+        let roots = vec![home.path().to_path_buf()];
+
+        let home_path = home.path().canonicalize().unwrap();
+        assert_eq!(resolve_in(&roots, "").unwrap(), home_path);
+        assert_eq!(
+            resolve_in(&roots, "~/Pictures").unwrap(),
+            home_path.join("Pictures")
+        );
+        assert!(resolve_in(&roots, "Pictures/../..").is_err());
+        assert!(resolve_in(&roots, outside.path().to_str().unwrap()).is_err());
+        assert!(resolve_in(&roots, "missing").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_in_refuses_symlinks_out() {
+        let home = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), home.path().join("link")).unwrap();
+        let roots = vec![home.path().to_path_buf()];
+        assert!(resolve_in(&roots, "link").is_err());
+    }
+
+    #[test]
+    fn test_list_dir_sorts_and_hides_dotfiles() {
+        let home = tempfile::tempdir().unwrap();
+        fs::create_dir(home.path().join("b-dir")).unwrap();
+        fs::write(home.path().join("A.png"), PNG).unwrap();
+        fs::write(home.path().join("c.txt"), "").unwrap();
+        fs::write(home.path().join(".hidden"), "").unwrap();
+        let roots = vec![home.path().to_path_buf()];
+        let dir = resolve_in(&roots, "").unwrap();
+
+        let listing = list_dir(&roots, &dir, false).unwrap();
+        let names: Vec<&str> = listing.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["b-dir", "A.png", "c.txt"]);
+        assert!(listing.entries[1].is_image);
+        assert!(!listing.entries[2].is_image);
+        assert_eq!(listing.parent, None);
+
+        assert_eq!(list_dir(&roots, &dir, true).unwrap().entries.len(), 4);
+        let sub = list_dir(&roots, &dir.join("b-dir"), false).unwrap();
+        assert_eq!(sub.parent, Some(dir));
+    }
+
+    #[test]
+    fn test_save_asset_checks_contents_and_renames() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = save_asset(dir.path(), "../My Wallpaper.PNG", PNG).unwrap();
+        assert_eq!(first, dir.path().join("My-Wallpaper.PNG"));
+        let second = save_asset(dir.path(), "My Wallpaper.PNG", PNG).unwrap();
+        assert_eq!(second, dir.path().join("My-Wallpaper-1.PNG"));
+
+        assert!(save_asset(dir.path(), "notes.txt", PNG).is_err());
+        assert!(save_asset(dir.path(), "fake.png", b"not an image").is_err());
+        assert!(save_asset(dir.path(), ".png", PNG).is_err());
+    }
+
+    #[test]
+    fn test_save_asset_concurrent_uploads_keep_both() {
+        let dir = tempfile::tempdir().unwrap();
+        let uploads: Vec<_> = (0..8)
+            .map(|_| {
+                let dir = dir.path().to_path_buf();
+                std::thread::spawn(move || save_asset(&dir, "wall.png", PNG).unwrap())
+            })
+            .collect();
+        let mut saved: Vec<PathBuf> = uploads.into_iter().map(|u| u.join().unwrap()).collect();
+        saved.sort();
+        saved.dedup();
+        assert_eq!(saved.len(), 8);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 8);
+    }
+
+    #[test]
+    fn test_image_content_type() {
+        assert_eq!(image_content_type(Path::new("a.JPG")), Some("image/jpeg"));
+        assert_eq!(image_content_type(Path::new("a.glsl")), None);
+    }
+}
@@ -0,0 +1,146 @@
+//! The Ghostty installation the UI works against, and the others found on this
+//! machine that it can be switched to; see `/api/ghostty`.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::cli::discovery::{find_ghostty_installations, ghostty_version};
+use crate::cli::keybinds::Keybinding;
+use crate::config::model::{ConfigSchema, FontFamily};
+use crate::discovery_cache::{self, Discovery};
+use crate::error::{AppError, CliError, CliErrorKind};
+
+/// The active installation and what discovery learned from it. Replaced whole
+/// when another installation is picked or discovery is rerun; see `/api/refresh`.
+#[derive(Debug)]
+pub struct Ghostty {
+    /// `None` when Ghostty couldn't be run, which turns off validation and
+    /// everything else that runs it.
+    pub path: Option<PathBuf>,
+    /// `ghostty +version` of `path`, or the built-in schema's version.
+    pub version: String,
+    /// Whether the schema is the built-in snapshot rather than Ghostty's own;
+    /// see `cli::snapshot`.
+    pub from_snapshot: bool,
+    pub schema: ConfigSchema,
+    pub fonts: Vec<FontFamily>,
+    pub actions: Vec<String>,
+    pub default_keybinds: Vec<Keybinding>,
+}
+
+impl Ghostty {
+    /// Run discovery against `path`, or use the built-in schema of `snapshot_version`
+    /// when there's no path or Ghostty can't be run.
+    pub async fn discover(
+        path: Option<PathBuf>,
+        cache: &Path,
+        refresh: bool,
+        snapshot_version: Option<&str>,
+    ) -> Result<Self, AppError> {
+        let (discovery, from_snapshot) =
+            discovery_cache::load_or_snapshot(path.as_deref(), cache, refresh, snapshot_version)
+                .await?;
+        // A Ghostty that discovery couldn't run won't validate either.
+        Ok(Ghostty::new(path.filter(|_| !from_snapshot), discovery))
+    }
+
+    fn new(path: Option<PathBuf>, discovery: Discovery) -> Self {
+        Ghostty {
+            from_snapshot: path.is_none(),
+            path,
+            version: discovery.version,
+            schema: discovery.schema,
+            fonts: discovery.fonts,
+            actions: discovery.actions,
+            default_keybinds: discovery.default_keybinds,
+        }
+    }
+
+    /// The Ghostty binary, or an error saying there's none to run.
+    pub fn path(&self) -> Result<&Path, AppError> {
+        self.path.as_deref().ok_or_else(|| {
+            CliError::new(
+                CliErrorKind::NotFound,
+                format!(
+                    "This needs Ghostty, which couldn't be run; the options shown come from the built-in Ghostty {} schema",
+                    self.version
+                ),
+            )
+            .into()
+        })
+    }
+
+    /// The built-in schema's version, when it stands in for Ghostty's own.
+    pub fn snapshot(&self) -> Option<&str> {
+        self.from_snapshot.then_some(self.version.as_str())
+    }
+}
+
+/// A Ghostty binary found on this machine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Installation {
+    pub path: PathBuf,
+    /// `None` when `ghostty +version` failed.
+    pub version: Option<String>,
+}
+
+/// Every installation found, with its version, plus `extra` (e.g. a `--ghostty`
+/// binary outside the usual locations) if it isn't among them.
+pub async fn find_installations(extra: Option<&Path>) -> Vec<Installation> {
+    let mut paths = tokio::task::spawn_blocking(find_ghostty_installations)
+        .await
+        .unwrap_or_default();
+    if let Some(extra) = extra {
+        if !paths.iter().any(|p| same_binary(p, extra)) {
+            paths.insert(0, extra.to_path_buf());
+        }
+    }
+    futures_util::future::join_all(paths.into_iter().map(|path| async move {
+        let version = ghostty_version(&path)
+            .await
+            .inspect_err(|e| tracing::warn!("{}: {}", path.display(), e))
+            .ok();
+        Installation { path, version }
+    }))
+    .await
+}
+
+/// Whether `a` and `b` are the same binary, e.g. one is a link to the other.
+pub fn same_binary(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_discover_without_ghostty() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("discovery-cache.json");
+        let ghostty = Ghostty::discover(None, &cache, false, None).await.unwrap();
+        assert_eq!(ghostty.snapshot(), Some(ghostty.version.as_str()));
+        assert!(ghostty.path().is_err());
+
+        // A binary that can't be run falls back too, and isn't kept.
+        let missing = dir.path().join("ghostty");
+        let ghostty = Ghostty::discover(Some(missing), &cache, false, None)
+            .await
+            .unwrap();
+        assert!(ghostty.from_snapshot);
+        assert_eq!(ghostty.path, None);
+    }
+
+    #[test]
+    fn test_same_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ghostty");
+        std::fs::write(&path, "").unwrap();
+        assert!(same_binary(&path, &dir.path().join(".").join("ghostty")));
+        assert!(!same_binary(&path, Path::new("/nonexistent/ghostty")));
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::model::{ConfigEntry, UserConfig};
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeKind {
+    /// A key was given a value, or its values changed.
+    Set,
+    /// A key was removed.
+    Delete,
+    /// The config was written to disk.
+    Save,
+    /// The config was written and Ghostty told to reload it.
+    Apply,
+}
+
+/// One entry of the change journal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change {
+    pub id: u64,
+    /// Unix time in seconds.
+    pub time: u64,
+    pub kind: ChangeKind,
+    /// The key set or deleted; `None` for saves and applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Every value of the key before and after, in file order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub old: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub new: Vec<String>,
+}
+
+/// `history.jsonl` in this tool's data directory.
+pub fn history_path() -> PathBuf {
+    directories::BaseDirs::new()
+        .map(|d| d.data_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("ghostty-config")
+        .join("history.jsonl")
+}
+
+/// An append-only journal of config changes, one JSON object per line.
+///
+/// Edits go through many handlers, so instead of each one describing its change,
+/// the journal remembers every key's values as of the last recorded change and
+/// records whatever differs when asked to.
+#[derive(Debug)]
+pub struct History {
+    path: PathBuf,
+    known: BTreeMap<String, Vec<String>>,
+    next_id: u64,
+}
+
+impl History {
+    /// Open the journal at `path`, taking `configs` as the starting point for
+    /// future changes.
+    pub fn open(path: PathBuf, configs: &[&UserConfig]) -> Self {
+        let next_id = read_changes(&path).last().map_or(1, |c| c.id + 1);
+        History {
+            path,
+            known: values_by_key(configs),
+            next_id,
+        }
+    }
+
+    /// Take `configs` as they are without recording anything, e.g. after they were
+    /// reloaded from disk.
+    pub fn resync(&mut self, configs: &[&UserConfig]) {
+        self.known = values_by_key(configs);
+    }
+
+    /// Record a set or delete for every key whose values in `configs` differ from
+    /// the last recorded ones.
+    pub fn record(&mut self, configs: &[&UserConfig]) -> Result<Vec<Change>, AppError> {
+        let current = values_by_key(configs);
+        let keys: BTreeSet<String> = current.keys().chain(self.known.keys()).cloned().collect();
+
+        let mut changes = Vec::new();
+        for key in keys {
+            let old = self.known.get(&key).cloned().unwrap_or_default();
+            let new = current.get(&key).cloned().unwrap_or_default();
+            if old == new {
+                continue;
+            }
+            let kind = if new.is_empty() {
+                ChangeKind::Delete
+            } else {
+                ChangeKind::Set
+            };
+            changes.push(self.change(kind, Some(key), old, new));
+        }
+        self.known = current;
+        self.append(&changes)?;
+        Ok(changes)
+    }
+
+    /// Record a save or apply.
+    pub fn record_action(&mut self, kind: ChangeKind) -> Result<Change, AppError> {
+        let change = self.change(kind, None, Vec::new(), Vec::new());
+        self.append(std::slice::from_ref(&change))?;
+        Ok(change)
+    }
+
+    /// Every recorded change, oldest first.
+    pub fn changes(&self) -> Vec<Change> {
+        read_changes(&self.path)
+    }
+
+    fn change(
+        &mut self,
+        kind: ChangeKind,
+        key: Option<String>,
+        old: Vec<String>,
+        new: Vec<String>,
+    ) -> Change {
+        let id = self.next_id;
+        self.next_id += 1;
+        Change {
+            id,
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            kind,
+            key,
+            old,
+            new,
+        }
+    }
+
+    fn append(&self, changes: &[Change]) -> Result<(), AppError> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut lines = String::new();
+        for change in changes {
+            lines.push_str(&serde_json::to_string(change).map_err(anyhow::Error::from)?);
+            lines.push('\n');
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(lines.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// The changes in the journal at `path`. A missing file has none, and lines that
+/// don't parse are skipped.
+fn read_changes(path: &Path) -> Vec<Change> {
+    let Ok(text) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            serde_json::from_str(line)
+                .inspect_err(|e| tracing::warn!("Skipping history entry: {}", e))
+                .ok()
+        })
+        .collect()
+}
+
+/// Every key's values across `configs`, in the order `AppState::all_values` uses.
+pub(crate) fn values_by_key(configs: &[&UserConfig]) -> BTreeMap<String, Vec<String>> {
+    let mut values: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for config in configs {
+        for entry in &config.entries {
+            if let ConfigEntry::KeyValue { key, value, .. } = entry {
+                values.entry(key.clone()).or_default().push(value.clone());
+            }
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file_io::parse_config;
+
+    #[test]
+    fn test_record_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("history.jsonl");
+        let mut config = parse_config("font-size = 12\nkeybind = a=b\n", Path::new("config"));
+        let mut history = History::open(path.clone(), &[&config]);
+        assert!(history.record(&[&config]).unwrap().is_empty());
+
+        config.set("font-size", "14");
+        config.push("keybind", "c=d");
+        config.remove("font-size");
+        config.set("theme", "Nord");
+        let changes = history.record(&[&config]).unwrap();
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.id, c.kind, c.key.as_deref(), c.old.clone(), c.new.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    1,
+                    ChangeKind::Delete,
+                    Some("font-size"),
+                    vec!["12".to_string()],
+                    vec![]
+                ),
+                (
+                    2,
+                    ChangeKind::Set,
+                    Some("keybind"),
+                    vec!["a=b".to_string()],
+                    vec!["a=b".to_string(), "c=d".to_string()]
+                ),
+                (
+                    3,
+                    ChangeKind::Set,
+                    Some("theme"),
+                    vec![],
+                    vec!["Nord".to_string()]
+                ),
+            ]
+        );
+        history.record_action(ChangeKind::Save).unwrap();
+
+        // Reopening continues the numbering and reads everything back.
+        let mut reopened = History::open(path, &[&config]);
+        assert_eq!(reopened.changes().len(), 4);
+        assert_eq!(reopened.record_action(ChangeKind::Apply).unwrap().id, 5);
+    }
+
+    #[test]
+    fn test_resync_records_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let mut config = parse_config("font-size = 12\n", Path::new("config"));
+        let mut history = History::open(path, &[&config]);
+        config.set("font-size", "13");
+        history.resync(&[&config]);
+        assert!(history.record(&[&config]).unwrap().is_empty());
+        assert!(history.changes().is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+//! Finding a server that is already running, so a second launch opens it instead of
+//! starting another one that edits the same config file.
+
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::auth;
+use crate::error::AppError;
+
+/// How long to wait on a server that may have died without cleaning up.
+const PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The running server, as recorded in `instance.json` and reported by `/api/instance`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Instance {
+    pub pid: u32,
+    /// Base URL to browse to, e.g. `http://127.0.0.1:3456`.
+    pub url: String,
+    pub config_path: PathBuf,
+    /// The session token, so a second launch can use the API. Only in the file,
+    /// never sent by `/api/instance`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+/// `instance.json`, next to `settings.json`.
+pub fn instance_path(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("instance.json")
+}
+
+impl Instance {
+    /// The instance recorded at `path`, if any. It may no longer be running; see
+    /// [`Instance::probe`].
+    pub fn load(path: &Path) -> Option<Self> {
+        let text = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&text).ok()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AppError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(anyhow::Error::from)?;
+        // Only this user may read the token. The mode only applies to new files.
+        let _ = fs::remove_file(path);
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        options.open(path)?.write_all(json.as_bytes())?;
+        Ok(())
+    }
+
+    /// Delete the record at `path` if it is this one, leaving any newer instance's.
+    pub fn remove(&self, path: &Path) {
+        if Instance::load(path).as_ref() == Some(self) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// Ask the recorded server who it is. `None` when nothing answers, or something
+    /// other than this tool does.
+    pub fn probe(&self) -> Option<Instance> {
+        let (status, body) = request(&self.url, None, "GET", "/api/instance")?;
+        if status != 200 {
+            return None;
+        }
+        serde_json::from_str(&body).ok()
+    }
+
+    /// Have the server open `page` in the browser, as `POST /api/open` does.
+    pub fn open(&self, page: &str) -> bool {
+        let path = format!("/api/open?page={}", encode_query(page));
+        matches!(
+            request(&self.url, self.token.as_deref(), "POST", &path),
+            Some((200, _))
+        )
+    }
+}
+
+/// A bare HTTP/1.1 request to `base_url`, which must be `http://IP:PORT`. Returns
+/// the status and body.
+fn request(base_url: &str, token: Option<&str>, method: &str, path: &str) -> Option<(u16, String)> {
+    let host = base_url.strip_prefix("http://")?;
+    let addr: SocketAddr = host.parse().ok()?;
+    let mut stream = TcpStream::connect_timeout(&addr, PROBE_TIMEOUT).ok()?;
+    stream.set_read_timeout(Some(PROBE_TIMEOUT)).ok()?;
+    let auth = token
+        .map(|t| format!("{}: {}\r\n", auth::HEADER, t))
+        .unwrap_or_default();
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: {host}\r\n{auth}Content-Type: application/x-www-form-urlencoded\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+    )
+    .ok()?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).ok()?;
+    parse_response(&response)
+}
+
+fn parse_response(response: &str) -> Option<(u16, String)> {
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    let status = head.split_whitespace().nth(1)?.parse().ok()?;
+    Some((status, body.to_string()))
+}
+
+/// Percent-encode everything but unreserved characters and `/`.
+fn encode_query(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = instance_path(&dir.path().join("settings.json"));
+        assert_eq!(Instance::load(&path), None);
+
+        let instance = Instance {
+            pid: 42,
+            url: "http://127.0.0.1:3456".to_string(),
+            config_path: PathBuf::from("/home/me/.config/ghostty/config"),
+            token: Some("abc".to_string()),
+        };
+        instance.save(&path).unwrap();
+        assert_eq!(Instance::load(&path), Some(instance.clone()));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // Another instance's record is left alone.
+        let other = Instance {
+            pid: 7,
+            ..instance.clone()
+        };
+        other.remove(&path);
+        assert!(path.exists());
+        instance.remove(&path);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let response = "HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\n{}";
+        assert_eq!(parse_response(response), Some((200, "{}".to_string())));
+        assert_eq!(parse_response("garbage"), None);
+        assert_eq!(
+            encode_query("/category/fonts?x=1"),
+            "/category/fonts%3Fx%3D1"
+        );
+    }
+
+    #[test]
+    fn test_probe_without_server() {
+        // Nothing listens on port 1 of loopback in a test environment.
+        let instance = Instance {
+            pid: 1,
+            url: "http://127.0.0.1:1".to_string(),
+            config_path: PathBuf::new(),
+            token: None,
+        };
+        assert_eq!(instance.probe(), None);
+        assert!(!instance.open("/"));
+    }
+}
@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use crate::app_state::{AppState, SharedState};
+use crate::cli::validate::{validate_user_config, validation_issues, Severity, ValidationIssue};
+use crate::events::AppEvent;
+
+/// Wait this long after the last change before validating, so a burst of edits
+/// (dragging a slider, typing in a field) runs Ghostty once.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// The latest validation of the in-memory config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationStatus {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationStatus {
+    pub fn errors(&self) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == Severity::Error)
+            .count()
+    }
+
+    pub fn valid(&self) -> bool {
+        self.errors() == 0
+    }
+}
+
+/// Validate the in-memory config now, cache the result, and tell open pages if it
+/// changed. Returns `None` if the validator couldn't be run.
+pub async fn revalidate(state: &AppState) -> Option<ValidationStatus> {
+    let ghostty = state.ghostty().await;
+    let ghostty_path = ghostty.path.as_deref()?;
+    // The in-memory config is only a blank stand-in while the file is damaged.
+    if state.in_safe_mode().await {
+        return None;
+    }
+    let config = state.user_config.read().await.clone();
+    let included = state.included.read().await.clone();
+    let issues = match validate_user_config(ghostty_path, &config, &included).await {
+        Ok(output) => validation_issues(&output, &config),
+        Err(e) => {
+            tracing::warn!("Background validation failed: {}", e);
+            return None;
+        }
+    };
+
+    let status = ValidationStatus { issues };
+    let changed = {
+        let mut cached = state.validation.write().await;
+        let changed = cached.as_ref() != Some(&status);
+        *cached = Some(status.clone());
+        changed
+    };
+    if changed {
+        state.publish(AppEvent::ValidationStatus {
+            valid: status.valid(),
+            errors: status.errors(),
+        });
+    }
+    Some(status)
+}
+
+/// Revalidate in the background whenever the in-memory config changes.
+pub fn spawn(state: SharedState) {
+    tokio::spawn(async move {
+        revalidate(&state).await;
+        loop {
+            state.revalidate.notified().await;
+            // Restart the wait on every further change until things settle.
+            while tokio::time::timeout(DEBOUNCE, state.revalidate.notified())
+                .await
+                .is_ok()
+            {}
+            revalidate(&state).await;
+        }
+    });
+}
+
+/// Header indicator showing whether the pending config is valid, or that it
+/// can't be checked when the schema came from the built-in `snapshot` version.
+pub fn render_status(status: Option<&ValidationStatus>, snapshot: Option<&str>) -> String {
+    let (dot, label, title) = match (status, snapshot) {
+        (_, Some(version)) => (
+            "bg-amber-400",
+            "Not validated".to_string(),
+            format!(
+                "Ghostty couldn't be run, so changes aren't checked; options come from the built-in Ghostty {} schema",
+                version
+            ),
+        ),
+        (None, None) => ("bg-gray-300", "Checking…".to_string(), String::new()),
+        (Some(status), None) if status.valid() => (
+            "bg-emerald-500",
+            "Valid".to_string(),
+            match status.issues.len() {
+                0 => "Ghostty accepts the pending config".to_string(),
+                n => format!("Ghostty accepts the pending config with {} warning(s)", n),
+            },
+        ),
+        (Some(status), None) => (
+            "bg-red-500",
+            match status.errors() {
+                1 => "1 problem".to_string(),
+                n => format!("{} problems", n),
+            },
+            status
+                .issues
+                .iter()
+                .find(|i| i.severity == Severity::Error)
+                .map(|i| i.message.clone())
+                .unwrap_or_default(),
+        ),
+    };
+    format!(
+        r#"<span id="validation-status" role="status" title="{title}" class="inline-flex items-center gap-1.5 text-sm text-gray-600"><span class="w-2 h-2 rounded-full {dot}" aria-hidden="true"></span>{label}</span>"#,
+        title = crate::routes::config_api::escape_html(&title),
+        dot = dot,
+        label = label,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(severity: Severity) -> ValidationIssue {
+        ValidationIssue {
+            key: Some("font-size".to_string()),
+            line: Some(1),
+            message: "font-size: invalid value \"big\"".to_string(),
+            severity,
+        }
+    }
+
+    #[test]
+    fn test_render_status() {
+        assert!(render_status(None, None).contains("Checking"));
+
+        let ok = ValidationStatus {
+            issues: vec![issue(Severity::Warning)],
+        };
+        assert!(ok.valid());
+        assert!(render_status(Some(&ok), None).contains(">Valid<"));
+
+        let bad = ValidationStatus {
+            issues: vec![issue(Severity::Error), issue(Severity::Error)],
+        };
+        let html = render_status(Some(&bad), None);
+        assert!(html.contains("2 problems"));
+        assert!(html.contains("invalid value &quot;big&quot;"));
+
+        let html = render_status(None, Some("1.1.3"));
+        assert!(html.contains("Not validated"));
+        assert!(html.contains("built-in Ghostty 1.1.3 schema"));
+    }
+}
@@ -1,100 +1,388 @@
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::time::Duration;
 
+use clap::Parser;
+use tokio::net::TcpListener;
+#[cfg(unix)]
+use tokio::signal::unix::{signal, SignalKind};
 use tokio::sync::RwLock;
 use tracing_subscriber::EnvFilter;
 
 mod app_state;
-mod cli;
-mod config;
+mod args;
+mod auth;
+mod autosave;
+mod bench;
+mod browser;
+mod commands;
+mod discovery_cache;
 mod error;
+mod events;
+mod file_browser;
+mod ghostty;
+mod history;
+mod instance;
+mod live_validation;
+mod profiles;
+mod pty;
+mod remote;
+mod revision;
 mod routes;
+mod scratch;
+mod session;
+mod settings;
+mod share;
+mod theme_state;
+mod updates;
+mod watcher;
 
-use app_state::AppState;
-use cli::actions::load_actions;
-use cli::discovery::{find_ghostty, run_ghostty};
-use cli::fonts::load_fonts;
-use cli::keybinds::load_keybinds;
+use ghostty_config_core::{cli, config};
+
+use app_state::{AppState, SharedState};
+use args::{Args, DEFAULT_PORT};
+use browser::BrowserLauncher;
 use cli::themes::load_themes;
-use config::file_io::{default_config_path, read_config};
-use config::parser::parse_show_config;
+use config::baseline::{Baseline, BaselineSource};
+use config::corruption::detect_corruption;
+use config::file_io::{read_config, write_atomic};
+use config::includes::load_included;
+use config::model::UserConfig;
+use config::os_shortcuts::Platform;
+use events::AppEvent;
+use ghostty::Ghostty;
+use history::{history_path, History};
+use instance::{instance_path, Instance};
+use session::{session_path, PendingSession};
+use settings::{categories_path, load_category_overrides, settings_path, Settings};
+use theme_state::{theme_state_path, ThemeState};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize logging
+    let args = Args::parse();
+
+    // Initialize logging. Logs go to stderr so subcommand output can be piped.
     tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .with_writer(std::io::stderr)
         .init();
 
+    cli::discovery::set_command_timeout(Duration::from_secs(args.ghostty_timeout));
+
+    if let Some(command) = &args.command {
+        return commands::run(command, &args).await;
+    }
+
     tracing::info!("Starting Ghostty Config UI...");
 
-    // Find ghostty binary
-    let ghostty_path = find_ghostty()?;
-    tracing::info!("Found ghostty at: {}", ghostty_path.display());
+    // Anyone who can reach the port could change the config, and with
+    // `--live-preview` run commands, so other machines always need the token.
+    if args.exposed() && args.no_auth {
+        anyhow::bail!(
+            "--no-auth can't be used with --bind {}: other machines could change the config",
+            args.bind
+        );
+    }
+
+    // A remote config is edited through a local working copy of it.
+    let settings_path = settings_path();
+    let remote = args.remote_target()?;
+    let config_path = match &remote {
+        Some(remote) => remote.working_copy(&settings_path.with_file_name("remote")),
+        None => args.config_path(),
+    };
+    match &remote {
+        Some(remote) => tracing::info!(
+            "Config file: {} (working copy {})",
+            remote.display(),
+            config_path.display()
+        ),
+        None => tracing::info!("Config file: {}", config_path.display()),
+    }
+
+    // A server may already be editing this config; send the user there instead.
+    let instance_path = instance_path(&settings_path);
+    if let Some(recorded) = Instance::load(&instance_path) {
+        if recorded
+            .probe()
+            .is_some_and(|running| running.config_path == config_path)
+        {
+            if args.no_open || !recorded.open(&args.open_page) {
+                let running = BrowserLauncher {
+                    command: None,
+                    base_url: recorded.url.clone(),
+                    token: recorded.token.clone(),
+                };
+                eprintln!(
+                    "Open {} in your browser",
+                    running.page_url(&args.open_page)?
+                );
+            }
+            tracing::info!("Ghostty Config is already running at {}", recorded.url);
+            return Ok(());
+        }
+    }
+
+    // The machine whose config is edited decides which platform's options apply.
+    let mut platform = Platform::current();
+    if let Some(remote) = &remote {
+        let text = remote.fetch().await?;
+        if let Some(dir) = config_path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        write_atomic(&config_path, text)?;
+        match remote.platform().await {
+            Ok(remote_platform) => platform = remote_platform,
+            Err(e) => tracing::warn!("Couldn't tell the remote platform: {}", e),
+        }
+    }
+
+    load_category_overrides(&categories_path(&settings_path));
+    let settings = Settings::load(&settings_path);
 
-    // Load config schema from ghostty
-    tracing::info!("Discovering config options...");
-    let config_output = run_ghostty(&ghostty_path, &["+show-config", "--default", "--docs"])?;
-    let schema = parse_show_config(&config_output)?;
-    tracing::info!("Discovered {} config options", schema.options.len());
+    // Find ghostty binary: `--ghostty`, the one picked last time, or the
+    // installed one. Without one the built-in schema stands in for it.
+    let ghostty_path = match (&args.ghostty, &settings.ghostty) {
+        (None, Some(picked)) if picked.is_file() => Ok(picked.clone()),
+        _ => args.ghostty_path(),
+    }
+    .inspect(|path| tracing::info!("Found ghostty at: {}", path.display()))
+    .inspect_err(|e| tracing::warn!("{}", e))
+    .ok();
 
-    // Load themes
-    tracing::info!("Loading themes...");
-    let themes = load_themes()?;
+    // Load config schema, fonts, actions, and default keybinds from ghostty,
+    // while reading the theme files
+    let cache_path = discovery_cache::cache_path(&settings_path);
+    let theme_config_path = config_path.clone();
+    let (ghostty, themes) = tokio::join!(
+        Ghostty::discover(
+            ghostty_path,
+            &cache_path,
+            args.refresh,
+            args.schema_version.as_deref(),
+        ),
+        tokio::task::spawn_blocking(move || load_themes(&theme_config_path)),
+    );
+    let ghostty = ghostty?;
+    let themes = themes??;
     tracing::info!("Loaded {} themes", themes.len());
+    tracing::info!(
+        "{} config options, {} font families, {} actions, {} default keybinds",
+        ghostty.schema.options().len(),
+        ghostty.fonts.len(),
+        ghostty.actions.len(),
+        ghostty.default_keybinds.len()
+    );
 
-    // Load fonts
-    tracing::info!("Loading fonts...");
-    let fonts = load_fonts(&ghostty_path).unwrap_or_else(|e| {
-        tracing::warn!("Failed to load fonts: {}", e);
-        Vec::new()
+    // Read user config, falling back to safe mode if the file is damaged
+    let safe_mode = std::fs::read(&config_path)
+        .map(|bytes| detect_corruption(&bytes))
+        .unwrap_or_default();
+    let user_config = if safe_mode.is_empty() {
+        read_config(&config_path)?
+    } else {
+        for problem in &safe_mode {
+            tracing::warn!("Config file is damaged: {}", problem.message);
+        }
+        tracing::warn!("Starting in safe mode; fix the file at /editor");
+        UserConfig::new(config_path.clone())
+    };
+    // Load the team baseline, if any
+    let baseline = args.baseline.as_deref().and_then(|source| {
+        Baseline::load(BaselineSource::parse(source))
+            .inspect(|_| tracing::info!("Comparing against baseline {}", source))
+            .inspect_err(|e| tracing::warn!("Failed to load baseline: {}", e))
+            .ok()
     });
-    tracing::info!("Loaded {} font families", fonts.len());
 
-    // Load actions
-    let actions = load_actions(&ghostty_path).unwrap_or_default();
-    tracing::info!("Loaded {} actions", actions.len());
+    let theme_state = ThemeState::load(&theme_state_path(&settings_path));
+
+    let (events, _) = tokio::sync::broadcast::channel(events::EVENT_CAPACITY);
 
-    // Load default keybinds
-    let default_keybinds = load_keybinds(&ghostty_path).unwrap_or_default();
-    tracing::info!("Loaded {} default keybinds", default_keybinds.len());
+    let listener = bind(args.bind, args.port).await?;
+    let addr = listener.local_addr()?;
+    // A wildcard address can't be browsed to; open it through loopback.
+    let browse_addr = if args.bind.is_unspecified() {
+        SocketAddr::new(Ipv4Addr::LOCALHOST.into(), addr.port())
+    } else {
+        addr
+    };
+    if args.exposed() {
+        tracing::warn!(
+            "Listening on {} for other machines; only the link with the session token gets in",
+            addr
+        );
+    }
+    let auth_token = if args.no_auth {
+        tracing::warn!("Authentication is off: any local client can change the config");
+        None
+    } else {
+        Some(auth::generate_token()?)
+    };
+    let browser = BrowserLauncher {
+        command: args.browser.clone(),
+        base_url: format!("http://{}", browse_addr),
+        token: auth_token.clone(),
+    };
 
-    // Read user config
-    let config_path = default_config_path();
-    tracing::info!("Config file: {}", config_path.display());
-    let user_config = read_config(&config_path)?;
+    let instance = Instance {
+        pid: std::process::id(),
+        url: browser.base_url.clone(),
+        config_path: config_path.clone(),
+        token: auth_token.clone(),
+    };
+    if let Err(e) = instance.save(&instance_path) {
+        tracing::warn!("Failed to record running instance: {}", e);
+    }
 
     // Build shared state
+    let included = load_included(&user_config);
+    let configs: Vec<&UserConfig> = std::iter::once(&user_config)
+        .chain(included.iter())
+        .collect();
+    let history = History::open(history_path(), &configs);
+    // Edits a previous run didn't get to save, offered for restoring on the index page.
+    let pending_session = PendingSession::load(&session_path(&settings_path))
+        .filter(|session| session.config_path == user_config.file_path)
+        .and_then(|session| session.still_pending(&configs));
+    if let Some(session) = &pending_session {
+        tracing::info!(
+            "Found {} unsaved edits from the last session",
+            session.edits.len()
+        );
+    }
     let state = Arc::new(AppState {
-        schema,
+        ghostty: RwLock::new(Arc::new(ghostty)),
+        installations: RwLock::new(None),
+        refreshing: AtomicBool::new(false),
+        base_config: RwLock::new(user_config.clone()),
+        included: RwLock::new(included),
         user_config: RwLock::new(user_config),
-        themes,
-        fonts,
-        actions,
-        default_keybinds,
-        ghostty_path,
-        unsaved: RwLock::new(std::collections::HashSet::new()),
+        themes: RwLock::new(themes),
+        font_axes: RwLock::new(std::collections::HashMap::new()),
+        browser: browser.clone(),
+        unsaved: RwLock::new(Vec::new()),
+        revisions: std::sync::Mutex::new(Default::default()),
+        config_writes: tokio::sync::Mutex::new(()),
+        events,
+        reduced_motion: AtomicBool::new(false),
+        baseline: RwLock::new(baseline),
+        settings: RwLock::new(settings),
+        settings_path,
+        updates: RwLock::new(None),
+        safe_mode: RwLock::new(safe_mode),
+        validation: RwLock::new(None),
+        revalidate: tokio::sync::Notify::new(),
+        theme_state: RwLock::new(theme_state),
+        history: RwLock::new(history),
+        pending_session: RwLock::new(pending_session),
+        live_preview: args.live_preview,
+        shutdown: tokio::sync::Notify::new(),
+        auth_token,
+        exposed: args.exposed(),
+        remote,
+        platform,
+        autosave: RwLock::new(Default::default()),
+        autosave_due: tokio::sync::Notify::new(),
     });
 
+    // Keep the header's validation status current
+    live_validation::spawn(state.clone());
+
+    // Write edits as they settle while autosave is on
+    autosave::spawn(state.clone());
+
+    // Watch for edits made outside the UI
+    let _watcher = watcher::watch_config(state.clone(), config_path)
+        .inspect_err(|e| tracing::warn!("Failed to watch config file: {}", e))
+        .ok();
+
     // Build router
-    let app = routes::build_router(state);
-
-    let addr = "127.0.0.1:3456";
-    tracing::info!("Server starting at http://{}", addr);
-
-    // Open browser
-    let url = format!("http://{}", addr);
-    tokio::spawn(async move {
-        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-        if let Err(e) = open::that(&url) {
-            tracing::warn!("Failed to open browser: {}", e);
-            eprintln!("Open http://{} in your browser", addr);
-        }
-    });
+    let app = routes::build_router(state.clone());
+
+    tracing::info!("Server starting at http://{}", browse_addr);
+
+    // Open browser. The link carries the session token, so print it when the
+    // browser isn't opened for the user.
+    let link = browser.page_url(&args.open_page)?;
+    if args.no_open {
+        eprintln!("Open {} in your browser", link);
+    } else {
+        let page = args.open_page.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            if let Err(e) = browser.open(&page) {
+                tracing::warn!("Failed to open browser: {}", e);
+                eprintln!("Open {} in your browser", link);
+            }
+        });
+    }
 
     // Start server
-    let listener = tokio::net::TcpListener::bind(addr).await?;
     tracing::info!("Listening on http://{}", addr);
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal(state))
+        .await?;
+    instance.remove(&instance_path);
+    scratch::remove_scratch_dir();
 
     Ok(())
 }
+
+/// Listen on `port`, or on the default port, moving to a free one if another
+/// program has it. A `--port` that is taken is an error.
+async fn bind(ip: IpAddr, port: Option<u16>) -> std::io::Result<TcpListener> {
+    let addr = SocketAddr::new(ip, port.unwrap_or(DEFAULT_PORT));
+    match TcpListener::bind(addr).await {
+        Err(e) if e.kind() == ErrorKind::AddrInUse && port.is_none() => {
+            tracing::warn!("Port {} is in use; picking a free one", DEFAULT_PORT);
+            TcpListener::bind(SocketAddr::new(ip, 0)).await
+        }
+        result => result,
+    }
+}
+
+/// Resolves on ctrl-c, SIGTERM (closing the console on Windows), or
+/// `POST /api/shutdown`, after telling open pages so their event streams close
+/// and the server can finish.
+async fn shutdown_signal(state: SharedState) {
+    #[cfg(unix)]
+    let terminate = async {
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(windows)]
+    let terminate = async {
+        match tokio::signal::windows::ctrl_close() {
+            Ok(mut close) => {
+                close.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to listen for console close: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate => {}
+        _ = state.shutdown.notified() => {}
+    }
+
+    let unsaved = state.unsaved_count().await;
+    if unsaved > 0 {
+        tracing::warn!("Shutting down with {} unsaved changes", unsaved);
+    } else {
+        tracing::info!("Shutting down");
+    }
+    state.publish(AppEvent::ShuttingDown);
+}
@@ -0,0 +1,150 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::config::file_io::{copy_atomic, write_atomic};
+use crate::error::AppError;
+
+/// Extension of profile files in the profiles directory.
+const PROFILE_EXT: &str = "conf";
+
+/// A named config snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub modified: Option<SystemTime>,
+}
+
+/// `profiles/` next to `settings.json` in this tool's config directory.
+pub fn profiles_dir(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("profiles")
+}
+
+/// Profile names become file names, so keep them to letters, digits, `-` and `_`.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn profile_path(dir: &Path, name: &str) -> Result<PathBuf, AppError> {
+    if !is_valid_name(name) {
+        return Err(AppError::Config(format!(
+            "Invalid profile name '{}': use letters, digits, - and _",
+            name
+        )));
+    }
+    Ok(dir.join(format!("{}.{}", name, PROFILE_EXT)))
+}
+
+/// Saved profiles, sorted by name. A missing directory means none.
+pub fn list_profiles(dir: &Path) -> Vec<Profile> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<Profile> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != PROFILE_EXT {
+                return None;
+            }
+            let name = path.file_stem()?.to_str()?.to_string();
+            is_valid_name(&name).then(|| Profile {
+                name,
+                modified: entry.metadata().and_then(|m| m.modified()).ok(),
+            })
+        })
+        .collect();
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    profiles
+}
+
+/// Store `text` as profile `name`, replacing any profile of that name.
+pub fn save_profile(dir: &Path, name: &str, text: &str) -> Result<(), AppError> {
+    let path = profile_path(dir, name)?;
+    fs::create_dir_all(dir)?;
+    write_atomic(&path, text)?;
+    Ok(())
+}
+
+pub fn read_profile(dir: &Path, name: &str) -> Result<String, AppError> {
+    let path = profile_path(dir, name)?;
+    if !path.is_file() {
+        return Err(AppError::Config(format!("No profile named '{}'", name)));
+    }
+    Ok(fs::read_to_string(path)?)
+}
+
+pub fn delete_profile(dir: &Path, name: &str) -> Result<(), AppError> {
+    let path = profile_path(dir, name)?;
+    if path.is_file() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Copy the config file to `<config>.bak` before a profile replaces it.
+///
+/// Returns the backup path, or `None` if there was no config file yet.
+pub fn backup_config(config_path: &Path) -> Result<Option<PathBuf>, AppError> {
+    if !config_path.is_file() {
+        return Ok(None);
+    }
+    let mut backup = config_path.as_os_str().to_owned();
+    backup.push(".bak");
+    let backup = PathBuf::from(backup);
+    copy_atomic(config_path, &backup)?;
+    Ok(Some(backup))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_names() {
+        assert!(is_valid_name("work"));
+        assert!(is_valid_name("live_stream-2"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("../config"));
+        assert!(!is_valid_name("my profile"));
+    }
+
+    #[test]
+    fn test_profile_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let profiles = dir.path().join("profiles");
+        assert!(list_profiles(&profiles).is_empty());
+
+        save_profile(&profiles, "work", "font-size = 12\n").unwrap();
+        save_profile(&profiles, "streaming", "font-size = 18\n").unwrap();
+        fs::write(profiles.join("notes.txt"), "ignored").unwrap();
+
+        let names: Vec<String> = list_profiles(&profiles)
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["streaming", "work"]);
+        assert_eq!(read_profile(&profiles, "work").unwrap(), "font-size = 12\n");
+        assert!(read_profile(&profiles, "missing").is_err());
+        assert!(save_profile(&profiles, "../evil", "").is_err());
+
+        delete_profile(&profiles, "work").unwrap();
+        assert_eq!(list_profiles(&profiles).len(), 1);
+    }
+
+    #[test]
+    fn test_backup_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config");
+        assert_eq!(backup_config(&config).unwrap(), None);
+
+        fs::write(&config, "theme = x\n").unwrap();
+        let backup = backup_config(&config).unwrap().unwrap();
+        assert_eq!(backup, dir.path().join("config.bak"));
+        assert_eq!(fs::read_to_string(backup).unwrap(), "theme = x\n");
+    }
+}
@@ -0,0 +1,177 @@
+//! A pseudo-terminal running the user's shell, for the live terminal preview.
+//!
+//! Only Unix has the PTYs this needs; elsewhere `Pty::spawn` fails and the preview
+//! keeps showing its sample scenes.
+
+use std::fs::File;
+use std::io;
+#[cfg(unix)]
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+#[cfg(unix)]
+use std::process::Stdio;
+use std::process::{Child, Command};
+
+/// A child process attached to the slave side of a PTY. Dropping it kills the child.
+pub struct Pty {
+    /// The master side: reads give the terminal's output, writes are typed input.
+    pub master: File,
+    child: Child,
+}
+
+#[cfg(unix)]
+impl Pty {
+    /// Start `command` on a new PTY of `cols` by `rows`.
+    pub fn spawn(mut command: Command, cols: u16, rows: u16) -> io::Result<Pty> {
+        let (master, slave) = open_pty()?;
+        set_size(master.as_raw_fd(), cols, rows)?;
+
+        let slave_fd = slave.as_raw_fd();
+        command
+            .stdin(Stdio::from(slave.try_clone()?))
+            .stdout(Stdio::from(slave.try_clone()?))
+            .stderr(Stdio::from(slave))
+            .env("TERM", "xterm-256color")
+            .env("COLORTERM", "truecolor");
+        // SAFETY: only async-signal-safe calls between fork and exec.
+        unsafe {
+            command.pre_exec(move || {
+                // A new session with the PTY as its controlling terminal, so the
+                // shell gets job control and ctrl+c.
+                if libc::setsid() == -1 || libc::ioctl(slave_fd, libc::TIOCSCTTY as _, 0) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        let child = command.spawn()?;
+        Ok(Pty {
+            master: File::from(master),
+            child,
+        })
+    }
+
+    pub fn resize(&self, cols: u16, rows: u16) -> io::Result<()> {
+        set_size(self.master.as_raw_fd(), cols, rows)
+    }
+}
+
+#[cfg(not(unix))]
+impl Pty {
+    pub fn spawn(_command: Command, _cols: u16, _rows: u16) -> io::Result<Pty> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the live preview needs a Unix pseudo-terminal",
+        ))
+    }
+
+    pub fn resize(&self, _cols: u16, _rows: u16) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for Pty {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(unix)]
+fn open_pty() -> io::Result<(OwnedFd, OwnedFd)> {
+    let (mut master, mut slave) = (-1, -1);
+    // SAFETY: openpty writes two new descriptors, which are owned from here on.
+    let result = unsafe {
+        libc::openpty(
+            &mut master,
+            &mut slave,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    if result == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: both descriptors were just opened and nothing else owns them.
+    unsafe { Ok((OwnedFd::from_raw_fd(master), OwnedFd::from_raw_fd(slave))) }
+}
+
+#[cfg(unix)]
+fn set_size(fd: i32, cols: u16, rows: u16) -> io::Result<()> {
+    let size = libc::winsize {
+        ws_row: rows,
+        ws_col: cols,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: TIOCSWINSZ reads a winsize, which `size` is.
+    if unsafe { libc::ioctl(fd, libc::TIOCSWINSZ as _, &size) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// The process Ghostty would start for the `command` option: a `direct:` command
+/// runs as is, anything else through the shell. Without one, the login shell.
+pub fn shell_command(command: Option<&str>) -> Command {
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+    match command.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(command) => {
+            if let Some(direct) = command.strip_prefix("direct:") {
+                let mut words = direct.split_whitespace();
+                let mut cmd = Command::new(words.next().unwrap_or(&shell));
+                cmd.args(words);
+                cmd
+            } else {
+                let command = command.strip_prefix("shell:").unwrap_or(command);
+                let mut cmd = Command::new(&shell);
+                cmd.arg("-c").arg(command);
+                cmd
+            }
+        }
+        None => Command::new(shell),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn args(cmd: &Command) -> Vec<String> {
+        cmd.get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_shell_command() {
+        let direct = shell_command(Some("direct:htop -d 10"));
+        assert_eq!(direct.get_program(), "htop");
+        assert_eq!(args(&direct), vec!["-d", "10"]);
+
+        let shell = shell_command(Some("shell:fish --login"));
+        assert_eq!(args(&shell), vec!["-c", "fish --login"]);
+        assert_eq!(args(&shell_command(Some("zsh"))), vec!["-c", "zsh"]);
+        assert!(args(&shell_command(None)).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_reads_output() {
+        let mut cmd = Command::new("/bin/sh");
+        cmd.arg("-c").arg("stty size; echo ready");
+        let mut pty = Pty::spawn(cmd, 80, 24).unwrap();
+        let mut output = String::new();
+        let mut buf = [0; 256];
+        while !output.contains("ready") {
+            match pty.master.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => output.push_str(&String::from_utf8_lossy(&buf[..n])),
+            }
+        }
+        assert!(output.contains("24 80"), "{output}");
+    }
+}
@@ -0,0 +1,336 @@
+//! Editing the config of another machine. It is copied over SSH into a local
+//! working copy, which the UI edits like any config file, and copied back when
+//! saved. The schema, fonts, and validation still come from the local Ghostty.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::config::os_shortcuts::Platform;
+use crate::error::{AppError, CliError, CliErrorKind};
+
+/// Where Ghostty looks for its config on Linux and macOS, and so the path used
+/// on the remote machine unless `--config` says otherwise.
+pub const DEFAULT_REMOTE_PATH: &str = "~/.config/ghostty/config";
+
+/// Seconds to wait for the SSH connection before giving up.
+const CONNECT_TIMEOUT_SECS: &str = "10";
+
+/// A config file on another machine, reached with the system's `ssh`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteTarget {
+    /// `host` or `user@host`, as `ssh` takes it; aliases from `~/.ssh/config` work.
+    pub host: String,
+    pub port: Option<u16>,
+    /// The path on the remote machine. A leading `~/` is its home directory.
+    pub path: String,
+}
+
+impl RemoteTarget {
+    /// Parse `--remote`: `user@host`, or `ssh://user@host:2222/path/to/config`.
+    /// `path` (`--config`) names the file when the URL doesn't.
+    pub fn parse(spec: &str, path: Option<&str>) -> Result<Self, AppError> {
+        let invalid = || {
+            AppError::Config(format!(
+                "Invalid remote '{}': expected user@host or ssh://user@host:port/path",
+                spec
+            ))
+        };
+        let (authority, url_path) = match spec.trim().strip_prefix("ssh://") {
+            Some(rest) => match rest.split_once('/') {
+                Some((authority, path)) => (authority, Some(path)),
+                None => (rest, None),
+            },
+            None => (spec.trim(), None),
+        };
+        // A `~` right after the host's slash is the remote home; anything else is absolute.
+        let url_path = url_path.filter(|p| !p.is_empty()).map(|p| {
+            if p.starts_with('~') {
+                p.to_string()
+            } else {
+                format!("/{}", p)
+            }
+        });
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port.parse().map_err(|_| invalid())?)),
+            None => (authority, None),
+        };
+        if host.is_empty() || host.starts_with('-') || host.contains(char::is_whitespace) {
+            return Err(invalid());
+        }
+        let path = url_path
+            .or_else(|| path.map(String::from))
+            .unwrap_or_else(|| DEFAULT_REMOTE_PATH.to_string());
+        Ok(RemoteTarget {
+            host: host.to_string(),
+            port,
+            path,
+        })
+    }
+
+    /// `user@host:path`, for messages.
+    pub fn display(&self) -> String {
+        format!("{}:{}", self.host, self.path)
+    }
+
+    /// The local working copy, under `dir`, named after the host and path so each
+    /// remote config keeps its own. It is named `config` like Ghostty's, so themes
+    /// next to it are found the same way.
+    pub fn working_copy(&self, dir: &Path) -> PathBuf {
+        let name: String = format!(
+            "{}-{}-{}",
+            self.host,
+            self.port.unwrap_or(22),
+            self.path.trim_start_matches(['~', '/'])
+        )
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '@' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+        dir.join(name).join("config")
+    }
+
+    /// The config's text on the remote machine; empty when it doesn't exist yet.
+    pub async fn fetch(&self) -> Result<String, AppError> {
+        let path = self.shell_path();
+        let output = self
+            .run(&format!("if [ -e {0} ]; then cat {0}; fi", path), None)
+            .await?;
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    }
+
+    /// The platform of the remote machine, from `uname -s`.
+    pub async fn platform(&self) -> Result<Platform, AppError> {
+        let output = self.run("uname -s", None).await?;
+        Ok(platform_of_uname(&String::from_utf8_lossy(&output)))
+    }
+
+    /// Replace the config on the remote machine with `text`, creating its directory
+    /// if needed. The file is written in place, so a symlinked config stays linked.
+    pub async fn push(&self, text: &str) -> Result<(), AppError> {
+        self.run(&self.push_script(text.len()), Some(text)).await?;
+        Ok(())
+    }
+
+    /// The script `push` runs for `len` bytes of input. They go to a private file
+    /// next to the config first, and only once all of them arrived is the config
+    /// overwritten from it, so a dropped connection leaves the config as it was.
+    fn push_script(&self, len: usize) -> String {
+        format!(
+            "mkdir -p \"$(dirname {0})\" && t={0}.tmp.$$ && \
+             if (umask 077 && cat > \"$t\") && [ $(wc -c < \"$t\") -eq {1} ]; \
+             then cat \"$t\" > {0} && rm -f \"$t\"; \
+             else rm -f \"$t\"; echo 'The config was not fully received' >&2; exit 1; fi",
+            self.shell_path(),
+            len
+        )
+    }
+
+    /// The path as a word for the remote shell, with `~/` left for it to expand.
+    fn shell_path(&self) -> String {
+        match self.path.strip_prefix("~/") {
+            Some(rest) => format!("\"$HOME\"/{}", shell_quote(rest)),
+            None if self.path == "~" => "\"$HOME\"".to_string(),
+            None => shell_quote(&self.path),
+        }
+    }
+
+    /// `ssh` arguments before the remote command. Batch mode fails instead of
+    /// prompting, since saves happen long after startup; use keys or an agent.
+    fn ssh_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "-o",
+            "BatchMode=yes",
+            "-o",
+            &format!("ConnectTimeout={}", CONNECT_TIMEOUT_SECS),
+        ]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+        if let Some(port) = self.port {
+            args.extend(["-p".to_string(), port.to_string()]);
+        }
+        args.extend([self.host.clone(), "--".to_string()]);
+        args
+    }
+
+    /// Run `script` on the remote machine, with `stdin` as its input, and return
+    /// what it printed.
+    async fn run(&self, script: &str, stdin: Option<&str>) -> Result<Vec<u8>, AppError> {
+        let mut child = Command::new("ssh")
+            .args(self.ssh_args())
+            .arg(script)
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| CliError::new(CliErrorKind::Spawn, format!("Failed to run ssh: {}", e)))?;
+        if let (Some(text), Some(mut input)) = (stdin, child.stdin.take()) {
+            input.write_all(text.as_bytes()).await?;
+        }
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(CliError {
+                exit_code: output.status.code(),
+                stderr: stderr.clone(),
+                ..CliError::new(
+                    CliErrorKind::Failed,
+                    format!("SSH to {} failed: {}", self.host, stderr),
+                )
+            }
+            .into());
+        }
+        Ok(output.stdout)
+    }
+}
+
+/// The platform of a remote machine from its `uname -s`: macOS for `Darwin`;
+/// Ghostty's other builds all use the Linux options.
+fn platform_of_uname(name: &str) -> Platform {
+    if name.trim() == "Darwin" {
+        Platform::MacOS
+    } else {
+        Platform::Linux
+    }
+}
+
+/// `value` single-quoted for a POSIX shell.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote() {
+        let target = RemoteTarget::parse("me@devbox", None).unwrap();
+        assert_eq!(target.host, "me@devbox");
+        assert_eq!(target.port, None);
+        assert_eq!(target.path, DEFAULT_REMOTE_PATH);
+
+        let target = RemoteTarget::parse("devbox", Some("/etc/ghostty/config")).unwrap();
+        assert_eq!(target.path, "/etc/ghostty/config");
+
+        let target = RemoteTarget::parse("ssh://me@devbox:2222/~/dotfiles/ghostty", None).unwrap();
+        assert_eq!(target.host, "me@devbox");
+        assert_eq!(target.port, Some(2222));
+        assert_eq!(target.path, "~/dotfiles/ghostty");
+        assert_eq!(
+            RemoteTarget::parse("ssh://devbox/srv/config", None)
+                .unwrap()
+                .path,
+            "/srv/config"
+        );
+
+        assert!(RemoteTarget::parse("", None).is_err());
+        assert!(RemoteTarget::parse("-oProxyCommand=x", None).is_err());
+        assert!(RemoteTarget::parse("devbox:ssh", None).is_err());
+    }
+
+    #[test]
+    fn test_platform_of_uname() {
+        assert_eq!(platform_of_uname("Darwin\n"), Platform::MacOS);
+        assert_eq!(platform_of_uname("Linux\n"), Platform::Linux);
+        assert_eq!(platform_of_uname("FreeBSD"), Platform::Linux);
+    }
+
+    #[test]
+    fn test_shell_path() {
+        let target = |path: &str| RemoteTarget {
+            host: "devbox".to_string(),
+            port: None,
+            path: path.to_string(),
+        };
+        assert_eq!(
+            target("~/.config/ghostty/config").shell_path(),
+            "\"$HOME\"/'.config/ghostty/config'"
+        );
+        assert_eq!(target("/srv/it's").shell_path(), "'/srv/it'\\''s'");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_push_script() {
+        use std::io::Write;
+        use std::process::Command;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ghostty/config");
+        let target = RemoteTarget {
+            host: "devbox".to_string(),
+            port: None,
+            path: path.to_string_lossy().into_owned(),
+        };
+        let push = |script: String, text: &str| {
+            let mut child = Command::new("sh")
+                .arg("-c")
+                .arg(script)
+                .stdin(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .unwrap();
+            child
+                .stdin
+                .take()
+                .unwrap()
+                .write_all(text.as_bytes())
+                .unwrap();
+            child.wait().unwrap().success()
+        };
+
+        let text = "font-size = 14\n";
+        assert!(push(target.push_script(text.len()), text));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), text);
+
+        // Input cut short leaves the config alone, and no temporary file behind.
+        assert!(!push(target.push_script(100), "font-size = 1"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), text);
+        assert_eq!(
+            std::fs::read_dir(path.parent().unwrap()).unwrap().count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_ssh_args() {
+        let target = RemoteTarget::parse("ssh://me@devbox:2222", None).unwrap();
+        let args = target.ssh_args();
+        assert_eq!(
+            args,
+            vec![
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "ConnectTimeout=10",
+                "-p",
+                "2222",
+                "me@devbox",
+                "--"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_working_copy() {
+        let target = RemoteTarget::parse("me@devbox", None).unwrap();
+        assert_eq!(
+            target.working_copy(Path::new("/cache")),
+            Path::new("/cache/me@devbox-22-.config_ghostty_config/config")
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Optimistic versioning of the in-memory config, so a tab that fell behind
+//! another one can't overwrite what it hasn't seen.
+//!
+//! Every response carries the current [`AppState::revision`] in [`HEADER`], and
+//! pages also get it as a `config-revision` meta tag, the revision they were
+//! rendered at. Writes send back the revision their page shows. One is refused
+//! with 409 Conflict when someone else changed the config since, and with 428
+//! when it has no revision. Pages also send a random id in [`CLIENT_HEADER`], so
+//! their own edits don't count against them.
+//!
+//! A write holds [`AppState::config_writes`] from its check until it's done, so
+//! two writes made on the same revision can't both pass the check.
+
+use std::collections::VecDeque;
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{Html, IntoResponse, Response};
+
+use crate::app_state::{AppState, SharedState};
+use crate::routes::config_api::toast_html;
+
+/// Header carrying the revision, both ways.
+pub const HEADER: &str = "x-config-revision";
+/// Header naming the page a write comes from, random for each page load.
+pub const CLIENT_HEADER: &str = "x-config-client";
+
+tokio::task_local! {
+    /// The page behind the request being handled, for [`current_client`].
+    static CLIENT: Option<String>;
+}
+
+/// How many of the latest changes [`Revisions`] remembers the writer of.
+const KEPT_WRITERS: usize = 1000;
+
+/// Who made each of the latest changes to the in-memory config.
+#[derive(Debug, Default)]
+pub struct Revisions {
+    /// The revision before the first of `writers`.
+    oldest: u64,
+    /// The page behind each revision after `oldest`; `None` for changes no page
+    /// made, like a reload from disk or an API call.
+    writers: VecDeque<Option<String>>,
+}
+
+impl Revisions {
+    pub fn current(&self) -> u64 {
+        self.oldest + self.writers.len() as u64
+    }
+
+    pub fn bump(&mut self, client: Option<String>) {
+        self.writers.push_back(client);
+        if self.writers.len() > KEPT_WRITERS {
+            self.writers.pop_front();
+            self.oldest += 1;
+        }
+    }
+
+    /// Whether anyone but `client` changed the config since `revision`. A revision
+    /// this run never had, e.g. from before a restart, or one too old to know who
+    /// changed it since, is always out of date.
+    pub fn changed_since(&self, revision: u64, client: Option<&str>) -> bool {
+        let Some(since) = revision
+            .checked_sub(self.oldest)
+            .and_then(|r| usize::try_from(r).ok())
+            .filter(|&r| r <= self.writers.len())
+        else {
+            return true;
+        };
+        self.writers
+            .iter()
+            .skip(since)
+            .any(|writer| writer.is_none() || writer.as_deref() != client)
+    }
+}
+
+/// The page behind the request being handled, if it said.
+pub fn current_client() -> Option<String> {
+    CLIENT.try_with(Clone::clone).ok().flatten()
+}
+
+/// Writes that don't depend on what the page shows of the config, so they go
+/// through without a revision: previews, this tool's own settings, and files
+/// other than the config.
+const UNVERSIONED: &[&str] = &[
+    "/api/open",
+    "/api/shutdown",
+    "/api/autosave",
+    "/api/settings/annotate",
+    "/api/settings/platforms",
+    "/api/save-targets",
+    "/api/pins",
+    "/api/scratch",
+    "/api/starter/preview",
+    "/api/session/discard",
+    "/api/themes/favorite",
+    "/api/themes/rescan",
+    "/api/themes/custom/preview",
+    "/api/palette/generate/image",
+    "/api/keybinds/normalize",
+    "/api/import/preview",
+    "/api/import/keybinds",
+    "/api/migrate",
+    "/api/share",
+    "/api/baseline",
+    "/api/baseline/refresh",
+    "/api/baseline/ignore",
+    "/api/files/upload",
+    "/api/accessibility/reduced-motion",
+    "/api/ghostty",
+    "/api/refresh",
+    "/api/safe-mode/restore",
+];
+
+/// Refuse writes from pages showing an outdated config, and tell every client the
+/// current revision.
+pub async fn guard(State(state): State<SharedState>, request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let client = request
+        .headers()
+        .get(CLIENT_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| !v.is_empty())
+        .map(str::to_string);
+    // Held until the write is done; see the module docs.
+    let mut writing = None;
+    if needs_revision(&method, &path) {
+        writing = Some(state.config_writes.lock().await);
+        let given = request
+            .headers()
+            .get(HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok());
+        let refused = match given {
+            None => Some((
+                StatusCode::PRECONDITION_REQUIRED,
+                format!(
+                    "Changes need the {} header from a response, to tell the config hasn't changed meanwhile",
+                    HEADER
+                ),
+            )),
+            Some(revision) if state.changed_since(revision, client.as_deref()) => Some((
+                StatusCode::CONFLICT,
+                "Not applied: the config changed in another tab or window since this page loaded"
+                    .to_string(),
+            )),
+            Some(_) => None,
+        };
+        if let Some((status, message)) = refused {
+            let mut response = (
+                status,
+                [
+                    ("HX-Retarget", "#toast-container"),
+                    ("HX-Reswap", "innerHTML"),
+                ],
+                Html(toast_html(&message, true)),
+            )
+                .into_response();
+            stamp(&mut response, &state);
+            return response;
+        }
+    }
+
+    // A page shows the config as it was when rendering started.
+    let rendered_at = state.revision();
+    let is_page = method == Method::GET && is_page(&path);
+    let mut response = CLIENT.scope(client, next.run(request)).await;
+    drop(writing);
+    if is_page && is_html(&response) {
+        response = with_meta(response, rendered_at).await;
+    }
+    stamp(&mut response, &state);
+    response
+}
+
+/// Writes to `/api`, except the [`UNVERSIONED`] ones and profiles, which are
+/// kept apart from the config until one is activated.
+pub(crate) fn needs_revision(method: &Method, path: &str) -> bool {
+    if method == Method::GET || method == Method::HEAD || !path.starts_with("/api/") {
+        return false;
+    }
+    if path.starts_with("/api/updates/") {
+        return false;
+    }
+    if path.starts_with("/api/profiles") {
+        return path.ends_with("/activate");
+    }
+    !UNVERSIONED.contains(&path)
+}
+
+fn is_page(path: &str) -> bool {
+    !["/api/", "/static/", "/events", "/ws"]
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+}
+
+fn is_html(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/html"))
+}
+
+fn stamp(response: &mut Response, state: &AppState) {
+    response
+        .headers_mut()
+        .insert(HEADER, HeaderValue::from(state.revision()));
+}
+
+/// `response` with the revision a page was rendered at added to its `<head>`.
+async fn with_meta(response: Response, revision: u64) -> Response {
+    let (parts, body) = response.into_parts();
+    match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => {
+            let html = insert_meta(&String::from_utf8_lossy(&bytes), revision);
+            let mut response = Response::from_parts(parts, Body::from(html));
+            response.headers_mut().remove(header::CONTENT_LENGTH);
+            response
+        }
+        Err(e) => {
+            tracing::warn!("Failed to read a page to add its revision: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to render the page",
+            )
+                .into_response()
+        }
+    }
+}
+
+fn insert_meta(html: &str, revision: u64) -> String {
+    let meta = format!(r#"<meta name="config-revision" content="{}">"#, revision);
+    match html.find("</head>") {
+        Some(at) => format!("{}{}\n{}", &html[..at], meta, &html[at..]),
+        None => html.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_since() {
+        let mut revisions = Revisions::default();
+        assert!(!revisions.changed_since(0, None));
+        revisions.bump(Some("tab-a".to_string()));
+        revisions.bump(Some("tab-a".to_string()));
+        assert_eq!(revisions.current(), 2);
+
+        // A page's own edits don't make it out of date; anyone else's do.
+        assert!(!revisions.changed_since(0, Some("tab-a")));
+        assert!(revisions.changed_since(0, Some("tab-b")));
+        assert!(revisions.changed_since(0, None));
+        assert!(!revisions.changed_since(2, Some("tab-b")));
+
+        // Reloads from disk count against every page.
+        revisions.bump(None);
+        assert!(revisions.changed_since(2, Some("tab-a")));
+        assert!(!revisions.changed_since(3, None));
+
+        // From before a restart.
+        assert!(revisions.changed_since(9, Some("tab-a")));
+
+        // Only the latest writers are kept; older revisions count as out of date.
+        for _ in 0..KEPT_WRITERS {
+            revisions.bump(Some("tab-a".to_string()));
+        }
+        assert_eq!(revisions.current(), 3 + KEPT_WRITERS as u64);
+        assert_eq!(revisions.writers.len(), KEPT_WRITERS);
+        assert!(revisions.changed_since(2, Some("tab-a")));
+        assert!(!revisions.changed_since(3, Some("tab-a")));
+        assert!(revisions.changed_since(3, Some("tab-b")));
+    }
+
+    #[test]
+    fn test_needs_revision() {
+        assert!(needs_revision(&Method::PUT, "/api/config/font-size"));
+        assert!(needs_revision(&Method::PUT, "/api/raw"));
+        assert!(needs_revision(&Method::POST, "/api/save"));
+        assert!(needs_revision(&Method::POST, "/api/profiles/work/activate"));
+
+        assert!(!needs_revision(&Method::GET, "/api/config/font-size"));
+        assert!(!needs_revision(&Method::POST, "/api/keybinds/normalize"));
+        assert!(!needs_revision(&Method::POST, "/api/updates/check"));
+        assert!(!needs_revision(&Method::PUT, "/api/profiles/work"));
+        assert!(!needs_revision(&Method::POST, "/api/shutdown"));
+    }
+
+    #[test]
+    fn test_insert_meta() {
+        assert_eq!(
+            insert_meta("<html><head><title>x</title></head><body></body></html>", 7),
+            "<html><head><title>x</title><meta name=\"config-revision\" content=\"7\">\n</head><body></body></html>"
+        );
+        assert_eq!(insert_meta("<div>fragment</div>", 7), "<div>fragment</div>");
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::atomic::Ordering;
+
+use axum::extract::State;
+use axum::response::{Html, IntoResponse, Response};
+
+use crate::app_state::SharedState;
+use crate::events::AppEvent;
+
+/// GET /api/accessibility/reduced-motion — the header toggle, reflecting the current mode.
+///
+/// Also fires a `reduced-motion` event on the page so it can apply the mode on load.
+pub async fn reduced_motion(State(state): State<SharedState>) -> Response {
+    toggle_response(state.reduced_motion.load(Ordering::Relaxed))
+}
+
+/// POST /api/accessibility/reduced-motion — switch reduced-motion mode on or off.
+pub async fn toggle_reduced_motion(State(state): State<SharedState>) -> Response {
+    let enabled = !state.reduced_motion.fetch_xor(true, Ordering::Relaxed);
+    state.publish(AppEvent::ReducedMotion { enabled });
+    toggle_response(enabled)
+}
+
+fn toggle_response(enabled: bool) -> Response {
+    let trigger = format!(r#"{{"reduced-motion":{{"enabled":{}}}}}"#, enabled);
+    ([("HX-Trigger", trigger)], Html(render_toggle(enabled))).into_response()
+}
+
+/// Header button that toggles reduced motion, swapped in place when pressed.
+pub fn render_toggle(enabled: bool) -> String {
+    let (label, class) = if enabled {
+        (
+            "Motion off",
+            "bg-indigo-50 border-indigo-300 text-indigo-700",
+        )
+    } else {
+        ("Motion on", "bg-white border-gray-300 text-gray-700")
+    };
+    format!(
+        r#"<button id="reduced-motion-toggle" type="button" hx-post="/api/accessibility/reduced-motion" hx-swap="outerHTML" aria-pressed="{enabled}" aria-label="Reduce motion" title="Turn off animated toasts and the blinking preview cursor" class="px-3 py-2 text-sm font-medium border rounded-lg hover:bg-gray-50 focus:outline-none focus-visible:ring-2 focus-visible:ring-indigo-500 {class}">{label}</button>"#,
+        enabled = enabled,
+        class = class,
+        label = label,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_reflects_state() {
+        let on = render_toggle(true);
+        assert!(on.contains(r#"aria-pressed="true""#));
+        assert!(on.contains("Motion off"));
+
+        let off = render_toggle(false);
+        assert!(off.contains(r#"aria-pressed="false""#));
+        assert!(off.contains(r#"hx-post="/api/accessibility/reduced-motion""#));
+    }
+}
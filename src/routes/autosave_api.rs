@@ -0,0 +1,96 @@
+use axum::extract::State;
+use axum::response::Html;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use super::config_api::escape_html;
+use crate::app_state::SharedState;
+use crate::autosave::AutosaveMode;
+use crate::error::AppError;
+use crate::events::AppEvent;
+
+#[derive(Deserialize, ToSchema)]
+pub struct AutosaveForm {
+    /// See `AutosaveMode::slug`.
+    pub mode: String,
+}
+
+/// GET /api/autosave — the header's autosave picker, reflecting the current mode.
+#[utoipa::path(
+    get,
+    path = "/api/autosave",
+    tag = "Config",
+    summary = "Picker for autosave: off, save once edits settle, or save and apply",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn autosave(State(state): State<SharedState>) -> Html<String> {
+    Html(render_picker(*state.autosave.read().await))
+}
+
+/// POST /api/autosave — switch autosave for this run. Turning it on writes any
+/// edits already pending once they settle.
+#[utoipa::path(
+    post,
+    path = "/api/autosave",
+    tag = "Config",
+    summary = "Switch autosave (`mode`: `off`, `save`, or `apply`) for this run",
+    request_body(content = AutosaveForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn set_autosave(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<AutosaveForm>,
+) -> Result<Html<String>, AppError> {
+    let mode = AutosaveMode::parse(&form.mode)
+        .ok_or_else(|| AppError::Config(format!("Unknown autosave mode: {}", form.mode)))?;
+    *state.autosave.write().await = mode;
+    if mode != AutosaveMode::Off {
+        state.autosave_due.notify_one();
+    }
+    state.publish(AppEvent::Autosave {
+        mode: mode.slug().to_string(),
+    });
+    Ok(Html(render_picker(mode)))
+}
+
+/// Header select for the autosave mode, swapped in place when changed.
+pub fn render_picker(mode: AutosaveMode) -> String {
+    let class = if mode == AutosaveMode::Off {
+        "bg-white border-gray-300 text-gray-700"
+    } else {
+        "bg-indigo-50 border-indigo-300 text-indigo-700"
+    };
+    let options: String = AutosaveMode::ALL
+        .into_iter()
+        .map(|m| {
+            format!(
+                r#"<option value="{}"{}>{}</option>"#,
+                m.slug(),
+                if m == mode { " selected" } else { "" },
+                escape_html(m.label())
+            )
+        })
+        .collect();
+    format!(
+        r#"<select id="autosave-toggle" name="mode" hx-post="/api/autosave" hx-swap="outerHTML" aria-label="Autosave" title="Write edits to disk shortly after the last one, instead of waiting for Save" class="px-2 py-2 text-sm font-medium border rounded-lg focus:outline-none focus-visible:ring-2 focus-visible:ring-indigo-500 {class}">{options}</select>"#,
+        class = class,
+        options = options,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_picker_reflects_mode() {
+        let off = render_picker(AutosaveMode::Off);
+        assert!(off.contains(r#"<option value="off" selected>"#));
+        assert!(off.contains("bg-white"));
+
+        let apply = render_picker(AutosaveMode::Apply);
+        assert!(apply.contains(r#"<option value="apply" selected>Autosave &amp; apply</option>"#));
+        assert!(apply.contains("bg-indigo-50"));
+        assert!(!apply.contains(r#"value="off" selected"#));
+    }
+}
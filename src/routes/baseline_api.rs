@@ -0,0 +1,276 @@
+use axum::extract::State;
+use axum::response::Html;
+use serde::Deserialize;
+
+use super::config_api::{escape_html, publish_toast, toast_html};
+use crate::app_state::SharedState;
+use crate::config::baseline::{Baseline, BaselineSource};
+use crate::config::categorize::categorize_key;
+use crate::config::diff::{ChangeKind, KeyChange};
+use crate::error::AppError;
+
+/// GET /api/baseline — list every key that deviates from the team baseline.
+pub async fn deviations(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let user_config = state.user_config.read().await;
+    let baseline = state.baseline.read().await;
+    let Some(baseline) = baseline.as_ref() else {
+        return Ok(Html(
+            r#"<div class="bg-white rounded-xl border border-gray-200 p-6 text-center text-gray-500">No baseline set. Enter a file path or URL above.</div>"#.to_string(),
+        ));
+    };
+    Ok(Html(render_deviations(
+        &baseline.deviations(&user_config),
+        baseline,
+    )))
+}
+
+#[derive(Deserialize)]
+pub struct BaselineForm {
+    pub source: String,
+}
+
+/// POST /api/baseline — load a baseline from a path or URL; an empty source clears it.
+pub async fn set_baseline(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<BaselineForm>,
+) -> Result<Html<String>, AppError> {
+    if form.source.trim().is_empty() {
+        *state.baseline.write().await = None;
+        return Ok(Html(toast_html("Baseline cleared", false)));
+    }
+
+    match Baseline::load(BaselineSource::parse(&form.source)) {
+        Ok(baseline) => {
+            let message = format!(
+                "Comparing against {}",
+                escape_html(&baseline.source.to_string())
+            );
+            *state.baseline.write().await = Some(baseline);
+            Ok(Html(toast_html(&message, false)))
+        }
+        Err(e @ (AppError::Config(_) | AppError::Cli(_))) => {
+            Ok(Html(toast_html(&escape_html(&e.to_string()), true)))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// POST /api/baseline/refresh — fetch the baseline again, keeping ignored keys.
+pub async fn refresh_baseline(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let Some(current) = state.baseline.read().await.clone() else {
+        return Ok(Html(toast_html("No baseline set", true)));
+    };
+
+    match Baseline::load(current.source) {
+        Ok(baseline) => {
+            *state.baseline.write().await = Some(Baseline {
+                ignored: current.ignored,
+                ..baseline
+            });
+            Ok(Html(toast_html("Baseline refreshed", false)))
+        }
+        Err(e @ (AppError::Config(_) | AppError::Cli(_))) => {
+            Ok(Html(toast_html(&escape_html(&e.to_string()), true)))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct BaselineKeyForm {
+    pub key: String,
+}
+
+/// POST /api/baseline/adopt — take the baseline's value for a key (in memory).
+pub async fn adopt_key(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<BaselineKeyForm>,
+) -> Result<Html<String>, AppError> {
+    let mut user_config = state.user_config.write().await;
+    let baseline = state.baseline.read().await;
+    let adopted = baseline
+        .as_ref()
+        .is_some_and(|b| b.adopt(&form.key, &mut user_config));
+    drop(baseline);
+    drop(user_config);
+    if !adopted {
+        return Ok(Html(toast_html("The baseline doesn't set that key", true)));
+    }
+
+    state.mark_unsaved().await;
+    let message = format!("Adopted baseline {} (unsaved)", escape_html(&form.key));
+    Ok(Html(publish_toast(&state, &message, false)))
+}
+
+/// POST /api/baseline/ignore — keep a key different from the baseline, or stop ignoring it.
+pub async fn toggle_ignore(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<BaselineKeyForm>,
+) -> Result<Html<String>, AppError> {
+    let mut baseline = state.baseline.write().await;
+    let Some(baseline) = baseline.as_mut() else {
+        return Ok(Html(toast_html("No baseline set", true)));
+    };
+
+    let message = if baseline.ignored.remove(&form.key) {
+        format!("No longer ignoring {}", escape_html(&form.key))
+    } else {
+        baseline.ignored.insert(form.key.clone());
+        format!("Ignoring {}", escape_html(&form.key))
+    };
+    Ok(Html(toast_html(&message, false)))
+}
+
+fn values_html(values: &[String]) -> String {
+    if values.is_empty() {
+        return r#"<span class="text-gray-400 italic">not set</span>"#.to_string();
+    }
+    values
+        .iter()
+        .map(|v| escape_html(v))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+fn key_action(url: &str, key: &str, label: &str, class: &str) -> String {
+    format!(
+        r##"<form hx-post="{url}" hx-target="#toast-container" hx-swap="innerHTML" hx-on::after-request="htmx.trigger('#baseline-deviations', 'refresh')" class="inline"><input type="hidden" name="key" value="{key}"><button type="submit" class="text-sm font-medium {class}" aria-label="{label} {key}">{label}</button></form>"##,
+        url = url,
+        key = escape_html(key),
+        label = label,
+        class = class,
+    )
+}
+
+fn render_deviations(deviations: &[KeyChange], baseline: &Baseline) -> String {
+    let source = escape_html(&baseline.source.to_string());
+    if deviations.is_empty() {
+        return format!(
+            r#"<div class="bg-white rounded-xl border border-gray-200 p-6 text-center text-gray-500">Your config matches every key in <span class="font-mono">{}</span>.</div>"#,
+            source
+        );
+    }
+
+    let (ignored, active): (Vec<&KeyChange>, Vec<&KeyChange>) = deviations
+        .iter()
+        .partition(|d| baseline.ignored.contains(&d.key));
+
+    let mut html = String::new();
+    html.push_str(&format!(
+        r#"<p class="text-sm text-gray-500 mb-3">{} of the keys in <span class="font-mono">{}</span> differ{}.</p>"#,
+        active.len(),
+        source,
+        if ignored.is_empty() {
+            String::new()
+        } else {
+            format!(", plus {} ignored", ignored.len())
+        }
+    ));
+    html.push_str(r#"<div class="bg-white rounded-xl border border-gray-200 overflow-hidden"><table class="w-full" aria-label="Deviations from the baseline"><thead class="bg-gray-50 border-b border-gray-200"><tr>"#);
+    for heading in ["Key", "Baseline", "Yours"] {
+        html.push_str(&format!(
+            r#"<th class="px-4 py-3 text-left text-xs font-semibold text-gray-500 uppercase tracking-wider">{}</th>"#,
+            heading
+        ));
+    }
+    html.push_str(r#"<th class="px-4 py-3"><span class="sr-only">Actions</span></th></tr></thead><tbody class="divide-y divide-gray-100">"#);
+
+    for d in active.iter().chain(ignored.iter()) {
+        let is_ignored = baseline.ignored.contains(&d.key);
+        let row_class = match (is_ignored, d.kind) {
+            (true, _) => "opacity-50",
+            (false, ChangeKind::Removed) => "bg-red-50",
+            (false, _) => "bg-amber-50",
+        };
+        let actions = if is_ignored {
+            key_action(
+                "/api/baseline/ignore",
+                &d.key,
+                "Unignore",
+                "text-gray-600 hover:text-gray-800",
+            )
+        } else {
+            format!(
+                "{} {}",
+                key_action(
+                    "/api/baseline/adopt",
+                    &d.key,
+                    "Adopt",
+                    "text-indigo-600 hover:text-indigo-800"
+                ),
+                key_action(
+                    "/api/baseline/ignore",
+                    &d.key,
+                    "Ignore",
+                    "text-gray-500 hover:text-gray-700 ml-3"
+                )
+            )
+        };
+        html.push_str(&format!(
+            r#"<tr class="{row_class}" data-focus-key="baseline:{key}"><td class="px-4 py-2 text-sm font-mono font-semibold text-gray-900"><a href="/category/{slug}#field-{key}" class="hover:underline">{key}</a></td><td class="px-4 py-2 text-sm font-mono text-gray-700">{baseline}</td><td class="px-4 py-2 text-sm font-mono text-gray-900">{current}</td><td class="px-4 py-2 text-right whitespace-nowrap">{actions}</td></tr>"#,
+            row_class = row_class,
+            key = escape_html(&d.key),
+            slug = categorize_key(&d.key).slug(),
+            baseline = values_html(&d.old),
+            current = values_html(&d.new),
+            actions = actions,
+        ));
+    }
+    html.push_str("</tbody></table></div>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::UserConfig;
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    fn baseline(ignored: &[&str]) -> Baseline {
+        Baseline {
+            source: BaselineSource::parse("https://example.com/team"),
+            config: UserConfig::new(PathBuf::from("baseline")),
+            ignored: ignored
+                .iter()
+                .map(|k| k.to_string())
+                .collect::<HashSet<_>>(),
+        }
+    }
+
+    fn change(key: &str, kind: ChangeKind) -> KeyChange {
+        KeyChange {
+            key: key.to_string(),
+            kind,
+            old: vec!["14".to_string()],
+            new: if kind == ChangeKind::Removed {
+                Vec::new()
+            } else {
+                vec!["16".to_string()]
+            },
+        }
+    }
+
+    #[test]
+    fn test_render_no_deviations() {
+        let html = render_deviations(&[], &baseline(&[]));
+        assert!(html.contains("matches every key"));
+        assert!(html.contains("https://example.com/team"));
+    }
+
+    #[test]
+    fn test_render_deviations_offers_actions() {
+        let html = render_deviations(
+            &[
+                change("font-size", ChangeKind::Changed),
+                change("theme", ChangeKind::Removed),
+            ],
+            &baseline(&["theme"]),
+        );
+        assert!(html.contains("1 of the keys"));
+        assert!(html.contains("plus 1 ignored"));
+        assert!(html.contains(r#"aria-label="Adopt font-size""#));
+        assert!(html.contains(r#"aria-label="Unignore theme""#));
+        assert!(!html.contains(r#"aria-label="Adopt theme""#));
+    }
+}
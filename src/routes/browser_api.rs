@@ -0,0 +1,38 @@
+use axum::extract::{Query, State};
+use axum::response::Html;
+use axum::Json;
+use serde::Deserialize;
+
+use super::config_api::{escape_html, toast_html};
+use crate::app_state::SharedState;
+use crate::error::AppError;
+use crate::instance::Instance;
+
+#[derive(Deserialize)]
+pub struct OpenQuery {
+    pub page: Option<String>,
+}
+
+/// POST /api/open — (re-)open the UI in the configured browser.
+pub async fn open_browser(
+    State(state): State<SharedState>,
+    Query(query): Query<OpenQuery>,
+) -> Result<Html<String>, AppError> {
+    let page = query.page.unwrap_or_else(|| "/".to_string());
+    let url = state.browser.open(&page)?;
+    Ok(Html(toast_html(
+        &format!("Opened {}", escape_html(&url)),
+        false,
+    )))
+}
+
+/// GET /api/instance — identify this server, so a second launch for the same config
+/// can open it instead of starting another.
+pub async fn instance(State(state): State<SharedState>) -> Json<Instance> {
+    Json(Instance {
+        pid: std::process::id(),
+        url: state.browser.base_url.clone(),
+        config_path: state.user_config.read().await.file_path.clone(),
+        token: None,
+    })
+}
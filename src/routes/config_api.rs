@@ -1,25 +1,53 @@
-use axum::extract::{Path, State};
-use axum::response::Html;
-use serde::Deserialize;
+use std::collections::BTreeMap;
 
-use crate::app_state::SharedState;
-use crate::config::file_io::{read_config, write_config};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::pages::{render_category_fields, render_field_oob};
+use super::validation_api::issue_badge_oob;
+use crate::app_state::{AppState, KeyChange, SharedState};
+use crate::cli::validate::{line_errors, validate_user_config, Severity, ValidationIssue};
+use crate::config::adjust_metric::format_adjustment;
+use crate::config::annotate::annotate_new_keys;
+use crate::config::file_io::{read_config, read_config_text, render_config, write_config};
+use crate::config::includes::load_included;
+use crate::config::model::{Category, ConfigOption, ConfigSchema, ConfigValueType};
+use crate::config::paths::{expand_path, path_warning};
+use crate::config::units::{format_byte_size, format_duration, is_plain_millis};
+use crate::config::validate_value::validate_value;
 use crate::error::AppError;
+use crate::events::AppEvent;
+use crate::history::ChangeKind;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct SetValueForm {
     pub value: String,
+    /// For durations and byte sizes, the unit `value` is in.
+    #[serde(default)]
+    pub unit: Option<String>,
 }
 
 /// GET /api/config/:key — return the current value.
+#[utoipa::path(
+    get,
+    path = "/api/config/{key}",
+    tag = "Config",
+    summary = "The current value of a key",
+    params(("key" = String, Path)),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
 pub async fn get_value(
     State(state): State<SharedState>,
     Path(key): Path<String>,
 ) -> Result<Html<String>, AppError> {
-    let user_config = state.user_config.read().await;
-    let value = user_config.get(&key).unwrap_or("").to_string();
+    let value = state.value_of(&key).await.unwrap_or_default();
 
-    let default = state
+    let ghostty = state.ghostty().await;
+    let default = ghostty
         .schema
         .find_option(&key)
         .map(|o| o.default_value.as_str())
@@ -35,97 +63,769 @@ pub async fn get_value(
 }
 
 /// PUT /api/config/:key — update a config value in memory (no disk write).
+#[utoipa::path(
+    put,
+    path = "/api/config/{key}",
+    tag = "Config",
+    summary = "Update a value in memory",
+    params(("key" = String, Path)),
+    request_body(content = SetValueForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
 pub async fn set_value(
     State(state): State<SharedState>,
     Path(key): Path<String>,
     axum::Form(form): axum::Form<SetValueForm>,
 ) -> Result<Html<String>, AppError> {
-    let value = form.value.trim().to_string();
-
-    let mut user_config = state.user_config.write().await;
+    let ghostty = state.ghostty().await;
+    let mut value = form.value.trim().to_string();
+    if let (Some(unit), Some(option)) = (&form.unit, ghostty.schema.find_option(&key)) {
+        if !value.is_empty() {
+            match with_unit(option, &value, unit) {
+                Ok(combined) => value = combined,
+                Err(message) => return Ok(Html(toast_html(&escape_html(&message), true))),
+            }
+        }
+    }
 
-    let is_default = state
+    let expanded = stored_value(&ghostty.schema, &key, &value);
+    let message = if expanded == value {
+        "Updated (unsaved)".to_string()
+    } else {
+        format!("Updated (unsaved) as {}", expanded)
+    };
+    let value = expanded;
+    let is_default = ghostty
         .schema
         .find_option(&key)
         .map(|o| o.default_value == value)
         .unwrap_or(false);
 
+    if !is_default && !value.is_empty() {
+        if let Some(message) = value_error(&state, &key, &value).await {
+            let mut html = toast_html(&escape_html(&message), true);
+            html.push_str(&issue_badge_oob(&ValidationIssue {
+                key: Some(key),
+                line: None,
+                message,
+                severity: Severity::Error,
+            }));
+            return Ok(Html(html));
+        }
+    }
+
     if is_default || value.is_empty() {
-        user_config.remove(&key);
+        state.remove_key(&key).await;
     } else {
-        user_config.set(&key, &value);
+        state
+            .edit_key(&key, |config| config.set(&key, &value))
+            .await?;
+    }
+
+    let mut html = field_changed(&state, &key, &escape_html(&message)).await?;
+    if !is_default {
+        html.push_str(&path_warning_html(&state, &key, &value).await);
+    }
+    Ok(Html(html))
+}
+
+/// The config value for `amount` of `unit`, for options edited with a unit picker.
+/// Other options take `amount` as it is.
+fn with_unit(option: &ConfigOption, amount: &str, unit: &str) -> Result<String, String> {
+    match option.value_type {
+        ConfigValueType::Duration | ConfigValueType::ByteSize => {}
+        ConfigValueType::AdjustMetric => {
+            return format_adjustment(amount, unit).ok_or_else(|| {
+                format!(
+                    "{} must be whole pixels, or a percentage above -100",
+                    option.key
+                )
+            })
+        }
+        _ => return Ok(amount.to_string()),
+    }
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("{} must be a whole number of {}", option.key, unit))?;
+    let value = match option.value_type {
+        ConfigValueType::Duration => {
+            format_duration(amount, unit, is_plain_millis(&option.default_value))
+        }
+        _ => format_byte_size(amount, unit),
+    };
+    value.ok_or_else(|| format!("{} is too large or not a unit", unit))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct BulkEdit {
+    /// Values by key. An empty or default value removes the key, as with `set_value`.
+    /// A repeatable key can be given a list, which replaces all its values.
+    #[serde(default)]
+    pub set: BTreeMap<String, BulkValue>,
+    #[serde(default)]
+    pub delete: Vec<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum BulkValue {
+    One(String),
+    /// Every value of a repeatable key, e.g. all of its `palette` entries.
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Serialize, PartialEq, ToSchema)]
+pub struct KeyResult {
+    pub key: String,
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BulkEditResult {
+    /// False when any key was invalid, in which case nothing was changed.
+    pub applied: bool,
+    pub results: Vec<KeyResult>,
+}
+
+/// PUT /api/config — set and delete many keys in memory at once (no disk write).
+///
+/// Either every change is applied or, if any key is invalid, none is; the response
+/// lists each key's validation result in both cases.
+#[utoipa::path(
+    put,
+    path = "/api/config",
+    tag = "Config",
+    summary = "Set and delete many keys in memory at once, a list replacing every value of a repeatable key; 422 with per-key results if any is invalid",
+    request_body(content = BulkEdit, content_type = "application/json"),
+    responses(
+        (status = 200, description = "OK", body = BulkEditResult),
+        (status = 422, description = "A key was invalid; nothing was changed", body = BulkEditResult),
+    ),
+)]
+pub async fn bulk_edit(
+    State(state): State<SharedState>,
+    Json(edit): Json<BulkEdit>,
+) -> Result<(StatusCode, Json<BulkEditResult>), AppError> {
+    let (changes, results) = check_bulk_edit(&state.ghostty().await.schema, &edit);
+    if results.iter().any(|r| !r.valid) {
+        let result = BulkEditResult {
+            applied: false,
+            results,
+        };
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(result)));
+    }
+
+    state.edit_keys(&changes).await?;
+    state.mark_unsaved().await;
+
+    let result = BulkEditResult {
+        applied: true,
+        results,
+    };
+    Ok((StatusCode::OK, Json(result)))
+}
+
+/// Validate a bulk edit, returning its changes (`None` to remove a key) and the
+/// result for each key.
+fn check_bulk_edit(schema: &ConfigSchema, edit: &BulkEdit) -> (Vec<KeyChange>, Vec<KeyResult>) {
+    let mut changes = Vec::new();
+    let mut results = Vec::new();
+    let sets = edit.set.iter().map(|(key, value)| (key, Some(value)));
+    let deletes = edit.delete.iter().map(|key| (key, None));
+    for (key, value) in sets.chain(deletes) {
+        let Some(option) = schema.find_option(key) else {
+            results.push(KeyResult {
+                key: key.clone(),
+                valid: false,
+                error: Some(format!("Unknown config key: {}", key)),
+            });
+            continue;
+        };
+        let stored = |v: &str| match option.value_type {
+            ConfigValueType::Path => expand_path(v),
+            _ => v.to_string(),
+        };
+        let values: Result<Vec<String>, String> = match value {
+            None => Ok(Vec::new()),
+            Some(BulkValue::One(v)) => Ok(Some(v.trim())
+                .filter(|v| !v.is_empty() && *v != option.default_value)
+                .map(stored)
+                .into_iter()
+                .collect()),
+            Some(BulkValue::Many(_)) if !option.is_repeatable => {
+                Err(format!("{} takes a single value, not a list", key))
+            }
+            Some(BulkValue::Many(values)) => Ok(values
+                .iter()
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .map(stored)
+                .collect()),
+        };
+        let error = match &values {
+            Ok(values) => values.iter().find_map(|v| validate_value(option, v).err()),
+            Err(e) => Some(e.clone()),
+        };
+        let values = values.unwrap_or_default();
+        changes.push((key.clone(), Some(values).filter(|v| !v.is_empty())));
+        results.push(KeyResult {
+            key: key.clone(),
+            valid: error.is_none(),
+            error,
+        });
     }
+    (changes, results)
+}
+
+/// POST /api/category/:slug/reset — remove every option of a category in memory (no
+/// disk write), all values of repeatable ones included, and re-render its fields.
+#[utoipa::path(
+    post,
+    path = "/api/category/{slug}/reset",
+    tag = "Config",
+    summary = "Reset every option of a category to its default in memory",
+    params(("slug" = String, Path)),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn reset_category(
+    State(state): State<SharedState>,
+    Path(slug): Path<String>,
+) -> Result<Html<String>, AppError> {
+    let category = Category::all()
+        .into_iter()
+        .find(|c| c.slug() == slug)
+        .ok_or_else(|| AppError::Config(format!("Unknown category: {}", slug)))?;
 
-    state.mark_unsaved(&key).await;
-    let count = state.unsaved_count().await;
+    let ghostty = state.ghostty().await;
+    let mut reset = Vec::new();
+    for option in ghostty.schema.options_for_category(&category) {
+        if !state.all_values(&option.key).await.is_empty() {
+            state.remove_key(&option.key).await;
+            reset.push(option.key.as_str());
+        }
+    }
+    if reset.is_empty() {
+        return Ok(Html(toast_html(
+            &format!("{} is already at its defaults", category.display_name()),
+            false,
+        )));
+    }
+    state.mark_unsaved().await;
 
-    Ok(Html(toast_with_badge("Updated (unsaved)", false, count)))
+    let message = format!(
+        "Reset {} {} option{} to defaults (unsaved)",
+        reset.len(),
+        category.display_name(),
+        if reset.len() == 1 { "" } else { "s" }
+    );
+    let mut html = publish_toast(&state, &message, false);
+    html.push_str(&render_category_fields(&state, &category).await?);
+    Ok(Html(html))
 }
 
 /// DELETE /api/config/:key — remove a config value in memory (no disk write).
+#[utoipa::path(
+    delete,
+    path = "/api/config/{key}",
+    tag = "Config",
+    summary = "Remove a key in memory",
+    params(("key" = String, Path)),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
 pub async fn delete_value(
     State(state): State<SharedState>,
     Path(key): Path<String>,
 ) -> Result<Html<String>, AppError> {
-    let mut user_config = state.user_config.write().await;
-    user_config.remove(&key);
-    state.mark_unsaved(&key).await;
-    let count = state.unsaved_count().await;
+    state.remove_key(&key).await;
+    Ok(Html(
+        field_changed(&state, &key, "Reset to default (unsaved)").await?,
+    ))
+}
 
-    Ok(Html(toast_with_badge(
-        "Reset to default (unsaved)",
-        false,
-        count,
-    )))
+/// Mark `key` unsaved and answer with a toast plus, out of band, its re-rendered
+/// field and the header's unsaved count.
+async fn field_changed(state: &SharedState, key: &str, message: &str) -> Result<String, AppError> {
+    state.mark_unsaved().await;
+    let mut html = publish_toast(state, message, false);
+    html.push_str(&render_field_oob(state, key).await?);
+    html.push_str(&unsaved_badge_oob(state.unsaved.read().await.len()));
+    Ok(html)
 }
 
-/// POST /api/save — write in-memory config to disk, then reload.
-pub async fn save_config(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
-    let path = {
-        let user_config = state.user_config.read().await;
-        write_config(&user_config)?;
-        user_config.file_path.clone()
+/// GET /api/config/:key/:index — return one value of a repeatable key.
+#[utoipa::path(
+    get,
+    path = "/api/config/{key}/{index}",
+    tag = "Config",
+    summary = "One value of a repeatable key",
+    params(("key" = String, Path), ("index" = usize, Path)),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn get_entry(
+    State(state): State<SharedState>,
+    Path((key, index)): Path<(String, usize)>,
+) -> Result<Html<String>, AppError> {
+    state
+        .all_values(&key)
+        .await
+        .into_iter()
+        .nth(index)
+        .map(Html)
+        .ok_or_else(|| AppError::Config(format!("{} has no entry {}", key, index)))
+}
+
+/// POST /api/config/:key — add a value to a repeatable key, after the ones it has.
+#[utoipa::path(
+    post,
+    path = "/api/config/{key}",
+    tag = "Config",
+    summary = "Add a value to a repeatable key",
+    params(("key" = String, Path)),
+    request_body(content = SetValueForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn add_entry(
+    State(state): State<SharedState>,
+    Path(key): Path<String>,
+    axum::Form(form): axum::Form<SetValueForm>,
+) -> Result<Html<String>, AppError> {
+    let value = stored_value(&state.ghostty().await.schema, &key, form.value.trim());
+    if let Some(message) = entry_error(&state, &key, &value).await {
+        return Ok(Html(toast_html(&message, true)));
+    }
+    state
+        .add_entry(&key, |config| config.push(&key, &value))
+        .await?;
+    entry_changed(&state, &key, Some(&value), "Added (unsaved)").await
+}
+
+/// PUT /api/config/:key/:index — replace one value of a repeatable key.
+#[utoipa::path(
+    put,
+    path = "/api/config/{key}/{index}",
+    tag = "Config",
+    summary = "Replace one value of a repeatable key",
+    params(("key" = String, Path), ("index" = usize, Path)),
+    request_body(content = SetValueForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn set_entry(
+    State(state): State<SharedState>,
+    Path((key, index)): Path<(String, usize)>,
+    axum::Form(form): axum::Form<SetValueForm>,
+) -> Result<Html<String>, AppError> {
+    let value = stored_value(&state.ghostty().await.schema, &key, form.value.trim());
+    if let Some(message) = entry_error(&state, &key, &value).await {
+        return Ok(Html(toast_html(&message, true)));
+    }
+    if !state
+        .edit_entry(&key, index, |config, i| config.set_at(&key, i, &value))
+        .await
+    {
+        return Ok(Html(toast_html(&missing_entry(&key, index), true)));
+    }
+    entry_changed(&state, &key, Some(&value), "Updated (unsaved)").await
+}
+
+/// POST /api/config/:key/:index — add a value of a repeatable key right after value `index`.
+#[utoipa::path(
+    post,
+    path = "/api/config/{key}/{index}",
+    tag = "Config",
+    summary = "Insert a value after value `index`",
+    params(("key" = String, Path), ("index" = usize, Path)),
+    request_body(content = SetValueForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn insert_entry(
+    State(state): State<SharedState>,
+    Path((key, index)): Path<(String, usize)>,
+    axum::Form(form): axum::Form<SetValueForm>,
+) -> Result<Html<String>, AppError> {
+    let value = stored_value(&state.ghostty().await.schema, &key, form.value.trim());
+    if let Some(message) = entry_error(&state, &key, &value).await {
+        return Ok(Html(toast_html(&message, true)));
+    }
+    if !state
+        .edit_entry(&key, index, |config, i| {
+            config.insert_after(&key, i, &value)
+        })
+        .await
+    {
+        return Ok(Html(toast_html(&missing_entry(&key, index), true)));
+    }
+    entry_changed(&state, &key, Some(&value), "Added (unsaved)").await
+}
+
+/// DELETE /api/config/:key/:index — remove one value of a repeatable key.
+#[utoipa::path(
+    delete,
+    path = "/api/config/{key}/{index}",
+    tag = "Config",
+    summary = "Remove one value of a repeatable key",
+    params(("key" = String, Path), ("index" = usize, Path)),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn delete_entry(
+    State(state): State<SharedState>,
+    Path((key, index)): Path<(String, usize)>,
+) -> Result<Html<String>, AppError> {
+    if !state
+        .edit_entry(&key, index, |config, i| config.remove_at(&key, i))
+        .await
+    {
+        return Ok(Html(toast_html(&missing_entry(&key, index), true)));
+    }
+    entry_changed(&state, &key, None, "Removed (unsaved)").await
+}
+
+/// Why `value` can't be stored as an entry of `key`, if it can't.
+async fn entry_error(state: &SharedState, key: &str, value: &str) -> Option<String> {
+    if state
+        .ghostty()
+        .await
+        .schema
+        .find_option(key)
+        .is_some_and(|o| !o.is_repeatable)
+    {
+        return Some(format!("{} takes a single value", escape_html(key)));
+    }
+    if value.is_empty() {
+        return Some("Enter a value to add".to_string());
+    }
+    value_error(state, key, value)
+        .await
+        .map(|e| escape_html(&e))
+}
+
+/// Why `value` isn't a valid value for `key`, checked against its type in the schema.
+async fn value_error(state: &SharedState, key: &str, value: &str) -> Option<String> {
+    let ghostty = state.ghostty().await;
+    let option = ghostty.schema.find_option(key)?;
+    validate_value(option, value).err()
+}
+
+fn missing_entry(key: &str, index: usize) -> String {
+    format!("{} has no entry {}", escape_html(key), index)
+}
+
+/// Mark `key` unsaved and answer with a toast plus the refreshed entry list, and
+/// a warning if the stored `value` is a path that won't work.
+async fn entry_changed(
+    state: &SharedState,
+    key: &str,
+    value: Option<&str>,
+    message: &str,
+) -> Result<Html<String>, AppError> {
+    state.mark_unsaved().await;
+    let mut html = publish_toast(state, message, false);
+    let entries = state.all_values(key).await;
+    html.push_str(&render_entries(key, &entries, true));
+    if let Some(value) = value {
+        html.push_str(&path_warning_html(state, key, value).await);
+    }
+    Ok(Html(html))
+}
+
+/// What to store for `value` of `key`: path options get `~` and environment
+/// variables expanded, since Ghostty doesn't expand variables itself.
+fn stored_value(schema: &ConfigSchema, key: &str, value: &str) -> String {
+    match schema.find_option(key) {
+        Some(option) if matches!(option.value_type, ConfigValueType::Path) && !value.is_empty() => {
+            expand_path(value)
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// A warning toast and field badge when the path `value` of `key` doesn't exist
+/// or can't be read. Empty for other options and for paths that are fine.
+async fn path_warning_html(state: &SharedState, key: &str, value: &str) -> String {
+    let is_path = state
+        .ghostty()
+        .await
+        .schema
+        .find_option(key)
+        .is_some_and(|o| matches!(o.value_type, ConfigValueType::Path));
+    if !is_path {
+        return String::new();
+    }
+    let config_path = state.user_config.read().await.file_path.clone();
+    let Some(warning) = path_warning(key, value, &config_path) else {
+        return String::new();
     };
+    let mut html = toast_html(&escape_html(&warning), true);
+    html.push_str(&issue_badge_oob(&ValidationIssue {
+        key: Some(key.to_string()),
+        line: None,
+        message: warning,
+        severity: Severity::Warning,
+    }));
+    html
+}
 
-    // Reload from disk so in-memory state matches the file.
-    let reloaded = read_config(&path)?;
-    *state.user_config.write().await = reloaded;
-    state.clear_unsaved().await;
+/// The editable list of a repeatable key's values on category pages.
+///
+/// With `oob`, the list replaces the one already on the page. Font family entries
+/// suggest installed fonts and preview the one being edited.
+pub fn render_entries(key: &str, entries: &[String], oob: bool) -> String {
+    let font = if key.starts_with("font-family") {
+        format!(
+            r#" list="fontlist" data-font-preview="font-preview-{}""#,
+            escape_html(key)
+        )
+    } else {
+        String::new()
+    };
+    let key = escape_html(key);
+    let mut html = format!(
+        r#"<div id="entries-{key}" class="space-y-2 mt-2"{oob}>"#,
+        key = key,
+        oob = if oob { r#" hx-swap-oob="true""# } else { "" },
+    );
+    for (index, value) in entries.iter().enumerate() {
+        html.push_str(&format!(
+            r##"<div class="flex items-center gap-2"><input type="text" name="value" value="{value}"{font} aria-label="{key} entry {number}" class="flex-1 px-3 py-1.5 border border-gray-300 rounded-lg text-sm font-mono focus:ring-2 focus:ring-indigo-500 focus:border-indigo-500" hx-put="/api/config/{key}/{index}" hx-target="#toast-container" hx-swap="innerHTML" hx-trigger="change" hx-include="this"><button type="button" hx-post="/api/config/{key}/{index}" hx-include="previous input" hx-target="#toast-container" hx-swap="innerHTML" aria-label="Duplicate {key} entry {number}" title="Duplicate below" class="text-gray-400 hover:text-indigo-600 transition-colors px-1">+</button><button type="button" hx-delete="/api/config/{key}/{index}" hx-target="#toast-container" hx-swap="innerHTML" aria-label="Remove {key} entry {number}" class="text-gray-400 hover:text-red-500 transition-colors px-1">&times;</button></div>"##,
+            key = key,
+            index = index,
+            number = index + 1,
+            value = escape_html(value),
+            font = font,
+        ));
+    }
+    html.push_str(&format!(
+        r##"<form class="flex items-center gap-2" hx-post="/api/config/{key}" hx-target="#toast-container" hx-swap="innerHTML"><input type="text" name="value"{font} placeholder="Add {key} entry" aria-label="New {key} entry" class="flex-1 px-3 py-1.5 border border-dashed border-gray-300 rounded-lg text-sm font-mono focus:ring-2 focus:ring-indigo-500 focus:border-indigo-500"><button type="submit" class="px-3 py-1.5 text-sm font-medium text-gray-700 bg-white border border-gray-300 rounded-lg hover:bg-gray-50 transition-colors">Add</button></form>"##,
+        key = key,
+        font = font,
+    ));
+    if !font.is_empty() {
+        html.push_str(&format!(r#"<div id="font-preview-{}"></div>"#, key));
+    }
+    html.push_str("</div>");
+    html
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SaveQuery {
+    /// Comment and group new keys on this save, whatever the setting says.
+    #[serde(default)]
+    pub annotate: Option<bool>,
+}
+
+impl SaveQuery {
+    async fn annotate(&self, state: &SharedState) -> bool {
+        match self.annotate {
+            Some(annotate) => annotate,
+            None => state.settings.read().await.annotate_new_keys,
+        }
+    }
+}
 
-    Ok(Html(toast_with_badge("Config saved to disk", false, 0)))
+/// POST /api/save — write in-memory config to disk, then reload.
+#[utoipa::path(
+    post,
+    path = "/api/save",
+    tag = "Config",
+    summary = "Write the in-memory config to disk; `annotate=true` or `false` overrides the setting for commenting new keys",
+    params(SaveQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn save_config(
+    State(state): State<SharedState>,
+    Query(query): Query<SaveQuery>,
+) -> Result<Response, AppError> {
+    if state.external_changes().await?.is_some() {
+        return Ok(merge_redirect());
+    }
+    if let Some(blocked) = validation_block(&state).await {
+        return Ok(blocked);
+    }
+
+    write_and_reload(&state, query.annotate(&state).await).await?;
+
+    Ok(Html(publish_toast(&state, "Config saved to disk", false)).into_response())
 }
 
 /// POST /api/apply — save config to disk and tell Ghostty to reload.
-pub async fn apply_config(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+#[utoipa::path(
+    post,
+    path = "/api/apply",
+    tag = "Config",
+    summary = "Save and tell Ghostty to reload; takes `annotate` like `/api/save`",
+    params(SaveQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn apply_config(
+    State(state): State<SharedState>,
+    Query(query): Query<SaveQuery>,
+) -> Result<Response, AppError> {
+    if state.external_changes().await?.is_some() {
+        return Ok(merge_redirect());
+    }
+    if let Some(blocked) = validation_block(&state).await {
+        return Ok(blocked);
+    }
+
+    write_and_reload(&state, query.annotate(&state).await).await?;
+
+    // The Ghostty this could signal isn't the one reading a remote config.
+    if let Some(remote) = &state.remote {
+        let message = format!(
+            "Config saved to {} (reload Ghostty there with {})",
+            remote.display(),
+            RELOAD_SHORTCUT
+        );
+        return Ok(Html(publish_toast(&state, &message, true)).into_response());
+    }
+
+    let reload_result = trigger_ghostty_reload();
+    if reload_result.is_ok() {
+        state.record_action(ChangeKind::Apply).await;
+    }
+
+    let (message, is_warn) = match &reload_result {
+        Ok(_) => ("Config saved and Ghostty reloaded".to_string(), false),
+        Err(e) => {
+            tracing::warn!("Failed to trigger Ghostty reload: {}", e);
+            (
+                format!(
+                    "Config saved (reload Ghostty manually with {})",
+                    RELOAD_SHORTCUT
+                ),
+                true,
+            )
+        }
+    };
+
+    Ok(Html(publish_toast(&state, &message, is_warn)).into_response())
+}
+
+/// Write the in-memory config, then reload it so memory and the merge base match the file.
+///
+/// With `annotate`, keys the file didn't have get a comment from their docs first.
+pub(crate) async fn write_and_reload(state: &SharedState, annotate: bool) -> Result<(), AppError> {
     let path = {
-        let user_config = state.user_config.read().await;
+        let mut user_config = state.user_config.write().await;
+        if annotate {
+            let base = state.base_config.read().await;
+            annotate_new_keys(&mut user_config, &base, &state.ghostty().await.schema);
+        }
         write_config(&user_config)?;
+        if let Some(remote) = &state.remote {
+            remote.push(&render_config(&user_config)).await?;
+        }
         user_config.file_path.clone()
     };
+    // Included files only get written when an edit was routed to them.
+    for config in state.included.read().await.iter() {
+        if read_config_text(&config.file_path)? != render_config(config) {
+            write_config(config)?;
+        }
+    }
 
-    // Reload from disk so in-memory state matches the file.
     let reloaded = read_config(&path)?;
+    *state.included.write().await = load_included(&reloaded);
+    *state.base_config.write().await = reloaded.clone();
     *state.user_config.write().await = reloaded;
+    state.record_action(ChangeKind::Save).await;
     state.clear_unsaved().await;
+    state.publish(AppEvent::Saved);
+    Ok(())
+}
 
-    let reload_result = trigger_ghostty_reload();
+/// Refuse to save a config Ghostty rejects. The `X-Save-Blocked` header tells the
+/// Save buttons not to reload the page, so the explanation stays visible.
+pub(crate) async fn validation_block(state: &SharedState) -> Option<Response> {
+    let problems = validation_problems(state).await?;
+    let main_file = state.user_config.read().await.file_path.clone();
+    Some(
+        (
+            [("X-Save-Blocked", "true")],
+            Html(toast_html(
+                &escape_html(&blocked_message(&problems, &main_file)),
+                true,
+            )),
+        )
+            .into_response(),
+    )
+}
 
-    let (message, is_warn) = match &reload_result {
-        Ok(_) => ("Config saved and Ghostty reloaded", false),
+/// What Ghostty finds wrong with the in-memory config, if anything.
+///
+/// Without Ghostty, or if the validator can't be run at all, there's nothing to
+/// report and saving goes ahead.
+pub(crate) async fn validation_problems(state: &SharedState) -> Option<String> {
+    let ghostty = state.ghostty().await;
+    let ghostty_path = ghostty.path.as_deref()?;
+    // Validate a copy, so edits aren't held up while Ghostty runs.
+    let user_config = state.user_config.read().await.clone();
+    let included = state.included.read().await.clone();
+    match validate_user_config(ghostty_path, &user_config, &included).await {
+        Ok(problems) if !problems.is_empty() => Some(problems),
+        Ok(_) => None,
         Err(e) => {
-            tracing::warn!("Failed to trigger Ghostty reload: {}", e);
-            (
-                "Config saved (reload Ghostty manually with Cmd+Shift+,)",
-                true,
-            )
+            tracing::warn!("Saving without validation: {}", e);
+            None
         }
+    }
+}
+
+/// One-line summary of validator output for a toast. Lines of files other than
+/// `main_file`, the config's own, are prefixed with the file's name.
+pub(crate) fn blocked_message(problems: &str, main_file: &std::path::Path) -> String {
+    let first = match line_errors(problems).first() {
+        Some(e) if e.file == main_file.display().to_string() => {
+            format!("line {}: {}", e.line, e.message)
+        }
+        Some(e) => {
+            let file = std::path::Path::new(&e.file)
+                .file_name()
+                .map_or(e.file.clone(), |name| name.to_string_lossy().into_owned());
+            format!("{} line {}: {}", file, e.line, e.message)
+        }
+        None => problems.lines().next().unwrap_or_default().to_string(),
     };
+    let more = problems
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .count()
+        .saturating_sub(1);
+    if more > 0 {
+        format!("Not saved — {} (and {} more)", first, more)
+    } else {
+        format!("Not saved — {}", first)
+    }
+}
 
-    Ok(Html(toast_with_badge(message, is_warn, 0)))
+/// The file changed on disk since it was loaded: send the browser to the merge page
+/// instead of overwriting someone else's edits.
+pub(crate) fn merge_redirect() -> Response {
+    (
+        [("HX-Redirect", "/merge")],
+        Html(toast_html(
+            "Config changed on disk — resolve conflicts first",
+            true,
+        )),
+    )
+        .into_response()
 }
 
+/// Ghostty's default reload-config keybinding, for when it has to be reloaded by hand.
+#[cfg(target_os = "macos")]
+pub(crate) const RELOAD_SHORTCUT: &str = "Cmd+Shift+,";
+#[cfg(not(target_os = "macos"))]
+pub(crate) const RELOAD_SHORTCUT: &str = "Ctrl+Shift+,";
+
 /// Trigger Ghostty to reload its config.
-fn trigger_ghostty_reload() -> Result<(), String> {
+///
+/// On macOS this sends the reload keystroke through System Events. On Linux every
+/// running Ghostty gets SIGUSR2, its reload signal; if none can be signalled, the
+/// GTK app's `reload-config` D-Bus action is tried instead. Windows has neither,
+/// so a running Ghostty window is brought forward and sent the reload keystroke.
+pub(crate) fn trigger_ghostty_reload() -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
         let output = std::process::Command::new("osascript")
@@ -149,27 +849,115 @@ end tell"#,
         Ok(())
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
+    {
+        let signalled = ghostty_pids(std::path::Path::new("/proc"))
+            .into_iter()
+            .filter(|pid| {
+                std::process::Command::new("kill")
+                    .args(["-USR2", &pid.to_string()])
+                    .status()
+                    .is_ok_and(|status| status.success())
+            })
+            .count();
+        if signalled > 0 {
+            return Ok(());
+        }
+
+        let output = std::process::Command::new("gdbus")
+            .args([
+                "call",
+                "--session",
+                "--dest",
+                "com.mitchellh.ghostty",
+                "--object-path",
+                "/com/mitchellh/ghostty",
+                "--method",
+                "org.gtk.Actions.Activate",
+                "reload-config",
+                "[]",
+                "{}",
+            ])
+            .output()
+            .map_err(|e| format!("No Ghostty process to signal, and gdbus failed: {}", e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!(
+                "No Ghostty process to signal, and D-Bus reload failed: {}",
+                stderr.trim()
+            ));
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    {
+        let output = std::process::Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command"])
+            .arg(
+                r#"$p = Get-Process ghostty -ErrorAction SilentlyContinue | Select-Object -First 1
+if (-not $p) { Write-Error 'Ghostty is not running'; exit 1 }
+$shell = New-Object -ComObject WScript.Shell
+if (-not $shell.AppActivate($p.Id)) { Write-Error 'Could not focus the Ghostty window'; exit 1 }
+Start-Sleep -Milliseconds 100
+$shell.SendKeys('^+,')"#,
+            )
+            .output()
+            .map_err(|e| format!("powershell failed: {}", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Reload failed: {}", stderr.trim()));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
     {
         Err("Auto-reload not supported on this platform".to_string())
     }
 }
 
-/// Build a toast HTML + an OOB swap to update the unsaved badge.
-fn toast_with_badge(message: &str, is_error: bool, unsaved_count: usize) -> String {
-    let mut html = toast_html(message, is_error);
-    html.push_str(&unsaved_badge_oob(unsaved_count));
-    html
+/// Process IDs of running `ghostty` processes, found by their `comm` under `proc_dir`.
+#[cfg(any(target_os = "linux", test))]
+fn ghostty_pids(proc_dir: &std::path::Path) -> Vec<u32> {
+    let Ok(entries) = std::fs::read_dir(proc_dir) else {
+        return Vec::new();
+    };
+    let mut pids: Vec<u32> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let comm = std::fs::read_to_string(entry.path().join("comm")).ok()?;
+            (comm.trim() == "ghostty" && pid != std::process::id()).then_some(pid)
+        })
+        .collect();
+    pids.sort_unstable();
+    pids
 }
 
+/// A toast for the page that made the change, also published so other open pages
+/// can show it. `message` is HTML, as for [`toast_html`]. The unsaved badge follows
+/// the `config-changed` events that `mark_unsaved` publishes.
+pub fn publish_toast(state: &AppState, message: &str, is_error: bool) -> String {
+    state.publish(AppEvent::Toast {
+        message: message.to_string(),
+        error: is_error,
+    });
+    toast_html(message, is_error)
+}
+
+/// A self-dismissing toast. Errors are announced as alerts, everything else politely.
 pub fn toast_html(message: &str, is_error: bool) -> String {
-    let color_class = if is_error {
-        "bg-amber-500"
+    let (color_class, role) = if is_error {
+        ("bg-amber-500", "alert")
     } else {
-        "bg-emerald-500"
+        ("bg-emerald-500", "status")
     };
     let mut html = String::new();
-    html.push_str("<div class=\"");
+    html.push_str("<div role=\"");
+    html.push_str(role);
+    html.push_str("\" class=\"");
     html.push_str(color_class);
     html.push_str(" text-white px-4 py-2 rounded-lg shadow-lg text-sm font-medium animate-fade-in\" style=\"animation: fadeIn 0.2s ease-out, fadeOut 0.3s ease-in 1.7s forwards;\">");
     html.push_str(message);
@@ -177,28 +965,205 @@ pub fn toast_html(message: &str, is_error: bool) -> String {
     html
 }
 
-/// OOB swap to update the unsaved badge in the header.
+/// The header's count of unsaved keys, as an OOB swap; `setUnsavedBadge` in
+/// `app.js` draws the same.
 pub fn unsaved_badge_oob(count: usize) -> String {
-    let mut html = String::new();
-    html.push_str("<span id=\"unsaved-badge\" hx-swap-oob=\"innerHTML\">");
-    if count > 0 {
-        html.push_str("<span class=\"inline-flex items-center justify-center w-5 h-5 text-xs font-bold text-white bg-red-500 rounded-full\">");
-        html.push_str(&count.to_string());
-        html.push_str("</span>");
+    let count = if count > 0 {
+        format!(
+            r#"<span class="inline-flex items-center justify-center w-5 h-5 text-xs font-bold text-white bg-red-500 rounded-full">{}</span>"#,
+            count
+        )
+    } else {
+        String::new()
+    };
+    format!(
+        r#"<span id="unsaved-badge" class="relative" hx-swap-oob="true">{}</span>"#,
+        count
+    )
+}
+
+/// Escape text for safe inclusion in HTML content or attribute values.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
     }
-    html.push_str("</span>");
-    html
+    escaped
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_bulk_edit() {
+        let option = |key: &str, default: &str, value_type| ConfigOption {
+            key: key.to_string(),
+            default_value: default.to_string(),
+            documentation: String::new(),
+            value_type,
+            category: Category::Fonts,
+            is_repeatable: false,
+            conditions: Default::default(),
+        };
+        let schema = ConfigSchema::new(vec![
+            option("font-size", "13", ConfigValueType::Integer),
+            option("font-family", "", ConfigValueType::Text),
+            option("theme", "", ConfigValueType::Text),
+            ConfigOption {
+                is_repeatable: true,
+                conditions: Default::default(),
+                ..option("palette", "", ConfigValueType::Text)
+            },
+        ]);
+        let one = |v: &str| BulkValue::One(v.to_string());
+        let edit = BulkEdit {
+            set: BTreeMap::from([
+                ("font-family".to_string(), one(" Iosevka ")),
+                ("font-size".to_string(), one("13")),
+                (
+                    "palette".to_string(),
+                    BulkValue::Many(vec!["0=#000000".to_string(), "1=#cc6666".to_string()]),
+                ),
+            ]),
+            delete: vec!["theme".to_string()],
+        };
+        let (changes, results) = check_bulk_edit(&schema, &edit);
+        assert_eq!(
+            changes,
+            vec![
+                ("font-family".to_string(), Some(vec!["Iosevka".to_string()])),
+                // Setting the default removes the key.
+                ("font-size".to_string(), None),
+                (
+                    "palette".to_string(),
+                    Some(vec!["0=#000000".to_string(), "1=#cc6666".to_string()])
+                ),
+                ("theme".to_string(), None),
+            ]
+        );
+        assert!(results.iter().all(|r| r.valid));
+
+        let edit = BulkEdit {
+            set: BTreeMap::from([
+                ("font-size".to_string(), one("big")),
+                ("font-sise".to_string(), one("14")),
+                ("theme".to_string(), BulkValue::Many(vec!["a".to_string()])),
+            ]),
+            delete: Vec::new(),
+        };
+        let (_, results) = check_bulk_edit(&schema, &edit);
+        assert!(!results[1].valid);
+        assert_eq!(
+            results[0].error.as_deref(),
+            Some("Unknown config key: font-sise")
+        );
+        assert_eq!(
+            results[2].error.as_deref(),
+            Some("theme takes a single value, not a list")
+        );
+    }
+
+    #[test]
+    fn test_blocked_message() {
+        let main = std::path::Path::new("/tmp/x");
+        assert_eq!(
+            blocked_message("/tmp/x:2:font-size: invalid value", main),
+            "Not saved — line 2: font-size: invalid value"
+        );
+        assert_eq!(
+            blocked_message("/tmp/x:2:a: bad\n/tmp/x:5:b: bad\n", main),
+            "Not saved — line 2: a: bad (and 1 more)"
+        );
+        assert_eq!(
+            blocked_message("/tmp/colors.conf:3:background: bad", main),
+            "Not saved — colors.conf line 3: background: bad"
+        );
+        assert_eq!(
+            blocked_message("error: odd", main),
+            "Not saved — error: odd"
+        );
+    }
+
+    #[test]
+    fn test_render_entries() {
+        let entries = vec!["-calt".to_string(), "\"ss01\"".to_string()];
+        let html = render_entries("font-feature", &entries, false);
+        assert!(html.contains(r#"hx-put="/api/config/font-feature/1""#));
+        assert!(html.contains(r#"hx-delete="/api/config/font-feature/0""#));
+        assert!(html.contains("&quot;ss01&quot;"));
+        assert!(html.contains(r#"hx-post="/api/config/font-feature""#));
+        assert!(!html.contains("hx-swap-oob"));
+        assert!(render_entries("font-feature", &[], true).contains(r#"hx-swap-oob="true""#));
+        assert!(!html.contains("fontlist"));
+
+        let html = render_entries("font-family", &entries, false);
+        assert_eq!(html.matches(r#"list="fontlist""#).count(), 3);
+        assert!(html.contains(r#"<div id="font-preview-font-family"></div>"#));
+    }
+
+    #[test]
+    fn test_ghostty_pids() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, comm) in [
+            ("412", "ghostty\n"),
+            ("77", "ghostty\n"),
+            ("90", "bash\n"),
+            ("self", "ghostty\n"),
+        ] {
+            std::fs::create_dir(dir.path().join(name)).unwrap();
+            std::fs::write(dir.path().join(name).join("comm"), comm).unwrap();
+        }
+        assert_eq!(ghostty_pids(dir.path()), vec![77, 412]);
+        assert!(ghostty_pids(&dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_with_unit() {
+        let option = |value_type, default: &str| ConfigOption {
+            key: "k".to_string(),
+            default_value: default.to_string(),
+            documentation: String::new(),
+            value_type,
+            category: crate::config::model::Category::Advanced,
+            is_repeatable: false,
+            conditions: Default::default(),
+        };
+        let undo = option(ConfigValueType::Duration, "5s");
+        assert_eq!(with_unit(&undo, "90", "s"), Ok("90s".to_string()));
+        let click = option(ConfigValueType::Duration, "0");
+        assert_eq!(with_unit(&click, "2", "s"), Ok("2000".to_string()));
+        let scrollback = option(ConfigValueType::ByteSize, "10000000");
+        assert_eq!(
+            with_unit(&scrollback, "25", "MB"),
+            Ok("25000000".to_string())
+        );
+        assert!(with_unit(&scrollback, "lots", "MB").is_err());
+        assert!(with_unit(&scrollback, "1", "TB").is_err());
+        let baseline = option(ConfigValueType::AdjustMetric, "");
+        assert_eq!(with_unit(&baseline, "-2", "px"), Ok("-2".to_string()));
+        assert_eq!(with_unit(&baseline, "12.5", "%"), Ok("12.5%".to_string()));
+        assert!(with_unit(&baseline, "1.5", "px").is_err());
+        let text = option(ConfigValueType::Text, "");
+        assert_eq!(
+            with_unit(&text, "anything", "MB"),
+            Ok("anything".to_string())
+        );
+    }
+
     #[test]
     fn test_toast_html_success() {
         let html = toast_html("Saved!", false);
         assert!(html.contains("bg-emerald-500"));
         assert!(html.contains("Saved!"));
+        assert!(html.contains(r#"role="status""#));
         assert!(!html.contains("bg-amber-500"));
     }
 
@@ -207,34 +1172,29 @@ mod tests {
         let html = toast_html("Error occurred", true);
         assert!(html.contains("bg-amber-500"));
         assert!(html.contains("Error occurred"));
+        assert!(html.contains(r#"role="alert""#));
         assert!(!html.contains("bg-emerald-500"));
     }
 
     #[test]
-    fn test_unsaved_badge_oob_zero() {
-        let html = unsaved_badge_oob(0);
-        assert!(html.contains("unsaved-badge"));
-        assert!(html.contains("hx-swap-oob"));
-        // Should not contain the count badge
-        assert!(!html.contains("bg-red-500"));
-    }
-
-    #[test]
-    fn test_unsaved_badge_oob_nonzero() {
+    fn test_unsaved_badge_oob() {
         let html = unsaved_badge_oob(3);
-        assert!(html.contains("unsaved-badge"));
-        assert!(html.contains("hx-swap-oob"));
-        assert!(html.contains("bg-red-500"));
-        assert!(html.contains("3"));
+        assert!(
+            html.starts_with(r#"<span id="unsaved-badge" class="relative" hx-swap-oob="true">"#)
+        );
+        assert!(html.contains(">3</span>"));
+        assert_eq!(
+            unsaved_badge_oob(0),
+            r#"<span id="unsaved-badge" class="relative" hx-swap-oob="true"></span>"#
+        );
     }
 
     #[test]
-    fn test_toast_with_badge() {
-        let html = toast_with_badge("Updated", false, 2);
-        // Should contain both the toast and the badge
-        assert!(html.contains("Updated"));
-        assert!(html.contains("bg-emerald-500"));
-        assert!(html.contains("unsaved-badge"));
-        assert!(html.contains("2"));
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"<a href="x">'&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+        );
+        assert_eq!(escape_html("font-size = 14"), "font-size = 14");
     }
 }
@@ -0,0 +1,120 @@
+use axum::extract::State;
+use axum::response::Html;
+
+use super::config_api::escape_html;
+use crate::app_state::SharedState;
+use crate::config::diff::{diff_keys, unified_hunks, ChangeKind, Hunk, KeyChange, LineTag};
+use crate::config::file_io::{read_config, read_config_text, render_config};
+use crate::error::AppError;
+
+/// Lines of unchanged context shown around each change.
+const DIFF_CONTEXT: usize = 3;
+
+/// GET /api/diff — diff the in-memory config against the file on disk.
+pub async fn diff(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let user_config = state.user_config.read().await;
+    let on_disk = read_config(&user_config.file_path)?;
+    let on_disk_text = read_config_text(&user_config.file_path)?;
+
+    let changes = diff_keys(&on_disk, &user_config);
+    let hunks = unified_hunks(&on_disk_text, &render_config(&user_config), DIFF_CONTEXT);
+
+    Ok(Html(render_diff(&changes, &hunks)))
+}
+
+fn render_diff(changes: &[KeyChange], hunks: &[Hunk]) -> String {
+    if hunks.is_empty() {
+        return r#"<div class="bg-white rounded-xl border border-gray-200 p-6 text-center text-gray-500">No unsaved changes &mdash; the in-memory config matches the file on disk.</div>"#.to_string();
+    }
+
+    let mut html = String::new();
+
+    // Key-level summary
+    if !changes.is_empty() {
+        html.push_str(r#"<div class="bg-white rounded-xl border border-gray-200 overflow-hidden mb-6"><table class="w-full"><thead class="bg-gray-50 border-b border-gray-200"><tr>"#);
+        html.push_str(r#"<th class="px-4 py-3 text-left text-xs font-semibold text-gray-500 uppercase tracking-wider">Key</th>"#);
+        html.push_str(r#"<th class="px-4 py-3 text-left text-xs font-semibold text-gray-500 uppercase tracking-wider">Change</th>"#);
+        html.push_str(r#"<th class="px-4 py-3 text-left text-xs font-semibold text-gray-500 uppercase tracking-wider">On disk</th>"#);
+        html.push_str(r#"<th class="px-4 py-3 text-left text-xs font-semibold text-gray-500 uppercase tracking-wider">Pending</th>"#);
+        html.push_str(r#"</tr></thead><tbody class="divide-y divide-gray-100">"#);
+        for change in changes {
+            let (label, badge_class) = match change.kind {
+                ChangeKind::Added => ("Added", "bg-emerald-100 text-emerald-700"),
+                ChangeKind::Removed => ("Removed", "bg-red-100 text-red-700"),
+                ChangeKind::Changed => ("Changed", "bg-amber-100 text-amber-700"),
+            };
+            html.push_str(&format!(
+                r#"<tr><td class="px-4 py-2 text-sm font-mono font-semibold text-gray-900">{key}</td><td class="px-4 py-2"><span class="text-xs {badge_class} px-2 py-0.5 rounded-full">{label}</span></td><td class="px-4 py-2 text-sm font-mono text-gray-500">{old}</td><td class="px-4 py-2 text-sm font-mono text-gray-900">{new}</td></tr>"#,
+                key = escape_html(&change.key),
+                badge_class = badge_class,
+                label = label,
+                old = values_html(&change.old),
+                new = values_html(&change.new),
+            ));
+        }
+        html.push_str("</tbody></table></div>");
+    }
+
+    // Line-level unified diff
+    html.push_str(r#"<div class="bg-white rounded-xl border border-gray-200 overflow-hidden"><pre class="text-sm font-mono leading-relaxed overflow-x-auto">"#);
+    for hunk in hunks {
+        html.push_str(r#"<div class="px-4 py-1 bg-indigo-50 text-indigo-600">"#);
+        html.push_str(&hunk.header());
+        html.push_str("</div>");
+        for line in &hunk.lines {
+            let (prefix, class) = match line.tag {
+                LineTag::Context => (' ', "text-gray-600"),
+                LineTag::Added => ('+', "bg-emerald-50 text-emerald-800"),
+                LineTag::Removed => ('-', "bg-red-50 text-red-800"),
+            };
+            html.push_str(&format!(
+                r#"<div class="px-4 {class}">{prefix} {text}</div>"#,
+                class = class,
+                prefix = prefix,
+                text = escape_html(&line.text),
+            ));
+        }
+    }
+    html.push_str("</pre></div>");
+
+    html
+}
+
+fn values_html(values: &[String]) -> String {
+    if values.is_empty() {
+        return r#"<span class="text-gray-300">&mdash;</span>"#.to_string();
+    }
+    values
+        .iter()
+        .map(|v| escape_html(v))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::diff::unified_hunks;
+
+    #[test]
+    fn test_render_diff_no_changes() {
+        let html = render_diff(&[], &[]);
+        assert!(html.contains("No unsaved changes"));
+    }
+
+    #[test]
+    fn test_render_diff_escapes_and_marks_lines() {
+        let hunks = unified_hunks("title = a\n", "title = <b>\n", 3);
+        let changes = vec![KeyChange {
+            key: "title".to_string(),
+            kind: ChangeKind::Changed,
+            old: vec!["a".to_string()],
+            new: vec!["<b>".to_string()],
+        }];
+        let html = render_diff(&changes, &hunks);
+        assert!(html.contains("Changed"));
+        assert!(html.contains("+ title = &lt;b&gt;"));
+        assert!(html.contains("- title = a"));
+        assert!(!html.contains("<b>"));
+    }
+}
@@ -0,0 +1,191 @@
+use axum::extract::{Query, State};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::config_api::{escape_html, publish_toast, toast_html};
+use crate::app_state::SharedState;
+use crate::config::doctor::{apply_fix, diagnose, find_fix, DoctorContext, Finding, Priority};
+use crate::config::model::ConfigSchema;
+use crate::error::AppError;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DoctorQuery {
+    /// `json` for the findings as JSON instead of HTML.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct FixForm {
+    pub id: String,
+}
+
+async fn findings(state: &SharedState) -> Vec<Finding> {
+    let ghostty = state.ghostty().await;
+    let user_config = state.user_config.read().await;
+    let themes = state.themes.read().await;
+    diagnose(&DoctorContext {
+        config: &user_config,
+        schema: &ghostty.schema,
+        fonts: &ghostty.fonts,
+        themes: &themes,
+    })
+}
+
+/// GET /api/doctor — health checks on the config (missing fonts, files, and
+/// themes, palette gaps, shadowed keybinds, and lint), most important first.
+#[utoipa::path(
+    get,
+    path = "/api/doctor",
+    tag = "Validation",
+    summary = "Health checks with fixes: missing fonts, files, and themes, palette gaps, shadowed keybinds, and lint (`format=json` for JSON)",
+    params(DoctorQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn doctor(
+    State(state): State<SharedState>,
+    Query(query): Query<DoctorQuery>,
+) -> Response {
+    let findings = findings(&state).await;
+    if query.format.as_deref() == Some("json") {
+        return Json(findings).into_response();
+    }
+    Html(render_findings(&findings, &state.ghostty().await.schema)).into_response()
+}
+
+/// POST /api/doctor/fix — apply the fix for one finding in memory.
+#[utoipa::path(
+    post,
+    path = "/api/doctor/fix",
+    tag = "Validation",
+    summary = "Apply the fix for the finding with this `id`",
+    request_body(content = FixForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn fix(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<FixForm>,
+) -> Result<Html<String>, AppError> {
+    let changed = {
+        let ghostty = state.ghostty().await;
+        let mut user_config = state.user_config.write().await;
+        let themes = state.themes.read().await;
+        let fix = find_fix(
+            &DoctorContext {
+                config: &user_config,
+                schema: &ghostty.schema,
+                fonts: &ghostty.fonts,
+                themes: &themes,
+            },
+            &form.id,
+        );
+        fix.map(|fix| (fix.label.clone(), apply_fix(&mut user_config, &fix)))
+    };
+    let Some((label, _)) = changed else {
+        return Ok(Html(
+            toast_html(
+                "That problem is already gone; the list has been refreshed",
+                true,
+            ) + &render_panel_oob(&state).await,
+        ));
+    };
+    state.mark_unsaved().await;
+
+    let mut html = publish_toast(&state, &format!("{} (unsaved)", escape_html(&label)), false);
+    html.push_str(&render_panel_oob(&state).await);
+    Ok(Html(html))
+}
+
+async fn render_panel_oob(state: &SharedState) -> String {
+    format!(
+        r#"<div id="doctor-area" class="mb-6" hx-swap-oob="innerHTML">{}</div>"#,
+        render_findings(&findings(state).await, &state.ghostty().await.schema)
+    )
+}
+
+/// The doctor panel: nothing when the config is healthy, else one row per finding
+/// with its fix button.
+fn render_findings(findings: &[Finding], schema: &ConfigSchema) -> String {
+    if findings.is_empty() {
+        return String::new();
+    }
+    let items: String = findings
+        .iter()
+        .map(|f| {
+            let href = schema
+                .find_option(&f.key)
+                .map(|o| o.permalink())
+                .unwrap_or_else(|| "/editor".to_string());
+            let location = match f.line {
+                Some(line) => format!("Line {}", line),
+                None => escape_html(&f.key),
+            };
+            let (badge, badge_class) = match f.priority {
+                Priority::High => ("High", "bg-red-100 text-red-700"),
+                Priority::Medium => ("Medium", "bg-amber-100 text-amber-700"),
+                Priority::Low => ("Low", "bg-gray-100 text-gray-600"),
+            };
+            let fix = f
+                .fix
+                .as_ref()
+                .map(|fix| {
+                    format!(
+                        r##"<button type="button" hx-post="/api/doctor/fix" hx-vals='{{"id": "{id}"}}' hx-target="#toast-container" hx-swap="innerHTML"
+        class="ml-auto shrink-0 px-2 py-1 text-xs font-medium text-indigo-700 border border-indigo-300 rounded hover:bg-indigo-50">{label}</button>"##,
+                        id = escape_html(&f.id),
+                        label = escape_html(&fix.label),
+                    )
+                })
+                .unwrap_or_default();
+            format!(
+                r#"<li class="flex items-center gap-2"><span class="px-1.5 py-0.5 text-xs font-medium rounded {badge_class}">{badge}</span><a href="{href}" class="font-semibold hover:underline">{location}</a>: <span>{message}</span>{fix}</li>"#,
+                href = escape_html(&href),
+                message = escape_html(&f.message),
+            )
+        })
+        .collect();
+    let count = findings.len();
+    format!(
+        r#"<div class="border rounded-lg p-4 bg-amber-50 border-amber-300 text-amber-800" id="doctor-result">
+            <div class="flex items-center gap-2 font-medium mb-1">
+                <span>&#x26a0;</span>
+                <span>Config doctor: {count} {problems}</span>
+            </div>
+            <ul class="text-sm space-y-1 mt-2">{items}</ul>
+        </div>"#,
+        problems = if count == 1 { "problem" } else { "problems" },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::doctor::Fix;
+
+    #[test]
+    fn test_render_findings() {
+        let schema = ConfigSchema::new(Vec::new());
+        assert_eq!(render_findings(&[], &schema), "");
+
+        let finding = Finding {
+            id: "missing-font:3".to_string(),
+            check: "missing-font",
+            priority: Priority::High,
+            key: "font-family".to_string(),
+            line: Some(3),
+            message: "Font family \"Nope\" is not installed".to_string(),
+            fix: Some(Fix {
+                label: "Remove line".to_string(),
+                edits: Vec::new(),
+            }),
+        };
+        let html = render_findings(&[finding], &schema);
+        assert!(html.contains("1 problem<"));
+        assert!(html.contains(r#"hx-vals='{"id": "missing-font:3"}'"#));
+        assert!(html.contains("Line 3</a>"));
+        assert!(html.contains("&quot;Nope&quot;"));
+    }
+}
@@ -0,0 +1,155 @@
+use axum::extract::{Query, State};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use super::config_api::escape_html;
+use crate::app_state::SharedState;
+use crate::cli::effective::{
+    compare_effective, show_effective_config, EffectiveValue, ValueSource,
+};
+use crate::config::categorize::categorize_key;
+use crate::error::AppError;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EffectiveQuery {
+    /// Only values that differ from Ghostty's defaults, like `+show-config` does by default.
+    #[serde(default)]
+    pub changed: bool,
+    /// `json` for the values as JSON instead of HTML.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// GET /api/effective-config — the config Ghostty loads from disk, with where each
+/// value comes from: the file, an include, the default, or somewhere else.
+#[utoipa::path(
+    get,
+    path = "/api/effective-config",
+    tag = "Validation",
+    summary = "The config Ghostty loads from disk and where each value comes from; `changed=true` for non-default values only, `format=json` for JSON",
+    params(EffectiveQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn effective_config(
+    State(state): State<SharedState>,
+    Query(query): Query<EffectiveQuery>,
+) -> Result<Response, AppError> {
+    let ghostty = state.ghostty().await;
+    let output = show_effective_config(ghostty.path()?).await?;
+    let mut values = {
+        let user_config = state.user_config.read().await;
+        let included = state.included.read().await;
+        compare_effective(&output, &ghostty.schema, &user_config, &included)
+    };
+    if query.changed {
+        values.retain(EffectiveValue::changed);
+    }
+
+    if query.format.as_deref() == Some("json") {
+        return Ok(Json(values).into_response());
+    }
+    Ok(Html(render_effective(&values, query.changed)).into_response())
+}
+
+fn source_badge(source: ValueSource) -> &'static str {
+    match source {
+        ValueSource::File => {
+            r#"<span class="text-xs bg-indigo-100 text-indigo-700 px-2 py-0.5 rounded-full">File</span>"#
+        }
+        ValueSource::Include => {
+            r#"<span class="text-xs bg-purple-100 text-purple-700 px-2 py-0.5 rounded-full">Include</span>"#
+        }
+        ValueSource::Default => {
+            r#"<span class="text-xs bg-gray-100 text-gray-600 px-2 py-0.5 rounded-full">Default</span>"#
+        }
+        ValueSource::Other => {
+            r#"<span class="text-xs bg-amber-100 text-amber-700 px-2 py-0.5 rounded-full" title="Not set in your files and not the documented default: a platform default or a command-line flag">Elsewhere</span>"#
+        }
+    }
+}
+
+fn values_html(values: &[String]) -> String {
+    if values.iter().all(|v| v.is_empty()) {
+        return r#"<span class="text-gray-300">&mdash;</span>"#.to_string();
+    }
+    values
+        .iter()
+        .map(|v| escape_html(v))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+fn render_effective(values: &[EffectiveValue], changed_only: bool) -> String {
+    if values.is_empty() {
+        let message = if changed_only {
+            "Ghostty loads every option with its default value."
+        } else {
+            "Ghostty reported no options."
+        };
+        return format!(
+            r#"<div class="bg-white rounded-xl border border-gray-200 p-6 text-center text-gray-500">{}</div>"#,
+            message
+        );
+    }
+
+    let mut html = String::new();
+    html.push_str(r#"<div class="bg-white rounded-xl border border-gray-200 overflow-hidden"><table class="w-full" aria-label="Effective config"><thead class="bg-gray-50 border-b border-gray-200"><tr>"#);
+    for heading in ["Key", "Source", "Effective", "Default", "In your file"] {
+        html.push_str(&format!(
+            r#"<th class="px-4 py-3 text-left text-xs font-semibold text-gray-500 uppercase tracking-wider">{}</th>"#,
+            heading
+        ));
+    }
+    html.push_str(r#"</tr></thead><tbody class="divide-y divide-gray-100">"#);
+    for value in values {
+        html.push_str(&format!(
+            r#"<tr><td class="px-4 py-2 text-sm font-mono font-semibold text-gray-900"><a href="/category/{slug}#field-{key}" class="hover:underline">{key}</a></td><td class="px-4 py-2">{badge}</td><td class="px-4 py-2 text-sm font-mono text-gray-900">{effective}</td><td class="px-4 py-2 text-sm font-mono text-gray-500">{default}</td><td class="px-4 py-2 text-sm font-mono text-gray-500">{file}</td></tr>"#,
+            slug = categorize_key(&value.key).slug(),
+            key = escape_html(&value.key),
+            badge = source_badge(value.source),
+            effective = values_html(&value.values),
+            default = values_html(std::slice::from_ref(&value.default)),
+            file = values_html(&value.file),
+        ));
+    }
+    html.push_str("</tbody></table></div>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(key: &str, source: ValueSource) -> EffectiveValue {
+        EffectiveValue {
+            key: key.to_string(),
+            values: vec!["<none>".to_string()],
+            default: "auto".to_string(),
+            file: Vec::new(),
+            source,
+        }
+    }
+
+    #[test]
+    fn test_render_effective_empty() {
+        assert!(render_effective(&[], true).contains("default value"));
+    }
+
+    #[test]
+    fn test_render_effective_marks_sources_and_escapes() {
+        let html = render_effective(
+            &[
+                value("window-decoration", ValueSource::Other),
+                value("theme", ValueSource::Include),
+            ],
+            true,
+        );
+        assert!(html.contains("Elsewhere"));
+        assert!(html.contains("Include"));
+        assert!(html.contains("&lt;none&gt;"));
+        assert!(!html.contains("<none>"));
+    }
+}
@@ -0,0 +1,37 @@
+use std::convert::Infallible;
+
+use axum::extract::State;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::stream::{self, Stream};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::app_state::SharedState;
+use crate::events::AppEvent;
+
+/// GET /events — server-sent stream of [`AppEvent`](crate::events::AppEvent)s.
+///
+/// Each message uses the event's name as the SSE event type and its JSON as data.
+pub async fn events(
+    State(state): State<SharedState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let rx = state.events.subscribe();
+    // The stream ends after `ShuttingDown`, so a graceful shutdown isn't held open.
+    let stream = stream::unfold(Some(rx), |rx| async move {
+        let mut rx = rx?;
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    let data = serde_json::to_string(&event).unwrap_or_default();
+                    let sse = Event::default().event(event.name()).data(data);
+                    let rx = (event != AppEvent::ShuttingDown).then_some(rx);
+                    return Some((Ok(sse), rx));
+                }
+                // A slow tab missed some events; later ones still apply.
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
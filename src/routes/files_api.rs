@@ -0,0 +1,318 @@
+use std::path::{Path, PathBuf};
+
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+
+use super::config_api::escape_html;
+use crate::app_state::SharedState;
+use crate::config::includes::resolve_include;
+use crate::config::model::ConfigValueType;
+use crate::error::AppError;
+use crate::file_browser::{
+    assets_dir, browse_roots, image_content_type, list_dir, resolve_in, save_asset, Listing,
+};
+
+/// Uploads larger than this are refused; wallpapers are rarely bigger.
+pub const MAX_UPLOAD_BYTES: usize = 32 * 1024 * 1024;
+
+#[derive(Deserialize)]
+pub struct BrowseQuery {
+    /// The directory to list; the home directory when empty.
+    #[serde(default)]
+    pub path: String,
+    /// The path option a file is picked for.
+    pub key: String,
+    /// Include names starting with `.`.
+    #[serde(default)]
+    pub hidden: bool,
+    /// `json` for the listing as JSON instead of HTML.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// GET /api/files — a directory under the home directory, as a picker for the
+/// path option `key`. Without a `path`, the directory of the key's current value.
+pub async fn browse(
+    State(state): State<SharedState>,
+    Query(query): Query<BrowseQuery>,
+) -> Result<Response, AppError> {
+    path_option(&state, &query.key).await?;
+    let roots = browse_roots();
+    let dir = if query.path.is_empty() {
+        let user_config = state.user_config.read().await;
+        user_config
+            .get(&query.key)
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| resolve_include(&user_config.file_path, value))
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .and_then(|dir| resolve_in(&roots, &dir.to_string_lossy()).ok())
+            .map_or_else(|| resolve_in(&roots, ""), Ok)?
+    } else {
+        // The assets folder is only made by the first upload otherwise.
+        if Path::new(&query.path) == assets_dir() {
+            tokio::fs::create_dir_all(&query.path).await?;
+        }
+        resolve_in(&roots, &query.path)?
+    };
+    let listing = list_dir(&roots, &dir, query.hidden)?;
+
+    if query.format.as_deref() == Some("json") {
+        return Ok(Json(listing).into_response());
+    }
+    Ok(Html(render_listing(&listing, &query.key, query.hidden, None)).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct ThumbnailQuery {
+    pub path: String,
+}
+
+/// GET /api/files/thumbnail — an image under the home directory, for the picker.
+pub async fn thumbnail(Query(query): Query<ThumbnailQuery>) -> Result<Response, AppError> {
+    let path = resolve_in(&browse_roots(), &query.path)?;
+    let content_type = image_content_type(&path)
+        .ok_or_else(|| AppError::Config(format!("{} is not an image", path.display())))?;
+    let bytes = tokio::fs::read(&path).await?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, "private, max-age=300"),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct UploadQuery {
+    pub key: String,
+    /// The uploaded file's name, cleaned up before it is used.
+    pub name: String,
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// POST /api/files/upload — save the image in the body to the assets folder.
+///
+/// Answers with the assets folder's listing, the new image marked, so it can be
+/// picked like any other file.
+pub async fn upload(
+    State(state): State<SharedState>,
+    Query(query): Query<UploadQuery>,
+    body: Bytes,
+) -> Result<Response, AppError> {
+    path_option(&state, &query.key).await?;
+    let saved = save_asset(&assets_dir(), &query.name, &body)?;
+    tracing::info!(path = %saved.display(), "saved uploaded asset");
+
+    if query.format.as_deref() == Some("json") {
+        return Ok(Json(serde_json::json!({ "path": saved })).into_response());
+    }
+    let roots = browse_roots();
+    let dir = resolve_in(&roots, &assets_dir().to_string_lossy())?;
+    let listing = list_dir(&roots, &dir, false)?;
+    let saved = dir.join(saved.file_name().unwrap_or_default());
+    Ok(Html(render_listing(&listing, &query.key, false, Some(&saved))).into_response())
+}
+
+/// Refuse keys that aren't path options, which also keeps them safe in element ids.
+async fn path_option(state: &SharedState, key: &str) -> Result<(), AppError> {
+    match state.ghostty().await.schema.find_option(key) {
+        Some(option) if matches!(option.value_type, ConfigValueType::Path) => Ok(()),
+        Some(_) => Err(AppError::Config(format!("{} is not a path option", key))),
+        None => Err(AppError::Config(format!("Unknown option: {}", key))),
+    }
+}
+
+/// `/api/files` with the given query parameters.
+fn browse_url(key: &str, path: &Path, hidden: bool) -> String {
+    let path = path.to_string_lossy();
+    let mut params = vec![("key", key), ("path", path.as_ref())];
+    if hidden {
+        params.push(("hidden", "true"));
+    }
+    format!(
+        "/api/files?{}",
+        serde_urlencoded::to_string(params).unwrap_or_default()
+    )
+}
+
+fn thumbnail_url(path: &Path) -> String {
+    format!(
+        "/api/files/thumbnail?{}",
+        serde_urlencoded::to_string([("path", path.to_string_lossy())]).unwrap_or_default()
+    )
+}
+
+/// Attributes that make a button set `key` to `path`, in the field's input too.
+fn pick_attributes(key: &str, path: &Path) -> String {
+    let path = path.to_string_lossy();
+    format!(
+        r##"title="Use {path_attr}" data-path="{path_attr}" hx-put="/api/config/{key}" hx-vals="{vals}" hx-target="#toast-container" hx-swap="innerHTML" onclick="document.getElementById('input-{key}').value = this.dataset.path" hx-on::after-request="refreshPreview()""##,
+        path_attr = escape_html(&path),
+        key = key,
+        vals = escape_html(&serde_json::json!({ "value": path }).to_string()),
+    )
+}
+
+/// The picker: the directory's entries, with buttons to go up, to the assets
+/// folder, and to show hidden files. Picking a file sets `key` to its path;
+/// images dropped on it are uploaded to the assets folder.
+fn render_listing(
+    listing: &Listing,
+    key: &str,
+    hidden: bool,
+    uploaded: Option<&PathBuf>,
+) -> String {
+    let target = format!("#file-browser-{}", key);
+    let nav_button = |label: &str, url: &str| {
+        format!(
+            r##"<button type="button" class="px-2 py-1 text-xs text-gray-600 bg-gray-100 rounded hover:bg-gray-200" hx-get="{url}" hx-target="{target}" hx-swap="innerHTML">{label}</button>"##,
+            url = escape_html(url),
+            target = target,
+            label = label,
+        )
+    };
+
+    let mut html = format!(
+        r#"<div class="mt-2 border border-gray-200 rounded-lg bg-white" data-upload-url="/api/files/upload?key={key}" data-upload-target="{target}">"#,
+        key = key,
+        target = target,
+    );
+    html.push_str(r#"<div class="flex items-center gap-2 px-3 py-2 border-b border-gray-100">"#);
+    if let Some(parent) = &listing.parent {
+        html.push_str(&nav_button("&uarr; Up", &browse_url(key, parent, hidden)));
+    }
+    html.push_str(&format!(
+        r#"<code class="flex-1 min-w-0 truncate text-xs text-gray-600" title="{path}">{path}</code>"#,
+        path = escape_html(&listing.path.to_string_lossy()),
+    ));
+    html.push_str(&nav_button(
+        "Assets",
+        &browse_url(key, &assets_dir(), hidden),
+    ));
+    html.push_str(&nav_button(
+        if hidden { "Hide hidden" } else { "Show hidden" },
+        &browse_url(key, &listing.path, !hidden),
+    ));
+    if key == "working-directory" {
+        html.push_str(&format!(
+            r#"<button type="button" class="px-2 py-1 text-xs text-white bg-indigo-600 rounded hover:bg-indigo-700" {}>Use this folder</button>"#,
+            pick_attributes(key, &listing.path),
+        ));
+    }
+    html.push_str(&format!(
+        r#"<button type="button" class="px-2 py-1 text-xs text-gray-400 hover:text-gray-600" aria-label="Close file browser" onclick="document.querySelector('{target}').innerHTML = ''">&times;</button></div>"#,
+        target = target,
+    ));
+
+    if listing.entries.is_empty() {
+        html.push_str(
+            r#"<p class="px-3 py-4 text-sm text-center text-gray-400">This folder is empty</p>"#,
+        );
+    } else {
+        html.push_str(
+            r#"<ul class="grid grid-cols-3 sm:grid-cols-4 gap-2 p-3 max-h-72 overflow-y-auto">"#,
+        );
+        for entry in &listing.entries {
+            let name = escape_html(&entry.name);
+            if entry.is_dir {
+                html.push_str(&format!(
+                    r##"<li><button type="button" class="w-full h-full p-2 text-left text-xs rounded border border-gray-100 hover:border-indigo-300 hover:bg-indigo-50" hx-get="{url}" hx-target="{target}" hx-swap="innerHTML"><span aria-hidden="true">&#x1F4C1;</span> <span class="break-all">{name}</span></button></li>"##,
+                    url = escape_html(&browse_url(key, &entry.path, hidden)),
+                    target = target,
+                    name = name,
+                ));
+                continue;
+            }
+            let preview = if entry.is_image {
+                format!(
+                    r#"<img src="{}" alt="" loading="lazy" class="w-full h-16 object-cover rounded mb-1 bg-gray-100">"#,
+                    escape_html(&thumbnail_url(&entry.path)),
+                )
+            } else {
+                String::new()
+            };
+            let ring = if uploaded == Some(&entry.path) {
+                "border-indigo-500 ring-2 ring-indigo-300"
+            } else {
+                "border-gray-100"
+            };
+            html.push_str(&format!(
+                r#"<li><button type="button" class="w-full h-full p-2 text-left text-xs rounded border {ring} hover:border-indigo-300 hover:bg-indigo-50" {pick}>{preview}<span class="break-all">{name}</span></button></li>"#,
+                ring = ring,
+                pick = pick_attributes(key, &entry.path),
+                preview = preview,
+                name = name,
+            ));
+        }
+        html.push_str("</ul>");
+    }
+    html.push_str(r#"<p class="px-3 py-2 text-xs text-gray-400 border-t border-gray-100">Drop an image here to copy it into the assets folder</p></div>"#);
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_browser::DirEntry;
+
+    fn listing() -> Listing {
+        Listing {
+            path: PathBuf::from("/home/me/Pictures"),
+            parent: Some(PathBuf::from("/home/me")),
+            entries: vec![
+                DirEntry {
+                    name: "Walls & more".to_string(),
+                    path: PathBuf::from("/home/me/Pictures/Walls & more"),
+                    is_dir: true,
+                    is_image: false,
+                },
+                DirEntry {
+                    name: "sky.png".to_string(),
+                    path: PathBuf::from("/home/me/Pictures/sky.png"),
+                    is_dir: false,
+                    is_image: true,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_browse_url_encodes_path() {
+        assert_eq!(
+            browse_url("background-image", Path::new("/home/me/a b&c"), false),
+            "/api/files?key=background-image&path=%2Fhome%2Fme%2Fa+b%26c"
+        );
+    }
+
+    #[test]
+    fn test_render_listing_links_entries() {
+        let html = render_listing(&listing(), "background-image", false, None);
+        assert!(html.contains("&uarr; Up"));
+        assert!(html.contains("Walls &amp; more"));
+        assert!(html.contains(r#"hx-put="/api/config/background-image""#));
+        assert!(html.contains("/api/files/thumbnail?path=%2Fhome%2Fme%2FPictures%2Fsky.png"));
+        assert!(html.contains("&quot;value&quot;:&quot;/home/me/Pictures/sky.png&quot;"));
+        assert!(!html.contains("ring-2"));
+        assert!(!html.contains("Use this folder"));
+    }
+
+    #[test]
+    fn test_render_listing_picks_folders_for_working_directory() {
+        let html = render_listing(&listing(), "working-directory", false, None);
+        assert!(html.contains("Use this folder"));
+        assert!(html.contains(r#"data-path="/home/me/Pictures""#));
+    }
+
+    #[test]
+    fn test_render_listing_marks_upload() {
+        let uploaded = PathBuf::from("/home/me/Pictures/sky.png");
+        let html = render_listing(&listing(), "background-image", false, Some(&uploaded));
+        assert!(html.contains("ring-2"));
+    }
+}
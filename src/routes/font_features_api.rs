@@ -0,0 +1,144 @@
+use axum::extract::{Path, State};
+use axum::response::Html;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use super::config_api::{escape_html, publish_toast, render_entries, toast_html};
+use crate::app_state::SharedState;
+use crate::config::font_features::{common_features, parse_features, set_feature};
+use crate::error::AppError;
+
+/// GET /api/fonts/features — a toggle for each common OpenType feature.
+#[utoipa::path(
+    get,
+    path = "/api/fonts/features",
+    tag = "Fonts",
+    summary = "OpenType feature toggles",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn panel(State(state): State<SharedState>) -> Html<String> {
+    let values = state.all_values("font-feature").await;
+    let set = parse_features(&values.iter().map(String::as_str).collect::<Vec<_>>());
+    Html(render_panel(&set))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct FeatureForm {
+    /// `on`, `off`, or `default`.
+    pub state: String,
+}
+
+/// PUT /api/fonts/features/:tag — turn a feature on or off, or back to the font's default.
+#[utoipa::path(
+    put,
+    path = "/api/fonts/features/{tag}",
+    tag = "Fonts",
+    summary = "Turn a feature on or off",
+    params(("tag" = String, Path)),
+    request_body(content = FeatureForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn set(
+    State(state): State<SharedState>,
+    Path(tag): Path<String>,
+    axum::Form(form): axum::Form<FeatureForm>,
+) -> Result<Html<String>, AppError> {
+    if tag.is_empty() || tag.len() > 4 || !tag.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Ok(Html(toast_html(
+            &format!("Invalid feature tag: {}", escape_html(&tag)),
+            true,
+        )));
+    }
+    let (enabled, message) = match form.state.as_str() {
+        "on" => (Some(true), format!("Turned on {} (unsaved)", tag)),
+        "off" => (Some(false), format!("Turned off {} (unsaved)", tag)),
+        "default" => (
+            None,
+            format!("{} back to the font's default (unsaved)", tag),
+        ),
+        other => {
+            return Ok(Html(toast_html(
+                &format!("Unknown feature state: {}", escape_html(other)),
+                true,
+            )))
+        }
+    };
+
+    // Clear the feature from every file that mentions it, then set it in one place.
+    set_feature(&mut *state.user_config.write().await, &tag, None);
+    for config in state.included.write().await.iter_mut() {
+        set_feature(config, &tag, None);
+    }
+    if enabled.is_some() {
+        state
+            .edit_key("font-feature", |config| set_feature(config, &tag, enabled))
+            .await?;
+    }
+    state.mark_unsaved().await;
+    let mut html = publish_toast(&state, &message, false);
+    html.push_str(&render_entries(
+        "font-feature",
+        &state.all_values("font-feature").await,
+        true,
+    ));
+    Ok(Html(html))
+}
+
+fn render_toggle(tag: &str, name: &str, enabled: Option<bool>) -> String {
+    let option = |value: &str, label: &str, selected: bool| {
+        format!(
+            r#"<option value="{}"{}>{}</option>"#,
+            value,
+            if selected { " selected" } else { "" },
+            label
+        )
+    };
+    format!(
+        r##"<label class="flex items-center justify-between gap-2 text-sm"><span><span class="font-mono text-xs {tag_class}">{tag}</span> <span class="text-gray-600">{name}</span></span><select name="state" aria-label="{name} ({tag})" class="px-2 py-1 border border-gray-300 rounded-lg text-sm bg-white focus:ring-2 focus:ring-indigo-500" hx-put="/api/fonts/features/{tag}" hx-target="#toast-container" hx-swap="innerHTML" hx-include="this">{default}{on}{off}</select></label>"##,
+        tag = escape_html(tag),
+        tag_class = if enabled.is_some() {
+            "text-indigo-700 font-semibold"
+        } else {
+            "text-gray-400"
+        },
+        name = escape_html(name),
+        default = option("default", "Default", enabled.is_none()),
+        on = option("on", "On", enabled == Some(true)),
+        off = option("off", "Off", enabled == Some(false)),
+    )
+}
+
+fn render_panel(set: &[(String, bool)]) -> String {
+    let common = common_features();
+    let enabled = |tag: &str| set.iter().find(|(t, _)| t == tag).map(|(_, e)| *e);
+    let mut html = String::from(
+        r#"<div class="bg-white rounded-xl border border-gray-200 p-5 mb-6"><h3 class="text-sm font-semibold text-gray-900 mb-1">Font features</h3><p class="text-xs text-gray-400 mb-3">Fonts ignore features they don't have; stylistic sets differ from font to font.</p><div class="grid md:grid-cols-2 gap-x-6 gap-y-2">"#,
+    );
+    for (tag, name) in &common {
+        html.push_str(&render_toggle(tag, name, enabled(tag)));
+    }
+    // Features set in the config that aren't in the list still get a toggle.
+    for (tag, on) in set
+        .iter()
+        .filter(|(t, _)| !common.iter().any(|(c, _)| c == t))
+    {
+        html.push_str(&render_toggle(tag, "", Some(*on)));
+    }
+    html.push_str("</div></div>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_panel() {
+        let html = render_panel(&[("calt".to_string(), false), ("cv05".to_string(), true)]);
+        assert!(html.contains(r#"hx-put="/api/fonts/features/ss20""#));
+        assert!(html.contains(r#"aria-label="Contextual alternates (calt)""#));
+        assert!(html.contains(r#"hx-put="/api/fonts/features/cv05""#));
+        assert_eq!(html.matches(r#"<option value="off" selected>"#).count(), 1);
+        assert_eq!(html.matches(r#"<option value="on" selected>"#).count(), 1);
+    }
+}
@@ -0,0 +1,208 @@
+use axum::extract::State;
+use axum::response::Html;
+
+use super::config_api::escape_html;
+use super::fonts_api::css_string;
+use crate::app_state::SharedState;
+use crate::config::adjust_metric::{metric_group, Adjustment, ADJUSTMENT_UNITS, METRIC_GROUPS};
+use crate::config::model::ConfigValueType;
+
+/// Ghostty's default `font-size`, for when neither the config nor the schema has one.
+const DEFAULT_FONT_SIZE: f64 = 13.0;
+
+/// GET /api/fonts/metrics — the `adjust-*` keys grouped, each with an amount and unit.
+#[utoipa::path(
+    get,
+    path = "/api/fonts/metrics",
+    tag = "Fonts",
+    summary = "The `adjust-*` metric controls",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn panel(State(state): State<SharedState>) -> Html<String> {
+    let ghostty = state.ghostty().await;
+    let mut metrics = Vec::new();
+    for option in ghostty.schema.options() {
+        if matches!(option.value_type, ConfigValueType::AdjustMetric) {
+            let value = state.value_of(&option.key).await;
+            metrics.push((
+                option.key.clone(),
+                value.as_deref().and_then(Adjustment::parse),
+            ));
+        }
+    }
+    Html(render_panel(&metrics))
+}
+
+/// GET /api/fonts/metrics/preview — sample text with the adjustments applied.
+#[utoipa::path(
+    get,
+    path = "/api/fonts/metrics/preview",
+    tag = "Fonts",
+    summary = "Sample text with metric adjustments applied",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn preview(State(state): State<SharedState>) -> Html<String> {
+    let ghostty = state.ghostty().await;
+    let family = state.all_values("font-family").await.into_iter().next();
+    let size = match state.value_of("font-size").await {
+        Some(size) => size.parse().ok(),
+        None => ghostty
+            .schema
+            .find_option("font-size")
+            .and_then(|o| o.default_value.parse().ok()),
+    }
+    .unwrap_or(DEFAULT_FONT_SIZE);
+    let mut adjustments = Vec::new();
+    for option in ghostty.schema.options() {
+        if let Some(adjustment) = state
+            .value_of(&option.key)
+            .await
+            .as_deref()
+            .and_then(Adjustment::parse)
+        {
+            adjustments.push((option.key.clone(), adjustment));
+        }
+    }
+    Html(render_preview(family.as_deref(), size, &adjustments))
+}
+
+fn render_metric(key: &str, value: Option<Adjustment>) -> String {
+    let (amount, unit) = value.map(|v| v.split()).unwrap_or_default();
+    let units: String = ADJUSTMENT_UNITS
+        .iter()
+        .map(|u| {
+            format!(
+                r#"<option value="{u}"{selected}>{u}</option>"#,
+                u = u,
+                selected = if *u == unit { " selected" } else { "" }
+            )
+        })
+        .collect();
+    let put = format!(
+        r##"hx-put="/api/config/{key}" hx-target="#toast-container" hx-swap="innerHTML" hx-include="#metric-{key}" hx-on::after-request="htmx.trigger('#metrics-preview', 'refresh')""##,
+        key = key
+    );
+    format!(
+        r#"<div id="metric-{key}" class="flex items-center gap-2"><label for="metric-input-{key}" class="flex-1 text-sm text-gray-700">{label}</label><input type="number" step="any" id="metric-input-{key}" name="value" value="{amount}" placeholder="0" class="w-20 px-2 py-1 border border-gray-300 rounded-lg text-sm focus:ring-2 focus:ring-indigo-500" hx-trigger="change" {put}><select name="unit" aria-label="{key} unit" class="px-2 py-1 border border-gray-300 rounded-lg text-sm bg-white focus:ring-2 focus:ring-indigo-500" {put}>{units}</select></div>"#,
+        key = key,
+        label = escape_html(key.strip_prefix("adjust-").unwrap_or(key)),
+        amount = amount,
+        put = put,
+        units = units,
+    )
+}
+
+fn render_panel(metrics: &[(String, Option<Adjustment>)]) -> String {
+    let mut html = String::from(
+        r#"<div class="bg-white rounded-xl border border-gray-200 p-5 mb-6"><h3 class="text-sm font-semibold text-gray-900 mb-1">Font metrics</h3><p class="text-xs text-gray-400 mb-3">Whole pixels added to each metric, or a percentage of it. Leave empty for the font's own.</p><div class="grid md:grid-cols-2 gap-6"><div class="space-y-4">"#,
+    );
+    for group in METRIC_GROUPS {
+        let rows: String = metrics
+            .iter()
+            .filter(|(key, _)| metric_group(key) == *group)
+            .map(|(key, value)| render_metric(key, *value))
+            .collect();
+        if !rows.is_empty() {
+            html.push_str(&format!(
+                r#"<fieldset><legend class="text-xs font-semibold uppercase tracking-wide text-gray-400 mb-2">{}</legend><div class="space-y-2">{}</div></fieldset>"#,
+                group, rows
+            ));
+        }
+    }
+    html.push_str(
+        r#"</div><div id="metrics-preview" hx-get="/api/fonts/metrics/preview" hx-trigger="load, refresh" hx-swap="innerHTML"></div></div></div>"#,
+    );
+    html
+}
+
+/// `key`'s adjustment applied to `base`, or `base` when it isn't adjusted.
+fn adjusted(adjustments: &[(String, Adjustment)], key: &str, base: f64) -> f64 {
+    adjustments
+        .iter()
+        .find(|(k, _)| k == key)
+        .map_or(base, |(_, a)| a.apply(base))
+}
+
+/// Sample text with the adjustments applied in CSS. Base metrics are estimated
+/// from the font size, since only Ghostty measures the real font.
+fn render_preview(family: Option<&str>, size: f64, adjustments: &[(String, Adjustment)]) -> String {
+    let size = size.clamp(6.0, 48.0);
+    let (width, height) = (size * 0.6, size * 1.2);
+    let thickness = (size / 14.0).max(1.0);
+    let metric = |key: &str, base: f64| adjusted(adjustments, key, base).max(0.0);
+    let cell_width = metric("adjust-cell-width", width);
+    let cell_height = metric("adjust-cell-height", height);
+    let family = match family {
+        Some(family) => format!("{}, monospace", escape_html(&css_string(family))),
+        None => "monospace".to_string(),
+    };
+    format!(
+        r#"<div class="rounded-lg bg-gray-900 text-gray-100 p-4 overflow-x-auto" role="img" aria-label="Sample text with the font metric adjustments">
+<div class="text-xs text-gray-400 mb-3 font-sans">Cell {w:.1} &times; {h:.1} px (about {bw:.1} &times; {bh:.1} unadjusted)</div>
+<div style="font-family: {family}; font-size: {size}px; line-height: {h:.1}px; letter-spacing: {spacing:.2}px">
+<div><span style="position: relative; top: {baseline:.1}px">Baseline</span> <span style="text-decoration: underline; text-underline-offset: {underline:.1}px; text-decoration-thickness: {underline_thickness:.1}px">underline</span> <span style="text-decoration: line-through; text-decoration-thickness: {strike_thickness:.1}px">strikethrough</span> <span style="text-decoration: overline; text-decoration-thickness: {overline_thickness:.1}px">overline</span></div>
+<div>$ echo hello<span style="display: inline-block; vertical-align: middle; margin-left: 2px; width: {cursor_thickness:.1}px; height: {cursor_height:.1}px; background: #e5e7eb"></span></div>
+<div>&#x250c;&#x2500;&#x2500;&#x2510; &#x2588;&#x2591;&#x2592;&#x2593; &#x2580;&#x2584;</div>
+</div></div>"#,
+        w = cell_width,
+        h = cell_height,
+        bw = width,
+        bh = height,
+        family = family,
+        size = size,
+        spacing = cell_width - width,
+        // Ghostty's baseline adjustment moves text up.
+        baseline = -(adjusted(adjustments, "adjust-font-baseline", 0.0)),
+        underline = adjusted(adjustments, "adjust-underline-position", thickness),
+        underline_thickness = metric("adjust-underline-thickness", thickness),
+        strike_thickness = metric("adjust-strikethrough-thickness", thickness),
+        overline_thickness = metric("adjust-overline-thickness", thickness),
+        cursor_thickness = metric("adjust-cursor-thickness", thickness),
+        cursor_height = metric("adjust-cursor-height", cell_height),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_panel() {
+        let metrics = [
+            (
+                "adjust-cell-height".to_string(),
+                Some(Adjustment::Percent(10.0)),
+            ),
+            ("adjust-cursor-thickness".to_string(), None),
+            (
+                "adjust-underline-position".to_string(),
+                Some(Adjustment::Pixels(-1)),
+            ),
+        ];
+        let html = render_panel(&metrics);
+        let cell = html.find(">Cell</legend>").unwrap();
+        let decorations = html.find(">Decorations</legend>").unwrap();
+        let cursor = html.find(">Cursor</legend>").unwrap();
+        assert!(cell < decorations && decorations < cursor);
+        assert!(!html.contains(">Other</legend>"));
+        assert!(html.contains(r#"id="metric-input-adjust-cell-height" name="value" value="10""#));
+        assert!(html.contains(r#"<option value="%" selected>%</option>"#));
+        assert!(html.contains(r#"name="value" value="-1""#));
+        assert!(html.contains(r##"hx-include="#metric-adjust-cursor-thickness""##));
+    }
+
+    #[test]
+    fn test_render_preview() {
+        let html = render_preview(
+            Some("JetBrains Mono"),
+            10.0,
+            &[
+                ("adjust-cell-height".to_string(), Adjustment::Percent(50.0)),
+                ("adjust-cell-width".to_string(), Adjustment::Pixels(2)),
+            ],
+        );
+        assert!(html.contains("Cell 8.0 &times; 18.0 px (about 6.0 &times; 12.0 unadjusted)"));
+        assert!(html.contains("line-height: 18.0px; letter-spacing: 2.00px"));
+        assert!(html.contains("font-family: &#39;JetBrains Mono&#39;, monospace"));
+    }
+}
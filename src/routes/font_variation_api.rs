@@ -0,0 +1,324 @@
+use axum::extract::{Path, State};
+use axum::response::Html;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use super::config_api::{escape_html, publish_toast, render_entries, toast_html};
+use crate::app_state::SharedState;
+use crate::cli::fonts::font_axes;
+use crate::config::font_variation::{
+    format_axis_value, parse_variation, remove_variation, set_variation, standard_axes, FontAxis,
+    VARIATION_KEYS,
+};
+use crate::error::AppError;
+
+/// The sliders for one `font-variation*` key.
+#[derive(Debug, Clone, PartialEq)]
+struct VariationSection {
+    key: &'static str,
+    /// The family the key applies to, if one is configured.
+    family: Option<String>,
+    axes: Vec<FontAxis>,
+    /// False when `axes` are the standard ones because the font's couldn't be read.
+    axes_known: bool,
+    /// The axis values the config sets.
+    values: Vec<(String, f32)>,
+}
+
+/// Axes of `family`, from the cache or else its font files.
+async fn axes_for(state: &SharedState, family: &str) -> Option<Vec<FontAxis>> {
+    if let Some(axes) = state.font_axes.read().await.get(family) {
+        return axes.clone();
+    }
+    let name = family.to_string();
+    let axes = tokio::task::spawn_blocking(move || font_axes(&name))
+        .await
+        .ok()
+        .flatten();
+    state
+        .font_axes
+        .write()
+        .await
+        .insert(family.to_string(), axes.clone());
+    axes
+}
+
+async fn sections(state: &SharedState) -> Vec<VariationSection> {
+    let regular_family = state.all_values("font-family").await.into_iter().next();
+    let mut sections = Vec::new();
+    for (key, family_key) in VARIATION_KEYS {
+        // Styled variants use the regular family unless they name their own.
+        let family = state
+            .all_values(family_key)
+            .await
+            .into_iter()
+            .next()
+            .or_else(|| regular_family.clone());
+        let axes = match &family {
+            Some(family) => axes_for(state, family).await,
+            None => None,
+        };
+        let values = state
+            .all_values(key)
+            .await
+            .iter()
+            .filter_map(|v| parse_variation(v))
+            .collect();
+        sections.push(VariationSection {
+            key,
+            family,
+            axes_known: axes.is_some(),
+            axes: axes.unwrap_or_else(standard_axes),
+            values,
+        });
+    }
+    sections
+}
+
+/// GET /api/fonts/variation — axis sliders for each `font-variation*` key.
+#[utoipa::path(
+    get,
+    path = "/api/fonts/variation",
+    tag = "Fonts",
+    summary = "Variable font axis sliders",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn panel(State(state): State<SharedState>) -> Html<String> {
+    Html(render_panel(&sections(&state).await))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AxisForm {
+    pub value: f32,
+}
+
+fn check_key_and_tag(key: &str, tag: &str) -> Result<(), String> {
+    if !VARIATION_KEYS.iter().any(|(k, _)| *k == key) {
+        return Err(format!("{} is not a font-variation key", escape_html(key)));
+    }
+    if tag.is_empty() || tag.len() > 4 || !tag.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("Invalid axis tag: {}", escape_html(tag)));
+    }
+    Ok(())
+}
+
+/// PUT /api/fonts/variation/:key/:tag — set one axis, keeping the key's other axes.
+#[utoipa::path(
+    put,
+    path = "/api/fonts/variation/{key}/{tag}",
+    tag = "Fonts",
+    summary = "Set one variation axis",
+    params(("key" = String, Path), ("tag" = String, Path)),
+    request_body(content = AxisForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn set_axis(
+    State(state): State<SharedState>,
+    Path((key, tag)): Path<(String, String)>,
+    axum::Form(form): axum::Form<AxisForm>,
+) -> Result<Html<String>, AppError> {
+    if let Err(message) = check_key_and_tag(&key, &tag) {
+        return Ok(Html(toast_html(&message, true)));
+    }
+    state
+        .edit_key(&key, |config| set_variation(config, &key, &tag, form.value))
+        .await?;
+    axis_changed(
+        &state,
+        &key,
+        &format!(
+            "Set {} {} to {} (unsaved)",
+            key,
+            tag,
+            format_axis_value(form.value)
+        ),
+    )
+    .await
+}
+
+/// DELETE /api/fonts/variation/:key/:tag — drop the axis so the font's default applies.
+#[utoipa::path(
+    delete,
+    path = "/api/fonts/variation/{key}/{tag}",
+    tag = "Fonts",
+    summary = "Reset one variation axis",
+    params(("key" = String, Path), ("tag" = String, Path)),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn reset_axis(
+    State(state): State<SharedState>,
+    Path((key, tag)): Path<(String, String)>,
+) -> Result<Html<String>, AppError> {
+    if let Err(message) = check_key_and_tag(&key, &tag) {
+        return Ok(Html(toast_html(&message, true)));
+    }
+    let mut removed = remove_variation(&mut *state.user_config.write().await, &key, &tag);
+    for config in state.included.write().await.iter_mut() {
+        removed |= remove_variation(config, &key, &tag);
+    }
+    if !removed {
+        return Ok(Html(toast_html(
+            &format!("{} doesn't set {}", key, escape_html(&tag)),
+            true,
+        )));
+    }
+    axis_changed(&state, &key, &format!("Reset {} {} (unsaved)", key, tag)).await
+}
+
+/// Toast, plus the key's entry list on the page brought up to date.
+async fn axis_changed(
+    state: &SharedState,
+    key: &str,
+    message: &str,
+) -> Result<Html<String>, AppError> {
+    state.mark_unsaved().await;
+    let mut html = publish_toast(state, &escape_html(message), false);
+    html.push_str(&render_entries(key, &state.all_values(key).await, true));
+    Ok(Html(html))
+}
+
+fn render_axis(key: &str, axis: &FontAxis, value: Option<f32>) -> String {
+    let step = if axis.max - axis.min > 10.0 { 1.0 } else { 0.1 };
+    let reset = if value.is_some() {
+        format!(
+            r##"<button type="button" hx-delete="/api/fonts/variation/{key}/{tag}" hx-target="#toast-container" hx-swap="innerHTML" hx-on::after-request="if(event.detail.successful) htmx.trigger('#font-variations', 'refresh')" aria-label="Reset {label}" class="text-gray-400 hover:text-red-500 transition-colors px-1">&times;</button>"##,
+            key = key,
+            tag = escape_html(&axis.tag),
+            label = escape_html(axis.label()),
+        )
+    } else {
+        String::new()
+    };
+    format!(
+        r##"<div class="flex items-center gap-3"><label for="axis-{key}-{tag}" class="w-28 text-sm text-gray-700">{label} <span class="font-mono text-xs text-gray-400">{tag}</span></label><input type="range" id="axis-{key}-{tag}" name="value" min="{min}" max="{max}" step="{step}" value="{current}" class="flex-1 accent-indigo-600" oninput="this.nextElementSibling.textContent = this.value" hx-put="/api/fonts/variation/{key}/{tag}" hx-target="#toast-container" hx-swap="innerHTML" hx-trigger="change" hx-include="this"><output class="w-12 text-right font-mono text-sm {value_class}">{current}</output>{reset}</div>"##,
+        key = key,
+        tag = escape_html(&axis.tag),
+        label = escape_html(axis.label()),
+        min = format_axis_value(axis.min),
+        max = format_axis_value(axis.max),
+        step = step,
+        current = format_axis_value(value.unwrap_or(axis.default)),
+        value_class = if value.is_some() {
+            "text-indigo-700"
+        } else {
+            "text-gray-400"
+        },
+        reset = reset,
+    )
+}
+
+fn render_section(section: &VariationSection) -> String {
+    let family = match &section.family {
+        Some(family) => format!(r#"<span class="font-mono">{}</span>"#, escape_html(family)),
+        None => "the default font".to_string(),
+    };
+    let note = if section.axes_known && section.axes.is_empty() {
+        r#"<p class="text-sm text-gray-500">This font has no variation axes.</p>"#
+    } else if section.axes_known {
+        ""
+    } else {
+        r#"<p class="text-xs text-gray-400 mb-2">Couldn't read this font's axes, so the standard ones are shown; Ghostty ignores axes the font doesn't have.</p>"#
+    };
+    let value_of = |tag: &str| {
+        section
+            .values
+            .iter()
+            .rev()
+            .find(|(t, _)| t == tag)
+            .map(|(_, v)| *v)
+    };
+    let mut rows: String = section
+        .axes
+        .iter()
+        .map(|axis| render_axis(section.key, axis, value_of(&axis.tag)))
+        .collect();
+    // Axes set by hand that the font doesn't list still get a row, to reset them.
+    let mut extra: Vec<&str> = Vec::new();
+    for (tag, value) in section.values.iter().rev() {
+        if section.axes.iter().any(|a| &a.tag == tag) || extra.contains(&tag.as_str()) {
+            continue;
+        }
+        extra.push(tag);
+        let axis = FontAxis {
+            tag: tag.clone(),
+            min: value.min(0.0),
+            default: *value,
+            max: value.max(1.0),
+        };
+        rows.push_str(&render_axis(section.key, &axis, Some(*value)));
+    }
+    format!(
+        r#"<p class="text-sm text-gray-600 mb-2"><span class="font-mono font-semibold text-gray-900">{key}</span> for {family}</p>{note}<div class="space-y-2">{rows}</div>"#,
+        key = section.key,
+        family = family,
+        note = note,
+        rows = rows,
+    )
+}
+
+fn render_panel(sections: &[VariationSection]) -> String {
+    let mut html = String::from(
+        r#"<div class="bg-white rounded-xl border border-gray-200 p-5 mb-6"><h3 class="text-sm font-semibold text-gray-900 mb-3">Font variations</h3>"#,
+    );
+    for (i, section) in sections.iter().enumerate() {
+        if i == 0 {
+            html.push_str(&render_section(section));
+        } else {
+            html.push_str(&format!(
+                r#"<details class="mt-4"{open}><summary class="text-sm text-gray-500 cursor-pointer hover:text-gray-700">{key}</summary><div class="mt-2">{body}</div></details>"#,
+                open = if section.values.is_empty() { "" } else { " open" },
+                key = section.key,
+                body = render_section(section),
+            ));
+        }
+    }
+    html.push_str("</div>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(key: &'static str, values: Vec<(String, f32)>) -> VariationSection {
+        VariationSection {
+            key,
+            family: Some("Recursive Mono".to_string()),
+            axes: vec![FontAxis {
+                tag: "wght".to_string(),
+                min: 300.0,
+                default: 400.0,
+                max: 1000.0,
+            }],
+            axes_known: true,
+            values,
+        }
+    }
+
+    #[test]
+    fn test_render_panel() {
+        let sections = [
+            section("font-variation", vec![("wght".to_string(), 600.0)]),
+            section("font-variation-bold", vec![("CASL".to_string(), 1.0)]),
+            section("font-variation-italic", Vec::new()),
+        ];
+        let html = render_panel(&sections);
+        assert!(html.contains(
+            r##"hx-put="/api/fonts/variation/font-variation/wght" hx-target="#toast-container""##
+        ));
+        assert!(html.contains(r#"min="300" max="1000" step="1" value="600""#));
+        assert!(html.contains(r#"hx-delete="/api/fonts/variation/font-variation/wght""#));
+        // A hand-set axis the font doesn't list can still be reset.
+        assert!(html.contains(r#"hx-delete="/api/fonts/variation/font-variation-bold/CASL""#));
+        assert!(!html.contains(r#"hx-delete="/api/fonts/variation/font-variation-italic"#));
+        assert_eq!(html.matches(" open>").count(), 1);
+    }
+
+    #[test]
+    fn test_check_key_and_tag() {
+        assert!(check_key_and_tag("font-variation-bold", "wght").is_ok());
+        assert!(check_key_and_tag("font-size", "wght").is_err());
+        assert!(check_key_and_tag("font-variation", "weight").is_err());
+        assert!(check_key_and_tag("font-variation", "w\"").is_err());
+    }
+}
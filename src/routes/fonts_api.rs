@@ -1,19 +1,40 @@
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::response::Html;
 use serde::Deserialize;
+use utoipa::IntoParams;
 
+use super::config_api::escape_html;
 use crate::app_state::SharedState;
+use crate::config::model::FontFamily;
 use crate::error::AppError;
 
-#[derive(Deserialize)]
+/// Code that shows off programming ligatures and easily confused glyphs.
+const SAMPLE_CODE: &str = r#"fn main() -> Result<(), Error> {
+    let xs = [0, 1, 2].map(|n| n * 2);   // O0 Il1 {} [] ()
+    if xs != [] && xs.len() >= 3 { println!("=> {:?}", xs); }
+}
+=== !== <= >= :: ... /* */ www ~> |> <|"#;
+
+/// Box-drawing and block characters, as used by TUIs and prompts.
+const SAMPLE_SYMBOLS: &str = "┌─┬─┐ │ ╞═╡ ░▒▓█ ▁▂▃▅▇ ⟩ ✓ ✗";
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct FontQuery {
     pub search: Option<String>,
 }
 
 /// GET /api/fonts — return all font families.
+#[utoipa::path(
+    get,
+    path = "/api/fonts",
+    tag = "Fonts",
+    summary = "All font families",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
 pub async fn list_fonts(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
     let mut html = String::new();
-    for font in &state.fonts {
+    for font in &state.ghostty().await.fonts {
         html.push_str(&format!(
             r#"<option value="{}">{}</option>"#,
             font.name, font.name
@@ -23,6 +44,14 @@ pub async fn list_fonts(State(state): State<SharedState>) -> Result<Html<String>
 }
 
 /// GET /api/fonts/search — search fonts.
+#[utoipa::path(
+    get,
+    path = "/api/fonts/search",
+    tag = "Fonts",
+    summary = "Search font families",
+    params(FontQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
 pub async fn search_fonts(
     State(state): State<SharedState>,
     Query(query): Query<FontQuery>,
@@ -32,7 +61,7 @@ pub async fn search_fonts(
 
     html.push_str(r#"<option value="">System Default</option>"#);
 
-    for font in &state.fonts {
+    for font in &state.ghostty().await.fonts {
         if !search.is_empty() && !font.name.to_lowercase().contains(&search) {
             continue;
         }
@@ -43,3 +72,111 @@ pub async fn search_fonts(
     }
     Ok(Html(html))
 }
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FontPreviewQuery {
+    /// Point size; defaults to the configured `font-size`.
+    pub size: Option<f32>,
+}
+
+/// GET /api/fonts/preview/:family — sample text set in `family`, for picking a font
+/// before selecting it.
+///
+/// The browser does the rendering, so the preview only looks right for fonts
+/// installed on the machine the page is viewed from, which is normally this one.
+#[utoipa::path(
+    get,
+    path = "/api/fonts/preview/{family}",
+    tag = "Fonts",
+    summary = "Sample text set in a family",
+    params(("family" = String, Path), FontPreviewQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn preview_font(
+    State(state): State<SharedState>,
+    Path(family): Path<String>,
+    Query(query): Query<FontPreviewQuery>,
+) -> Html<String> {
+    let size = match query.size {
+        Some(size) => size,
+        None => state
+            .value_of("font-size")
+            .await
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(13.0),
+    };
+    let ghostty = state.ghostty().await;
+    let installed = ghostty
+        .fonts
+        .iter()
+        .find(|f| f.name.eq_ignore_ascii_case(family.trim()));
+    Html(render_font_preview(family.trim(), installed, size))
+}
+
+/// `value` as a single-quoted CSS string.
+pub(crate) fn css_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn render_font_preview(family: &str, installed: Option<&FontFamily>, size: f32) -> String {
+    let size = size.clamp(6.0, 48.0);
+    let note = match installed {
+        Some(font) if font.styles.is_empty() => String::new(),
+        Some(font) => format!(
+            " &middot; {}",
+            escape_html(
+                &font
+                    .styles
+                    .iter()
+                    .map(|s| s.strip_prefix(font.name.as_str()).unwrap_or(s).trim())
+                    .filter(|s| !s.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        ),
+        None => r#" &middot; <span class="text-amber-300">not in Ghostty's font list</span>"#
+            .to_string(),
+    };
+    format!(
+        r#"<div class="mt-2 rounded-lg bg-gray-900 text-gray-100 p-3 overflow-x-auto" role="img" aria-label="Sample text in {name}">
+<div class="text-xs text-gray-400 mb-2 font-sans">{name}{note}</div>
+<div style="font-family: {family}, monospace; font-size: {size}px; line-height: 1.4">
+<pre class="whitespace-pre" style="font-family: inherit">{code}</pre>
+<div>{symbols}</div>
+<div><b>Bold</b> <i>Italic</i> <b><i>Bold italic</i></b> &mdash; The quick brown fox jumps over the lazy dog</div>
+</div></div>"#,
+        name = escape_html(family),
+        note = note,
+        family = escape_html(&css_string(family)),
+        size = size,
+        code = escape_html(SAMPLE_CODE),
+        symbols = SAMPLE_SYMBOLS,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_font_preview() {
+        let font = FontFamily {
+            name: "JetBrains Mono".to_string(),
+            styles: vec![
+                "JetBrains Mono Regular".to_string(),
+                "JetBrains Mono Bold".to_string(),
+            ],
+        };
+        let html = render_font_preview("JetBrains Mono", Some(&font), 14.0);
+        assert!(html.contains("font-family: &#39;JetBrains Mono&#39;, monospace"));
+        assert!(html.contains("font-size: 14px"));
+        assert!(html.contains("Regular, Bold"));
+        assert!(html.contains("=&gt; {:?}"));
+
+        let html = render_font_preview("Evil'; color: red", None, 100.0);
+        assert!(html.contains("not in Ghostty"));
+        assert!(html.contains("font-size: 48px"));
+        assert!(html.contains(r"&#39;Evil\&#39;; color: red&#39;"));
+    }
+}
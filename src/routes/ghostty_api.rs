@@ -0,0 +1,272 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::config_api::{escape_html, toast_html};
+use crate::app_state::SharedState;
+use crate::discovery_cache::cache_path;
+use crate::error::AppError;
+use crate::events::AppEvent;
+use crate::ghostty::{find_installations, same_binary, Ghostty, Installation};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GhosttyQuery {
+    /// `json` for the installations as JSON instead of the header switcher.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+#[derive(Serialize)]
+struct GhosttyStatus {
+    /// The binary in use; `null` when the built-in schema stands in for Ghostty.
+    active: Option<PathBuf>,
+    version: String,
+    from_snapshot: bool,
+    installations: Vec<Installation>,
+}
+
+/// The installations found on this machine, looked up once per session.
+async fn installations(state: &SharedState) -> Vec<Installation> {
+    if let Some(found) = state.installations.read().await.clone() {
+        return found;
+    }
+    let active = state.ghostty().await.path.clone();
+    let found = find_installations(active.as_deref()).await;
+    *state.installations.write().await = Some(found.clone());
+    found
+}
+
+/// GET /api/ghostty — the Ghostty version in use, for the header, with a menu to
+/// switch to another installation when there are several.
+#[utoipa::path(
+    get,
+    path = "/api/ghostty",
+    tag = "Config",
+    summary = "The Ghostty version in use and the installations found, each with its version (`format=json` for JSON)",
+    params(GhosttyQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn status(
+    State(state): State<SharedState>,
+    Query(query): Query<GhosttyQuery>,
+) -> Response {
+    let ghostty = state.ghostty().await;
+    let installations = installations(&state).await;
+    if query.format.as_deref() == Some("json") {
+        return Json(GhosttyStatus {
+            active: ghostty.path.clone(),
+            version: ghostty.version.clone(),
+            from_snapshot: ghostty.from_snapshot,
+            installations,
+        })
+        .into_response();
+    }
+    Html(render_switcher(&ghostty, &installations)).into_response()
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SwitchForm {
+    #[schema(value_type = String)]
+    pub path: PathBuf,
+}
+
+/// POST /api/ghostty — make another installation the active one: rediscover its
+/// options, fonts, actions, and keybinds, and remember it for the next start.
+/// Only installations found on this machine can be picked.
+#[utoipa::path(
+    post,
+    path = "/api/ghostty",
+    tag = "Config",
+    summary = "Switch to another installation found on this machine and rediscover its options, fonts, and keybinds",
+    request_body(content = SwitchForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn switch(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<SwitchForm>,
+) -> Result<Html<String>, AppError> {
+    let Some(installation) = installations(&state)
+        .await
+        .into_iter()
+        .find(|i| same_binary(&i.path, &form.path))
+    else {
+        return Err(AppError::Config(format!(
+            "{} isn't a Ghostty installation found on this machine",
+            form.path.display()
+        )));
+    };
+    let current = state.ghostty().await;
+    if current
+        .path
+        .as_deref()
+        .is_some_and(|p| same_binary(p, &installation.path))
+    {
+        return Ok(Html(toast_html("Already using that Ghostty", false)));
+    }
+
+    let ghostty = Ghostty::discover(
+        Some(installation.path.clone()),
+        &cache_path(&state.settings_path),
+        false,
+        None,
+    )
+    .await?;
+    if ghostty.from_snapshot {
+        return Ok(Html(toast_html(
+            &format!(
+                "Couldn't run Ghostty at {}; still using the previous one",
+                escape_html(&installation.path.to_string_lossy())
+            ),
+            true,
+        )));
+    }
+
+    let version = ghostty.version.clone();
+    *state.ghostty.write().await = Arc::new(ghostty);
+    // Both came from the previous binary.
+    *state.validation.write().await = None;
+    *state.updates.write().await = None;
+    {
+        let mut settings = state.settings.write().await;
+        settings.ghostty = Some(installation.path.clone());
+        settings.save(&state.settings_path)?;
+    }
+    tracing::info!(
+        "Switched to Ghostty {} at {}",
+        version,
+        installation.path.display()
+    );
+    state.publish(AppEvent::GhosttyChanged {
+        version: version.clone(),
+    });
+    Ok(Html(toast_html(
+        &format!("Switched to Ghostty {}", escape_html(&version)),
+        false,
+    )))
+}
+
+/// The header's version badge, or a menu of installations when there's a choice,
+/// with a button to look for new ones, fonts, and themes.
+fn render_switcher(ghostty: &Ghostty, installations: &[Installation]) -> String {
+    format!(
+        r##"<span id="ghostty-switcher" class="inline-flex items-center gap-1">{}<button type="button" hx-post="/api/refresh" hx-target="#toast-container" hx-swap="innerHTML" aria-label="Refresh" title="Look for newly installed Ghostty versions, fonts, and themes" class="text-xs text-gray-500 hover:text-gray-700 hover:bg-gray-100 px-1.5 py-1 rounded-lg">&#x21bb;</button></span>"##,
+        render_choice(ghostty, installations)
+    )
+}
+
+fn render_choice(ghostty: &Ghostty, installations: &[Installation]) -> String {
+    let is_active = |installation: &Installation| {
+        ghostty
+            .path
+            .as_deref()
+            .is_some_and(|p| same_binary(p, &installation.path))
+    };
+    let choices = installations.len() + usize::from(!installations.iter().any(is_active));
+    if choices < 2 {
+        let (label, title) = match &ghostty.path {
+            Some(path) => (
+                format!("Ghostty {}", ghostty.version),
+                path.to_string_lossy().into_owned(),
+            ),
+            None => (
+                format!("Built-in {} schema", ghostty.version),
+                "No Ghostty could be run".to_string(),
+            ),
+        };
+        return format!(
+            r#"<span title="{title}" class="text-xs font-mono text-gray-500 bg-gray-100 px-2 py-1 rounded-lg">{label}</span>"#,
+            title = escape_html(&title),
+            label = escape_html(&label),
+        );
+    }
+
+    let mut options = String::new();
+    if ghostty.path.is_none() {
+        options.push_str(&format!(
+            r#"<option value="" selected disabled>Built-in {} schema</option>"#,
+            escape_html(&ghostty.version)
+        ));
+    }
+    for installation in installations {
+        options.push_str(&format!(
+            r#"<option value="{path}"{selected}{disabled}>{version} &mdash; {path}</option>"#,
+            path = escape_html(&installation.path.to_string_lossy()),
+            selected = if is_active(installation) {
+                " selected"
+            } else {
+                ""
+            },
+            disabled = if installation.version.is_none() {
+                " disabled"
+            } else {
+                ""
+            },
+            version = escape_html(installation.version.as_deref().unwrap_or("not runnable")),
+        ));
+    }
+    format!(
+        r##"<select name="path" hx-post="/api/ghostty" hx-trigger="change" hx-target="#toast-container" hx-swap="innerHTML" aria-label="Ghostty installation" title="Ghostty installation whose options are shown" class="text-xs font-mono text-gray-600 border border-gray-300 rounded-lg px-2 py-1 max-w-[16rem]">{}</select>"##,
+        options
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::ConfigSchema;
+
+    fn ghostty(path: Option<&str>, version: &str) -> Ghostty {
+        Ghostty {
+            path: path.map(PathBuf::from),
+            version: version.to_string(),
+            from_snapshot: path.is_none(),
+            schema: ConfigSchema::new(Vec::new()),
+            fonts: Vec::new(),
+            actions: Vec::new(),
+            default_keybinds: Vec::new(),
+        }
+    }
+
+    fn installation(path: &str, version: Option<&str>) -> Installation {
+        Installation {
+            path: PathBuf::from(path),
+            version: version.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_render_switcher() {
+        let installations = [
+            installation("/usr/bin/ghostty", Some("1.1.3")),
+            installation("/opt/ghostty-tip/ghostty", Some("1.2.0-dev+abc")),
+            installation("/opt/broken/ghostty", None),
+        ];
+        let html = render_switcher(
+            &ghostty(Some("/usr/bin/ghostty"), "1.1.3"),
+            &installations[..1],
+        );
+        assert!(html.contains(">Ghostty 1.1.3<"));
+        assert!(!html.contains("<select"));
+        assert!(html.contains(r#"hx-post="/api/refresh""#));
+
+        let html = render_switcher(&ghostty(None, "1.1.3"), &[]);
+        assert!(html.contains("Built-in 1.1.3 schema"));
+
+        let html = render_switcher(&ghostty(Some("/usr/bin/ghostty"), "1.1.3"), &installations);
+        assert!(html.contains(r#"hx-post="/api/ghostty""#));
+        assert!(html.contains(r#"value="/usr/bin/ghostty" selected>1.1.3"#));
+        assert!(html.contains(r#"value="/opt/ghostty-tip/ghostty">1.2.0-dev+abc"#));
+        assert!(html.contains(r#"value="/opt/broken/ghostty" disabled>not runnable"#));
+
+        // Without a runnable Ghostty, any installation is a choice.
+        let html = render_switcher(&ghostty(None, "1.1.3"), &installations[1..2]);
+        assert!(html.contains("<select"));
+        assert!(html.contains("selected disabled>Built-in 1.1.3 schema"));
+    }
+}
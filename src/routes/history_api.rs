@@ -0,0 +1,160 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use axum::extract::{Path, State};
+use axum::response::Html;
+
+use super::config_api::{escape_html, publish_toast, toast_html};
+use super::profiles_api::age;
+use crate::app_state::SharedState;
+use crate::config::model::ConfigSchema;
+use crate::error::AppError;
+use crate::history::{Change, ChangeKind};
+
+/// How many entries the history page lists, newest first.
+const SHOWN: usize = 200;
+
+/// GET /api/history — recent config changes, newest first, each set or delete with
+/// a button to revert it.
+#[utoipa::path(
+    get,
+    path = "/api/history",
+    tag = "Config",
+    summary = "Journal of edits, saves, and applies, newest first",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn list(State(state): State<SharedState>) -> Html<String> {
+    let changes = state.history.read().await.changes();
+    Html(render_history(&changes, &state.ghostty().await.schema))
+}
+
+/// POST /api/history/:id/revert — give the entry's key its values from before the
+/// change, in memory. The revert is journaled like any other edit.
+#[utoipa::path(
+    post,
+    path = "/api/history/{id}/revert",
+    tag = "Config",
+    summary = "Give a journaled key its values from before that change, in memory",
+    params(("id" = u64, Path)),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn revert(
+    State(state): State<SharedState>,
+    Path(id): Path<u64>,
+) -> Result<Html<String>, AppError> {
+    let change = state
+        .history
+        .read()
+        .await
+        .changes()
+        .into_iter()
+        .find(|c| c.id == id)
+        .ok_or_else(|| AppError::Config(format!("No history entry {}", id)))?;
+    let Some(key) = change.key else {
+        return Ok(Html(toast_html("Saves can't be reverted", true)));
+    };
+
+    state.replace_values(&key, &change.old).await?;
+    state.mark_unsaved().await;
+
+    let message = format!("Reverted {} (unsaved)", escape_html(&key));
+    let mut html = publish_toast(&state, &message, false);
+    html.push_str(&format!(
+        r#"<div id="history-list" hx-swap-oob="innerHTML">{}</div>"#,
+        render_history(
+            &state.history.read().await.changes(),
+            &state.ghostty().await.schema
+        )
+    ));
+    Ok(Html(html))
+}
+
+/// A key's values for display; "(unset)" when it has none.
+fn values_text(values: &[String]) -> String {
+    if values.is_empty() {
+        r#"<span class="text-gray-400">(unset)</span>"#.to_string()
+    } else {
+        escape_html(&values.join(", "))
+    }
+}
+
+fn render_history(changes: &[Change], schema: &ConfigSchema) -> String {
+    if changes.is_empty() {
+        return r#"<p class="text-sm text-gray-500">No changes yet. Edits, saves, and applies show up here.</p>"#.to_string();
+    }
+
+    let mut html = String::from(
+        r#"<ul class="bg-white rounded-xl border border-gray-200 divide-y divide-gray-100">"#,
+    );
+    for change in changes.iter().rev().take(SHOWN) {
+        let when = age(UNIX_EPOCH + Duration::from_secs(change.time));
+        let (label, badge_class) = match change.kind {
+            ChangeKind::Set => ("Set", "bg-indigo-100 text-indigo-700"),
+            ChangeKind::Delete => ("Delete", "bg-red-100 text-red-700"),
+            ChangeKind::Save => ("Save", "bg-gray-100 text-gray-600"),
+            ChangeKind::Apply => ("Apply", "bg-emerald-100 text-emerald-700"),
+        };
+        let detail = match &change.key {
+            Some(key) => {
+                let href = schema
+                    .find_option(key)
+                    .map(|o| o.permalink())
+                    .unwrap_or_else(|| "/editor".to_string());
+                format!(
+                    r##"<a href="{href}" class="font-mono text-sm font-semibold text-gray-900 hover:underline">{key}</a><span class="font-mono text-sm text-gray-600 truncate">{old} &rarr; {new}</span></div><button hx-post="/api/history/{id}/revert" hx-target="#toast-container" hx-swap="innerHTML" class="shrink-0 px-3 py-1.5 text-sm font-medium text-gray-700 bg-white border border-gray-300 rounded-lg hover:bg-gray-50 transition-colors">Revert</button>"##,
+                    href = escape_html(&href),
+                    key = escape_html(key),
+                    old = values_text(&change.old),
+                    new = values_text(&change.new),
+                    id = change.id,
+                )
+            }
+            None => {
+                let text = match change.kind {
+                    ChangeKind::Apply => "Saved and reloaded Ghostty",
+                    _ => "Saved to disk",
+                };
+                format!(r#"<span class="text-sm text-gray-600">{text}</span></div>"#)
+            }
+        };
+        html.push_str(&format!(
+            r#"<li class="flex items-center justify-between gap-4 px-4 py-3"><div class="flex items-center gap-2 min-w-0"><span class="px-1.5 py-0.5 text-xs font-medium rounded {badge_class}">{label}</span><span class="text-xs text-gray-400 w-20 shrink-0">{when}</span>{detail}</li>"#,
+        ));
+    }
+    html.push_str("</ul>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(id: u64, kind: ChangeKind, key: Option<&str>, old: &[&str], new: &[&str]) -> Change {
+        Change {
+            id,
+            time: 0,
+            kind,
+            key: key.map(String::from),
+            old: old.iter().map(|v| v.to_string()).collect(),
+            new: new.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_render_history() {
+        let schema = ConfigSchema::new(Vec::new());
+        assert!(render_history(&[], &schema).contains("No changes yet"));
+
+        let html = render_history(
+            &[
+                change(1, ChangeKind::Set, Some("title"), &[], &["<b>"]),
+                change(2, ChangeKind::Save, None, &[], &[]),
+            ],
+            &schema,
+        );
+        // Newest first; only edits can be reverted.
+        assert!(html.find("Saved to disk").unwrap() < html.find("title").unwrap());
+        assert!(html.contains(r#"hx-post="/api/history/1/revert""#));
+        assert!(!html.contains("/api/history/2/revert"));
+        assert!(html.contains("(unset)</span> &rarr; &lt;b&gt;"));
+    }
+}
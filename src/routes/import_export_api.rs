@@ -1,74 +1,636 @@
-use axum::extract::State;
-use axum::response::Html;
+use std::path::Path;
+
+use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
 use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
 
-use super::config_api::{toast_html, unsaved_badge_oob};
+use super::config_api::{escape_html, publish_toast, toast_html};
+use super::keybinds_api::retain_keybinds;
+use super::themes_api::reload_themes;
 use crate::app_state::SharedState;
-use crate::config::model::ConfigEntry;
+use crate::cli::themes::{user_theme_dir, write_user_theme};
+use crate::config::annotate::annotate_defaults;
+use crate::config::color_import::{import_colors, ColorFormat};
+use crate::config::diff::{diff_keys, ChangeKind, KeyChange};
+use crate::config::export_filter::ExportFilter;
+use crate::config::file_io::{parse_config, render_config};
+use crate::config::keybind_conflicts::{find_conflicts, same_trigger};
+use crate::config::keybind_import::{import_keybinds, ImportedKeybinds, KeymapFormat};
+use crate::config::model::UserConfig;
+use crate::config::os_shortcuts::{os_blocked_keybinds, os_conflicts, Platform};
+use crate::config::structured::{export_data, import_data, DataFormat};
 use crate::error::AppError;
+use crate::share::decode_share;
 
-/// GET /api/export — export config as plain text.
-pub async fn export_config(State(state): State<SharedState>) -> Result<String, AppError> {
-    let user_config = state.user_config.read().await;
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// `json`, `toml`, or `yaml` for the set options as data; the config file's
+    /// own format otherwise.
+    #[serde(default)]
+    pub format: Option<String>,
+    /// Comma-separated category slugs, e.g. `fonts,colors`, to export only those.
+    #[serde(default)]
+    pub categories: Option<String>,
+    /// Only values that differ from Ghostty's defaults.
+    #[serde(default)]
+    pub changed_only: bool,
+    /// Put each option's default in a comment above it. Only for the config format.
+    #[serde(default)]
+    pub annotate: bool,
+}
 
-    let mut output = String::new();
-    for entry in &user_config.entries {
-        match entry {
-            ConfigEntry::Comment(text) => {
-                output.push_str(text);
-                output.push('\n');
-            }
-            ConfigEntry::BlankLine => {
-                output.push('\n');
-            }
-            ConfigEntry::KeyValue { key, value } => {
-                output.push_str(&format!("{} = {}\n", key, value));
-            }
-        }
+/// GET /api/export — export config as plain text, or as JSON, TOML, or YAML,
+/// optionally only some categories or only what differs from the defaults.
+#[utoipa::path(
+    get,
+    path = "/api/export",
+    tag = "Import/Export",
+    summary = "The config as plain text, or as JSON, TOML, or YAML with ?format=; ?categories= and ?changed_only= select what to export",
+    params(ExportQuery),
+    responses((status = 200, description = "OK", content_type = "text/plain", body = String)),
+)]
+pub async fn export_config(
+    State(state): State<SharedState>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, AppError> {
+    let format = match query.format.as_deref() {
+        Some(name) => DataFormat::parse(name)?,
+        None => DataFormat::Native,
+    };
+    if query.annotate && format != DataFormat::Native {
+        return Err(AppError::Config(
+            "Defaults can only be annotated in the Ghostty config format".to_string(),
+        ));
     }
-
-    Ok(output)
+    let filter = ExportFilter {
+        categories: ExportFilter::parse_categories(query.categories.as_deref().unwrap_or(""))?,
+        changed_only: query.changed_only,
+    };
+    let ghostty = state.ghostty().await;
+    let mut exported = filter.apply(&*state.user_config.read().await, &ghostty.schema);
+    if query.annotate {
+        annotate_defaults(&mut exported, &ghostty.schema);
+    }
+    let text = match format {
+        DataFormat::Native => render_config(&exported),
+        _ => export_data(&exported, &ghostty.schema, format)?,
+    };
+    Ok(([(header::CONTENT_TYPE, format.content_type())], text).into_response())
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct ImportForm {
     pub config_text: String,
+    /// `native`, `json`, `toml`, or `yaml`; detected from the text when missing
+    /// or `auto`.
+    #[serde(default)]
+    pub format: Option<String>,
 }
 
-/// POST /api/import — import config from plain text (in memory, unsaved).
+/// POST /api/import — import config from plain text or JSON, TOML, or YAML data
+/// (in memory, unsaved).
+#[utoipa::path(
+    post,
+    path = "/api/import",
+    tag = "Import/Export",
+    summary = "Import config text or JSON, TOML, or YAML data into memory",
+    request_body(content = ImportForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
 pub async fn import_config(
     State(state): State<SharedState>,
     axum::Form(form): axum::Form<ImportForm>,
 ) -> Result<Html<String>, AppError> {
     let mut user_config = state.user_config.write().await;
-    let file_path = user_config.file_path.clone();
-
-    let mut new_entries = Vec::new();
-    for line in form.config_text.lines() {
-        if line.trim().is_empty() {
-            new_entries.push(ConfigEntry::BlankLine);
-        } else if line.starts_with('#') {
-            new_entries.push(ConfigEntry::Comment(line.to_string()));
-        } else if let Some((key, value)) = line.split_once('=') {
-            new_entries.push(ConfigEntry::KeyValue {
-                key: key.trim().to_string(),
-                value: value.trim().to_string(),
-            });
-        } else {
-            new_entries.push(ConfigEntry::Comment(line.to_string()));
-        }
+    *user_config = read_import(&form, &user_config.file_path)?;
+    let platform = Platform::current();
+    let blocked: Vec<String> = os_blocked_keybinds(&user_config.get_all("keybind"), platform)
+        .into_iter()
+        .map(|(value, used_for)| format!("{} ({})", value, used_for))
+        .collect();
+    drop(user_config);
+    state.mark_unsaved().await;
+
+    let mut html = if blocked.is_empty() {
+        publish_toast(
+            &state,
+            "Configuration imported (unsaved). Use Save or Apply.",
+            false,
+        )
+    } else {
+        publish_toast(
+            &state,
+            &escape_html(&format!(
+                "Configuration imported (unsaved). These keybindings will never fire because {} uses the keys: {}",
+                platform.display_name(),
+                blocked.join(", ")
+            )),
+            true,
+        )
+    };
+    html.push_str(r#"<div id="import-review" hx-swap-oob="innerHTML"></div>"#);
+    Ok(Html(html))
+}
+
+/// Parse submitted import text in its chosen or detected format.
+fn read_import(form: &ImportForm, file_path: &Path) -> Result<UserConfig, AppError> {
+    let format = match form.format.as_deref() {
+        None | Some("auto") => DataFormat::detect(&form.config_text),
+        Some(name) => DataFormat::parse(name)?,
+    };
+    match format {
+        DataFormat::Native => Ok(parse_config(&form.config_text, file_path)),
+        _ => import_data(&form.config_text, format, file_path),
     }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PreviewQuery {
+    /// `json` for the changes as JSON instead of HTML.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// POST /api/import/preview — what importing the text would change, key by key,
+/// with a button that imports it. Nothing is changed until then.
+#[utoipa::path(
+    post,
+    path = "/api/import/preview",
+    tag = "Import/Export",
+    summary = "The key-level changes importing the text would make, for review (`format=json` for JSON)",
+    params(PreviewQuery),
+    request_body(content = ImportForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn preview_import(
+    State(state): State<SharedState>,
+    Query(query): Query<PreviewQuery>,
+    axum::Form(form): axum::Form<ImportForm>,
+) -> Result<Response, AppError> {
+    review_import(&state, &form, query.format.as_deref() == Some("json")).await
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SharedImportQuery {
+    /// A code from `/api/share`.
+    pub from: String,
+    #[serde(default)]
+    pub format: Option<String>,
+}
 
-    user_config.entries = new_entries;
-    user_config.file_path = file_path;
+/// GET /api/import?from= — the import review for a shared config, decoded from
+/// the code a share link carries.
+#[utoipa::path(
+    get,
+    path = "/api/import",
+    tag = "Import/Export",
+    summary = "The import review for a config shared with `/api/share`, from its `from` code",
+    params(SharedImportQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn preview_shared(
+    State(state): State<SharedState>,
+    Query(query): Query<SharedImportQuery>,
+) -> Result<Response, AppError> {
+    let json = query.format.as_deref() == Some("json");
+    let form = match decode_share(&query.from) {
+        Ok(text) => ImportForm {
+            config_text: text,
+            format: Some("native".to_string()),
+        },
+        Err(AppError::Config(msg)) if !json => return Ok(Html(review_error(&msg)).into_response()),
+        Err(e) => return Err(e),
+    };
+    review_import(&state, &form, json).await
+}
+
+async fn review_import(
+    state: &SharedState,
+    form: &ImportForm,
+    json: bool,
+) -> Result<Response, AppError> {
+    let user_config = state.user_config.read().await;
+    let imported = match read_import(form, &user_config.file_path) {
+        Ok(imported) => imported,
+        Err(AppError::Config(msg)) if !json => return Ok(Html(review_error(&msg)).into_response()),
+        Err(e) => return Err(e),
+    };
+    let changes = diff_keys(&user_config, &imported);
     drop(user_config);
-    state.mark_unsaved("import").await;
-    let count = state.unsaved_count().await;
 
-    let mut html = toast_html(
-        "Configuration imported (unsaved). Use Save or Apply.",
-        false,
+    if json {
+        return Ok(Json(serde_json::json!({ "changes": changes })).into_response());
+    }
+    Ok(Html(render_import_review(form, &changes)).into_response())
+}
+
+pub(crate) fn review_error(message: &str) -> String {
+    format!(
+        r#"<p class="text-sm text-amber-700" role="alert">{}</p>"#,
+        escape_html(message)
+    )
+}
+
+/// The key-level changes an import would make, and a form that submits the same
+/// text to `/api/import`.
+fn render_import_review(form: &ImportForm, changes: &[KeyChange]) -> String {
+    if changes.is_empty() {
+        return r#"<p class="text-sm text-gray-500">Importing this would not change any settings.</p>"#.to_string();
+    }
+
+    let count = |kind: ChangeKind| changes.iter().filter(|c| c.kind == kind).count();
+    let mut html = format!(
+        r#"<h4 class="text-sm font-semibold text-gray-900 mb-2">{} &mdash; {} added, {} changed, {} removed</h4>"#,
+        plural(changes.len(), "setting"),
+        count(ChangeKind::Added),
+        count(ChangeKind::Changed),
+        count(ChangeKind::Removed),
     );
-    html.push_str(&unsaved_badge_oob(count));
+    html.push_str(r#"<div class="border border-gray-200 rounded-lg overflow-hidden mb-4 max-h-96 overflow-y-auto"><table class="w-full"><thead class="bg-gray-50 border-b border-gray-200"><tr>"#);
+    for heading in ["Key", "Change", "Current", "Imported"] {
+        html.push_str(&format!(
+            r#"<th class="px-3 py-2 text-left text-xs font-semibold text-gray-500 uppercase tracking-wider">{}</th>"#,
+            heading
+        ));
+    }
+    html.push_str(r#"</tr></thead><tbody class="divide-y divide-gray-100">"#);
+    for change in changes {
+        let (label, badge_class) = match change.kind {
+            ChangeKind::Added => ("Added", "bg-emerald-100 text-emerald-700"),
+            ChangeKind::Removed => ("Removed", "bg-red-100 text-red-700"),
+            ChangeKind::Changed => ("Changed", "bg-amber-100 text-amber-700"),
+        };
+        html.push_str(&format!(
+            r#"<tr><td class="px-3 py-2 text-sm font-mono font-semibold text-gray-900">{key}</td><td class="px-3 py-2"><span class="text-xs {badge_class} px-2 py-0.5 rounded-full">{label}</span></td><td class="px-3 py-2 text-sm font-mono text-gray-500">{old}</td><td class="px-3 py-2 text-sm font-mono text-gray-900">{new}</td></tr>"#,
+            key = escape_html(&change.key),
+            badge_class = badge_class,
+            label = label,
+            old = values_html(&change.old),
+            new = values_html(&change.new),
+        ));
+    }
+    html.push_str("</tbody></table></div>");
+
+    html.push_str(&format!(
+        r##"<form hx-post="/api/import" hx-target="#toast-container" hx-swap="innerHTML" class="flex items-center gap-3"><textarea name="config_text" hidden>{text}</textarea><input type="hidden" name="format" value="{format}"><button type="submit" class="px-4 py-2 bg-indigo-600 text-white rounded-lg text-sm font-medium hover:bg-indigo-700 transition-colors">Import {count}</button><button type="button" onclick="document.getElementById('import-review').innerHTML = ''" class="text-sm text-gray-600 hover:text-gray-800">Cancel</button></form>"##,
+        text = escape_html(&form.config_text),
+        format = escape_html(form.format.as_deref().unwrap_or("auto")),
+        count = plural(changes.len(), "change"),
+    ));
+    html
+}
+
+fn values_html(values: &[String]) -> String {
+    if values.is_empty() {
+        return r#"<span class="text-gray-300">(unset)</span>"#.to_string();
+    }
+    values
+        .iter()
+        .map(|v| escape_html(v))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ImportColorsForm {
+    pub text: String,
+    #[serde(default)]
+    pub format: String,
+    /// `config` to set the colors directly, `theme` to save them as a custom theme.
+    #[serde(default)]
+    pub target: String,
+    #[serde(default)]
+    pub name: String,
+}
+
+/// POST /api/import/colors — convert an Alacritty or Kitty color scheme.
+#[utoipa::path(
+    post,
+    path = "/api/import/colors",
+    tag = "Import/Export",
+    summary = "Convert an Alacritty or Kitty color scheme",
+    request_body(content = ImportColorsForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn import_color_scheme(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<ImportColorsForm>,
+) -> Result<Html<String>, AppError> {
+    let colors = match ColorFormat::parse(&form.format, &form.text)
+        .and_then(|format| import_colors(&form.text, format))
+    {
+        Ok(colors) => colors,
+        Err(AppError::Config(msg)) => return Ok(Html(toast_html(&escape_html(&msg), true))),
+        Err(e) => return Err(e),
+    };
+
+    if form.target == "theme" {
+        let dir = user_theme_dir(&state.user_config.read().await.file_path);
+        let theme = match write_user_theme(&dir, &form.name, &colors.to_theme_file()) {
+            Ok(theme) => theme,
+            Err(AppError::Config(msg)) => return Ok(Html(toast_html(&escape_html(&msg), true))),
+            Err(e) => return Err(e),
+        };
+        reload_themes(&state).await?;
+        let message = format!("Saved theme {}", escape_html(&theme.name));
+        return Ok(Html(toast_html(&message, false)));
+    }
+
+    let entries = colors.entries();
+    let mut user_config = state.user_config.write().await;
+    apply_colors(&mut user_config, &entries);
+    drop(user_config);
+
+    state.mark_unsaved().await;
+    let message = format!("Imported {} colors (unsaved)", entries.len());
+    Ok(Html(publish_toast(&state, &message, false)))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ImportKeybindsForm {
+    pub text: String,
+    #[serde(default)]
+    pub format: String,
+}
+
+/// POST /api/import/keybinds — translate an iTerm2, kitty, or WezTerm keymap and
+/// list what would be added, and what couldn't be translated, for confirmation.
+#[utoipa::path(
+    post,
+    path = "/api/import/keybinds",
+    tag = "Import/Export",
+    summary = "Translate another terminal's keymap for review",
+    request_body(content = ImportKeybindsForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn review_keybinds(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<ImportKeybindsForm>,
+) -> Result<Html<String>, AppError> {
+    let ghostty = state.ghostty().await;
+    let imported = match KeymapFormat::parse(&form.format, &form.text)
+        .and_then(|format| import_keybinds(&form.text, format, &ghostty.actions))
+    {
+        Ok(imported) => imported,
+        Err(AppError::Config(msg)) => return Ok(Html(review_error(&msg))),
+        Err(e) => return Err(e),
+    };
+
+    let custom = state.all_values("keybind").await;
+    let custom: Vec<&str> = custom.iter().map(String::as_str).collect();
+    let platform = Platform::current();
+    let notes: Vec<Option<String>> = imported
+        .bindings
+        .iter()
+        .map(|binding| {
+            let trigger = binding.split_once('=').map_or(binding.as_str(), |(t, _)| t);
+            if let Some(os) = os_conflicts(trigger, platform).into_iter().next() {
+                return Some(format!(
+                    "never fires: {} uses it for {}",
+                    platform.display_name(),
+                    os.action
+                ));
+            }
+            let replaced: Vec<String> = find_conflicts(trigger, &ghostty.default_keybinds, &custom)
+                .into_iter()
+                .map(|c| c.action)
+                .collect();
+            (!replaced.is_empty()).then(|| format!("replaces {}", replaced.join(", ")))
+        })
+        .collect();
+    Ok(Html(render_keybind_review(&imported, &notes)))
+}
+
+/// The translated bindings with a note on each that replaces or loses to another,
+/// the bindings that were skipped, and a button to add the translated ones.
+fn render_keybind_review(imported: &ImportedKeybinds, notes: &[Option<String>]) -> String {
+    let mut html = String::new();
+    if imported.bindings.is_empty() {
+        html.push_str(r#"<p class="text-sm text-amber-700 mb-3" role="alert">None of these keybindings have a Ghostty equivalent.</p>"#);
+    } else {
+        html.push_str(&format!(
+            r#"<h4 class="text-sm font-semibold text-gray-900 mb-2">{} to add</h4><ul class="mb-4 space-y-1 text-sm">"#,
+            plural(imported.bindings.len(), "keybinding")
+        ));
+        for (binding, note) in imported.bindings.iter().zip(notes) {
+            html.push_str(&format!(
+                r#"<li><span class="font-mono text-gray-700">keybind = {}</span>"#,
+                escape_html(binding)
+            ));
+            if let Some(note) = note {
+                html.push_str(&format!(
+                    r#" <span class="text-xs text-amber-700">({})</span>"#,
+                    escape_html(note)
+                ));
+            }
+            html.push_str("</li>");
+        }
+        html.push_str("</ul>");
+    }
+    if !imported.skipped.is_empty() {
+        html.push_str(&format!(
+            r#"<h4 class="text-sm font-semibold text-gray-900 mb-2">{} not imported</h4><ul class="mb-4 space-y-1 text-sm">"#,
+            plural(imported.skipped.len(), "keybinding")
+        ));
+        for skipped in &imported.skipped {
+            html.push_str(&format!(
+                r#"<li><span class="font-mono text-gray-500">{}</span> <span class="text-xs text-gray-400">&mdash; {}</span></li>"#,
+                escape_html(&skipped.source),
+                escape_html(&skipped.reason)
+            ));
+        }
+        html.push_str("</ul>");
+    }
+    if !imported.bindings.is_empty() {
+        html.push_str(&format!(
+            r##"<form hx-post="/api/import/keybinds/apply" hx-target="#toast-container" hx-swap="innerHTML" class="flex items-center gap-3"><textarea name="bindings" hidden>{bindings}</textarea><button type="submit" class="px-4 py-2 bg-indigo-600 text-white rounded-lg text-sm font-medium hover:bg-indigo-700 transition-colors">Add {count}</button><button type="button" onclick="document.getElementById('keybind-import-review').innerHTML = ''" class="text-sm text-gray-600 hover:text-gray-800">Cancel</button></form>"##,
+            bindings = escape_html(&imported.bindings.join("\n")),
+            count = plural(imported.bindings.len(), "keybinding"),
+        ));
+    }
+    html
+}
+
+pub(crate) fn plural(n: usize, noun: &str) -> String {
+    format!("{} {}{}", n, noun, if n == 1 { "" } else { "s" })
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ApplyKeybindsForm {
+    /// `trigger=action` values, one per line.
+    pub bindings: String,
+}
+
+/// POST /api/import/keybinds/apply — add the reviewed bindings (in memory). Each
+/// replaces any custom binding on the same keys, as adding one by hand does.
+#[utoipa::path(
+    post,
+    path = "/api/import/keybinds/apply",
+    tag = "Import/Export",
+    summary = "Add the reviewed keybindings",
+    request_body(content = ApplyKeybindsForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn apply_keybinds(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<ApplyKeybindsForm>,
+) -> Result<Html<String>, AppError> {
+    let bindings: Vec<&str> = form
+        .bindings
+        .lines()
+        .map(str::trim)
+        .filter(|b| b.contains('='))
+        .collect();
+    if bindings.is_empty() {
+        return Ok(Html(toast_html("No keybindings to add", true)));
+    }
+    let triggers: Vec<&str> = bindings
+        .iter()
+        .filter_map(|b| b.split_once('=').map(|(t, _)| t))
+        .collect();
+    retain_keybinds(&state, |value| {
+        value
+            .split_once('=')
+            .is_none_or(|(t, _)| !triggers.iter().any(|new| same_trigger(t, new)))
+    })
+    .await;
+    state
+        .add_entry("keybind", |config| {
+            for binding in &bindings {
+                config.push("keybind", binding);
+            }
+        })
+        .await?;
+    state.mark_unsaved().await;
+
+    let message = format!(
+        "Imported {} (unsaved)",
+        plural(bindings.len(), "keybinding")
+    );
+    let mut html = publish_toast(&state, &message, false);
+    html.push_str(r#"<div id="keybind-import-review" hx-swap-oob="innerHTML"></div>"#);
     Ok(Html(html))
 }
+
+/// Set imported colors, replacing only the palette slots the import defines.
+fn apply_colors(config: &mut UserConfig, entries: &[(String, String)]) {
+    let slot = |value: &str| value.split_once('=').map(|(i, _)| i.trim().to_string());
+    let imported: Vec<&str> = entries
+        .iter()
+        .filter(|(key, _)| key == "palette")
+        .map(|(_, value)| value.as_str())
+        .collect();
+
+    if !imported.is_empty() {
+        let mut palette: Vec<String> = config
+            .get_all("palette")
+            .into_iter()
+            .filter(|existing| !imported.iter().any(|new| slot(new) == slot(existing)))
+            .map(String::from)
+            .collect();
+        palette.extend(imported.iter().map(|v| v.to_string()));
+        config.set_all("palette", &palette);
+    }
+
+    for (key, value) in entries.iter().filter(|(key, _)| key != "palette") {
+        config.set(key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::keybind_import::SkippedBinding;
+
+    #[test]
+    fn test_apply_colors_keeps_other_palette_slots() {
+        let mut config = parse_config(
+            "palette = 0=#000000\npalette = 1=#aa0000\nbackground = #111111\n",
+            Path::new("/tmp/config"),
+        );
+        let entries = vec![
+            ("palette".to_string(), "1=#ff5555".to_string()),
+            ("palette".to_string(), "2=#50fa7b".to_string()),
+            ("background".to_string(), "#282a36".to_string()),
+        ];
+        apply_colors(&mut config, &entries);
+        assert_eq!(
+            config.get_all("palette"),
+            vec!["0=#000000", "1=#ff5555", "2=#50fa7b"]
+        );
+        assert_eq!(config.get("background"), Some("#282a36"));
+    }
+
+    #[test]
+    fn test_read_import_detects_format() {
+        let file = Path::new("/tmp/config");
+        let form = |text: &str, format: Option<&str>| ImportForm {
+            config_text: text.to_string(),
+            format: format.map(String::from),
+        };
+        let native = read_import(&form("font-size = 14\n", None), file).unwrap();
+        assert_eq!(native.get("font-size"), Some("14"));
+        let json = read_import(&form(r#"{"font-size": 14}"#, Some("auto")), file).unwrap();
+        assert_eq!(json.get("font-size"), Some("14"));
+        assert!(read_import(&form("", Some("xml")), file).is_err());
+    }
+
+    #[test]
+    fn test_render_import_review() {
+        let form = ImportForm {
+            config_text: "title = <b>\n".to_string(),
+            format: None,
+        };
+        let changes = vec![
+            KeyChange {
+                key: "title".to_string(),
+                kind: ChangeKind::Changed,
+                old: vec!["a".to_string()],
+                new: vec!["<b>".to_string()],
+            },
+            KeyChange {
+                key: "font-size".to_string(),
+                kind: ChangeKind::Removed,
+                old: vec!["13".to_string()],
+                new: Vec::new(),
+            },
+        ];
+        let html = render_import_review(&form, &changes);
+        assert!(html.contains("2 settings &mdash; 0 added, 1 changed, 1 removed"));
+        assert!(html.contains("(unset)"));
+        assert!(html.contains(r#"<textarea name="config_text" hidden>title = &lt;b&gt;"#));
+        assert!(html.contains(r#"<input type="hidden" name="format" value="auto">"#));
+        assert!(html.contains("Import 2 changes"));
+
+        assert!(render_import_review(&form, &[]).contains("would not change any settings"));
+    }
+
+    #[test]
+    fn test_render_keybind_review() {
+        let imported = ImportedKeybinds {
+            bindings: vec![
+                "super+t=new_tab".to_string(),
+                "ctrl+a>c=new_tab".to_string(),
+            ],
+            skipped: vec![SkippedBinding {
+                source: "map ctrl+a launch".to_string(),
+                reason: "kitty's launch has no Ghostty equivalent".to_string(),
+            }],
+        };
+        let notes = vec![Some("replaces new_tab".to_string()), None];
+        let html = render_keybind_review(&imported, &notes);
+        assert!(html.contains("2 keybindings to add"));
+        assert!(html.contains("keybind = super+t=new_tab</span> <span class=\"text-xs text-amber-700\">(replaces new_tab)"));
+        assert!(html.contains("1 keybinding not imported"));
+        assert!(html.contains("kitty&#39;s launch has no Ghostty equivalent"));
+        assert!(html.contains(
+            "<textarea name=\"bindings\" hidden>super+t=new_tab\nctrl+a&gt;c=new_tab</textarea>"
+        ));
+    }
+}
@@ -1,72 +1,563 @@
 use axum::extract::State;
-use axum::response::Html;
-use serde::Deserialize;
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-use super::config_api::{toast_html, unsaved_badge_oob};
+use super::config_api::{escape_html, publish_toast, toast_html};
 use crate::app_state::SharedState;
+use crate::cli::keybinds::Keybinding;
+use crate::config::key_event::{trigger_for_event, KeyEvent};
+use crate::config::keybind::parse_trigger;
+use crate::config::keybind_conflicts::{
+    find_conflicts, normalize_trigger, same_trigger, ConflictSource, KeybindConflict,
+};
+use crate::config::keybind_presets::{
+    find_preset, install_preset, installed_presets, remove_preset, PRESETS,
+};
 use crate::config::model::ConfigEntry;
+use crate::config::os_shortcuts::{os_conflicts, Platform};
 use crate::error::AppError;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct AddKeybindForm {
     pub trigger: String,
     pub action: String,
+    /// Set by the confirmation prompt to add the binding despite conflicts.
+    #[serde(default)]
+    pub force: bool,
 }
 
 /// POST /api/keybinds — add a new keybinding (in memory).
+///
+/// If the trigger collides with a default or custom binding, nothing is stored and a
+/// confirmation prompt is returned instead. Confirming replaces any custom bindings
+/// on the same trigger; defaults are shadowed by the new binding.
+#[utoipa::path(
+    post,
+    path = "/api/keybinds",
+    tag = "Keybinds",
+    summary = "Add a keybinding",
+    request_body(content = AddKeybindForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
 pub async fn add_keybind(
     State(state): State<SharedState>,
     axum::Form(form): axum::Form<AddKeybindForm>,
-) -> Result<Html<String>, AppError> {
+) -> Result<Response, AppError> {
     let trigger = form.trigger.trim();
     let action = form.action.trim();
 
     if trigger.is_empty() || action.is_empty() {
+        return Ok(Html(toast_html("Both trigger and action are required", true)).into_response());
+    }
+
+    if let Err(e) = parse_trigger(trigger) {
+        let message = match e {
+            AppError::Config(msg) => msg,
+            other => other.to_string(),
+        };
         return Ok(Html(toast_html(
-            "Both trigger and action are required",
+            &format!("Invalid trigger: {}", escape_html(&message)),
             true,
-        )));
+        ))
+        .into_response());
+    }
+
+    if !form.force {
+        let custom = state.all_values("keybind").await;
+        let custom: Vec<&str> = custom.iter().map(String::as_str).collect();
+        let ghostty = state.ghostty().await;
+        let mut conflicts = find_conflicts(trigger, &ghostty.default_keybinds, &custom);
+        conflicts.extend(os_conflicts(trigger, Platform::current()));
+        // Re-adding the exact same binding is a no-op conflict, not worth a prompt.
+        if conflicts
+            .iter()
+            .any(|c| c.action == action && c.source == ConflictSource::Custom)
+        {
+            return Ok(Html(toast_html("That keybinding already exists", true)).into_response());
+        }
+        if !conflicts.is_empty() {
+            return Ok((
+                [("HX-Retarget", "#keybind-conflicts")],
+                Html(render_conflicts(trigger, action, &conflicts)),
+            )
+                .into_response());
+        }
     }
 
-    let keybind_value = format!("{}={}", trigger, action);
+    retain_keybinds(&state, |value| {
+        value
+            .split_once('=')
+            .is_none_or(|(t, _)| !same_trigger(t, trigger))
+    })
+    .await;
+    state
+        .add_entry("keybind", |config| {
+            config.entries.push(ConfigEntry::key_value(
+                "keybind",
+                format!("{}={}", trigger, action),
+            ))
+        })
+        .await?;
+    state.mark_unsaved().await;
+
+    Ok(Html(publish_toast(&state, "Keybinding added (unsaved)", false)).into_response())
+}
 
+/// Keep only the `keybind` entries, in the main config and included files, whose
+/// value passes `keep`.
+pub(crate) async fn retain_keybinds(state: &SharedState, keep: impl Fn(&str) -> bool) {
     let mut user_config = state.user_config.write().await;
-    user_config.entries.push(ConfigEntry::KeyValue {
-        key: "keybind".to_string(),
-        value: keybind_value,
-    });
-    drop(user_config);
-    state.mark_unsaved("keybind").await;
-    let count = state.unsaved_count().await;
-
-    let mut html = toast_html("Keybinding added (unsaved)", false);
-    html.push_str(&unsaved_badge_oob(count));
-    Ok(Html(html))
+    let mut included = state.included.write().await;
+    for config in std::iter::once(&mut *user_config).chain(included.iter_mut()) {
+        config.entries.retain(|e| match e {
+            ConfigEntry::KeyValue { key, value, .. } if key == "keybind" => keep(value),
+            _ => true,
+        });
+    }
 }
 
-#[derive(Deserialize)]
+/// Warning panel listing the colliding bindings, with a form to add anyway.
+fn render_conflicts(trigger: &str, action: &str, conflicts: &[KeybindConflict]) -> String {
+    let mut html = String::new();
+    html.push_str(r#"<div class="bg-amber-50 border border-amber-300 rounded-xl p-4 mb-6 text-sm text-amber-900" role="alert">"#);
+    html.push_str(&format!(
+        r#"<p class="font-medium mb-2"><span class="font-mono">{}</span> is already bound:</p><ul class="mb-3 space-y-1">"#,
+        escape_html(trigger)
+    ));
+    for conflict in conflicts {
+        let source = match conflict.source {
+            ConflictSource::Default => "default",
+            ConflictSource::Custom => "custom",
+            ConflictSource::System => Platform::current().display_name(),
+        };
+        html.push_str(&format!(
+            r#"<li><span class="font-mono">{} &rarr; {}</span> <span class="text-xs text-amber-700">({})</span></li>"#,
+            escape_html(&conflict.trigger),
+            escape_html(&conflict.action),
+            source
+        ));
+    }
+    html.push_str("</ul>");
+    html.push_str(&format!(
+        r##"<form hx-post="/api/keybinds" hx-target="#toast-container" hx-swap="innerHTML" hx-on::after-request="if(event.detail.successful) setTimeout(() => location.reload(), 500)" class="flex items-center gap-3"><input type="hidden" name="trigger" value="{trigger}"><input type="hidden" name="action" value="{action}"><input type="hidden" name="force" value="true"><button type="submit" data-autofocus class="px-3 py-1.5 bg-amber-600 text-white rounded-lg text-sm font-medium hover:bg-amber-700 transition-colors">Override with {action}</button><button type="button" onclick="document.getElementById('keybind-conflicts').innerHTML = ''" class="text-sm text-amber-800 hover:text-amber-900">Cancel</button></form>"##,
+        trigger = escape_html(trigger),
+        action = escape_html(action),
+    ));
+    html.push_str("</div>");
+    html
+}
+
+/// Fragment id of a custom binding's row on the keybinds page.
+pub(crate) fn keybind_anchor(trigger: &str) -> String {
+    let keys: String = normalize_trigger(trigger)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("keybind-{}", keys)
+}
+
+/// How many summary conflicts to list before leaving the rest to the keybinds page.
+const SUMMARY_CONFLICTS: usize = 3;
+
+/// Card for the `keybind` option on its category page: how many custom bindings
+/// there are, the ones that can't fire, and links into the keybinds editor.
+///
+/// A custom binding can't fire when a later custom binding takes the same keys,
+/// or when the OS takes them first. Rebinding a default is what custom bindings
+/// are for, so that only counts toward the overrides.
+pub(crate) fn render_keybind_summary(
+    custom: &[String],
+    defaults: &[Keybinding],
+    platform: Platform,
+) -> String {
+    let bindings: Vec<(&str, &str)> = custom
+        .iter()
+        .filter_map(|v| v.split_once('='))
+        .map(|(t, a)| (t.trim(), a.trim()))
+        .collect();
+    let overrides = bindings
+        .iter()
+        .filter(|(trigger, _)| defaults.iter().any(|d| same_trigger(&d.trigger, trigger)))
+        .count();
+
+    let mut conflicts: Vec<(&str, &str, String)> = Vec::new();
+    for (i, (trigger, action)) in bindings.iter().enumerate() {
+        let later: Vec<&str> = custom.iter().skip(i + 1).map(String::as_str).collect();
+        if let Some(winner) = find_conflicts(trigger, &[], &later).into_iter().last() {
+            conflicts.push((
+                trigger,
+                action,
+                format!("replaced by {}", escape_html(&winner.action)),
+            ));
+        } else if let Some(os) = os_conflicts(trigger, platform).into_iter().next() {
+            conflicts.push((
+                trigger,
+                action,
+                format!(
+                    "blocked by {} ({})",
+                    escape_html(&os.action),
+                    platform.display_name()
+                ),
+            ));
+        }
+    }
+
+    let plural =
+        |n: usize, one: &str, many: &str| format!("{} {}", n, if n == 1 { one } else { many });
+    let mut html = String::from(
+        r#"<div class="mt-2 rounded-lg border border-gray-200 bg-gray-50 p-3 text-sm">"#,
+    );
+    html.push_str(&format!(
+        r#"<p class="text-gray-700">{} &middot; {} &middot; {}</p>"#,
+        plural(bindings.len(), "custom binding", "custom bindings"),
+        plural(overrides, "default overridden", "defaults overridden"),
+        match conflicts.len() {
+            0 => r#"<span class="text-green-700">no conflicts</span>"#.to_string(),
+            n => format!(
+                r#"<span class="text-red-700">{}</span>"#,
+                plural(n, "binding can't fire", "bindings can't fire")
+            ),
+        }
+    ));
+    if !conflicts.is_empty() {
+        html.push_str(r#"<ul class="mt-2 space-y-1">"#);
+        for (trigger, action, reason) in conflicts.iter().take(SUMMARY_CONFLICTS) {
+            html.push_str(&format!(
+                r#"<li><a href="/keybinds#{anchor}" class="font-mono text-indigo-600 hover:underline">{trigger} &rarr; {action}</a> <span class="text-xs text-gray-500">{reason}</span></li>"#,
+                anchor = keybind_anchor(trigger),
+                trigger = escape_html(trigger),
+                action = escape_html(action),
+                reason = reason,
+            ));
+        }
+        if conflicts.len() > SUMMARY_CONFLICTS {
+            html.push_str(&format!(
+                r#"<li class="text-xs text-gray-500">and {} more</li>"#,
+                conflicts.len() - SUMMARY_CONFLICTS
+            ));
+        }
+        html.push_str("</ul>");
+    }
+    html.push_str(
+        r##"<div class="mt-3 flex gap-4"><a href="/keybinds" class="text-indigo-600 hover:underline">Open the keybinds editor &rarr;</a><a href="/keybinds#keybind-trigger" class="text-gray-500 hover:text-gray-700">Add a binding</a></div></div>"##,
+    );
+    html
+}
+
+#[derive(Deserialize, ToSchema)]
 pub struct DeleteKeybindForm {
     pub trigger: String,
     pub action: String,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NormalizedTrigger {
+    /// The trigger in Ghostty syntax, or `null` for a modifier pressed on its own.
+    pub trigger: Option<String>,
+}
+
+/// POST /api/keybinds/normalize — the Ghostty trigger for a key press recorded
+/// in the browser, with this platform's name for the Command/Super key.
+#[utoipa::path(
+    post,
+    path = "/api/keybinds/normalize",
+    tag = "Keybinds",
+    summary = "The Ghostty trigger for a recorded key press",
+    request_body(content = KeyEvent, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", body = NormalizedTrigger)),
+)]
+pub async fn normalize(
+    axum::Form(event): axum::Form<KeyEvent>,
+) -> Result<Json<NormalizedTrigger>, AppError> {
+    Ok(Json(NormalizedTrigger {
+        trigger: trigger_for_event(&event, Platform::current())?,
+    }))
+}
+
 /// POST /api/keybinds/delete — remove a custom keybinding (in memory).
+#[utoipa::path(
+    post,
+    path = "/api/keybinds/delete",
+    tag = "Keybinds",
+    summary = "Remove a keybinding",
+    request_body(content = DeleteKeybindForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
 pub async fn delete_keybind(
     State(state): State<SharedState>,
     axum::Form(form): axum::Form<DeleteKeybindForm>,
 ) -> Result<Html<String>, AppError> {
     let target = format!("{}={}", form.trigger.trim(), form.action.trim());
 
-    let mut user_config = state.user_config.write().await;
-    user_config.entries.retain(|e| match e {
-        ConfigEntry::KeyValue { key, value } => !(key == "keybind" && value == &target),
-        _ => true,
-    });
-    drop(user_config);
-    state.mark_unsaved("keybind-delete").await;
-    let count = state.unsaved_count().await;
-
-    let mut html = toast_html("Keybinding removed (unsaved)", false);
-    html.push_str(&unsaved_badge_oob(count));
+    retain_keybinds(&state, |value| value != target).await;
+    state.mark_unsaved().await;
+
+    Ok(Html(publish_toast(
+        &state,
+        "Keybinding removed (unsaved)",
+        false,
+    )))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PresetForm {
+    pub name: String,
+}
+
+/// A preset binding and the existing binding it takes the keys from.
+type PresetConflict = (&'static str, KeybindConflict);
+
+/// POST /api/keybinds/preset/apply — install a preset's bindings (in memory) as a
+/// marked block, replacing an earlier copy of the same preset, and report the
+/// existing bindings it overrides.
+#[utoipa::path(
+    post,
+    path = "/api/keybinds/preset/apply",
+    tag = "Keybinds",
+    summary = "Install a keybinding preset",
+    request_body(content = PresetForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn apply_preset(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<PresetForm>,
+) -> Result<Html<String>, AppError> {
+    let preset = find_preset(&form.name)
+        .ok_or_else(|| AppError::Config(format!("Unknown keybind preset: {}", form.name)))?;
+
+    remove_preset_everywhere(&state, preset.name).await;
+    let custom = state.all_values("keybind").await;
+    let custom: Vec<&str> = custom.iter().map(String::as_str).collect();
+    let platform = Platform::current();
+    let ghostty = state.ghostty().await;
+    let mut conflicts: Vec<PresetConflict> = Vec::new();
+    for binding in preset.bindings {
+        let trigger = binding.split_once('=').map_or(*binding, |(t, _)| t);
+        conflicts.extend(
+            find_conflicts(trigger, &ghostty.default_keybinds, &custom)
+                .into_iter()
+                .chain(os_conflicts(trigger, platform))
+                .map(|c| (trigger, c)),
+        );
+    }
+
+    state
+        .add_entry("keybind", |config| install_preset(config, preset))
+        .await?;
+    state.mark_unsaved().await;
+
+    let message = format!("Installed the {} keybindings (unsaved)", preset.title);
+    let mut html = publish_toast(&state, &message, false);
+    html.push_str(&presets_oob(
+        &installed_everywhere(&state).await,
+        Some(&conflicts),
+    ));
+    Ok(Html(html))
+}
+
+/// POST /api/keybinds/preset/remove — take out a preset's marked block (in memory),
+/// leaving the user's own bindings alone.
+#[utoipa::path(
+    post,
+    path = "/api/keybinds/preset/remove",
+    tag = "Keybinds",
+    summary = "Remove a preset's bindings",
+    request_body(content = PresetForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn remove_preset_bindings(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<PresetForm>,
+) -> Result<Html<String>, AppError> {
+    let preset = find_preset(&form.name)
+        .ok_or_else(|| AppError::Config(format!("Unknown keybind preset: {}", form.name)))?;
+    if !remove_preset_everywhere(&state, preset.name).await {
+        return Ok(Html(toast_html(
+            &format!("The {} keybindings aren't installed", preset.title),
+            true,
+        )));
+    }
+    state.mark_unsaved().await;
+
+    let message = format!("Removed the {} keybindings (unsaved)", preset.title);
+    let mut html = publish_toast(&state, &message, false);
+    html.push_str(&presets_oob(&installed_everywhere(&state).await, None));
     Ok(Html(html))
 }
+
+/// Remove the preset's block from the main config and every included file.
+async fn remove_preset_everywhere(state: &SharedState, name: &str) -> bool {
+    let mut user_config = state.user_config.write().await;
+    let mut included = state.included.write().await;
+    let mut removed = false;
+    for config in std::iter::once(&mut *user_config).chain(included.iter_mut()) {
+        removed |= remove_preset(config, name);
+    }
+    removed
+}
+
+/// Presets installed in the main config or any included file.
+pub(crate) async fn installed_everywhere(state: &SharedState) -> Vec<&'static str> {
+    let user_config = state.user_config.read().await;
+    let included = state.included.read().await;
+    std::iter::once(&*user_config)
+        .chain(included.iter())
+        .flat_map(installed_presets)
+        .collect()
+}
+
+fn presets_oob(installed: &[&str], report: Option<&[PresetConflict]>) -> String {
+    format!(
+        r#"<div id="keybind-presets" hx-swap-oob="innerHTML">{}</div>"#,
+        render_presets(installed, report)
+    )
+}
+
+/// The preset library, with apply or remove buttons, and after an install the
+/// bindings it overrode.
+pub(crate) fn render_presets(installed: &[&str], report: Option<&[PresetConflict]>) -> String {
+    let mut html = String::from(
+        r#"<div class="bg-white rounded-xl border border-gray-200 p-4 mb-6"><h3 class="font-semibold text-gray-900 mb-1">Presets</h3><p class="text-xs text-gray-400 mb-3">Curated sets of bindings, added as a marked block at the end of the config so they can be removed cleanly.</p><div class="grid md:grid-cols-3 gap-3">"#,
+    );
+    for preset in PRESETS {
+        let is_installed = installed.contains(&preset.name);
+        let button = if is_installed {
+            format!(
+                r##"<button hx-post="/api/keybinds/preset/remove" hx-vals='{{"name": "{name}"}}' hx-target="#toast-container" hx-swap="innerHTML" class="text-xs text-red-600 hover:text-red-700 font-medium">Remove</button><button hx-post="/api/keybinds/preset/apply" hx-vals='{{"name": "{name}"}}' hx-target="#toast-container" hx-swap="innerHTML" class="text-xs text-indigo-600 hover:text-indigo-700 font-medium">Reinstall</button>"##,
+                name = preset.name
+            )
+        } else {
+            format!(
+                r##"<button hx-post="/api/keybinds/preset/apply" hx-vals='{{"name": "{name}"}}' hx-target="#toast-container" hx-swap="innerHTML" class="px-3 py-1 bg-indigo-600 text-white rounded-lg text-xs font-medium hover:bg-indigo-700 transition-colors">Apply</button>"##,
+                name = preset.name
+            )
+        };
+        html.push_str(&format!(
+            r#"<div class="rounded-lg border {border} p-3"><div class="flex items-center justify-between mb-1"><span class="text-sm font-medium text-gray-900">{title}</span>{badge}</div><p class="text-xs text-gray-500 mb-2">{description} {count} bindings.</p><div class="flex items-center gap-3">{button}</div></div>"#,
+            border = if is_installed { "border-indigo-300 bg-indigo-50" } else { "border-gray-200" },
+            title = preset.title,
+            badge = if is_installed {
+                r#"<span class="text-xs bg-indigo-100 text-indigo-700 px-2 py-0.5 rounded-full">Installed</span>"#
+            } else {
+                ""
+            },
+            description = preset.description,
+            count = preset.bindings.len(),
+            button = button,
+        ));
+    }
+    html.push_str("</div>");
+    if let Some(conflicts) = report {
+        html.push_str(r#"<div class="mt-3 text-sm" role="status">"#);
+        if conflicts.is_empty() {
+            html.push_str(r#"<p class="text-green-700">No existing bindings were overridden.</p>"#);
+        } else {
+            html.push_str(&format!(
+                r#"<p class="text-amber-800 mb-1">{} existing {} on the same keys:</p><ul class="space-y-1 text-amber-900">"#,
+                conflicts.len(),
+                if conflicts.len() == 1 { "binding is" } else { "bindings are" },
+            ));
+            for (trigger, conflict) in conflicts {
+                let outcome = match conflict.source {
+                    ConflictSource::Default => "default, now overridden".to_string(),
+                    ConflictSource::Custom => "custom, now overridden".to_string(),
+                    ConflictSource::System => format!(
+                        "{} takes these keys first",
+                        Platform::current().display_name()
+                    ),
+                };
+                html.push_str(&format!(
+                    r#"<li><span class="font-mono">{} &rarr; {}</span> <span class="text-xs text-amber-700">({})</span></li>"#,
+                    escape_html(trigger),
+                    escape_html(&conflict.action),
+                    outcome,
+                ));
+            }
+            html.push_str("</ul>");
+        }
+        html.push_str(r#" <button type="button" onclick="location.reload()" class="mt-2 text-xs text-indigo-600 hover:text-indigo-700 font-medium">Refresh the table</button></div>"#);
+    }
+    html.push_str("</div>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_presets() {
+        let html = render_presets(&["vim"], None);
+        assert!(html.contains("tmux-style"));
+        assert!(html.contains(r#"hx-post="/api/keybinds/preset/remove" hx-vals='{"name": "vim"}'"#));
+        assert!(html.contains(r#"hx-post="/api/keybinds/preset/apply" hx-vals='{"name": "tmux"}'"#));
+        assert_eq!(html.matches(">Installed<").count(), 1);
+        assert!(!html.contains("Refresh the table"));
+
+        let conflicts = vec![(
+            "super+t",
+            KeybindConflict {
+                trigger: "super+t".to_string(),
+                action: "new_tab".to_string(),
+                source: ConflictSource::Default,
+            },
+        )];
+        let html = render_presets(&["macos"], Some(&conflicts));
+        assert!(html.contains("1 existing binding is on the same keys"));
+        assert!(html.contains("super+t &rarr; new_tab"));
+        assert!(html.contains("(default, now overridden)"));
+        assert!(render_presets(&[], Some(&[])).contains("No existing bindings were overridden"));
+    }
+
+    #[test]
+    fn test_render_conflicts_offers_override() {
+        let conflicts = vec![KeybindConflict {
+            trigger: "super+t".to_string(),
+            action: "new_tab".to_string(),
+            source: ConflictSource::Default,
+        }];
+        let html = render_conflicts("cmd+t", "new_window", &conflicts);
+        assert!(html.contains("super+t &rarr; new_tab"));
+        assert!(html.contains("(default)"));
+        assert!(html.contains(r#"name="force" value="true""#));
+        assert!(html.contains(r#"name="trigger" value="cmd+t""#));
+        assert!(html.contains("data-autofocus"));
+    }
+
+    #[test]
+    fn test_render_keybind_summary() {
+        let defaults = vec![Keybinding {
+            trigger: "super+t".to_string(),
+            action: "new_tab".to_string(),
+        }];
+        let custom = vec![
+            "super+t=new_window".to_string(),
+            "ctrl+a=select_all".to_string(),
+            "control+a=copy_to_clipboard".to_string(),
+        ];
+        let html = render_keybind_summary(&custom, &defaults, Platform::Linux);
+        assert!(html.contains("3 custom bindings &middot; 1 default overridden"));
+        assert!(html.contains("1 binding can't fire"));
+        assert!(html.contains(r#"href="/keybinds#keybind-ctrl-a""#));
+        assert!(html.contains("replaced by copy_to_clipboard"));
+        assert!(html.contains(r#"href="/keybinds""#));
+
+        let html = render_keybind_summary(&[], &defaults, Platform::Linux);
+        assert!(html.contains("0 custom bindings"));
+        assert!(html.contains("no conflicts"));
+    }
+
+    #[test]
+    fn test_render_conflicts_names_os() {
+        let conflicts = vec![KeybindConflict {
+            trigger: "super+space".to_string(),
+            action: "Spotlight".to_string(),
+            source: ConflictSource::System,
+        }];
+        let html = render_conflicts("cmd+space", "toggle_quick_terminal", &conflicts);
+        assert!(html.contains("super+space &rarr; Spotlight"));
+        assert!(html.contains(&format!("({})", Platform::current().display_name())));
+    }
+}
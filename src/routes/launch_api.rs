@@ -0,0 +1,164 @@
+use std::path::PathBuf;
+
+use axum::extract::State;
+use axum::response::{Html, IntoResponse, Response};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use super::config_api::{
+    escape_html, merge_redirect, publish_toast, validation_block, write_and_reload,
+};
+use crate::app_state::SharedState;
+use crate::cli::discovery::{launch_ghostty, LaunchOptions};
+use crate::config::os_shortcuts::Platform;
+use crate::config::paths::expand_path;
+use crate::error::AppError;
+use crate::scratch::write_scratch_copy;
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct LaunchForm {
+    /// Save the config first and open the saved file; otherwise the window loads
+    /// a scratch copy and the file on disk is left alone.
+    #[serde(default)]
+    pub save: bool,
+    /// Start the window here instead of the config's `working-directory`; `~` is
+    /// expanded. Blank keeps the config's.
+    #[serde(default)]
+    pub working_directory: String,
+    /// Run this instead of the config's `command`. Blank keeps the config's.
+    #[serde(default)]
+    pub command: String,
+}
+
+impl LaunchForm {
+    /// The overrides the form asks for, checked before anything is written.
+    fn options(&self) -> Result<LaunchOptions, AppError> {
+        let working_directory = match self.working_directory.trim() {
+            "" => None,
+            dir => {
+                let path = PathBuf::from(expand_path(dir));
+                if !path.is_dir() {
+                    return Err(AppError::Config(format!(
+                        "No such directory: {}",
+                        path.display()
+                    )));
+                }
+                Some(path)
+            }
+        };
+        let command = Some(self.command.trim())
+            .filter(|c| !c.is_empty())
+            .map(str::to_string);
+        Ok(LaunchOptions {
+            config_file: None,
+            working_directory,
+            command,
+        })
+    }
+}
+
+/// POST /api/launch — open a new Ghostty window with the current config, to check
+/// changes end to end. With `save`, the config is saved first (refused like Save
+/// when it changed on disk or Ghostty rejects it); otherwise the window loads a
+/// scratch copy, unsaved edits included.
+#[utoipa::path(
+    post,
+    path = "/api/launch",
+    tag = "Config",
+    summary = "Open a new Ghostty window with the current config: a scratch copy, or with `save=true` the saved file; `working_directory` and `command` override the config's",
+    request_body(content = LaunchForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn launch(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<LaunchForm>,
+) -> Result<Response, AppError> {
+    let ghostty_path = ghostty_path(&state).await?;
+    let mut options = form.options()?;
+    let message = if form.save {
+        if state.external_changes().await?.is_some() {
+            return Ok(merge_redirect());
+        }
+        if let Some(blocked) = validation_block(&state).await {
+            return Ok(blocked);
+        }
+        let annotate = state.settings.read().await.annotate_new_keys;
+        write_and_reload(&state, annotate).await?;
+        options.config_file = Some(state.user_config.read().await.file_path.clone());
+        "Config saved and a new Ghostty window opened with it".to_string()
+    } else {
+        let path = write_scratch_copy(&state).await?;
+        options.config_file = Some(path.clone());
+        scratch_message(&path)
+    };
+    launch_ghostty(&ghostty_path, &options, Platform::current()).await?;
+    Ok(Html(publish_toast(&state, &message, false)).into_response())
+}
+
+/// POST /api/scratch — open a Ghostty window with a scratch copy of the config,
+/// unsaved edits included, to see them rendered without saving.
+#[utoipa::path(
+    post,
+    path = "/api/scratch",
+    tag = "Config",
+    summary = "Open a Ghostty window that loads a scratch copy of the config, unsaved edits included, instead of the files on disk",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn try_in_ghostty(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let ghostty_path = ghostty_path(&state).await?;
+    let path = write_scratch_copy(&state).await?;
+    let options = LaunchOptions {
+        config_file: Some(path.clone()),
+        ..LaunchOptions::default()
+    };
+    launch_ghostty(&ghostty_path, &options, Platform::current()).await?;
+    Ok(Html(publish_toast(&state, &scratch_message(&path), false)))
+}
+
+async fn ghostty_path(state: &SharedState) -> Result<PathBuf, AppError> {
+    state.ghostty().await.path.clone().ok_or_else(|| {
+        AppError::Config(
+            "Ghostty couldn't be found, so there's nothing to open the config in".to_string(),
+        )
+    })
+}
+
+fn scratch_message(path: &std::path::Path) -> String {
+    format!(
+        "Opened Ghostty with a copy of your unsaved config ({}); your config file is untouched",
+        escape_html(&path.display().to_string())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_form_options() {
+        let form = LaunchForm {
+            working_directory: "  ".to_string(),
+            command: " htop ".to_string(),
+            ..LaunchForm::default()
+        };
+        let options = form.options().unwrap();
+        assert_eq!(options.working_directory, None);
+        assert_eq!(options.command.as_deref(), Some("htop"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let form = LaunchForm {
+            working_directory: dir.path().display().to_string(),
+            ..LaunchForm::default()
+        };
+        assert_eq!(
+            form.options().unwrap().working_directory.as_deref(),
+            Some(dir.path())
+        );
+
+        let form = LaunchForm {
+            working_directory: dir.path().join("missing").display().to_string(),
+            ..LaunchForm::default()
+        };
+        assert!(form.options().is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use axum::extract::State;
+use axum::response::Html;
+
+use super::config_api::{escape_html, publish_toast, toast_html};
+use crate::app_state::SharedState;
+use crate::config::merge::{apply_merge, three_way_merge, MergeOutcome, Resolution};
+use crate::error::AppError;
+
+/// GET /api/merge — show how external edits would merge with unsaved in-memory edits.
+pub async fn merge_preview(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let Some(theirs) = state.external_changes().await? else {
+        return Ok(Html(
+            r#"<div class="bg-white rounded-xl border border-gray-200 p-6 text-center text-gray-500">The config file has not changed on disk since it was loaded.</div>"#.to_string(),
+        ));
+    };
+
+    let base = state.base_config.read().await;
+    let ours = state.user_config.read().await;
+    let outcome = three_way_merge(&base, &theirs, &ours);
+
+    Ok(Html(render_merge(&outcome)))
+}
+
+/// POST /api/merge — merge external edits into memory using the submitted per-key choices.
+///
+/// Form fields are `<key>=ours|theirs`, one per conflicting key.
+pub async fn merge_apply(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<HashMap<String, String>>,
+) -> Result<Html<String>, AppError> {
+    let Some(theirs) = state.external_changes().await? else {
+        return Ok(Html(toast_html("Nothing to merge", true)));
+    };
+
+    let mut user_config = state.user_config.write().await;
+    let mut base = state.base_config.write().await;
+    let outcome = three_way_merge(&base, &theirs, &user_config);
+
+    let mut resolutions = HashMap::new();
+    for conflict in &outcome.conflicts {
+        let resolution = match form.get(&conflict.key).map(String::as_str) {
+            Some("ours") => Resolution::Ours,
+            Some("theirs") => Resolution::Theirs,
+            _ => {
+                return Ok(Html(toast_html(
+                    &format!("Choose a version for {}", escape_html(&conflict.key)),
+                    true,
+                )))
+            }
+        };
+        resolutions.insert(conflict.key.clone(), resolution);
+    }
+
+    let merged = apply_merge(&theirs, &user_config, &outcome, &resolutions);
+    // The external file is the new base; whatever still differs from it is unsaved.
+    *user_config = merged;
+    *base = theirs;
+    drop(base);
+    drop(user_config);
+
+    state.clear_unsaved().await;
+    state.mark_unsaved().await;
+
+    Ok(Html(publish_toast(
+        &state,
+        "Merged external changes (unsaved)",
+        false,
+    )))
+}
+
+fn render_merge(outcome: &MergeOutcome) -> String {
+    let mut html = String::new();
+
+    if !outcome.from_theirs.is_empty() || !outcome.from_ours.is_empty() {
+        html.push_str(r#"<div class="bg-white rounded-xl border border-gray-200 p-4 mb-6 text-sm text-gray-600 space-y-1">"#);
+        if !outcome.from_theirs.is_empty() {
+            html.push_str(&format!(
+                r#"<p><span class="font-medium text-gray-900">Taken from disk:</span> <span class="font-mono">{}</span></p>"#,
+                keys_html(&outcome.from_theirs)
+            ));
+        }
+        if !outcome.from_ours.is_empty() {
+            html.push_str(&format!(
+                r#"<p><span class="font-medium text-gray-900">Kept from your edits:</span> <span class="font-mono">{}</span></p>"#,
+                keys_html(&outcome.from_ours)
+            ));
+        }
+        html.push_str("</div>");
+    }
+
+    html.push_str(r##"<form hx-post="/api/merge" hx-target="#toast-container" hx-swap="innerHTML" hx-on::after-request="if(event.detail.successful) setTimeout(() => window.location.reload(), 600)">"##);
+
+    if outcome.conflicts.is_empty() {
+        html.push_str(r#"<p class="text-sm text-gray-500 mb-4">No conflicting keys &mdash; the changes merge cleanly.</p>"#);
+    } else {
+        html.push_str(r#"<div class="bg-white rounded-xl border border-gray-200 overflow-hidden mb-4"><table class="w-full"><thead class="bg-gray-50 border-b border-gray-200"><tr>"#);
+        for heading in ["Key", "Original", "On disk", "Your edit"] {
+            html.push_str(&format!(
+                r#"<th class="px-4 py-3 text-left text-xs font-semibold text-gray-500 uppercase tracking-wider">{}</th>"#,
+                heading
+            ));
+        }
+        html.push_str(r#"</tr></thead><tbody class="divide-y divide-gray-100">"#);
+        for conflict in &outcome.conflicts {
+            let key = escape_html(&conflict.key);
+            html.push_str(&format!(
+                r#"<tr><td class="px-4 py-3 text-sm font-mono font-semibold text-gray-900">{key}</td><td class="px-4 py-3 text-sm font-mono text-gray-400">{base}</td><td class="px-4 py-3"><label class="flex items-start gap-2 text-sm font-mono"><input type="radio" name="{key}" value="theirs" class="mt-1">{theirs}</label></td><td class="px-4 py-3"><label class="flex items-start gap-2 text-sm font-mono"><input type="radio" name="{key}" value="ours" checked class="mt-1">{ours}</label></td></tr>"#,
+                key = key,
+                base = values_html(&conflict.base),
+                theirs = values_html(&conflict.theirs),
+                ours = values_html(&conflict.ours),
+            ));
+        }
+        html.push_str("</tbody></table></div>");
+    }
+
+    html.push_str(r#"<button type="submit" class="px-4 py-2 bg-indigo-600 text-white rounded-lg text-sm font-medium hover:bg-indigo-700 transition-colors">Merge into unsaved config</button></form>"#);
+    html
+}
+
+fn keys_html(keys: &[String]) -> String {
+    keys.iter()
+        .map(|k| escape_html(k))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn values_html(values: &[String]) -> String {
+    if values.is_empty() {
+        return r#"<span class="text-gray-300">(unset)</span>"#.to_string();
+    }
+    values
+        .iter()
+        .map(|v| escape_html(v))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
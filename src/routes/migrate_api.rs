@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use axum::extract::{Query, State};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::config_api::{escape_html, publish_toast, toast_html};
+use super::import_export_api::{plural, review_error};
+use super::keybinds_api::retain_keybinds;
+use crate::app_state::SharedState;
+use crate::config::keybind_conflicts::same_trigger;
+use crate::config::migrate::{migrate, MigrateSource, Migration};
+use crate::error::AppError;
+
+#[derive(Deserialize, ToSchema)]
+pub struct MigrateForm {
+    /// A [`MigrateSource`] slug, e.g. `kitty`.
+    pub source: String,
+    /// The other terminal's config; read from where it usually is when empty.
+    #[serde(default)]
+    pub text: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MigrateQuery {
+    /// `json` for the mapping as JSON instead of HTML.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// POST /api/migrate — what another terminal's settings become in Ghostty, and
+/// which have no equivalent.
+#[utoipa::path(
+    post,
+    path = "/api/migrate",
+    tag = "Import/Export",
+    summary = "Map an iTerm2, Alacritty, kitty, WezTerm, or Windows Terminal config to Ghostty settings for review; reads the usual file when `text` is empty (`format=json` for JSON)",
+    params(MigrateQuery),
+    request_body(content = MigrateForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn preview_migration(
+    State(state): State<SharedState>,
+    Query(query): Query<MigrateQuery>,
+    axum::Form(form): axum::Form<MigrateForm>,
+) -> Result<Response, AppError> {
+    let json = query.format.as_deref() == Some("json");
+    let migration = match read_migration(&state, &form).await {
+        Ok(migration) => migration,
+        Err(AppError::Config(msg)) if !json => return Ok(Html(review_error(&msg)).into_response()),
+        Err(e) => return Err(e),
+    };
+    if json {
+        return Ok(Json(migration).into_response());
+    }
+    Ok(Html(render_migration(&migration)).into_response())
+}
+
+async fn read_migration(state: &SharedState, form: &MigrateForm) -> Result<Migration, AppError> {
+    let source = MigrateSource::parse(&form.source)?;
+    let actions = &state.ghostty().await.actions;
+    if !form.text.trim().is_empty() {
+        return migrate(&form.text, source, actions);
+    }
+    let Some(path) = source.find_config() else {
+        return Err(AppError::Config(format!(
+            "No {} config found; paste it instead",
+            source.display_name()
+        )));
+    };
+    let text = std::fs::read_to_string(&path)?;
+    migrate(&text, source, actions)
+}
+
+/// The mapping table, each row with a checkbox to leave it out, the settings
+/// that weren't mapped, and a button to apply the checked rows.
+fn render_migration(migration: &Migration) -> String {
+    let mut html = String::new();
+    if migration.mappings.is_empty() {
+        html.push_str(r#"<p class="text-sm text-amber-700 mb-4" role="alert">None of these settings have a Ghostty equivalent.</p>"#);
+    } else {
+        html.push_str(&format!(
+            r##"<form hx-post="/api/migrate/apply" hx-target="#toast-container" hx-swap="innerHTML"><h4 class="text-sm font-semibold text-gray-900 mb-2">{} to migrate</h4><div class="bg-white rounded-xl border border-gray-200 overflow-hidden mb-4"><table class="w-full"><thead class="bg-gray-50 border-b border-gray-200"><tr>"##,
+            plural(migration.mappings.len(), "setting")
+        ));
+        for heading in ["", "From", "Ghostty setting", "Note"] {
+            html.push_str(&format!(
+                r#"<th class="px-4 py-3 text-left text-xs font-semibold text-gray-500 uppercase tracking-wider">{}</th>"#,
+                heading
+            ));
+        }
+        html.push_str(r#"</tr></thead><tbody class="divide-y divide-gray-100">"#);
+        for (i, mapping) in migration.mappings.iter().enumerate() {
+            let line = format!("{} = {}", mapping.key, mapping.value);
+            html.push_str(&format!(
+                r#"<tr><td class="px-4 py-3"><input type="checkbox" name="m{i}" value="{line}" checked aria-label="Migrate {line}"></td><td class="px-4 py-3 text-sm font-mono text-gray-500">{source}</td><td class="px-4 py-3 text-sm font-mono text-gray-900">{line}</td><td class="px-4 py-3 text-xs text-gray-500">{note}</td></tr>"#,
+                i = i,
+                line = escape_html(&line),
+                source = escape_html(&mapping.source),
+                note = escape_html(mapping.note.as_deref().unwrap_or("")),
+            ));
+        }
+        html.push_str(r#"</tbody></table></div><button type="submit" class="px-4 py-2 bg-indigo-600 text-white rounded-lg text-sm font-medium hover:bg-indigo-700 transition-colors mb-6">Apply checked settings</button></form>"#);
+    }
+
+    if !migration.unmapped.is_empty() {
+        html.push_str(&format!(
+            r#"<h4 class="text-sm font-semibold text-gray-900 mb-2">{} not migrated</h4><ul class="space-y-1 text-sm">"#,
+            plural(migration.unmapped.len(), "setting")
+        ));
+        for unmapped in &migration.unmapped {
+            html.push_str(&format!(
+                r#"<li><span class="font-mono text-gray-500">{}</span> <span class="text-xs text-gray-400">&mdash; {}</span></li>"#,
+                escape_html(&unmapped.source),
+                escape_html(&unmapped.reason)
+            ));
+        }
+        html.push_str("</ul>");
+    }
+    html
+}
+
+/// The checked `key = value` lines in table order. Fields are named `m<row>`.
+fn checked_entries(form: &HashMap<String, String>) -> Vec<(String, String)> {
+    let mut rows: Vec<(usize, &str)> = form
+        .iter()
+        .filter_map(|(name, line)| Some((name.strip_prefix('m')?.parse().ok()?, line.as_str())))
+        .collect();
+    rows.sort_by_key(|(i, _)| *i);
+    rows.into_iter()
+        .filter_map(|(_, line)| {
+            let (key, value) = line.split_once(" = ")?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Group the values of each key, keys in the order they first appear.
+fn group_by_key(entries: &[(String, String)]) -> Vec<(&str, Vec<String>)> {
+    let mut grouped: Vec<(&str, Vec<String>)> = Vec::new();
+    for (key, value) in entries {
+        match grouped.iter_mut().find(|(k, _)| k == key) {
+            Some((_, values)) => values.push(value.clone()),
+            None => grouped.push((key, vec![value.clone()])),
+        }
+    }
+    grouped
+}
+
+/// POST /api/migrate/apply — set the checked settings (in memory). Keybindings
+/// replace custom bindings on the same keys and palette colors replace only
+/// their slots; every other setting replaces the current value.
+#[utoipa::path(
+    post,
+    path = "/api/migrate/apply",
+    tag = "Import/Export",
+    summary = "Set the checked settings from a migration review",
+    request_body(content = HashMap<String, String>, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn apply_migration(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<HashMap<String, String>>,
+) -> Result<Html<String>, AppError> {
+    let entries = checked_entries(&form);
+    if entries.is_empty() {
+        return Ok(Html(toast_html("No settings checked", true)));
+    }
+
+    for (key, values) in group_by_key(&entries) {
+        match key {
+            "keybind" => {
+                let triggers: Vec<&str> = values
+                    .iter()
+                    .filter_map(|b| b.split_once('=').map(|(t, _)| t))
+                    .collect();
+                retain_keybinds(&state, |value| {
+                    value
+                        .split_once('=')
+                        .is_none_or(|(t, _)| !triggers.iter().any(|new| same_trigger(t, new)))
+                })
+                .await;
+                state
+                    .add_entry("keybind", |config| {
+                        for binding in &values {
+                            config.push("keybind", binding);
+                        }
+                    })
+                    .await?;
+            }
+            "palette" => {
+                let slot = |value: &str| value.split_once('=').map(|(i, _)| i.trim().to_string());
+                let mut palette: Vec<String> = state
+                    .all_values("palette")
+                    .await
+                    .into_iter()
+                    .filter(|existing| !values.iter().any(|new| slot(new) == slot(existing)))
+                    .collect();
+                palette.extend(values);
+                state.replace_values("palette", &palette).await?;
+            }
+            _ => state.replace_values(key, &values).await?,
+        }
+    }
+    state.mark_unsaved().await;
+
+    let message = format!("Migrated {} (unsaved)", plural(entries.len(), "setting"));
+    let mut html = publish_toast(&state, &message, false);
+    html.push_str(r#"<div id="migrate-review" hx-swap-oob="innerHTML"></div>"#);
+    Ok(Html(html))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::migrate::{Mapping, Unmapped};
+
+    #[test]
+    fn test_checked_entries_in_table_order() {
+        let form: HashMap<String, String> = [
+            ("m10", "keybind = ctrl+a=text:="),
+            ("m2", "font-family = Fira Code"),
+            ("source", "kitty"),
+            ("m3", "not a line"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(
+            checked_entries(&form),
+            vec![
+                ("font-family".to_string(), "Fira Code".to_string()),
+                ("keybind".to_string(), "ctrl+a=text:=".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_by_key() {
+        let entries = vec![
+            ("font-family".to_string(), "A".to_string()),
+            ("font-size".to_string(), "13".to_string()),
+            ("font-family".to_string(), "B".to_string()),
+        ];
+        assert_eq!(
+            group_by_key(&entries),
+            vec![
+                ("font-family", vec!["A".to_string(), "B".to_string()]),
+                ("font-size", vec!["13".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_migration() {
+        let migration = Migration {
+            mappings: vec![Mapping {
+                source: "scrollback_lines 5000".to_string(),
+                key: "scrollback-limit".to_string(),
+                value: "5000000".to_string(),
+                note: Some("about <5000> lines".to_string()),
+            }],
+            unmapped: vec![Unmapped {
+                source: "tab_bar_style powerline".to_string(),
+                reason: "no Ghostty setting is mapped from this".to_string(),
+            }],
+        };
+        let html = render_migration(&migration);
+        assert!(html.contains("1 setting to migrate"));
+        assert!(html.contains(r#"name="m0" value="scrollback-limit = 5000000" checked"#));
+        assert!(html.contains("about &lt;5000&gt; lines"));
+        assert!(html.contains("1 setting not migrated"));
+        assert!(html.contains("tab_bar_style powerline"));
+
+        let none = render_migration(&Migration {
+            mappings: Vec::new(),
+            unmapped: migration.unmapped,
+        });
+        assert!(none.contains("None of these settings"));
+        assert!(!none.contains("<form"));
+    }
+}
@@ -3,14 +3,47 @@ use tower_http::services::ServeDir;
 
 use crate::app_state::SharedState;
 
+pub mod accessibility_api;
+pub mod autosave_api;
+pub mod baseline_api;
+pub mod browser_api;
 pub mod config_api;
+pub mod diff_api;
+pub mod doctor_api;
+pub mod effective_api;
+pub mod events_api;
+pub mod files_api;
+pub mod font_features_api;
+pub mod font_metrics_api;
+pub mod font_variation_api;
 pub mod fonts_api;
+pub mod ghostty_api;
+pub mod history_api;
 pub mod import_export_api;
 pub mod keybinds_api;
+pub mod launch_api;
+pub mod merge_api;
+pub mod migrate_api;
+pub mod openapi_api;
 pub mod pages;
+pub mod palette_api;
+pub mod pins_api;
 pub mod preview_api;
+pub mod profiles_api;
+pub mod pty_api;
+pub mod raw_api;
+pub mod refresh_api;
+pub mod safe_mode_api;
+pub mod save_targets_api;
+pub mod schema_api;
+pub mod session_api;
+pub mod share_api;
+pub mod shutdown_api;
+pub mod starter_api;
 pub mod themes_api;
+pub mod updates_api;
 pub mod validation_api;
+pub mod ws_api;
 
 pub fn build_router(state: SharedState) -> Router {
     Router::new()
@@ -18,30 +51,211 @@ pub fn build_router(state: SharedState) -> Router {
         .route("/", axum::routing::get(pages::index))
         .route("/category/{slug}", axum::routing::get(pages::category))
         .route("/themes", axum::routing::get(pages::themes_page))
+        .route("/themes/new", axum::routing::get(pages::theme_editor))
         .route("/keybinds", axum::routing::get(pages::keybinds_page))
         .route(
             "/import-export",
             axum::routing::get(pages::import_export_page),
         )
+        .route("/diff", axum::routing::get(pages::diff_page))
+        .route("/merge", axum::routing::get(pages::merge_page))
+        .route("/migrate", axum::routing::get(pages::migrate_page))
+        .route("/baseline", axum::routing::get(pages::baseline_page))
+        .route("/editor", axum::routing::get(pages::editor_page))
+        .route("/profiles", axum::routing::get(pages::profiles_page))
+        .route("/history", axum::routing::get(pages::history_page))
+        .route("/welcome", axum::routing::get(pages::welcome_page))
+        // Schema
+        .route("/api/schema", axum::routing::get(schema_api::schema))
+        .route(
+            "/api/schema/inactive",
+            axum::routing::get(schema_api::inactive),
+        )
+        .route(
+            "/api/openapi.json",
+            axum::routing::get(openapi_api::openapi),
+        )
+        .route("/api/docs", axum::routing::get(openapi_api::docs))
         // Config API (HTMX)
+        .route("/api/config", axum::routing::put(config_api::bulk_edit))
+        .route(
+            "/api/category/{slug}/reset",
+            axum::routing::post(config_api::reset_category),
+        )
         .route(
             "/api/config/{key}",
             axum::routing::get(config_api::get_value)
                 .put(config_api::set_value)
+                .post(config_api::add_entry)
                 .delete(config_api::delete_value),
         )
+        .route(
+            "/api/config/{key}/{index}",
+            axum::routing::get(config_api::get_entry)
+                .put(config_api::set_entry)
+                .post(config_api::insert_entry)
+                .delete(config_api::delete_entry),
+        )
+        .route(
+            "/api/raw",
+            axum::routing::get(raw_api::get_raw).put(raw_api::put_raw),
+        )
+        .route("/api/pins", axum::routing::post(pins_api::toggle_pin))
+        .route(
+            "/api/autosave",
+            axum::routing::get(autosave_api::autosave).post(autosave_api::set_autosave),
+        )
+        .route(
+            "/api/scratch",
+            axum::routing::post(launch_api::try_in_ghostty),
+        )
+        .route("/api/launch", axum::routing::post(launch_api::launch))
+        .route("/api/history", axum::routing::get(history_api::list))
+        .route("/api/session", axum::routing::get(session_api::banner))
+        .route("/api/starter", axum::routing::post(starter_api::create))
+        .route(
+            "/api/starter/preview",
+            axum::routing::post(starter_api::preview),
+        )
+        .route(
+            "/api/session/restore",
+            axum::routing::post(session_api::restore),
+        )
+        .route(
+            "/api/session/discard",
+            axum::routing::post(session_api::discard),
+        )
+        .route(
+            "/api/history/{id}/revert",
+            axum::routing::post(history_api::revert),
+        )
+        .route(
+            "/api/settings/annotate",
+            axum::routing::post(save_targets_api::set_annotate),
+        )
+        .route(
+            "/api/settings/platforms",
+            axum::routing::post(schema_api::set_show_all_platforms),
+        )
+        .route(
+            "/api/save-targets",
+            axum::routing::get(save_targets_api::get_save_targets)
+                .post(save_targets_api::set_save_targets),
+        )
+        // Profiles
+        .route(
+            "/api/profiles",
+            axum::routing::get(profiles_api::list).post(profiles_api::create),
+        )
+        .route(
+            "/api/profiles/{name}",
+            axum::routing::get(profiles_api::get)
+                .put(profiles_api::update)
+                .delete(profiles_api::delete),
+        )
+        .route(
+            "/api/profiles/{name}/activate",
+            axum::routing::post(profiles_api::activate),
+        )
         // Themes API
         .route("/api/themes", axum::routing::get(themes_api::list_themes))
+        .route(
+            "/api/themes/favorite",
+            axum::routing::post(themes_api::toggle_favorite),
+        )
         .route(
             "/api/themes/apply",
             axum::routing::post(themes_api::apply_theme),
         )
+        .route(
+            "/api/themes/{name}/contrast",
+            axum::routing::get(themes_api::theme_contrast_report),
+        )
+        .route(
+            "/api/themes/rescan",
+            axum::routing::post(themes_api::rescan_themes),
+        )
+        .route(
+            "/api/themes/overrides",
+            axum::routing::get(themes_api::theme_overrides),
+        )
+        .route(
+            "/api/themes/overrides/clear",
+            axum::routing::post(themes_api::clear_overrides),
+        )
+        .route(
+            "/api/themes/overrides/extract",
+            axum::routing::post(themes_api::extract_overrides),
+        )
+        .route(
+            "/api/themes/custom",
+            axum::routing::get(themes_api::list_custom_themes)
+                .post(themes_api::create_custom_theme),
+        )
+        .route(
+            "/api/themes/custom/preview",
+            axum::routing::post(themes_api::preview_custom_theme),
+        )
+        .route(
+            "/api/themes/custom/{name}",
+            axum::routing::put(themes_api::update_custom_theme)
+                .delete(themes_api::delete_custom_theme),
+        )
+        // Palette API
+        .route(
+            "/api/palette",
+            axum::routing::get(palette_api::grid).put(palette_api::update_grid),
+        )
+        .route(
+            "/api/palette/generate",
+            axum::routing::get(palette_api::generate),
+        )
+        .route(
+            "/api/palette/generate/image",
+            axum::routing::post(palette_api::generate_from_image).layer(
+                axum::extract::DefaultBodyLimit::max(files_api::MAX_UPLOAD_BYTES),
+            ),
+        )
+        .route(
+            "/api/palette/{index}",
+            axum::routing::get(palette_api::get_slot)
+                .put(palette_api::update_slot)
+                .delete(palette_api::reset_slot),
+        )
         // Fonts API
         .route("/api/fonts", axum::routing::get(fonts_api::list_fonts))
         .route(
             "/api/fonts/search",
             axum::routing::get(fonts_api::search_fonts),
         )
+        .route(
+            "/api/fonts/features",
+            axum::routing::get(font_features_api::panel),
+        )
+        .route(
+            "/api/fonts/features/{tag}",
+            axum::routing::put(font_features_api::set),
+        )
+        .route(
+            "/api/fonts/metrics",
+            axum::routing::get(font_metrics_api::panel),
+        )
+        .route(
+            "/api/fonts/metrics/preview",
+            axum::routing::get(font_metrics_api::preview),
+        )
+        .route(
+            "/api/fonts/variation",
+            axum::routing::get(font_variation_api::panel),
+        )
+        .route(
+            "/api/fonts/variation/{key}/{tag}",
+            axum::routing::put(font_variation_api::set_axis).delete(font_variation_api::reset_axis),
+        )
+        .route(
+            "/api/fonts/preview/{family}",
+            axum::routing::get(fonts_api::preview_font),
+        )
         // Keybinds API
         .route(
             "/api/keybinds",
@@ -51,6 +265,18 @@ pub fn build_router(state: SharedState) -> Router {
             "/api/keybinds/delete",
             axum::routing::post(keybinds_api::delete_keybind),
         )
+        .route(
+            "/api/keybinds/normalize",
+            axum::routing::post(keybinds_api::normalize),
+        )
+        .route(
+            "/api/keybinds/preset/apply",
+            axum::routing::post(keybinds_api::apply_preset),
+        )
+        .route(
+            "/api/keybinds/preset/remove",
+            axum::routing::post(keybinds_api::remove_preset_bindings),
+        )
         // Save / Apply
         .route("/api/save", axum::routing::post(config_api::save_config))
         .route("/api/apply", axum::routing::post(config_api::apply_config))
@@ -59,6 +285,13 @@ pub fn build_router(state: SharedState) -> Router {
             "/api/validate",
             axum::routing::get(validation_api::validate),
         )
+        .route(
+            "/api/validate/status",
+            axum::routing::get(validation_api::status),
+        )
+        .route("/api/lint", axum::routing::get(validation_api::lint))
+        .route("/api/doctor", axum::routing::get(doctor_api::doctor))
+        .route("/api/doctor/fix", axum::routing::post(doctor_api::fix))
         // Import/Export
         .route(
             "/api/export",
@@ -66,14 +299,154 @@ pub fn build_router(state: SharedState) -> Router {
         )
         .route(
             "/api/import",
-            axum::routing::post(import_export_api::import_config),
+            axum::routing::get(import_export_api::preview_shared)
+                .post(import_export_api::import_config),
+        )
+        .route(
+            "/api/import/preview",
+            axum::routing::post(import_export_api::preview_import),
+        )
+        .route(
+            "/api/import/colors",
+            axum::routing::post(import_export_api::import_color_scheme),
+        )
+        .route(
+            "/api/import/keybinds",
+            axum::routing::post(import_export_api::review_keybinds),
+        )
+        .route(
+            "/api/import/keybinds/apply",
+            axum::routing::post(import_export_api::apply_keybinds),
+        )
+        .route(
+            "/api/share",
+            axum::routing::get(share_api::share_link).post(share_api::share_gist),
+        )
+        // Diff
+        .route("/api/diff", axum::routing::get(diff_api::diff))
+        .route(
+            "/api/effective-config",
+            axum::routing::get(effective_api::effective_config),
+        )
+        .route(
+            "/api/merge",
+            axum::routing::get(merge_api::merge_preview).post(merge_api::merge_apply),
+        )
+        // Migrating from other terminals
+        .route(
+            "/api/migrate",
+            axum::routing::post(migrate_api::preview_migration),
+        )
+        .route(
+            "/api/migrate/apply",
+            axum::routing::post(migrate_api::apply_migration),
+        )
+        // Team baseline
+        .route(
+            "/api/baseline",
+            axum::routing::get(baseline_api::deviations).post(baseline_api::set_baseline),
+        )
+        .route(
+            "/api/baseline/refresh",
+            axum::routing::post(baseline_api::refresh_baseline),
+        )
+        .route(
+            "/api/baseline/adopt",
+            axum::routing::post(baseline_api::adopt_key),
+        )
+        .route(
+            "/api/baseline/ignore",
+            axum::routing::post(baseline_api::toggle_ignore),
         )
         // Preview
         .route(
             "/api/preview",
             axum::routing::get(preview_api::preview_data),
         )
+        // File picker for path options
+        .route("/api/files", axum::routing::get(files_api::browse))
+        .route(
+            "/api/files/thumbnail",
+            axum::routing::get(files_api::thumbnail),
+        )
+        .route(
+            "/api/files/upload",
+            axum::routing::post(files_api::upload).layer(axum::extract::DefaultBodyLimit::max(
+                files_api::MAX_UPLOAD_BYTES,
+            )),
+        )
+        .route(
+            "/api/preview/background-image",
+            axum::routing::get(preview_api::background_image),
+        )
+        .route(
+            "/api/preview/terminal",
+            axum::routing::get(preview_api::terminal_options),
+        )
+        // Live updates
+        .route("/events", axum::routing::get(events_api::events))
+        .route(
+            "/ws",
+            axum::routing::get(ws_api::ws)
+                .layer(axum::middleware::from_fn(ws_api::require_same_origin)),
+        )
+        .route("/ws/preview", axum::routing::get(pty_api::pty_preview))
+        // Accessibility
+        .route(
+            "/api/accessibility/reduced-motion",
+            axum::routing::get(accessibility_api::reduced_motion)
+                .post(accessibility_api::toggle_reduced_motion),
+        )
+        // Ghostty installations
+        .route(
+            "/api/ghostty",
+            axum::routing::get(ghostty_api::status).post(ghostty_api::switch),
+        )
+        .route("/api/refresh", axum::routing::post(refresh_api::refresh))
+        // Update checks
+        .route(
+            "/api/updates",
+            axum::routing::get(updates_api::update_status),
+        )
+        .route(
+            "/api/updates/check",
+            axum::routing::post(updates_api::check_now),
+        )
+        .route(
+            "/api/updates/enable",
+            axum::routing::post(updates_api::enable_checks),
+        )
+        .route(
+            "/api/updates/disable",
+            axum::routing::post(updates_api::disable_checks),
+        )
+        .route(
+            "/api/updates/remind-later",
+            axum::routing::post(updates_api::remind_later),
+        )
+        // Browser
+        .route("/api/open", axum::routing::post(browser_api::open_browser))
+        .route("/api/instance", axum::routing::get(browser_api::instance))
+        .route("/api/shutdown", axum::routing::post(shutdown_api::shutdown))
+        // Safe mode
+        .route(
+            "/api/safe-mode/restore",
+            axum::routing::post(safe_mode_api::restore_backup),
+        )
         // Static files
         .nest_service("/static", ServeDir::new("static"))
+        .layer(axum::middleware::from_fn(crate::error::render_errors))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            crate::revision::guard,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            safe_mode_api::guard,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            crate::auth::guard,
+        ))
         .with_state(state)
 }
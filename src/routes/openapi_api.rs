@@ -0,0 +1,314 @@
+//! An OpenAPI description of the HTTP API, for scripts and other tools.
+//!
+//! The description is derived from the `#[utoipa::path]` attribute on each handler
+//! and the schemas of the forms and JSON they take and return. Most endpoints answer
+//! with HTML fragments for the UI; the description says so.
+
+use axum::http::Method;
+use axum::response::Html;
+use axum::Json;
+use utoipa::openapi::path::{Operation, ParameterBuilder, ParameterIn};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+use utoipa::openapi::{ObjectBuilder, Required, ResponseBuilder, Type};
+use utoipa::{Modify, OpenApi};
+
+use super::{
+    autosave_api, config_api, doctor_api, effective_api, font_features_api, font_metrics_api,
+    font_variation_api, fonts_api, ghostty_api, history_api, import_export_api, keybinds_api,
+    launch_api, migrate_api, palette_api, pins_api, refresh_api, schema_api, session_api,
+    share_api, starter_api, themes_api, validation_api,
+};
+use crate::revision::needs_revision;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Ghostty Config",
+        description = "Edit a Ghostty config through the running ghostty-config server. \
+            Changes stay in memory until `POST /api/save`. Changing requests need the \
+            session token as the `X-Auth-Token` header unless the server runs with `--no-auth`. \
+            Every response has the config's revision in `X-Config-Revision`; changes to the \
+            config send it back and are refused with 409 when the config changed since.",
+    ),
+    paths(
+        schema_api::schema,
+        schema_api::inactive,
+        ghostty_api::status,
+        ghostty_api::switch,
+        refresh_api::refresh,
+        config_api::bulk_edit,
+        config_api::get_value,
+        config_api::set_value,
+        config_api::add_entry,
+        config_api::delete_value,
+        config_api::get_entry,
+        config_api::set_entry,
+        config_api::insert_entry,
+        config_api::delete_entry,
+        config_api::reset_category,
+        config_api::save_config,
+        config_api::apply_config,
+        autosave_api::autosave,
+        autosave_api::set_autosave,
+        launch_api::try_in_ghostty,
+        launch_api::launch,
+        history_api::list,
+        history_api::revert,
+        session_api::banner,
+        session_api::restore,
+        session_api::discard,
+        starter_api::preview,
+        starter_api::create,
+        pins_api::toggle_pin,
+        themes_api::list_themes,
+        themes_api::apply_theme,
+        themes_api::toggle_favorite,
+        themes_api::theme_contrast_report,
+        themes_api::rescan_themes,
+        themes_api::theme_overrides,
+        themes_api::clear_overrides,
+        themes_api::extract_overrides,
+        themes_api::list_custom_themes,
+        themes_api::create_custom_theme,
+        themes_api::preview_custom_theme,
+        themes_api::update_custom_theme,
+        themes_api::delete_custom_theme,
+        palette_api::grid,
+        palette_api::update_grid,
+        palette_api::generate,
+        palette_api::generate_from_image,
+        palette_api::get_slot,
+        palette_api::update_slot,
+        palette_api::reset_slot,
+        fonts_api::list_fonts,
+        fonts_api::search_fonts,
+        fonts_api::preview_font,
+        font_features_api::panel,
+        font_features_api::set,
+        font_metrics_api::panel,
+        font_metrics_api::preview,
+        font_variation_api::panel,
+        font_variation_api::set_axis,
+        font_variation_api::reset_axis,
+        keybinds_api::add_keybind,
+        keybinds_api::delete_keybind,
+        keybinds_api::normalize,
+        keybinds_api::apply_preset,
+        keybinds_api::remove_preset_bindings,
+        validation_api::validate,
+        validation_api::lint,
+        doctor_api::doctor,
+        doctor_api::fix,
+        effective_api::effective_config,
+        validation_api::status,
+        import_export_api::export_config,
+        import_export_api::import_config,
+        import_export_api::preview_shared,
+        import_export_api::preview_import,
+        import_export_api::import_color_scheme,
+        import_export_api::review_keybinds,
+        import_export_api::apply_keybinds,
+        share_api::share_link,
+        share_api::share_gist,
+        migrate_api::preview_migration,
+        migrate_api::apply_migration,
+    ),
+    security(("token" = [])),
+    modifiers(&Conventions),
+)]
+struct ApiDoc;
+
+/// What every operation has in common and the handlers don't say: the session
+/// token, the errors any request can get, and the revision changes send back.
+struct Conventions;
+
+impl Modify for Conventions {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "token",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Auth-Token"))),
+            );
+        for (path, item) in openapi.paths.paths.iter_mut() {
+            let operations = [
+                (Method::GET, &mut item.get),
+                (Method::PUT, &mut item.put),
+                (Method::POST, &mut item.post),
+                (Method::DELETE, &mut item.delete),
+            ];
+            for (method, operation) in operations {
+                if let Some(operation) = operation {
+                    add_conventions(operation, needs_revision(&method, path));
+                }
+            }
+        }
+    }
+}
+
+fn add_conventions(operation: &mut Operation, versioned: bool) {
+    let mut respond = |status: &str, description: &str| {
+        operation.responses.responses.insert(
+            status.to_string(),
+            ResponseBuilder::new()
+                .description(description)
+                .build()
+                .into(),
+        );
+    };
+    respond("400", "The request was invalid; the body explains why");
+    respond("401", "Missing the session token (see `--no-auth`)");
+    if !versioned {
+        return;
+    }
+    respond(
+        "409",
+        "The config was changed by someone else since that revision; the response has the current one",
+    );
+    respond("428", "Missing `X-Config-Revision`");
+    let revision = ParameterBuilder::new()
+        .name("X-Config-Revision")
+        .parameter_in(ParameterIn::Header)
+        .required(Required::True)
+        .description(Some(
+            "The `X-Config-Revision` of a response received since the last change by someone else",
+        ))
+        .schema(Some(ObjectBuilder::new().schema_type(Type::Integer)))
+        .build();
+    operation
+        .parameters
+        .get_or_insert_with(Vec::new)
+        .push(revision);
+}
+
+/// GET /api/openapi.json — the OpenAPI description of the config, theme, font,
+/// keybind, validation, and import/export endpoints.
+pub async fn openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// GET /api/docs — Swagger UI for `/api/openapi.json`.
+pub async fn docs() -> Html<&'static str> {
+    Html(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <title>Ghostty Config API</title>
+    <link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/swagger-ui-dist@5.17.14/swagger-ui.css">
+</head>
+<body>
+    <div id="swagger-ui"></div>
+    <script src="https://cdn.jsdelivr.net/npm/swagger-ui-dist@5.17.14/swagger-ui-bundle.js"></script>
+    <script>SwaggerUIBundle({ url: '/api/openapi.json', dom_id: '#swagger-ui' });</script>
+</body>
+</html>"#,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use super::*;
+
+    #[test]
+    fn test_openapi_document() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let bulk = &doc["paths"]["/api/config"]["put"];
+        assert_eq!(bulk["tags"][0], "Config");
+        assert!(bulk["requestBody"]["content"]["application/json"].is_object());
+
+        let entry = &doc["paths"]["/api/config/{key}/{index}"];
+        for method in ["get", "put", "post", "delete"] {
+            assert!(entry[method].is_object(), "{method}");
+        }
+        let params = entry["get"]["parameters"].as_array().unwrap();
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[1]["name"], "index");
+        assert_eq!(params[1]["schema"]["type"], "integer");
+
+        let set = &doc["paths"]["/api/config/{key}"]["put"];
+        assert_eq!(set["parameters"][1]["name"], "X-Config-Revision");
+        assert!(set["responses"]["409"].is_object());
+        let form = &set["requestBody"]["content"]["application/x-www-form-urlencoded"];
+        assert_eq!(form["schema"]["$ref"], "#/components/schemas/SetValueForm");
+        let schema = &doc["components"]["schemas"]["SetValueForm"];
+        assert_eq!(schema["required"], serde_json::json!(["value"]));
+        assert!(doc["paths"]["/api/shutdown"]["post"]["responses"]["409"].is_null());
+    }
+
+    /// Route prefixes the description covers.
+    const DOCUMENTED_PREFIXES: &[&str] = &[
+        "/api/schema",
+        "/api/ghostty",
+        "/api/refresh",
+        "/api/config",
+        "/api/category",
+        "/api/save",
+        "/api/apply",
+        "/api/autosave",
+        "/api/scratch",
+        "/api/launch",
+        "/api/themes",
+        "/api/palette",
+        "/api/fonts",
+        "/api/keybinds",
+        "/api/validate",
+        "/api/effective-config",
+        "/api/lint",
+        "/api/doctor",
+        "/api/pins",
+        "/api/history",
+        "/api/session",
+        "/api/starter",
+        "/api/import",
+        "/api/export",
+        "/api/share",
+        "/api/migrate",
+    ];
+
+    /// The method and path of every route in the router, from `mod.rs`.
+    fn router_routes() -> Vec<(String, String)> {
+        let method = Regex::new(r"(?:routing::|\.)(get|put|post|delete)\(").unwrap();
+        let mut routes = Vec::new();
+        for block in include_str!("mod.rs").split(".route(").skip(1) {
+            let path = block.split('"').nth(1).unwrap();
+            for m in method.captures_iter(block) {
+                routes.push((m[1].to_string(), path.to_string()));
+            }
+        }
+        routes
+    }
+
+    /// Every route under a documented prefix is described with the method it's
+    /// routed with, and nothing is described that isn't routed.
+    #[test]
+    fn test_operations_cover_routes() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let documented = |path: &str| {
+            DOCUMENTED_PREFIXES.iter().any(|p| {
+                path.strip_prefix(p)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+        };
+        let routes = router_routes();
+        for (method, path) in routes.iter().filter(|(_, path)| documented(path)) {
+            assert!(
+                doc["paths"][path][method].is_object(),
+                "{} {path} is not described",
+                method.to_uppercase()
+            );
+        }
+        for (path, item) in doc["paths"].as_object().unwrap() {
+            for method in item.as_object().unwrap().keys() {
+                assert!(
+                    routes.iter().any(|(m, p)| m == method && p == path),
+                    "{} {path} is described but not routed",
+                    method.to_uppercase()
+                );
+            }
+        }
+    }
+}
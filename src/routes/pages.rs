@@ -1,9 +1,39 @@
+use std::collections::HashMap;
+
 use askama::Template;
-use axum::extract::{Path, State};
-use axum::response::Html;
+use axum::extract::{Path, Query, State};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use serde::Deserialize;
 
-use crate::app_state::SharedState;
-use crate::config::model::{Category, ConfigValueType};
+use super::config_api::render_entries;
+use super::keybinds_api::{
+    installed_everywhere, keybind_anchor, render_keybind_summary, render_presets,
+};
+use super::pins_api::render_pin_button;
+use super::preview_api::image_layer_style;
+use super::safe_mode_api::render_banner;
+use super::schema_api::inactive_options;
+use super::starter_api::is_first_run;
+use super::themes_api::{
+    active_label, render_contrast_warning, render_favorite_button, render_theme_sample,
+};
+use crate::app_state::{AppState, SharedState};
+use crate::config::adjust_metric::{Adjustment, ADJUSTMENT_UNITS};
+use crate::config::background::{resolve_background, with_alpha};
+use crate::config::color_names::picker_hex;
+use crate::config::custom_theme::{normalize_hex, PALETTE_NAMES};
+use crate::config::file_io::render_config;
+use crate::config::keybind_presets::{KeybindPreset, PRESETS};
+use crate::config::migrate::MigrateSource;
+use crate::config::model::{
+    Category, ConfigOption, ConfigValueType, EnumVariant, ThemeInfo, ThemeOrigin,
+};
+use crate::config::os_shortcuts::{os_conflicts, Platform};
+use crate::config::starter::Padding;
+use crate::config::theme_metadata::{theme_families, theme_tags, ThemeTag};
+use crate::config::theme_selection::ThemeSelection;
+use crate::config::units::{split_byte_size, split_duration, BYTE_UNITS, DURATION_UNITS};
+use crate::config::validate_value::value_range;
 use crate::error::AppError;
 
 #[derive(Template)]
@@ -14,6 +44,12 @@ struct IndexTemplate {
     theme_count: usize,
     font_count: usize,
     modified_count: usize,
+    /// The "My settings" fields; empty when nothing is pinned.
+    pinned_html: String,
+    /// The built-in schema's Ghostty version, when Ghostty couldn't be run.
+    schema_snapshot: Option<String>,
+    /// No config yet, so the page offers the wizard; see `is_first_run`.
+    first_run: bool,
 }
 
 struct CategoryInfo {
@@ -25,31 +61,58 @@ struct CategoryInfo {
 }
 
 pub async fn index(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
-    let unsaved = state.unsaved.read().await;
+    let ghostty = state.ghostty().await;
+    let pinned: Vec<&ConfigOption> = state
+        .settings
+        .read()
+        .await
+        .pinned
+        .iter()
+        .filter_map(|key| ghostty.schema.find_option(key))
+        .collect();
+    let pinned_html = if pinned.is_empty() {
+        String::new()
+    } else {
+        let filter = CategoryQuery::default();
+        CategoryFieldsTemplate {
+            fields: option_fields(&state, &pinned, &filter).await,
+            fields_oob: false,
+            filter,
+            hidden_count: 0,
+            platform_name: state.platform.display_name().to_string(),
+        }
+        .render()
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Template error: {}", e)))?
+    };
+
+    let first_run = is_first_run(&state).await;
+    let unsaved = state.unsaved_keys().await;
     let modified_count = unsaved.len();
 
-    let categories: Vec<CategoryInfo> = Category::all()
-        .into_iter()
-        .map(|cat| {
-            let options = state.schema.options_for_category(&cat);
-            let count = options.len();
-            let modified = options.iter().filter(|o| unsaved.contains(&o.key)).count();
-            CategoryInfo {
-                slug: cat.slug().to_string(),
-                name: cat.display_name().to_string(),
-                icon: cat.icon().to_string(),
-                count,
-                modified,
-            }
-        })
-        .collect();
+    let mut categories = Vec::new();
+    for cat in Category::all() {
+        if !state.shows_platforms(cat.platform().as_slice()).await {
+            continue;
+        }
+        let options = shown_options(&state, ghostty.schema.options_for_category(&cat)).await;
+        categories.push(CategoryInfo {
+            slug: cat.slug().to_string(),
+            name: cat.display_name().to_string(),
+            icon: cat.icon().to_string(),
+            count: options.len(),
+            modified: options.iter().filter(|o| unsaved.contains(&o.key)).count(),
+        });
+    }
 
     let tmpl = IndexTemplate {
-        config_count: state.schema.options.len(),
-        theme_count: state.themes.len(),
-        font_count: state.fonts.len(),
+        config_count: ghostty.schema.options().len(),
+        theme_count: state.themes.read().await.len(),
+        font_count: ghostty.fonts.len(),
         modified_count,
         categories,
+        pinned_html,
+        schema_snapshot: ghostty.snapshot().map(String::from),
+        first_run,
     };
 
     Ok(Html(tmpl.render().map_err(|e| {
@@ -65,6 +128,55 @@ struct CategoryTemplate {
     category_slug: String,
     categories: Vec<SidebarCategory>,
     fields: Vec<FieldData>,
+    fields_oob: bool,
+    filter: CategoryQuery,
+    show_all_platforms: bool,
+    /// Options left out for being specific to another platform.
+    hidden_count: usize,
+    /// The platform whose options are shown; see `AppState::platform`.
+    platform_name: String,
+}
+
+#[derive(Template)]
+#[template(path = "pages/category.html", block = "fields")]
+struct CategoryFieldsTemplate {
+    fields: Vec<FieldData>,
+    fields_oob: bool,
+    filter: CategoryQuery,
+    hidden_count: usize,
+    platform_name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CategoryQuery {
+    /// `1` to list only the options the config sets or that have unsaved edits.
+    #[serde(default)]
+    pub modified: Option<String>,
+    /// Only options whose key or documentation contains this, ignoring case.
+    #[serde(default)]
+    pub q: Option<String>,
+}
+
+impl CategoryQuery {
+    fn modified_only(&self) -> bool {
+        matches!(self.modified.as_deref(), Some("1" | "true" | "on"))
+    }
+
+    fn search(&self) -> &str {
+        self.q.as_deref().unwrap_or_default().trim()
+    }
+
+    fn is_active(&self) -> bool {
+        self.modified_only() || !self.search().is_empty()
+    }
+
+    fn matches(&self, option: &ConfigOption, is_set: bool) -> bool {
+        let search = self.search().to_lowercase();
+        (is_set || !self.modified_only())
+            && (search.is_empty()
+                || option.key.to_lowercase().contains(&search)
+                || option.documentation.to_lowercase().contains(&search))
+    }
 }
 
 struct SidebarCategory {
@@ -74,80 +186,287 @@ struct SidebarCategory {
     active: bool,
 }
 
+/// The sidebar's category links, leaving out the categories of other platforms
+/// unless all are shown. `active` is the page's own category, if it has one.
+async fn sidebar_categories(state: &AppState, active: Option<&Category>) -> Vec<SidebarCategory> {
+    let mut categories = Vec::new();
+    for cat in Category::all() {
+        if active != Some(&cat) && !state.shows_platforms(cat.platform().as_slice()).await {
+            continue;
+        }
+        categories.push(SidebarCategory {
+            active: active == Some(&cat),
+            slug: cat.slug().to_string(),
+            name: cat.display_name().to_string(),
+            icon: cat.icon().to_string(),
+        });
+    }
+    categories
+}
+
 struct FieldData {
     key: String,
-    default_value: String,
+    /// Whether the config or one of its includes sets it.
+    is_set: bool,
+    /// The value the config sets; empty when it isn't set.
     current_value: String,
+    default_value: String,
+    /// `current_value`, or `default_value` when unset: what selects, sliders, and
+    /// color pickers show, since they can't show "nothing".
+    effective_value: String,
     documentation: String,
     value_type: String,
     is_modified: bool,
-    enum_options: Vec<String>,
+    enum_options: Vec<EnumVariant>,
     type_tag: String,
+    /// The team baseline's value when this key deviates from it (and isn't ignored).
+    baseline_value: Option<String>,
+    anchor: String,
+    docs_url: String,
+    /// For repeatable keys, the editable list of their values.
+    entries_html: Option<String>,
+    /// For durations and byte sizes, the value as an amount of `unit`, and the
+    /// units to pick from.
+    unit_amount: String,
+    unit: String,
+    unit_options: Vec<String>,
+    /// For percentages, the slider's lowest value.
+    slider_min: f64,
+    /// For colors, the `#rrggbb` the color picker shows.
+    color_hex: String,
+    /// For keybinds, a summary card linking into the keybinds editor.
+    keybind_summary: Option<String>,
+    /// See `render_pin_button`.
+    pin_button: String,
+    /// Whether it depends on the platform or another option; see `conditions.toml`.
+    has_conditions: bool,
+    /// Why it has no effect as things stand, e.g. "macOS only".
+    inactive_reason: Option<String>,
 }
 
+/// GET /category/:slug — one category's options; `?modified=1` and `?q=` narrow
+/// the list.
 pub async fn category(
     State(state): State<SharedState>,
     Path(slug): Path<String>,
+    Query(filter): Query<CategoryQuery>,
 ) -> Result<Html<String>, AppError> {
     let target_cat = Category::all()
         .into_iter()
         .find(|c| c.slug() == slug)
         .ok_or_else(|| AppError::Config(format!("Unknown category: {}", slug)))?;
 
+    let (fields, hidden_count) = category_fields(&state, &target_cat, &filter).await;
+
+    let categories = sidebar_categories(&state, Some(&target_cat)).await;
+
+    let tmpl = CategoryTemplate {
+        category_name: target_cat.display_name().to_string(),
+        category_slug: target_cat.slug().to_string(),
+        categories,
+        fields,
+        fields_oob: false,
+        filter,
+        show_all_platforms: state.settings.read().await.show_all_platforms,
+        hidden_count,
+        platform_name: state.platform.display_name().to_string(),
+    };
+
+    Ok(Html(tmpl.render().map_err(|e| {
+        AppError::Internal(anyhow::anyhow!("Template error: {}", e))
+    })?))
+}
+
+/// The fields of `category`'s page that pass `filter`, with their current values,
+/// and how many options were left out for being specific to another platform.
+async fn category_fields(
+    state: &AppState,
+    category: &Category,
+    filter: &CategoryQuery,
+) -> (Vec<FieldData>, usize) {
+    let ghostty = state.ghostty().await;
+    let options = ghostty.schema.options_for_category(category);
+    let count = options.len();
+    let shown = shown_options(state, options).await;
+    (
+        option_fields(state, &shown, filter).await,
+        count - shown.len(),
+    )
+}
+
+/// `options` without the ones for platforms that aren't shown.
+async fn shown_options<'a>(
+    state: &AppState,
+    options: Vec<&'a ConfigOption>,
+) -> Vec<&'a ConfigOption> {
+    let mut shown = Vec::with_capacity(options.len());
+    for option in options {
+        if state.shows_platforms(&option.conditions.platforms).await {
+            shown.push(option);
+        }
+    }
+    shown
+}
+
+/// Edit widgets for `options` that pass `filter`, with their current values.
+async fn option_fields(
+    state: &AppState,
+    options: &[&ConfigOption],
+    filter: &CategoryQuery,
+) -> Vec<FieldData> {
+    let inactive = inactive_options(state).await;
+    let ghostty = state.ghostty().await;
     let user_config = state.user_config.read().await;
-    let unsaved = state.unsaved.read().await;
-    let options = state.schema.options_for_category(&target_cat);
+    let included = state.included.read().await;
+    let unsaved = state.unsaved_keys().await;
+    let pinned = state.settings.read().await.pinned.clone();
+    let baseline_values: HashMap<String, String> = state
+        .baseline
+        .read()
+        .await
+        .as_ref()
+        .map(|baseline| {
+            baseline
+                .deviations(&user_config)
+                .into_iter()
+                .filter(|d| !baseline.ignored.contains(&d.key))
+                .map(|d| (d.key, d.old.join(", ")))
+                .collect()
+        })
+        .unwrap_or_default();
 
-    let fields: Vec<FieldData> = options
+    options
         .iter()
-        .filter(|o| !matches!(o.value_type, ConfigValueType::Keybind))
-        .map(|opt| {
-            let current = user_config.get(&opt.key).unwrap_or("").to_string();
+        .filter_map(|opt| {
+            let current = user_config
+                .get(&opt.key)
+                .or_else(|| included.iter().rev().find_map(|c| c.get(&opt.key)));
+            let is_set = current.is_some();
+            let current = current.unwrap_or("").to_string();
             let is_modified = unsaved.contains(&opt.key);
-            let display_value = if !current.is_empty() {
+            if !filter.matches(opt, is_set || is_modified) {
+                return None;
+            }
+            let effective_value = if is_set {
                 current.clone()
             } else {
                 opt.default_value.clone()
             };
 
             let enum_options = match &opt.value_type {
-                ConfigValueType::Enum(vals) => vals.clone(),
+                ConfigValueType::Enum(variants) => variants.clone(),
                 _ => Vec::new(),
             };
 
-            FieldData {
+            let names = |units: &[&str]| units.iter().map(|u| u.to_string()).collect();
+            let sizes = |units: &[(&str, u64)]| units.iter().map(|(u, _)| u.to_string()).collect();
+            let (split, unit_options): (Option<(String, &str)>, Vec<String>) = match &opt.value_type
+            {
+                ConfigValueType::Duration => (
+                    split_duration(&effective_value).map(|(a, u)| (a.to_string(), u)),
+                    sizes(DURATION_UNITS),
+                ),
+                ConfigValueType::ByteSize => (
+                    split_byte_size(&effective_value).map(|(a, u)| (a.to_string(), u)),
+                    sizes(BYTE_UNITS),
+                ),
+                ConfigValueType::AdjustMetric => (
+                    Adjustment::parse(&effective_value).map(|a| a.split()),
+                    names(ADJUSTMENT_UNITS),
+                ),
+                _ => (None, Vec::new()),
+            };
+            let (unit_amount, unit) = split
+                .map(|(amount, unit)| (amount, unit.to_string()))
+                .unwrap_or_default();
+            let color_hex = picker_hex(&effective_value).unwrap_or_else(|| "#000000".to_string());
+            let all_values = || -> Vec<String> {
+                std::iter::once(&*user_config)
+                    .chain(included.iter())
+                    .flat_map(|c| c.get_all(&opt.key))
+                    .map(String::from)
+                    .collect()
+            };
+            let is_keybind = matches!(opt.value_type, ConfigValueType::Keybind);
+
+            Some(FieldData {
                 key: opt.key.clone(),
+                is_set,
+                current_value: current,
                 default_value: opt.default_value.clone(),
-                current_value: display_value,
+                effective_value,
                 documentation: opt.documentation.clone(),
                 value_type: opt.value_type.to_string(),
                 is_modified,
                 enum_options,
                 type_tag: format!("{}", opt.value_type),
-            }
+                baseline_value: baseline_values.get(&opt.key).cloned(),
+                anchor: opt.anchor(),
+                docs_url: opt.docs_url(),
+                pin_button: render_pin_button(&opt.key, pinned.contains(&opt.key)),
+                entries_html: (opt.is_repeatable && !is_keybind)
+                    .then(|| render_entries(&opt.key, &all_values(), false)),
+                unit_amount,
+                unit,
+                unit_options,
+                slider_min: value_range(&opt.key).map_or(0.0, |(min, _)| min),
+                color_hex,
+                keybind_summary: is_keybind.then(|| {
+                    render_keybind_summary(
+                        &all_values(),
+                        &ghostty.default_keybinds,
+                        Platform::current(),
+                    )
+                }),
+                has_conditions: !opt.conditions.is_empty(),
+                inactive_reason: inactive.get(&opt.key).cloned(),
+            })
         })
-        .collect();
+        .collect()
+}
 
-    let categories: Vec<SidebarCategory> = Category::all()
-        .into_iter()
-        .map(|cat| SidebarCategory {
-            active: cat == target_cat,
-            slug: cat.slug().to_string(),
-            name: cat.display_name().to_string(),
-            icon: cat.icon().to_string(),
-        })
-        .collect();
+#[derive(Template)]
+#[template(path = "partials/field.html")]
+struct FieldTemplate {
+    field: FieldData,
+    oob: bool,
+}
 
-    let tmpl = CategoryTemplate {
-        category_name: target_cat.display_name().to_string(),
-        category_slug: target_cat.slug().to_string(),
-        categories,
-        fields,
+/// `key`'s field card as it now stands, as an OOB swap for after an edit. Empty for
+/// keys the schema doesn't know.
+pub(crate) async fn render_field_oob(state: &AppState, key: &str) -> Result<String, AppError> {
+    let ghostty = state.ghostty().await;
+    let Some(option) = ghostty.schema.find_option(key) else {
+        return Ok(String::new());
     };
+    let Some(field) = option_fields(state, &[option], &CategoryQuery::default())
+        .await
+        .pop()
+    else {
+        return Ok(String::new());
+    };
+    FieldTemplate { field, oob: true }
+        .render()
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Template error: {}", e)))
+}
 
-    Ok(Html(tmpl.render().map_err(|e| {
-        AppError::Internal(anyhow::anyhow!("Template error: {}", e))
-    })?))
+/// Just the field list of `category`'s page, unfiltered, as an OOB swap for after
+/// a change to many of its fields.
+pub(crate) async fn render_category_fields(
+    state: &AppState,
+    category: &Category,
+) -> Result<String, AppError> {
+    let filter = CategoryQuery::default();
+    let (fields, hidden_count) = category_fields(state, category, &filter).await;
+    let tmpl = CategoryFieldsTemplate {
+        fields,
+        fields_oob: true,
+        filter,
+        hidden_count,
+        platform_name: state.platform.display_name().to_string(),
+    };
+    tmpl.render()
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Template error: {}", e)))
 }
 
 #[derive(Template)]
@@ -155,54 +474,219 @@ pub async fn category(
 struct ThemesTemplate {
     categories: Vec<SidebarCategory>,
     themes: Vec<ThemeCardData>,
+    /// The theme, or both halves of a light/dark pair; see `ThemeSelection::describe`.
     current_theme: String,
     total_count: usize,
+    custom_count: usize,
+    /// Style for the `background-image` layer drawn over every card, or empty.
+    image_style: String,
+    /// Theme families with more than one variant.
+    families: Vec<ThemeFilterOption>,
+    tags: Vec<ThemeFilterOption>,
 }
 
 #[allow(dead_code)]
 struct ThemeCardData {
     name: String,
     background: String,
+    /// Theme background with `background-opacity` applied.
+    card_background: String,
     foreground: String,
     is_dark: bool,
     is_active: bool,
+    /// "Active", or "Light"/"Dark" for half of a pair; empty when not active.
+    active_label: String,
+    custom: bool,
+    /// From a user theme directory other than the editor's.
+    user: bool,
     palette_colors: Vec<String>,
+    tags: Vec<&'static str>,
+    /// The card's star; see `render_favorite_button`.
+    favorite_button: String,
+    /// See `render_contrast_warning`.
+    contrast_warning: String,
+}
+
+/// A theme family or tag offered as a filter on the themes page.
+struct ThemeFilterOption {
+    value: String,
+    label: String,
 }
 
 pub async fn themes_page(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let selection = ThemeSelection::parse(&state.value_of("theme").await.unwrap_or_default());
     let user_config = state.user_config.read().await;
-    let current_theme = user_config.get("theme").unwrap_or("").to_string();
+    let all_themes = state.themes.read().await;
+    let layers = resolve_background(&user_config, &all_themes);
+    let image_style = layers
+        .image
+        .as_ref()
+        .map(image_layer_style)
+        .unwrap_or_default();
+    let theme_state = state.theme_state.read().await;
 
-    let themes: Vec<ThemeCardData> = state
-        .themes
+    let themes: Vec<ThemeCardData> = all_themes
         .iter()
-        .map(|t| ThemeCardData {
-            name: t.name.clone(),
-            background: t.background.clone(),
-            foreground: t.foreground.clone(),
-            is_dark: t.is_dark,
-            is_active: t.name == current_theme,
-            palette_colors: t.palette[..8].to_vec(),
+        .map(|t| {
+            let active = active_label(&selection, &t.name);
+            ThemeCardData {
+                name: t.name.clone(),
+                background: t.background.clone(),
+                card_background: with_alpha(&t.background, layers.opacity),
+                foreground: t.foreground.clone(),
+                is_dark: t.is_dark,
+                is_active: active.is_some(),
+                active_label: active.unwrap_or_default().to_string(),
+                custom: t.custom,
+                user: t.origin == ThemeOrigin::User && !t.custom,
+                palette_colors: t.palette[..8].to_vec(),
+                tags: theme_tags(t).iter().map(ThemeTag::label).collect(),
+                favorite_button: render_favorite_button(
+                    &t.name,
+                    theme_state.favorites.contains(&t.name),
+                ),
+                contrast_warning: render_contrast_warning(t),
+            }
         })
         .collect();
-
-    let categories: Vec<SidebarCategory> = Category::all()
+    let families: Vec<ThemeFilterOption> = theme_families(&all_themes)
         .into_iter()
-        .map(|cat| SidebarCategory {
-            active: false,
-            slug: cat.slug().to_string(),
-            name: cat.display_name().to_string(),
-            icon: cat.icon().to_string(),
+        .map(|(family, count)| ThemeFilterOption {
+            label: format!("{} ({})", family, count),
+            value: family,
         })
         .collect();
+    let tags: Vec<ThemeFilterOption> = ThemeTag::all()
+        .iter()
+        .map(|t| ThemeFilterOption {
+            value: t.slug().to_string(),
+            label: t.label().to_string(),
+        })
+        .collect();
+
+    let categories = sidebar_categories(&state, None).await;
 
     let total_count = themes.len();
+    let custom_count = themes.iter().filter(|t| t.custom).count();
 
     let tmpl = ThemesTemplate {
         categories,
         themes,
-        current_theme,
+        current_theme: selection.describe(),
         total_count,
+        custom_count,
+        image_style,
+        families,
+        tags,
+    };
+
+    Ok(Html(tmpl.render().map_err(|e| {
+        AppError::Internal(anyhow::anyhow!("Template error: {}", e))
+    })?))
+}
+
+#[derive(Template)]
+#[template(path = "pages/theme_editor.html")]
+struct ThemeEditorTemplate {
+    categories: Vec<SidebarCategory>,
+    /// Name of the custom theme being edited; empty when creating one.
+    editing: String,
+    /// Theme the colors were copied from, if any.
+    base: String,
+    background: String,
+    foreground: String,
+    cursor_color: String,
+    selection_background: String,
+    palette: Vec<PaletteSlot>,
+    sample: String,
+}
+
+struct PaletteSlot {
+    index: usize,
+    label: &'static str,
+    color: String,
+}
+
+#[derive(Deserialize)]
+pub struct ThemeEditorQuery {
+    /// Edit this custom theme in place.
+    pub edit: Option<String>,
+    /// Start a new theme from this theme's colors.
+    pub base: Option<String>,
+}
+
+/// Starting colors for a theme built from scratch.
+const BLANK_PALETTE: [&str; 16] = [
+    "#1d1f21", "#cc6666", "#b5bd68", "#f0c674", "#81a2be", "#b294bb", "#8abeb7", "#c5c8c6",
+    "#666666", "#d54e53", "#b9ca4a", "#e7c547", "#7aa6da", "#c397d8", "#70c0b1", "#eaeaea",
+];
+
+/// GET /themes/new — the theme editor, for a new theme or (`?edit=`) a custom one.
+pub async fn theme_editor(
+    State(state): State<SharedState>,
+    Query(query): Query<ThemeEditorQuery>,
+) -> Result<Html<String>, AppError> {
+    let themes = state.themes.read().await;
+    let source_name = query.edit.as_deref().or(query.base.as_deref());
+    let source = source_name.and_then(|name| themes.iter().find(|t| t.name == name));
+    if let (Some(name), None) = (&query.edit, source.filter(|t| t.custom)) {
+        return Err(AppError::Config(format!(
+            "No custom theme named '{}'",
+            name
+        )));
+    }
+
+    let hex = |value: Option<&str>, fallback: &str| {
+        value
+            .and_then(normalize_hex)
+            .unwrap_or_else(|| fallback.to_string())
+    };
+    let palette = (0..16)
+        .map(|i| PaletteSlot {
+            index: i,
+            label: PALETTE_NAMES[i],
+            color: hex(
+                source.and_then(|t| t.palette.get(i)).map(String::as_str),
+                BLANK_PALETTE[i],
+            ),
+        })
+        .collect();
+
+    let background = hex(source.map(|t| t.background.as_str()), "#1d1f21");
+    let foreground = hex(source.map(|t| t.foreground.as_str()), "#c5c8c6");
+    let cursor_color = hex(source.and_then(|t| t.cursor_color.as_deref()), &foreground);
+    let selection_background = hex(
+        source.and_then(|t| t.selection_background.as_deref()),
+        "#373b41",
+    );
+
+    let sample = match source {
+        Some(theme) => render_theme_sample(theme),
+        None => render_theme_sample(&ThemeInfo {
+            name: String::new(),
+            background: background.clone(),
+            foreground: foreground.clone(),
+            palette: BLANK_PALETTE.iter().map(|c| c.to_string()).collect(),
+            is_dark: true,
+            cursor_color: Some(cursor_color.clone()),
+            selection_background: Some(selection_background.clone()),
+            custom: true,
+            origin: ThemeOrigin::User,
+        }),
+    };
+
+    let categories = sidebar_categories(&state, None).await;
+
+    let tmpl = ThemeEditorTemplate {
+        categories,
+        editing: query.edit.clone().unwrap_or_default(),
+        base: query.base.clone().unwrap_or_default(),
+        background,
+        foreground,
+        cursor_color,
+        selection_background,
+        palette,
+        sample,
     };
 
     Ok(Html(tmpl.render().map_err(|e| {
@@ -216,53 +700,72 @@ struct KeybindsTemplate {
     categories: Vec<SidebarCategory>,
     keybinds: Vec<KeybindData>,
     actions: Vec<String>,
+    presets_html: String,
 }
 
 struct KeybindData {
     trigger: String,
     action: String,
     is_custom: bool,
+    /// A custom binding that isn't in the files on disk yet.
+    is_unsaved: bool,
+    /// Fragment id of the row, which the category page's keybind summary links to.
+    anchor: String,
+    /// What the OS uses this trigger for, if it takes it before Ghostty.
+    os_conflict: Option<String>,
 }
 
 pub async fn keybinds_page(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
-    let user_config = state.user_config.read().await;
-    let custom_keybinds: Vec<&str> = user_config.get_all("keybind");
+    let custom_keybinds = state.all_values("keybind").await;
+    let saved_keybinds: Option<Vec<String>> = state
+        .unsaved
+        .read()
+        .await
+        .iter()
+        .find(|change| change.key == "keybind")
+        .map(|change| change.old.clone());
+    let ghostty = state.ghostty().await;
 
-    let mut keybinds: Vec<KeybindData> = state
+    let mut keybinds: Vec<KeybindData> = ghostty
         .default_keybinds
         .iter()
         .map(|kb| KeybindData {
             trigger: kb.trigger.clone(),
             action: kb.action.clone(),
             is_custom: false,
+            is_unsaved: false,
+            anchor: keybind_anchor(&kb.trigger),
+            os_conflict: None,
         })
         .collect();
 
     // Add custom keybinds from user config
+    let platform = Platform::current();
     for kb_str in &custom_keybinds {
         if let Some((trigger, action)) = kb_str.split_once('=') {
             keybinds.push(KeybindData {
                 trigger: trigger.trim().to_string(),
                 action: action.trim().to_string(),
                 is_custom: true,
+                is_unsaved: saved_keybinds
+                    .as_ref()
+                    .is_some_and(|saved| !saved.contains(kb_str)),
+                anchor: keybind_anchor(trigger),
+                os_conflict: os_conflicts(trigger, platform)
+                    .into_iter()
+                    .next()
+                    .map(|c| format!("{} ({})", c.action, platform.display_name())),
             });
         }
     }
 
-    let categories: Vec<SidebarCategory> = Category::all()
-        .into_iter()
-        .map(|cat| SidebarCategory {
-            active: false,
-            slug: cat.slug().to_string(),
-            name: cat.display_name().to_string(),
-            icon: cat.icon().to_string(),
-        })
-        .collect();
+    let categories = sidebar_categories(&state, None).await;
 
     let tmpl = KeybindsTemplate {
         categories,
         keybinds,
-        actions: state.actions.clone(),
+        actions: ghostty.actions.clone(),
+        presets_html: render_presets(&installed_everywhere(&state).await, None),
     };
 
     Ok(Html(tmpl.render().map_err(|e| {
@@ -287,22 +790,309 @@ pub async fn import_export_page(
         config_text.push_str(&format!("{} = {}\n", key, value));
     }
 
-    let categories: Vec<SidebarCategory> = Category::all()
+    let categories = sidebar_categories(&state, None).await;
+
+    let tmpl = ImportExportTemplate {
+        categories,
+        config_text,
+    };
+
+    Ok(Html(tmpl.render().map_err(|e| {
+        AppError::Internal(anyhow::anyhow!("Template error: {}", e))
+    })?))
+}
+
+#[derive(Template)]
+#[template(path = "pages/diff.html")]
+struct DiffTemplate {
+    categories: Vec<SidebarCategory>,
+    file_path: String,
+    unsaved_count: usize,
+    annotate_new_keys: bool,
+}
+
+pub async fn diff_page(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let file_path = state
+        .user_config
+        .read()
+        .await
+        .file_path
+        .display()
+        .to_string();
+    let unsaved_count = state.unsaved_count().await;
+
+    let categories = sidebar_categories(&state, None).await;
+
+    let tmpl = DiffTemplate {
+        categories,
+        file_path,
+        unsaved_count,
+        annotate_new_keys: state.settings.read().await.annotate_new_keys,
+    };
+
+    Ok(Html(tmpl.render().map_err(|e| {
+        AppError::Internal(anyhow::anyhow!("Template error: {}", e))
+    })?))
+}
+
+#[derive(Template)]
+#[template(path = "pages/editor.html")]
+struct EditorTemplate {
+    categories: Vec<SidebarCategory>,
+    file_path: String,
+    text: String,
+    unsaved_count: usize,
+    /// Problems and restore options while in safe mode, as HTML.
+    safe_mode_banner: String,
+}
+
+pub async fn editor_page(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let (file_path, mut text) = {
+        let user_config = state.user_config.read().await;
+        (
+            user_config.file_path.display().to_string(),
+            render_config(&user_config),
+        )
+    };
+    let safe_mode_banner = render_banner(&state).await;
+    if !safe_mode_banner.is_empty() {
+        // The loaded config is a blank stand-in; show what is actually on disk.
+        let bytes = std::fs::read(&file_path)?;
+        text = String::from_utf8_lossy(&bytes).replace('\0', "");
+    }
+    let unsaved_count = state.unsaved_count().await;
+
+    let categories = sidebar_categories(&state, None).await;
+
+    let tmpl = EditorTemplate {
+        categories,
+        file_path,
+        text,
+        unsaved_count,
+        safe_mode_banner,
+    };
+
+    Ok(Html(tmpl.render().map_err(|e| {
+        AppError::Internal(anyhow::anyhow!("Template error: {}", e))
+    })?))
+}
+
+#[derive(Template)]
+#[template(path = "pages/merge.html")]
+struct MergeTemplate {
+    categories: Vec<SidebarCategory>,
+    file_path: String,
+}
+
+pub async fn merge_page(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let file_path = state
+        .user_config
+        .read()
+        .await
+        .file_path
+        .display()
+        .to_string();
+
+    let categories = sidebar_categories(&state, None).await;
+
+    let tmpl = MergeTemplate {
+        categories,
+        file_path,
+    };
+
+    Ok(Html(tmpl.render().map_err(|e| {
+        AppError::Internal(anyhow::anyhow!("Template error: {}", e))
+    })?))
+}
+
+/// A terminal on the migration page, with where its config was found.
+struct MigrateSourceOption {
+    slug: String,
+    name: String,
+    hint: String,
+    /// The config file found in its usual place; empty when there is none.
+    detected: String,
+}
+
+#[derive(Template)]
+#[template(path = "pages/migrate.html")]
+struct MigrateTemplate {
+    categories: Vec<SidebarCategory>,
+    sources: Vec<MigrateSourceOption>,
+}
+
+pub async fn migrate_page(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let categories = sidebar_categories(&state, None).await;
+
+    let sources = MigrateSource::all()
         .into_iter()
-        .map(|cat| SidebarCategory {
-            active: false,
-            slug: cat.slug().to_string(),
-            name: cat.display_name().to_string(),
-            icon: cat.icon().to_string(),
+        .map(|source| MigrateSourceOption {
+            slug: source.slug().to_string(),
+            name: source.display_name().to_string(),
+            hint: source.hint().to_string(),
+            detected: source
+                .find_config()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
         })
         .collect();
 
-    let tmpl = ImportExportTemplate {
+    let tmpl = MigrateTemplate {
         categories,
-        config_text,
+        sources,
     };
 
     Ok(Html(tmpl.render().map_err(|e| {
         AppError::Internal(anyhow::anyhow!("Template error: {}", e))
     })?))
 }
+
+#[derive(Template)]
+#[template(path = "pages/baseline.html")]
+struct BaselineTemplate {
+    categories: Vec<SidebarCategory>,
+    /// Path or URL of the current baseline; empty when none is set.
+    source: String,
+}
+
+pub async fn baseline_page(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let source = state
+        .baseline
+        .read()
+        .await
+        .as_ref()
+        .map(|b| b.source.to_string())
+        .unwrap_or_default();
+
+    let categories = sidebar_categories(&state, None).await;
+
+    let tmpl = BaselineTemplate { categories, source };
+
+    Ok(Html(tmpl.render().map_err(|e| {
+        AppError::Internal(anyhow::anyhow!("Template error: {}", e))
+    })?))
+}
+
+#[derive(Template)]
+#[template(path = "pages/profiles.html")]
+struct ProfilesTemplate {
+    categories: Vec<SidebarCategory>,
+    /// Where activating a profile backs up the current config file.
+    backup_path: String,
+}
+
+pub async fn profiles_page(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let backup_path = format!("{}.bak", state.user_config.read().await.file_path.display());
+
+    let categories = sidebar_categories(&state, None).await;
+
+    let tmpl = ProfilesTemplate {
+        categories,
+        backup_path,
+    };
+
+    Ok(Html(tmpl.render().map_err(|e| {
+        AppError::Internal(anyhow::anyhow!("Template error: {}", e))
+    })?))
+}
+
+#[derive(Template)]
+#[template(path = "pages/welcome.html")]
+struct WelcomeTemplate {
+    categories: Vec<SidebarCategory>,
+    config_path: String,
+    light_themes: Vec<String>,
+    dark_themes: Vec<String>,
+    /// Preselected themes; see `suggested_theme`.
+    light_theme: String,
+    dark_theme: String,
+    cursor_styles: Vec<String>,
+    paddings: Vec<(&'static str, &'static str)>,
+    presets: Vec<&'static KeybindPreset>,
+}
+
+/// Themes offered first by the wizard, when installed.
+const SUGGESTED_LIGHT_THEMES: &[&str] =
+    &["Catppuccin Latte", "GitHub Light Default", "Builtin Light"];
+const SUGGESTED_DARK_THEMES: &[&str] = &["Catppuccin Mocha", "GitHub Dark Default", "Builtin Dark"];
+
+/// The first of `suggested` among `names`, or else the first of `names`.
+fn suggested_theme(names: &[String], suggested: &[&str]) -> String {
+    suggested
+        .iter()
+        .find(|s| names.iter().any(|n| n == *s))
+        .map(|s| s.to_string())
+        .or_else(|| names.first().cloned())
+        .unwrap_or_default()
+}
+
+/// GET /welcome — the first-run wizard: a few questions that make a commented
+/// starter config. Only offered while there's no config; see `is_first_run`.
+pub async fn welcome_page(State(state): State<SharedState>) -> Result<Response, AppError> {
+    if !is_first_run(&state).await {
+        return Ok(Redirect::to("/").into_response());
+    }
+    let categories = sidebar_categories(&state, None).await;
+    let (light_themes, dark_themes) = {
+        let themes = state.themes.read().await;
+        let names = |dark: bool| -> Vec<String> {
+            themes
+                .iter()
+                .filter(|t| t.is_dark == dark)
+                .map(|t| t.name.clone())
+                .collect()
+        };
+        (names(false), names(true))
+    };
+    let cursor_styles = match state
+        .ghostty()
+        .await
+        .schema
+        .find_option("cursor-style")
+        .map(|o| &o.value_type)
+    {
+        Some(ConfigValueType::Enum(variants)) => variants.iter().map(|v| v.value.clone()).collect(),
+        _ => Vec::new(),
+    };
+
+    let tmpl = WelcomeTemplate {
+        categories,
+        config_path: state
+            .user_config
+            .read()
+            .await
+            .file_path
+            .display()
+            .to_string(),
+        light_theme: suggested_theme(&light_themes, SUGGESTED_LIGHT_THEMES),
+        dark_theme: suggested_theme(&dark_themes, SUGGESTED_DARK_THEMES),
+        light_themes,
+        dark_themes,
+        cursor_styles,
+        paddings: Padding::ALL.iter().map(|p| (p.slug(), p.label())).collect(),
+        presets: PRESETS.iter().collect(),
+    };
+
+    Ok(Html(
+        tmpl.render()
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Template error: {}", e)))?,
+    )
+    .into_response())
+}
+
+#[derive(Template)]
+#[template(path = "pages/history.html")]
+struct HistoryTemplate {
+    categories: Vec<SidebarCategory>,
+}
+
+/// GET /history — the change journal, with a revert button per edit.
+pub async fn history_page(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let categories = sidebar_categories(&state, None).await;
+
+    let tmpl = HistoryTemplate { categories };
+
+    Ok(Html(tmpl.render().map_err(|e| {
+        AppError::Internal(anyhow::anyhow!("Template error: {}", e))
+    })?))
+}
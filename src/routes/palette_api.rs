@@ -0,0 +1,487 @@
+use std::collections::HashMap;
+
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::config_api::{escape_html, publish_toast, toast_html};
+use super::preview_api::default_palette_color;
+use crate::app_state::SharedState;
+use crate::config::background::active_theme;
+use crate::config::color_names::picker_hex;
+use crate::config::custom_theme::{hex_to_rgb, normalize_hex, PALETTE_NAMES};
+use crate::config::palette::{
+    ansi_palette, parse_palette_entry, remove_palette_entry, set_palette_entry, PALETTE_SIZE,
+};
+use crate::config::palette_generator::{
+    dominant_colors, palette_from_colors, palette_from_seed, Appearance, GeneratedPalette,
+};
+use crate::error::AppError;
+
+/// How many colors an image is reduced to before a palette is built from them.
+const IMAGE_COLORS: usize = 8;
+/// Images are shrunk to fit this many pixels a side before their colors are counted.
+const IMAGE_SAMPLE_SIZE: u32 = 96;
+
+/// One of the 16 ANSI slots as the grid shows it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GridSlot {
+    index: usize,
+    /// What the config sets, if anything.
+    explicit: Option<String>,
+    /// What applies without an explicit entry: the theme's color, or Ghostty's default.
+    inherited: String,
+}
+
+impl GridSlot {
+    /// The color to show, as `#rrggbb` for the color picker.
+    fn color(&self) -> String {
+        self.explicit
+            .as_deref()
+            .and_then(picker_hex)
+            .unwrap_or_else(|| self.inherited.clone())
+    }
+}
+
+/// The ANSI slots with their explicit and inherited colors.
+async fn grid_slots(state: &SharedState) -> Vec<GridSlot> {
+    let values = state.all_values("palette").await;
+    let explicit = ansi_palette(&values.iter().map(String::as_str).collect::<Vec<_>>());
+    let user_config = state.user_config.read().await;
+    let themes = state.themes.read().await;
+    let theme = active_theme(&user_config, &themes);
+    explicit
+        .into_iter()
+        .enumerate()
+        .map(|(index, explicit)| GridSlot {
+            index,
+            explicit,
+            inherited: theme
+                .and_then(|t| t.palette.get(index))
+                .and_then(|c| normalize_hex(c))
+                .unwrap_or_else(|| default_palette_color(index)),
+        })
+        .collect()
+}
+
+/// GET /api/palette — the 16-color grid for the colors page.
+#[utoipa::path(
+    get,
+    path = "/api/palette",
+    tag = "Themes",
+    summary = "The 16-color palette grid",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn grid(State(state): State<SharedState>) -> Html<String> {
+    Html(render_grid(&grid_slots(&state).await))
+}
+
+/// PUT /api/palette — save the whole grid at once, from fields `c0`..`c15`.
+///
+/// Only slots that differ from what they inherit become explicit entries, so
+/// submitting the grid untouched doesn't pin the theme's colors into the config.
+#[utoipa::path(
+    put,
+    path = "/api/palette",
+    tag = "Themes",
+    summary = "Set all 16 palette colors from fields `c0`..`c15`",
+    request_body(content = HashMap<String, String>, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn update_grid(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<HashMap<String, String>>,
+) -> Result<Html<String>, AppError> {
+    let slots = grid_slots(&state).await;
+    let mut changes: Vec<(usize, String)> = Vec::new();
+    for slot in &slots {
+        let Some(value) = form.get(&format!("c{}", slot.index)) else {
+            continue;
+        };
+        let Some(color) = normalize_hex(value) else {
+            return Ok(Html(toast_html(
+                &format!(
+                    "Invalid color for {}: {}",
+                    PALETTE_NAMES[slot.index],
+                    escape_html(value)
+                ),
+                true,
+            )));
+        };
+        let unchanged = match &slot.explicit {
+            Some(explicit) => picker_hex(explicit).as_deref() == Some(color.as_str()),
+            None => color == slot.inherited,
+        };
+        if !unchanged {
+            changes.push((slot.index, color));
+        }
+    }
+    if changes.is_empty() {
+        return Ok(Html(toast_html("No palette changes", false)));
+    }
+
+    state
+        .edit_key("palette", |config| {
+            for (index, color) in &changes {
+                set_palette_entry(config, *index, color);
+            }
+        })
+        .await?;
+    state.mark_unsaved().await;
+    let message = match changes.len() {
+        1 => "Updated 1 palette color (unsaved)".to_string(),
+        n => format!("Updated {} palette colors (unsaved)", n),
+    };
+    Ok(Html(publish_toast(&state, &message, false)))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaletteSlotJson {
+    pub index: usize,
+    /// The explicit color in the config, or `null` when the slot is inherited.
+    pub color: Option<String>,
+}
+
+fn check_index(index: usize) -> Result<(), AppError> {
+    if index < PALETTE_SIZE {
+        Ok(())
+    } else {
+        Err(AppError::Config(format!(
+            "Palette index {} is out of range (0-{})",
+            index,
+            PALETTE_SIZE - 1
+        )))
+    }
+}
+
+/// GET /api/palette/:index — the explicit color of one slot.
+#[utoipa::path(
+    get,
+    path = "/api/palette/{index}",
+    tag = "Themes",
+    summary = "The explicit color of one palette slot (0-255)",
+    params(("index" = usize, Path)),
+    responses((status = 200, description = "OK", body = PaletteSlotJson)),
+)]
+pub async fn get_slot(
+    State(state): State<SharedState>,
+    Path(index): Path<usize>,
+) -> Result<Json<PaletteSlotJson>, AppError> {
+    check_index(index)?;
+    let color = state
+        .all_values("palette")
+        .await
+        .iter()
+        .rev()
+        .filter_map(|v| parse_palette_entry(v))
+        .find(|(i, _)| *i == index)
+        .map(|(_, color)| color);
+    Ok(Json(PaletteSlotJson { index, color }))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SlotForm {
+    pub color: String,
+}
+
+/// PUT /api/palette/:index — set one slot, leaving the other palette entries alone.
+#[utoipa::path(
+    put,
+    path = "/api/palette/{index}",
+    tag = "Themes",
+    summary = "Set one palette slot",
+    params(("index" = usize, Path)),
+    request_body(content = SlotForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn update_slot(
+    State(state): State<SharedState>,
+    Path(index): Path<usize>,
+    axum::Form(form): axum::Form<SlotForm>,
+) -> Result<Html<String>, AppError> {
+    check_index(index)?;
+    let Some(color) = normalize_hex(&form.color) else {
+        return Ok(Html(toast_html(
+            &format!("Invalid color: {}", escape_html(&form.color)),
+            true,
+        )));
+    };
+    state
+        .edit_key("palette", |config| set_palette_entry(config, index, &color))
+        .await?;
+    state.mark_unsaved().await;
+    Ok(Html(publish_toast(
+        &state,
+        &format!("Set palette {} to {} (unsaved)", index, color),
+        false,
+    )))
+}
+
+/// DELETE /api/palette/:index — drop the slot's explicit color so the theme's applies.
+#[utoipa::path(
+    delete,
+    path = "/api/palette/{index}",
+    tag = "Themes",
+    summary = "Drop a slot's explicit color",
+    params(("index" = usize, Path)),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn reset_slot(
+    State(state): State<SharedState>,
+    Path(index): Path<usize>,
+) -> Result<Html<String>, AppError> {
+    check_index(index)?;
+    let mut removed = remove_palette_entry(&mut *state.user_config.write().await, index);
+    for config in state.included.write().await.iter_mut() {
+        removed |= remove_palette_entry(config, index);
+    }
+    if !removed {
+        return Ok(Html(toast_html(
+            &format!("Palette {} isn't set", index),
+            true,
+        )));
+    }
+    state.mark_unsaved().await;
+    Ok(Html(publish_toast(
+        &state,
+        &format!("Reset palette {} (unsaved)", index),
+        false,
+    )))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GenerateQuery {
+    /// The seed color, for `GET`.
+    #[serde(default)]
+    pub seed: Option<String>,
+    /// `dark` or `light`. From a seed it defaults to dark; from an image it
+    /// follows the image's main color.
+    #[serde(default)]
+    pub appearance: Option<String>,
+    /// `json` for the bulk edit that applies the palette instead of its preview.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+impl GenerateQuery {
+    fn appearance(&self) -> Result<Option<Appearance>, AppError> {
+        match self.appearance.as_deref().map(str::trim) {
+            None | Some("") | Some("auto") => Ok(None),
+            Some("dark") => Ok(Some(Appearance::Dark)),
+            Some("light") => Ok(Some(Appearance::Light)),
+            Some(other) => Err(AppError::Config(format!(
+                "Unknown appearance '{}': expected dark or light",
+                other
+            ))),
+        }
+    }
+}
+
+/// GET /api/palette/generate — a background, foreground, and 16-color palette
+/// built around the `seed` color, for review before applying it.
+#[utoipa::path(
+    get,
+    path = "/api/palette/generate",
+    tag = "Themes",
+    summary = "A background, foreground, and 16 ANSI colors built around the `seed` color, `appearance=dark` or `light`; `format=json` for the `PUT /api/config` body that applies them",
+    params(GenerateQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn generate(Query(query): Query<GenerateQuery>) -> Result<Response, AppError> {
+    let seed = query.seed.as_deref().unwrap_or_default();
+    let rgb = hex_to_rgb(seed)
+        .ok_or_else(|| AppError::Config(format!("Invalid seed color: '{}'", seed)))?;
+    let palette = palette_from_seed(rgb, query.appearance()?.unwrap_or_default());
+    Ok(generated_response(&palette, &query))
+}
+
+/// POST /api/palette/generate/image — the same from the dominant colors of the
+/// image in the body, e.g. a wallpaper.
+#[utoipa::path(
+    post,
+    path = "/api/palette/generate/image",
+    tag = "Themes",
+    summary = "The same from the dominant colors of the PNG, JPEG, GIF, or WebP image in the body; the appearance follows the image unless given",
+    params(GenerateQuery),
+    request_body(content_type = "application/octet-stream", description = "A PNG, JPEG, GIF, or WebP image"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn generate_from_image(
+    Query(query): Query<GenerateQuery>,
+    body: Bytes,
+) -> Result<Response, AppError> {
+    let appearance = query.appearance()?;
+    let colors = tokio::task::spawn_blocking(move || image_colors(&body))
+        .await
+        .map_err(anyhow::Error::from)??;
+    let palette = palette_from_colors(&colors, appearance)
+        .ok_or_else(|| AppError::Config("The image has no pixels".to_string()))?;
+    Ok(generated_response(&palette, &query))
+}
+
+/// The most prominent colors of an encoded PNG, JPEG, GIF, or WebP image.
+fn image_colors(bytes: &[u8]) -> Result<Vec<(u8, u8, u8)>, AppError> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| AppError::Config(format!("Couldn't read the image: {}", e)))?;
+    let pixels: Vec<(u8, u8, u8)> = image
+        .thumbnail(IMAGE_SAMPLE_SIZE, IMAGE_SAMPLE_SIZE)
+        .to_rgb8()
+        .pixels()
+        .map(|p| (p[0], p[1], p[2]))
+        .collect();
+    Ok(dominant_colors(&pixels, IMAGE_COLORS))
+}
+
+/// The `PUT /api/config` body that applies `palette`.
+fn bulk_edit(palette: &GeneratedPalette) -> serde_json::Value {
+    serde_json::json!({
+        "set": {
+            "background": palette.background,
+            "foreground": palette.foreground,
+            "palette": palette.palette_entries(),
+        }
+    })
+}
+
+fn generated_response(palette: &GeneratedPalette, query: &GenerateQuery) -> Response {
+    if query.format.as_deref() == Some("json") {
+        return Json(bulk_edit(palette)).into_response();
+    }
+    Html(render_generated(palette)).into_response()
+}
+
+/// A sample of the generated colors, with a button that applies them through
+/// the bulk edit endpoint.
+fn render_generated(palette: &GeneratedPalette) -> String {
+    let mut rows = String::new();
+    for row in palette.palette.chunks(8) {
+        rows.push_str(r#"<div class="flex gap-1">"#);
+        for color in row {
+            rows.push_str(&format!(
+                r#"<span class="w-8 h-8 rounded" style="background:{color}" title="{color}"></span>"#,
+                color = escape_html(color),
+            ));
+        }
+        rows.push_str("</div>");
+    }
+    let mut sample = String::new();
+    for color in &palette.palette[1..7] {
+        sample.push_str(&format!(
+            r#"<span style="color:{}">&#9632;&#9632;</span> "#,
+            escape_html(color)
+        ));
+    }
+    format!(
+        r#"<div class="flex flex-wrap items-start gap-4">
+<div class="rounded-lg p-3 font-mono text-xs space-y-2" style="background:{background};color:{foreground}"><div>$ ls ~/projects</div><div>{sample}</div>{rows}</div>
+<div class="space-y-2"><p class="text-xs font-mono text-gray-500">background {background}<br>foreground {foreground}</p>
+<button type="button" data-bulk-edit="{edit}" onclick="applyGeneratedPalette(this)" class="px-3 py-1.5 text-sm font-medium text-white bg-indigo-600 rounded-lg hover:bg-indigo-700 transition-colors">Apply</button>
+<p id="palette-generated-status" class="text-xs text-gray-500" role="status"></p></div>
+</div>"#,
+        background = escape_html(&palette.background),
+        foreground = escape_html(&palette.foreground),
+        sample = sample,
+        rows = rows,
+        edit = escape_html(&bulk_edit(palette).to_string()),
+    )
+}
+
+fn render_grid(slots: &[GridSlot]) -> String {
+    let mut html = String::from(
+        r##"<form hx-put="/api/palette" hx-target="#toast-container" hx-swap="innerHTML" hx-on::after-request="if(event.detail.successful) htmx.trigger('#palette-editor', 'refresh')" class="bg-white rounded-xl border border-gray-200 p-5 mb-6">
+<div class="flex items-center justify-between mb-3"><h3 class="text-sm font-semibold text-gray-900">Palette</h3><button type="submit" class="px-3 py-1.5 text-sm font-medium text-white bg-indigo-600 rounded-lg hover:bg-indigo-700 transition-colors">Save palette</button></div>
+<div class="grid grid-cols-4 md:grid-cols-8 gap-3">"##,
+    );
+    for slot in slots {
+        let color = slot.color();
+        let (state_label, reset) = match &slot.explicit {
+            Some(_) => (
+                "set",
+                format!(
+                    r##"<button type="button" hx-delete="/api/palette/{index}" hx-target="#toast-container" hx-swap="innerHTML" hx-on::after-request="if(event.detail.successful) htmx.trigger('#palette-editor', 'refresh')" aria-label="Reset {name}" class="text-gray-400 hover:text-red-500 transition-colors">&times;</button>"##,
+                    index = slot.index,
+                    name = PALETTE_NAMES[slot.index],
+                ),
+            ),
+            None => ("theme", String::new()),
+        };
+        html.push_str(&format!(
+            r#"<div class="flex flex-col items-center gap-1"><input type="color" id="palette-{index}" name="c{index}" value="{color}" aria-label="{name}" class="w-10 h-10 rounded cursor-pointer border border-gray-300"><label for="palette-{index}" class="text-xs text-gray-700">{index} {name}</label><span class="text-xs font-mono text-gray-400">{state_label}{reset}</span></div>"#,
+            index = slot.index,
+            color = escape_html(&color),
+            name = PALETTE_NAMES[slot.index],
+            state_label = state_label,
+            reset = reset,
+        ));
+    }
+    html.push_str("</div></form>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_grid() {
+        let slots: Vec<GridSlot> = (0..16)
+            .map(|index| GridSlot {
+                index,
+                explicit: (index == 1).then(|| "#FF0000".to_string()),
+                inherited: default_palette_color(index),
+            })
+            .collect();
+        let html = render_grid(&slots);
+        assert_eq!(html.matches(r#"type="color""#).count(), 16);
+        assert!(html.contains(r##"name="c1" value="#ff0000""##));
+        assert!(html.contains(r#"hx-delete="/api/palette/1""#));
+        assert!(!html.contains(r#"hx-delete="/api/palette/0""#));
+    }
+
+    #[test]
+    fn test_render_generated() {
+        let palette = palette_from_seed((0x2e, 0x7d, 0xe9), Appearance::Dark);
+        let html = render_generated(&palette);
+        assert_eq!(html.matches(r#"class="w-8 h-8 rounded""#).count(), 16);
+        assert!(html.contains(&format!("background:{}", palette.background)));
+        // The bulk edit is attribute-escaped JSON.
+        assert!(html.contains(&format!(
+            r#"data-bulk-edit="{{&quot;set&quot;:{{&quot;background&quot;:&quot;{}&quot;"#,
+            palette.background
+        )));
+    }
+
+    #[test]
+    fn test_image_colors() {
+        let mut image = image::RgbImage::from_pixel(40, 20, image::Rgb([12, 24, 48]));
+        for x in 30..40 {
+            for y in 0..20 {
+                image.put_pixel(x, y, image::Rgb([220, 80, 40]));
+            }
+        }
+        let mut png = Vec::new();
+        image
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let colors = image_colors(&png).unwrap();
+        assert_eq!(colors[0], (12, 24, 48));
+        // Shrinking blends the edge between them a little.
+        let (r, g, b) = colors[1];
+        assert!(
+            r > 210 && (70..90).contains(&g) && b < 50,
+            "{:?}",
+            colors[1]
+        );
+        assert!(image_colors(b"not an image").is_err());
+    }
+
+    #[test]
+    fn test_check_index() {
+        assert!(check_index(255).is_ok());
+        assert!(check_index(256).is_err());
+    }
+}
@@ -0,0 +1,71 @@
+use axum::extract::State;
+use axum::response::Html;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use super::config_api::escape_html;
+use crate::app_state::SharedState;
+use crate::error::AppError;
+
+/// The pin on an option's field, which swaps itself on toggle.
+pub(crate) fn render_pin_button(key: &str, pinned: bool) -> String {
+    let key = escape_html(key);
+    let (icon, class, label) = if pinned {
+        ("&#x2605;", "text-amber-500", "Unpin")
+    } else {
+        ("&#x2606;", "text-gray-300 hover:text-amber-500", "Pin")
+    };
+    format!(
+        r##"<button type="button" name="key" value="{key}" class="text-sm leading-none {class}" aria-pressed="{pinned}" aria-label="{label} {key}" title="{label} on the home page" hx-post="/api/pins" hx-swap="outerHTML">{icon}</button>"##
+    )
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PinForm {
+    pub key: String,
+}
+
+/// POST /api/pins — pin an option to "My settings" on the index page, or unpin
+/// it, answering with its new pin.
+#[utoipa::path(
+    post,
+    path = "/api/pins",
+    tag = "Config",
+    summary = "Pin an option (`key` field) to the index page, or unpin it",
+    request_body(content = PinForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn toggle_pin(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<PinForm>,
+) -> Result<Html<String>, AppError> {
+    if state
+        .ghostty()
+        .await
+        .schema
+        .find_option(&form.key)
+        .is_none()
+    {
+        return Err(AppError::Config(format!(
+            "Unknown config key: {}",
+            form.key
+        )));
+    }
+    let mut settings = state.settings.write().await;
+    let pinned = settings.toggle_pin(&form.key);
+    settings.save(&state.settings_path)?;
+    Ok(Html(render_pin_button(&form.key, pinned)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_pin_button() {
+        let html = render_pin_button("font-size", false);
+        assert!(html.contains(r#"value="font-size""#));
+        assert!(html.contains(r#"aria-pressed="false""#));
+        assert!(render_pin_button("theme", true).contains(r#"aria-label="Unpin theme""#));
+    }
+}
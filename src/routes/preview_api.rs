@@ -1,81 +1,821 @@
-use axum::extract::State;
-use axum::response::Html;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::Ordering;
 
+use axum::extract::{Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+
+use super::config_api::escape_html;
 use crate::app_state::SharedState;
+use crate::config::background::{active_theme, resolve_background, with_alpha, BackgroundImage};
+use crate::config::color_vision::{confusable_pairs, describe_pairs, simulate_value, ColorVision};
+use crate::config::model::{ThemeInfo, UserConfig};
 use crate::error::AppError;
+use crate::file_browser::image_content_type;
 
-/// GET /api/preview — return a terminal preview HTML partial.
-pub async fn preview_data(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
-    let user_config = state.user_config.read().await;
+/// Checkerboard shown behind a translucent window so `background-opacity` is visible.
+const TRANSPARENCY_GRID: &str =
+    "background: repeating-conic-gradient(#9ca3af 0% 25%, #e5e7eb 0% 50%) 50% / 16px 16px;";
+
+#[derive(Deserialize)]
+pub struct PreviewQuery {
+    #[serde(default)]
+    pub sample: Option<String>,
+    /// A theme to audition: the preview shows it as if `theme` were set to it,
+    /// without touching the config.
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// A color vision deficiency to simulate: `protanopia`, `deuteranopia`, or
+    /// `tritanopia`.
+    #[serde(default)]
+    pub cvd: Option<String>,
+}
+
+impl PreviewQuery {
+    fn color_vision(&self) -> Result<Option<ColorVision>, AppError> {
+        match self.cvd.as_deref() {
+            None | Some("") | Some("none") => Ok(None),
+            Some(slug) => ColorVision::parse(slug).map(Some).ok_or_else(|| {
+                AppError::Config(format!(
+                    "Unknown color vision deficiency: {}; expected protanopia, deuteranopia, or tritanopia",
+                    slug
+                ))
+            }),
+        }
+    }
+}
+
+/// A canned scene the static preview can show.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Sample {
+    Shell,
+    Code,
+    Vim,
+    Htop,
+    Ansi,
+}
+
+impl Sample {
+    const ALL: [Sample; 5] = [
+        Sample::Shell,
+        Sample::Code,
+        Sample::Vim,
+        Sample::Htop,
+        Sample::Ansi,
+    ];
+
+    fn slug(self) -> &'static str {
+        match self {
+            Sample::Shell => "shell",
+            Sample::Code => "code",
+            Sample::Vim => "vim",
+            Sample::Htop => "htop",
+            Sample::Ansi => "ansi",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Sample::Shell => "Shell",
+            Sample::Code => "Code",
+            Sample::Vim => "Vim",
+            Sample::Htop => "htop",
+            Sample::Ansi => "ANSI colors",
+        }
+    }
+
+    /// The sample named `slug`, or the shell for anything else.
+    fn parse(slug: Option<&str>) -> Sample {
+        Sample::ALL
+            .into_iter()
+            .find(|s| Some(s.slug()) == slug)
+            .unwrap_or(Sample::Shell)
+    }
+}
 
-    let bg = user_config.get("background").unwrap_or("#1e1e2e");
-    let fg = user_config.get("foreground").unwrap_or("#cdd6f4");
-    let cursor_color = user_config.get("cursor-color").unwrap_or("#f5e0dc");
+/// GET /api/preview?sample=&theme=&cvd= — return a terminal preview HTML partial
+/// showing one of the canned scenes: `shell` (the default), `code`, `vim`, `htop`,
+/// or `ansi`. With `theme`, that theme stands in for the configured one; explicit
+/// color settings still apply on top, as they would in Ghostty. With `cvd`, every
+/// color is shown as someone with that color vision deficiency sees it, and
+/// accents that become hard to tell apart are listed.
+pub async fn preview_data(
+    State(state): State<SharedState>,
+    Query(query): Query<PreviewQuery>,
+) -> Result<Html<String>, AppError> {
+    let sample = Sample::parse(query.sample.as_deref());
+    let vision = query.color_vision()?;
+    // A color as the simulated eye sees it; values that aren't colors pass through.
+    let seen = |value: &str| {
+        vision
+            .and_then(|v| simulate_value(value, v))
+            .unwrap_or_else(|| value.to_string())
+    };
+    let themes = state.themes.read().await;
+    let current = state.user_config.read().await;
+    let auditioned;
+    let user_config = match &query.theme {
+        Some(name) => {
+            if !themes.iter().any(|t| &t.name == name) {
+                return Err(AppError::Config(format!("Unknown theme: {}", name)));
+            }
+            let mut config = current.clone();
+            config.set("theme", name);
+            auditioned = config;
+            &auditioned
+        }
+        None => &*current,
+    };
+    let theme = active_theme(user_config, &themes);
+    let layers = resolve_background(user_config, &themes);
+
+    let bg = with_alpha(&seen(&layers.color), layers.opacity);
+    let fg = seen(
+        user_config
+            .get("foreground")
+            .or(theme.map(|t| t.foreground.as_str()))
+            .unwrap_or("#cdd6f4"),
+    );
+    let cursor_color = seen(
+        user_config
+            .get("cursor-color")
+            .or(theme.and_then(|t| t.cursor_color.as_deref()))
+            .unwrap_or("#f5e0dc"),
+    );
     let font_family = user_config.get("font-family").unwrap_or("monospace");
     let font_size = user_config.get("font-size").unwrap_or("13");
 
     // Get palette colors for ANSI preview
-    let palette_colors: Vec<String> = (0..16)
-        .map(|i| {
-            let key = "palette".to_string();
-            // Check for palette = i=#color entries
-            for val in user_config.get_all(&key) {
-                if let Some((idx_str, color)) = val.split_once('=') {
-                    if idx_str.trim().parse::<usize>().ok() == Some(i) {
-                        return color.trim().to_string();
-                    }
-                }
-            }
-            default_palette_color(i)
+    let actual_palette = palette_colors(user_config, theme, 256);
+    let palette: Vec<String> = actual_palette.iter().map(|c| seen(c)).collect();
+    let vision_note = vision
+        .map(|v| render_vision_note(v, &confusable_pairs(&actual_palette[..16], v)))
+        .unwrap_or_default();
+
+    let backdrop = if layers.opacity < 1.0 {
+        TRANSPARENCY_GRID
+    } else {
+        ""
+    };
+    // Ghostty's cursor blinks; reduced-motion mode shows it steady.
+    let cursor_class = if state.reduced_motion.load(Ordering::Relaxed) {
+        ""
+    } else {
+        "animate-pulse"
+    };
+    let label = escape_html(&format!(
+        "Terminal preview in {} at {}px",
+        font_family, font_size
+    ));
+    let image_layer = layers
+        .image
+        .as_ref()
+        .map(|image| {
+            format!(
+                r#"<div class="absolute inset-0 pointer-events-none" aria-hidden="true" style="{}"></div>"#,
+                image_layer_style(image)
+            )
+        })
+        .unwrap_or_default();
+    let live_button = if state.live_preview {
+        r#"<button type="button" onclick="startLivePreview()" class="px-2 py-0.5 text-xs text-gray-300 border border-gray-600 rounded hover:bg-gray-700">Run your shell</button>"#
+    } else {
+        ""
+    };
+    let scene = Scene {
+        palette: &palette,
+        cursor: &format!(
+            r#"<span class="inline-block w-2 h-4 align-middle {}" aria-hidden="true" style="background-color: {}"></span>"#,
+            cursor_class, cursor_color
+        ),
+    }
+    .render(sample);
+    let vision_options: String = std::iter::once(("none", "Normal vision"))
+        .chain(ColorVision::ALL.iter().map(|v| (v.slug(), v.label())))
+        .map(|(slug, label)| {
+            format!(
+                r#"<option value="{}"{}>{}</option>"#,
+                slug,
+                if vision.map_or("none", |v| v.slug()) == slug {
+                    " selected"
+                } else {
+                    ""
+                },
+                label
+            )
+        })
+        .collect();
+    let sample_options: String = Sample::ALL
+        .iter()
+        .map(|s| {
+            format!(
+                r#"<option value="{}"{}>{}</option>"#,
+                s.slug(),
+                if *s == sample { " selected" } else { "" },
+                s.label()
+            )
         })
         .collect();
 
     Ok(Html(format!(
-        r#"<div class="rounded-xl overflow-hidden shadow-lg border border-gray-700" id="terminal-preview">
+        r##"<div class="rounded-xl overflow-hidden shadow-lg border border-gray-700" id="terminal-preview" role="figure" aria-label="{label}" tabindex="-1">
             <div class="flex items-center gap-2 px-4 py-2 bg-gray-800 border-b border-gray-700">
-                <span class="w-3 h-3 rounded-full bg-red-500"></span>
-                <span class="w-3 h-3 rounded-full bg-yellow-500"></span>
-                <span class="w-3 h-3 rounded-full bg-green-500"></span>
-                <span class="ml-2 text-gray-400 text-xs">ghostty</span>
+                <span class="w-3 h-3 rounded-full bg-red-500" aria-hidden="true"></span>
+                <span class="w-3 h-3 rounded-full bg-yellow-500" aria-hidden="true"></span>
+                <span class="w-3 h-3 rounded-full bg-green-500" aria-hidden="true"></span>
+                <span class="ml-2 text-gray-400 text-xs" aria-hidden="true">ghostty</span>
+                <select id="preview-sample" name="sample" aria-label="Preview sample"
+                        class="ml-auto bg-gray-700 text-gray-200 text-xs rounded px-1.5 py-0.5 border border-gray-600"
+                        hx-get="/api/preview" hx-target="#preview-area" hx-swap="innerHTML" hx-include="#preview-cvd">{sample_options}</select>
+                <select id="preview-cvd" name="cvd" aria-label="Simulated color vision"
+                        class="bg-gray-700 text-gray-200 text-xs rounded px-1.5 py-0.5 border border-gray-600"
+                        hx-get="/api/preview" hx-target="#preview-area" hx-swap="innerHTML" hx-include="#preview-sample">{vision_options}</select>
+                {live_button}
+            </div>
+            <div class="relative" style="{backdrop}">
+            <div class="relative" style="background-color: {bg};">
+            {image_layer}
+            <div class="relative p-4 whitespace-pre overflow-x-auto" style="color: {fg}; font-family: '{font_family}', monospace; font-size: {font_size}px; line-height: 1.5;">{scene}</div>
             </div>
-            <div class="p-4" style="background-color: {bg}; color: {fg}; font-family: '{font_family}', monospace; font-size: {font_size}px; line-height: 1.5;">
-                <div><span style="color: {c2}">user</span><span style="color: {fg}">@</span><span style="color: {c4}">ghostty</span> <span style="color: {c6}">~</span> <span style="color: {fg}">$</span> ls -la</div>
-                <div style="color: {c4}">drwxr-xr-x</span>  <span>5 user staff  160 Jan  1 12:00 .</div>
-                <div style="color: {c2}">-rw-r--r--</span>  <span>1 user staff  842 Jan  1 12:00 config</div>
-                <div style="color: {c1}">-rwxr-xr-x</span>  <span>1 user staff 2048 Jan  1 12:00 script.sh</div>
-                <div style="color: {c3}">-rw-r--r--</span>  <span>1 user staff  256 Jan  1 12:00 notes.txt</div>
-                <div><span style="color: {c2}">user</span><span style="color: {fg}">@</span><span style="color: {c4}">ghostty</span> <span style="color: {c6}">~</span> <span style="color: {fg}">$</span> <span class="inline-block w-2 h-4 animate-pulse" style="background-color: {cursor_color}"></span></div>
             </div>
-        </div>"#,
+            {vision_note}
+        </div>"##,
+        label = label,
+        sample_options = sample_options,
+        vision_options = vision_options,
+        vision_note = vision_note,
+        scene = scene,
+        live_button = live_button,
+        backdrop = backdrop,
+        image_layer = image_layer,
         bg = bg,
         fg = fg,
-        cursor_color = cursor_color,
         font_family = font_family,
         font_size = font_size,
-        c1 = palette_colors
-            .get(1)
-            .map(|s| s.as_str())
-            .unwrap_or("#ff5555"),
-        c2 = palette_colors
-            .get(2)
-            .map(|s| s.as_str())
-            .unwrap_or("#50fa7b"),
-        c3 = palette_colors
-            .get(3)
-            .map(|s| s.as_str())
-            .unwrap_or("#f1fa8c"),
-        c4 = palette_colors
-            .get(4)
-            .map(|s| s.as_str())
-            .unwrap_or("#bd93f9"),
-        c6 = palette_colors
-            .get(6)
-            .map(|s| s.as_str())
-            .unwrap_or("#8be9fd"),
     )))
 }
 
-fn default_palette_color(index: usize) -> String {
+/// The line under a simulated preview: which accents the simulated eye can't
+/// tell apart, if any.
+fn render_vision_note(vision: ColorVision, pairs: &[(usize, usize)]) -> String {
+    let (class, message) = if pairs.is_empty() {
+        (
+            "bg-gray-800 text-gray-300",
+            format!(
+                "Simulating {}: the accent colors stay distinguishable",
+                vision.label()
+            ),
+        )
+    } else {
+        (
+            "bg-amber-100 text-amber-900",
+            format!(
+                "Simulating {}: hard to tell apart: {}",
+                vision.label(),
+                describe_pairs(pairs)
+            ),
+        )
+    };
+    format!(
+        r#"<p class="px-4 py-2 text-xs {}" role="status">{}</p>"#,
+        class,
+        escape_html(&message)
+    )
+}
+
+/// A run of text in a canned scene: its palette color (`None` for the foreground)
+/// and the text.
+type Run = (Option<usize>, &'static str);
+
+const KEYWORD: Option<usize> = Some(5);
+const TYPE: Option<usize> = Some(3);
+const FUNCTION: Option<usize> = Some(4);
+const STRING: Option<usize> = Some(2);
+const NUMBER: Option<usize> = Some(6);
+const COMMENT: Option<usize> = Some(8);
+const PLAIN: Option<usize> = None;
+
+/// Rust with the operators that programming fonts turn into ligatures.
+const CODE_SAMPLE: &[&[Run]] = &[
+    &[
+        (KEYWORD, "use"),
+        (PLAIN, " std::collections::"),
+        (TYPE, "HashMap"),
+        (PLAIN, ";"),
+    ],
+    &[],
+    &[(COMMENT, "// Count words, ignoring case.")],
+    &[
+        (KEYWORD, "fn"),
+        (PLAIN, " "),
+        (FUNCTION, "count_words"),
+        (PLAIN, "(text: &"),
+        (TYPE, "str"),
+        (PLAIN, ") -> "),
+        (TYPE, "HashMap"),
+        (PLAIN, "<"),
+        (TYPE, "String"),
+        (PLAIN, ", "),
+        (TYPE, "usize"),
+        (PLAIN, "> {"),
+    ],
+    &[
+        (KEYWORD, "    let mut"),
+        (PLAIN, " counts = "),
+        (TYPE, "HashMap"),
+        (PLAIN, "::"),
+        (FUNCTION, "new"),
+        (PLAIN, "();"),
+    ],
+    &[
+        (KEYWORD, "    for"),
+        (PLAIN, " word "),
+        (KEYWORD, "in"),
+        (PLAIN, " text."),
+        (FUNCTION, "split_whitespace"),
+        (PLAIN, "() {"),
+    ],
+    &[
+        (PLAIN, "        *counts."),
+        (FUNCTION, "entry"),
+        (PLAIN, "(word."),
+        (FUNCTION, "to_lowercase"),
+        (PLAIN, "())."),
+        (FUNCTION, "or_insert"),
+        (PLAIN, "("),
+        (NUMBER, "0"),
+        (PLAIN, ") += "),
+        (NUMBER, "1"),
+        (PLAIN, ";"),
+    ],
+    &[(PLAIN, "    }")],
+    &[(PLAIN, "    counts")],
+    &[(PLAIN, "}")],
+    &[],
+    &[
+        (KEYWORD, "fn"),
+        (PLAIN, " "),
+        (FUNCTION, "main"),
+        (PLAIN, "() {"),
+    ],
+    &[
+        (KEYWORD, "    let"),
+        (PLAIN, " counts = "),
+        (FUNCTION, "count_words"),
+        (PLAIN, "("),
+        (STRING, "\"the fox => the dog\""),
+        (PLAIN, ");"),
+    ],
+    &[
+        (KEYWORD, "    if"),
+        (PLAIN, " counts["),
+        (STRING, "\"the\""),
+        (PLAIN, "] >= "),
+        (NUMBER, "2"),
+        (PLAIN, " && counts."),
+        (FUNCTION, "len"),
+        (PLAIN, "() != "),
+        (NUMBER, "0"),
+        (PLAIN, " {"),
+    ],
+    &[
+        (FUNCTION, "        println!"),
+        (PLAIN, "("),
+        (STRING, "\"{:?}\""),
+        (PLAIN, ", counts);"),
+    ],
+    &[(PLAIN, "    }")],
+    &[(PLAIN, "}")],
+];
+
+/// The file open in the vim scene. The cursor goes at the start of line `VIM_CURSOR_LINE`.
+const VIM_SAMPLE: &[&[Run]] = &[
+    &[(KEYWORD, "use"), (PLAIN, " std::io;")],
+    &[],
+    &[
+        (KEYWORD, "fn"),
+        (PLAIN, " "),
+        (FUNCTION, "main"),
+        (PLAIN, "() {"),
+    ],
+    &[
+        (KEYWORD, "    let mut"),
+        (PLAIN, " input = "),
+        (TYPE, "String"),
+        (PLAIN, "::"),
+        (FUNCTION, "new"),
+        (PLAIN, "();"),
+    ],
+    &[
+        (PLAIN, "io::"),
+        (FUNCTION, "stdin"),
+        (PLAIN, "()."),
+        (FUNCTION, "read_line"),
+        (PLAIN, "(&"),
+        (KEYWORD, "mut"),
+        (PLAIN, " input)?;"),
+    ],
+    &[
+        (FUNCTION, "    println!"),
+        (PLAIN, "("),
+        (STRING, "\"{}\""),
+        (PLAIN, ", input."),
+        (FUNCTION, "trim"),
+        (PLAIN, "());"),
+    ],
+    &[(PLAIN, "}")],
+];
+const VIM_CURSOR_LINE: usize = 4;
+
+/// Width of the vim and htop scenes' full-width bars, in cells.
+const SCENE_WIDTH: usize = 64;
+
+/// What the canned scenes are drawn with.
+struct Scene<'a> {
+    /// All 256 palette colors.
+    palette: &'a [String],
+    /// The cursor block's HTML.
+    cursor: &'a str,
+}
+
+impl Scene<'_> {
+    fn render(&self, sample: Sample) -> String {
+        let lines = match sample {
+            Sample::Shell => self.shell(),
+            Sample::Code => CODE_SAMPLE.iter().map(|line| self.runs(line)).collect(),
+            Sample::Vim => self.vim(),
+            Sample::Htop => self.htop(),
+            Sample::Ansi => self.ansi(),
+        };
+        lines
+            .iter()
+            .map(|line| {
+                // An empty line still takes up a row.
+                let line = if line.is_empty() { " " } else { line };
+                format!("<div>{}</div>", line)
+            })
+            .collect()
+    }
+
+    /// `text` in palette color `color`.
+    fn fg(&self, color: usize, text: &str) -> String {
+        format!(
+            r#"<span style="color: {}">{}</span>"#,
+            self.palette[color],
+            escape_html(text)
+        )
+    }
+
+    /// `text` in palette color `fg` on palette color `bg`.
+    fn on(&self, fg: usize, bg: usize, text: &str) -> String {
+        format!(
+            r#"<span style="color: {}; background-color: {}">{}</span>"#,
+            self.palette[fg],
+            self.palette[bg],
+            escape_html(text)
+        )
+    }
+
+    fn runs(&self, runs: &[Run]) -> String {
+        runs.iter()
+            .map(|(color, text)| match color {
+                Some(color) => self.fg(*color, text),
+                None => escape_html(text),
+            })
+            .collect()
+    }
+
+    fn prompt(&self) -> String {
+        format!(
+            "{}@{} {} $ ",
+            self.fg(2, "user"),
+            self.fg(4, "ghostty"),
+            self.fg(6, "~")
+        )
+    }
+
+    fn shell(&self) -> Vec<String> {
+        let entry = |mode: &str, color: usize, rest: &str, name: &str| {
+            format!(
+                "{}  {}{}",
+                self.fg(color, mode),
+                escape_html(rest),
+                self.fg(color, name)
+            )
+        };
+        vec![
+            self.prompt() + "ls -la",
+            entry("drwxr-xr-x", 4, "5 user staff  160 Jan  1 12:00 ", "."),
+            entry("-rw-r--r--", 2, "1 user staff  842 Jan  1 12:00 ", "config"),
+            entry(
+                "-rwxr-xr-x",
+                1,
+                "1 user staff 2048 Jan  1 12:00 ",
+                "script.sh",
+            ),
+            entry(
+                "-rw-r--r--",
+                3,
+                "1 user staff  256 Jan  1 12:00 ",
+                "notes.txt",
+            ),
+            self.prompt() + self.cursor,
+        ]
+    }
+
+    fn vim(&self) -> Vec<String> {
+        let mut lines: Vec<String> = VIM_SAMPLE
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let number = format!("{:>3} ", i + 1);
+                if i == VIM_CURSOR_LINE {
+                    format!(
+                        "{}    {}{}",
+                        self.fg(3, &number),
+                        self.cursor,
+                        self.runs(line)
+                    )
+                } else {
+                    format!("{}{}", self.fg(8, &number), self.runs(line))
+                }
+            })
+            .collect();
+        lines.extend((0..3).map(|_| self.fg(4, "~")));
+        let file = " main.rs [+]";
+        let position = format!("rust  {}:5 ", VIM_CURSOR_LINE + 1);
+        let gap = SCENE_WIDTH - " NORMAL ".len() - file.len() - position.len();
+        lines.push(format!(
+            "{}{}",
+            self.on(0, 2, " NORMAL "),
+            self.on(15, 8, &format!("{}{}{}", file, " ".repeat(gap), position))
+        ));
+        lines.push(":w".to_string());
+        lines
+    }
+
+    fn htop(&self) -> Vec<String> {
+        // A meter: its label, then bars of each color, padded out, then the reading.
+        let meter = |label: &str, bars: &[(usize, usize)], reading: &str, side: String| {
+            let filled: usize = bars.iter().map(|(_, n)| n).sum();
+            let bars: String = bars
+                .iter()
+                .map(|(color, n)| self.fg(*color, &"|".repeat(*n)))
+                .collect();
+            let pad = 24 - filled - reading.len();
+            format!(
+                "{}[{}{}{}]   {}",
+                self.fg(6, &format!("{:>5}  ", label)),
+                bars,
+                " ".repeat(pad),
+                self.fg(8, reading),
+                side
+            )
+        };
+        let header = "  PID USER      PRI  NI  VIRT   RES   SHR S CPU% MEM%   TIME+  Command";
+        let processes = [
+            " 1423 user       20   0 2.1G  412M   96M S 12.4  2.6  1:23.45 ghostty",
+            "  982 root       20   0  48M   12M    8M S  3.1  0.1  0:12.01 Xorg",
+            " 2210 user       20   0 812M  156M   44M S  1.2  1.0  0:05.33 nvim",
+            "  311 root       20   0  24M    6M    4M S  0.0  0.0  0:00.91 systemd",
+        ];
+        let keys = [
+            ("F1", "Help  "),
+            ("F2", "Setup "),
+            ("F3", "Search"),
+            ("F5", "Tree  "),
+            ("F9", "Kill  "),
+            ("F10", "Quit  "),
+        ];
+
+        let mut lines = vec![
+            meter(
+                "1",
+                &[(4, 2), (2, 8), (1, 3)],
+                "45.2%",
+                format!("{} 87, 312 thr; 2 running", self.fg(6, "Tasks:")),
+            ),
+            meter(
+                "2",
+                &[(4, 1), (2, 4), (1, 1)],
+                "22.8%",
+                format!("{} 0.42 0.51 0.48", self.fg(6, "Load average:")),
+            ),
+            meter(
+                "Mem",
+                &[(2, 6), (4, 1), (3, 4)],
+                "3.21G/15.5G",
+                format!("{} 3 days, 04:12:55", self.fg(6, "Uptime:")),
+            ),
+            meter("Swp", &[(1, 1)], "128M/2.00G", String::new()),
+            String::new(),
+            self.on(
+                0,
+                2,
+                &format!("{:<width$}", header, width = SCENE_WIDTH + 8),
+            ),
+            self.on(
+                0,
+                6,
+                &format!("{:<width$}", processes[0], width = SCENE_WIDTH + 8),
+            ),
+        ];
+        lines.extend(processes[1..].iter().map(|p| escape_html(p)));
+        lines.push(
+            keys.iter()
+                .map(|(key, label)| format!("{}{}", escape_html(key), self.on(0, 6, label)))
+                .collect(),
+        );
+        lines
+    }
+
+    fn ansi(&self) -> Vec<String> {
+        let swatch = |index: usize, width: usize| {
+            format!(
+                r#"<span title="{}" style="background-color: {}">{}</span>"#,
+                index,
+                self.palette[index],
+                " ".repeat(width)
+            )
+        };
+        let row = |label: &str, indexes: std::ops::Range<usize>| {
+            let cells: Vec<String> = indexes.map(|i| swatch(i, 4)).collect();
+            format!("{:<8}{}", label, cells.join(" "))
+        };
+        let numbers = |indexes: std::ops::Range<usize>| {
+            let cells: Vec<String> = indexes.map(|i| format!("{:<4}", i)).collect();
+            format!("{:<8}{}", "", cells.join(" "))
+        };
+        let text: Vec<String> = (0..16).map(|i| self.fg(i, "Aa")).collect();
+
+        let mut lines = vec![
+            row("Normal", 0..8),
+            numbers(0..8),
+            row("Bright", 8..16),
+            numbers(8..16),
+            format!("{:<8}{}", "Text", text.join(" ")),
+            String::new(),
+        ];
+        // The 6×6×6 color cube, one red level per row, then the gray ramp.
+        lines.extend((0..6).map(|red| {
+            (0..36)
+                .map(|i| swatch(16 + red * 36 + i, 2))
+                .collect::<String>()
+        }));
+        lines.push((232..256).map(|i| swatch(i, 3)).collect());
+        lines
+    }
+}
+
+/// The first `count` palette colors: `palette` entries, then the theme's, then our
+/// defaults, which past the 16 ANSI colors are xterm's 256-color cube and grays.
+fn palette_colors(
+    user_config: &UserConfig,
+    theme: Option<&ThemeInfo>,
+    count: usize,
+) -> Vec<String> {
+    (0..count)
+        .map(|i| {
+            // Check for palette = i=#color entries
+            for val in user_config.get_all("palette") {
+                if let Some((idx_str, color)) = val.split_once('=') {
+                    if idx_str.trim().parse::<usize>().ok() == Some(i) {
+                        return color.trim().to_string();
+                    }
+                }
+            }
+            theme
+                .and_then(|t| t.palette.get(i))
+                .filter(|c| !c.is_empty())
+                .cloned()
+                .unwrap_or_else(|| default_palette_color(i))
+        })
+        .collect()
+}
+
+/// xterm.js's names for the 16 ANSI colors, in palette order.
+const XTERM_COLOR_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "brightBlack",
+    "brightRed",
+    "brightGreen",
+    "brightYellow",
+    "brightBlue",
+    "brightMagenta",
+    "brightCyan",
+    "brightWhite",
+];
+
+/// GET /api/preview/terminal — xterm.js options for the live preview, from the
+/// in-memory config, plus the window padding in pixels.
+pub async fn terminal_options(State(state): State<SharedState>) -> Json<serde_json::Value> {
+    let user_config = state.user_config.read().await;
+    let themes = state.themes.read().await;
+    let reduced_motion = state.reduced_motion.load(Ordering::Relaxed);
+    Json(xterm_options(&user_config, &themes, reduced_motion))
+}
+
+fn xterm_options(
+    user_config: &UserConfig,
+    themes: &[ThemeInfo],
+    reduced_motion: bool,
+) -> serde_json::Value {
+    let theme = active_theme(user_config, themes);
+    let layers = resolve_background(user_config, themes);
+    let foreground = user_config
+        .get("foreground")
+        .or(theme.map(|t| t.foreground.as_str()))
+        .unwrap_or("#cdd6f4");
+    let cursor = user_config
+        .get("cursor-color")
+        .or(theme.and_then(|t| t.cursor_color.as_deref()))
+        .unwrap_or("#f5e0dc");
+
+    let mut colors = serde_json::Map::new();
+    colors.insert(
+        "background".into(),
+        with_alpha(&layers.color, layers.opacity).into(),
+    );
+    colors.insert("foreground".into(), foreground.into());
+    colors.insert("cursor".into(), cursor.into());
+    for (name, color) in XTERM_COLOR_NAMES
+        .iter()
+        .zip(palette_colors(user_config, theme, 16))
+    {
+        colors.insert(name.to_string(), color.into());
+    }
+
+    // `window-padding-x = 2,4` pads the two sides differently; the preview uses the first.
+    let padding = |key: &str| {
+        user_config
+            .get(key)
+            .and_then(|v| v.split(',').next())
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .unwrap_or(2)
+    };
+    let font_size = user_config
+        .get("font-size")
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(13.0);
+    serde_json::json!({
+        "fontFamily": format!("'{}', monospace", user_config.get("font-family").unwrap_or("monospace")),
+        "fontSize": font_size,
+        "allowTransparency": layers.opacity < 1.0,
+        "cursorBlink": !reduced_motion && user_config.get("cursor-style-blink") != Some("false"),
+        "theme": colors,
+        "padding": { "x": padding("window-padding-x"), "y": padding("window-padding-y") },
+    })
+}
+
+/// CSS for a layer drawing the configured `background-image` as Ghostty would.
+pub fn image_layer_style(image: &BackgroundImage) -> String {
+    let mut hasher = DefaultHasher::new();
+    image.path.hash(&mut hasher);
+    format!(
+        "background-image: url('/api/preview/background-image?v={:x}'); background-size: {}; background-position: {}; background-repeat: {}; opacity: {:.2};",
+        hasher.finish(),
+        image.fit.css_size(),
+        image.css_position(),
+        if image.repeat { "repeat" } else { "no-repeat" },
+        image.opacity,
+    )
+}
+
+/// GET /api/preview/background-image — serve the configured `background-image` file.
+///
+/// Only the path currently set in the config is served, and only if it looks like an image.
+pub async fn background_image(State(state): State<SharedState>) -> Result<Response, AppError> {
+    let user_config = state.user_config.read().await;
+    let themes = state.themes.read().await;
+    let Some(image) = resolve_background(&user_config, &themes).image else {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    };
+    drop(themes);
+    drop(user_config);
+
+    let Some(content_type) = image_content_type(&image.path) else {
+        return Ok(StatusCode::UNSUPPORTED_MEDIA_TYPE.into_response());
+    };
+
+    match tokio::fs::read(&image.path).await {
+        Ok(bytes) => Ok(([(header::CONTENT_TYPE, content_type)], bytes).into_response()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Ok(StatusCode::NOT_FOUND.into_response())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub(crate) fn default_palette_color(index: usize) -> String {
     match index {
         0 => "#21222c",
         1 => "#ff5555",
@@ -93,7 +833,80 @@ fn default_palette_color(index: usize) -> String {
         13 => "#ff92df",
         14 => "#a4ffff",
         15 => "#ffffff",
+        16..=231 => {
+            let level = |v: usize| if v == 0 { 0 } else { 55 + 40 * v };
+            let n = index - 16;
+            return format!(
+                "#{:02x}{:02x}{:02x}",
+                level(n / 36),
+                level(n / 6 % 6),
+                level(n % 6)
+            );
+        }
+        232..=255 => {
+            let gray = 8 + 10 * (index - 232);
+            return format!("#{:02x}{:02x}{:02x}", gray, gray, gray);
+        }
         _ => "#ffffff",
     }
     .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_parse() {
+        assert_eq!(Sample::parse(Some("htop")), Sample::Htop);
+        assert_eq!(Sample::parse(Some("nope")), Sample::Shell);
+        assert_eq!(Sample::parse(None), Sample::Shell);
+    }
+
+    #[test]
+    fn test_color_vision_query() {
+        let query = |cvd: Option<&str>| PreviewQuery {
+            sample: None,
+            theme: None,
+            cvd: cvd.map(String::from),
+        };
+        assert_eq!(query(None).color_vision().unwrap(), None);
+        assert_eq!(query(Some("none")).color_vision().unwrap(), None);
+        assert_eq!(
+            query(Some("deuteranopia")).color_vision().unwrap(),
+            Some(ColorVision::Deuteranopia)
+        );
+        assert!(query(Some("grayscale")).color_vision().is_err());
+
+        let note = render_vision_note(ColorVision::Deuteranopia, &[(1, 2)]);
+        assert!(note.contains("hard to tell apart: Red and green"));
+        assert!(render_vision_note(ColorVision::Tritanopia, &[]).contains("stay distinguishable"));
+    }
+
+    #[test]
+    fn test_default_palette_256() {
+        assert_eq!(default_palette_color(16), "#000000");
+        assert_eq!(default_palette_color(196), "#ff0000");
+        assert_eq!(default_palette_color(231), "#ffffff");
+        assert_eq!(default_palette_color(232), "#080808");
+        assert_eq!(default_palette_color(255), "#eeeeee");
+    }
+
+    #[test]
+    fn test_scenes_render() {
+        let palette: Vec<String> = (0..256).map(default_palette_color).collect();
+        let scene = Scene {
+            palette: &palette,
+            cursor: "<cursor>",
+        };
+        for sample in Sample::ALL {
+            let html = scene.render(sample);
+            assert!(html.starts_with("<div>"), "{:?}", sample);
+        }
+        // Code is escaped, so its operators survive for the font's ligatures.
+        let code = scene.render(Sample::Code);
+        assert!(code.contains("&amp;&amp; counts."));
+        assert!(scene.render(Sample::Vim).contains("<cursor>"));
+        assert!(scene.render(Sample::Ansi).contains(r#"title="255""#));
+    }
+}
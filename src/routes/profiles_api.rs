@@ -0,0 +1,219 @@
+use std::time::{Duration, SystemTime};
+
+use axum::extract::{Path, State};
+use axum::response::{Html, IntoResponse, Response};
+use serde::Deserialize;
+
+use super::config_api::{escape_html, publish_toast, toast_html, write_and_reload};
+use crate::app_state::SharedState;
+use crate::config::file_io::{parse_config, render_config};
+use crate::error::AppError;
+use crate::profiles::{
+    backup_config, delete_profile, list_profiles, profiles_dir, read_profile, save_profile, Profile,
+};
+
+/// GET /api/profiles — the saved profiles, with actions for each.
+pub async fn list(State(state): State<SharedState>) -> Html<String> {
+    let profiles = list_profiles(&profiles_dir(&state.settings_path));
+    let active = state.settings.read().await.active_profile.clone();
+    Html(render_profiles(&profiles, active.as_deref()))
+}
+
+#[derive(Deserialize)]
+pub struct CreateProfileForm {
+    pub name: String,
+}
+
+/// POST /api/profiles — save the current config, unsaved edits included, as a profile.
+pub async fn create(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<CreateProfileForm>,
+) -> Result<Html<String>, AppError> {
+    store_current(&state, form.name.trim(), "saved").await
+}
+
+/// GET /api/profiles/:name — the profile's config text.
+pub async fn get(
+    State(state): State<SharedState>,
+    Path(name): Path<String>,
+) -> Result<String, AppError> {
+    read_profile(&profiles_dir(&state.settings_path), &name)
+}
+
+/// PUT /api/profiles/:name — overwrite the profile with the current config.
+pub async fn update(
+    State(state): State<SharedState>,
+    Path(name): Path<String>,
+) -> Result<Html<String>, AppError> {
+    store_current(&state, &name, "updated").await
+}
+
+/// DELETE /api/profiles/:name
+pub async fn delete(
+    State(state): State<SharedState>,
+    Path(name): Path<String>,
+) -> Result<Html<String>, AppError> {
+    delete_profile(&profiles_dir(&state.settings_path), &name)?;
+    let mut settings = state.settings.write().await;
+    if settings.active_profile.as_deref() == Some(name.as_str()) {
+        settings.active_profile = None;
+        settings.save(&state.settings_path)?;
+    }
+    Ok(Html(toast_html(
+        &format!("Profile '{}' deleted", escape_html(&name)),
+        false,
+    )))
+}
+
+async fn store_current(
+    state: &SharedState,
+    name: &str,
+    verb: &str,
+) -> Result<Html<String>, AppError> {
+    let text = render_config(&*state.user_config.read().await);
+    match save_profile(&profiles_dir(&state.settings_path), name, &text) {
+        Ok(()) => Ok(Html(toast_html(
+            &format!("Profile '{}' {}", escape_html(name), verb),
+            false,
+        ))),
+        Err(AppError::Config(msg)) => Ok(Html(toast_html(&escape_html(&msg), true))),
+        Err(e) => Err(e),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ActivateForm {
+    /// Also tell Ghostty to reload the new config.
+    #[serde(default)]
+    pub apply: bool,
+}
+
+/// POST /api/profiles/:name/activate — make the profile the live config.
+///
+/// The current config file is copied to `<config>.bak` first. Unsaved edits are dropped.
+pub async fn activate(
+    State(state): State<SharedState>,
+    Path(name): Path<String>,
+    axum::Form(form): axum::Form<ActivateForm>,
+) -> Result<Response, AppError> {
+    let text = match read_profile(&profiles_dir(&state.settings_path), &name) {
+        Ok(text) => text,
+        Err(AppError::Config(msg)) => {
+            return Ok(Html(toast_html(&escape_html(&msg), true)).into_response())
+        }
+        Err(e) => return Err(e),
+    };
+
+    {
+        let mut user_config = state.user_config.write().await;
+        backup_config(&user_config.file_path)?;
+        *user_config = parse_config(&text, &user_config.file_path);
+    }
+    state.bump_revision();
+    write_and_reload(&state, false).await?;
+    {
+        let mut settings = state.settings.write().await;
+        settings.active_profile = Some(name.clone());
+        settings.save(&state.settings_path)?;
+    }
+
+    let (message, is_warn) = if !form.apply {
+        (format!("Switched to profile '{}'", name), false)
+    } else {
+        match super::config_api::trigger_ghostty_reload() {
+            Ok(()) => (
+                format!("Switched to profile '{}' and reloaded Ghostty", name),
+                false,
+            ),
+            Err(e) => {
+                tracing::warn!("Failed to trigger Ghostty reload: {}", e);
+                (
+                    format!(
+                        "Switched to profile '{}' (reload Ghostty manually with {})",
+                        name,
+                        super::config_api::RELOAD_SHORTCUT
+                    ),
+                    true,
+                )
+            }
+        }
+    };
+    Ok(Html(publish_toast(&state, &escape_html(&message), is_warn)).into_response())
+}
+
+/// How long ago `time` was, roughly.
+pub(crate) fn age(time: SystemTime) -> String {
+    let secs = SystemTime::now()
+        .duration_since(time)
+        .unwrap_or(Duration::ZERO)
+        .as_secs();
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{} min ago", secs / 60),
+        3600..86400 => format!("{} h ago", secs / 3600),
+        _ => format!("{} d ago", secs / 86400),
+    }
+}
+
+fn render_profiles(profiles: &[Profile], active: Option<&str>) -> String {
+    if profiles.is_empty() {
+        return r#"<p class="text-sm text-gray-500">No profiles yet. Save the current config as one to switch back to it later.</p>"#.to_string();
+    }
+
+    let mut html = String::from(
+        r#"<ul class="bg-white rounded-xl border border-gray-200 divide-y divide-gray-100">"#,
+    );
+    for profile in profiles {
+        let name = escape_html(&profile.name);
+        let badge = if active == Some(profile.name.as_str()) {
+            r#"<span class="text-xs bg-emerald-100 text-emerald-700 px-2 py-0.5 rounded-full">Active</span>"#
+        } else {
+            ""
+        };
+        let saved = profile
+            .modified
+            .map(|t| format!("saved {}", age(t)))
+            .unwrap_or_default();
+        html.push_str(&format!(
+            r##"<li class="flex items-center justify-between gap-4 px-4 py-3"><div class="flex items-center gap-2"><span class="font-mono text-sm font-semibold text-gray-900">{name}</span>{badge}<span class="text-xs text-gray-400">{saved}</span></div><div class="flex items-center gap-2"><button hx-post="/api/profiles/{name}/activate" hx-target="#toast-container" hx-swap="innerHTML" hx-confirm="Switch to '{name}'? Your config file is backed up and unsaved edits are dropped." hx-on::after-request="if(event.detail.successful) setTimeout(() => location.reload(), 600)" class="px-3 py-1.5 text-sm font-medium text-white bg-indigo-600 rounded-lg hover:bg-indigo-700 transition-colors">Activate</button><button hx-post="/api/profiles/{name}/activate" hx-vals='{{"apply": "true"}}' hx-target="#toast-container" hx-swap="innerHTML" hx-confirm="Switch to '{name}' and reload Ghostty? Your config file is backed up and unsaved edits are dropped." hx-on::after-request="if(event.detail.successful) setTimeout(() => location.reload(), 600)" class="px-3 py-1.5 text-sm font-medium text-gray-700 bg-white border border-gray-300 rounded-lg hover:bg-gray-50 transition-colors">Activate &amp; Apply</button><button hx-put="/api/profiles/{name}" hx-target="#toast-container" hx-swap="innerHTML" hx-confirm="Overwrite '{name}' with the current config?" hx-on::after-request="htmx.trigger('#profile-list', 'refresh')" class="px-3 py-1.5 text-sm font-medium text-gray-700 bg-white border border-gray-300 rounded-lg hover:bg-gray-50 transition-colors">Update</button><button hx-delete="/api/profiles/{name}" hx-target="#toast-container" hx-swap="innerHTML" hx-confirm="Delete profile '{name}'?" hx-on::after-request="htmx.trigger('#profile-list', 'refresh')" aria-label="Delete profile {name}" class="text-gray-400 hover:text-red-500 transition-colors px-2">&times;</button></div></li>"##,
+            name = name,
+            badge = badge,
+            saved = saved,
+        ));
+    }
+    html.push_str("</ul>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_profiles() {
+        let profiles = [
+            Profile {
+                name: "streaming".to_string(),
+                modified: Some(SystemTime::now()),
+            },
+            Profile {
+                name: "work".to_string(),
+                modified: None,
+            },
+        ];
+        let html = render_profiles(&profiles, Some("work"));
+        assert!(html.contains(r#"hx-post="/api/profiles/streaming/activate""#));
+        assert!(html.contains("saved just now"));
+        assert_eq!(html.matches(">Active<").count(), 1);
+
+        assert!(render_profiles(&[], None).contains("No profiles yet"));
+    }
+
+    #[test]
+    fn test_age() {
+        let now = SystemTime::now();
+        assert_eq!(age(now), "just now");
+        assert_eq!(age(now - Duration::from_secs(300)), "5 min ago");
+        assert_eq!(age(now - Duration::from_secs(3 * 86400)), "3 d ago");
+    }
+}
@@ -0,0 +1,179 @@
+use std::io::{Read, Write};
+use std::net::IpAddr;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc};
+
+use crate::app_state::SharedState;
+use crate::error::AppError;
+use crate::events::AppEvent;
+use crate::pty::{shell_command, Pty};
+
+#[derive(Deserialize)]
+pub struct PtySize {
+    #[serde(default = "default_cols")]
+    pub cols: u16,
+    #[serde(default = "default_rows")]
+    pub rows: u16,
+}
+
+fn default_cols() -> u16 {
+    80
+}
+
+fn default_rows() -> u16 {
+    24
+}
+
+/// A text frame from the page: typed input, or the pane's new size.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum PtyInput {
+    Input { data: String },
+    Resize { cols: u16, rows: u16 },
+}
+
+/// GET /ws/preview — run the configured `command` (or the login shell) on a PTY and
+/// bridge it to the preview's xterm.js pane: output goes out as binary frames, and
+/// input and resizes come in as JSON text frames.
+///
+/// Only served with `--live-preview`, and only to pages from this server.
+pub async fn pty_preview(
+    ws: WebSocketUpgrade,
+    State(state): State<SharedState>,
+    Query(size): Query<PtySize>,
+    headers: HeaderMap,
+) -> Response {
+    if !state.live_preview {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    if !same_origin(&headers) {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    let command = shell_command(state.value_of("command").await.as_deref());
+    let pty = match Pty::spawn(command, size.cols, size.rows) {
+        Ok(pty) => pty,
+        Err(e) => return AppError::from(e).into_response(),
+    };
+    let events = state.events.subscribe();
+    ws.on_upgrade(move |socket| bridge(socket, pty, events))
+}
+
+/// Whether the request comes from a page this server served, under a local name.
+/// Browsers let any site open a WebSocket to localhost, and this one runs a shell;
+/// checking the host too keeps DNS-rebound names out.
+pub(crate) fn same_origin(headers: &HeaderMap) -> bool {
+    let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+    let (Some(origin), Some(host)) = (header(header::ORIGIN), header(header::HOST)) else {
+        return false;
+    };
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if port.parse::<u16>().is_ok() => name,
+        _ => host,
+    };
+    let local = name == "localhost"
+        || name
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok();
+    local && origin.split_once("://").is_some_and(|(_, o)| o == host)
+}
+
+async fn bridge(mut socket: WebSocket, pty: Pty, mut events: broadcast::Receiver<AppEvent>) {
+    let Ok(mut reader) = pty.master.try_clone() else {
+        return;
+    };
+    // Reads block, so they get their own thread. It ends when the shell exits and
+    // the PTY reports EOF, or when this side stops listening.
+    let (tx, mut rx) = mpsc::channel::<Vec<u8>>(16);
+    tokio::task::spawn_blocking(move || {
+        let mut buf = [0; 4096];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if tx.blocking_send(buf[..n].to_vec()).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    let mut writer = &pty.master;
+    loop {
+        tokio::select! {
+            output = rx.recv() => match output {
+                Some(bytes) => {
+                    if socket.send(Message::binary(bytes)).await.is_err() {
+                        break;
+                    }
+                }
+                // The shell exited.
+                None => break,
+            },
+            msg = socket.recv() => match msg {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str(text.as_str()) {
+                    Ok(PtyInput::Input { data }) => {
+                        if writer.write_all(data.as_bytes()).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(PtyInput::Resize { cols, rows }) => {
+                        let _ = pty.resize(cols, rows);
+                    }
+                    Err(_) => {}
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            // Let the server stop without waiting for the shell.
+            event = events.recv() => match event {
+                Ok(AppEvent::ShuttingDown) | Err(RecvError::Closed) => break,
+                _ => {}
+            },
+        }
+    }
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(origin: &str, host: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ORIGIN, origin.parse().unwrap());
+        headers.insert(header::HOST, host.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_same_origin() {
+        assert!(same_origin(&headers(
+            "http://127.0.0.1:3456",
+            "127.0.0.1:3456"
+        )));
+        assert!(same_origin(&headers(
+            "http://localhost:3456",
+            "localhost:3456"
+        )));
+        assert!(same_origin(&headers("http://[::1]:3456", "[::1]:3456")));
+        assert!(!same_origin(&headers(
+            "https://evil.example",
+            "127.0.0.1:3456"
+        )));
+        // A DNS-rebound name matches its own origin but isn't local.
+        assert!(!same_origin(&headers(
+            "http://evil.example:3456",
+            "evil.example:3456"
+        )));
+        assert!(!same_origin(&HeaderMap::new()));
+    }
+}
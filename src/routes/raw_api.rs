@@ -0,0 +1,156 @@
+use std::path::Path;
+
+use axum::extract::State;
+use axum::response::{Html, IntoResponse, Response};
+use serde::Deserialize;
+
+use super::config_api::{escape_html, merge_redirect, publish_toast, toast_html, write_and_reload};
+use crate::app_state::SharedState;
+use crate::cli::validate::{line_errors, validate_user_config};
+use crate::config::corruption::detect_corruption;
+use crate::config::file_io::{parse_config, render_config};
+use crate::error::AppError;
+
+/// GET /api/raw — the config as text, including unsaved edits.
+pub async fn get_raw(State(state): State<SharedState>) -> String {
+    render_config(&*state.user_config.read().await)
+}
+
+#[derive(Deserialize)]
+pub struct RawForm {
+    pub text: String,
+}
+
+/// PUT /api/raw — replace the whole config with the submitted text and save it.
+///
+/// The text is checked with `ghostty +validate-config` first; if Ghostty objects,
+/// nothing is changed and the complaints are shown in `#raw-errors`. Without
+/// Ghostty it is saved unchecked.
+pub async fn put_raw(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<RawForm>,
+) -> Result<Response, AppError> {
+    let file_path = state.user_config.read().await.file_path.clone();
+    let problems = match &state.ghostty().await.path {
+        Some(ghostty_path) => {
+            let config = parse_config(&form.text, &file_path);
+            let included = state.included.read().await.clone();
+            match validate_user_config(ghostty_path, &config, &included).await {
+                Ok(problems) => problems,
+                Err(e @ AppError::Cli(_)) => {
+                    return Ok(Html(toast_html(&escape_html(&e.to_string()), true)).into_response())
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        None => String::new(),
+    };
+    if !problems.is_empty() {
+        return Ok((
+            [("HX-Retarget", "#raw-errors")],
+            Html(render_problems(&problems, &file_path)),
+        )
+            .into_response());
+    }
+
+    if let Some(problem) = detect_corruption(form.text.as_bytes()).first() {
+        let location = problem.line.map(|l| format!(":{}", l)).unwrap_or_default();
+        return Ok((
+            [("HX-Retarget", "#raw-errors")],
+            Html(render_problems(
+                &format!("{}{}: {}", file_path.display(), location, problem.message),
+                &file_path,
+            )),
+        )
+            .into_response());
+    }
+
+    // In safe mode the file on disk is the damaged one being replaced, not an edit to merge.
+    let safe_mode = state.in_safe_mode().await;
+    if !safe_mode && state.external_changes().await?.is_some() {
+        return Ok(merge_redirect());
+    }
+
+    {
+        let mut user_config = state.user_config.write().await;
+        *user_config = parse_config(&form.text, &user_config.file_path);
+    }
+    state.bump_revision();
+    write_and_reload(&state, false).await?;
+    if safe_mode {
+        state.safe_mode.write().await.clear();
+        return Ok((
+            [("HX-Redirect", "/")],
+            Html(toast_html("Config repaired and saved", false)),
+        )
+            .into_response());
+    }
+
+    let mut html = publish_toast(&state, "Config saved to disk", false);
+    html.push_str(r#"<div id="raw-errors" hx-swap-oob="true"></div>"#);
+    Ok(Html(html).into_response())
+}
+
+/// Validator output, with each message about `main_file` linked to its line in
+/// the editor. Messages about included files name the file instead.
+fn render_problems(output: &str, main_file: &Path) -> String {
+    let errors = line_errors(output);
+    let items: String = if errors.is_empty() {
+        format!(
+            r#"<li class="font-mono whitespace-pre-wrap">{}</li>"#,
+            escape_html(output)
+        )
+    } else {
+        errors
+            .iter()
+            .map(|e| {
+                if e.file != main_file.display().to_string() {
+                    return format!(
+                        r#"<li><span class="font-semibold">{file}, line {line}</span>: <span class="font-mono">{message}</span></li>"#,
+                        file = escape_html(&e.file),
+                        line = e.line,
+                        message = escape_html(&e.message),
+                    );
+                }
+                format!(
+                    r#"<li><button type="button" onclick="jumpToLine({line})" class="font-semibold hover:underline">Line {line}</button>: <span class="font-mono">{message}</span></li>"#,
+                    line = e.line,
+                    message = escape_html(&e.message),
+                )
+            })
+            .collect()
+    };
+    format!(
+        r#"<div role="alert" class="border rounded-lg p-4 bg-amber-50 border-amber-300 text-amber-800"><p class="font-medium mb-2">Not saved: Ghostty rejected this config.</p><ul class="text-sm space-y-1">{}</ul></div>"#,
+        items
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_round_trips() {
+        let text = "# Fonts\nfont-size = 14\n\nkeybind = ctrl+a=select_all\nkeybind = ctrl+c=copy_to_clipboard\n";
+        let config = parse_config(text, Path::new("/tmp/config"));
+        assert_eq!(render_config(&config), text);
+    }
+
+    #[test]
+    fn test_render_problems_links_lines() {
+        let main = Path::new("/tmp/x");
+        let html = render_problems("/tmp/x:4:font-size: invalid value <big>", main);
+        assert!(html.contains("jumpToLine(4)"));
+        assert!(html.contains("Line 4"));
+        assert!(html.contains("font-size: invalid value &lt;big&gt;"));
+
+        let html = render_problems("/tmp/colors.conf:2:background: bad", main);
+        assert!(html.contains("/tmp/colors.conf, line 2"));
+        assert!(!html.contains("jumpToLine"));
+
+        let html = render_problems("error: something odd", main);
+        assert!(html.contains("error: something odd"));
+        assert!(!html.contains("jumpToLine"));
+    }
+}
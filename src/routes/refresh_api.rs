@@ -0,0 +1,203 @@
+use std::collections::HashSet;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::Html;
+
+use super::config_api::{escape_html, publish_toast, toast_html};
+use super::themes_api::reload_themes;
+use crate::app_state::SharedState;
+use crate::discovery_cache::cache_path;
+use crate::error::AppError;
+use crate::events::AppEvent;
+use crate::ghostty::{find_installations, Ghostty};
+
+/// POST /api/refresh — look again for what was found at startup: Ghostty
+/// installations, the active one's options, fonts, actions, and keybinds
+/// (skipping the discovery cache), and themes. Runs in the background; open pages
+/// get a toast when it's done, and reload if Ghostty's version changed.
+#[utoipa::path(
+    post,
+    path = "/api/refresh",
+    tag = "Config",
+    summary = "Look again for Ghostty installations, the active one's options, fonts, actions, and keybinds, and themes, in the background; a toast says what was found",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn refresh(State(state): State<SharedState>) -> Html<String> {
+    if state.refreshing.swap(true, Ordering::SeqCst) {
+        return Html(toast_html("Already refreshing", false));
+    }
+    tokio::spawn(async move {
+        let (message, is_error) = match rediscover(&state).await {
+            Ok(message) => (message, false),
+            Err(e) => {
+                tracing::warn!("Refresh failed: {}", e);
+                (
+                    format!("Refresh failed: {}", escape_html(&e.to_string())),
+                    true,
+                )
+            }
+        };
+        state.refreshing.store(false, Ordering::SeqCst);
+        publish_toast(&state, &message, is_error);
+    });
+    Html(toast_html(
+        "Looking for new Ghostty versions, fonts, and themes…",
+        false,
+    ))
+}
+
+/// Rerun discovery and swap in what it found. Returns the toast saying what changed.
+async fn rediscover(state: &SharedState) -> Result<String, AppError> {
+    let old = state.ghostty().await;
+    let found = find_installations(old.path.as_deref()).await;
+    // Without a Ghostty at startup, one installed since takes over.
+    let path = old.path.clone().or_else(|| {
+        found
+            .iter()
+            .find(|i| i.version.is_some())
+            .map(|i| i.path.clone())
+    });
+    *state.installations.write().await = Some(found);
+
+    let old_themes = theme_names(state).await;
+    reload_themes(state).await?;
+    let new_themes = theme_names(state).await;
+    state.font_axes.write().await.clear();
+
+    let Some(path) = path else {
+        return Ok(refresh_summary(&old, &old, &old_themes, &new_themes));
+    };
+    let new = Ghostty::discover(
+        Some(path.clone()),
+        &cache_path(&state.settings_path),
+        true,
+        None,
+    )
+    .await?;
+    if new.from_snapshot {
+        return Ok(format!(
+            "Couldn't run Ghostty at {}; kept what was found before",
+            escape_html(&path.to_string_lossy())
+        ));
+    }
+
+    let summary = refresh_summary(&old, &new, &old_themes, &new_themes);
+    let version_changed = old.path.is_none() || old.version != new.version;
+    let version = new.version.clone();
+    *state.ghostty.write().await = Arc::new(new);
+    if version_changed {
+        // Both came from the previous version.
+        *state.validation.write().await = None;
+        *state.updates.write().await = None;
+        state.publish(AppEvent::GhosttyChanged { version });
+    } else {
+        state.revalidate.notify_one();
+    }
+    Ok(summary)
+}
+
+async fn theme_names(state: &SharedState) -> Vec<String> {
+    state
+        .themes
+        .read()
+        .await
+        .iter()
+        .map(|t| t.name.clone())
+        .collect()
+}
+
+/// What a refresh found that wasn't there before, for its toast.
+fn refresh_summary(
+    old: &Ghostty,
+    new: &Ghostty,
+    old_themes: &[String],
+    new_themes: &[String],
+) -> String {
+    let added = |before: Vec<&str>, after: Vec<&str>| {
+        let before: HashSet<&str> = before.into_iter().collect();
+        after.into_iter().filter(|a| !before.contains(a)).count()
+    };
+    let mut found = Vec::new();
+    if old.path.is_none() || old.version != new.version {
+        found.push(format!("Ghostty {}", new.version));
+    }
+    let options = new
+        .schema
+        .options()
+        .len()
+        .saturating_sub(old.schema.options().len());
+    let fonts = added(
+        old.fonts.iter().map(|f| f.name.as_str()).collect(),
+        new.fonts.iter().map(|f| f.name.as_str()).collect(),
+    );
+    let themes = added(
+        old_themes.iter().map(String::as_str).collect(),
+        new_themes.iter().map(String::as_str).collect(),
+    );
+    for (count, one, many) in [
+        (options, "new option", "new options"),
+        (fonts, "new font family", "new font families"),
+        (themes, "new theme", "new themes"),
+    ] {
+        match count {
+            0 => {}
+            1 => found.push(format!("1 {}", one)),
+            n => found.push(format!("{} {}", n, many)),
+        }
+    }
+    if found.is_empty() {
+        "Refreshed; nothing new found".to_string()
+    } else {
+        escape_html(&format!("Found {}", found.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::{ConfigSchema, FontFamily};
+    use std::path::PathBuf;
+
+    fn ghostty(version: &str, fonts: &[&str]) -> Ghostty {
+        Ghostty {
+            path: Some(PathBuf::from("/usr/bin/ghostty")),
+            version: version.to_string(),
+            from_snapshot: false,
+            schema: ConfigSchema::new(Vec::new()),
+            fonts: fonts
+                .iter()
+                .map(|name| FontFamily {
+                    name: name.to_string(),
+                    styles: Vec::new(),
+                })
+                .collect(),
+            actions: Vec::new(),
+            default_keybinds: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_refresh_summary() {
+        let themes = vec!["Dracula".to_string()];
+        let old = ghostty("1.1.3", &["Menlo"]);
+        assert_eq!(
+            refresh_summary(&old, &ghostty("1.1.3", &["Menlo"]), &themes, &themes),
+            "Refreshed; nothing new found"
+        );
+
+        let more_themes = vec!["Nord".to_string(), "Dracula".to_string()];
+        let new = ghostty("1.2.0", &["Menlo", "Iosevka", "Fira Code"]);
+        assert_eq!(
+            refresh_summary(&old, &new, &themes, &more_themes),
+            "Found Ghostty 1.2.0, 2 new font families, 1 new theme"
+        );
+
+        // Removed fonts aren't news.
+        assert_eq!(
+            refresh_summary(&old, &ghostty("1.1.3", &[]), &themes, &[]),
+            "Refreshed; nothing new found"
+        );
+    }
+}
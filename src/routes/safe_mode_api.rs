@@ -0,0 +1,174 @@
+use std::fs;
+use std::path::Path;
+
+use axum::extract::{Request, State};
+use axum::http::Method;
+use axum::middleware::Next;
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use serde::Deserialize;
+
+use super::config_api::{escape_html, toast_html};
+use crate::app_state::SharedState;
+use crate::config::corruption::{backup_candidates, CorruptionProblem};
+use crate::config::file_io::{copy_atomic, write_atomic};
+use crate::error::AppError;
+
+/// Requests still allowed in safe mode, besides GETs of `/api`, `/static` and live updates.
+const ALLOWED_IN_SAFE_MODE: &[(Method, &str)] = &[
+    (Method::PUT, "/api/raw"),
+    (Method::POST, "/api/safe-mode/restore"),
+    (Method::POST, "/api/shutdown"),
+];
+
+/// While the config file is damaged, send pages to the raw editor and refuse changes
+/// other than fixing or restoring the file.
+pub async fn guard(State(state): State<SharedState>, request: Request, next: Next) -> Response {
+    if !state.in_safe_mode().await {
+        return next.run(request).await;
+    }
+
+    let path = request.uri().path().to_string();
+    let method = request.method().clone();
+    let allowed = ALLOWED_IN_SAFE_MODE
+        .iter()
+        .any(|(m, p)| *m == method && *p == path);
+    if allowed {
+        return next.run(request).await;
+    }
+    if method == Method::GET {
+        let is_page = !["/api/", "/static/", "/events", "/ws"]
+            .iter()
+            .any(|prefix| path.starts_with(prefix));
+        if is_page && path != "/editor" {
+            return Redirect::to("/editor").into_response();
+        }
+        return next.run(request).await;
+    }
+
+    (
+        [("X-Save-Blocked", "true")],
+        Html(toast_html(
+            "The config file is damaged. Fix it in the editor or restore a backup first.",
+            true,
+        )),
+    )
+        .into_response()
+}
+
+/// Safe-mode banner for the editor page; empty outside safe mode.
+pub async fn render_banner(state: &SharedState) -> String {
+    let path = state.user_config.read().await.file_path.clone();
+    let problems = state.safe_mode.read().await;
+    if problems.is_empty() {
+        return String::new();
+    }
+    let backups: Vec<String> = backup_candidates(&path)
+        .iter()
+        .filter_map(|backup| Some(backup.file_name()?.to_str()?.to_string()))
+        .collect();
+    render_safe_mode(&problems, &backups)
+}
+
+/// The detected problems, each linked to its line, and a restore button per backup.
+fn render_safe_mode(problems: &[CorruptionProblem], backups: &[String]) -> String {
+    let items: String = problems
+        .iter()
+        .map(|p| match p.line {
+            Some(line) => format!(
+                r#"<li><button type="button" onclick="jumpToLine({line})" class="font-semibold hover:underline">Line {line}</button>: {message}</li>"#,
+                line = line,
+                message = escape_html(&p.message)
+            ),
+            None => format!("<li>{}</li>", escape_html(&p.message)),
+        })
+        .collect();
+    let backups: String = backups
+        .iter()
+        .map(|name| {
+            format!(
+                r##"<form hx-post="/api/safe-mode/restore" hx-target="#toast-container" hx-swap="innerHTML" hx-confirm="Replace the config file with {name}?" class="inline"><input type="hidden" name="file" value="{name}"><button type="submit" class="px-3 py-1.5 text-sm font-medium text-white bg-red-600 rounded-lg hover:bg-red-700 transition-colors">Restore {name}</button></form>"##,
+                name = escape_html(name)
+            )
+        })
+        .collect();
+    let restore = if backups.is_empty() {
+        String::new()
+    } else {
+        format!(
+            r#"<div class="flex flex-wrap gap-2 mt-3">{}</div>"#,
+            backups
+        )
+    };
+
+    format!(
+        r#"<div role="alert" class="border rounded-lg p-4 mb-4 bg-red-50 border-red-300 text-red-800"><p class="font-medium mb-2">Safe mode: the config file is damaged, so nothing else can be changed until it is fixed.</p><ul class="text-sm space-y-1">{}</ul>{}</div>"#,
+        items, restore
+    )
+}
+
+#[derive(Deserialize)]
+pub struct RestoreForm {
+    /// File name of a backup next to the config file.
+    pub file: String,
+}
+
+/// POST /api/safe-mode/restore — replace the damaged config file with a backup.
+pub async fn restore_backup(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<RestoreForm>,
+) -> Result<Response, AppError> {
+    let path = state.user_config.read().await.file_path.clone();
+    let Some(backup) = backup_candidates(&path)
+        .into_iter()
+        .find(|b| b.file_name().and_then(|n| n.to_str()) == Some(form.file.as_str()))
+    else {
+        return Ok(Html(toast_html("No such backup", true)).into_response());
+    };
+
+    // Keep the damaged file around in case the backup is older than expected.
+    let mut damaged = path.clone().into_os_string();
+    damaged.push(".damaged");
+    copy_atomic(&path, Path::new(&damaged))?;
+    write_atomic(&path, fs::read(&backup)?)?;
+    if !state.reload_repaired().await? {
+        return Ok(Html(toast_html(
+            &format!(
+                "{} is damaged too; fix the file in the editor",
+                escape_html(&form.file)
+            ),
+            true,
+        ))
+        .into_response());
+    }
+    Ok((
+        [("HX-Redirect", "/")],
+        Html(toast_html("Config restored from backup", false)),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_safe_mode() {
+        let problems = [
+            CorruptionProblem {
+                line: Some(3),
+                message: "Unresolved merge conflict marker `<<<<<<<`".to_string(),
+            },
+            CorruptionProblem {
+                line: None,
+                message: "Something else".to_string(),
+            },
+        ];
+        let html = render_safe_mode(&problems, &["config.bak".to_string()]);
+        assert!(html.contains("jumpToLine(3)"));
+        assert!(html.contains("marker `&lt;&lt;&lt;&lt;&lt;&lt;&lt;`"));
+        assert!(html.contains(r#"name="file" value="config.bak""#));
+
+        let html = render_safe_mode(&problems, &[]);
+        assert!(!html.contains("/api/safe-mode/restore"));
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::{BTreeMap, HashMap};
+
+use axum::extract::State;
+use axum::response::Html;
+use serde::Deserialize;
+
+use super::config_api::{escape_html, toast_html};
+use crate::app_state::SharedState;
+use crate::config::model::Category;
+use crate::error::AppError;
+
+/// GET /api/save-targets — the per-category save target form.
+pub async fn get_save_targets(State(state): State<SharedState>) -> Html<String> {
+    let settings = state.settings.read().await;
+    Html(render_save_targets(&settings.save_targets))
+}
+
+/// POST /api/save-targets — store the file new keys of each category go to.
+///
+/// Fields are named by category slug; a blank field means the main config file.
+pub async fn set_save_targets(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<HashMap<String, String>>,
+) -> Result<Html<String>, AppError> {
+    let targets: BTreeMap<String, String> = Category::all()
+        .into_iter()
+        .filter_map(|cat| {
+            let target = form.get(cat.slug())?.trim();
+            (!target.is_empty()).then(|| (cat.slug().to_string(), target.to_string()))
+        })
+        .collect();
+
+    let mut settings = state.settings.write().await;
+    settings.save_targets = targets;
+    settings.save(&state.settings_path)?;
+
+    Ok(Html(toast_html("Save targets updated", false)))
+}
+
+#[derive(Deserialize)]
+pub struct AnnotateForm {
+    /// Sent by the checkbox only while it is checked.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// POST /api/settings/annotate — turn comments for newly added keys on or off.
+pub async fn set_annotate(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<AnnotateForm>,
+) -> Result<Html<String>, AppError> {
+    let mut settings = state.settings.write().await;
+    settings.annotate_new_keys = form.enabled;
+    settings.save(&state.settings_path)?;
+
+    let message = if form.enabled {
+        "New keys will be saved with comments"
+    } else {
+        "New keys will be saved without comments"
+    };
+    Ok(Html(toast_html(message, false)))
+}
+
+/// One text field per category, prefilled with its current target.
+fn render_save_targets(targets: &BTreeMap<String, String>) -> String {
+    let mut html = String::from(
+        r##"<form hx-post="/api/save-targets" hx-target="#toast-container" hx-swap="innerHTML" class="bg-white border border-gray-200 rounded-xl p-4 space-y-3"><div><h3 class="text-sm font-semibold text-gray-900">Save targets</h3><p class="text-xs text-gray-500 mt-1">New keys of a category are written to this file, relative to the config file, and it is added as a <span class="font-mono">config-file</span> include. Leave blank to use the main config. Keys already set somewhere stay where they are.</p></div><div class="grid grid-cols-2 gap-3">"##,
+    );
+    for cat in Category::all() {
+        let slug = cat.slug();
+        let value = targets.get(slug).map(String::as_str).unwrap_or("");
+        html.push_str(&format!(
+            r#"<label class="text-sm text-gray-700">{name}<input type="text" name="{slug}" value="{value}" placeholder="config" class="mt-1 w-full font-mono text-sm border border-gray-300 rounded-lg px-2 py-1 focus:outline-none focus:ring-2 focus:ring-indigo-500"></label>"#,
+            name = escape_html(cat.display_name()),
+            slug = slug,
+            value = escape_html(value),
+        ));
+    }
+    html.push_str(r#"</div><div class="flex justify-end"><button type="submit" class="px-3 py-1.5 text-sm font-medium text-white bg-indigo-600 rounded-lg hover:bg-indigo-700 transition-colors">Save targets</button></div></form>"#);
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_save_targets() {
+        let targets = BTreeMap::from([("colors".to_string(), "colors.conf".to_string())]);
+        let html = render_save_targets(&targets);
+        assert!(html.contains(r#"name="colors" value="colors.conf""#));
+        assert!(html.contains(r#"name="keybindings" value="""#));
+        assert_eq!(html.matches("<input").count(), Category::all().len());
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+
+use axum::extract::{Query, State};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+use crate::app_state::{AppState, SharedState};
+use crate::config::conditions::unmet_condition;
+use crate::config::model::ConfigOption;
+use crate::error::AppError;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SchemaQuery {
+    /// Only options in this category, by slug.
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+/// A schema option with links to its field in the UI and to Ghostty's docs.
+#[derive(Serialize)]
+pub struct SchemaEntry<'a> {
+    #[serde(flatten)]
+    pub option: &'a ConfigOption,
+    pub anchor: String,
+    pub permalink: String,
+    pub docs_url: String,
+}
+
+impl<'a> From<&'a ConfigOption> for SchemaEntry<'a> {
+    fn from(option: &'a ConfigOption) -> Self {
+        SchemaEntry {
+            option,
+            anchor: option.anchor(),
+            permalink: option.permalink(),
+            docs_url: option.docs_url(),
+        }
+    }
+}
+
+/// GET /api/schema — every discovered config option as JSON.
+#[utoipa::path(
+    get,
+    path = "/api/schema",
+    tag = "Config",
+    summary = "Every discovered config option",
+    params(SchemaQuery),
+    responses((status = 200, description = "OK", body = Object)),
+)]
+pub async fn schema(
+    State(state): State<SharedState>,
+    Query(query): Query<SchemaQuery>,
+) -> Json<serde_json::Value> {
+    let ghostty = state.ghostty().await;
+    let entries: Vec<SchemaEntry> = ghostty
+        .schema
+        .options()
+        .iter()
+        .filter(|o| {
+            query
+                .category
+                .as_deref()
+                .is_none_or(|slug| o.category.slug() == slug)
+        })
+        .map(SchemaEntry::from)
+        .collect();
+    Json(serde_json::json!(entries))
+}
+
+/// GET /api/schema/inactive — the options that have no effect on the edited machine with
+/// the config as it stands, each with why.
+#[utoipa::path(
+    get,
+    path = "/api/schema/inactive",
+    tag = "Config",
+    summary = "Options with no effect on this machine or with the config as it stands, each with why",
+    responses((status = 200, description = "OK", body = BTreeMap<String, String>)),
+)]
+pub async fn inactive(State(state): State<SharedState>) -> Json<BTreeMap<String, String>> {
+    Json(inactive_options(&state).await)
+}
+
+#[derive(Deserialize)]
+pub struct PlatformsForm {
+    /// Sent by the checkbox only while it is checked.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// POST /api/settings/platforms — list the options of every platform, or only
+/// those that affect the edited machine. The page reloads to show the change.
+pub async fn set_show_all_platforms(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<PlatformsForm>,
+) -> Result<Response, AppError> {
+    let mut settings = state.settings.write().await;
+    settings.show_all_platforms = form.enabled;
+    settings.save(&state.settings_path)?;
+    Ok(([("HX-Refresh", "true")], Html(String::new())).into_response())
+}
+
+/// Why each option whose platform or prerequisite doesn't hold has no effect, by
+/// key, counting unsaved edits and includes; options that do have an effect are
+/// left out.
+pub(crate) async fn inactive_options(state: &AppState) -> BTreeMap<String, String> {
+    let ghostty = state.ghostty().await;
+    let user_config = state.user_config.read().await;
+    let included = state.included.read().await;
+    let value_of = |key: &str| {
+        user_config
+            .get(key)
+            .or_else(|| included.iter().rev().find_map(|c| c.get(key)))
+            .map(String::from)
+            .or_else(|| {
+                ghostty
+                    .schema
+                    .find_option(key)
+                    .map(|o| o.default_value.clone())
+            })
+            .unwrap_or_default()
+    };
+    ghostty
+        .schema
+        .options()
+        .iter()
+        .filter_map(|option| {
+            let reason = unmet_condition(option, state.platform, value_of)?;
+            Some((option.key.clone(), reason))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::{Category, ConfigValueType};
+
+    #[test]
+    fn test_schema_entry_json() {
+        let option = ConfigOption {
+            key: "theme".to_string(),
+            default_value: String::new(),
+            documentation: "Theme".to_string(),
+            value_type: ConfigValueType::Text,
+            category: Category::Appearance,
+            is_repeatable: false,
+            conditions: Default::default(),
+        };
+        let json = serde_json::to_value(SchemaEntry::from(&option)).unwrap();
+        assert_eq!(json["key"], "theme");
+        assert_eq!(json["permalink"], "/category/appearance#field-theme");
+        assert_eq!(
+            json["docs_url"],
+            "https://ghostty.org/docs/config/reference#theme"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use axum::extract::State;
+use axum::response::{Html, IntoResponse, Response};
+
+use super::config_api::{escape_html, toast_html};
+use crate::app_state::SharedState;
+use crate::error::AppError;
+use crate::session::PendingSession;
+
+/// GET /api/session — the banner offering to restore edits a previous run left
+/// unsaved; nothing when there are none.
+#[utoipa::path(
+    get,
+    path = "/api/session",
+    tag = "Config",
+    summary = "Banner offering to restore edits the last run left unsaved",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn banner(State(state): State<SharedState>) -> Html<String> {
+    Html(
+        state
+            .pending_session
+            .read()
+            .await
+            .as_ref()
+            .map(render_banner)
+            .unwrap_or_default(),
+    )
+}
+
+/// POST /api/session/restore — put the previous run's unsaved edits back in
+/// memory, on top of any made since. They stay unsaved.
+#[utoipa::path(
+    post,
+    path = "/api/session/restore",
+    tag = "Config",
+    summary = "Put the last run's unsaved edits back in memory",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn restore(State(state): State<SharedState>) -> Result<Response, AppError> {
+    let Some(session) = state.pending_session.write().await.take() else {
+        return Ok(Html(toast_html("Nothing to restore", true)).into_response());
+    };
+    for (key, values) in &session.edits {
+        state.replace_values(key, values).await?;
+    }
+    state.mark_unsaved().await;
+
+    // Reload so every panel shows the restored values.
+    Ok((
+        [("HX-Redirect", "/")],
+        Html(toast_html("Unsaved edits restored", false)),
+    )
+        .into_response())
+}
+
+/// POST /api/session/discard — drop the previous run's unsaved edits.
+#[utoipa::path(
+    post,
+    path = "/api/session/discard",
+    tag = "Config",
+    summary = "Drop the last run's unsaved edits",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn discard(State(state): State<SharedState>) -> Html<String> {
+    state.pending_session.write().await.take();
+    // Rewrite the file with just this run's edits, if any.
+    state.persist_session().await;
+    Html(String::new())
+}
+
+fn render_banner(session: &PendingSession) -> String {
+    let count = session.edits.len();
+    let keys: Vec<String> = session
+        .edits
+        .keys()
+        .map(|key| format!(r#"<span class="font-mono">{}</span>"#, escape_html(key)))
+        .collect();
+    format!(
+        r##"<div class="border rounded-lg p-4 bg-sky-50 border-sky-300 text-sky-900 flex items-start justify-between gap-4">
+            <div>
+                <div class="font-medium">The last session ended with {count} unsaved {edits}</div>
+                <div class="text-sm mt-1">{keys}</div>
+            </div>
+            <div class="flex items-center gap-2 shrink-0">
+                <button hx-post="/api/session/restore" hx-target="#toast-container" hx-swap="innerHTML"
+                        class="px-3 py-1.5 text-sm font-medium text-white bg-sky-600 rounded-lg hover:bg-sky-700">Restore unsaved session</button>
+                <button hx-post="/api/session/discard" hx-target="#session-banner" hx-swap="innerHTML"
+                        hx-confirm="Discard these edits? They can't be recovered afterwards."
+                        class="px-3 py-1.5 text-sm font-medium text-gray-700 bg-white border border-gray-300 rounded-lg hover:bg-gray-50">Discard</button>
+            </div>
+        </div>"##,
+        edits = if count == 1 { "edit" } else { "edits" },
+        keys = keys.join(", "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn test_render_banner() {
+        let session = PendingSession {
+            config_path: "config".into(),
+            edits: BTreeMap::from([
+                ("font-size".to_string(), vec!["14".to_string()]),
+                ("title".to_string(), Vec::new()),
+            ]),
+        };
+        let html = render_banner(&session);
+        assert!(html.contains("2 unsaved edits"));
+        assert!(html.contains(r#"<span class="font-mono">font-size</span>, "#));
+        assert!(html.contains(r#"hx-post="/api/session/restore""#));
+    }
+}
@@ -0,0 +1,134 @@
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::config_api::{escape_html, toast_html};
+use crate::app_state::SharedState;
+use crate::config::export_filter::ExportFilter;
+use crate::config::file_io::render_config;
+use crate::error::AppError;
+use crate::share::{encode_share, publish_gist};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ShareQuery {
+    /// Comma-separated category slugs to share only those, as for `/api/export`.
+    #[serde(default)]
+    pub categories: Option<String>,
+    #[serde(default)]
+    pub changed_only: bool,
+    /// `json` for the code and link as JSON instead of HTML.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// GET /api/share — a link carrying the config in its fragment. Opening it on
+/// the import page shows what importing it would change.
+#[utoipa::path(
+    get,
+    path = "/api/share",
+    tag = "Import/Export",
+    summary = "A link carrying the config compressed in its fragment; ?categories= and ?changed_only= as for export",
+    params(ShareQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn share_link(
+    State(state): State<SharedState>,
+    Query(query): Query<ShareQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let text = shared_text(&state, query.categories.as_deref(), query.changed_only).await?;
+    let code = encode_share(&text);
+    let host = headers
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or("127.0.0.1");
+    let url = format!("http://{}/import-export#share={}", host, code);
+
+    if query.format.as_deref() == Some("json") {
+        return Ok(Json(serde_json::json!({ "code": code, "url": url })).into_response());
+    }
+    Ok(Html(render_share_link(&url, text.lines().count())).into_response())
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct GistForm {
+    /// A GitHub token with the `gist` scope. It is not stored.
+    pub token: String,
+    /// Make the Gist public; it is secret (unlisted) otherwise.
+    #[serde(default)]
+    pub public: bool,
+    #[serde(default)]
+    pub categories: Option<String>,
+    #[serde(default)]
+    pub changed_only: bool,
+}
+
+/// POST /api/share — publish the config as a GitHub Gist.
+#[utoipa::path(
+    post,
+    path = "/api/share",
+    tag = "Import/Export",
+    summary = "Publish the config as a GitHub Gist with the given token",
+    request_body(content = GistForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn share_gist(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<GistForm>,
+) -> Result<Html<String>, AppError> {
+    let text = shared_text(&state, form.categories.as_deref(), form.changed_only).await?;
+    let gist = publish_gist(&form.token, &text, form.public).await?;
+    tracing::info!(url = %gist.url, "published config as a Gist");
+    Ok(Html(format!(
+        r#"{toast}<div id="share-result" hx-swap-oob="innerHTML"><p class="text-sm text-gray-700">Published: <a href="{url}" target="_blank" rel="noopener" class="text-indigo-600 hover:underline break-all">{url}</a></p></div>"#,
+        toast = toast_html("Published the config as a Gist", false),
+        url = escape_html(&gist.url),
+    )))
+}
+
+/// The config text to share, limited the way an export can be.
+async fn shared_text(
+    state: &SharedState,
+    categories: Option<&str>,
+    changed_only: bool,
+) -> Result<String, AppError> {
+    let filter = ExportFilter {
+        categories: ExportFilter::parse_categories(categories.unwrap_or(""))?,
+        changed_only,
+    };
+    let config = filter.apply(
+        &*state.user_config.read().await,
+        &state.ghostty().await.schema,
+    );
+    if config.all_set_values().is_empty() {
+        return Err(AppError::Config(
+            "There are no settings to share".to_string(),
+        ));
+    }
+    Ok(render_config(&config))
+}
+
+fn render_share_link(url: &str, lines: usize) -> String {
+    format!(
+        r#"<div class="flex items-center gap-2"><label for="share-url" class="sr-only">Share link</label><input id="share-url" type="text" readonly value="{url}" onclick="this.select()" class="flex-1 min-w-0 px-3 py-1.5 border border-gray-300 rounded-lg text-sm font-mono bg-gray-50"><button type="button" onclick="navigator.clipboard.writeText(document.getElementById('share-url').value)" class="px-3 py-1.5 bg-gray-800 text-white rounded-lg text-sm font-medium hover:bg-gray-900 transition-colors">Copy</button></div><p class="mt-2 text-xs text-gray-500">{lines} {noun} of config, compressed into the part of the link after <code>#</code>, which browsers never send to a server. Whoever opens it in ghostty-config (on their own port if it differs) reviews the changes before importing them.</p>"#,
+        url = escape_html(url),
+        lines = lines,
+        noun = if lines == 1 { "line" } else { "lines" },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_share_link() {
+        let html = render_share_link("http://127.0.0.1:3456/import-export#share=a_b-c", 2);
+        assert!(html.contains(r#"value="http://127.0.0.1:3456/import-export#share=a_b-c""#));
+        assert!(html.contains("2 lines of config"));
+    }
+}
@@ -0,0 +1,62 @@
+use axum::extract::State;
+use axum::response::Html;
+use serde::Deserialize;
+
+use super::config_api::toast_html;
+use crate::app_state::SharedState;
+use crate::error::AppError;
+
+#[derive(Deserialize)]
+pub struct ShutdownForm {
+    /// Quit even though there are unsaved changes.
+    #[serde(default)]
+    pub discard: bool,
+}
+
+/// POST /api/shutdown — stop the server. With unsaved changes, asks first unless
+/// `discard` is set.
+pub async fn shutdown(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<ShutdownForm>,
+) -> Result<Html<String>, AppError> {
+    let unsaved = state.unsaved_count().await;
+    if unsaved > 0 && !form.discard {
+        return Ok(Html(render_confirm(unsaved)));
+    }
+    // The response still goes out: the server finishes in-flight requests first.
+    state.shutdown.notify_one();
+    Ok(Html(toast_html(
+        "Ghostty Config has stopped. You can close this tab.",
+        false,
+    )))
+}
+
+/// Asks whether to quit without saving. Marked `data-persist` so it stays up until
+/// answered.
+fn render_confirm(unsaved: usize) -> String {
+    let changes = if unsaved == 1 { "change" } else { "changes" };
+    format!(
+        r##"<div role="alertdialog" aria-label="Quit without saving?" data-persist class="bg-white border border-amber-300 px-4 py-3 rounded-lg shadow-lg text-sm">
+<p class="text-gray-800 mb-2">You have {unsaved} unsaved {changes}. Quit anyway?</p>
+<div class="flex gap-2 justify-end">
+<a href="/diff" class="px-3 py-1.5 text-sm text-gray-700 border border-gray-300 rounded-lg hover:bg-gray-50">Review changes</a>
+<button type="button" hx-post="/api/shutdown" hx-vals='{{"discard": "true"}}' hx-target="#toast-container" hx-swap="innerHTML" data-autofocus
+        class="px-3 py-1.5 text-sm font-medium text-white bg-amber-600 rounded-lg hover:bg-amber-700">Quit without saving</button>
+</div>
+</div>"##
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_confirm() {
+        let html = render_confirm(2);
+        assert!(html.contains("2 unsaved changes"));
+        assert!(html.contains(r#"hx-vals='{"discard": "true"}'"#));
+        assert!(html.contains("data-persist"));
+        assert!(render_confirm(1).contains("1 unsaved change."));
+    }
+}
@@ -0,0 +1,184 @@
+use axum::extract::State;
+use axum::response::{Html, IntoResponse, Response};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use super::config_api::{escape_html, publish_toast, write_and_reload};
+use crate::app_state::{AppState, SharedState};
+use crate::config::file_io::render_config;
+use crate::config::starter::{starter_config, Padding, StarterChoices};
+use crate::config::theme_selection::ThemeSelection;
+use crate::error::AppError;
+
+/// Whether there's no config to speak of yet: the file is missing or empty, and
+/// nothing has been edited. The index page then offers the wizard at `/welcome`.
+pub(crate) async fn is_first_run(state: &AppState) -> bool {
+    state.user_config.read().await.entries.is_empty()
+        && state.included.read().await.is_empty()
+        && state.unsaved.read().await.is_empty()
+}
+
+/// The first-run wizard's answers, as the `/welcome` form sends them.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct StarterForm {
+    /// `light`, `dark`, or `system` to follow the OS with both themes.
+    #[serde(default)]
+    pub appearance: String,
+    #[serde(default)]
+    pub light_theme: String,
+    #[serde(default)]
+    pub dark_theme: String,
+    #[serde(default)]
+    pub font_family: String,
+    #[serde(default)]
+    pub font_size: String,
+    #[serde(default)]
+    pub cursor_style: String,
+    /// See `Padding::slug`; empty for the default.
+    #[serde(default)]
+    pub padding: String,
+    /// A keybinding preset name, or empty for none.
+    #[serde(default)]
+    pub keybind_preset: String,
+}
+
+impl StarterForm {
+    fn choices(&self) -> Result<StarterChoices, AppError> {
+        let (light, dark) = (self.light_theme.trim(), self.dark_theme.trim());
+        let theme = match self.appearance.as_str() {
+            "light" => ThemeSelection::Single(light.to_string()),
+            "dark" => ThemeSelection::Single(dark.to_string()),
+            "system" | "" if !light.is_empty() && !dark.is_empty() => ThemeSelection::Paired {
+                light: light.to_string(),
+                dark: dark.to_string(),
+            },
+            "system" | "" => ThemeSelection::None,
+            other => {
+                return Err(AppError::Config(format!(
+                    "Unknown appearance: {} (light, dark, or system)",
+                    other
+                )))
+            }
+        };
+        let padding = match self.padding.as_str() {
+            "" => Padding::Default,
+            slug => Padding::parse(slug)
+                .ok_or_else(|| AppError::Config(format!("Unknown padding: {}", slug)))?,
+        };
+        Ok(StarterChoices {
+            theme: theme.to_value(),
+            font_family: self.font_family.clone(),
+            font_size: self.font_size.clone(),
+            cursor_style: self.cursor_style.clone(),
+            padding,
+            keybind_preset: Some(self.keybind_preset.clone()).filter(|p| !p.is_empty()),
+        })
+    }
+}
+
+/// POST /api/starter/preview — the config the wizard's answers make, for review
+/// before it is written.
+#[utoipa::path(
+    post,
+    path = "/api/starter/preview",
+    tag = "Config",
+    summary = "The commented starter config the first-run wizard's answers make",
+    request_body(content = StarterForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn preview(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<StarterForm>,
+) -> Result<Html<String>, AppError> {
+    let path = state.user_config.read().await.file_path.clone();
+    let config = starter_config(&form.choices()?, &state.ghostty().await.schema, path);
+    Ok(Html(render_preview(
+        &render_config(&config),
+        &config.file_path.to_string_lossy(),
+    )))
+}
+
+/// POST /api/starter — write the config the wizard's answers make and go to the
+/// home page. Refused once there is a config, so it can't overwrite one.
+#[utoipa::path(
+    post,
+    path = "/api/starter",
+    tag = "Config",
+    summary = "Write the starter config; refused once there is a config",
+    request_body(content = StarterForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn create(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<StarterForm>,
+) -> Result<Response, AppError> {
+    let choices = form.choices()?;
+    if !is_first_run(&state).await {
+        return Err(AppError::Config(
+            "There is a config already; edit it instead of starting a new one".to_string(),
+        ));
+    }
+    {
+        let mut user_config = state.user_config.write().await;
+        let path = user_config.file_path.clone();
+        *user_config = starter_config(&choices, &state.ghostty().await.schema, path);
+    }
+    state.bump_revision();
+    write_and_reload(&state, false).await?;
+    let html = publish_toast(&state, "Starter config saved", false);
+    Ok(([("HX-Redirect", "/")], Html(html)).into_response())
+}
+
+/// The generated text, with a button that writes it.
+fn render_preview(text: &str, path: &str) -> String {
+    format!(
+        r##"<div class="bg-white rounded-xl border border-gray-200 p-5">
+<h3 class="text-sm font-semibold text-gray-900 mb-1">Your starter config</h3>
+<p class="text-xs text-gray-500 mb-3">It will be saved to <span class="font-mono">{path}</span>. Change an answer and review again, or save it and keep editing from the home page.</p>
+<pre class="text-sm font-mono bg-gray-50 border border-gray-200 rounded-lg p-4 overflow-x-auto whitespace-pre">{text}</pre>
+<button type="button" hx-post="/api/starter" hx-include="#starter-form" hx-target="#toast-container" hx-swap="innerHTML" class="mt-4 px-4 py-2 text-sm font-medium text-white bg-indigo-600 rounded-lg hover:bg-indigo-700">Save this config</button>
+</div>"##,
+        path = escape_html(path),
+        text = escape_html(text),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_choices() {
+        let form = StarterForm {
+            appearance: "system".to_string(),
+            light_theme: "Catppuccin Latte".to_string(),
+            dark_theme: "Catppuccin Mocha".to_string(),
+            padding: "compact".to_string(),
+            keybind_preset: String::new(),
+            ..Default::default()
+        };
+        let choices = form.choices().unwrap();
+        assert_eq!(
+            choices.theme,
+            "light:Catppuccin Latte,dark:Catppuccin Mocha"
+        );
+        assert_eq!(choices.padding, Padding::Compact);
+        assert_eq!(choices.keybind_preset, None);
+
+        let dark = StarterForm {
+            appearance: "dark".to_string(),
+            ..form
+        };
+        assert_eq!(dark.choices().unwrap().theme, "Catppuccin Mocha");
+
+        let bad = StarterForm {
+            padding: "huge".to_string(),
+            ..Default::default()
+        };
+        assert!(bad.choices().is_err());
+
+        let html = render_preview("theme = <b>\n", "/home/me/.config/ghostty/config");
+        assert!(html.contains("theme = &lt;b&gt;"));
+        assert!(html.contains(r##"hx-include="#starter-form""##));
+    }
+}
@@ -1,30 +1,97 @@
-use axum::extract::{Query, State};
-use axum::response::Html;
+use std::collections::HashMap;
+use std::path::{Path as FsPath, PathBuf};
+
+use axum::extract::{Path, Query, State};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
 use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
 
+use super::config_api::{escape_html, publish_toast, toast_html};
+use super::preview_api::image_layer_style;
 use crate::app_state::SharedState;
+use crate::cli::themes::{
+    delete_user_theme, load_themes, update_user_theme, user_theme_dir, write_user_theme,
+};
+use crate::config::background::{active_theme, resolve_background, with_alpha};
+use crate::config::contrast::{theme_contrast, ContrastReport};
+use crate::config::custom_theme::{theme_from_fields, PALETTE_NAMES};
+use crate::config::model::{ThemeInfo, ThemeOrigin};
+use crate::config::theme_metadata::{theme_family, theme_tags, ThemeTag};
+use crate::config::theme_overrides::{
+    clear_theme_overrides, find_theme_overrides, render_theme_file, ThemeOverride,
+};
+use crate::config::theme_selection::{ThemeSelection, ThemeVariant};
 use crate::error::AppError;
+use crate::theme_state::theme_state_path;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ThemeQuery {
     pub search: Option<String>,
     pub filter: Option<String>, // "all", "dark", "light"
+    /// Only variants of this family, e.g. `Gruvbox`; see `theme_family`.
+    pub family: Option<String>,
+    /// Only themes with this tag slug, e.g. `pastel`.
+    pub tag: Option<String>,
+    /// "all", "favorites", or "recent" (most recently applied first).
+    pub view: Option<String>,
 }
 
 /// GET /api/themes — list themes with optional search/filter.
+#[utoipa::path(
+    get,
+    path = "/api/themes",
+    tag = "Themes",
+    summary = "Theme cards, filtered by `search`, `filter`, `family`, `tag`, and `view`",
+    params(ThemeQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
 pub async fn list_themes(
     State(state): State<SharedState>,
     Query(query): Query<ThemeQuery>,
 ) -> Result<Html<String>, AppError> {
+    let selection = ThemeSelection::parse(&state.value_of("theme").await.unwrap_or_default());
     let user_config = state.user_config.read().await;
-    let current_theme = user_config.get("theme").unwrap_or("").to_string();
+    let themes = state.themes.read().await;
+    let layers = resolve_background(&user_config, &themes);
+    let image_layer = layers
+        .image
+        .as_ref()
+        .map(|image| {
+            format!(
+                "<div class=\"absolute inset-0 pointer-events-none\" aria-hidden=\"true\" style=\"{}\"></div>",
+                image_layer_style(image)
+            )
+        })
+        .unwrap_or_default();
 
     let search = query.search.unwrap_or_default().to_lowercase();
     let filter = query.filter.unwrap_or_else(|| "all".to_string());
+    let family = query
+        .family
+        .filter(|f| !f.is_empty())
+        .map(|f| f.to_lowercase());
+    let tag = query.tag.as_deref().and_then(ThemeTag::from_slug);
+    let theme_state = state.theme_state.read().await;
+    let view = query.view.as_deref().unwrap_or("all");
+    let recent_rank = |theme: &ThemeInfo| theme_state.recent.iter().position(|r| *r == theme.name);
+
+    let mut listed: Vec<&ThemeInfo> = themes
+        .iter()
+        .filter(|theme| match view {
+            "favorites" => theme_state.favorites.contains(&theme.name),
+            "recent" => recent_rank(theme).is_some(),
+            _ => true,
+        })
+        .collect();
+    if view == "recent" {
+        listed.sort_by_key(|theme| recent_rank(theme));
+    }
 
     let mut html = String::new();
 
-    for theme in &state.themes {
+    for theme in listed {
         if !search.is_empty() && !theme.name.to_lowercase().contains(&search) {
             continue;
         }
@@ -35,80 +102,834 @@ pub async fn list_themes(
             _ => {}
         }
 
-        let is_active = theme.name == current_theme;
-        let active_class = if is_active {
-            "ring-2 ring-indigo-500"
-        } else {
-            "hover:ring-2 hover:ring-gray-400"
-        };
+        if family
+            .as_ref()
+            .is_some_and(|f| theme_family(&theme.name).to_lowercase() != *f)
+        {
+            continue;
+        }
+        if tag.is_some_and(|t| !theme_tags(theme).contains(&t)) {
+            continue;
+        }
 
-        let palette_swatches: String = theme.palette[..8]
-            .iter()
-            .filter(|c| !c.is_empty())
-            .map(|c| {
-                let mut s = String::new();
-                s.push_str(
-                    "<span class=\"w-4 h-4 rounded-full inline-block\" style=\"background-color: ",
-                );
-                s.push_str(c);
-                s.push_str("\"></span>");
-                s
-            })
-            .collect::<Vec<_>>()
-            .join("");
-
-        let active_badge = if is_active {
-            "<span class=\"text-xs bg-indigo-100 text-indigo-700 px-2 py-0.5 rounded-full\">Active</span>"
-        } else {
-            ""
-        };
+        html.push_str(&render_theme_card(
+            theme,
+            active_label(&selection, &theme.name),
+            theme_state.favorites.contains(&theme.name),
+            &with_alpha(&theme.background, layers.opacity),
+            &image_layer,
+        ));
+    }
 
-        html.push_str(
-            "<div class=\"rounded-xl border border-gray-200 p-3 cursor-pointer transition-all ",
-        );
-        html.push_str(active_class);
-        html.push_str("\" hx-post=\"/api/themes/apply?name=");
-        html.push_str(&theme.name);
-        html.push_str("\" hx-target=\"#toast-container\" hx-swap=\"innerHTML\" onclick=\"setTimeout(function(){location.reload()},500)\">");
-        html.push_str(
-            "<div class=\"rounded-lg h-20 mb-2 flex items-end p-2\" style=\"background-color: ",
+    let empty = match view {
+        "favorites" => "No favorites yet. Star a theme to keep it here.",
+        "recent" => "Themes you apply show up here.",
+        _ => "",
+    };
+    if html.is_empty() && !empty.is_empty() {
+        html = format!(
+            r#"<p class="col-span-full text-center py-12 text-gray-400">{}</p>"#,
+            empty
         );
-        html.push_str(&theme.background);
-        html.push_str("; color: ");
-        html.push_str(&theme.foreground);
-        html.push_str("\"><span class=\"text-xs font-mono opacity-80\">$ ghostty</span></div>");
-        html.push_str("<div class=\"flex items-center justify-between mb-1\"><span class=\"font-medium text-sm truncate\">");
-        html.push_str(&theme.name);
-        html.push_str("</span>");
-        html.push_str(active_badge);
-        html.push_str("</div><div class=\"flex gap-1 mt-1\">");
-        html.push_str(&palette_swatches);
-        html.push_str("</div></div>");
     }
 
     Ok(Html(html))
 }
 
-#[derive(Deserialize)]
+/// The badge for a theme the config uses: "Active" for a single theme, or which
+/// halves of a light/dark pair it is. `None` when it isn't used.
+pub(crate) fn active_label(selection: &ThemeSelection, name: &str) -> Option<&'static str> {
+    match (selection, selection.variants_of(name).as_slice()) {
+        (_, []) => None,
+        (ThemeSelection::Paired { .. }, [variant]) => Some(variant.label()),
+        (ThemeSelection::Paired { .. }, _) => Some("Light + dark"),
+        _ => Some("Active"),
+    }
+}
+
+/// One selectable theme card. Cards act as toggle buttons: Enter or Space applies the
+/// theme, and `data-focus-key` lets focus return to the card after the page reloads.
+/// `#theme-variant` is sent along so a click can set one half of a light/dark pair.
+fn render_theme_card(
+    theme: &ThemeInfo,
+    active: Option<&str>,
+    favorite: bool,
+    card_background: &str,
+    image_layer: &str,
+) -> String {
+    let is_active = active.is_some();
+    let active_class = if is_active {
+        "ring-2 ring-indigo-500"
+    } else {
+        "hover:ring-2 hover:ring-gray-400"
+    };
+
+    let palette_swatches: String = theme.palette[..8]
+        .iter()
+        .filter(|c| !c.is_empty())
+        .map(|c| {
+            let mut s = String::new();
+            s.push_str(
+                "<span class=\"w-4 h-4 rounded-full inline-block\" style=\"background-color: ",
+            );
+            s.push_str(c);
+            s.push_str("\"></span>");
+            s
+        })
+        .collect::<Vec<_>>()
+        .join("");
+
+    let active_badge = if let Some(label) = active {
+        format!(
+            "<span class=\"text-xs bg-indigo-100 text-indigo-700 px-2 py-0.5 rounded-full\">{}</span>",
+            label
+        )
+    } else if theme.custom {
+        "<span class=\"text-xs bg-purple-100 text-purple-700 px-2 py-0.5 rounded-full\">Custom</span>"
+            .to_string()
+    } else if theme.origin == ThemeOrigin::User {
+        "<span class=\"text-xs bg-teal-100 text-teal-700 px-2 py-0.5 rounded-full\">User</span>"
+            .to_string()
+    } else {
+        String::new()
+    };
+    let name = escape_html(&theme.name);
+
+    let mut html = String::new();
+    html.push_str(
+        "<div class=\"rounded-xl border border-gray-200 p-3 cursor-pointer transition-all focus:outline-none focus-visible:ring-2 focus-visible:ring-indigo-600 ",
+    );
+    html.push_str(active_class);
+    html.push_str("\" role=\"button\" tabindex=\"0\" aria-pressed=\"");
+    html.push_str(if is_active { "true" } else { "false" });
+    html.push_str("\" aria-label=\"Apply theme ");
+    html.push_str(&name);
+    html.push_str("\" data-focus-key=\"theme:");
+    html.push_str(&name);
+    html.push_str("\" data-theme-name=\"");
+    html.push_str(&name);
+    html.push_str("\" hx-post=\"/api/themes/apply?name=");
+    html.push_str(&theme.name);
+    html.push_str("\" hx-trigger=\"click, keydown[key=='Enter'||key==' ']\" hx-include=\"#theme-variant\" hx-target=\"#toast-container\" hx-swap=\"innerHTML\" hx-on::after-request=\"if(event.detail.successful) setTimeout(() => location.reload(), 500)\">");
+    html.push_str(
+        "<div class=\"relative overflow-hidden rounded-lg h-20 mb-2 flex items-end p-2\" aria-hidden=\"true\" style=\"background-color: ",
+    );
+    html.push_str(card_background);
+    html.push_str("; color: ");
+    html.push_str(&theme.foreground);
+    html.push_str("\">");
+    html.push_str(image_layer);
+    html.push_str("<span class=\"relative text-xs font-mono opacity-80\">$ ghostty</span></div>");
+    html.push_str("<div class=\"flex items-center justify-between mb-1\"><span class=\"font-medium text-sm truncate\">");
+    html.push_str(&name);
+    html.push_str("</span><span class=\"flex items-center gap-1\">");
+    html.push_str(&active_badge);
+    html.push_str(&render_contrast_warning(theme));
+    html.push_str(&render_favorite_button(&theme.name, favorite));
+    html.push_str("</span></div><div class=\"flex gap-1 mt-1\" aria-hidden=\"true\">");
+    html.push_str(&palette_swatches);
+    html.push_str("</div>");
+    html.push_str(&render_tag_chips(&theme_tags(theme)));
+    html.push_str("</div>");
+    html
+}
+
+/// A warning on a theme card listing the colors that fall below WCAG AA on its
+/// background; nothing when all of them pass.
+pub(crate) fn render_contrast_warning(theme: &ThemeInfo) -> String {
+    let report = theme_contrast(theme);
+    let failing: Vec<String> = report
+        .warnings()
+        .map(|c| format!("{} {:.1}:1", c.label, c.ratio))
+        .collect();
+    if failing.is_empty() {
+        return String::new();
+    }
+    let detail = format!(
+        "Below WCAG AA contrast on the background: {}",
+        failing.join(", ")
+    );
+    format!(
+        r#"<span class="text-xs bg-amber-100 text-amber-800 px-1.5 py-0.5 rounded-full" title="{detail}" aria-label="{detail}">&#x26a0; {}</span>"#,
+        failing.len()
+    )
+}
+
+/// GET /api/themes/{name}/contrast — WCAG contrast of the theme's foreground and
+/// palette against its background.
+#[utoipa::path(
+    get,
+    path = "/api/themes/{name}/contrast",
+    tag = "Themes",
+    summary = "WCAG contrast of the theme's foreground and palette on its background",
+    params(("name" = String, Path)),
+    responses((status = 200, description = "OK", body = ContrastReport)),
+)]
+pub async fn theme_contrast_report(
+    State(state): State<SharedState>,
+    Path(name): Path<String>,
+) -> Result<Json<ContrastReport>, AppError> {
+    let themes = state.themes.read().await;
+    let theme = themes
+        .iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| AppError::Config(format!("Unknown theme: {}", name)))?;
+    Ok(Json(theme_contrast(theme)))
+}
+
+/// The star on a theme card. It swaps itself on toggle, and keeps its clicks and
+/// keys from also applying the theme through the card around it.
+pub(crate) fn render_favorite_button(name: &str, favorite: bool) -> String {
+    let name = escape_html(name);
+    let (star, class, label) = if favorite {
+        ("&#x2605;", "text-amber-500", "Unstar")
+    } else {
+        ("&#x2606;", "text-gray-300 hover:text-amber-500", "Star")
+    };
+    format!(
+        r##"<button type="button" name="name" value="{name}" class="text-lg leading-none {class}" aria-pressed="{favorite}" aria-label="{label} theme {name}" hx-post="/api/themes/favorite" hx-trigger="click consume" hx-swap="outerHTML" onkeydown="event.stopPropagation()">{star}</button>"##
+    )
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct FavoriteForm {
+    pub name: String,
+}
+
+/// POST /api/themes/favorite — star or unstar a theme, answering with its new star.
+#[utoipa::path(
+    post,
+    path = "/api/themes/favorite",
+    tag = "Themes",
+    summary = "Star or unstar a theme",
+    request_body(content = FavoriteForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn toggle_favorite(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<FavoriteForm>,
+) -> Result<Html<String>, AppError> {
+    let mut theme_state = state.theme_state.write().await;
+    let favorite = theme_state.toggle_favorite(&form.name);
+    theme_state.save(&theme_state_path(&state.settings_path))?;
+    Ok(Html(render_favorite_button(&form.name, favorite)))
+}
+
+/// Small labels for a card's tags; nothing when there are none.
+fn render_tag_chips(tags: &[ThemeTag]) -> String {
+    if tags.is_empty() {
+        return String::new();
+    }
+    let chips: String = tags
+        .iter()
+        .map(|t| {
+            format!(
+                r#"<span class="text-[10px] bg-gray-100 text-gray-600 px-1.5 py-0.5 rounded">{}</span>"#,
+                t.label()
+            )
+        })
+        .collect();
+    format!(r#"<div class="flex flex-wrap gap-1 mt-2">{}</div>"#, chips)
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ApplyThemeQuery {
     pub name: String,
 }
 
-/// POST /api/themes/apply — set the theme in config.
+#[derive(Deserialize, ToSchema)]
+pub struct ApplyThemeForm {
+    /// "light" or "dark" to set that half of a `light:NAME,dark:NAME` pair; empty
+    /// to use the theme for both.
+    #[serde(default)]
+    pub variant: String,
+}
+
+/// POST /api/themes/apply — set the theme in config, or one half of a light/dark pair.
+#[utoipa::path(
+    post,
+    path = "/api/themes/apply",
+    tag = "Themes",
+    summary = "Set the theme (`name` query), or one half of a light/dark pair (`variant` field)",
+    params(ApplyThemeQuery),
+    request_body(content = ApplyThemeForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
 pub async fn apply_theme(
     State(state): State<SharedState>,
     Query(query): Query<ApplyThemeQuery>,
+    axum::Form(form): axum::Form<ApplyThemeForm>,
 ) -> Result<Html<String>, AppError> {
+    let variant = match form.variant.as_str() {
+        "" => None,
+        v => Some(
+            ThemeVariant::parse(v)
+                .ok_or_else(|| AppError::Config(format!("Unknown theme variant: {}", v)))?,
+        ),
+    };
+    let selection = match variant {
+        Some(variant) => {
+            let current = state.value_of("theme").await.unwrap_or_default();
+            ThemeSelection::parse(&current).with_variant(variant, &query.name)
+        }
+        None => ThemeSelection::Single(query.name.clone()),
+    };
+    state
+        .edit_key("theme", |config| config.set("theme", &selection.to_value()))
+        .await?;
+    state.mark_unsaved().await;
+
+    let mut theme_state = state.theme_state.write().await;
+    theme_state.record_recent(&query.name);
+    if let Err(e) = theme_state.save(&theme_state_path(&state.settings_path)) {
+        tracing::warn!("Failed to save recent themes: {}", e);
+    }
+    drop(theme_state);
+
+    let message = match variant {
+        Some(variant) => format!(
+            "{} theme set to: {} (unsaved)",
+            variant.label(),
+            escape_html(&query.name)
+        ),
+        None => format!("Theme set to: {} (unsaved)", escape_html(&query.name)),
+    };
+    Ok(Html(publish_toast(&state, &message, false)))
+}
+
+/// GET /api/themes/overrides — warn about explicit color keys that override the theme.
+///
+/// Returns nothing when there are none.
+#[utoipa::path(
+    get,
+    path = "/api/themes/overrides",
+    tag = "Themes",
+    summary = "Explicit color keys that override the theme",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn theme_overrides(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let user_config = state.user_config.read().await;
+    let themes = state.themes.read().await;
+    let overrides = find_theme_overrides(&user_config, &themes);
+    let theme = user_config.get("theme").unwrap_or("");
+    Ok(Html(render_overrides(theme, &overrides)))
+}
+
+/// POST /api/themes/overrides/clear — drop the explicit color keys so the theme applies as-is.
+#[utoipa::path(
+    post,
+    path = "/api/themes/overrides/clear",
+    tag = "Themes",
+    summary = "Drop the overriding color keys",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn clear_overrides(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
     let mut user_config = state.user_config.write().await;
-    user_config.set("theme", &query.name);
+    let themes = state.themes.read().await;
+    let overrides = find_theme_overrides(&user_config, &themes);
+    drop(themes);
+    if overrides.is_empty() {
+        return Ok(Html(toast_html("No theme overrides to clear", true)));
+    }
+
+    clear_theme_overrides(&mut user_config);
     drop(user_config);
-    state.mark_unsaved("theme").await;
-    let count = state.unsaved_count().await;
+    state.mark_unsaved().await;
 
-    let mut html = String::from("<div class=\"bg-emerald-500 text-white px-4 py-2 rounded-lg shadow-lg text-sm font-medium\">Theme set to: ");
-    html.push_str(&query.name);
-    html.push_str(" (unsaved)</div>");
-    html.push_str(&super::config_api::unsaved_badge_oob(count));
+    Ok(Html(publish_toast(
+        &state,
+        "Cleared theme overrides (unsaved)",
+        false,
+    )))
+}
 
-    Ok(Html(html))
+#[derive(Deserialize, ToSchema)]
+pub struct ExtractThemeForm {
+    pub name: String,
+}
+
+/// POST /api/themes/overrides/extract — save the theme plus overrides as a custom theme and use it.
+#[utoipa::path(
+    post,
+    path = "/api/themes/overrides/extract",
+    tag = "Themes",
+    summary = "Save the theme plus overrides as a custom theme and use it",
+    request_body(content = ExtractThemeForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn extract_overrides(
+    State(state): State<SharedState>,
+    axum::Form(form): axum::Form<ExtractThemeForm>,
+) -> Result<Html<String>, AppError> {
+    let mut user_config = state.user_config.write().await;
+    let mut themes = state.themes.write().await;
+    let overrides = find_theme_overrides(&user_config, &themes);
+    if overrides.is_empty() {
+        return Ok(Html(toast_html("No theme overrides to extract", true)));
+    }
+
+    let contents = render_theme_file(active_theme(&user_config, &themes), &overrides);
+    let dir = user_theme_dir(&user_config.file_path);
+    let theme = match write_user_theme(&dir, &form.name, &contents) {
+        Ok(theme) => theme,
+        Err(AppError::Config(msg)) => return Ok(Html(toast_html(&escape_html(&msg), true))),
+        Err(e) => return Err(e),
+    };
+
+    clear_theme_overrides(&mut user_config);
+    user_config.set("theme", &theme.name);
+    let message = format!(
+        "Saved theme {} and switched to it (unsaved)",
+        escape_html(&theme.name)
+    );
+    themes.retain(|t| t.name != theme.name);
+    themes.push(theme);
+    themes.sort_by_key(|t| t.name.to_lowercase());
+    drop(themes);
+    drop(user_config);
+
+    state.mark_unsaved().await;
+
+    Ok(Html(publish_toast(&state, &message, false)))
+}
+
+fn render_overrides(theme: &str, overrides: &[ThemeOverride]) -> String {
+    if overrides.is_empty() {
+        return String::new();
+    }
+
+    let swatch = |color: &str| {
+        let color = color.rsplit('=').next().unwrap_or(color);
+        format!(
+            r#"<span class="inline-block w-3 h-3 rounded-sm border border-gray-300 align-middle mr-1" style="background-color: {}"></span>"#,
+            escape_html(color)
+        )
+    };
+
+    let mut html = String::new();
+    html.push_str(r#"<div class="bg-amber-50 border border-amber-300 rounded-xl p-4 mb-6 text-sm text-amber-900" role="alert">"#);
+    html.push_str(&format!(
+        r#"<p class="font-medium mb-2">These keys override colors from the theme <span class="font-mono">{}</span>:</p>"#,
+        escape_html(theme)
+    ));
+    html.push_str(r#"<table class="mb-3"><tbody>"#);
+    for o in overrides {
+        let theme_value = match &o.theme_value {
+            Some(v) => format!("{}{}", swatch(v), escape_html(v)),
+            None => r#"<span class="text-amber-600">&mdash;</span>"#.to_string(),
+        };
+        html.push_str(&format!(
+            r#"<tr><td class="pr-4 py-0.5 font-mono font-semibold">{}</td><td class="pr-4 py-0.5 font-mono">{}{}</td><td class="py-0.5 font-mono text-amber-700">theme: {}</td></tr>"#,
+            escape_html(&o.key),
+            swatch(&o.value),
+            escape_html(&o.value),
+            theme_value
+        ));
+    }
+    html.push_str("</tbody></table>");
+    html.push_str(r##"<div class="flex flex-wrap items-center gap-3">
+<button hx-post="/api/themes/overrides/clear" hx-target="#toast-container" hx-swap="innerHTML" hx-on::after-request="if(event.detail.successful) setTimeout(() => location.reload(), 500)" class="px-3 py-1.5 bg-amber-600 text-white rounded-lg text-sm font-medium hover:bg-amber-700 transition-colors">Clear overrides</button>
+<form hx-post="/api/themes/overrides/extract" hx-target="#toast-container" hx-swap="innerHTML" hx-on::after-request="if(event.detail.successful) setTimeout(() => location.reload(), 500)" class="flex items-center gap-2">
+<input type="text" name="name" required placeholder="New theme name" class="px-3 py-1.5 border border-amber-300 rounded-lg text-sm bg-white focus:ring-2 focus:ring-amber-500">
+<button type="submit" class="px-3 py-1.5 bg-white border border-amber-400 text-amber-800 rounded-lg text-sm font-medium hover:bg-amber-100 transition-colors">Extract to custom theme</button>
+</form></div></div>"##);
+    html
+}
+
+/// Where custom themes live for the config being edited.
+async fn custom_theme_dir(state: &SharedState) -> PathBuf {
+    user_theme_dir(&state.user_config.read().await.file_path)
+}
+
+/// Re-read bundled and user themes after a theme changed on disk.
+pub(crate) async fn reload_themes(state: &SharedState) -> Result<(), AppError> {
+    let config_path = state.user_config.read().await.file_path.clone();
+    let themes = tokio::task::spawn_blocking(move || load_themes(&config_path))
+        .await
+        .map_err(anyhow::Error::from)??;
+    *state.themes.write().await = themes;
+    Ok(())
+}
+
+/// POST /api/themes/rescan — pick up theme files added, changed, or removed on disk
+/// since the server started.
+#[utoipa::path(
+    post,
+    path = "/api/themes/rescan",
+    tag = "Themes",
+    summary = "Reload theme files from disk",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn rescan_themes(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    reload_themes(&state).await?;
+    let themes = state.themes.read().await;
+    let user = themes
+        .iter()
+        .filter(|t| t.origin == ThemeOrigin::User)
+        .count();
+    let message = format!("Found {} themes ({} user)", themes.len(), user);
+    drop(themes);
+    Ok(Html(publish_toast(&state, &message, false)))
+}
+
+/// GET /api/themes/custom — list custom themes with edit and delete controls.
+#[utoipa::path(
+    get,
+    path = "/api/themes/custom",
+    tag = "Themes",
+    summary = "Custom themes",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn list_custom_themes(
+    State(state): State<SharedState>,
+) -> Result<Html<String>, AppError> {
+    let themes = state.themes.read().await;
+    Ok(Html(render_custom_list(&themes)))
+}
+
+/// POST /api/themes/custom — create a custom theme from the editor form.
+#[utoipa::path(
+    post,
+    path = "/api/themes/custom",
+    tag = "Themes",
+    summary = "Create a custom theme",
+    request_body(content = HashMap<String, String>, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn create_custom_theme(
+    State(state): State<SharedState>,
+    axum::Form(fields): axum::Form<HashMap<String, String>>,
+) -> Result<Response, AppError> {
+    let name = fields.get("name").cloned().unwrap_or_default();
+    save_custom_theme(&state, &name, &fields, write_user_theme).await
+}
+
+/// PUT /api/themes/custom/{name} — replace the colors of an existing custom theme.
+#[utoipa::path(
+    put,
+    path = "/api/themes/custom/{name}",
+    tag = "Themes",
+    summary = "Replace a custom theme's colors",
+    params(("name" = String, Path)),
+    request_body(content = HashMap<String, String>, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn update_custom_theme(
+    State(state): State<SharedState>,
+    Path(name): Path<String>,
+    axum::Form(fields): axum::Form<HashMap<String, String>>,
+) -> Result<Response, AppError> {
+    save_custom_theme(&state, &name, &fields, update_user_theme).await
+}
+
+async fn save_custom_theme(
+    state: &SharedState,
+    name: &str,
+    fields: &HashMap<String, String>,
+    write: fn(&FsPath, &str, &str) -> Result<ThemeInfo, AppError>,
+) -> Result<Response, AppError> {
+    let dir = custom_theme_dir(state).await;
+    let saved = theme_from_fields(name, fields)
+        .and_then(|theme| write(&dir, &theme.name, &render_theme_file(Some(&theme), &[])));
+    let theme = match saved {
+        Ok(theme) => theme,
+        Err(AppError::Config(msg)) => {
+            return Ok(Html(toast_html(&escape_html(&msg), true)).into_response())
+        }
+        Err(e) => return Err(e),
+    };
+    reload_themes(state).await?;
+
+    let message = format!("Saved theme {}", escape_html(&theme.name));
+    Ok((
+        [("HX-Redirect", "/themes")],
+        Html(toast_html(&message, false)),
+    )
+        .into_response())
+}
+
+/// DELETE /api/themes/custom/{name} — delete a custom theme file.
+#[utoipa::path(
+    delete,
+    path = "/api/themes/custom/{name}",
+    tag = "Themes",
+    summary = "Delete a custom theme",
+    params(("name" = String, Path)),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn delete_custom_theme(
+    State(state): State<SharedState>,
+    Path(name): Path<String>,
+) -> Result<Html<String>, AppError> {
+    let dir = custom_theme_dir(&state).await;
+    match delete_user_theme(&dir, &name) {
+        Ok(()) => {}
+        Err(AppError::Config(msg)) => return Ok(Html(toast_html(&escape_html(&msg), true))),
+        Err(e) => return Err(e),
+    }
+    reload_themes(&state).await?;
+
+    let in_use = state
+        .user_config
+        .read()
+        .await
+        .get("theme")
+        .is_some_and(|t| !ThemeSelection::parse(t).variants_of(&name).is_empty());
+    if in_use {
+        let message = format!(
+            "Deleted theme {}, but the config still uses it",
+            escape_html(&name)
+        );
+        return Ok(Html(toast_html(&message, true)));
+    }
+    Ok(Html(toast_html(
+        &format!("Deleted theme {}", escape_html(&name)),
+        false,
+    )))
+}
+
+/// POST /api/themes/custom/preview — render the editor's live sample for the current form.
+#[utoipa::path(
+    post,
+    path = "/api/themes/custom/preview",
+    tag = "Themes",
+    summary = "Sample rendering of an unsaved custom theme",
+    request_body(content = HashMap<String, String>, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn preview_custom_theme(
+    axum::Form(fields): axum::Form<HashMap<String, String>>,
+) -> Html<String> {
+    let name = fields.get("name").cloned().unwrap_or_default();
+    match theme_from_fields(&name, &fields) {
+        Ok(theme) => Html(render_theme_sample(&theme)),
+        Err(e) => Html(format!(
+            r#"<p class="text-sm text-amber-700" role="alert">{}</p>"#,
+            escape_html(&e.to_string())
+        )),
+    }
+}
+
+/// A small terminal drawn in the theme's colors, used by the theme editor.
+pub fn render_theme_sample(theme: &ThemeInfo) -> String {
+    let color = |i: usize| {
+        theme
+            .palette
+            .get(i)
+            .filter(|c| !c.is_empty())
+            .map(String::as_str)
+            .unwrap_or(theme.foreground.as_str())
+    };
+    let cursor = theme.cursor_color.as_deref().unwrap_or(&theme.foreground);
+    let selection = theme.selection_background.as_deref().unwrap_or("#44475a");
+
+    let mut swatches = String::new();
+    for (i, c) in theme.palette.iter().enumerate() {
+        if !c.is_empty() {
+            swatches.push_str(&format!(
+                r#"<span class="w-5 h-5 rounded inline-block" title="{} {}" style="background-color: {}"></span>"#,
+                i,
+                PALETTE_NAMES[i],
+                c
+            ));
+        }
+    }
+
+    format!(
+        r#"<div class="rounded-xl overflow-hidden border border-gray-700 p-4 font-mono text-sm leading-relaxed" role="figure" aria-label="Sample terminal in the edited theme" style="background-color: {bg}; color: {fg}">
+            <div><span style="color: {c2}">user</span>@<span style="color: {c4}">ghostty</span> <span style="color: {c6}">~</span> $ git status</div>
+            <div>On branch <span style="color: {c5}">main</span></div>
+            <div style="color: {c1}">&nbsp;&nbsp;modified:   src/main.rs</div>
+            <div style="color: {c3}">&nbsp;&nbsp;untracked:  notes.txt</div>
+            <div><span style="background-color: {selection}">selected text</span> <span class="inline-block w-2 h-4 align-middle" aria-hidden="true" style="background-color: {cursor}"></span></div>
+            <div class="flex flex-wrap gap-1 mt-3" aria-hidden="true">{swatches}</div>
+        </div>"#,
+        bg = theme.background,
+        fg = theme.foreground,
+        c1 = color(1),
+        c2 = color(2),
+        c3 = color(3),
+        c4 = color(4),
+        c5 = color(5),
+        c6 = color(6),
+        selection = selection,
+        cursor = cursor,
+        swatches = swatches,
+    )
+}
+
+fn render_custom_list(themes: &[ThemeInfo]) -> String {
+    let custom: Vec<&ThemeInfo> = themes.iter().filter(|t| t.custom).collect();
+    if custom.is_empty() {
+        return r#"<p class="text-sm text-gray-500">No custom themes yet.</p>"#.to_string();
+    }
+
+    let mut html =
+        String::from(r#"<ul class="divide-y divide-gray-100" aria-label="Custom themes">"#);
+    for theme in custom {
+        let name = escape_html(&theme.name);
+        html.push_str(&format!(
+            r##"<li class="flex items-center gap-3 py-2" data-focus-key="custom-theme:{name}"><span class="w-6 h-6 rounded border border-gray-300" aria-hidden="true" style="background-color: {bg}"></span><span class="flex-1 text-sm font-medium">{name}</span><a href="/themes/new?edit={name}" class="text-sm text-indigo-600 hover:text-indigo-800" aria-label="Edit theme {name}">Edit</a><button type="button" hx-delete="/api/themes/custom/{name}" hx-confirm="Delete the theme {name}?" hx-target="#toast-container" hx-swap="innerHTML" hx-on::after-request="if(event.detail.successful) setTimeout(() => location.reload(), 500)" class="text-sm text-red-600 hover:text-red-800" aria-label="Delete theme {name}">Delete</button></li>"##,
+            name = name,
+            bg = theme.background,
+        ));
+    }
+    html.push_str("</ul>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_overrides_empty() {
+        assert_eq!(render_overrides("Nord", &[]), "");
+    }
+
+    #[test]
+    fn test_render_overrides_lists_keys() {
+        let overrides = vec![ThemeOverride {
+            key: "palette".to_string(),
+            value: "1=#ff0000".to_string(),
+            theme_value: Some("#bf616a".to_string()),
+        }];
+        let html = render_overrides("Nord", &overrides);
+        assert!(html.contains("1=#ff0000"));
+        assert!(html.contains("background-color: #ff0000"));
+        assert!(html.contains("theme: "));
+        assert!(html.contains("/api/themes/overrides/extract"));
+    }
+
+    #[test]
+    fn test_theme_card_is_accessible_button() {
+        let theme = ThemeInfo {
+            name: "Tokyo <Night>".to_string(),
+            background: "#1a1b26".to_string(),
+            foreground: "#c0caf5".to_string(),
+            palette: vec![String::new(); 16],
+            is_dark: true,
+            cursor_color: None,
+            selection_background: None,
+            custom: false,
+            origin: Default::default(),
+        };
+        let html = render_theme_card(&theme, Some("Active"), false, "#1a1b26", "");
+        assert!(html.contains(r#"role="button""#));
+        assert!(html.contains(r#"tabindex="0""#));
+        assert!(html.contains(r#"aria-pressed="true""#));
+        assert!(html.contains(r#"aria-label="Apply theme Tokyo &lt;Night&gt;""#));
+        assert!(html.contains("keydown[key=='Enter'||key==' ']"));
+
+        assert!(html.contains(r##"hx-include="#theme-variant""##));
+
+        let inactive = render_theme_card(&theme, None, true, "#1a1b26", "");
+        assert!(inactive.contains(r#"aria-pressed="false""#));
+        assert!(inactive.contains(r#"aria-label="Unstar theme Tokyo &lt;Night&gt;""#));
+    }
+
+    #[test]
+    fn test_render_contrast_warning() {
+        let mut palette = vec![String::new(); 16];
+        palette[0] = "#000000".to_string();
+        palette[4] = "#000080".to_string();
+        let theme = ThemeInfo {
+            name: "Murky".to_string(),
+            background: "#000000".to_string(),
+            foreground: "#ffffff".to_string(),
+            palette,
+            is_dark: true,
+            cursor_color: None,
+            selection_background: None,
+            custom: false,
+            origin: Default::default(),
+        };
+        let html = render_contrast_warning(&theme);
+        assert!(html.contains("Blue 1.3:1"), "{html}");
+        assert!(!html.contains("Black"));
+        assert!(html.contains("&#x26a0; 1"));
+
+        let readable = ThemeInfo {
+            palette: vec![String::new(); 16],
+            ..theme
+        };
+        assert!(render_contrast_warning(&readable).is_empty());
+    }
+
+    #[test]
+    fn test_active_label() {
+        let pair = ThemeSelection::parse("light:A,dark:B");
+        assert_eq!(active_label(&pair, "A"), Some("Light"));
+        assert_eq!(active_label(&pair, "B"), Some("Dark"));
+        assert_eq!(active_label(&pair, "C"), None);
+        let same = ThemeSelection::parse("light:A,dark:A");
+        assert_eq!(active_label(&same, "A"), Some("Light + dark"));
+        assert_eq!(
+            active_label(&ThemeSelection::parse("A"), "A"),
+            Some("Active")
+        );
+        assert_eq!(active_label(&ThemeSelection::None, "A"), None);
+    }
+
+    #[test]
+    fn test_render_favorite_button() {
+        let html = render_favorite_button("Tokyo <Night>", false);
+        assert!(html.contains(r#"value="Tokyo &lt;Night&gt;""#));
+        assert!(html.contains(r#"aria-pressed="false""#));
+        assert!(html.contains("click consume"));
+        assert!(render_favorite_button("Nord", true).contains(r#"aria-pressed="true""#));
+    }
+
+    #[test]
+    fn test_render_tag_chips() {
+        assert!(render_tag_chips(&[]).is_empty());
+        let html = render_tag_chips(&[ThemeTag::Pastel, ThemeTag::HighContrast]);
+        assert!(html.contains(">Pastel<"));
+        assert!(html.contains(">High contrast<"));
+    }
+
+    #[test]
+    fn test_render_custom_list_only_custom() {
+        let theme = |name: &str, custom| ThemeInfo {
+            name: name.to_string(),
+            background: "#000000".to_string(),
+            foreground: "#ffffff".to_string(),
+            palette: vec![String::new(); 16],
+            is_dark: true,
+            cursor_color: None,
+            selection_background: None,
+            custom,
+            origin: Default::default(),
+        };
+        assert!(render_custom_list(&[theme("Nord", false)]).contains("No custom themes"));
+
+        let html = render_custom_list(&[theme("Nord", false), theme("Mine", true)]);
+        assert!(html.contains(r#"hx-delete="/api/themes/custom/Mine""#));
+        assert!(html.contains("/themes/new?edit=Mine"));
+        assert!(!html.contains("Nord"));
+    }
+
+    #[test]
+    fn test_render_theme_sample_uses_palette() {
+        let mut palette = vec![String::new(); 16];
+        palette[1] = "#ff5555".to_string();
+        let theme = ThemeInfo {
+            name: "Mine".to_string(),
+            background: "#101010".to_string(),
+            foreground: "#eeeeee".to_string(),
+            palette,
+            is_dark: true,
+            cursor_color: Some("#00ff00".to_string()),
+            selection_background: None,
+            custom: true,
+            origin: Default::default(),
+        };
+        let html = render_theme_sample(&theme);
+        assert!(html.contains("background-color: #101010"));
+        assert!(html.contains("color: #ff5555"));
+        // Unset palette slots fall back to the foreground
+        assert!(html.contains(r#"<span style="color: #eeeeee">user</span>"#));
+        assert!(html.contains("background-color: #00ff00"));
+    }
 }
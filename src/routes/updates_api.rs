@@ -0,0 +1,178 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::extract::State;
+use axum::response::Html;
+
+use super::config_api::escape_html;
+use crate::app_state::SharedState;
+use crate::cli::discovery::ghostty_version;
+use crate::error::AppError;
+use crate::updates::{check_for_updates, Update, REMIND_LATER_SECS};
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Updates found earlier this session, or a fresh check if there are none yet.
+async fn updates(state: &SharedState) -> Vec<Update> {
+    if let Some(updates) = state.updates.read().await.clone() {
+        return updates;
+    }
+    let version = match &state.ghostty().await.path {
+        Some(ghostty_path) => ghostty_version(ghostty_path)
+            .await
+            .inspect_err(|e| tracing::warn!("{}", e))
+            .ok(),
+        None => None,
+    };
+    let updates = check_for_updates(version.as_deref());
+    *state.updates.write().await = Some(updates.clone());
+    updates
+}
+
+/// GET /api/updates — the update notice on the index page.
+///
+/// Nothing is fetched until the user opts in, and nothing is shown while
+/// "remind me later" is in effect.
+pub async fn update_status(State(state): State<SharedState>) -> Html<String> {
+    let settings = state.settings.read().await.clone();
+    if !settings.check_updates {
+        return Html(render_opt_in());
+    }
+    if settings
+        .remind_updates_after
+        .is_some_and(|after| now_secs() < after)
+    {
+        return Html(String::new());
+    }
+    Html(render_updates(&updates(&state).await, false))
+}
+
+/// POST /api/updates/check — check once without turning on automatic checks.
+pub async fn check_now(State(state): State<SharedState>) -> Html<String> {
+    *state.updates.write().await = None;
+    Html(render_updates(&updates(&state).await, true))
+}
+
+/// POST /api/updates/enable — check for updates every time the index page loads.
+pub async fn enable_checks(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    {
+        let mut settings = state.settings.write().await;
+        settings.check_updates = true;
+        settings.remind_updates_after = None;
+        settings.save(&state.settings_path)?;
+    }
+    Ok(Html(render_updates(&updates(&state).await, true)))
+}
+
+/// POST /api/updates/disable — stop checking and hide the notice.
+pub async fn disable_checks(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let mut settings = state.settings.write().await;
+    settings.check_updates = false;
+    settings.save(&state.settings_path)?;
+    Ok(Html(String::new()))
+}
+
+/// POST /api/updates/remind-later — hide update notices for a week.
+pub async fn remind_later(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
+    let mut settings = state.settings.write().await;
+    settings.remind_updates_after = Some(now_secs() + REMIND_LATER_SECS);
+    settings.save(&state.settings_path)?;
+    Ok(Html(String::new()))
+}
+
+fn action_button(url: &str, label: &str, class: &str) -> String {
+    format!(
+        r##"<button type="button" hx-post="{url}" hx-target="#update-check" hx-swap="innerHTML" class="text-sm font-medium {class}">{label}</button>"##,
+        url = url,
+        label = label,
+        class = class,
+    )
+}
+
+fn render_opt_in() -> String {
+    format!(
+        r#"<div class="bg-white rounded-xl border border-gray-200 p-4 flex items-center justify-between gap-4"><p class="text-sm text-gray-600">Check GitHub for new releases of Ghostty and this tool? Only the public release list is requested; nothing about your setup is sent.</p><div class="flex items-center gap-4 shrink-0">{} {}</div></div>"#,
+        action_button(
+            "/api/updates/check",
+            "Check now",
+            "text-gray-600 hover:text-gray-800"
+        ),
+        action_button(
+            "/api/updates/enable",
+            "Always check",
+            "text-indigo-600 hover:text-indigo-800"
+        ),
+    )
+}
+
+/// The list of available updates. Being up to date is only worth saying after
+/// the user asked; otherwise the notice stays out of the way.
+fn render_updates(updates: &[Update], requested: bool) -> String {
+    if updates.is_empty() {
+        if !requested {
+            return String::new();
+        }
+        return r#"<div class="bg-white rounded-xl border border-gray-200 p-4 text-sm text-gray-600" role="status">Ghostty and ghostty-config are up to date.</div>"#.to_string();
+    }
+
+    let items: String = updates
+        .iter()
+        .map(|u| {
+            format!(
+                r#"<li><span class="font-semibold">{product} {latest}</span> is available (you have {current}). <a href="{url}" target="_blank" rel="noopener noreferrer" class="text-indigo-600 hover:underline">Release notes</a></li>"#,
+                product = u.product,
+                latest = escape_html(&u.latest.tag),
+                current = escape_html(&u.current),
+                url = escape_html(&u.latest.url),
+            )
+        })
+        .collect();
+    format!(
+        r#"<div class="bg-indigo-50 rounded-xl border border-indigo-200 p-4 flex items-start justify-between gap-4" role="status"><ul class="text-sm text-indigo-900 space-y-1">{items}</ul><div class="flex items-center gap-4 shrink-0">{later} {off}</div></div>"#,
+        items = items,
+        later = action_button(
+            "/api/updates/remind-later",
+            "Remind me later",
+            "text-indigo-600 hover:text-indigo-800"
+        ),
+        off = action_button(
+            "/api/updates/disable",
+            "Stop checking",
+            "text-gray-500 hover:text-gray-700"
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::updates::Release;
+
+    #[test]
+    fn test_render_updates() {
+        let updates = [Update {
+            product: "Ghostty",
+            current: "1.1.3".to_string(),
+            latest: Release {
+                tag: "v1.2.0".to_string(),
+                url: "https://github.com/ghostty-org/ghostty/releases/tag/v1.2.0".to_string(),
+            },
+        }];
+        let html = render_updates(&updates, false);
+        assert!(html.contains("Ghostty v1.2.0"));
+        assert!(html.contains("you have 1.1.3"));
+        assert!(html.contains("releases/tag/v1.2.0"));
+        assert!(html.contains("/api/updates/remind-later"));
+    }
+
+    #[test]
+    fn test_render_up_to_date_only_when_requested() {
+        assert!(render_updates(&[], false).is_empty());
+        assert!(render_updates(&[], true).contains("up to date"));
+        assert!(render_opt_in().contains("/api/updates/enable"));
+    }
+}
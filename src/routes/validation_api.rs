@@ -1,19 +1,118 @@
-use axum::extract::State;
-use axum::response::Html;
+use axum::extract::{Query, State};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+use utoipa::IntoParams;
 
+use super::config_api::escape_html;
 use crate::app_state::SharedState;
-use crate::cli::validate::validate_config;
+use crate::cli::validate::{validate_user_config, validation_issues, Severity, ValidationIssue};
+use crate::config::lint::{lint_config, LintWarning};
 use crate::error::AppError;
+use crate::events::AppEvent;
+use crate::live_validation::{render_status, ValidationStatus};
 
-/// GET /api/validate — run ghostty +validate-config and return the result.
-pub async fn validate(State(state): State<SharedState>) -> Result<Html<String>, AppError> {
-    let result = validate_config(&state.ghostty_path)?;
+/// GET /api/validate/status — the header indicator for the latest background validation.
+#[utoipa::path(
+    get,
+    path = "/api/validate/status",
+    tag = "Validation",
+    summary = "The latest background validation result",
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn status(State(state): State<SharedState>) -> Html<String> {
+    Html(render_status(
+        state.validation.read().await.as_ref(),
+        state.ghostty().await.snapshot(),
+    ))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ValidateQuery {
+    /// `json` for the issue list as JSON instead of HTML.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// GET /api/validate — run ghostty +validate-config on the config as it would be
+/// saved, unsaved edits included, and return the issues it reports.
+///
+/// The HTML response also carries out-of-band badges for the fields at fault.
+#[utoipa::path(
+    get,
+    path = "/api/validate",
+    tag = "Validation",
+    summary = "Run `ghostty +validate-config` on the config, unsaved edits included",
+    params(ValidateQuery),
+    responses((status = 200, description = "OK", content_type = "text/html", body = String)),
+)]
+pub async fn validate(
+    State(state): State<SharedState>,
+    Query(query): Query<ValidateQuery>,
+) -> Result<Response, AppError> {
+    let ghostty = state.ghostty().await;
+    let ghostty_path = ghostty.path()?;
+    let issues = {
+        // Validate a copy, so edits aren't held up while Ghostty runs.
+        let user_config = state.user_config.read().await.clone();
+        let included = state.included.read().await.clone();
+        match validate_user_config(ghostty_path, &user_config, &included).await {
+            Ok(output) => validation_issues(&output, &user_config),
+            Err(e) => vec![ValidationIssue {
+                key: None,
+                line: None,
+                message: format!("Validation error: {}", e),
+                severity: Severity::Error,
+            }],
+        }
+    };
+
+    let valid = !issues.iter().any(|i| i.severity == Severity::Error);
+    let status = ValidationStatus {
+        issues: issues.clone(),
+    };
+    let previous = state.validation.write().await.replace(status.clone());
+    if previous.as_ref() != Some(&status) {
+        state.publish(AppEvent::ValidationStatus {
+            valid: status.valid(),
+            errors: status.errors(),
+        });
+    }
+    let output = issues
+        .iter()
+        .map(|i| i.message.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    state.publish(AppEvent::Validated {
+        valid,
+        output,
+        issues: issues.clone(),
+    });
+
+    if query.format.as_deref() == Some("json") {
+        return Ok(Json(issues).into_response());
+    }
+    Ok(Html(render_validation(valid, &issues)).into_response())
+}
 
-    let (icon, color_class) = if result.contains("valid")
-        || result.contains("Valid")
-        || result.trim().is_empty()
-        || result == "Configuration is valid!"
-    {
+/// GET /api/lint — keys this Ghostty doesn't know, deprecated options and values,
+/// and single-value keys set more than once, checked against the discovered schema.
+#[utoipa::path(
+    get,
+    path = "/api/lint",
+    tag = "Validation",
+    summary = "Unknown keys, deprecated options and values, and repeated single-value keys",
+    responses((status = 200, description = "OK", body = [LintWarning])),
+)]
+pub async fn lint(State(state): State<SharedState>) -> Json<Vec<LintWarning>> {
+    let user_config = state.user_config.read().await;
+    Json(lint_config(&user_config, &state.ghostty().await.schema))
+}
+
+/// The result panel, followed by a badge for each key with an issue.
+fn render_validation(valid: bool, issues: &[ValidationIssue]) -> String {
+    let (icon, color_class) = if valid {
         (
             "&#x2705;",
             "bg-emerald-50 border-emerald-300 text-emerald-800",
@@ -22,16 +121,107 @@ pub async fn validate(State(state): State<SharedState>) -> Result<Html<String>,
         ("&#x26a0;", "bg-amber-50 border-amber-300 text-amber-800")
     };
 
-    Ok(Html(format!(
+    let body = if issues.is_empty() {
+        r#"<p class="text-sm mt-2">Configuration is valid!</p>"#.to_string()
+    } else {
+        let items: String = issues
+            .iter()
+            .map(|issue| {
+                let location = match (issue.line, &issue.key) {
+                    (Some(line), Some(key)) => format!(
+                        r##"<a href="#field-{key}" class="font-semibold hover:underline">Line {line}</a>: "##,
+                        key = escape_html(key),
+                        line = line
+                    ),
+                    (Some(line), None) => format!(r#"<span class="font-semibold">Line {}</span>: "#, line),
+                    _ => String::new(),
+                };
+                format!(
+                    r#"<li>{location}<span class="font-mono">{message}</span>{severity}</li>"#,
+                    location = location,
+                    message = escape_html(&issue.message),
+                    severity = match issue.severity {
+                        Severity::Error => "",
+                        Severity::Warning => r#" <span class="text-xs">(warning)</span>"#,
+                    },
+                )
+            })
+            .collect();
+        format!(r#"<ul class="text-sm space-y-1 mt-2">{}</ul>"#, items)
+    };
+
+    let mut html = format!(
         r#"<div class="border rounded-lg p-4 {color_class}" id="validation-result">
             <div class="flex items-center gap-2 font-medium mb-1">
                 <span>{icon}</span>
                 <span>Validation Result</span>
             </div>
-            <pre class="text-sm font-mono whitespace-pre-wrap mt-2">{result}</pre>
+            {body}
         </div>"#,
         color_class = color_class,
         icon = icon,
-        result = result,
-    )))
+        body = body,
+    );
+
+    let mut badged: Vec<&str> = Vec::new();
+    for issue in issues {
+        let Some(key) = issue.key.as_deref() else {
+            continue;
+        };
+        if badged.contains(&key) {
+            continue;
+        }
+        badged.push(key);
+        html.push_str(&issue_badge_oob(issue));
+    }
+    html
+}
+
+/// Badge swapped into the `issue-<key>` slot next to a field on category pages.
+/// Pages without that field ignore it.
+pub(crate) fn issue_badge_oob(issue: &ValidationIssue) -> String {
+    let key = escape_html(issue.key.as_deref().unwrap_or_default());
+    let (label, class) = match issue.severity {
+        Severity::Error => ("Invalid", "bg-red-100 text-red-700"),
+        Severity::Warning => ("Warning", "bg-amber-100 text-amber-800"),
+    };
+    format!(
+        r#"<span id="issue-{key}" data-issue-badge hx-swap-oob="true"><span class="text-xs {class} px-2 py-0.5 rounded-full" title="{message}">{label}</span></span>"#,
+        key = key,
+        class = class,
+        message = escape_html(&issue.message),
+        label = label,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(key: Option<&str>, line: Option<usize>, severity: Severity) -> ValidationIssue {
+        ValidationIssue {
+            key: key.map(String::from),
+            line,
+            message: format!("{}: bad <value>", key.unwrap_or("config")),
+            severity,
+        }
+    }
+
+    #[test]
+    fn test_render_validation_badges() {
+        let issues = [
+            issue(Some("font-size"), Some(2), Severity::Error),
+            issue(Some("font-size"), Some(5), Severity::Error),
+            issue(None, None, Severity::Warning),
+        ];
+        let html = render_validation(false, &issues);
+        assert!(html.contains("Line 2"));
+        assert!(html.contains("bad &lt;value&gt;"));
+        assert!(html.contains("(warning)"));
+        assert_eq!(html.matches(r#"id="issue-font-size""#).count(), 1);
+
+        let html = render_validation(true, &[]);
+        assert!(html.contains("Configuration is valid!"));
+        assert!(!html.contains("hx-swap-oob"));
+    }
 }
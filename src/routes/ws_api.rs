@@ -0,0 +1,87 @@
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+
+use super::pty_api::same_origin;
+use crate::app_state::SharedState;
+use crate::events::AppEvent;
+
+/// GET /ws — WebSocket that pushes every [`AppEvent`] to the page as a JSON text frame.
+///
+/// Lets all open tabs and the preview pane follow edits made in any one of them.
+/// Only served to pages from this server; see [`require_same_origin`].
+pub async fn ws(ws: WebSocketUpgrade, State(state): State<SharedState>) -> Response {
+    let rx = state.events.subscribe();
+    ws.on_upgrade(move |socket| forward_events(socket, rx))
+}
+
+/// Refuse upgrades from pages served by other sites, which browsers let open
+/// WebSockets anywhere; the events carry config values and validation output.
+pub async fn require_same_origin(request: Request, next: Next) -> Response {
+    if !same_origin(request.headers()) {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    next.run(request).await
+}
+
+async fn forward_events(mut socket: WebSocket, mut rx: Receiver<AppEvent>) {
+    loop {
+        tokio::select! {
+            event = rx.recv() => match event {
+                Ok(event) => {
+                    let Ok(json) = serde_json::to_string(&event) else {
+                        continue;
+                    };
+                    if socket.send(Message::text(json)).await.is_err()
+                        || event == AppEvent::ShuttingDown
+                    {
+                        break;
+                    }
+                }
+                // A slow tab missed some events; later ones still apply.
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            },
+            msg = socket.recv() => match msg {
+                // The page doesn't send anything; only watch for it going away.
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::header;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn status(origin: &str) -> StatusCode {
+        let app = Router::new().route(
+            "/ws",
+            axum::routing::get(|| async { StatusCode::OK })
+                .layer(axum::middleware::from_fn(require_same_origin)),
+        );
+        let request = Request::builder()
+            .uri("/ws")
+            .header(header::HOST, "127.0.0.1:3456")
+            .header(header::ORIGIN, origin)
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_require_same_origin() {
+        assert_eq!(status("https://evil.example").await, StatusCode::FORBIDDEN);
+        assert_eq!(status("http://127.0.0.1:3456").await, StatusCode::OK);
+    }
+}
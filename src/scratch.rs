@@ -0,0 +1,69 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use tempfile::TempDir;
+
+use crate::app_state::AppState;
+use crate::config::scratch::scratch_files;
+use crate::error::AppError;
+
+/// Numbers scratch copies, so a window that's still open keeps the files it loaded.
+static NEXT_COPY: AtomicUsize = AtomicUsize::new(1);
+
+/// Where this run keeps its scratch copies: a randomly named directory only this
+/// user can enter, so nobody else can swap in a config of their own. Created on
+/// first use and removed when the server stops.
+static SCRATCH_DIR: Mutex<Option<TempDir>> = Mutex::new(None);
+
+fn scratch_dir() -> std::io::Result<PathBuf> {
+    let mut dir = SCRATCH_DIR.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(dir) = &*dir {
+        return Ok(dir.path().to_path_buf());
+    }
+    let mut builder = tempfile::Builder::new();
+    builder.prefix("ghostty-config-scratch-");
+    #[cfg(unix)]
+    builder.permissions(std::os::unix::fs::PermissionsExt::from_mode(0o700));
+    let created = builder.tempdir()?;
+    let path = created.path().to_path_buf();
+    *dir = Some(created);
+    Ok(path)
+}
+
+/// Write a scratch copy of the in-memory config and its included files, unsaved
+/// edits and all, for a Ghostty window to load instead of the files on disk.
+/// Returns the copy's main file.
+pub async fn write_scratch_copy(state: &AppState) -> Result<PathBuf, AppError> {
+    let dir = scratch_dir()?.join(NEXT_COPY.fetch_add(1, Ordering::Relaxed).to_string());
+    std::fs::create_dir(&dir)?;
+    let files = {
+        let user_config = state.user_config.read().await;
+        let included = state.included.read().await;
+        scratch_files(&user_config, &included, &dir)
+    };
+    for (path, text) in &files {
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)?
+            .write_all(text.as_bytes())?;
+    }
+    Ok(files[0].0.clone())
+}
+
+/// Remove this run's scratch copies.
+pub fn remove_scratch_dir() {
+    let dir = SCRATCH_DIR
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    if let Some(dir) = dir {
+        let path = dir.path().to_path_buf();
+        if let Err(e) = dir.close() {
+            tracing::warn!("Failed to remove {}: {}", path.display(), e);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::model::UserConfig;
+use crate::error::AppError;
+use crate::history::values_by_key;
+
+/// Edits that weren't saved, kept on disk on every change so they survive the
+/// server stopping; see `/api/session`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingSession {
+    /// The config file the edits were made to.
+    pub config_path: PathBuf,
+    /// Each edited key's values across the config and its includes, in file order;
+    /// empty when the key was removed.
+    pub edits: BTreeMap<String, Vec<String>>,
+}
+
+/// `unsaved-session.json`, next to `settings.json`.
+pub fn session_path(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("unsaved-session.json")
+}
+
+impl PendingSession {
+    /// The keys whose values in `current` differ from `saved`, the same files as
+    /// last read from disk.
+    pub fn between(config_path: PathBuf, saved: &[&UserConfig], current: &[&UserConfig]) -> Self {
+        let saved = values_by_key(saved);
+        let mut current = values_by_key(current);
+        let mut edits: BTreeMap<String, Vec<String>> = saved
+            .keys()
+            .filter(|key| !current.contains_key(*key))
+            .map(|key| (key.clone(), Vec::new()))
+            .collect();
+        current.retain(|key, values| saved.get(key) != Some(values));
+        edits.extend(current);
+        PendingSession { config_path, edits }
+    }
+
+    /// The edits that would still change `configs`, or `None` when none would, e.g.
+    /// because they were saved some other way.
+    pub fn still_pending(mut self, configs: &[&UserConfig]) -> Option<Self> {
+        let current = values_by_key(configs);
+        self.edits
+            .retain(|key, values| current.get(key).map_or(!values.is_empty(), |v| v != values));
+        (!self.edits.is_empty()).then_some(self)
+    }
+
+    /// Read the session at `path`. A missing or unreadable file gives `None`.
+    pub fn load(path: &Path) -> Option<Self> {
+        let text = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&text)
+            .inspect_err(|e| {
+                tracing::warn!("Ignoring invalid session in {}: {}", path.display(), e)
+            })
+            .ok()
+    }
+
+    /// Write the session to `path`, or remove the file when there are no edits.
+    pub fn save(&self, path: &Path) -> Result<(), AppError> {
+        if self.edits.is_empty() {
+            return remove(path);
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(anyhow::Error::from)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// Forget the session at `path`. A missing file is fine.
+pub fn remove(path: &Path) -> Result<(), AppError> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file_io::parse_config;
+
+    #[test]
+    fn test_between_and_still_pending() {
+        let saved = parse_config(
+            "font-size = 12\ntheme = Nord\nkeybind = a=b\n",
+            Path::new("config"),
+        );
+        let mut current = saved.clone();
+        current.set("font-size", "14");
+        current.remove("theme");
+        current.push("keybind", "c=d");
+        let session = PendingSession::between("config".into(), &[&saved], &[&current]);
+        assert_eq!(
+            session.edits,
+            BTreeMap::from([
+                ("font-size".to_string(), vec!["14".to_string()]),
+                (
+                    "keybind".to_string(),
+                    vec!["a=b".to_string(), "c=d".to_string()]
+                ),
+                ("theme".to_string(), Vec::new()),
+            ])
+        );
+
+        // After the font size was saved elsewhere, only the rest is left to restore.
+        let mut on_disk = saved.clone();
+        on_disk.set("font-size", "14");
+        let left = session.clone().still_pending(&[&on_disk]).unwrap();
+        assert_eq!(left.edits.len(), 2);
+        assert_eq!(session.still_pending(&[&current]), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = session_path(&dir.path().join("settings.json"));
+        assert_eq!(PendingSession::load(&path), None);
+
+        let session = PendingSession {
+            config_path: "config".into(),
+            edits: BTreeMap::from([("font-size".to_string(), vec!["14".to_string()])]),
+        };
+        session.save(&path).unwrap();
+        assert_eq!(PendingSession::load(&path), Some(session.clone()));
+
+        PendingSession {
+            edits: BTreeMap::new(),
+            ..session
+        }
+        .save(&path)
+        .unwrap();
+        assert!(!path.exists());
+        remove(&path).unwrap();
+    }
+}
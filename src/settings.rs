@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::categorize::{set_category_overrides, CategoryMap};
+use crate::error::AppError;
+
+/// Preferences for this tool itself, stored apart from the Ghostty config.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Ask GitHub for new releases on the index page. Off until the user opts in.
+    pub check_updates: bool,
+    /// Unix time before which update notices stay hidden ("remind me later").
+    pub remind_updates_after: Option<u64>,
+    /// File that new keys of each category are written to, by category slug,
+    /// e.g. `colors` → `colors.conf`. Relative to the config file's directory.
+    pub save_targets: BTreeMap<String, String>,
+    /// Profile most recently activated; see `/profiles`.
+    pub active_profile: Option<String>,
+    /// On save, put a comment from the docs above each new key and group appended
+    /// keys under category headers. `?annotate=` on a save overrides it.
+    pub annotate_new_keys: bool,
+    /// Options shown under "My settings" on the index page, in the order pinned.
+    pub pinned: Vec<String>,
+    /// Ghostty binary last picked in the header, used at startup unless
+    /// `--ghostty` names one; see `/api/ghostty`.
+    pub ghostty: Option<PathBuf>,
+    /// List options for every platform, not just the one Ghostty runs on here;
+    /// see `AppState::shows_platforms`.
+    pub show_all_platforms: bool,
+}
+
+/// `settings.json` in this tool's own config directory, next to Ghostty's.
+pub fn settings_path() -> PathBuf {
+    directories::BaseDirs::new()
+        .map(|d| d.config_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("ghostty-config")
+        .join("settings.json")
+}
+
+/// `categories.toml`, next to `settings.json`: moves options between categories.
+pub fn categories_path(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("categories.toml")
+}
+
+/// Put options in the categories the user's `categories.toml` asks for. Call before
+/// the schema is loaded. A missing file is fine; an invalid one is ignored.
+pub fn load_category_overrides(path: &Path) {
+    let Ok(text) = fs::read_to_string(path) else {
+        return;
+    };
+    match CategoryMap::parse(&text) {
+        Ok(overrides) => {
+            set_category_overrides(overrides);
+            tracing::info!("Using category overrides from {}", path.display());
+        }
+        Err(e) => tracing::warn!("Ignoring {}: {}", path.display(), e),
+    }
+}
+
+impl Settings {
+    /// Read settings from `path`. A missing or unreadable file gives the defaults.
+    pub fn load(path: &Path) -> Self {
+        let Ok(text) = fs::read_to_string(path) else {
+            return Settings::default();
+        };
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring invalid settings in {}: {}", path.display(), e);
+            Settings::default()
+        })
+    }
+
+    /// Pin `key`, or unpin it if it was pinned. Returns whether it is now pinned.
+    pub fn toggle_pin(&mut self, key: &str) -> bool {
+        if self.pinned.iter().any(|k| k == key) {
+            self.pinned.retain(|k| k != key);
+            false
+        } else {
+            self.pinned.push(key.to_string());
+            true
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AppError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(anyhow::Error::from)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("settings.json");
+        assert_eq!(Settings::load(&path), Settings::default());
+
+        let settings = Settings {
+            check_updates: true,
+            remind_updates_after: Some(1_700_000_000),
+            save_targets: BTreeMap::from([("colors".to_string(), "colors.conf".to_string())]),
+            active_profile: Some("work".to_string()),
+            annotate_new_keys: true,
+            pinned: vec!["font-size".to_string()],
+            ghostty: Some(PathBuf::from("/opt/ghostty-tip/ghostty")),
+            show_all_platforms: true,
+        };
+        settings.save(&path).unwrap();
+        assert_eq!(Settings::load(&path), settings);
+    }
+
+    #[test]
+    fn test_toggle_pin() {
+        let mut settings = Settings::default();
+        assert!(settings.toggle_pin("theme"));
+        assert!(settings.toggle_pin("font-size"));
+        assert_eq!(settings.pinned, ["theme", "font-size"]);
+        assert!(!settings.toggle_pin("theme"));
+        assert_eq!(settings.pinned, ["font-size"]);
+    }
+
+    #[test]
+    fn test_invalid_file_gives_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        fs::write(&path, "{not json").unwrap();
+        assert_eq!(Settings::load(&path), Settings::default());
+
+        fs::write(&path, r#"{"check_updates": true, "unknown": 1}"#).unwrap();
+        assert!(Settings::load(&path).check_updates);
+    }
+}
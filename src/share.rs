@@ -0,0 +1,171 @@
+//! Sharing a config: as a compressed code that fits in a URL fragment, or as a
+//! GitHub Gist published with the user's own token.
+
+use std::io::{Read, Write};
+use std::process::Stdio;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::error::{AppError, CliError, CliErrorKind};
+
+/// Decoded configs larger than this are refused, so a tiny code can't expand
+/// into something huge.
+const MAX_SHARED_BYTES: u64 = 1024 * 1024;
+
+/// Seconds to wait for GitHub before giving up.
+const PUBLISH_TIMEOUT_SECS: &str = "20";
+
+/// Compress config text into URL-safe base64.
+pub fn encode_share(text: &str) -> String {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    // Writing to a Vec can't fail.
+    let _ = encoder.write_all(text.as_bytes());
+    URL_SAFE_NO_PAD.encode(encoder.finish().unwrap_or_default())
+}
+
+/// The config text in a code made by [`encode_share`].
+pub fn decode_share(code: &str) -> Result<String, AppError> {
+    let invalid =
+        || AppError::Config("The shared config link is damaged or incomplete".to_string());
+    let compressed = URL_SAFE_NO_PAD
+        .decode(code.trim().trim_end_matches('='))
+        .map_err(|_| invalid())?;
+    let mut text = String::new();
+    DeflateDecoder::new(compressed.as_slice())
+        .take(MAX_SHARED_BYTES + 1)
+        .read_to_string(&mut text)
+        .map_err(|_| invalid())?;
+    if text.len() as u64 > MAX_SHARED_BYTES {
+        return Err(AppError::Config(
+            "The shared config is larger than 1 MB".to_string(),
+        ));
+    }
+    Ok(text)
+}
+
+/// A published Gist.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Gist {
+    #[serde(rename = "html_url")]
+    pub url: String,
+}
+
+/// GitHub tokens are letters, digits, and `_`; anything else is a paste mistake,
+/// and would also break the curl config the token is passed in.
+fn valid_token(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// A double-quoted curl config value.
+fn curl_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The curl config for creating a Gist holding `text` as a file named `config`.
+///
+/// It is passed on stdin so the token doesn't show up in the process list.
+fn gist_request(token: &str, text: &str, public: bool) -> String {
+    let body = serde_json::json!({
+        "description": "Ghostty config",
+        "public": public,
+        "files": { "config": { "content": text } },
+    });
+    format!(
+        "url = \"https://api.github.com/gists\"\nheader = {}\nheader = \"Accept: application/vnd.github+json\"\ndata-binary = {}\n",
+        curl_quote(&format!("Authorization: Bearer {}", token)),
+        curl_quote(&body.to_string()),
+    )
+}
+
+/// Publish `text` as a Gist with the user's GitHub token, which needs the `gist`
+/// scope. The token is only used for this request.
+pub async fn publish_gist(token: &str, text: &str, public: bool) -> Result<Gist, AppError> {
+    let token = token.trim();
+    if !valid_token(token) {
+        return Err(AppError::Config(
+            "That doesn't look like a GitHub token".to_string(),
+        ));
+    }
+
+    let mut child = Command::new("curl")
+        .args(["-fsSL", "--max-time", PUBLISH_TIMEOUT_SECS, "--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| CliError::new(CliErrorKind::Spawn, format!("Failed to run curl: {}", e)))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(gist_request(token, text, public).as_bytes())
+            .await?;
+    }
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(CliError {
+            exit_code: output.status.code(),
+            stderr: stderr.clone(),
+            ..CliError::new(
+                CliErrorKind::Failed,
+                format!("Failed to publish the Gist: {}", stderr),
+            )
+        }
+        .into());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| {
+        CliError::new(
+            CliErrorKind::Output,
+            format!("Unexpected Gist data from GitHub: {}", e),
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_round_trip() {
+        let text = "font-family = \"JetBrains Mono\"\ntheme = catppuccin-mocha\n".repeat(20);
+        let code = encode_share(&text);
+        assert!(code.len() < text.len());
+        assert!(code
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(decode_share(&code).unwrap(), text);
+    }
+
+    #[test]
+    fn test_decode_share_refuses_damage() {
+        assert!(decode_share("not base64!").is_err());
+        assert!(decode_share(&URL_SAFE_NO_PAD.encode("not deflate")).is_err());
+        let huge = encode_share(&"a".repeat(MAX_SHARED_BYTES as usize + 1));
+        assert!(decode_share(&huge).is_err());
+    }
+
+    #[test]
+    fn test_gist_request_quotes_body() {
+        assert!(valid_token("github_pat_11AB"));
+        assert!(!valid_token("ghp_x\"\nurl = evil"));
+        let request = gist_request("ghp_abc", "title = \"a\\b\"\n", false);
+        assert!(request.contains("header = \"Authorization: Bearer ghp_abc\"\n"));
+        assert!(request.contains(r#"\"content\":\"title = \\\"a\\\\b\\\"\\n\""#));
+        assert!(request.contains(r#"\"public\":false"#));
+        assert_eq!(request.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_gist_from_github_json() {
+        let json = r#"{"id": "aa5a315d", "html_url": "https://gist.github.com/aa5a315d"}"#;
+        let gist: Gist = serde_json::from_str(json).unwrap();
+        assert_eq!(gist.url, "https://gist.github.com/aa5a315d");
+    }
+}
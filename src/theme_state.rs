@@ -0,0 +1,98 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// How many recently applied themes are remembered.
+pub const RECENT_LIMIT: usize = 12;
+
+/// Starred and recently applied themes. Kept out of `settings.json` since they
+/// change as the user browses, not as preferences.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeState {
+    pub favorites: BTreeSet<String>,
+    /// Most recent first.
+    pub recent: Vec<String>,
+}
+
+/// `theme-state.json`, next to `settings.json`.
+pub fn theme_state_path(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("theme-state.json")
+}
+
+impl ThemeState {
+    /// Read the state from `path`. A missing or unreadable file gives an empty one.
+    pub fn load(path: &Path) -> Self {
+        let Ok(text) = fs::read_to_string(path) else {
+            return ThemeState::default();
+        };
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring invalid theme state in {}: {}", path.display(), e);
+            ThemeState::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AppError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(anyhow::Error::from)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Star `name`, or unstar it if it was starred. Returns whether it is now a favorite.
+    pub fn toggle_favorite(&mut self, name: &str) -> bool {
+        if self.favorites.remove(name) {
+            false
+        } else {
+            self.favorites.insert(name.to_string());
+            true
+        }
+    }
+
+    /// Move `name` to the front of the recent list, dropping the oldest past the limit.
+    pub fn record_recent(&mut self, name: &str) {
+        self.recent.retain(|r| r != name);
+        self.recent.insert(0, name.to_string());
+        self.recent.truncate(RECENT_LIMIT);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = theme_state_path(&dir.path().join("nested").join("settings.json"));
+        assert_eq!(ThemeState::load(&path), ThemeState::default());
+
+        let mut state = ThemeState::default();
+        assert!(state.toggle_favorite("Dracula"));
+        state.record_recent("Nord");
+        state.save(&path).unwrap();
+        assert_eq!(ThemeState::load(&path), state);
+
+        assert!(!state.toggle_favorite("Dracula"));
+        assert!(state.favorites.is_empty());
+    }
+
+    #[test]
+    fn test_record_recent() {
+        let mut state = ThemeState::default();
+        for i in 0..RECENT_LIMIT + 2 {
+            state.record_recent(&format!("Theme {}", i));
+        }
+        state.record_recent("Theme 5");
+        assert_eq!(state.recent.len(), RECENT_LIMIT);
+        assert_eq!(state.recent[0], "Theme 5");
+        assert_eq!(state.recent[1], format!("Theme {}", RECENT_LIMIT + 1));
+        assert_eq!(state.recent.iter().filter(|r| *r == "Theme 5").count(), 1);
+    }
+}
@@ -0,0 +1,141 @@
+use std::process::Command;
+
+use serde::Deserialize;
+
+use crate::error::{AppError, CliError, CliErrorKind};
+
+/// GitHub repository of this tool.
+pub const CONFIGURATOR_REPO: &str = "madeye/ghostty-config";
+/// GitHub repository of Ghostty itself.
+pub const GHOSTTY_REPO: &str = "ghostty-org/ghostty";
+
+/// How long "remind me later" hides update notices.
+pub const REMIND_LATER_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Seconds to wait for GitHub before giving up.
+const FETCH_TIMEOUT_SECS: &str = "10";
+
+/// The newest published release of a repository.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Release {
+    #[serde(rename = "tag_name")]
+    pub tag: String,
+    /// Release notes page.
+    #[serde(rename = "html_url")]
+    pub url: String,
+}
+
+/// A newer release of something installed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Update {
+    pub product: &'static str,
+    pub current: String,
+    pub latest: Release,
+}
+
+/// Fetch the latest release of `repo` from the GitHub API.
+///
+/// This is a plain anonymous request: nothing about the user's setup is sent.
+pub fn latest_release(repo: &str) -> Result<Release, AppError> {
+    let url = format!("https://api.github.com/repos/{}/releases/latest", repo);
+    let output = Command::new("curl")
+        .args([
+            "-fsSL",
+            "--max-time",
+            FETCH_TIMEOUT_SECS,
+            "-H",
+            "Accept: application/vnd.github+json",
+            &url,
+        ])
+        .output()
+        .map_err(|e| CliError::new(CliErrorKind::Spawn, format!("Failed to run curl: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(CliError {
+            exit_code: output.status.code(),
+            stderr: stderr.clone(),
+            ..CliError::new(
+                CliErrorKind::Failed,
+                format!("Failed to check {} for updates: {}", repo, stderr),
+            )
+        }
+        .into());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| {
+        CliError::new(
+            CliErrorKind::Output,
+            format!("Unexpected release data for {}: {}", repo, e),
+        )
+        .into()
+    })
+}
+
+/// Numeric parts of a version like `v1.2.3` or `1.2.0-dev`, ignoring any suffix.
+fn version_parts(version: &str) -> Option<Vec<u64>> {
+    let core = version
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Whether `latest` is a higher version than `current`. Unparseable versions never are.
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (version_parts(latest), version_parts(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+/// Check this tool and, if its version is known, Ghostty for newer releases.
+///
+/// A repository that can't be reached is logged and skipped.
+pub fn check_for_updates(ghostty_version: Option<&str>) -> Vec<Update> {
+    let mut installed = vec![(
+        "ghostty-config",
+        CONFIGURATOR_REPO,
+        env!("CARGO_PKG_VERSION"),
+    )];
+    if let Some(version) = ghostty_version {
+        installed.push(("Ghostty", GHOSTTY_REPO, version));
+    }
+
+    installed
+        .into_iter()
+        .filter_map(|(product, repo, current)| {
+            let latest = latest_release(repo)
+                .inspect_err(|e| tracing::warn!("{}", e))
+                .ok()?;
+            is_newer(&latest.tag, current).then(|| Update {
+                product,
+                current: current.to_string(),
+                latest,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("v1.2.0", "1.1.3"));
+        assert!(is_newer("1.10.0", "1.9.9"));
+        assert!(is_newer("v0.2.0", "0.1.0"));
+        assert!(!is_newer("v1.1.3", "1.1.3"));
+        assert!(!is_newer("1.1.2", "1.1.3"));
+        assert!(!is_newer("v1.2.0", "1.2.0-dev+abc"));
+        assert!(!is_newer("tip", "1.1.3"));
+    }
+
+    #[test]
+    fn test_release_from_github_json() {
+        let json = r#"{"tag_name": "v1.2.0", "html_url": "https://github.com/ghostty-org/ghostty/releases/tag/v1.2.0", "draft": false}"#;
+        let release: Release = serde_json::from_str(json).unwrap();
+        assert_eq!(release.tag, "v1.2.0");
+        assert!(release.url.ends_with("/v1.2.0"));
+    }
+}
@@ -0,0 +1,137 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::app_state::{AppState, SharedState};
+use crate::error::AppError;
+use crate::events::AppEvent;
+
+/// Editors often write a file in several steps; wait for them to settle.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Whether a filesystem event may have changed the file at `path`.
+///
+/// Removals are ignored: editors that save by rename briefly delete the file,
+/// and the create that follows is what matters.
+fn touches_file(event: &Event, path: &Path) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+        && event
+            .paths
+            .iter()
+            .any(|p| p.file_name() == path.file_name())
+}
+
+/// Pick up edits made to the config file outside the UI.
+///
+/// Without unsaved edits the file is simply reloaded; otherwise it is left for the
+/// merge page. Returns the event that was published, if any.
+pub async fn sync_external_changes(state: &AppState) -> Result<Option<AppEvent>, AppError> {
+    if state.in_safe_mode().await {
+        return Ok(state
+            .reload_repaired()
+            .await?
+            .then_some(AppEvent::ConfigReloaded));
+    }
+
+    let _writing = state.config_writes.lock().await;
+    let Some(on_disk) = state.external_changes().await? else {
+        return Ok(None);
+    };
+
+    let event = if state.unsaved_count().await == 0 {
+        *state.base_config.write().await = on_disk.clone();
+        *state.user_config.write().await = on_disk;
+        state.bump_revision();
+        state.resync_history().await;
+        AppEvent::ConfigReloaded
+    } else {
+        AppEvent::ExternalChange
+    };
+    state.publish(event.clone());
+    Ok(Some(event))
+}
+
+/// Watch the config file and sync external edits into `state`.
+///
+/// The parent directory is watched rather than the file itself so that saves that
+/// replace the file keep being noticed. The returned watcher must be kept alive.
+pub fn watch_config(
+    state: SharedState,
+    config_path: PathBuf,
+) -> notify::Result<RecommendedWatcher> {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let watched = config_path.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+        Ok(event) if touches_file(&event, &watched) => {
+            let _ = tx.send(());
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Config watcher error: {}", e),
+    })?;
+
+    let dir = config_path.parent().unwrap_or(Path::new("."));
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+    tokio::spawn(async move {
+        while rx.recv().await.is_some() {
+            tokio::time::sleep(DEBOUNCE).await;
+            while rx.try_recv().is_ok() {}
+
+            if !config_path.exists() {
+                continue;
+            }
+            match sync_external_changes(&state).await {
+                Ok(Some(event)) => tracing::info!("Config changed on disk: {}", event.name()),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to sync external config change: {}", e),
+            }
+        }
+    });
+
+    Ok(watcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, ModifyKind, RemoveKind};
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn test_touches_file() {
+        let config = Path::new("/home/me/.config/ghostty/config");
+        assert!(touches_file(
+            &event(
+                EventKind::Modify(ModifyKind::Any),
+                "/home/me/.config/ghostty/config"
+            ),
+            config
+        ));
+        assert!(touches_file(
+            &event(
+                EventKind::Create(CreateKind::File),
+                "/home/me/.config/ghostty/config"
+            ),
+            config
+        ));
+        assert!(!touches_file(
+            &event(
+                EventKind::Remove(RemoveKind::File),
+                "/home/me/.config/ghostty/config"
+            ),
+            config
+        ));
+        assert!(!touches_file(
+            &event(
+                EventKind::Modify(ModifyKind::Any),
+                "/home/me/.config/ghostty/config.swp"
+            ),
+            config
+        ));
+    }
+}
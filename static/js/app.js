@@ -1,19 +1,58 @@
 // Ghostty Config UI - Helpers
 
 // Auto-dismiss toasts
+function dismissLater(toast) {
+    setTimeout(() => {
+        toast.style.transition = 'opacity 0.3s ease-out';
+        toast.style.opacity = '0';
+        setTimeout(() => toast.remove(), 300);
+    }, 2000);
+}
+
 document.addEventListener('htmx:afterSwap', function(e) {
     if (e.detail.target.id === 'toast-container') {
         const toast = e.detail.target.firstElementChild;
-        if (toast) {
-            setTimeout(() => {
-                toast.style.transition = 'opacity 0.3s ease-out';
-                toast.style.opacity = '0';
-                setTimeout(() => toast.remove(), 300);
-            }, 2000);
-        }
+        // Confirmations stay until answered.
+        if (toast && !toast.hasAttribute('data-persist')) dismissLater(toast);
     }
 });
 
+// Focus management: fragments mark the control to focus with data-autofocus.
+document.addEventListener('htmx:afterSwap', function(e) {
+    const target = e.detail.target.querySelector('[data-autofocus]');
+    if (target) target.focus();
+});
+
+// Controls tagged with data-focus-key get focus back after the page reloads
+// (e.g. the theme card that was just applied).
+document.addEventListener('htmx:beforeRequest', function(e) {
+    const owner = e.detail.elt.closest('[data-focus-key]');
+    if (owner) sessionStorage.setItem('focus-key', owner.dataset.focusKey);
+});
+
+function restoreFocus() {
+    const key = sessionStorage.getItem('focus-key');
+    if (!key) return;
+    sessionStorage.removeItem('focus-key');
+    const el = Array.from(document.querySelectorAll('[data-focus-key]'))
+        .find(el => el.dataset.focusKey === key);
+    if (el) {
+        el.focus();
+        el.scrollIntoView({ block: 'nearest' });
+    }
+}
+
+document.addEventListener('DOMContentLoaded', restoreFocus);
+
+// Reduced-motion mode, toggled from the header (see /api/accessibility/reduced-motion)
+function setReducedMotion(enabled) {
+    document.documentElement.classList.toggle('reduce-motion', enabled);
+}
+
+document.addEventListener('reduced-motion', function(e) {
+    setReducedMotion(e.detail.enabled);
+});
+
 // Color picker sync: when typing hex, update the color input
 document.addEventListener('input', function(e) {
     if (e.target.type === 'text' && e.target.id && e.target.id.startsWith('input-')) {
@@ -25,6 +64,24 @@ document.addEventListener('input', function(e) {
     }
 });
 
+// Font fields: preview the family being edited or picked from the list
+function showFontPreview(input) {
+    const target = document.getElementById(input.dataset.fontPreview);
+    const family = input.value.trim();
+    if (!target) return;
+    if (!family) {
+        target.innerHTML = '';
+        return;
+    }
+    htmx.ajax('GET', '/api/fonts/preview/' + encodeURIComponent(family), { target: target, swap: 'innerHTML' });
+}
+
+['focusin', 'change'].forEach(function(type) {
+    document.addEventListener(type, function(e) {
+        if (e.target.dataset && e.target.dataset.fontPreview) showFontPreview(e.target);
+    });
+});
+
 // Debounce helper
 function debounce(fn, delay) {
     let timer;
@@ -33,3 +90,584 @@ function debounce(fn, delay) {
         timer = setTimeout(() => fn.apply(this, args), delay);
     };
 }
+
+// Live updates pushed by the server (see /events), so every open tab stays in sync
+function setUnsavedBadge(count) {
+    const badge = document.getElementById('unsaved-badge');
+    if (!badge) return;
+    badge.innerHTML = count > 0
+        ? '<span class="inline-flex items-center justify-center w-5 h-5 text-xs font-bold text-white bg-red-500 rounded-full">' + count + '</span>'
+        : '';
+}
+
+// The preview's sample and simulated color vision, as picked, for its query string.
+function previewParams() {
+    const params = new URLSearchParams();
+    const sample = document.getElementById('preview-sample');
+    const cvd = document.getElementById('preview-cvd');
+    if (sample) params.set('sample', sample.value);
+    if (cvd) params.set('cvd', cvd.value);
+    return params;
+}
+
+function refreshPreview() {
+    if (livePreview) {
+        restyleLivePreview();
+    } else if (document.getElementById('preview-area')) {
+        // Keep showing the sample and simulation the user picked.
+        const query = previewParams().toString();
+        htmx.ajax('GET', '/api/preview' + (query ? '?' + query : ''), { target: '#preview-area', swap: 'innerHTML' });
+    }
+}
+
+// Live preview (with --live-preview): the user's shell on a PTY, drawn by xterm.js
+// and restyled from the in-memory config as it changes. See /ws/preview.
+const XTERM_CDN = 'https://cdn.jsdelivr.net/npm/@xterm';
+let livePreview = null;
+
+function loadScript(src) {
+    return new Promise(function(resolve, reject) {
+        const script = document.createElement('script');
+        script.src = src;
+        script.onload = resolve;
+        script.onerror = reject;
+        document.head.appendChild(script);
+    });
+}
+
+async function terminalOptions() {
+    return (await fetch('/api/preview/terminal')).json();
+}
+
+// Ghostty's window padding, around the terminal rather than inside it so the fit
+// addon still measures the grid correctly.
+function applyTerminalFrame(frame, options) {
+    frame.style.padding = options.padding.y + 'px ' + options.padding.x + 'px';
+    frame.style.backgroundColor = options.theme.background;
+}
+
+async function startLivePreview() {
+    const area = document.getElementById('preview-area');
+    if (!area || livePreview) return;
+    if (!window.Terminal) {
+        const css = document.createElement('link');
+        css.rel = 'stylesheet';
+        css.href = XTERM_CDN + '/xterm@5.5.0/css/xterm.css';
+        document.head.appendChild(css);
+        await loadScript(XTERM_CDN + '/xterm@5.5.0/lib/xterm.js');
+        await loadScript(XTERM_CDN + '/addon-fit@0.10.0/lib/addon-fit.js');
+    }
+    const options = await terminalOptions();
+    area.innerHTML = '<div class="rounded-xl overflow-hidden shadow-lg border border-gray-700">'
+        + '<div class="flex items-center gap-2 px-4 py-2 bg-gray-800 border-b border-gray-700">'
+        + '<span class="text-gray-400 text-xs">ghostty (live)</span>'
+        + '<button type="button" onclick="stopLivePreview()" class="ml-auto px-2 py-0.5 text-xs text-gray-300 border border-gray-600 rounded hover:bg-gray-700">Stop</button>'
+        + '</div><div id="live-terminal-frame"><div id="live-terminal" class="h-72"></div></div></div>';
+    const frame = document.getElementById('live-terminal-frame');
+    const pane = document.getElementById('live-terminal');
+    applyTerminalFrame(frame, options);
+
+    const term = new Terminal(options);
+    const fit = new FitAddon.FitAddon();
+    term.loadAddon(fit);
+    term.open(pane);
+    fit.fit();
+
+    const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
+    const socket = new WebSocket(protocol + '//' + window.location.host
+        + '/ws/preview?cols=' + term.cols + '&rows=' + term.rows);
+    socket.binaryType = 'arraybuffer';
+    socket.addEventListener('message', function(msg) {
+        term.write(new Uint8Array(msg.data));
+    });
+    socket.addEventListener('close', function() {
+        term.write('\r\n[process exited]\r\n');
+    });
+    const send = function(message) {
+        if (socket.readyState === WebSocket.OPEN) socket.send(JSON.stringify(message));
+    };
+    term.onData(function(data) { send({ type: 'input', data: data }); });
+    term.onResize(function(size) { send({ type: 'resize', cols: size.cols, rows: size.rows }); });
+    const observer = new ResizeObserver(function() { fit.fit(); });
+    observer.observe(pane);
+
+    livePreview = { term: term, fit: fit, socket: socket, observer: observer, frame: frame };
+    term.focus();
+}
+
+function stopLivePreview() {
+    if (!livePreview) return;
+    livePreview.observer.disconnect();
+    livePreview.socket.close();
+    livePreview.term.dispose();
+    livePreview = null;
+    refreshPreview();
+}
+
+async function restyleLivePreview() {
+    const options = await terminalOptions();
+    if (!livePreview) return;
+    const term = livePreview.term;
+    term.options.theme = options.theme;
+    term.options.fontFamily = options.fontFamily;
+    term.options.fontSize = options.fontSize;
+    term.options.cursorBlink = options.cursorBlink;
+    applyTerminalFrame(livePreview.frame, options);
+    livePreview.fit.fit();
+}
+
+// Themes page tabs: switch the grid between all, starred, and recent themes,
+// keeping the search and filters.
+const THEME_TAB_ON = ['border-indigo-500', 'text-indigo-700'];
+const THEME_TAB_OFF = ['border-transparent', 'text-gray-500', 'hover:text-gray-700'];
+
+function showThemeView(tab) {
+    document.querySelectorAll('[data-theme-view]').forEach(function(t) {
+        const on = t === tab;
+        t.setAttribute('aria-selected', on);
+        t.classList.remove(...(on ? THEME_TAB_OFF : THEME_TAB_ON));
+        t.classList.add(...(on ? THEME_TAB_ON : THEME_TAB_OFF));
+    });
+    document.getElementById('theme-view').value = tab.dataset.themeView;
+    htmx.trigger('#theme-filter', 'change');
+}
+
+// Themes page: hovering or focusing a card auditions its theme in the preview
+// without applying it; leaving the grid shows the configured theme again.
+let auditionedTheme = null;
+
+// `null` ends the audition.
+function auditionTheme(name) {
+    if (name === null) return endAudition();
+    if (name === auditionedTheme || !document.getElementById('preview-area')) return;
+    auditionedTheme = name;
+    const params = previewParams();
+    params.set('theme', name);
+    htmx.ajax('GET', '/api/preview?' + params, { target: '#preview-area', swap: 'innerHTML' });
+}
+
+function endAudition() {
+    if (auditionedTheme === null) return;
+    auditionedTheme = null;
+    refreshPreview();
+}
+
+const auditionSoon = debounce(auditionTheme, 150);
+
+['mouseover', 'focusin'].forEach(function(type) {
+    document.addEventListener(type, function(e) {
+        const card = e.target.closest && e.target.closest('[data-theme-name]');
+        if (card) auditionSoon(card.dataset.themeName);
+    });
+});
+
+['mouseout', 'focusout'].forEach(function(type) {
+    document.addEventListener(type, function(e) {
+        const inGrid = function(el) { return el && el.closest && el.closest('#theme-grid'); };
+        if (inGrid(e.target) && !inGrid(e.relatedTarget)) auditionSoon(null);
+    });
+});
+
+// The percentage next to an opacity slider.
+function showPercent(input) {
+    const output = document.getElementById('percent-' + input.id.replace(/^input-/, ''));
+    if (output) output.textContent = Math.round(input.value * 100) + '%';
+}
+
+document.addEventListener('DOMContentLoaded', function() {
+    document.querySelectorAll('input[type=range][id^="input-"]').forEach(showPercent);
+});
+
+// The description of the chosen value under an enum's select.
+function showEnumDoc(select) {
+    const help = document.getElementById('enum-doc-' + select.id.replace(/^input-/, ''));
+    const option = select.selectedOptions[0];
+    if (help) help.textContent = option ? option.title : '';
+}
+
+document.addEventListener('DOMContentLoaded', function() {
+    document.querySelectorAll('select[id^="input-"]').forEach(showEnumDoc);
+});
+
+// A color picked with the picker, keeping any alpha the text value had.
+function pickColor(picker, key) {
+    const input = document.getElementById('input-' + key);
+    const alpha = input.value.match(/^#?[0-9a-fA-F]{6}([0-9a-fA-F]{2})$/);
+    input.value = picker.value + (alpha ? alpha[1] : '');
+    htmx.trigger(input, 'change');
+}
+
+// Show a typed hex color in the picker; names are resolved when the page reloads.
+function syncColorPicker(input, key) {
+    const picker = document.getElementById('color-' + key);
+    const hex = input.value.match(/^#?([0-9a-fA-F]{6})([0-9a-fA-F]{2})?$/);
+    if (picker && hex) picker.value = '#' + hex[1].toLowerCase();
+}
+
+// Draw what a field's inputs show besides their value, once it is (re)rendered.
+function initField(key) {
+    const input = document.getElementById('input-' + key);
+    if (!input) return;
+    if (input.type === 'range') showPercent(input);
+    if (input.tagName === 'SELECT') showEnumDoc(input);
+}
+
+// Re-render one field's card from this page, so its value, badges, and revert
+// button match the config.
+function reloadField(key) {
+    const id = 'field-' + key;
+    if (!document.getElementById(id)) return Promise.resolve();
+    return htmx.ajax('GET', window.location.pathname + window.location.search, {
+        target: '#' + CSS.escape(id), select: '#' + CSS.escape(id), swap: 'outerHTML',
+    }).then(() => initField(key));
+}
+
+// Edits answer with their field re-rendered out of band. A slider being dragged is
+// left alone, and focus stays on the control that made the edit, or moves to the
+// field's input when that control is gone (the revert button).
+document.addEventListener('htmx:oobBeforeSwap', function(e) {
+    const field = e.detail.target;
+    if (!field.id || !field.id.startsWith('field-')) return;
+    const key = field.id.slice('field-'.length);
+    const active = document.activeElement;
+    if (!field.contains(active)) {
+        setTimeout(() => initField(key));
+        return;
+    }
+    if (active.type === 'range') {
+        e.detail.shouldSwap = false;
+        return;
+    }
+    const focusId = active.id || 'input-' + key;
+    setTimeout(() => {
+        initField(key);
+        const control = document.getElementById(focusId) || document.getElementById('input-' + key);
+        if (control) control.focus();
+    });
+});
+
+// Show a field changed elsewhere, unless it's being edited here.
+function refreshField(key) {
+    const field = document.getElementById('field-' + key);
+    if (!field || field.contains(document.activeElement)) return;
+    reloadField(key).catch(() => {});
+}
+
+// Grey out the fields on this page that have no effect now, e.g. after the option
+// they depend on changed, and say why.
+function refreshConditions() {
+    const notes = document.querySelectorAll('[data-condition]');
+    if (!notes.length) return;
+    fetch('/api/schema/inactive')
+        .then(r => r.ok ? r.json() : Promise.reject())
+        .then(inactive => {
+            notes.forEach(function(note) {
+                const reason = inactive[note.dataset.condition] || '';
+                note.textContent = reason;
+                const field = document.getElementById('field-' + note.dataset.condition);
+                if (field) field.classList.toggle('opacity-60', reason !== '');
+            });
+        })
+        .catch(() => {});
+}
+
+function clearIssueBadges() {
+    document.querySelectorAll('[data-issue-badge]').forEach(function(slot) {
+        slot.replaceChildren();
+    });
+}
+
+// Mark the fields on this page that validation complained about.
+function showIssueBadges(issues) {
+    clearIssueBadges();
+    (issues || []).forEach(function(issue) {
+        const slot = issue.key && document.getElementById('issue-' + issue.key);
+        if (!slot || slot.childElementCount) return;
+        const badge = document.createElement('span');
+        badge.className = 'text-xs px-2 py-0.5 rounded-full ' + (issue.severity === 'warning'
+            ? 'bg-amber-100 text-amber-800'
+            : 'bg-red-100 text-red-700');
+        badge.title = issue.message;
+        badge.textContent = issue.severity === 'warning' ? 'Warning' : 'Invalid';
+        slot.appendChild(badge);
+    });
+}
+
+function showValidation(valid, output) {
+    const area = document.getElementById('validation-area');
+    if (!area || document.hasFocus()) return;
+    const box = document.createElement('div');
+    box.id = 'validation-result';
+    box.className = 'border rounded-lg p-4 ' + (valid
+        ? 'bg-emerald-50 border-emerald-300 text-emerald-800'
+        : 'bg-amber-50 border-amber-300 text-amber-800');
+    const pre = document.createElement('pre');
+    pre.className = 'text-sm font-mono whitespace-pre-wrap';
+    pre.textContent = output || (valid ? 'Configuration is valid!' : '');
+    box.appendChild(pre);
+    area.replaceChildren(box);
+}
+
+function showExternalChangeBanner() {
+    if (document.getElementById('external-change-banner') || window.location.pathname === '/merge') {
+        return;
+    }
+    const banner = document.createElement('div');
+    banner.id = 'external-change-banner';
+    banner.setAttribute('role', 'alert');
+    banner.className = 'fixed bottom-4 left-1/2 -translate-x-1/2 z-50 bg-amber-500 text-white px-4 py-2 rounded-lg shadow-lg text-sm font-medium';
+    banner.innerHTML = 'The config file was changed outside this window. <a href="/merge" class="underline font-semibold">Review and merge</a>';
+    document.body.appendChild(banner);
+}
+
+// The server stopped: nothing on the page works any more.
+function showStoppedBanner() {
+    if (document.getElementById('stopped-banner')) return;
+    const banner = document.createElement('div');
+    banner.id = 'stopped-banner';
+    banner.setAttribute('role', 'alert');
+    banner.className = 'fixed inset-x-0 top-0 z-50 bg-gray-800 text-white text-center px-4 py-2 text-sm font-medium';
+    banner.textContent = 'Ghostty Config has stopped. You can close this tab.';
+    document.body.appendChild(banner);
+}
+
+// Error responses are not swapped by default. The server retargets the error
+// toasts it renders for HTMX at the toast container, so show those.
+document.addEventListener('htmx:beforeSwap', function(e) {
+    if (e.detail.xhr.status >= 400 && e.detail.xhr.getResponseHeader('HX-Retarget')) {
+        e.detail.shouldSwap = true;
+        e.detail.isError = false;
+    }
+});
+
+// Images dropped on the file browser are uploaded to the assets folder; the
+// answer is the assets folder's listing with the new image marked.
+document.addEventListener('dragover', function(e) {
+    if (e.target.closest('[data-upload-url]')) e.preventDefault();
+});
+
+document.addEventListener('drop', function(e) {
+    const zone = e.target.closest('[data-upload-url]');
+    if (!zone || !e.dataTransfer.files.length) return;
+    e.preventDefault();
+    const file = e.dataTransfer.files[0];
+    const url = zone.dataset.uploadUrl + '&name=' + encodeURIComponent(file.name);
+    fetch(url, { method: 'POST', body: file, headers: { 'HX-Request': 'true' } })
+        .then(res => res.text().then(html => {
+            const target = res.ok
+                ? document.querySelector(zone.dataset.uploadTarget)
+                : document.getElementById('toast-container');
+            if (!target) return;
+            target.innerHTML = html;
+            htmx.process(target);
+            if (!res.ok && target.firstElementChild) dismissLater(target.firstElementChild);
+        }));
+});
+
+// A palette built from the dominant colors of a picked image, shown for review.
+function generatePaletteFromImage(input) {
+    const file = input.files[0];
+    if (!file) return;
+    const appearance = document.getElementById('palette-appearance').value;
+    fetch('/api/palette/generate/image?appearance=' + appearance, { method: 'POST', body: file, headers: { 'HX-Request': 'true' } })
+        .then(res => res.text().then(html => {
+            const target = document.getElementById(res.ok ? 'palette-generated' : 'toast-container');
+            target.innerHTML = html;
+            if (!res.ok && target.firstElementChild) dismissLater(target.firstElementChild);
+        }));
+}
+
+// Apply a generated palette with one bulk edit, so another can be generated and
+// tried over it.
+function applyGeneratedPalette(button) {
+    const status = document.getElementById('palette-generated-status');
+    return fetch('/api/config', { method: 'PUT', headers: { 'Content-Type': 'application/json', ...revisionHeaders() }, body: button.dataset.bulkEdit })
+        .then(res => {
+            if (res.status === 409) {
+                showStaleBanner(res.headers.get('X-Config-Revision'), () => applyGeneratedPalette(button));
+            }
+            return res.json();
+        })
+        .then(result => {
+            if (result.applied) {
+                status.textContent = 'Applied (unsaved)';
+                htmx.trigger('#palette-editor', 'refresh');
+            } else {
+                status.textContent = result.results.filter(r => !r.valid).map(r => r.key + ': ' + r.error).join('; ');
+            }
+        })
+        .catch(() => { status.textContent = 'Not applied'; });
+}
+
+// The categories checked in the share form, as the comma-separated list /api/share takes.
+function shareCategories() {
+    return [...document.querySelectorAll('[data-share-category]:checked')].map(c => c.value).join(',');
+}
+
+// A share link (/import-export#share=<code>) opens the review of its config.
+document.addEventListener('DOMContentLoaded', function() {
+    const match = window.location.hash.match(/^#share=([A-Za-z0-9_-]+)$/);
+    const review = document.getElementById('import-review');
+    if (!match || !review) return;
+    history.replaceState(null, '', window.location.pathname);
+    htmx.ajax('GET', '/api/import?from=' + match[1], { target: '#import-review', swap: 'innerHTML' })
+        .then(() => review.scrollIntoView({ block: 'center' }));
+});
+
+// A change was refused for lack of the session token, e.g. the page was opened by
+// typing the address rather than from the link ghostty-config opened or printed.
+document.addEventListener('htmx:responseError', function(e) {
+    if (e.detail.xhr.status !== 401 || document.getElementById('auth-banner')) return;
+    const banner = document.createElement('div');
+    banner.id = 'auth-banner';
+    banner.setAttribute('role', 'alert');
+    banner.className = 'fixed inset-x-0 top-0 z-50 bg-amber-500 text-white text-center px-4 py-2 text-sm font-medium';
+    banner.textContent = 'Changes are refused: open this page from the link ghostty-config opened or printed at startup.';
+    document.body.appendChild(banner);
+});
+
+// The config revision this page was rendered at, and a random id for this page
+// load; see src/revision.rs. Writes carry both, and one made after another tab or
+// window changed the config is refused with a 409.
+let configRevision = document.querySelector('meta[name="config-revision"]')?.content;
+const configClient = Array.from(crypto.getRandomValues(new Uint8Array(8)), b => b.toString(16).padStart(2, '0')).join('');
+
+function revisionHeaders() {
+    const headers = { 'X-Config-Client': configClient };
+    if (configRevision !== undefined) headers['X-Config-Revision'] = configRevision;
+    return headers;
+}
+
+document.addEventListener('htmx:configRequest', function(e) {
+    Object.assign(e.detail.headers, revisionHeaders());
+});
+
+document.addEventListener('htmx:responseError', function(e) {
+    if (e.detail.xhr.status !== 409) return;
+    const request = e.detail.requestConfig;
+    showStaleBanner(e.detail.xhr.getResponseHeader('X-Config-Revision'), function() {
+        return htmx.ajax(request.verb.toUpperCase(), request.path, { source: request.elt, target: '#toast-container', swap: 'innerHTML' });
+    });
+});
+
+// A change was refused because the page fell behind: offer to reload, or to make
+// the change again on top of the current config (`revision`) and then reload.
+function showStaleBanner(revision, reapply) {
+    document.getElementById('stale-banner')?.remove();
+    const banner = document.createElement('div');
+    banner.id = 'stale-banner';
+    banner.setAttribute('role', 'alert');
+    banner.className = 'fixed bottom-4 left-1/2 -translate-x-1/2 z-50 flex items-center gap-3 bg-amber-500 text-white px-4 py-2 rounded-lg shadow-lg text-sm font-medium';
+    banner.innerHTML = '<span>The config changed in another tab or window, so your last change wasn\'t applied.</span>'
+        + '<button type="button" data-stale="reload" class="underline font-semibold">Reload</button>'
+        + '<button type="button" data-stale="reapply" class="underline font-semibold">Reload &amp; reapply my change</button>';
+    banner.querySelector('[data-stale="reload"]').addEventListener('click', () => window.location.reload());
+    banner.querySelector('[data-stale="reapply"]').addEventListener('click', function() {
+        banner.remove();
+        configRevision = revision;
+        // Refused again if the config changed once more meanwhile: keep that banner.
+        Promise.resolve(reapply()).then(() => {
+            if (!document.getElementById('stale-banner')) window.location.reload();
+        });
+    });
+    document.body.appendChild(banner);
+}
+
+// A toast published by another tab. The tab that made the change already shows it.
+function showToast(message) {
+    const container = document.getElementById('toast-container');
+    if (!container || document.hasFocus()) return;
+    container.innerHTML = message;
+    const toast = container.firstElementChild;
+    if (toast) dismissLater(toast);
+}
+
+function handleServerEvent(event) {
+    switch (event.type) {
+        case 'config-reloaded':
+            // The config file was edited elsewhere and reloaded: show the new values.
+            window.location.reload();
+            break;
+        case 'ghostty-changed':
+            // Another installation was picked: its options and keybinds may differ.
+            window.location.reload();
+            break;
+        case 'external-change':
+            // Edited elsewhere while there are unsaved edits here.
+            showExternalChangeBanner();
+            break;
+        case 'config-changed':
+            setUnsavedBadge(event.unsaved);
+            refreshField(event.key);
+            refreshConditions();
+            refreshPreview();
+            break;
+        case 'saved':
+            setUnsavedBadge(0);
+            break;
+        case 'unsaved-count':
+            setUnsavedBadge(event.unsaved);
+            break;
+        case 'toast':
+            showToast(event.message);
+            break;
+        case 'shutting-down':
+            showStoppedBanner();
+            break;
+        case 'validated':
+            showValidation(event.valid, event.output);
+            if (!document.hasFocus()) showIssueBadges(event.issues);
+            break;
+        case 'validation-status':
+            if (document.getElementById('validation-status')) {
+                htmx.ajax('GET', '/api/validate/status', { target: '#validation-status', swap: 'outerHTML' });
+            }
+            break;
+        case 'autosave':
+            // Switched in another tab: show the new mode in the header.
+            if (document.getElementById('autosave-toggle')) {
+                htmx.ajax('GET', '/api/autosave', { target: '#autosave-toggle', swap: 'outerHTML' });
+            }
+            break;
+        case 'reduced-motion':
+            // Toggled in another tab: update the header button, the page, and the cursor.
+            if (document.getElementById('reduced-motion-toggle')) {
+                htmx.ajax('GET', '/api/accessibility/reduced-motion', { target: '#reduced-motion-toggle', swap: 'outerHTML' });
+            }
+            setReducedMotion(event.enabled);
+            refreshPreview();
+            break;
+    }
+}
+
+// Every event type /events sends, by its SSE event name.
+const SERVER_EVENTS = [
+    'config-reloaded', 'external-change', 'config-changed', 'saved', 'validated',
+    'validation-status', 'reduced-motion', 'autosave', 'unsaved-count', 'ghostty-changed',
+    'toast', 'shutting-down',
+];
+
+// Each event is also re-dispatched on <body> as `server:<type>`, so any element can
+// subscribe with hx-trigger="server:saved from:body".
+function connectEvents() {
+    const source = new EventSource('/events');
+    SERVER_EVENTS.forEach(function(name) {
+        source.addEventListener(name, function(msg) {
+            let event;
+            try {
+                event = JSON.parse(msg.data);
+            } catch (e) {
+                return; // Ignore malformed messages
+            }
+            // Don't keep retrying a server that was told to stop.
+            if (name === 'shutting-down') source.close();
+            handleServerEvent(event);
+            document.body.dispatchEvent(new CustomEvent('server:' + name, { detail: event }));
+        });
+    });
+    // Otherwise EventSource reconnects on its own after the server restarts.
+}
+
+if (window.EventSource) {
+    connectEvents();
+}
@@ -39,71 +39,30 @@ document.addEventListener('keydown', function(e) {
     e.stopPropagation();
 
     // Cancel on Escape
-    if (e.key === 'Escape') {
+    if (e.key === 'Escape' && !(e.ctrlKey || e.altKey || e.shiftKey || e.metaKey)) {
         stopCapture();
         return;
     }
 
-    // Skip modifier-only presses
-    if (['Control', 'Alt', 'Shift', 'Meta'].includes(e.key)) {
-        return;
-    }
-
-    const parts = [];
-
-    // Build modifier string using Ghostty naming
-    if (e.metaKey) parts.push('super');
-    if (e.ctrlKey) parts.push('ctrl');
-    if (e.altKey) parts.push('alt');
-    if (e.shiftKey) parts.push('shift');
-
-    // Map key name to Ghostty format
-    let keyName = e.key.toLowerCase();
-
-    // Common key mappings
-    const keyMap = {
-        'arrowup': 'arrow_up',
-        'arrowdown': 'arrow_down',
-        'arrowleft': 'arrow_left',
-        'arrowright': 'arrow_right',
-        'enter': 'enter',
-        'return': 'enter',
-        'backspace': 'backspace',
-        'delete': 'delete',
-        'tab': 'tab',
-        'escape': 'escape',
-        'space': 'space',
-        ' ': 'space',
-        'pageup': 'page_up',
-        'pagedown': 'page_down',
-        'home': 'home',
-        'end': 'end',
-        '[': 'bracket_left',
-        ']': 'bracket_right',
-        ',': 'comma',
-        '.': 'period',
-        '/': 'slash',
-        '\\': 'backslash',
-        ';': 'semicolon',
-        "'": 'apostrophe',
-        '`': 'grave_accent',
-        '-': 'minus',
-        '=': 'equal',
-    };
-
-    if (keyMap[keyName]) {
-        keyName = keyMap[keyName];
-    }
-
-    // Function keys
-    if (e.key.match(/^F\d+$/i)) {
-        keyName = e.key.toLowerCase();
-    }
-
-    parts.push(keyName);
-
-    const input = document.getElementById('keybind-trigger');
-    input.value = parts.join('+');
-
-    stopCapture();
+    // The server knows Ghostty's key names and this platform's modifier names.
+    const event = new URLSearchParams({
+        key: e.key,
+        code: e.code,
+        ctrl: e.ctrlKey,
+        alt: e.altKey,
+        shift: e.shiftKey,
+        meta: e.metaKey,
+    });
+    fetch('/api/keybinds/normalize', { method: 'POST', body: event })
+        .then(r => r.ok ? r.json() : r.text().then(msg => Promise.reject(msg)))
+        .then(result => {
+            // A modifier on its own: keep listening for the rest of the shortcut.
+            if (!result.trigger) return;
+            document.getElementById('keybind-trigger').value = result.trigger;
+            stopCapture();
+        })
+        .catch(msg => {
+            const input = document.getElementById('keybind-trigger');
+            input.placeholder = (typeof msg === 'string' && msg) ? msg : 'Could not read that key, try another';
+        });
 });